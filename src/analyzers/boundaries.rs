@@ -0,0 +1,233 @@
+use super::{Analyzer, AnalysisResult};
+use crate::ast::{NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority};
+use crate::config::{relative_issue_path, LayerConfig};
+use crate::output::i18n::Lang;
+use std::sync::Arc;
+use anyhow::Result;
+use globset::{GlobBuilder, GlobMatcher};
+use std::collections::{BTreeMap, HashMap};
+
+// (import 元のレイヤー, import 先のレイヤー) ごとの違反数
+type LayerPairs = BTreeMap<(String, String), usize>;
+
+pub struct BoundariesAnalyzer {
+    layers: Vec<LayerConfig>,
+    // 設定の誤りを伝えるメッセージの言語
+    lang: Lang,
+}
+
+// ファイルが属するレイヤーと、pattern に一致したディレクトリ（features/* ならフィーチャーごとのディレクトリ）
+struct Zone<'a> {
+    layer: &'a LayerConfig,
+    directory: String,
+}
+
+impl BoundariesAnalyzer {
+    pub fn new() -> Self {
+        Self { layers: Vec::new(), lang: Lang::En }
+    }
+
+    pub fn with_layers(mut self, layers: Vec<LayerConfig>) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    pub fn with_lang(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    fn matchers(&self) -> Result<Vec<GlobMatcher>> {
+        let names: Vec<&str> = self.layers.iter().map(|layer| layer.name.as_str()).collect();
+        for layer in &self.layers {
+            if let Some(unknown) = layer.allow.iter().find(|name| !names.contains(&name.as_str())) {
+                return Err(anyhow::anyhow!("{}", self.lang.trf("error.boundaries_unknown_layer", &[&layer.name, unknown])));
+            }
+        }
+
+        // * が / をまたがないようにして、features/* がフィーチャーのディレクトリだけに一致するようにする
+        self.layers.iter()
+            .map(|layer| {
+                let glob = GlobBuilder::new(layer.pattern.trim_end_matches('/')).literal_separator(true).build()
+                    .map_err(|e| anyhow::anyhow!("{}", self.lang.trf("error.boundaries_pattern", &[&layer.name, &e])))?;
+                Ok(glob.compile_matcher())
+            })
+            .collect()
+    }
+
+    // 定義順に見て最初に一致したレイヤー。ディレクトリは浅いものから試す
+    fn zone<'a>(&'a self, matchers: &[GlobMatcher], relative_path: &str) -> Option<Zone<'a>> {
+        let segments: Vec<&str> = relative_path.split('/').collect();
+        self.layers.iter().zip(matchers).find_map(|(layer, matcher)| {
+            (1..segments.len())
+                .map(|depth| segments[..depth].join("/"))
+                .find(|directory| matcher.is_match(directory))
+                .map(|directory| Zone { layer, directory })
+        })
+    }
+
+    fn analyze_boundaries(&self, project: &NgProject) -> Result<(Vec<Issue>, LayerPairs)> {
+        let mut issues = Vec::new();
+        let mut pairs = LayerPairs::new();
+        let Some(graph) = &project.import_graph else {
+            return Ok((issues, pairs));
+        };
+        if self.layers.is_empty() {
+            return Ok((issues, pairs));
+        }
+        let matchers = self.matchers()?;
+
+        let files: HashMap<&str, &str> = graph.files.iter().map(|file| (file.id.as_str(), file.file_path.as_str())).collect();
+        for dependency in &graph.dependencies {
+            let (Some(from_path), Some(to_path)) = (files.get(dependency.from_file.as_str()), files.get(dependency.to_file.as_str())) else { continue };
+            let to_relative = relative_issue_path(to_path, &project.root_path);
+            let (Some(from), Some(to)) = (
+                self.zone(&matchers, &relative_issue_path(from_path, &project.root_path)),
+                self.zone(&matchers, &to_relative),
+            ) else { continue };
+
+            if from.directory == to.directory || from.layer.allow.contains(&to.layer.name) {
+                continue;
+            }
+            *pairs.entry((from.layer.name.clone(), to.layer.name.clone())).or_default() += 1;
+            issues.push(Issue {
+                severity: Severity::Error,
+                rule: "layer-boundary-violation".to_string(),
+                message: format!(
+                    "Layer '{}' ({}) must not import {} from layer '{}' ({}). Allowed layers: {}",
+                    from.layer.name,
+                    from.directory,
+                    to_relative,
+                    to.layer.name,
+                    to.directory,
+                    if from.layer.allow.is_empty() { "none".to_string() } else { from.layer.allow.join(", ") }
+                ),
+                file_path: from_path.to_string(),
+                line: dependency.line_number,
+                column: None,
+                fingerprint: None,
+            });
+        }
+        Ok((issues, pairs))
+    }
+
+    fn generate_boundary_recommendations(&self, pairs: &LayerPairs) -> Vec<Recommendation> {
+        if pairs.is_empty() {
+            return Vec::new();
+        }
+
+        vec![Recommendation {
+            category: "Architecture".to_string(),
+            title: "Fix Layer Boundary Violations".to_string(),
+            description: format!(
+                "Imports cross the configured layers: {}. Move the shared code into a layer both sides may depend on, or update boundaries.layers if the dependency is intended.",
+                pairs.iter().map(|((from, to), count)| format!("{} → {} ({})", from, to, count)).collect::<Vec<_>>().join(", ")
+            ),
+            priority: Priority::High,
+            file_path: None,
+        }]
+    }
+}
+
+impl Analyzer for BoundariesAnalyzer {
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
+        let (issues, pairs) = self.analyze_boundaries(project)?;
+        let recommendations = self.generate_boundary_recommendations(&pairs);
+
+        Ok(AnalysisResult {
+            project: project.clone(),
+            issues,
+            metrics: ProjectMetrics {
+                total_components: project.components.len() as u32,
+                total_services: project.services.len() as u32,
+                total_modules: project.modules.len() as u32,
+                lines_of_code: project.files.iter().map(|f| f.lines.logical).sum(),
+                test_coverage: project.test_coverage,
+                ..Default::default()
+            },
+            recommendations,
+            omitted_issues: vec![],
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "boundaries"
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports imports that cross the layers configured in boundaries.layers"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Dependency, FileInfo, FileType, ImportExportGraph, ImportType};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_analyze_boundaries() {
+        let layer = |name: &str, pattern: &str, allow: &[&str]| LayerConfig {
+            name: name.to_string(),
+            pattern: pattern.to_string(),
+            allow: allow.iter().map(|name| name.to_string()).collect(),
+        };
+        let analyzer = BoundariesAnalyzer::new().with_layers(vec![
+            layer("core", "src/app/core", &[]),
+            layer("shared", "src/app/shared", &["core"]),
+            layer("features", "src/app/features/*", &["core", "shared"]),
+        ]);
+
+        let files = [
+            "src/app/core/auth.service.ts",
+            "src/app/shared/button.component.ts",
+            "src/app/features/cart/cart.component.ts",
+            "src/app/features/cart/cart.store.ts",
+            "src/app/features/orders/orders.component.ts",
+            "src/main.ts",
+        ];
+        let edges = [
+            // 許可されている
+            (2, 0), (2, 1), (2, 3), (1, 0), (5, 2),
+            // feature → 別の feature、shared → feature、core → shared
+            (2, 4), (1, 3), (0, 1),
+        ];
+        let project = NgProject {
+            root_path: PathBuf::from("."),
+            import_graph: Some(ImportExportGraph {
+                files: files.iter().map(|path| FileInfo {
+                    id: path.to_string(),
+                    file_path: format!("./{}", path),
+                    relative_path: path.to_string(),
+                    file_type: FileType::TypeScript,
+                    exports: Vec::new(),
+                    imports: Vec::new(),
+                }).collect(),
+                dependencies: edges.iter().map(|(from, to)| Dependency {
+                    from_file: files[*from].to_string(),
+                    to_file: files[*to].to_string(),
+                    import_type: ImportType::Named,
+                    imported_symbols: Vec::new(),
+                    line_number: Some(1),
+                }).collect(),
+                exports: Vec::new(),
+                imports: Vec::new(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let (issues, pairs) = analyzer.analyze_boundaries(&project).unwrap();
+        assert_eq!(issues.len(), 3);
+        assert!(issues.iter().all(|issue| matches!(issue.severity, Severity::Error)));
+        assert_eq!(issues[0].file_path, "./src/app/features/cart/cart.component.ts");
+        assert_eq!(
+            issues[0].message,
+            "Layer 'features' (src/app/features/cart) must not import src/app/features/orders/orders.component.ts from layer 'features' (src/app/features/orders). Allowed layers: core, shared"
+        );
+        assert_eq!(pairs.len(), 3);
+
+        let invalid = BoundariesAnalyzer::new().with_layers(vec![layer("core", "src/app/core", &["shared"])]);
+        assert!(invalid.analyze_boundaries(&project).is_err());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::analyzers::dependency_graph::DependencyGraphAnalyzer;
+use crate::ast::{BudgetResult, ChangeDetectionStrategy, NgComponent, NgProject};
+use crate::config::BudgetsConfig;
+use crate::parsers::project::count_lines;
+use anyhow::Result;
+use std::path::Path;
+
+// 設定された予算だけを、設定ファイルのキーの順に評価する
+pub fn evaluate_budgets(project: &NgProject, budgets: &BudgetsConfig) -> Result<Vec<BudgetResult>> {
+    let mut results = Vec::new();
+    let components = &project.components;
+
+    if let Some(limit) = budgets.max_components_per_module {
+        let worst = project.modules.iter()
+            .map(|module| (module.name.as_str(), module.declarations.iter().filter(|name| components.iter().any(|c| c.name == **name)).count()))
+            .max_by(|a, b| a.1.cmp(&b.1).then_with(|| b.0.cmp(a.0)));
+        results.push(max_budget("max_components_per_module", limit as f64, worst.map(|(name, count)| (name.to_string(), count as f64))));
+    }
+
+    if let Some(limit) = budgets.max_average_complexity {
+        let average = if components.is_empty() {
+            0.0
+        } else {
+            components.iter().map(|c| c.complexity_score as f64).sum::<f64>() / components.len() as f64
+        };
+        results.push(BudgetResult { budget: "max_average_complexity".to_string(), limit, actual: average, passed: average <= limit, worst: None });
+    }
+
+    if let Some(limit) = budgets.max_template_lines {
+        let worst = components.iter()
+            .filter_map(|component| Some((component.name.clone(), template_lines(component)? as f64)))
+            .max_by(|a, b| a.1.total_cmp(&b.1).then_with(|| b.0.cmp(&a.0)));
+        results.push(max_budget("max_template_lines", limit as f64, worst));
+    }
+
+    if let Some(limit) = budgets.max_inputs {
+        let worst = components.iter()
+            .map(|component| (component.name.clone(), component.inputs.len() as f64))
+            .max_by(|a, b| a.1.total_cmp(&b.1).then_with(|| b.0.cmp(&a.0)));
+        results.push(max_budget("max_inputs", limit as f64, worst));
+    }
+
+    if let Some(limit) = budgets.min_onpush_percentage {
+        let on_push = components.iter().filter(|c| matches!(c.change_detection, ChangeDetectionStrategy::OnPush)).count();
+        // コンポーネントがなければ満たしているものとする
+        let percentage = if components.is_empty() { 100.0 } else { on_push as f64 * 100.0 / components.len() as f64 };
+        results.push(BudgetResult { budget: "min_onpush_percentage".to_string(), limit, actual: percentage, passed: percentage >= limit, worst: None });
+    }
+
+    // import グラフは budgets.max_cycles があれば必ず読み込まれる
+    if let (Some(limit), Some(graph)) = (budgets.max_cycles, &project.import_graph) {
+        let cycles = DependencyGraphAnalyzer::new()
+            .with_analyses(true, false, false, false)
+            .analyze_dependencies(graph)?
+            .circular_dependencies;
+        let worst = cycles.first().map(|cycle| cycle.cycle.join(" -> "));
+        let actual = cycles.len() as f64;
+        results.push(BudgetResult { budget: "max_cycles".to_string(), limit: limit as f64, actual, passed: actual <= limit as f64, worst });
+    }
+
+    Ok(results)
+}
+
+fn max_budget(budget: &str, limit: f64, worst: Option<(String, f64)>) -> BudgetResult {
+    let actual = worst.as_ref().map(|(_, value)| *value).unwrap_or(0.0);
+    BudgetResult {
+        budget: budget.to_string(),
+        limit,
+        actual,
+        passed: actual <= limit,
+        worst: worst.map(|(name, _)| name),
+    }
+}
+
+// インラインのテンプレートか、コンポーネントの .ts からの相対パスの templateUrl を読む
+fn template_lines(component: &NgComponent) -> Option<u32> {
+    let template = match (&component.template, &component.template_url) {
+        (Some(template), _) => template.clone(),
+        (None, Some(url)) => {
+            let path = Path::new(&component.file_path).parent().unwrap_or(Path::new("")).join(url);
+            std::fs::read_to_string(path).ok()?
+        }
+        (None, None) => return None,
+    };
+    Some(count_lines(&template).physical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::{ComponentBuilder, NgProjectBuilder};
+    use crate::ast::NgModule;
+
+    #[test]
+    fn test_evaluate_budgets() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("user-list.component.html"), "<ul>\n  <li *ngFor=\"let user of users\">{{ user.name }}</li>\n</ul>\n").unwrap();
+        let module = |name: &str, declarations: &[&str]| NgModule {
+            name: name.to_string(),
+            file_path: format!("{}.ts", name),
+            imports: vec![],
+            exports: vec![],
+            declarations: declarations.iter().map(|d| d.to_string()).collect(),
+            providers: vec![],
+            bootstrap: vec![],
+            for_root_imports: vec![],
+        };
+        let project = NgProjectBuilder::new(dir.path())
+            .component(ComponentBuilder::new("UserListComponent")
+                .file_path(&dir.path().join("user-list.component.ts").display().to_string())
+                .template_url("./user-list.component.html")
+                .inputs(&["users", "selected", "pageSize"])
+                .complexity(8)
+                .on_push())
+            .component(ComponentBuilder::new("UserCardComponent").template("<p>{{ user.name }}</p>").inputs(&["user"]).complexity(2))
+            .module(module("UsersModule", &["UserListComponent", "UserCardComponent", "UserPipe"]))
+            .module(module("SharedModule", &["UserCardComponent"]))
+            .build();
+
+        let budgets = BudgetsConfig {
+            max_components_per_module: Some(1),
+            max_average_complexity: Some(5.0),
+            max_template_lines: Some(3),
+            max_inputs: Some(2),
+            min_onpush_percentage: Some(50.0),
+            max_cycles: Some(0),
+        };
+        let results = evaluate_budgets(&project, &budgets).unwrap();
+        let found: Vec<(&str, f64, bool, Option<&str>)> = results.iter()
+            .map(|result| (result.budget.as_str(), result.actual, result.passed, result.worst.as_deref()))
+            .collect();
+        assert_eq!(found, vec![
+            ("max_components_per_module", 2.0, false, Some("UsersModule")),
+            ("max_average_complexity", 5.0, true, None),
+            ("max_template_lines", 3.0, true, Some("UserListComponent")),
+            ("max_inputs", 3.0, false, Some("UserListComponent")),
+            ("min_onpush_percentage", 50.0, true, None),
+        ]);
+    }
+}
@@ -0,0 +1,305 @@
+use super::dependency_graph::{lookup_module, module_path_index, normalize_file_path, resolve_module, static_dependencies};
+use super::module_graph::lazy_feature_dir;
+use crate::ast::{BundleChunk, BundleEstimate, ImportExportGraph, NgProject, SharedBundleFile};
+use crate::parsers::html::line_at;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+// 動的 import の辺は辿らず、静的な import だけを辿った範囲をそのチャンクの中身とみなす
+pub fn estimate_bundles(project: &NgProject, graph: &ImportExportGraph) -> BundleEstimate {
+    let module_paths = module_path_index(graph);
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for dependency in static_dependencies(graph) {
+        adjacency.entry(dependency.from_file.as_str()).or_default().push(dependency.to_file.as_str());
+    }
+    let files = project_files(project, graph);
+
+    let eager_entries = eager_entries(project, graph);
+    let eager = reachable(&eager_entries, &adjacency, &files);
+
+    let entry_paths = |ids: &[String]| ids.iter().filter_map(|id| files.get(id.as_str()).map(|(path, _)| *path)).collect::<Vec<_>>().join(", ");
+    let mut chunks = vec![chunk("eager", entry_paths(&eager_entries), &eager, &files)];
+
+    for route in &project.lazy_routes {
+        let Some(entry) = lookup_module(&module_paths, &route.target) else { continue };
+        let name = route.module.clone().unwrap_or_else(|| route.target.rsplit('/').next().unwrap_or(&route.target).to_string());
+        if chunks.iter().any(|chunk| chunk.name == name && chunk.route_file.as_deref() == Some(route.file_path.as_str())) {
+            continue;
+        }
+        let members: BTreeSet<&str> = reachable(std::slice::from_ref(&entry), &adjacency, &files).into_iter()
+            .filter(|id| !eager.contains(id))
+            .collect();
+        let mut route_chunk = chunk(&name, entry_paths(std::slice::from_ref(&entry)), &members, &files);
+        route_chunk.route_file = Some(route.file_path.clone());
+        route_chunk.line = route.line;
+        chunks.push(route_chunk);
+    }
+
+    // 初期バンドルに入らず、複数のルートに含まれるファイル
+    let mut owners: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for route_chunk in chunks.iter().skip(1) {
+        for file in &route_chunk.files {
+            owners.entry(file.as_str()).or_default().push(route_chunk.name.clone());
+        }
+    }
+    let bytes_by_path: HashMap<&str, u64> = files.values().copied().collect();
+    let mut shared: Vec<SharedBundleFile> = owners.into_iter()
+        .filter(|(_, chunks)| chunks.len() > 1)
+        .map(|(file_path, chunks)| SharedBundleFile {
+            file_path: file_path.to_string(),
+            bytes: bytes_by_path.get(file_path).copied().unwrap_or(0),
+            chunks,
+        })
+        .collect();
+    shared.sort_by(|a, b| (b.chunks.len() as u64 * b.bytes).cmp(&(a.chunks.len() as u64 * a.bytes)).then_with(|| a.file_path.cmp(&b.file_path)));
+
+    BundleEstimate { chunks, shared }
+}
+
+// 初期バンドルのファイルが、遅延読み込みされるルートのディレクトリにあるファイルを静的に import しているもの
+pub struct LazyBoundaryImport {
+    pub importer: String,
+    pub line: Option<u32>,
+    // import しているファイルの相対パス
+    pub target: String,
+    pub route: String,
+    pub route_file: String,
+    pub route_line: Option<u32>,
+    // その import のために初期バンドルへ入っているファイルの合計サイズ
+    pub bytes: u64,
+}
+
+// 機能のディレクトリはルートの対象ファイルのディレクトリ。ルート定義と同じディレクトリを指すものは除く
+pub fn lazy_boundary_imports(project: &NgProject, graph: &ImportExportGraph) -> Vec<LazyBoundaryImport> {
+    let module_paths = module_path_index(graph);
+    let files = project_files(project, graph);
+    let paths: HashMap<&str, String> = graph.files.iter().map(|file| (file.id.as_str(), normalize_file_path(&file.file_path))).collect();
+
+    let mut features: Vec<(String, String, &str, Option<u32>)> = Vec::new();
+    for route in &project.lazy_routes {
+        let Some(dir) = lazy_feature_dir(route) else { continue };
+        if lookup_module(&module_paths, &route.target).is_none() || features.iter().any(|(existing, ..)| existing == dir) {
+            continue;
+        }
+        let name = route.module.clone().unwrap_or_else(|| route.target.rsplit('/').next().unwrap_or(&route.target).to_string());
+        features.push((format!("{}/", dir), name, route.file_path.as_str(), route.line));
+    }
+    // 入れ子のルートは、いちばん深い機能のものとして扱う
+    let feature_of = |id: &str| -> Option<usize> {
+        let path = paths.get(id)?;
+        features.iter().enumerate()
+            .filter(|(_, (dir, ..))| path.starts_with(dir.as_str()))
+            .max_by_key(|(_, (dir, ..))| dir.len())
+            .map(|(index, _)| index)
+    };
+
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for dependency in static_dependencies(graph) {
+        adjacency.entry(dependency.from_file.as_str()).or_default().push(dependency.to_file.as_str());
+    }
+    let eager_entries = eager_entries(project, graph);
+    let eager = reachable(&eager_entries, &adjacency, &files);
+
+    let mut violations: Vec<(&str, &str, usize)> = Vec::new();
+    for dependency in static_dependencies(graph) {
+        let (from, to) = (dependency.from_file.as_str(), dependency.to_file.as_str());
+        if !eager.contains(from) || !files.contains_key(to) {
+            continue;
+        }
+        let Some(feature) = feature_of(to) else { continue };
+        if feature_of(from) != Some(feature) {
+            violations.push((from, to, feature));
+        }
+    }
+    if violations.is_empty() {
+        return Vec::new();
+    }
+
+    // 境界を越える import がなかった場合の初期バンドル
+    let crossing: HashSet<(&str, &str)> = violations.iter().map(|(from, to, _)| (*from, *to)).collect();
+    let mut bounded: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (from, targets) in &adjacency {
+        bounded.insert(from, targets.iter().copied().filter(|to| !crossing.contains(&(*from, *to))).collect());
+    }
+    let bounded_eager = reachable(&eager_entries, &bounded, &files);
+
+    violations.into_iter()
+        .map(|(from, to, feature)| {
+            let (_, name, route_file, route_line) = &features[feature];
+            let bytes = reachable(&[to.to_string()], &adjacency, &files).iter()
+                .filter(|id| !bounded_eager.contains(*id))
+                .filter_map(|id| files.get(id).map(|(_, bytes)| *bytes))
+                .sum();
+            let importer = graph.files.iter().find(|file| file.id == from).map(|file| file.file_path.clone()).unwrap_or_default();
+            LazyBoundaryImport {
+                line: import_line(project, graph, &module_paths, &importer, to),
+                importer,
+                target: files[to].0.to_string(),
+                route: name.clone(),
+                route_file: route_file.to_string(),
+                route_line: *route_line,
+                bytes,
+            }
+        })
+        .collect()
+}
+
+// to を参照している import 文の行
+fn import_line(project: &NgProject, graph: &ImportExportGraph, module_paths: &HashMap<String, String>, importer: &str, to: &str) -> Option<u32> {
+    let source_module = graph.imports.iter()
+        .filter(|import| import.file_path == importer)
+        .find(|import| resolve_module(module_paths, &import.file_path, &import.source_module).as_deref() == Some(to))?
+        .source_module
+        .clone();
+    let content = project.sources.read(Path::new(importer)).ok()?.content;
+    let offset = content.find(&format!("'{}'", source_module)).or_else(|| content.find(&format!("\"{}\"", source_module)))?;
+    Some(line_at(&content, offset))
+}
+
+// プロジェクト内のファイルの相対パスとサイズ。解析で読んだソースを使う
+fn project_files<'a>(project: &NgProject, graph: &'a ImportExportGraph) -> HashMap<&'a str, (&'a str, u64)> {
+    graph.files.iter()
+        .filter(|file| !file.id.starts_with("external:"))
+        .map(|file| {
+            let bytes = project.sources.read(Path::new(&file.file_path)).map(|source| source.content.len() as u64).unwrap_or(0);
+            (file.id.as_str(), (file.relative_path.as_str(), bytes))
+        })
+        .collect()
+}
+
+// main.ts があればそこから、なければ bootstrap を持つモジュールから辿る
+fn eager_entries(project: &NgProject, graph: &ImportExportGraph) -> Vec<String> {
+    let mains: Vec<String> = graph.files.iter()
+        .filter(|file| Path::new(&file.file_path).file_name().is_some_and(|name| name == "main.ts"))
+        .map(|file| file.id.clone())
+        .collect();
+    if !mains.is_empty() {
+        return mains;
+    }
+
+    let module_paths = module_path_index(graph);
+    project.modules.iter()
+        .filter(|module| !module.bootstrap.is_empty())
+        .filter_map(|module| lookup_module(&module_paths, module.file_path.strip_suffix(".ts").unwrap_or(&module.file_path)))
+        .collect()
+}
+
+fn reachable<'a>(entries: &[String], adjacency: &HashMap<&str, Vec<&'a str>>, files: &HashMap<&'a str, (&str, u64)>) -> BTreeSet<&'a str> {
+    let mut visited: BTreeSet<&'a str> = BTreeSet::new();
+    let mut stack: Vec<&'a str> = entries.iter()
+        .filter_map(|entry| files.get_key_value(entry.as_str()).map(|(id, _)| *id))
+        .collect();
+    while let Some(id) = stack.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+        for next in adjacency.get(id).into_iter().flatten() {
+            if files.contains_key(next) && !visited.contains(next) {
+                stack.push(next);
+            }
+        }
+    }
+    visited
+}
+
+fn chunk(name: &str, entry: String, members: &BTreeSet<&str>, files: &HashMap<&str, (&str, u64)>) -> BundleChunk {
+    let mut paths: Vec<String> = members.iter().filter_map(|id| files.get(id).map(|(path, _)| path.to_string())).collect();
+    paths.sort();
+    BundleChunk {
+        name: name.to_string(),
+        entry,
+        route_file: None,
+        line: None,
+        files: paths,
+        bytes: members.iter().filter_map(|id| files.get(id).map(|(_, bytes)| *bytes)).sum(),
+    }
+}
+
+impl BundleEstimate {
+    pub fn eager(&self) -> Option<&BundleChunk> {
+        self.chunks.iter().find(|chunk| chunk.route_file.is_none())
+    }
+
+    pub fn routes(&self) -> impl Iterator<Item = &BundleChunk> {
+        self.chunks.iter().filter(|chunk| chunk.route_file.is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::dependency_graph::DependencyGraphAnalyzer;
+    use crate::parsers::ProjectParser;
+
+    #[tokio::test]
+    async fn test_estimate_bundles() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        let app = src.join("app");
+        std::fs::create_dir_all(app.join("admin")).unwrap();
+        std::fs::create_dir_all(app.join("orders")).unwrap();
+        std::fs::create_dir_all(app.join("charts")).unwrap();
+        std::fs::write(src.join("main.ts"), "import { AppModule } from './app/app.module';\n").unwrap();
+        std::fs::write(
+            app.join("app.module.ts"),
+            "import { routes } from './app.routes';\n@NgModule({ bootstrap: [AppComponent] })\nexport class AppModule {}\n",
+        ).unwrap();
+        std::fs::write(
+            app.join("app.routes.ts"),
+            "import { Logger } from './logger';\nexport const routes = [\n  { path: 'admin', loadChildren: () => import('./admin/admin.module').then(m => m.AdminModule) },\n  { path: 'orders', loadComponent: () => import('./orders/orders.component').then(m => m.OrdersComponent) },\n];\n",
+        ).unwrap();
+        std::fs::write(app.join("logger.ts"), "export class Logger {}\n").unwrap();
+        std::fs::write(app.join("admin/admin.module.ts"), "import { Chart } from '../charts/chart';\nimport { Logger } from '../logger';\nexport class AdminModule {}\n").unwrap();
+        std::fs::write(app.join("orders/orders.component.ts"), "import { Chart } from '../charts/chart';\nexport class OrdersComponent {}\n").unwrap();
+        std::fs::write(app.join("charts/chart.ts"), "export class Chart { render() { return 'a large charting helper'; } }\n").unwrap();
+
+        let project = ProjectParser::new().parse_project(&dir.path().to_path_buf()).await.unwrap();
+        let graph = DependencyGraphAnalyzer::new().analyze_project(&dir.path().to_path_buf()).await.unwrap();
+        let estimate = estimate_bundles(&project, &graph);
+
+        let eager = estimate.eager().unwrap();
+        assert_eq!(eager.files.len(), 4);
+        let routes: Vec<&BundleChunk> = estimate.routes().collect();
+        assert_eq!(routes.iter().map(|chunk| chunk.name.as_str()).collect::<Vec<_>>(), vec!["AdminModule", "OrdersComponent"]);
+        assert_eq!(routes[0].files.len(), 2);
+        assert_eq!(routes[0].line, Some(3));
+        assert!(routes[1].bytes > 0);
+        assert_eq!(estimate.shared.len(), 1);
+        assert!(estimate.shared[0].file_path.ends_with("charts/chart.ts"));
+        assert_eq!(estimate.shared[0].chunks, vec!["AdminModule", "OrdersComponent"]);
+    }
+
+    #[tokio::test]
+    async fn test_lazy_boundary_imports() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("src/app");
+        std::fs::create_dir_all(app.join("admin/reports")).unwrap();
+        std::fs::write(dir.path().join("src/main.ts"), "import { AppModule } from './app/app.module';\n").unwrap();
+        std::fs::write(
+            app.join("app.module.ts"),
+            "import { routes } from './app.routes';\nimport { AdminStore } from './admin/admin.store';\nexport class AppModule {}\n",
+        ).unwrap();
+        std::fs::write(
+            app.join("app.routes.ts"),
+            "export const routes = [\n  { path: 'admin', loadChildren: () => import('./admin/admin.module').then(m => m.AdminModule) },\n];\n",
+        ).unwrap();
+        std::fs::write(app.join("admin/admin.module.ts"), "import { AdminStore } from './admin.store';\nexport class AdminModule {}\n").unwrap();
+        std::fs::write(app.join("admin/admin.store.ts"), "import { ReportApi } from './reports/report.api';\nexport class AdminStore {}\n").unwrap();
+        std::fs::write(app.join("admin/reports/report.api.ts"), "export class ReportApi { load() { return 'reports'; } }\n").unwrap();
+
+        let project = ProjectParser::new().parse_project(&dir.path().to_path_buf()).await.unwrap();
+        let graph = DependencyGraphAnalyzer::new().analyze_project(&dir.path().to_path_buf()).await.unwrap();
+        let violations = lazy_boundary_imports(&project, &graph);
+
+        assert_eq!(violations.len(), 1);
+        let violation = &violations[0];
+        assert!(violation.importer.ends_with("app.module.ts"));
+        assert_eq!(violation.line, Some(2));
+        assert!(violation.target.ends_with("admin/admin.store.ts"));
+        assert_eq!(violation.route, "AdminModule");
+        assert_eq!(violation.route_line, Some(2));
+        let store = std::fs::metadata(app.join("admin/admin.store.ts")).unwrap().len();
+        let api = std::fs::metadata(app.join("admin/reports/report.api.ts")).unwrap().len();
+        assert_eq!(violation.bytes, store + api);
+    }
+}
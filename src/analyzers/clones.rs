@@ -0,0 +1,332 @@
+use super::{Analyzer, AnalysisResult};
+use crate::ast::{NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority};
+use crate::config::{build_glob_set, relative_issue_path};
+use crate::parsers::entry_points::ENTRY_FILE_GLOBS;
+use crate::parsers::tokens::{tokenize, Token};
+use std::sync::Arc;
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+
+// winnowing の窓の大きさ。min_tokens 以上一致する区間は必ずどこかの窓の最小値を共有する
+const WINNOW_WINDOW: usize = 8;
+// 同じ指紋がこれより多く現れる場合は定型的なコードとみなして比較しない
+const MAX_BUCKET_SIZE: usize = 50;
+
+pub struct ClonesAnalyzer {
+    min_tokens: usize,
+}
+
+// トークン列の [start, end) の範囲
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Occurrence {
+    file: usize,
+    start: usize,
+    end: usize,
+}
+
+impl Occurrence {
+    fn contains(&self, other: &Occurrence) -> bool {
+        self.file == other.file && self.start <= other.start && other.end <= self.end
+    }
+}
+
+// 同じトークン列が現れる箇所
+#[derive(Debug)]
+struct CloneGroup {
+    tokens: usize,
+    occurrences: Vec<Occurrence>,
+}
+
+impl ClonesAnalyzer {
+    pub fn new() -> Self {
+        Self { min_tokens: 100 }
+    }
+
+    pub fn with_min_tokens(mut self, min_tokens: usize) -> Self {
+        self.min_tokens = min_tokens.max(1);
+        self
+    }
+
+    // テスト・設定ファイルと minify 済みのファイルを除いた .ts のパスとトークン列。解析できないファイルは飛ばす
+    fn tokenize_files(&self, project: &NgProject) -> Result<Vec<(String, Vec<Token>)>> {
+        let tool_files = build_glob_set(&ENTRY_FILE_GLOBS.map(String::from))?;
+        Ok(project.files.iter()
+            .filter(|file| !file.minified && !tool_files.is_match(relative_issue_path(&file.file_path, &project.root_path)))
+            .filter_map(|file| {
+                let source = project.sources.read(Path::new(&file.file_path)).ok()?;
+                Some((file.file_path.clone(), tokenize(&source.content).ok()?))
+            })
+            .collect())
+    }
+
+    fn find_clones(&self, files: &[Vec<Token>]) -> Vec<CloneGroup> {
+        let window = WINNOW_WINDOW.min(self.min_tokens);
+        let k = self.min_tokens - window + 1;
+
+        // 指紋のハッシュ → (ファイル, 開始位置)
+        let mut fingerprints: HashMap<u64, Vec<(usize, usize)>> = HashMap::new();
+        for (file, tokens) in files.iter().enumerate() {
+            let hashes = kgram_hashes(tokens, k);
+            let mut last = None;
+            for start in 0..hashes.len().saturating_sub(window - 1) {
+                // 窓の中の最小値（同じ値なら右端）を選ぶ
+                let position = (start..start + window).rev().min_by_key(|i| hashes[*i]).unwrap();
+                if last != Some(position) {
+                    fingerprints.entry(hashes[position]).or_default().push((file, position));
+                    last = Some(position);
+                }
+            }
+        }
+
+        let mut pairs: HashSet<(Occurrence, Occurrence)> = HashSet::new();
+        for bucket in fingerprints.values().filter(|bucket| bucket.len() > 1 && bucket.len() <= MAX_BUCKET_SIZE) {
+            for (i, a) in bucket.iter().enumerate() {
+                for b in &bucket[i + 1..] {
+                    if let Some(pair) = extend_match(files, *a, *b, k) {
+                        if pair.0.end - pair.0.start >= self.min_tokens {
+                            pairs.insert(pair);
+                        }
+                    }
+                }
+            }
+        }
+
+        // 同じ長さで同じトークン列を持つ箇所を 1 つのグループにする
+        let mut groups: BTreeMap<(usize, u64), Vec<Occurrence>> = BTreeMap::new();
+        for (a, b) in pairs {
+            let key = (a.end - a.start, sequence_hash(&files[a.file][a.start..a.end]));
+            let occurrences = groups.entry(key).or_default();
+            for occurrence in [a, b] {
+                if !occurrences.contains(&occurrence) {
+                    occurrences.push(occurrence);
+                }
+            }
+        }
+        let mut groups: Vec<CloneGroup> = groups.into_iter()
+            .map(|((tokens, _), mut occurrences)| {
+                occurrences.sort();
+                CloneGroup { tokens, occurrences }
+            })
+            .collect();
+        groups.sort_by(|a, b| b.tokens.cmp(&a.tokens).then_with(|| a.occurrences.cmp(&b.occurrences)));
+
+        // より長いクローンの中に収まるだけのグループは報告しない
+        let mut kept: Vec<CloneGroup> = Vec::new();
+        for group in groups {
+            let nested = group.occurrences.iter().all(|occurrence| {
+                kept.iter().any(|longer| longer.occurrences.iter().any(|outer| outer.contains(occurrence)))
+            });
+            if !nested {
+                kept.push(group);
+            }
+        }
+        kept
+    }
+
+    // 2 つ目以降の出現が占める行（ファイル, 行）
+    fn duplicated_lines(&self, files: &[Vec<Token>], groups: &[CloneGroup]) -> usize {
+        let mut lines: HashSet<(usize, u32)> = HashSet::new();
+        for group in groups {
+            for occurrence in group.occurrences.iter().skip(1) {
+                let tokens = &files[occurrence.file];
+                for line in tokens[occurrence.start].line..=tokens[occurrence.end - 1].line {
+                    lines.insert((occurrence.file, line));
+                }
+            }
+        }
+        lines.len()
+    }
+
+    fn analyze_clones(&self, project: &NgProject, paths: &[String], files: &[Vec<Token>], groups: &[CloneGroup]) -> Vec<Issue> {
+        groups.iter()
+            .map(|group| {
+                let ranges: Vec<(usize, u32, u32)> = group.occurrences.iter()
+                    .map(|occurrence| {
+                        let tokens = &files[occurrence.file];
+                        (occurrence.file, tokens[occurrence.start].line, tokens[occurrence.end - 1].line)
+                    })
+                    .collect();
+                let (first_file, first_line, last_line) = ranges[0];
+                Issue {
+                    severity: Severity::Warning,
+                    rule: "code-clone".to_string(),
+                    message: format!(
+                        "Duplicated block of {} tokens ({} lines) appears in {} places: {}. Extract it into a shared function, component or service.",
+                        group.tokens,
+                        last_line - first_line + 1,
+                        ranges.len(),
+                        ranges.iter()
+                            .map(|(file, start, end)| format!("{}:{}-{}", relative_issue_path(&paths[*file], &project.root_path), start, end))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    file_path: paths[first_file].clone(),
+                    line: Some(first_line),
+                    column: None,
+                    fingerprint: None,
+                }
+            })
+            .collect()
+    }
+
+    fn generate_clone_recommendations(&self, groups: &[CloneGroup], duplicated_lines: usize, percentage: f64) -> Vec<Recommendation> {
+        if groups.is_empty() {
+            return Vec::new();
+        }
+
+        vec![Recommendation {
+            category: "Maintainability".to_string(),
+            title: "Reduce Copy-Pasted Code".to_string(),
+            description: format!(
+                "{:.1}% of the lines ({} lines in {} clone group(s)) repeat code found elsewhere. Extract the shared logic so changes only have to be made once.",
+                percentage,
+                duplicated_lines,
+                groups.len()
+            ),
+            priority: if percentage >= 5.0 { Priority::Medium } else { Priority::Low },
+            file_path: None,
+        }]
+    }
+}
+
+// 長さ k の連続するトークン列ごとのローリングハッシュ
+fn kgram_hashes(tokens: &[Token], k: usize) -> Vec<u64> {
+    const BASE: u64 = 1_000_003;
+    if tokens.len() < k {
+        return Vec::new();
+    }
+    let power = (1..k).fold(1u64, |power, _| power.wrapping_mul(BASE));
+    let mut hash = tokens[..k].iter().fold(0u64, |hash, token| hash.wrapping_mul(BASE).wrapping_add(token.hash));
+    let mut hashes = vec![hash];
+    for i in k..tokens.len() {
+        hash = hash.wrapping_sub(tokens[i - k].hash.wrapping_mul(power)).wrapping_mul(BASE).wrapping_add(tokens[i].hash);
+        hashes.push(hash);
+    }
+    hashes
+}
+
+fn sequence_hash(tokens: &[Token]) -> u64 {
+    tokens.iter().fold(0xcbf29ce484222325, |hash: u64, token| (hash ^ token.hash).wrapping_mul(0x100000001b3))
+}
+
+// 指紋が一致した 2 箇所を実際に比べ、前後に一致する限り広げる。同じファイル内では重ならない範囲までにする
+fn extend_match(files: &[Vec<Token>], a: (usize, usize), b: (usize, usize), k: usize) -> Option<(Occurrence, Occurrence)> {
+    let (a, b) = if a <= b { (a, b) } else { (b, a) };
+    let (left, right) = (&files[a.0], &files[b.0]);
+    let same = |i: usize, j: usize| left[i].hash == right[j].hash;
+    if (0..k).any(|offset| !same(a.1 + offset, b.1 + offset)) {
+        return None;
+    }
+
+    let mut back = 0;
+    while back < a.1 && back < b.1 && same(a.1 - back - 1, b.1 - back - 1) {
+        back += 1;
+    }
+    let mut length = k;
+    while a.1 + length < left.len() && b.1 + length < right.len() && same(a.1 + length, b.1 + length) {
+        length += 1;
+    }
+    let (mut start_a, start_b) = (a.1 - back, b.1 - back);
+    let mut length = length + back;
+    if a.0 == b.0 && start_a + length > start_b {
+        // 自分自身と重なる繰り返しは、重ならない部分だけを比べる
+        length = start_b - start_a;
+        start_a = start_b - length;
+        if length < k {
+            return None;
+        }
+    }
+
+    Some((
+        Occurrence { file: a.0, start: start_a, end: start_a + length },
+        Occurrence { file: b.0, start: start_b, end: start_b + length },
+    ))
+}
+
+impl Analyzer for ClonesAnalyzer {
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
+        let (paths, files): (Vec<String>, Vec<Vec<Token>>) = self.tokenize_files(project)?.into_iter().unzip();
+        let groups = self.find_clones(&files);
+        let duplicated_lines = self.duplicated_lines(&files, &groups);
+        let total_lines: u32 = project.files.iter()
+            .filter(|file| paths.contains(&file.file_path))
+            .map(|file| file.lines.physical)
+            .sum();
+        let percentage = if total_lines > 0 {
+            duplicated_lines as f64 / total_lines as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        let issues = self.analyze_clones(project, &paths, &files, &groups);
+        let recommendations = self.generate_clone_recommendations(&groups, duplicated_lines, percentage);
+
+        Ok(AnalysisResult {
+            project: project.clone(),
+            issues,
+            metrics: ProjectMetrics {
+                total_components: project.components.len() as u32,
+                total_services: project.services.len() as u32,
+                total_modules: project.modules.len() as u32,
+                lines_of_code: project.files.iter().map(|f| f.lines.logical).sum(),
+                test_coverage: project.test_coverage,
+                duplication_percentage: Some(percentage),
+                ..Default::default()
+            },
+            recommendations,
+            omitted_issues: vec![],
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "clones"
+    }
+
+    fn description(&self) -> &'static str {
+        "Finds duplicated blocks of code (copy-paste) across the project by comparing token sequences"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHARED: &str = r#"
+export function summarize(orders: Order[]): Summary {
+  const total = orders.reduce((sum, order) => sum + order.price * order.quantity, 0);
+  const count = orders.filter(order => order.status === 'paid').length;
+  return { total, count, average: count > 0 ? total / count : 0 };
+}
+"#;
+
+    #[test]
+    fn test_find_clones() {
+        let files = vec![
+            tokenize(&format!("import {{ Order }} from './order';\n{}", SHARED)).unwrap(),
+            tokenize(&format!("export const VERSION = 1;\n{}\nexport const OTHER = 2;\n", SHARED)).unwrap(),
+            tokenize("export function unrelated(value: number) {\n  return value * 2;\n}\n").unwrap(),
+        ];
+        let analyzer = ClonesAnalyzer::new().with_min_tokens(40);
+        let groups = analyzer.find_clones(&files);
+
+        assert_eq!(groups.len(), 1);
+        let occurrences: Vec<(usize, u32, u32)> = groups[0].occurrences.iter()
+            .map(|o| (o.file, files[o.file][o.start].line, files[o.file][o.end - 1].line))
+            .collect();
+        assert_eq!(occurrences, vec![(0, 3, 7), (1, 3, 7)]);
+        assert_eq!(analyzer.duplicated_lines(&files, &groups), 5);
+
+        assert!(ClonesAnalyzer::new().with_min_tokens(500).find_clones(&files).is_empty());
+    }
+
+    #[test]
+    fn test_repeated_block_in_one_file() {
+        let file = tokenize(&format!("{}\n{}", SHARED, SHARED.replace("summarize", "summarizeAgain"))).unwrap();
+        let groups = ClonesAnalyzer::new().with_min_tokens(40).find_clones(&[file]);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].occurrences.len(), 2);
+        assert!(groups[0].occurrences[0].end <= groups[0].occurrences[1].start);
+    }
+}
@@ -1,77 +1,211 @@
 use super::{Analyzer, AnalysisResult};
-use crate::ast::{NgProject, NgComponent, Issue, Severity, ChangeDetectionStrategy, ProjectMetrics, Recommendation, Priority};
-use async_trait::async_trait;
+use super::component_tree::{build_component_tree, render_chains, DEEPEST_RENDER_CHAINS};
+use super::inheritance::{ancestors, class_index, leaf_classes};
+use crate::ast::{NgProject, NgComponent, ElementBindings, Issue, Severity, ChangeDetectionStrategy, ProjectMetrics, Recommendation, Priority, AdoptionMetrics, RenderDepth, ApiSurface, ApiSurfaceBucket};
+use crate::config::{build_glob_set, relative_issue_path};
+use crate::parsers::entry_points::is_conventional_resource;
+use globset::GlobSet;
+use crate::parsers::typescript::resolve_relative_path;
+use std::sync::Arc;
 use anyhow::Result;
 use rayon::prelude::*;
+use std::collections::HashSet;
+use std::path::Path;
 
 pub struct ComponentAnalyzer {
     max_complexity: u32,
-    #[allow(dead_code)]
     max_depth: u32,
+    max_render_depth: u32,
+    max_inheritance_depth: u32,
+    max_inline_lines: u32,
     max_inputs: usize,
     max_outputs: usize,
+    max_api_size: usize,
+    allowed_event_outputs: Vec<String>,
+    max_file_lines: u32,
+    max_selection_branches: u32,
+    min_test_coverage: f64,
+    coverage_min_complexity: u32,
+    adoption_gates: Vec<(String, f64)>,
+    ignored_unused_inputs: Vec<String>,
+    ignored_unused_outputs: Vec<String>,
 }
 
+// adoption-threshold のオプション名、表示名、対応するメトリクス
+type AdoptionGate = (&'static str, &'static str, fn(&AdoptionMetrics) -> Option<f64>);
+
+pub const ADOPTION_GATES: [AdoptionGate; 4] = [
+    ("min_standalone_percentage", "Standalone components", |a| a.standalone_percentage),
+    ("min_signal_inputs_percentage", "Signal inputs", |a| a.signal_inputs_percentage),
+    ("min_onpush_percentage", "OnPush components", |a| a.onpush_percentage),
+    ("min_typed_forms_percentage", "Typed forms", |a| a.typed_forms_percentage),
+];
+
+const NATIVE_DOM_EVENTS: [&str; 48] = [
+    "click", "dblclick", "contextmenu", "auxclick",
+    "mousedown", "mouseup", "mousemove", "mouseover", "mouseout", "mouseenter", "mouseleave", "wheel",
+    "pointerdown", "pointerup", "pointermove", "pointerover", "pointerout", "pointerenter", "pointerleave", "pointercancel",
+    "touchstart", "touchend", "touchmove", "touchcancel",
+    "keydown", "keyup", "keypress",
+    "focus", "blur", "focusin", "focusout",
+    "change", "input", "submit", "reset", "select", "invalid",
+    "drag", "dragstart", "dragend", "dragover", "dragenter", "dragleave", "drop",
+    "scroll", "load", "error", "resize",
+];
+
 impl ComponentAnalyzer {
     pub fn new() -> Self {
         Self {
             max_complexity: 10,
             max_depth: 5,
+            max_render_depth: 8,
+            max_inheritance_depth: 2,
+            max_inline_lines: 50,
             max_inputs: 10,
             max_outputs: 10,
+            max_api_size: 20,
+            allowed_event_outputs: Vec::new(),
+            max_file_lines: 400,
+            max_selection_branches: 3,
+            min_test_coverage: 50.0,
+            coverage_min_complexity: 5,
+            adoption_gates: Vec::new(),
+            ignored_unused_inputs: Vec::new(),
+            ignored_unused_outputs: Vec::new(),
         }
     }
 
+    pub fn with_allowed_event_outputs(mut self, names: Vec<String>) -> Self {
+        self.allowed_event_outputs = names;
+        self
+    }
+
+    pub fn with_max_file_lines(mut self, max_file_lines: u32) -> Self {
+        self.max_file_lines = max_file_lines;
+        self
+    }
+
+    pub fn with_max_selection_branches(mut self, max_branches: u32) -> Self {
+        self.max_selection_branches = max_branches;
+        self
+    }
+
+    pub fn with_coverage_thresholds(mut self, min_coverage: f64, min_complexity: u32) -> Self {
+        self.min_test_coverage = min_coverage;
+        self.coverage_min_complexity = min_complexity;
+        self
+    }
+
+    pub fn with_adoption_gates(mut self, gates: Vec<(String, f64)>) -> Self {
+        self.adoption_gates = gates;
+        self
+    }
+
+    pub fn with_component_tree_limits(mut self, max_depth: u32, max_inline_lines: u32) -> Self {
+        self.max_depth = max_depth;
+        self.max_inline_lines = max_inline_lines;
+        self
+    }
+
+    pub fn with_max_api_size(mut self, max_api_size: usize) -> Self {
+        self.max_api_size = max_api_size;
+        self
+    }
+
+    pub fn with_max_render_depth(mut self, max_render_depth: u32) -> Self {
+        self.max_render_depth = max_render_depth;
+        self
+    }
+
+    pub fn with_unused_binding_ignores(mut self, inputs: Vec<String>, outputs: Vec<String>) -> Self {
+        self.ignored_unused_inputs = inputs;
+        self.ignored_unused_outputs = outputs;
+        self
+    }
+
+    pub fn with_max_inheritance_depth(mut self, max_inheritance_depth: u32) -> Self {
+        self.max_inheritance_depth = max_inheritance_depth;
+        self
+    }
+
     #[allow(dead_code)]
     pub fn with_config(max_complexity: u32, max_depth: u32, max_inputs: usize, max_outputs: usize) -> Self {
         Self {
             max_complexity,
             max_depth,
+            max_render_depth: 8,
+            max_inheritance_depth: 2,
+            max_inline_lines: 50,
             max_inputs,
             max_outputs,
+            max_api_size: 20,
+            allowed_event_outputs: Vec::new(),
+            max_file_lines: 400,
+            max_selection_branches: 3,
+            min_test_coverage: 50.0,
+            coverage_min_complexity: 5,
+            adoption_gates: Vec::new(),
+            ignored_unused_inputs: Vec::new(),
+            ignored_unused_outputs: Vec::new(),
         }
     }
 
-    fn analyze_component(&self, component: &NgComponent) -> Vec<Issue> {
+    fn analyze_component(&self, component: &NgComponent, minified: bool) -> Vec<Issue> {
         let mut issues = Vec::new();
 
-        issues.extend(self.check_complexity(component));
+        // minify 済みのファイルは複雑度を測っても意味がないので除く
+        if !minified {
+            issues.extend(self.check_complexity(component));
+        }
         issues.extend(self.check_change_detection(component));
         issues.extend(self.check_inputs_outputs(component));
+        issues.extend(self.check_api_surface(component));
+        issues.extend(self.check_output_names(component));
+        issues.extend(self.check_selection_chains(component));
         issues.extend(self.check_lifecycle_hooks(component));
         issues.extend(self.check_template_style(component));
+        issues.extend(self.check_legacy_control_flow(component));
+        issues.extend(self.check_native_element(component));
 
         issues
     }
 
+    // クラスとテンプレートの複雑度を合わせて閾値と比べる
     fn check_complexity(&self, component: &NgComponent) -> Vec<Issue> {
         let mut issues = Vec::new();
+        let complexity = component.total_complexity();
+        let breakdown = match &component.template_complexity {
+            Some(template) => format!(" (class {} + template {})", component.complexity_score, template.score),
+            None => String::new(),
+        };
 
-        if component.complexity_score > self.max_complexity {
+        if complexity > self.max_complexity {
             issues.push(Issue {
                 severity: Severity::Warning,
                 rule: "component-complexity".to_string(),
                 message: format!(
-                    "Component complexity ({}) exceeds threshold ({}). Consider breaking down into smaller components.",
-                    component.complexity_score, self.max_complexity
+                    "Component complexity ({}){} exceeds threshold ({}). Consider breaking down into smaller components.",
+                    complexity, breakdown, self.max_complexity
                 ),
                 file_path: component.file_path.clone(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
-        if component.complexity_score > self.max_complexity * 2 {
+        if complexity > self.max_complexity * 2 {
             issues.push(Issue {
                 severity: Severity::Error,
                 rule: "component-complexity-critical".to_string(),
                 message: format!(
-                    "Component complexity ({}) is critically high. Immediate refactoring required.",
-                    component.complexity_score
+                    "Component complexity ({}){} is critically high. Immediate refactoring required.",
+                    complexity, breakdown
                 ),
                 file_path: component.file_path.clone(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
@@ -89,6 +223,7 @@ impl ComponentAnalyzer {
                 file_path: component.file_path.clone(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
@@ -109,6 +244,7 @@ impl ComponentAnalyzer {
                 file_path: component.file_path.clone(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
@@ -123,27 +259,106 @@ impl ComponentAnalyzer {
                 file_path: component.file_path.clone(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
         issues
     }
 
-    fn check_lifecycle_hooks(&self, component: &NgComponent) -> Vec<Issue> {
-        let mut issues = Vec::new();
-        let hooks = &component.lifecycle_hooks;
+    // 入力・出力・public メソッドを合わせた数が多いコンポーネントは、何でもやろうとしている
+    fn check_api_surface(&self, component: &NgComponent) -> Vec<Issue> {
+        let api_size = api_size(component);
+        if api_size <= self.max_api_size {
+            return Vec::new();
+        }
 
-        if hooks.contains(&"ngOnInit".to_string()) && hooks.contains(&"ngOnDestroy".to_string()) {
-            if !self.has_proper_cleanup_pattern(component) {
-                issues.push(Issue {
+        vec![Issue {
+            severity: Severity::Warning,
+            rule: "component-api-surface".to_string(),
+            message: format!(
+                "'{}' exposes {} API members ({} inputs, {} outputs, {} public methods), above the limit of {}. Split it into smaller components composed in the template, or move logic into a service.",
+                component.name,
+                api_size,
+                component.inputs.len(),
+                component.outputs.len(),
+                component.public_methods.len(),
+                self.max_api_size
+            ),
+            file_path: component.file_path.clone(),
+            line: None,
+            column: None,
+            fingerprint: None,
+        }]
+    }
+
+    fn check_output_names(&self, component: &NgComponent) -> Vec<Issue> {
+        let prefix = component.name.trim_end_matches("Component");
+        let prefix = match prefix.chars().next() {
+            Some(first) => format!("{}{}", first.to_lowercase(), &prefix[first.len_utf8()..]),
+            None => "custom".to_string(),
+        };
+
+        component.outputs.iter()
+            .map(|output| output.alias.as_deref().unwrap_or(&output.name))
+            .filter(|name| NATIVE_DOM_EVENTS.contains(&name.to_lowercase().as_str()))
+            .filter(|name| !self.allowed_event_outputs.iter().any(|allowed| allowed == name))
+            .map(|name| {
+                let mut chars = name.chars();
+                let capitalized = chars.next()
+                    .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                    .unwrap_or_default();
+                Issue {
                     severity: Severity::Warning,
-                    rule: "missing-cleanup-pattern".to_string(),
-                    message: "Component implements ngOnInit and ngOnDestroy but may be missing proper cleanup patterns (unsubscribe, etc.)".to_string(),
+                    rule: "native-event-output-name".to_string(),
+                    message: format!(
+                        "Output '{}' shadows the native DOM '{}' event, so ({}) bindings may fire for both the output and the bubbling DOM event. Use a prefixed name such as '{}{}'.",
+                        name, name.to_lowercase(), name, prefix, capitalized
+                    ),
                     file_path: component.file_path.clone(),
                     line: None,
                     column: None,
-                });
-            }
+                    fingerprint: None,
+                }
+            })
+            .collect()
+    }
+
+    fn check_selection_chains(&self, component: &NgComponent) -> Vec<Issue> {
+        component.selection_chains.iter()
+            .filter(|chain| chain.branches > self.max_selection_branches)
+            .map(|chain| Issue {
+                severity: Severity::Warning,
+                rule: "component-selection-chain".to_string(),
+                message: format!(
+                    "{} chain on '{}' selects between {} child components (maximum {}). Consider a type-to-component map rendered with ngComponentOutlet.",
+                    chain.kind, chain.discriminator, chain.branches, self.max_selection_branches
+                ),
+                file_path: chain.file_path.clone(),
+                line: chain.line,
+                column: None,
+                fingerprint: None,
+            })
+            .collect()
+    }
+
+    fn check_lifecycle_hooks(&self, component: &NgComponent) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        let hooks = &component.lifecycle_hooks;
+
+        if hooks.contains(&"ngOnInit".to_string())
+            && hooks.contains(&"ngOnDestroy".to_string())
+            && !self.has_proper_cleanup_pattern(component)
+        {
+            issues.push(Issue {
+                severity: Severity::Warning,
+                rule: "missing-cleanup-pattern".to_string(),
+                message: "Component implements ngOnInit and ngOnDestroy but may be missing proper cleanup patterns (unsubscribe, etc.)".to_string(),
+                file_path: component.file_path.clone(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            });
         }
 
         if hooks.len() > 4 {
@@ -157,6 +372,7 @@ impl ComponentAnalyzer {
                 file_path: component.file_path.clone(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
@@ -174,6 +390,7 @@ impl ComponentAnalyzer {
                 file_path: component.file_path.clone(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
@@ -185,6 +402,7 @@ impl ComponentAnalyzer {
                 file_path: component.file_path.clone(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
@@ -197,6 +415,310 @@ impl ComponentAnalyzer {
                     file_path: component.file_path.clone(),
                     line: None,
                     column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        issues
+    }
+
+    // *ngIf / *ngFor / *ngSwitchCase は組み込みのブロック構文に置き換えられる
+    fn check_legacy_control_flow(&self, component: &NgComponent) -> Vec<Issue> {
+        let Some(control_flow) = &component.control_flow else {
+            return Vec::new();
+        };
+        if control_flow.structural_directives.is_empty() {
+            return Vec::new();
+        }
+
+        let directives: Vec<String> = control_flow.structural_directives.iter()
+            .map(|(name, count)| format!("{} {}", count, name))
+            .collect();
+        vec![Issue {
+            severity: Severity::Info,
+            rule: "legacy-control-flow".to_string(),
+            message: format!(
+                "Template of '{}' uses {}. Migrate to the built-in @if / @for / @switch blocks (ng generate @angular/core:control-flow).",
+                component.name,
+                directives.join(", ")
+            ),
+            file_path: control_flow.file_path.clone(),
+            line: None,
+            column: None,
+            fingerprint: None,
+        }]
+    }
+
+    // nativeElement を直接書き換えると SSR や Web Worker で動かず、innerHTML はサニタイズもされない
+    fn check_native_element(&self, component: &NgComponent) -> Vec<Issue> {
+        let writes = &component.host.native_element_writes;
+        if writes.is_empty() {
+            return Vec::new();
+        }
+
+        vec![Issue {
+            severity: Severity::Warning,
+            rule: "native-element-manipulation".to_string(),
+            message: format!(
+                "'{}' modifies the DOM through ElementRef.nativeElement ({}). Use Renderer2 or @HostBinding so it works with server-side rendering and goes through Angular's sanitization.",
+                component.name,
+                writes.join(", ")
+            ),
+            file_path: component.file_path.clone(),
+            line: None,
+            column: None,
+            fingerprint: None,
+        }]
+    }
+
+    fn check_file_sizes(&self, project: &NgProject) -> Vec<Issue> {
+        project.files.iter()
+            .filter(|file| file.lines.logical > self.max_file_lines)
+            .map(|file| Issue {
+                severity: Severity::Warning,
+                rule: "max-file-lines".to_string(),
+                message: format!(
+                    "File has {} lines of code ({} physical), which exceeds the maximum of {}. Consider splitting it into smaller units.",
+                    file.lines.logical, file.lines.physical, self.max_file_lines
+                ),
+                file_path: file.file_path.clone(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            })
+            .collect()
+    }
+
+    // テンプレートから組み立てたコンポーネントツリーの深さと、一箇所でしか使われない小さなコンポーネント
+    fn check_component_tree(&self, project: &NgProject) -> Vec<Issue> {
+        let tree = build_component_tree(project);
+        let mut issues = Vec::new();
+
+        for node in &tree.nodes {
+            // 上限を超えた最初の階層だけ報告し、その下の子孫は重ねて報告しない
+            if node.depth == self.max_depth + 1 {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "deep-component-nesting".to_string(),
+                    message: format!(
+                        "'{}' is nested {} components deep (max {}): {}. Flatten the hierarchy or use content projection.",
+                        node.name,
+                        node.depth,
+                        self.max_depth,
+                        tree.deepest_path(&node.name).join(" > ")
+                    ),
+                    file_path: node.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+
+            let Some(component) = project.components.iter().find(|c| c.name == node.name) else { continue };
+            let parents: Vec<&str> = tree.edges.iter()
+                .filter(|edge| edge.child == node.name)
+                .map(|edge| edge.parent.as_str())
+                .collect();
+            if node.usages == 1 && parents.len() == 1 && component.lines.logical <= self.max_inline_lines {
+                issues.push(Issue {
+                    severity: Severity::Info,
+                    rule: "single-use-component".to_string(),
+                    message: format!(
+                        "'{}' ({} lines) is only used once, in '{}'. Consider inlining it into its parent.",
+                        node.name, component.lines.logical, parents[0]
+                    ),
+                    file_path: node.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        issues
+    }
+
+    // bootstrap されるコンポーネントから描画されるまでの深さ。深いツリーは変更検知と初期描画のコストが大きい
+    fn check_render_depth(&self, project: &NgProject) -> (Vec<Issue>, Option<RenderDepth>) {
+        let chains = render_chains(project, &build_component_tree(project));
+        if chains.is_empty() {
+            return (Vec::new(), None);
+        }
+
+        let depth = |chain: &[String]| chain.len() as u32 - 1;
+        let issues = project.components.iter()
+            .filter_map(|component| {
+                let chain = chains.iter().find(|chain| chain.path.last() == Some(&component.name))?;
+                // 上限を超えた最初の階層だけ報告する
+                (depth(&chain.path) == self.max_render_depth + 1).then(|| Issue {
+                    severity: Severity::Warning,
+                    rule: "deep-render-tree".to_string(),
+                    message: format!(
+                        "'{}' is rendered {} levels below the bootstrap component (max {}): {}. Deep render trees increase change detection and initial render cost.",
+                        component.name,
+                        depth(&chain.path),
+                        self.max_render_depth,
+                        chain.path.join(" > ")
+                    ),
+                    file_path: component.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                })
+            })
+            .collect();
+
+        let mut leaves: Vec<&Vec<String>> = chains.iter().filter(|chain| chain.leaf).map(|chain| &chain.path).collect();
+        leaves.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        let render_depth = RenderDepth {
+            max_depth: chains.iter().map(|chain| depth(&chain.path)).max().unwrap_or(0),
+            deepest_chains: leaves.into_iter().take(DEEPEST_RENDER_CHAINS).cloned().collect(),
+        };
+        (issues, Some(render_depth))
+    }
+
+    // テンプレートで要素として使われているコンポーネントの、どこからも束縛されない入力と購読されない出力。
+    // ルーティングや bootstrap で表示するものはコードから値を渡すので対象外
+    fn check_unused_bindings(&self, project: &NgProject) -> Result<Vec<Issue>> {
+        let ignored_inputs = build_glob_set(&self.ignored_unused_inputs)?;
+        let ignored_outputs = build_glob_set(&self.ignored_unused_outputs)?;
+        let rendered_by_code: HashSet<&str> = project.routed_components.iter()
+            .chain(&project.bootstrap_components)
+            .map(String::as_str)
+            .chain(project.lazy_routes.iter().filter_map(|route| route.module.as_deref()))
+            .collect();
+        let mut issues = Vec::new();
+
+        for component in &project.components {
+            if rendered_by_code.contains(component.name.as_str()) {
+                continue;
+            }
+            let tags: Vec<String> = component.selector.iter()
+                .flat_map(|selector| selector.split(','))
+                .map(|selector| selector.trim().to_lowercase())
+                .filter(|selector| !selector.is_empty() && selector.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+                .collect();
+            let bindings: Vec<&ElementBindings> = project.components.iter()
+                .flat_map(|parent| &parent.element_bindings)
+                .filter(|bindings| tags.contains(&bindings.tag))
+                .collect();
+            if bindings.is_empty() {
+                continue;
+            }
+            let relative_path = relative_issue_path(&component.file_path, &project.root_path);
+            let ignored = |globs: &GlobSet| globs.is_match(&component.name) || globs.is_match(&relative_path);
+
+            if !ignored(&ignored_inputs) {
+                for input in &component.inputs {
+                    let name = input.alias.as_deref().unwrap_or(&input.name);
+                    if !bindings.iter().any(|bindings| bindings.inputs.iter().any(|bound| bound == name)) {
+                        issues.push(Issue {
+                            severity: Severity::Warning,
+                            rule: "unused-input".to_string(),
+                            message: format!(
+                                "Input '{}' of {} is never bound in any template that uses <{}>. Remove it, or add the component to the rule's ignore option if it is part of a published API.",
+                                name, component.name, tags[0]
+                            ),
+                            file_path: component.file_path.clone(),
+                            line: None,
+                            column: None,
+                            fingerprint: None,
+                        });
+                    }
+                }
+            }
+            if !ignored(&ignored_outputs) {
+                for output in &component.outputs {
+                    let name = output.alias.as_deref().unwrap_or(&output.name);
+                    if !bindings.iter().any(|bindings| bindings.outputs.iter().any(|bound| bound == name)) {
+                        issues.push(Issue {
+                            severity: Severity::Warning,
+                            rule: "unused-output".to_string(),
+                            message: format!(
+                                "Output '{}' of {} is never listened to in any template that uses <{}>. Remove it, or add the component to the rule's ignore option if it is part of a published API.",
+                                name, component.name, tags[0]
+                            ),
+                            file_path: component.file_path.clone(),
+                            line: None,
+                            column: None,
+                            fingerprint: None,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(issues)
+    }
+
+    fn check_inheritance(&self, project: &NgProject) -> Vec<Issue> {
+        let by_name = class_index(project);
+        let mut issues = Vec::new();
+
+        // 深さは末端のクラスでだけ報告する
+        for class in leaf_classes(project) {
+            let chain = ancestors(class, &by_name);
+            if chain.len() as u32 > self.max_inheritance_depth {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "deep-inheritance-chain".to_string(),
+                    message: format!(
+                        "{} has an inheritance chain of {} levels ({} -> {}), above the maximum of {}. Prefer composition (inject() helpers, services, host directives) over stacked base classes",
+                        class.name,
+                        chain.len(),
+                        class.name,
+                        chain.iter().map(|ancestor| ancestor.name.as_str()).collect::<Vec<_>>().join(" -> "),
+                        self.max_inheritance_depth
+                    ),
+                    file_path: class.file_path.clone(),
+                    line: Some(class.line),
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        for component in &project.components {
+            let Some(base) = component.heritage.base_class.as_deref() else { continue };
+            let Some(base_class) = by_name.get(base) else { continue };
+            if base_class.decorator.as_deref() == Some("Component") && !base_class.is_abstract {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "extends-concrete-component".to_string(),
+                    message: format!(
+                        "{} extends the concrete component {} ({}). Its template, styles and metadata are not inherited, and the two stay coupled. Move the shared logic into an abstract base class or a service",
+                        component.name, base, base_class.file_path
+                    ),
+                    file_path: component.file_path.clone(),
+                    line: by_name.get(component.name.as_str()).map(|class| class.line),
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        for class in &project.classes {
+            let chain = ancestors(class, &by_name);
+            for hook in &class.lifecycle_hooks {
+                if class.heritage.super_calls.contains(hook) {
+                    continue;
+                }
+                let Some(parent) = chain.iter()
+                    .filter_map(|ancestor| ancestor.class)
+                    .find(|ancestor| ancestor.lifecycle_hooks.contains(hook))
+                else { continue };
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    rule: "missing-super-lifecycle-call".to_string(),
+                    message: format!(
+                        "{}.{}() overrides {}.{}() without calling super.{}(), so the base class logic (subscriptions, cleanup) never runs",
+                        class.name, hook, parent.name, hook, hook
+                    ),
+                    file_path: class.file_path.clone(),
+                    line: Some(class.line),
+                    column: None,
+                    fingerprint: None,
                 });
             }
         }
@@ -204,6 +726,145 @@ impl ComponentAnalyzer {
         issues
     }
 
+    // templateUrl / styleUrls がコンポーネントのフォルダ外を指していないか、誰からも参照されないテンプレートやスタイルが残っていないか
+    fn check_resource_colocation(&self, project: &NgProject) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        for component in &project.components {
+            let component_path = Path::new(&component.file_path);
+            let dir = component_dir(component);
+            for url in component.template_url.iter().chain(&component.style_urls) {
+                let resolved = resolve_relative_path(component_path, url);
+                if !dir.is_empty() && !resolved.starts_with(&format!("{}/", dir)) {
+                    issues.push(Issue {
+                        severity: Severity::Warning,
+                        rule: "component-resource-outside-folder".to_string(),
+                        message: format!(
+                            "'{}' points outside the component's folder. Keep templates and styles next to the component, or move shared styles into a global stylesheet.",
+                            url
+                        ),
+                        file_path: component.file_path.clone(),
+                        line: None,
+                        column: None,
+                        fingerprint: None,
+                    });
+                }
+            }
+        }
+
+        let referenced = referenced_by_components(project);
+        let component_dirs: HashSet<String> = project.components.iter().map(component_dir).collect();
+        for resource in &project.resource_files {
+            let normalized = normalize_path(resource);
+            let path = Path::new(&normalized);
+            let dir = path.parent().map(|dir| dir.display().to_string()).unwrap_or_default();
+            if !component_dirs.contains(&dir) || is_sass_partial(path) || referenced.contains(&normalized) {
+                continue;
+            }
+
+            issues.push(Issue {
+                severity: Severity::Warning,
+                rule: "orphan-component-resource".to_string(),
+                message: "File sits in a component folder but no component references it in templateUrl or styleUrls. Delete it or wire it up.".to_string(),
+                file_path: resource.clone(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        issues
+    }
+
+    // コンポーネントのフォルダの外にある .html / .css などで、どこからも読み込まれていないもの。
+    // コンポーネントのフォルダの中は orphan-component-resource で扱う
+    fn check_orphan_resources(&self, project: &NgProject) -> Vec<Issue> {
+        if project.components.is_empty() {
+            return Vec::new();
+        }
+        let mut referenced = referenced_by_components(project);
+        referenced.extend(project.referenced_resources.iter().map(|resource| normalize_path(resource)));
+        let component_dirs: HashSet<String> = project.components.iter().map(component_dir).collect();
+
+        project.resource_files.iter()
+            .filter(|resource| {
+                let normalized = normalize_path(resource);
+                let path = Path::new(&normalized);
+                let dir = path.parent().map(|dir| dir.display().to_string()).unwrap_or_default();
+                let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+                !component_dirs.contains(&dir)
+                    && !is_sass_partial(path)
+                    && !is_conventional_resource(file_name)
+                    && !referenced.contains(&normalized)
+            })
+            .map(|resource| Issue {
+                severity: Severity::Warning,
+                rule: "orphan-resource-file".to_string(),
+                message: "File is not used by any component's templateUrl or styleUrls, the global styles, index or assets in angular.json, or an @import / @use in another stylesheet. Delete it if it is dead.".to_string(),
+                file_path: resource.clone(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            })
+            .collect()
+    }
+
+    fn check_test_coverage(&self, project: &NgProject) -> Vec<Issue> {
+        // --coverage が指定されていなければ判定しない
+        if project.test_coverage.is_none() {
+            return Vec::new();
+        }
+
+        let minified = minified_files(project);
+        project.components.iter()
+            .filter(|component| !minified.contains(component.file_path.as_str()))
+            .filter(|component| component.complexity_score >= self.coverage_min_complexity)
+            .filter_map(|component| {
+                let message = match component.test_coverage {
+                    None => format!(
+                        "Component has complexity {} but does not appear in the coverage report. Add tests for it.",
+                        component.complexity_score
+                    ),
+                    Some(coverage) if coverage < self.min_test_coverage => format!(
+                        "Component has complexity {} but only {:.1}% line coverage (minimum {:.0}%).",
+                        component.complexity_score, coverage, self.min_test_coverage
+                    ),
+                    Some(_) => return None,
+                };
+                Some(Issue {
+                    severity: Severity::Warning,
+                    rule: "low-test-coverage".to_string(),
+                    message,
+                    file_path: component.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                })
+            })
+            .collect()
+    }
+
+    fn check_adoption_gates(&self, project: &NgProject) -> Vec<Issue> {
+        let adoption = super::adoption_metrics(project);
+
+        self.adoption_gates.iter()
+            .filter_map(|(option, min)| {
+                let (_, label, metric) = ADOPTION_GATES.iter().find(|(name, _, _)| name == option)?;
+                // 対象がなければ判定しない
+                let actual = metric(&adoption).filter(|actual| actual < min)?;
+                Some(Issue {
+                    severity: Severity::Error,
+                    rule: "adoption-threshold".to_string(),
+                    message: format!("{} adoption is {:.1}%, below the required {:.0}% ({})", label, actual, min, option),
+                    file_path: project.root_path.display().to_string(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                })
+            })
+            .collect()
+    }
+
     fn has_proper_cleanup_pattern(&self, _component: &NgComponent) -> bool {
         true
     }
@@ -245,15 +906,34 @@ impl ComponentAnalyzer {
             });
         }
 
+        let legacy_templates = project.components.iter()
+            .filter(|c| c.control_flow.as_ref().is_some_and(|flow| !flow.structural_directives.is_empty()))
+            .count();
+        if legacy_templates > 0 {
+            recommendations.push(Recommendation {
+                category: "Code Quality".to_string(),
+                title: "Migrate to Built-in Control Flow".to_string(),
+                description: format!(
+                    "{} templates still use *ngIf / *ngFor / *ngSwitch. Run `ng generate @angular/core:control-flow` to convert them to @if / @for / @switch blocks.",
+                    legacy_templates
+                ),
+                priority: Priority::Low,
+                file_path: None,
+            });
+        }
+
         recommendations
     }
 
     fn calculate_metrics(&self, project: &NgProject) -> ProjectMetrics {
         let total_components = project.components.len() as u32;
-        let average_complexity = if total_components > 0 {
-            project.components.iter()
-                .map(|c| c.complexity_score as f64)
-                .sum::<f64>() / total_components as f64
+        let minified = minified_files(project);
+        let measured: Vec<f64> = project.components.iter()
+            .filter(|c| !minified.contains(c.file_path.as_str()))
+            .map(|c| c.complexity_score as f64)
+            .collect();
+        let average_complexity = if !measured.is_empty() {
+            measured.iter().sum::<f64>() / measured.len() as f64
         } else {
             0.0
         };
@@ -263,21 +943,98 @@ impl ComponentAnalyzer {
             total_services: project.services.len() as u32,
             total_modules: project.modules.len() as u32,
             average_complexity,
-            lines_of_code: 0,
-            test_coverage: None,
+            lines_of_code: project.files.iter().filter(|f| !f.minified).map(|f| f.lines.logical).sum(),
+            test_coverage: project.test_coverage,
+            feature_testability: Vec::new(),
+            adoption: Some(super::adoption_metrics(project)),
+            render_depth: None,
+            duplicated_lines: None,
+            duplication_percentage: None,
+            zoneless_readiness: Vec::new(),
+            api_surface: api_surface(project),
+            type_safety: None,
+            cd_hotspots: Vec::new(),
         }
     }
 }
 
-#[async_trait]
+fn api_size(component: &NgComponent) -> usize {
+    component.inputs.len() + component.outputs.len() + component.public_methods.len()
+}
+
+// 上限と表示名。最後のバケットは上限なし
+const API_SIZE_BUCKETS: [(usize, &str); 4] = [(5, "0-5"), (10, "6-10"), (20, "11-20"), (usize::MAX, "21+")];
+
+fn api_surface(project: &NgProject) -> Option<ApiSurface> {
+    if project.components.is_empty() {
+        return None;
+    }
+
+    let sizes: Vec<usize> = project.components.iter().map(api_size).collect();
+    let mut distribution: Vec<ApiSurfaceBucket> = API_SIZE_BUCKETS.iter()
+        .map(|(_, range)| ApiSurfaceBucket { range: range.to_string(), components: 0 })
+        .collect();
+    for size in &sizes {
+        let bucket = API_SIZE_BUCKETS.iter().position(|(max, _)| size <= max).unwrap_or(API_SIZE_BUCKETS.len() - 1);
+        distribution[bucket].components += 1;
+    }
+
+    Some(ApiSurface {
+        average: sizes.iter().sum::<usize>() as f64 / sizes.len() as f64,
+        max: sizes.iter().copied().max().unwrap_or(0) as u32,
+        distribution,
+    })
+}
+
+fn minified_files(project: &NgProject) -> HashSet<&str> {
+    project.files.iter().filter(|file| file.minified).map(|file| file.file_path.as_str()).collect()
+}
+
+fn component_dir(component: &NgComponent) -> String {
+    Path::new(&component.file_path).parent()
+        .map(|dir| normalize_path(&dir.display().to_string()))
+        .unwrap_or_default()
+}
+
+// templateUrl と styleUrls が指すファイル
+fn referenced_by_components(project: &NgProject) -> HashSet<String> {
+    project.components.iter()
+        .flat_map(|component| {
+            let component_path = Path::new(&component.file_path);
+            component.template_url.iter().chain(&component.style_urls).map(move |url| resolve_relative_path(component_path, url))
+        })
+        .collect()
+}
+
+// Sass のパーシャル（_variables.scss など）は他のスタイルから読み込まれる
+fn is_sass_partial(path: &Path) -> bool {
+    path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.starts_with('_'))
+}
+
+fn normalize_path(path: &str) -> String {
+    path.trim_start_matches("./").to_string()
+}
+
 impl Analyzer for ComponentAnalyzer {
-    async fn analyze(&self, project: &NgProject) -> Result<AnalysisResult> {
-        let issues: Vec<Issue> = project.components
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
+        let minified = minified_files(project);
+        let mut issues: Vec<Issue> = project.components
             .par_iter()
-            .flat_map(|component| self.analyze_component(component))
+            .flat_map(|component| self.analyze_component(component, minified.contains(component.file_path.as_str())))
             .collect();
+        issues.extend(self.check_file_sizes(project));
+        issues.extend(self.check_resource_colocation(project));
+        issues.extend(self.check_orphan_resources(project));
+        issues.extend(self.check_inheritance(project));
+        issues.extend(self.check_unused_bindings(project)?);
+        issues.extend(self.check_component_tree(project));
+        let (render_issues, render_depth) = self.check_render_depth(project);
+        issues.extend(render_issues);
+        issues.extend(self.check_test_coverage(project));
+        issues.extend(self.check_adoption_gates(project));
 
-        let metrics = self.calculate_metrics(project);
+        let mut metrics = self.calculate_metrics(project);
+        metrics.render_depth = render_depth;
         let recommendations = self.generate_recommendations(project);
 
         Ok(AnalysisResult {
@@ -285,6 +1042,7 @@ impl Analyzer for ComponentAnalyzer {
             issues,
             metrics,
             recommendations,
+            omitted_issues: vec![],
         })
     }
 
@@ -301,37 +1059,24 @@ impl Analyzer for ComponentAnalyzer {
 mod tests {
     use super::*;
     use crate::ast::*;
+    use crate::ast::builder::{ComponentBuilder, NgProjectBuilder};
     use std::path::PathBuf;
 
-    #[tokio::test]
-    async fn test_component_analysis() {
+    #[test]
+    fn test_component_analysis() {
         let analyzer = ComponentAnalyzer::new();
         
-        let component = NgComponent {
-            name: "TestComponent".to_string(),
-            file_path: PathBuf::from("test.component.ts"),
-            selector: Some("app-test".to_string()),
-            template_url: Some("test.component.html".to_string()),
-            template: None,
-            style_urls: vec!["test.component.css".to_string()],
-            inputs: vec![],
-            outputs: vec![],
-            lifecycle_hooks: vec!["ngOnInit".to_string()],
-            dependencies: vec![],
-            change_detection: ChangeDetectionStrategy::Default,
-            complexity_score: 5,
-        };
+        let component = ComponentBuilder::new("TestComponent")
+            .selector("app-test")
+            .template_url("test.component.html")
+            .style_urls(&["test.component.css"])
+            .lifecycle_hooks(&["ngOnInit"])
+            .complexity(5)
+            .build();
 
-        let project = NgProject {
-            root_path: PathBuf::from("."),
-            components: vec![component],
-            services: vec![],
-            modules: vec![],
-            pipes: vec![],
-            directives: vec![],
-        };
+        let project = Arc::new(NgProjectBuilder::new(".").component(component).build());
 
-        let result = analyzer.analyze(&project).await.unwrap();
+        let result = analyzer.analyze(&project).unwrap();
         
         assert_eq!(result.issues.len(), 1);
         assert_eq!(result.issues[0].rule, "change-detection-strategy");
@@ -342,27 +1087,305 @@ mod tests {
     fn test_complexity_check() {
         let analyzer = ComponentAnalyzer::new();
         
-        let component = NgComponent {
-            name: "ComplexComponent".to_string(),
-            file_path: PathBuf::from("complex.component.ts"),
-            selector: Some("app-complex".to_string()),
-            template_url: Some("complex.component.html".to_string()),
-            template: None,
-            style_urls: vec![],
-            inputs: vec![],
-            outputs: vec![],
-            lifecycle_hooks: vec![],
-            dependencies: vec![],
-            change_detection: ChangeDetectionStrategy::Default,
-            complexity_score: 15,
-        };
+        let component = ComponentBuilder::new("ComplexComponent")
+            .template_url("complex.component.html")
+            .complexity(15)
+            .build();
 
-        let issues = analyzer.analyze_component(&component);
+        let issues = analyzer.analyze_component(&component, false);
         
         let complexity_issues: Vec<_> = issues.iter()
             .filter(|issue| issue.rule.contains("complexity"))
             .collect();
         
         assert!(!complexity_issues.is_empty());
+
+        // クラスだけでは閾値に届かなくても、テンプレートと合わせて超えれば報告する
+        let component = ComponentBuilder::new("DashboardComponent")
+            .complexity(6)
+            .template_complexity(3, 2, 10, 1)
+            .build();
+        let issues = analyzer.check_complexity(&component);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("(14) (class 6 + template 8)"));
+    }
+
+    #[test]
+    fn test_native_event_output_names() {
+        let component = ComponentBuilder::new("ButtonComponent")
+            .template_url("button.component.html")
+            .output("click", None)
+            .output("saved", Some("submit"))
+            .output("change", None)
+            .on_push()
+            .build();
+
+        let analyzer = ComponentAnalyzer::new().with_allowed_event_outputs(vec!["change".to_string()]);
+        let issues = analyzer.check_output_names(&component);
+
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.contains("'buttonClick'"));
+        assert!(issues[1].message.contains("'submit'"));
+    }
+
+    #[test]
+    fn test_api_surface() {
+        let wide = ComponentBuilder::new("DashboardComponent")
+            .inputs(&["user", "filters", "layout", "theme"])
+            .output("changed", None)
+            .public_methods(&["refresh", "export", "print"])
+            .build();
+        let project = NgProjectBuilder::new("/app")
+            .component(wide.clone())
+            .component(ComponentBuilder::new("BadgeComponent").inputs(&["label"]))
+            .build();
+
+        let analyzer = ComponentAnalyzer::new().with_max_api_size(6);
+        let issues = analyzer.check_api_surface(&wide);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("8 API members (4 inputs, 1 outputs, 3 public methods)"));
+        assert!(analyzer.with_max_api_size(8).check_api_surface(&wide).is_empty());
+
+        let surface = api_surface(&project).unwrap();
+        assert_eq!(surface.average, 4.5);
+        assert_eq!(surface.max, 8);
+        let counts: Vec<u32> = surface.distribution.iter().map(|bucket| bucket.components).collect();
+        assert_eq!(counts, vec![1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_native_element_manipulation() {
+        let component = ComponentBuilder::new("TooltipComponent")
+            .with(|c| c.host.native_element_writes = vec!["style.top".to_string(), "appendChild".to_string()])
+            .build();
+
+        let issues = ComponentAnalyzer::new().check_native_element(&component);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("(style.top, appendChild)"));
+        assert!(ComponentAnalyzer::new().check_native_element(&ComponentBuilder::new("PlainComponent").build()).is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_resource_colocation() {
+        let component = ComponentBuilder::new("OrdersComponent")
+            .file_path("./src/app/orders/orders.component.ts")
+            .template_url("./orders.component.html")
+            .style_urls(&["../shared/table.scss"])
+            .on_push()
+            .build();
+
+        let project = NgProject {
+            root_path: PathBuf::from("."),
+            components: vec![component],
+            resource_files: vec![
+                "./src/app/orders/orders.component.html".to_string(),
+                "./src/app/orders/orders.component.scss".to_string(),
+                "./src/app/orders/_mixins.scss".to_string(),
+                "./src/app/shared/table.scss".to_string(),
+            ],
+            ..Default::default()
+        };
+
+        let issues = ComponentAnalyzer::new().check_resource_colocation(&project);
+
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].rule, "component-resource-outside-folder");
+        assert!(issues[0].message.contains("'../shared/table.scss'"));
+        assert_eq!(issues[1].rule, "orphan-component-resource");
+        assert_eq!(issues[1].file_path, "./src/app/orders/orders.component.scss");
+    }
+
+    #[test]
+    fn test_orphan_resource_files() {
+        let project = NgProjectBuilder::new(".")
+            .component(ComponentBuilder::new("OrdersComponent")
+                .file_path("./src/app/orders/orders.component.ts")
+                .style_urls(&["../shared/table.scss"]))
+            .resource_file("./src/app/orders/legacy.html")
+            .resource_file("./src/app/shared/table.scss")
+            .resource_file("./src/app/shared/old-table.scss")
+            .resource_file("./src/app/shared/_mixins.scss")
+            .resource_file("./src/theme/dark.scss")
+            .resource_file("./src/styles.scss")
+            .with(|p| p.referenced_resources = vec!["./src/theme/dark.scss".to_string()])
+            .build();
+
+        let issues = ComponentAnalyzer::new().check_orphan_resources(&project);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].file_path, "./src/app/shared/old-table.scss");
+    }
+
+    #[test]
+    fn test_unused_bindings() {
+        let project = NgProjectBuilder::new(".")
+            .component(ComponentBuilder::new("ListComponent").binds("app-card", &["title", "expanded"], &["expandedChange"]))
+            .component(ComponentBuilder::new("CardComponent")
+                .inputs(&["title", "subtitle", "expanded"])
+                .output("expandedChange", None)
+                .output("closed", Some("close")))
+            .component(ComponentBuilder::new("LibButtonComponent").file_path("projects/ui/button.component.ts").inputs(&["size"]))
+            .component(ComponentBuilder::new("PageComponent").inputs(&["id"]).binds("app-page", &[], &[]))
+            .component(ComponentBuilder::new("DialogComponent").inputs(&["data"]))
+            .with(|p| p.routed_components = vec!["PageComponent".to_string()])
+            .build();
+        let project = NgProject {
+            components: project.components.into_iter()
+                .map(|mut component| {
+                    if component.name == "ListComponent" {
+                        component.element_bindings.push(ElementBindings { tag: "app-lib-button".to_string(), inputs: vec![], outputs: vec![] });
+                    }
+                    component
+                })
+                .collect(),
+            ..project
+        };
+
+        let analyzer = ComponentAnalyzer::new();
+        let issues = analyzer.check_unused_bindings(&project).unwrap();
+        let found: Vec<(&str, &str)> = issues.iter().map(|i| (i.rule.as_str(), i.message.split(' ').nth(1).unwrap())).collect();
+        assert_eq!(found, vec![
+            ("unused-input", "'subtitle'"),
+            ("unused-output", "'close'"),
+            ("unused-input", "'size'"),
+        ]);
+
+        let issues = analyzer.with_unused_binding_ignores(vec!["projects/ui/**".to_string()], vec![]).check_unused_bindings(&project).unwrap();
+        assert_eq!(issues.len(), 2);
+    }
+
+    #[test]
+    fn test_inheritance_rules() {
+        let class = |name: &str, base: Option<&str>, decorator: Option<&str>, hooks: &[&str], super_calls: &[&str]| NgClass {
+            name: name.to_string(),
+            file_path: format!("{}.ts", name),
+            line: 3,
+            is_abstract: decorator.is_none(),
+            decorator: decorator.map(String::from),
+            lifecycle_hooks: hooks.iter().map(|hook| hook.to_string()).collect(),
+            heritage: ClassHeritage {
+                base_class: base.map(String::from),
+                super_calls: super_calls.iter().map(|call| call.to_string()).collect(),
+                ..Default::default()
+            },
+        };
+        let project = NgProjectBuilder::new(".")
+            .component(ComponentBuilder::new("OrdersComponent").file_path("OrdersComponent.ts").extends("ListComponent"))
+            .component(ComponentBuilder::new("ListComponent").file_path("ListComponent.ts").extends("BaseList"))
+            .with(|p| p.classes = vec![
+                class("BaseList", Some("Destroyable"), None, &["ngOnInit", "ngOnDestroy"], &[]),
+                class("Destroyable", None, None, &["ngOnDestroy"], &[]),
+                class("ListComponent", Some("BaseList"), Some("Component"), &["ngOnInit", "ngOnDestroy"], &["ngOnDestroy"]),
+                class("OrdersComponent", Some("ListComponent"), Some("Component"), &[], &[]),
+            ])
+            .build();
+
+        let issues = ComponentAnalyzer::new().check_inheritance(&project);
+        let found: Vec<(&str, &str)> = issues.iter().map(|i| (i.rule.as_str(), i.file_path.as_str())).collect();
+
+        assert_eq!(found, vec![
+            ("deep-inheritance-chain", "OrdersComponent.ts"),
+            ("extends-concrete-component", "OrdersComponent.ts"),
+            ("missing-super-lifecycle-call", "BaseList.ts"),
+            ("missing-super-lifecycle-call", "ListComponent.ts"),
+        ]);
+        assert!(issues[0].message.contains("(OrdersComponent -> ListComponent -> BaseList -> Destroyable)"));
+        assert!(issues[2].message.contains("BaseList.ngOnDestroy() overrides Destroyable.ngOnDestroy()"));
+        assert!(issues[3].message.contains("ListComponent.ngOnInit() overrides BaseList.ngOnInit()"));
+        assert!(ComponentAnalyzer::new().with_max_inheritance_depth(3).check_inheritance(&project).iter().all(|i| i.rule != "deep-inheritance-chain"));
+    }
+
+    fn tree_component(name: &str, selector: &str, usages: &[(&str, u32)]) -> NgComponent {
+        usages.iter()
+            .fold(ComponentBuilder::new(name), |builder, (tag, count)| builder.uses(tag, "embeds", *count))
+            .file_path(&format!("{}.component.ts", selector))
+            .selector(selector)
+            .on_push()
+            .standalone()
+            .lines(20, 15)
+            .build()
+    }
+
+    #[test]
+    fn test_component_tree_rules() {
+        let component = tree_component;
+
+        let project = NgProject {
+            root_path: PathBuf::from("."),
+            components: vec![
+                component("AppComponent", "app-root", &[("app-shell", 1)]),
+                component("ShellComponent", "app-shell", &[("app-page", 2)]),
+                component("PageComponent", "app-page", &[("app-card", 1)]),
+                component("CardComponent", "app-card", &[]),
+            ],
+            ..Default::default()
+        };
+
+        let issues = ComponentAnalyzer::new()
+            .with_component_tree_limits(2, 50)
+            .check_component_tree(&project);
+        let found: Vec<(&str, &str)> = issues.iter().map(|i| (i.rule.as_str(), i.file_path.as_str())).collect();
+
+        assert_eq!(found, vec![
+            ("single-use-component", "app-shell.component.ts"),
+            ("deep-component-nesting", "app-card.component.ts"),
+            ("single-use-component", "app-card.component.ts"),
+        ]);
+        assert!(issues[1].message.contains("AppComponent > ShellComponent > PageComponent > CardComponent"));
+    }
+
+    #[test]
+    fn test_render_depth() {
+        let component = tree_component;
+        let project = NgProject {
+            root_path: PathBuf::from("."),
+            components: vec![
+                component("AppComponent", "app-root", &[("app-shell", 1), ("router-outlet", 1)]),
+                component("ShellComponent", "app-shell", &[("app-nav", 1)]),
+                component("NavComponent", "app-nav", &[]),
+                component("OrdersComponent", "app-orders", &[("app-table", 1), ("router-outlet", 1)]),
+                component("TableComponent", "app-table", &[("app-cell", 1)]),
+                component("CellComponent", "app-cell", &[]),
+                component("DetailComponent", "app-detail", &[]),
+                component("OrphanComponent", "app-orphan", &[("app-cell", 1)]),
+            ],
+            modules: vec![NgModule {
+                name: "AppModule".to_string(),
+                file_path: "app.module.ts".to_string(),
+                imports: vec![],
+                exports: vec![],
+                declarations: vec![],
+                providers: vec![],
+                bootstrap: vec!["AppComponent".to_string()],
+                for_root_imports: vec![],
+            }],
+            routed_components: vec!["OrdersComponent".to_string()],
+            lazy_routes: vec![LazyRoute {
+                module: Some("DetailComponent".to_string()),
+                target: "detail.component".to_string(),
+                file_path: "app.routes.ts".to_string(),
+                line: Some(1),
+            }],
+            ..Default::default()
+        };
+
+        let (issues, render_depth) = ComponentAnalyzer::new().with_max_render_depth(2).check_render_depth(&project);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, "deep-render-tree");
+        assert_eq!(issues[0].file_path, "app-cell.component.ts");
+        assert!(issues[0].message.contains("AppComponent > OrdersComponent > TableComponent > CellComponent"));
+
+        let render_depth = render_depth.unwrap();
+        assert_eq!(render_depth.max_depth, 3);
+        // 子ルートの router-outlet にはつながないので、OrdersComponent の下に DetailComponent は来ない
+        assert_eq!(render_depth.deepest_chains, vec![
+            vec!["AppComponent", "OrdersComponent", "TableComponent", "CellComponent"],
+            vec!["AppComponent", "ShellComponent", "NavComponent"],
+            vec!["AppComponent", "DetailComponent"],
+        ]);
+
+        let project = NgProject { modules: vec![], ..project };
+        assert_eq!(ComponentAnalyzer::new().check_render_depth(&project).1, None);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::ast::{ComponentTree, ComponentTreeEdge, ComponentTreeNode, NgComponent, NgProject};
+use std::collections::{HashMap, HashSet};
+
+pub const DEEPEST_RENDER_CHAINS: usize = 5;
+
+// ルートから、あるコンポーネントまでの最長の経路と、その先に描画する子がないか
+pub struct RenderChain {
+    pub path: Vec<String>,
+    pub leaf: bool,
+}
+
+// 要素セレクタ（app-card など）からコンポーネントを引く。属性セレクタなどは対象外
+pub fn selector_index(project: &NgProject) -> HashMap<&str, &NgComponent> {
+    let mut by_tag = HashMap::new();
+    for component in &project.components {
+        for selector in component.selector.iter().flat_map(|s| s.split(',')) {
+            let selector = selector.trim();
+            if !selector.is_empty() && selector.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                by_tag.insert(selector, component);
+            }
+        }
+    }
+    by_tag
+}
+
+// コンポーネントと、そのテンプレートからたどれる子孫コンポーネントの論理行数の合計と、子孫の数
+pub fn subtree_lines(component: &NgComponent, by_tag: &HashMap<&str, &NgComponent>) -> (u32, usize) {
+    let mut seen: HashSet<&str> = HashSet::from([component.name.as_str()]);
+    let mut stack = vec![component];
+    let mut lines = 0;
+    while let Some(current) = stack.pop() {
+        lines += current.lines.logical;
+        for usage in &current.template_usages {
+            if let Some(child) = by_tag.get(usage.tag.as_str()) {
+                if seen.insert(child.name.as_str()) {
+                    stack.push(child);
+                }
+            }
+        }
+    }
+    (lines, seen.len() - 1)
+}
+
+pub fn build_component_tree(project: &NgProject) -> ComponentTree {
+    let by_tag = selector_index(project);
+
+    let mut edges: Vec<ComponentTreeEdge> = Vec::new();
+    for component in &project.components {
+        for usage in &component.template_usages {
+            let Some(child) = by_tag.get(usage.tag.as_str()) else { continue };
+            // 自分自身を描画するツリー構造は深さの計算から外す
+            if child.name == component.name {
+                continue;
+            }
+            match edges.iter_mut().find(|edge| edge.parent == component.name && edge.child == child.name) {
+                Some(edge) => edge.count += usage.count,
+                None => edges.push(ComponentTreeEdge {
+                    parent: component.name.clone(),
+                    child: child.name.clone(),
+                    kind: usage.kind.clone(),
+                    count: usage.count,
+                }),
+            }
+        }
+    }
+
+    let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in &edges {
+        parents.entry(edge.child.as_str()).or_default().push(edge.parent.as_str());
+    }
+
+    let mut depths: HashMap<&str, (u32, Option<&str>)> = HashMap::new();
+    for component in &project.components {
+        longest_depth(&component.name, &parents, &mut depths, &mut Vec::new());
+    }
+
+    let nodes = project.components.iter()
+        .map(|component| {
+            let (depth, deepest_parent) = depths.get(component.name.as_str()).copied().unwrap_or((0, None));
+            ComponentTreeNode {
+                name: component.name.clone(),
+                selector: component.selector.clone(),
+                file_path: component.file_path.clone(),
+                depth,
+                deepest_parent: deepest_parent.map(String::from),
+                usages: edges.iter().filter(|edge| edge.child == component.name).map(|edge| edge.count).sum(),
+            }
+        })
+        .collect();
+
+    ComponentTree { nodes, edges }
+}
+
+// 親をたどった最長の深さ。循環している辺は無視する
+fn longest_depth<'a>(
+    name: &'a str,
+    parents: &HashMap<&'a str, Vec<&'a str>>,
+    depths: &mut HashMap<&'a str, (u32, Option<&'a str>)>,
+    stack: &mut Vec<&'a str>,
+) -> u32 {
+    if let Some((depth, _)) = depths.get(name) {
+        return *depth;
+    }
+
+    stack.push(name);
+    let mut best = (0, None);
+    for parent in parents.get(name).into_iter().flatten() {
+        if stack.contains(parent) {
+            continue;
+        }
+        let depth = longest_depth(parent, parents, depths, stack) + 1;
+        if depth > best.0 {
+            best = (depth, Some(*parent));
+        }
+    }
+    stack.pop();
+
+    depths.insert(name, best);
+    best.0
+}
+
+// bootstrap されるコンポーネントをルートに、テンプレートでの利用と router-outlet へのルーティングをたどる
+// どのルートがどの router-outlet に入るかは分からないので、ルーティング先はルートからテンプレートでたどれる
+// router-outlet にだけつなぐ。子ルートの分は深さに含まれないため、実際より浅く見積もる
+pub fn render_chains(project: &NgProject, tree: &ComponentTree) -> Vec<RenderChain> {
+    let names: HashSet<&str> = project.components.iter().map(|c| c.name.as_str()).collect();
+    let mut roots: Vec<&str> = Vec::new();
+    for root in project.modules.iter().flat_map(|m| &m.bootstrap).chain(&project.bootstrap_components) {
+        if names.contains(root.as_str()) && !roots.contains(&root.as_str()) {
+            roots.push(root);
+        }
+    }
+    if roots.is_empty() {
+        return Vec::new();
+    }
+
+    let mut children: HashMap<&str, Vec<&str>> = HashMap::new();
+    for edge in &tree.edges {
+        children.entry(edge.parent.as_str()).or_default().push(edge.child.as_str());
+    }
+
+    let template_reachable = reachable(&roots, &children);
+    let routed: Vec<&str> = project.routed_components.iter().map(String::as_str)
+        .chain(project.lazy_routes.iter().filter_map(|route| route.module.as_deref()))
+        .filter(|name| names.contains(name))
+        .collect();
+    let hosts: Vec<&str> = project.components.iter()
+        .filter(|c| template_reachable.contains(c.name.as_str()) && c.template_usages.iter().any(|usage| usage.tag == "router-outlet"))
+        .map(|c| c.name.as_str())
+        .collect();
+    for host in hosts {
+        let host_children = children.entry(host).or_default();
+        for name in &routed {
+            if *name != host && !host_children.contains(name) {
+                host_children.push(name);
+            }
+        }
+    }
+
+    // ルートは深さ 0 に固定するため、ルートに入る辺は使わない
+    let reachable = reachable(&roots, &children);
+    let mut parents: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (parent, targets) in &children {
+        if !reachable.contains(parent) {
+            continue;
+        }
+        for child in targets {
+            if !roots.contains(child) {
+                parents.entry(*child).or_default().push(*parent);
+            }
+        }
+    }
+    for targets in parents.values_mut() {
+        targets.sort();
+    }
+
+    let mut depths: HashMap<&str, (u32, Option<&str>)> = HashMap::new();
+    project.components.iter()
+        .filter(|c| reachable.contains(c.name.as_str()))
+        .map(|c| {
+            longest_depth(&c.name, &parents, &mut depths, &mut Vec::new());
+            let mut path = vec![c.name.clone()];
+            let mut current = c.name.as_str();
+            while let Some(parent) = depths.get(current).and_then(|(_, parent)| *parent) {
+                if path.iter().any(|visited| visited == parent) {
+                    break;
+                }
+                path.push(parent.to_string());
+                current = parent;
+            }
+            path.reverse();
+            let leaf = children.get(c.name.as_str()).is_none_or(|targets| targets.iter().all(|child| roots.contains(child)));
+            RenderChain { path, leaf }
+        })
+        .collect()
+}
+
+fn reachable<'a>(roots: &[&'a str], children: &HashMap<&'a str, Vec<&'a str>>) -> HashSet<&'a str> {
+    let mut seen: HashSet<&str> = roots.iter().copied().collect();
+    let mut stack: Vec<&str> = roots.to_vec();
+    while let Some(name) = stack.pop() {
+        for child in children.get(name).into_iter().flatten() {
+            if seen.insert(child) {
+                stack.push(child);
+            }
+        }
+    }
+    seen
+}
+
+impl ComponentTree {
+    // ルートから name までの最長の経路
+    pub fn deepest_path(&self, name: &str) -> Vec<String> {
+        let mut path = vec![name.to_string()];
+        let mut current = name;
+        while let Some(parent) = self.nodes.iter()
+            .find(|node| node.name == current)
+            .and_then(|node| node.deepest_parent.as_deref())
+        {
+            if path.iter().any(|visited| visited == parent) {
+                break;
+            }
+            path.push(parent.to_string());
+            current = parent;
+        }
+        path.reverse();
+        path
+    }
+}
@@ -1,14 +1,64 @@
 use super::{Analyzer, AnalysisResult};
-use crate::ast::{NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority};
-use async_trait::async_trait;
+use super::component_tree::selector_index;
+use super::dependency_graph::{find_unused_exports, longest_import_chains, module_path_index, resolve_module};
+use super::injection_graph::is_root_injector;
+use super::module_graph::{build_module_graph, lazy_feature_dir, lazy_route_matches};
+use crate::ast::{ImportExportGraph, ImportType, NgProject, NgModule, NgService, NgGuard, InstalledPackage, Issue, Severity, ProjectMetrics, Recommendation, Priority, ProviderOverride};
+use crate::config::{build_glob_set, relative_issue_path};
+use std::sync::Arc;
 use anyhow::Result;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
 
-pub struct DependencyAnalyzer;
+pub struct DependencyAnalyzer {
+    max_constructor_services: usize,
+    ignored_packages: Vec<String>,
+    max_import_depth: u32,
+    max_import_chains: usize,
+    ignored_exports: Vec<String>,
+    preferred_injection: String,
+}
+
+// これより短いガードは `() => true` のような定型なので比較しない
+const MIN_GUARD_TOKENS: usize = 8;
+const GUARD_SIMILARITY_THRESHOLD: f64 = 0.85;
 
 impl DependencyAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self {
+            max_constructor_services: 3,
+            ignored_packages: Vec::new(),
+            max_import_depth: 12,
+            max_import_chains: 5,
+            ignored_exports: Vec::new(),
+            preferred_injection: "inject".to_string(),
+        }
+    }
+
+    pub fn with_max_constructor_services(mut self, max_constructor_services: usize) -> Self {
+        self.max_constructor_services = max_constructor_services;
+        self
+    }
+
+    pub fn with_import_depth_budget(mut self, max_depth: u32, max_chains: usize) -> Self {
+        self.max_import_depth = max_depth;
+        self.max_import_chains = max_chains;
+        self
+    }
+
+    pub fn with_ignored_exports(mut self, ignored_exports: Vec<String>) -> Self {
+        self.ignored_exports = ignored_exports;
+        self
+    }
+
+    pub fn with_ignored_packages(mut self, ignored_packages: Vec<String>) -> Self {
+        self.ignored_packages = ignored_packages;
+        self
+    }
+
+    pub fn with_preferred_injection(mut self, preferred_injection: String) -> Self {
+        self.preferred_injection = preferred_injection;
+        self
     }
 
     fn analyze_circular_dependencies(&self, project: &NgProject) -> Vec<Issue> {
@@ -38,6 +88,7 @@ impl DependencyAnalyzer {
                     file_path: project.root_path.display().to_string().replace('\\', "/"),
                     line: None,
                     column: None,
+                    fingerprint: None,
                 });
             }
         }
@@ -97,6 +148,501 @@ impl DependencyAnalyzer {
         None
     }
 
+    // テンプレート経由の再帰（A が B を投影し、B が A を埋め込むなど）は import の循環検出では見つからない
+    fn analyze_composition_cycles(&self, project: &NgProject) -> Vec<Issue> {
+        let by_tag = selector_index(project);
+
+        let mut graph: HashMap<String, Vec<String>> = HashMap::new();
+        let mut edge_kinds: HashMap<(String, String), String> = HashMap::new();
+        for component in &project.components {
+            let children = graph.entry(component.name.clone()).or_default();
+            for usage in &component.template_usages {
+                let Some(child) = by_tag.get(usage.tag.as_str()) else { continue };
+                // 自分自身を描画するツリー構造は意図的なものが多いので除外
+                if child.name == component.name {
+                    continue;
+                }
+                if !children.contains(&child.name) {
+                    children.push(child.name.clone());
+                }
+                edge_kinds.entry((component.name.clone(), child.name.clone())).or_insert_with(|| usage.kind.clone());
+            }
+        }
+
+        let Some(cycles) = self.detect_cycles(&graph) else {
+            return Vec::new();
+        };
+
+        let mut issues = Vec::new();
+        for mut cycle in cycles {
+            // 報告が毎回同じになるよう、名前が最小の要素から始める
+            cycle.pop();
+            let start = cycle.iter().enumerate().min_by_key(|(_, name)| name.as_str()).map(|(i, _)| i).unwrap_or(0);
+            cycle.rotate_left(start);
+            cycle.push(cycle[0].clone());
+
+            let mut path = cycle[0].clone();
+            for pair in cycle.windows(2) {
+                let kind = edge_kinds.get(&(pair[0].clone(), pair[1].clone())).map(String::as_str).unwrap_or("embeds");
+                path.push_str(&format!(" -[{}]-> {}", kind, pair[1]));
+            }
+
+            let file_path = project.components.iter()
+                .find(|c| c.name == cycle[0])
+                .map(|c| c.file_path.clone())
+                .unwrap_or_else(|| project.root_path.display().to_string());
+            issues.push(Issue {
+                severity: Severity::Warning,
+                rule: "recursive-component-composition".to_string(),
+                message: format!(
+                    "Components render each other recursively through their templates: {}. Make sure the recursion terminates or break the cycle.",
+                    path
+                ),
+                file_path,
+                line: None,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        issues
+    }
+
+    // ワークスペースのいずれかの package.json が直接依存するライブラリが、複数のメジャーバージョンでインストールされていないか
+    fn analyze_duplicate_versions(&self, project: &NgProject) -> Vec<Issue> {
+        let Some(workspace) = &project.workspace_packages else {
+            return Vec::new();
+        };
+        let Some(lockfile) = &workspace.lockfile else {
+            return Vec::new();
+        };
+
+        // 推移的な依存だけの重複はほとんど無害なので対象にしない
+        let declared: BTreeSet<&str> = workspace.manifests.iter()
+            .flat_map(|manifest| manifest.dependencies.iter().map(|d| d.name.as_str()))
+            .filter(|name| !self.ignored_packages.iter().any(|ignored| ignored == name))
+            .collect();
+
+        let mut issues = Vec::new();
+        for name in declared {
+            let mut majors: BTreeMap<(u64, u64), Vec<&InstalledPackage>> = BTreeMap::new();
+            for package in workspace.installed.iter().filter(|p| p.name == name) {
+                if let Some(major) = major_version(&package.version) {
+                    majors.entry(major).or_default().push(package);
+                }
+            }
+            if majors.len() < 2 {
+                continue;
+            }
+
+            let groups: Vec<String> = majors.iter()
+                .map(|(major, packages)| {
+                    let mut versions: Vec<&str> = packages.iter().map(|p| p.version.as_str()).collect();
+                    versions.sort();
+                    versions.dedup();
+
+                    let importers: Vec<&str> = workspace.manifests.iter()
+                        .filter(|manifest| manifest.dependencies.iter().any(|d| {
+                            d.name == name && d.resolved.as_deref().and_then(major_version) == Some(*major)
+                        }))
+                        .map(|manifest| manifest.file_path.as_str())
+                        .collect();
+                    let used_by = if importers.is_empty() {
+                        format!("transitive via {}", packages[0].location)
+                    } else {
+                        format!("used by {}", importers.join(", "))
+                    };
+                    format!("{} ({})", versions.join(", "), used_by)
+                })
+                .collect();
+
+            issues.push(Issue {
+                severity: Severity::Warning,
+                rule: "duplicate-dependency-version".to_string(),
+                message: format!(
+                    "'{}' is installed in {} major versions: {}. Align the workspace on a single major version.",
+                    name,
+                    majors.len(),
+                    groups.join("; ")
+                ),
+                file_path: workspace.root_path.join(lockfile).display().to_string().replace('\\', "/"),
+                line: None,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        issues
+    }
+
+    // forRoot() はルートモジュールから一度だけ import するもので、機能モジュールや遅延読み込みモジュールで呼ぶとプロバイダーが二重に作られる
+    fn analyze_for_root_imports(&self, project: &NgProject) -> Vec<Issue> {
+        let misplaced = feature_modules(project);
+        let mut issues = Vec::new();
+        for module in &project.modules {
+            let Some(kind) = misplaced.get(module.name.as_str()) else { continue };
+            for import in &module.for_root_imports {
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    rule: "forroot-in-feature-module".to_string(),
+                    message: format!(
+                        "{} '{}' imports {}.forRoot(). forRoot() registers application-wide providers and belongs in the root module only; use forChild() or import {} without forRoot().",
+                        kind, module.name, import.module, import.module
+                    ),
+                    file_path: module.file_path.clone(),
+                    line: import.line,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        issues
+    }
+
+    // providedIn とコンポーネント・モジュールの providers から、サービスのインスタンスがどこで作られるかを見る
+    fn analyze_injection_scopes(&self, project: &NgProject) -> Vec<Issue> {
+        let features = lazy_features(project);
+        let feature_of = |file_path: &str| features.iter()
+            .filter(|(dir, _)| file_path.starts_with(&format!("{}/", dir)))
+            .max_by_key(|(dir, _)| dir.len())
+            .map(|(dir, name)| (dir.as_str(), name.as_str()));
+        let mut issues = Vec::new();
+
+        for service in &project.services {
+            let consumers: Vec<(&str, &str)> = project.components.iter()
+                .map(|component| (component.name.as_str(), component.file_path.as_str(), &component.dependencies))
+                .chain(project.services.iter().map(|other| (other.name.as_str(), other.file_path.as_str(), &other.dependencies)))
+                .filter(|(_, _, dependencies)| dependencies.contains(&service.name))
+                .map(|(name, file_path, _)| (name, file_path))
+                .collect();
+            let module_providers: Vec<&str> = project.modules.iter()
+                .filter(|module| module.providers.contains(&service.name))
+                .map(|module| module.name.as_str())
+                .collect();
+
+            match service.provided_in.as_deref() {
+                Some("root") if !consumers.is_empty() => {
+                    let scopes: BTreeSet<Option<(&str, &str)>> = consumers.iter().map(|(_, file_path)| feature_of(file_path)).collect();
+                    if let [Some((dir, feature))] = scopes.into_iter().collect::<Vec<_>>().as_slice() {
+                        let location = if service.file_path.starts_with(&format!("{}/", dir)) {
+                            String::new()
+                        } else {
+                            format!(" It lives outside the feature ({}), so an eager import of that file pulls it into the initial bundle.", service.file_path)
+                        };
+                        issues.push(Issue {
+                            severity: Severity::Info,
+                            rule: "root-service-single-lazy-feature".to_string(),
+                            message: format!(
+                                "{} is provided in root but only injected from the lazy feature '{}' ({}).{} Provide it in that route's `providers` so it is created and released with the feature.",
+                                service.name,
+                                feature,
+                                consumers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", "),
+                                location
+                            ),
+                            file_path: service.file_path.clone(),
+                            line: None,
+                            column: None,
+                            fingerprint: None,
+                        });
+                    }
+                }
+                Some("any") => {
+                    issues.push(Issue {
+                        severity: Severity::Warning,
+                        rule: "provided-in-any".to_string(),
+                        message: format!(
+                            "{} uses providedIn: 'any' (deprecated since Angular 15), which creates a separate instance in every lazy-loaded injector. Use providedIn: 'root' for a singleton, or list it in the providers of the routes that need their own instance.",
+                            service.name
+                        ),
+                        file_path: service.file_path.clone(),
+                        line: None,
+                        column: None,
+                        fingerprint: None,
+                    });
+                }
+                _ => {}
+            }
+
+            // ルートやモジュールにもあるのに、コンポーネントごとに別のインスタンスを作っている
+            let shared_scope = match service.provided_in.as_deref() {
+                Some(scope @ ("root" | "platform")) => Some(format!("provided in {}", scope)),
+                _ if !module_providers.is_empty() => Some(format!("provided by {}", module_providers.join(", "))),
+                _ => None,
+            };
+            let Some(shared_scope) = shared_scope else { continue };
+            for component in project.components.iter().filter(|component| component.providers.contains(&service.name)) {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "component-provided-service".to_string(),
+                    message: format!(
+                        "{} lists {} in its providers although it is {}. Every {} instance and its children get a new {} instead of the shared one; remove it from providers unless a per-component instance is intended.",
+                        component.name, service.name, shared_scope, component.name, service.name
+                    ),
+                    file_path: component.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        issues
+    }
+
+    // ErrorHandler などはルートインジェクターに 1 つだけあることが前提なので、重複した上書きや子インジェクターでの上書きを検出する
+    fn analyze_provider_overrides(&self, project: &NgProject) -> Vec<Issue> {
+        let describe = |provider_override: &ProviderOverride| format!(
+            "{} ({}, {}:{})",
+            provider_override.implementation.as_deref().unwrap_or("?"),
+            provider_override.injector,
+            relative_issue_path(&provider_override.file_path, &project.root_path),
+            provider_override.line.unwrap_or(0)
+        );
+        let mut issues = Vec::new();
+
+        let mut root_overrides: BTreeMap<&str, Vec<&ProviderOverride>> = BTreeMap::new();
+        for provider_override in &project.provider_overrides {
+            if is_root_injector(project, provider_override) {
+                root_overrides.entry(provider_override.token.as_str()).or_default().push(provider_override);
+                continue;
+            }
+            let location = match provider_override.scope.as_str() {
+                "component" => format!("the component injector of '{}'", provider_override.injector),
+                "route" => format!("the environment injector of {}", provider_override.injector),
+                _ => format!("the lazy-loaded module '{}'", provider_override.injector),
+            };
+            issues.push(Issue {
+                severity: Severity::Warning,
+                rule: "framework-provider-override".to_string(),
+                message: format!(
+                    "{} is overridden in {} instead of the root injector. Framework services read it from the root injector, so the override is ignored or applies only to that subtree; register it once in the application config or root module.",
+                    provider_override.token, location
+                ),
+                file_path: provider_override.file_path.clone(),
+                line: provider_override.line,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        for (token, overrides) in &root_overrides {
+            if *token == "HTTP_INTERCEPTORS" {
+                // multi: true がないと、それまでに登録したインターセプターをすべて置き換える
+                for provider_override in overrides.iter().filter(|provider_override| !provider_override.multi) {
+                    issues.push(Issue {
+                        severity: Severity::Warning,
+                        rule: "http-interceptor-order".to_string(),
+                        message: format!(
+                            "HTTP_INTERCEPTORS is provided without multi: true, so {} replaces every other interceptor. Add multi: true.",
+                            provider_override.implementation.as_deref().unwrap_or("this provider")
+                        ),
+                        file_path: provider_override.file_path.clone(),
+                        line: provider_override.line,
+                        column: None,
+                        fingerprint: None,
+                    });
+                }
+                let injectors: BTreeSet<(&str, &str)> = overrides.iter().map(|o| (o.file_path.as_str(), o.injector.as_str())).collect();
+                if injectors.len() > 1 {
+                    // 同じインターセプターの重複はここでまとめて伝え、duplicate-interceptor では出さない
+                    let mut implementations: Vec<&str> = overrides.iter().filter_map(|o| o.implementation.as_deref()).collect();
+                    implementations.sort();
+                    let mut duplicated: Vec<&str> = implementations.windows(2).filter(|pair| pair[0] == pair[1]).map(|pair| pair[0]).collect();
+                    duplicated.dedup();
+                    let duplicated = if duplicated.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" Registered more than once: {}; each registration runs the interceptor again, so keep a single one.", duplicated.join(", "))
+                    };
+                    issues.push(Issue {
+                        severity: Severity::Warning,
+                        rule: "http-interceptor-order".to_string(),
+                        message: format!(
+                            "HTTP interceptors are registered in {} places of the root injector: {}. They run in provider order, which follows module import order; register them together in one providers array so the order is explicit.{}",
+                            injectors.len(),
+                            overrides.iter().map(|o| describe(o)).collect::<Vec<_>>().join(", "),
+                            duplicated
+                        ),
+                        file_path: overrides[0].file_path.clone(),
+                        line: overrides[0].line,
+                        column: None,
+                        fingerprint: None,
+                    });
+                }
+                continue;
+            }
+
+            if overrides.len() > 1 {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "framework-provider-override".to_string(),
+                    message: format!(
+                        "{} is overridden {} times in the root injector: {}. Only the last registration takes effect; keep a single override.",
+                        token,
+                        overrides.len(),
+                        overrides.iter().map(|o| describe(o)).collect::<Vec<_>>().join(", ")
+                    ),
+                    file_path: overrides[0].file_path.clone(),
+                    line: overrides[0].line,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        issues
+    }
+
+    // 同じサービス・forRoot()・インターセプターを複数のインジェクターや複数の場所で登録しているもの
+    fn analyze_duplicate_providers(&self, project: &NgProject) -> Vec<Issue> {
+        let location = |file_path: &str, line: Option<u32>| match line {
+            Some(line) => format!("{}:{}", relative_issue_path(file_path, &project.root_path), line),
+            None => relative_issue_path(file_path, &project.root_path),
+        };
+        let describe = |registration: &ProviderOverride| format!("{} ({})", registration.injector, location(&registration.file_path, registration.line));
+        let mut issues = Vec::new();
+
+        for service in &project.services {
+            let registrations: Vec<&ProviderOverride> = project.provider_registrations.iter()
+                .filter(|registration| registration.token == service.name && !registration.multi)
+                .collect();
+            let mut root: Vec<(String, &str, Option<u32>)> = Vec::new();
+            if let Some(scope @ ("root" | "platform")) = service.provided_in.as_deref() {
+                root.push((format!("providedIn: '{}' ({})", scope, location(&service.file_path, None)), service.file_path.as_str(), None));
+            }
+            root.extend(registrations.iter()
+                .filter(|registration| registration.scope != "component" && is_root_injector(project, registration))
+                .map(|registration| (describe(registration), registration.file_path.as_str(), registration.line)));
+            let features: Vec<&ProviderOverride> = registrations.iter()
+                .filter(|registration| registration.scope != "component" && !is_root_injector(project, registration))
+                .copied()
+                .collect();
+            let components: Vec<&str> = registrations.iter()
+                .filter(|registration| registration.scope == "component")
+                .map(|registration| registration.injector.as_str())
+                .collect();
+
+            let (message, file_path, line) = match (root.as_slice(), features.first()) {
+                ([_, ..], Some(feature)) => (
+                    format!(
+                        "{} is provided in the root injector ({}) and again in {}. Code in those features gets a separate instance, so state kept in the root instance is not shared with them; remove the feature registration or stop providing it in root.",
+                        service.name,
+                        root.iter().map(|(description, ..)| description.as_str()).collect::<Vec<_>>().join(", "),
+                        features.iter().map(|feature| describe(feature)).collect::<Vec<_>>().join(", ")
+                    ),
+                    feature.file_path.as_str(),
+                    feature.line,
+                ),
+                ([_, (_, file_path, line), ..], None) => (
+                    format!(
+                        "{} is registered {} times in the root injector: {}. Only one instance is created, so the extra registrations are redundant and make it unclear which configuration applies; keep a single registration.",
+                        service.name,
+                        root.len(),
+                        root.iter().map(|(description, ..)| description.as_str()).collect::<Vec<_>>().join(", ")
+                    ),
+                    *file_path,
+                    *line,
+                ),
+                _ => continue,
+            };
+            let components = if components.is_empty() {
+                String::new()
+            } else {
+                format!(" It is also provided by {}.", components.join(", "))
+            };
+            issues.push(Issue {
+                severity: Severity::Warning,
+                rule: "duplicate-provider".to_string(),
+                message: format!("{}{}", message, components),
+                file_path: file_path.to_string(),
+                line,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        // 機能モジュール・遅延読み込みモジュールからの import は forroot-in-feature-module が報告する
+        let misplaced = feature_modules(project);
+        let mut for_root: BTreeMap<&str, Vec<(&NgModule, Option<u32>)>> = BTreeMap::new();
+        for module in project.modules.iter().filter(|module| !misplaced.contains_key(module.name.as_str())) {
+            for import in &module.for_root_imports {
+                for_root.entry(import.module.as_str()).or_default().push((module, import.line));
+            }
+        }
+        for (imported, importers) in for_root.iter().filter(|(_, importers)| importers.len() > 1) {
+            // 正しい import は bootstrap を持つモジュールの分なので、それ以外を指す
+            let (module, line) = importers.iter().find(|(module, _)| module.bootstrap.is_empty()).copied().unwrap_or(importers[1]);
+            issues.push(Issue {
+                severity: Severity::Error,
+                rule: "duplicate-forroot-import".to_string(),
+                message: format!(
+                    "{}.forRoot() is imported {} times: {}. Each import registers its root providers again, creating new instances that replace the configured ones; import it once in the root module and use forChild() or the plain module elsewhere.",
+                    imported,
+                    importers.len(),
+                    importers.iter().map(|(module, line)| format!("{} ({})", module.name, location(&module.file_path, *line))).collect::<Vec<_>>().join(", ")
+                ),
+                file_path: module.file_path.clone(),
+                line,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        // ルートインジェクターの複数の場所にある { provide: HTTP_INTERCEPTORS } の重複は http-interceptor-order が報告する
+        let ordered = scattered_root_interceptors(project);
+        let reported_by_order = |registration: &ProviderOverride| ordered.iter()
+            .any(|o| o.file_path == registration.file_path && o.line == registration.line);
+        let mut interceptors: BTreeMap<&str, Vec<&ProviderOverride>> = BTreeMap::new();
+        for registration in project.provider_registrations.iter().filter(|registration| registration.token == "HTTP_INTERCEPTORS") {
+            if let Some(implementation) = registration.implementation.as_deref().filter(|implementation| *implementation != "factory") {
+                interceptors.entry(implementation).or_default().push(registration);
+            }
+        }
+        for (interceptor, registrations) in interceptors.iter().filter(|(_, registrations)| registrations.len() > 1) {
+            let Some(anchor) = registrations.iter().find(|registration| !reported_by_order(registration)) else { continue };
+            issues.push(Issue {
+                severity: Severity::Warning,
+                rule: "duplicate-interceptor".to_string(),
+                message: format!(
+                    "{} is registered as an HTTP interceptor {} times: {}. It runs once per registration for every request (adding headers or retrying twice, for example); keep a single registration.",
+                    interceptor,
+                    registrations.len(),
+                    registrations.iter().map(|registration| describe(registration)).collect::<Vec<_>>().join(", ")
+                ),
+                file_path: anchor.file_path.clone(),
+                line: anchor.line,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        issues
+    }
+
+    fn analyze_module_cycles(&self, project: &NgProject) -> Vec<Issue> {
+        let graph = build_module_graph(project);
+
+        graph.cycles.iter()
+            .map(|cycle| {
+                let module = project.modules.iter().find(|module| module.name == cycle[0]);
+                Issue {
+                    severity: Severity::Error,
+                    rule: "circular-module-import".to_string(),
+                    message: format!(
+                        "Circular NgModule imports: {} -> {}. Angular cannot compile modules that import each other; move the shared declarations into a separate module imported by both.",
+                        cycle.join(" -> "),
+                        cycle[0]
+                    ),
+                    file_path: module.map(|module| module.file_path.clone()).unwrap_or_default(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                }
+            })
+            .collect()
+    }
+
     fn analyze_unused_dependencies(&self, project: &NgProject) -> Vec<Issue> {
         let mut issues = Vec::new();
         let mut all_dependencies = HashSet::new();
@@ -132,6 +678,7 @@ impl DependencyAnalyzer {
                     file_path: project.root_path.display().to_string().replace('\\', "/"),
                     line: None,
                     column: None,
+                    fingerprint: None,
                 });
             }
         }
@@ -139,6 +686,40 @@ impl DependencyAnalyzer {
         issues
     }
 
+    // 同じクラスでコンストラクタ引数と inject() の両方を使っているもの。preferred_injection 側へ寄せるよう案内する
+    fn analyze_injection_styles(&self, project: &NgProject) -> Vec<Issue> {
+        let classes = project.components.iter()
+            .map(|component| (&component.name, &component.file_path, &component.injection))
+            .chain(project.services.iter().map(|service| (&service.name, &service.file_path, &service.injection)));
+
+        classes
+            .filter(|(_, _, injection)| !injection.constructor.is_empty() && !injection.inject.is_empty())
+            .map(|(name, file_path, injection)| {
+                let (moved, target) = if self.preferred_injection == "constructor" {
+                    (&injection.inject, "constructor parameters")
+                } else {
+                    (&injection.constructor, "inject() fields")
+                };
+                Issue {
+                    severity: Severity::Warning,
+                    rule: "inconsistent-injection-style".to_string(),
+                    message: format!(
+                        "{} injects {} through the constructor and {} with inject(). Move {} to {} so the class uses one style.",
+                        name,
+                        injection.constructor.join(", "),
+                        injection.inject.join(", "),
+                        moved.join(", "),
+                        target
+                    ),
+                    file_path: file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                }
+            })
+            .collect()
+    }
+
     fn analyze_dependency_depth(&self, project: &NgProject) -> Vec<Issue> {
         let mut issues = Vec::new();
         let max_depth = 5;
@@ -156,6 +737,7 @@ impl DependencyAnalyzer {
                     file_path: component.file_path.clone(),
                     line: None,
                     column: None,
+                    fingerprint: None,
                 });
             }
         }
@@ -163,6 +745,164 @@ impl DependencyAnalyzer {
         issues
     }
 
+    // DI の名前ではなく実際のファイルの import で深さを測る。循環は 1 段として数える
+    fn analyze_import_depth(&self, project: &NgProject) -> Vec<Issue> {
+        let Some(graph) = &project.import_graph else {
+            return Vec::new();
+        };
+
+        longest_import_chains(graph, usize::MAX).into_iter()
+            .take_while(|chain| chain.depth > self.max_import_depth)
+            .take(self.max_import_chains)
+            .map(|chain| Issue {
+                severity: Severity::Warning,
+                rule: "deep-import-chain".to_string(),
+                message: format!(
+                    "Import chain is {} files deep (budget {}): {}",
+                    chain.depth,
+                    self.max_import_depth,
+                    chain.path.join(" -> ")
+                ),
+                file_path: chain.file_path,
+                line: None,
+                column: None,
+                fingerprint: None,
+            })
+            .collect()
+    }
+
+    // バレル（index.ts / public-api.ts）の export * を、実際にバレル経由で使われているシンボルとあわせて報告する
+    fn analyze_wildcard_reexports(&self, project: &NgProject) -> Vec<Issue> {
+        let Some(graph) = &project.import_graph else {
+            return Vec::new();
+        };
+        let module_paths = module_path_index(graph);
+        let mut issues = Vec::new();
+
+        for export in graph.exports.iter().filter(|export| export.symbol_name == "*") {
+            let Some(source_module) = &export.source_module else { continue };
+            let is_barrel = Path::new(&export.file_path).file_stem()
+                .is_some_and(|stem| ["index", "public-api", "public_api"].contains(&stem.to_string_lossy().as_ref()));
+            if !is_barrel {
+                continue;
+            }
+
+            let barrel_id = graph.files.iter().find(|file| file.file_path == export.file_path).map(|file| file.id.as_str());
+            let available = resolve_module(&module_paths, &export.file_path, source_module)
+                .map(|target| exported_symbols(graph, &module_paths, &target, &mut HashSet::new()))
+                .unwrap_or_default();
+            let consumed: BTreeSet<&str> = graph.dependencies.iter()
+                .filter(|dependency| Some(dependency.to_file.as_str()) == barrel_id)
+                .flat_map(|dependency| &dependency.imported_symbols)
+                .filter(|symbol| available.contains(symbol.as_str()))
+                .map(String::as_str)
+                .collect();
+
+            let suggestion = if consumed.is_empty() {
+                "Nothing from it is imported through this barrel; re-export only the intended public API by name.".to_string()
+            } else {
+                format!(
+                    "Symbols imported through this barrel: {}. Replace it with `export {{ {} }} from '{}';`",
+                    consumed.iter().copied().collect::<Vec<_>>().join(", "),
+                    consumed.iter().copied().collect::<Vec<_>>().join(", "),
+                    source_module
+                )
+            };
+            issues.push(Issue {
+                severity: Severity::Warning,
+                rule: "wildcard-reexport".to_string(),
+                message: format!(
+                    "Barrel re-exports everything from '{}' with `export *`, which hides the public API and hurts tree-shaking. {}",
+                    source_module, suggestion
+                ),
+                file_path: export.file_path.clone(),
+                line: export.line_number,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        issues
+    }
+
+    // どこからも import されていない export をファイルごとにまとめる。ディレクトリごとの件数も返す
+    fn analyze_unused_exports(&self, project: &NgProject) -> Result<(Vec<Issue>, BTreeMap<String, usize>)> {
+        let Some(graph) = &project.import_graph else {
+            return Ok((Vec::new(), BTreeMap::new()));
+        };
+        let mut by_file: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for unused in find_unused_exports(graph, &build_glob_set(&self.ignored_exports)?)? {
+            by_file.entry(unused.file_path).or_default().push(unused.symbol);
+        }
+
+        let mut by_directory: BTreeMap<String, usize> = BTreeMap::new();
+        for (file_path, symbols) in &by_file {
+            let relative = relative_issue_path(file_path, &project.root_path);
+            let directory = Path::new(&relative).parent().map(|dir| dir.display().to_string()).filter(|dir| !dir.is_empty());
+            *by_directory.entry(directory.unwrap_or_else(|| ".".to_string())).or_default() += symbols.len();
+        }
+
+        let issues = by_file.into_iter()
+            .map(|(file_path, symbols)| Issue {
+                severity: Severity::Info,
+                rule: "unused-export".to_string(),
+                message: format!(
+                    "Exported but never imported anywhere: {}. Remove the export (or the dead code), or add it to `ignore` if it is public library API.",
+                    symbols.join(", ")
+                ),
+                file_path,
+                line: None,
+                column: None,
+                fingerprint: None,
+            })
+            .collect();
+        Ok((issues, by_directory))
+    }
+
+    // 本番コードがバレル経由でモックやテスト用ユーティリティを import していないか
+    fn analyze_testing_leaks(&self, project: &NgProject) -> Vec<Issue> {
+        let Some(graph) = &project.import_graph else {
+            return Vec::new();
+        };
+        let module_paths = module_path_index(graph);
+        let file_paths: HashMap<&str, &str> = graph.files.iter().map(|file| (file.id.as_str(), file.file_path.as_str())).collect();
+
+        // (import 元, import 先の指定) ごとに、テスト用のファイルから来るシンボルをまとめる
+        let mut leaks: BTreeMap<(&str, &str), BTreeMap<&str, String>> = BTreeMap::new();
+        for import in &graph.imports {
+            if is_testing_file(&import.file_path) || !matches!(import.import_type, ImportType::Named | ImportType::Default) {
+                continue;
+            }
+            let Some(target) = resolve_module(&module_paths, &import.file_path, &import.source_module) else { continue };
+            let Some(target_path) = file_paths.get(target.as_str()) else { continue };
+            if is_testing_file(target_path) {
+                continue;
+            }
+            let Some(origin) = symbol_origin(graph, &module_paths, &target, &import.symbol_name, &mut HashSet::new()) else { continue };
+            if is_testing_file(&origin) {
+                leaks.entry((import.file_path.as_str(), import.source_module.as_str()))
+                    .or_default()
+                    .insert(import.symbol_name.as_str(), origin);
+            }
+        }
+
+        leaks.into_iter()
+            .map(|((file_path, source_module), symbols)| Issue {
+                severity: Severity::Warning,
+                rule: "testing-code-in-production".to_string(),
+                message: format!(
+                    "Imports test code through the barrel '{}': {}. Mocks and testing utilities end up in the production bundle; import them only from spec files and stop re-exporting them from the barrel.",
+                    source_module,
+                    symbols.iter().map(|(symbol, origin)| format!("{} (from {})", symbol, origin)).collect::<Vec<_>>().join(", ")
+                ),
+                file_path: file_path.to_string(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            })
+            .collect()
+    }
+
     fn calculate_dependency_depth(&self, name: &str, project: &NgProject, visited: &mut HashSet<String>) -> u32 {
         if visited.contains(name) {
             return 0;
@@ -190,6 +930,113 @@ impl DependencyAnalyzer {
         max_depth + 1
     }
 
+    fn analyze_constructor_chains(&self, project: &NgProject) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        let services: HashMap<&str, &NgService> = project.services.iter()
+            .map(|service| (service.name.as_str(), service))
+            .collect();
+
+        for component in &project.components {
+            // 共通の依存は 1 回だけ生成されるので、コンポーネントごとに訪問済みを共有して数える
+            let mut visited = HashSet::new();
+            let mut working = Vec::new();
+            for dep in &component.dependencies {
+                collect_constructor_work(dep, &services, &mut visited, &mut working);
+            }
+
+            if working.len() > self.max_constructor_services {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "constructor-initialization-chain".to_string(),
+                    message: format!(
+                        "Instantiating '{}' transitively constructs {} services that perform work in their constructors (in construction order: {}), exceeding the maximum of {}. Move initialization out of constructors or defer it.",
+                        component.name,
+                        working.len(),
+                        working.join(", "),
+                        self.max_constructor_services
+                    ),
+                    file_path: component.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        issues
+    }
+
+    fn analyze_duplicated_guards(&self, project: &NgProject) -> Vec<Issue> {
+        let guards: Vec<(&NgGuard, HashSet<&[String]>)> = project.guards.iter()
+            .filter(|guard| guard.body_tokens.len() >= MIN_GUARD_TOKENS)
+            .map(|guard| (guard, guard.body_tokens.windows(3).collect()))
+            .collect();
+
+        // 類似するガード同士を union-find でグループ化する
+        let mut parent: Vec<usize> = (0..guards.len()).collect();
+        fn find(parent: &mut [usize], i: usize) -> usize {
+            if parent[i] != i {
+                let root = find(parent, parent[i]);
+                parent[i] = root;
+            }
+            parent[i]
+        }
+
+        let mut lowest_similarity: HashMap<usize, f64> = HashMap::new();
+        for i in 0..guards.len() {
+            for j in (i + 1)..guards.len() {
+                if guards[i].0.kind != guards[j].0.kind {
+                    continue;
+                }
+                let similarity = jaccard(&guards[i].1, &guards[j].1);
+                if similarity >= GUARD_SIMILARITY_THRESHOLD {
+                    let (a, b) = (find(&mut parent, i), find(&mut parent, j));
+                    parent[b] = a;
+                    let min = lowest_similarity.get(&a).copied().unwrap_or(1.0)
+                        .min(lowest_similarity.get(&b).copied().unwrap_or(1.0))
+                        .min(similarity);
+                    lowest_similarity.insert(a, min);
+                }
+            }
+        }
+
+        let mut groups: HashMap<usize, Vec<&NgGuard>> = HashMap::new();
+        for (i, (guard, _)) in guards.iter().enumerate() {
+            let root = find(&mut parent, i);
+            groups.entry(root).or_default().push(guard);
+        }
+
+        let mut duplicates: Vec<(f64, Vec<&NgGuard>)> = groups.into_iter()
+            .filter(|(_, members)| members.len() > 1)
+            .map(|(root, members)| (lowest_similarity.get(&root).copied().unwrap_or(1.0), members))
+            .collect();
+        duplicates.sort_by(|a, b| a.1[0].file_path.cmp(&b.1[0].file_path));
+
+        duplicates.into_iter()
+            .map(|(similarity, members)| {
+                let sites: Vec<String> = members.iter()
+                    .map(|guard| format!("{} ({})", guard.name, guard.file_path))
+                    .collect();
+                Issue {
+                    severity: Severity::Warning,
+                    rule: "duplicated-guard-logic".to_string(),
+                    message: format!(
+                        "{} {}s share near-identical logic ({:.0}% similar): {}. Consolidate the checks into a shared functional {}.",
+                        members.len(),
+                        members[0].kind,
+                        similarity * 100.0,
+                        sites.join(", "),
+                        members[0].kind
+                    ),
+                    file_path: members[0].file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                }
+            })
+            .collect()
+    }
+
     fn generate_dependency_recommendations(&self, project: &NgProject) -> Vec<Recommendation> {
         let mut recommendations = Vec::new();
 
@@ -235,22 +1082,205 @@ impl DependencyAnalyzer {
     }
 }
 
-#[async_trait]
+// http-interceptor-order が「複数の場所に登録されている」として報告する、ルートインジェクターの HTTP_INTERCEPTORS
+fn scattered_root_interceptors(project: &NgProject) -> Vec<&ProviderOverride> {
+    let overrides: Vec<&ProviderOverride> = project.provider_overrides.iter()
+        .filter(|o| o.token == "HTTP_INTERCEPTORS" && is_root_injector(project, o))
+        .collect();
+    let injectors: BTreeSet<(&str, &str)> = overrides.iter().map(|o| (o.file_path.as_str(), o.injector.as_str())).collect();
+    if injectors.len() > 1 {
+        overrides
+    } else {
+        Vec::new()
+    }
+}
+
+// forRoot() を import してはいけない遅延読み込みモジュールと、ルートモジュールから辿れない機能モジュール（モジュール名 -> 種類）
+fn feature_modules(project: &NgProject) -> HashMap<&str, &'static str> {
+    let is_lazy = |module: &NgModule| project.lazy_routes.iter().any(|route| lazy_route_matches(route, module));
+
+    // bootstrap を持つモジュールから import で辿れるものはアプリ起動時に読み込まれる
+    let mut eager: HashSet<&str> = HashSet::new();
+    let mut pending: Vec<&str> = project.modules.iter()
+        .filter(|module| !module.bootstrap.is_empty())
+        .map(|module| module.name.as_str())
+        .collect();
+    while let Some(name) = pending.pop() {
+        if !eager.insert(name) {
+            continue;
+        }
+        if let Some(module) = project.modules.iter().find(|m| m.name == name && !is_lazy(m)) {
+            pending.extend(module.imports.iter().map(String::as_str));
+        }
+    }
+
+    project.modules.iter()
+        .filter_map(|module| {
+            let kind = if is_lazy(module) {
+                "Lazy-loaded module"
+            } else if !eager.is_empty() && !eager.contains(module.name.as_str()) {
+                "Feature module"
+            } else {
+                return None;
+            };
+            Some((module.name.as_str(), kind))
+        })
+        .collect()
+}
+
+// 遅延読み込みされるルートの対象ファイルのディレクトリと機能名。ルート定義と同じディレクトリを指すものは除く
+fn lazy_features(project: &NgProject) -> Vec<(String, String)> {
+    let mut features: Vec<(String, String)> = Vec::new();
+    for route in &project.lazy_routes {
+        let Some(dir) = lazy_feature_dir(route) else { continue };
+        let name = route.module.clone().unwrap_or_else(|| dir.rsplit('/').next().unwrap_or(dir).to_string());
+        if !features.iter().any(|(existing, _)| existing == dir) {
+            features.push((dir.to_string(), name));
+        }
+    }
+    features
+}
+
+fn jaccard<T: Eq + std::hash::Hash>(a: &HashSet<T>, b: &HashSet<T>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+// 0.x はマイナーバージョンが破壊的変更を表すので、メジャーと合わせて区別する
+fn major_version(version: &str) -> Option<(u64, u64)> {
+    let mut parts = version.trim_start_matches(|c: char| !c.is_ascii_digit()).split(['.', '-', '+']);
+    let major = parts.next()?.parse().ok()?;
+    let minor = if major == 0 { parts.next().and_then(|m| m.parse().ok()).unwrap_or(0) } else { 0 };
+    Some((major, minor))
+}
+
+// ファイルが export するシンボル。export * の先もたどる
+fn exported_symbols(
+    graph: &ImportExportGraph,
+    module_paths: &HashMap<String, String>,
+    file_id: &str,
+    visited: &mut HashSet<String>,
+) -> BTreeSet<String> {
+    let mut symbols = BTreeSet::new();
+    if !visited.insert(file_id.to_string()) {
+        return symbols;
+    }
+    let Some(file) = graph.files.iter().find(|file| file.id == file_id) else {
+        return symbols;
+    };
+
+    for export in graph.exports.iter().filter(|export| export.file_path == file.file_path) {
+        if export.symbol_name != "*" {
+            symbols.insert(export.symbol_name.clone());
+            continue;
+        }
+        let Some(target) = export.source_module.as_deref().and_then(|source| resolve_module(module_paths, &file.file_path, source)) else { continue };
+        symbols.extend(exported_symbols(graph, module_paths, &target, visited));
+    }
+
+    symbols
+}
+
+// *.mock.ts や testing/ 配下など、テストからだけ使われるはずのファイル
+fn is_testing_file(file_path: &str) -> bool {
+    let path = Path::new(file_path);
+    let in_testing_dir = path.parent().is_some_and(|parent| {
+        parent.components().any(|component| ["testing", "mocks", "__mocks__"].contains(&component.as_os_str().to_string_lossy().as_ref()))
+    });
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_default();
+    let has_testing_suffix = [".mock", ".mocks", ".stub", ".stubs", ".fake", ".spec", ".test"].iter().any(|suffix| stem.ends_with(suffix));
+    in_testing_dir || has_testing_suffix || ["test", "test-setup", "setup-jest"].contains(&stem.as_str())
+}
+
+// 再エクスポートをたどって、シンボルを定義しているファイルを返す
+fn symbol_origin(
+    graph: &ImportExportGraph,
+    module_paths: &HashMap<String, String>,
+    file_id: &str,
+    symbol: &str,
+    visited: &mut HashSet<String>,
+) -> Option<String> {
+    if !visited.insert(file_id.to_string()) {
+        return None;
+    }
+    let file = graph.files.iter().find(|file| file.id == file_id)?;
+
+    for export in graph.exports.iter().filter(|export| export.file_path == file.file_path) {
+        if export.symbol_name != symbol && export.symbol_name != "*" {
+            continue;
+        }
+        let Some(source) = export.source_module.as_deref() else {
+            return Some(file.file_path.clone());
+        };
+        let Some(target) = resolve_module(module_paths, &file.file_path, source) else { continue };
+        if let Some(origin) = symbol_origin(graph, module_paths, &target, symbol, visited) {
+            return Some(origin);
+        }
+    }
+
+    None
+}
+
 impl Analyzer for DependencyAnalyzer {
-    async fn analyze(&self, project: &NgProject) -> Result<AnalysisResult> {
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
         let mut all_issues = Vec::new();
 
         all_issues.extend(self.analyze_circular_dependencies(project));
         all_issues.extend(self.analyze_unused_dependencies(project));
+        all_issues.extend(self.analyze_injection_styles(project));
         all_issues.extend(self.analyze_dependency_depth(project));
+        all_issues.extend(self.analyze_import_depth(project));
+        all_issues.extend(self.analyze_wildcard_reexports(project));
+        all_issues.extend(self.analyze_testing_leaks(project));
+        all_issues.extend(self.analyze_constructor_chains(project));
+        all_issues.extend(self.analyze_duplicated_guards(project));
+        all_issues.extend(self.analyze_composition_cycles(project));
+        all_issues.extend(self.analyze_duplicate_versions(project));
+        all_issues.extend(self.analyze_for_root_imports(project));
+        all_issues.extend(self.analyze_module_cycles(project));
+        all_issues.extend(self.analyze_injection_scopes(project));
+        all_issues.extend(self.analyze_duplicate_providers(project));
+        let (unused_export_issues, unused_exports_by_directory) = self.analyze_unused_exports(project)?;
+        all_issues.extend(unused_export_issues);
+        let provider_issues = self.analyze_provider_overrides(project);
+        let has_provider_issues = !provider_issues.is_empty();
+        all_issues.extend(provider_issues);
 
-        let recommendations = self.generate_dependency_recommendations(project);
+        let mut recommendations = self.generate_dependency_recommendations(project);
+        if has_provider_issues {
+            recommendations.push(Recommendation {
+                category: "Dependency Management".to_string(),
+                title: "Consolidate Framework Provider Overrides".to_string(),
+                description: "Framework tokens such as ErrorHandler, RouteReuseStrategy and HTTP_INTERCEPTORS are overridden in several places. Keep one registration per token in the application config or root module; `ng-analyzer graph --kind injection` lists every override.".to_string(),
+                priority: Priority::Medium,
+                file_path: None,
+            });
+        }
+
+        if !unused_exports_by_directory.is_empty() {
+            let mut directories: Vec<(&String, &usize)> = unused_exports_by_directory.iter().collect();
+            directories.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            recommendations.push(Recommendation {
+                category: "Dependency Management".to_string(),
+                title: "Remove Unused Exports".to_string(),
+                description: format!(
+                    "Exported symbols that nothing imports make the public API look larger than it is. Most unused exports by directory: {}",
+                    directories.iter().take(5).map(|(directory, count)| format!("{} ({})", directory, count)).collect::<Vec<_>>().join(", ")
+                ),
+                priority: Priority::Low,
+                file_path: None,
+            });
+        }
 
         Ok(AnalysisResult {
             project: project.clone(),
             issues: all_issues,
             metrics: ProjectMetrics::default(),
             recommendations,
+            omitted_issues: vec![],
         })
     }
 
@@ -261,4 +1291,492 @@ impl Analyzer for DependencyAnalyzer {
     fn description(&self) -> &'static str {
         "Analyzes dependency relationships, circular dependencies, and architectural patterns"
     }
-}
\ No newline at end of file
+}
+
+// `name` から辿れる依存のうち、コンストラクタで処理を行うサービスを生成される順 (依存の深い方から) に集める
+fn collect_constructor_work<'a>(
+    name: &'a str,
+    services: &HashMap<&str, &'a NgService>,
+    visited: &mut HashSet<&'a str>,
+    working: &mut Vec<&'a str>,
+) {
+    let Some(service) = services.get(name) else { return };
+    if !visited.insert(service.name.as_str()) {
+        return;
+    }
+    for dep in &service.dependencies {
+        collect_constructor_work(dep, services, visited, working);
+    }
+    if service.constructor_statements > 0 {
+        working.push(service.name.as_str());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::dependency_graph::DependencyGraphAnalyzer;
+    use crate::ast::*;
+    use crate::ast::builder::{ComponentBuilder, NgProjectBuilder, ServiceBuilder};
+    use crate::parsers::ProjectParser;
+    use std::path::PathBuf;
+
+    fn service(name: &str, dependencies: &[&str], constructor_statements: u32) -> NgService {
+        ServiceBuilder::new(name)
+            .dependencies(dependencies)
+            .constructor_statements(constructor_statements)
+            .build()
+    }
+
+    #[test]
+    fn test_inconsistent_injection_style() {
+        let mut mixed = service("OrderService", &["HttpClient", "Store"], 0);
+        mixed.injection.constructor = vec!["HttpClient".to_string()];
+        mixed.injection.inject = vec!["Store".to_string()];
+        let mut consistent = service("CartService", &["Store"], 0);
+        consistent.injection.inject = vec!["Store".to_string()];
+        let project = NgProject {
+            services: vec![mixed, consistent],
+            ..Default::default()
+        };
+
+        let issues = DependencyAnalyzer::new().analyze_injection_styles(&project);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].file_path, "order.service.ts");
+        assert!(issues[0].message.ends_with("Move HttpClient to inject() fields so the class uses one style."));
+
+        let issues = DependencyAnalyzer::new().with_preferred_injection("constructor".to_string()).analyze_injection_styles(&project);
+        assert!(issues[0].message.ends_with("Move Store to constructor parameters so the class uses one style."));
+    }
+
+    #[test]
+    fn test_constructor_chain_detection() {
+        let analyzer = DependencyAnalyzer::new().with_max_constructor_services(2);
+
+        let component = ComponentBuilder::new("DashboardComponent")
+            .template_url("dashboard.component.html")
+            .dependencies(&["ReportService"])
+            .on_push()
+            .build();
+
+        let project = NgProject {
+            root_path: PathBuf::from("."),
+            components: vec![component],
+            services: vec![
+                service("ReportService", &["ApiService"], 2),
+                service("ApiService", &["ConfigService"], 1),
+                service("ConfigService", &["LoggerService"], 3),
+                service("LoggerService", &[], 0),
+            ],
+            ..Default::default()
+        };
+
+        let issues = analyzer.analyze_constructor_chains(&project);
+
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("constructs 3 services"));
+        assert!(issues[0].message.contains("(in construction order: ConfigService, ApiService, ReportService)"));
+
+        // 1 本の経路は短くても、枝分かれした依存を合わせて数える。共通の依存は 1 回だけ
+        let project = NgProject {
+            components: vec![ComponentBuilder::new("SettingsComponent").dependencies(&["ProfileService", "ThemeService"]).build()],
+            services: vec![
+                service("ProfileService", &["ConfigService"], 1),
+                service("ThemeService", &["ConfigService"], 1),
+                service("ConfigService", &[], 1),
+            ],
+            ..Default::default()
+        };
+        let issues = analyzer.analyze_constructor_chains(&project);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("(in construction order: ConfigService, ProfileService, ThemeService)"));
+    }
+
+    #[test]
+    fn test_duplicated_guard_detection() {
+        let parser = crate::parsers::typescript::TypeScriptParser::new();
+        let sources = [
+            ("admin.guard.ts", "export const adminGuard: CanActivateFn = (route, state) => {\n  const auth = inject(AuthService);\n  // 管理者のみ\n  return auth.isLoggedIn() && auth.hasRole('admin') ? true : inject(Router).parseUrl('/login');\n};"),
+            ("staff.guard.ts", "export function staffGuard(r: ActivatedRouteSnapshot, s: RouterStateSnapshot) {\n  const auth = inject(AuthService);\n  return auth.isLoggedIn() && auth.hasRole('admin') ? true : inject(Router).parseUrl('/login');\n}"),
+            ("feature.guard.ts", "export const featureGuard = () => inject(FeatureFlags).isEnabled('beta') || inject(Router).parseUrl('/');"),
+        ];
+
+        let guards = sources.iter()
+            .flat_map(|(path, content)| {
+                let module = parser.parse_file(content).unwrap();
+                parser.extract_guards(&module, content, std::path::Path::new(path))
+            })
+            .collect();
+        let project = NgProject {
+            root_path: PathBuf::from("."),
+            guards,
+            ..Default::default()
+        };
+
+        let issues = DependencyAnalyzer::new().analyze_duplicated_guards(&project);
+
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("adminGuard (admin.guard.ts), staffGuard (staff.guard.ts)"));
+    }
+
+    #[test]
+    fn test_composition_cycle_detection() {
+        let analyzer = DependencyAnalyzer::new();
+
+        let component = |name: &str, selector: &str, usages: &[(&str, &str)]| usages.iter()
+            .fold(ComponentBuilder::new(name), |builder, (tag, kind)| builder.uses(tag, kind, 1))
+            .selector(selector)
+            .on_push()
+            .build();
+
+        let project = NgProject {
+            root_path: PathBuf::from("."),
+            components: vec![
+                component("TreeComponent", "app-tree", &[("app-tree", "embeds"), ("app-card", "embeds"), ("app-panel", "projected")]),
+                component("PanelComponent", "app-panel", &[("app-tree", "template")]),
+                component("CardComponent", "app-card", &[]),
+            ],
+            ..Default::default()
+        };
+
+        let issues = analyzer.analyze_composition_cycles(&project);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].file_path, "panel.component.ts");
+        assert!(issues[0].message.contains("PanelComponent -[template]-> TreeComponent -[projected]-> PanelComponent"));
+    }
+
+    #[test]
+    fn test_duplicate_dependency_versions() {
+        let analyzer = DependencyAnalyzer::new().with_ignored_packages(vec!["tslib".to_string()]);
+
+        let dependency = |name: &str, resolved: &str| DeclaredDependency {
+            name: name.to_string(),
+            range: "*".to_string(),
+            resolved: Some(resolved.to_string()),
+        };
+        let installed = |name: &str, version: &str, location: &str| InstalledPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            location: location.to_string(),
+        };
+
+        let project = NgProject {
+            root_path: PathBuf::from("apps/shop/src"),
+            workspace_packages: Some(WorkspacePackages {
+                root_path: PathBuf::from("."),
+                lockfile: Some("package-lock.json".to_string()),
+                manifests: vec![
+                    PackageManifest {
+                        file_path: "apps/legacy/package.json".to_string(),
+                        name: Some("legacy".to_string()),
+                        dependencies: vec![dependency("rxjs", "6.6.7"), dependency("tslib", "1.14.1")],
+                    },
+                    PackageManifest {
+                        file_path: "apps/shop/package.json".to_string(),
+                        name: Some("shop".to_string()),
+                        dependencies: vec![dependency("rxjs", "7.8.1"), dependency("tslib", "2.6.2")],
+                    },
+                ],
+                installed: vec![
+                    installed("rxjs", "7.8.1", "node_modules/rxjs"),
+                    installed("rxjs", "6.6.7", "apps/legacy/node_modules/rxjs"),
+                    installed("tslib", "2.6.2", "node_modules/tslib"),
+                    installed("tslib", "1.14.1", "apps/legacy/node_modules/tslib"),
+                    installed("debug", "2.6.9", "node_modules/debug"),
+                    installed("debug", "4.3.4", "node_modules/a/node_modules/debug"),
+                ],
+            }),
+            ..Default::default()
+        };
+
+        let issues = analyzer.analyze_duplicate_versions(&project);
+
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].message,
+            "'rxjs' is installed in 2 major versions: 6.6.7 (used by apps/legacy/package.json); 7.8.1 (used by apps/shop/package.json). Align the workspace on a single major version."
+        );
+        assert_eq!(issues[0].file_path, "./package-lock.json");
+        assert_eq!(major_version("0.14.2"), Some((0, 14)));
+    }
+
+    #[test]
+    fn test_for_root_in_feature_modules() {
+        let analyzer = DependencyAnalyzer::new();
+
+        let module = |name: &str, imports: &[&str], for_root: &[&str], bootstrap: bool| NgModule {
+            name: name.to_string(),
+            file_path: format!("src/app/{}.module.ts", name.trim_end_matches("Module").to_lowercase()),
+            imports: imports.iter().map(|i| i.to_string()).collect(),
+            exports: vec![],
+            declarations: vec![],
+            providers: vec![],
+            bootstrap: if bootstrap { vec!["AppComponent".to_string()] } else { vec![] },
+            for_root_imports: for_root.iter()
+                .map(|m| ForRootImport { module: m.to_string(), line: Some(4) })
+                .collect(),
+        };
+
+        let project = NgProject {
+            root_path: PathBuf::from("."),
+            modules: vec![
+                module("AppModule", &["AppRoutingModule", "CoreModule"], &[], true),
+                module("AppRoutingModule", &["RouterModule"], &["RouterModule"], false),
+                module("CoreModule", &["StoreModule"], &["StoreModule"], false),
+                module("AdminModule", &["RouterModule"], &["RouterModule"], false),
+                module("SharedModule", &["TranslateModule"], &["TranslateModule"], false),
+            ],
+            lazy_routes: vec![LazyRoute {
+                module: None,
+                target: "src/app/admin.module".to_string(),
+                file_path: "src/app/app-routing.module.ts".to_string(),
+                line: Some(3),
+            }],
+            ..Default::default()
+        };
+
+        let issues = analyzer.analyze_for_root_imports(&project);
+
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.starts_with("Lazy-loaded module 'AdminModule' imports RouterModule.forRoot()"));
+        assert!(issues[1].message.starts_with("Feature module 'SharedModule' imports TranslateModule.forRoot()"));
+        assert_eq!(issues[1].line, Some(4));
+    }
+
+    #[test]
+    fn test_provider_overrides() {
+        let provider = |token: &str, implementation: &str, multi: bool, scope: &str, injector: &str, file_path: &str| ProviderOverride {
+            token: token.to_string(),
+            implementation: Some(implementation.to_string()),
+            multi,
+            scope: scope.to_string(),
+            injector: injector.to_string(),
+            file_path: file_path.to_string(),
+            line: Some(3),
+        };
+        let module = |name: &str, file_path: &str| NgModule {
+            name: name.to_string(),
+            file_path: file_path.to_string(),
+            imports: vec![],
+            exports: vec![],
+            declarations: vec![],
+            providers: vec![],
+            bootstrap: vec![],
+            for_root_imports: vec![],
+        };
+        let project = NgProject {
+            root_path: PathBuf::from("."),
+            modules: vec![module("CoreModule", "src/app/core/core.module.ts"), module("AdminModule", "src/app/admin/admin.module.ts")],
+            lazy_routes: vec![LazyRoute {
+                module: Some("AdminModule".to_string()),
+                target: "src/app/admin/admin.module".to_string(),
+                file_path: "src/app/app.routes.ts".to_string(),
+                line: Some(2),
+            }],
+            provider_overrides: vec![
+                provider("ErrorHandler", "SentryErrorHandler", false, "application", "appConfig", "src/app/app.config.ts"),
+                provider("ErrorHandler", "GlobalErrorHandler", false, "module", "CoreModule", "src/app/core/core.module.ts"),
+                provider("RouteReuseStrategy", "AdminReuseStrategy", false, "module", "AdminModule", "src/app/admin/admin.module.ts"),
+                provider("HTTP_INTERCEPTORS", "AuthInterceptor", true, "application", "appConfig", "src/app/app.config.ts"),
+                provider("HTTP_INTERCEPTORS", "LoggingInterceptor", false, "module", "CoreModule", "src/app/core/core.module.ts"),
+            ],
+            ..Default::default()
+        };
+
+        let issues = DependencyAnalyzer::new().analyze_provider_overrides(&project);
+        let messages: Vec<(&str, &str)> = issues.iter().map(|issue| (issue.rule.as_str(), issue.message.as_str())).collect();
+        assert_eq!(messages.len(), 4);
+        assert!(messages[0].1.starts_with("RouteReuseStrategy is overridden in the lazy-loaded module 'AdminModule'"));
+        assert_eq!(messages[1].0, "framework-provider-override");
+        assert!(messages[1].1.starts_with("ErrorHandler is overridden 2 times in the root injector: SentryErrorHandler (appConfig, src/app/app.config.ts:3), GlobalErrorHandler (CoreModule, src/app/core/core.module.ts:3)."));
+        assert_eq!(messages[2], ("http-interceptor-order", "HTTP_INTERCEPTORS is provided without multi: true, so LoggingInterceptor replaces every other interceptor. Add multi: true."));
+        assert!(messages[3].1.starts_with("HTTP interceptors are registered in 2 places of the root injector"));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_providers() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("src/app");
+        std::fs::create_dir_all(app.join("admin")).unwrap();
+        std::fs::write(app.join("session.service.ts"), "@Injectable({ providedIn: 'root' })\nexport class SessionService {}\n").unwrap();
+        std::fs::write(app.join("cart.service.ts"), "@Injectable()\nexport class CartService {}\n").unwrap();
+        std::fs::write(
+            app.join("app.module.ts"),
+            "@NgModule({\n  imports: [CoreModule, StoreModule.forRoot(reducers)],\n  providers: [CartService, { provide: HTTP_INTERCEPTORS, useClass: AuthInterceptor, multi: true }],\n  bootstrap: [AppComponent],\n})\nexport class AppModule {}\n",
+        ).unwrap();
+        std::fs::write(
+            app.join("core.module.ts"),
+            "@NgModule({\n  imports: [StoreModule.forRoot(reducers)],\n  providers: [CartService, { provide: HTTP_INTERCEPTORS, useClass: AuthInterceptor, multi: true }],\n})\nexport class CoreModule {}\n",
+        ).unwrap();
+        std::fs::write(
+            app.join("app.routes.ts"),
+            "export const routes: Routes = [\n  { path: 'admin', providers: [SessionService, { provide: HTTP_INTERCEPTORS, useClass: AuthInterceptor, multi: true }], loadChildren: () => import('./admin/admin.module').then(m => m.AdminModule) },\n];\n",
+        ).unwrap();
+        // 遅延読み込みモジュールの forRoot() は forroot-in-feature-module だけが報告する
+        std::fs::write(app.join("admin/admin.module.ts"), "@NgModule({\n  imports: [StoreModule.forRoot(reducers)],\n})\nexport class AdminModule {}\n").unwrap();
+
+        let project = ProjectParser::new().parse_project(&dir.path().to_path_buf()).await.unwrap();
+        let issues = DependencyAnalyzer::new().analyze_duplicate_providers(&project);
+        let found: Vec<(&str, &str, Option<u32>)> = issues.iter()
+            .map(|issue| (issue.rule.as_str(), issue.file_path.rsplit('/').next().unwrap(), issue.line))
+            .collect();
+
+        assert_eq!(found, vec![
+            ("duplicate-provider", "core.module.ts", Some(3)),
+            ("duplicate-provider", "app.routes.ts", Some(2)),
+            ("duplicate-forroot-import", "core.module.ts", Some(2)),
+            ("duplicate-interceptor", "app.routes.ts", Some(2)),
+        ]);
+        let messages: Vec<&str> = issues.iter().map(|issue| issue.message.as_str()).collect();
+        assert!(messages[1].starts_with("SessionService is provided in the root injector (providedIn: 'root' (src/app/session.service.ts)) and again in route 'admin' (src/app/app.routes.ts:2)."));
+        assert!(messages[0].starts_with("CartService is registered 2 times in the root injector: "));
+        assert!(messages[2].starts_with("StoreModule.forRoot() is imported 2 times: "));
+        assert!(messages[3].starts_with("AuthInterceptor is registered as an HTTP interceptor 3 times: "));
+
+        // 既存のルールが報告するものは重ねて出さない
+        let analyzer = DependencyAnalyzer::new();
+        let for_root: Vec<String> = analyzer.analyze_for_root_imports(&project).into_iter().map(|issue| issue.file_path.rsplit('/').next().unwrap().to_string()).collect();
+        assert_eq!(for_root, vec!["admin.module.ts"]);
+        let order = analyzer.analyze_provider_overrides(&project);
+        assert!(order.iter().any(|issue| issue.rule == "http-interceptor-order" && issue.message.ends_with("Registered more than once: AuthInterceptor; each registration runs the interceptor again, so keep a single one.")));
+    }
+
+    #[test]
+    fn test_injection_scopes() {
+        let project = NgProjectBuilder::new(".")
+            .component(ComponentBuilder::new("OrdersComponent").file_path("src/app/orders/orders.component.ts").dependencies(&["OrdersApi", "SessionService"]))
+            .component(ComponentBuilder::new("HomeComponent").file_path("src/app/home.component.ts").dependencies(&["SessionService"])
+                .with(|component| component.providers = vec!["SessionService".to_string(), "CartService".to_string()]))
+            .service(ServiceBuilder::new("OrdersApi").file_path("src/app/core/orders-api.service.ts"))
+            .service(ServiceBuilder::new("OrdersStore").file_path("src/app/orders/orders.store.ts").dependencies(&["OrdersApi"]))
+            .service(ServiceBuilder::new("SessionService").file_path("src/app/core/session.service.ts"))
+            .service(ServiceBuilder::new("CartService").file_path("src/app/cart.service.ts").provided_in(None))
+            .service(ServiceBuilder::new("LegacyService").file_path("src/app/legacy.service.ts").provided_in(Some("any")))
+            .with(|p| p.lazy_routes = vec![
+                LazyRoute {
+                    module: None,
+                    target: "src/app/orders/orders.routes".to_string(),
+                    file_path: "src/app/app.routes.ts".to_string(),
+                    line: Some(4),
+                },
+                LazyRoute {
+                    module: None,
+                    target: "src/app/home.component".to_string(),
+                    file_path: "src/app/app.routes.ts".to_string(),
+                    line: Some(8),
+                },
+            ])
+            .build();
+
+        let issues = DependencyAnalyzer::new().analyze_injection_scopes(&project);
+        let found: Vec<(&str, &str)> = issues.iter().map(|issue| (issue.rule.as_str(), issue.file_path.as_str())).collect();
+
+        assert_eq!(found, vec![
+            ("root-service-single-lazy-feature", "src/app/core/orders-api.service.ts"),
+            ("component-provided-service", "src/app/home.component.ts"),
+            ("provided-in-any", "src/app/legacy.service.ts"),
+        ]);
+        assert!(issues[0].message.starts_with("OrdersApi is provided in root but only injected from the lazy feature 'orders' (OrdersComponent, OrdersStore). It lives outside the feature"));
+        assert!(issues[1].message.starts_with("HomeComponent lists SessionService in its providers although it is provided in root."));
+    }
+
+    #[tokio::test]
+    async fn test_wildcard_reexports() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = dir.path().join("shared");
+        std::fs::create_dir_all(shared.join("ui")).unwrap();
+        std::fs::write(shared.join("index.ts"), "export * from './format';\nexport * from './ui';\nexport { Unused } from './unused';\n").unwrap();
+        std::fs::write(shared.join("format.ts"), "export function formatDate() {}\nexport function formatMoney() {}\n").unwrap();
+        std::fs::write(shared.join("ui/index.ts"), "export * from './button';\n").unwrap();
+        std::fs::write(shared.join("ui/button.ts"), "export class Button {}\n").unwrap();
+        std::fs::write(shared.join("unused.ts"), "export class Unused {}\n").unwrap();
+        std::fs::write(dir.path().join("app.ts"), "import { formatDate } from './shared';\n").unwrap();
+
+        let root = dir.path().to_path_buf();
+        let project = NgProject {
+            root_path: root.clone(),
+            import_graph: Some(DependencyGraphAnalyzer::new().analyze_project(&root).await.unwrap()),
+            ..Default::default()
+        };
+
+        let mut issues = DependencyAnalyzer::new().analyze_wildcard_reexports(&project);
+        issues.sort_by(|a, b| (&a.file_path, &a.message).cmp(&(&b.file_path, &b.message)));
+
+        assert_eq!(issues.len(), 3);
+        assert!(issues[0].message.contains("'./format'"));
+        assert!(issues[0].message.contains("export { formatDate } from './format';"));
+        assert!(issues[1].message.contains("'./ui'"));
+        assert!(issues[1].message.contains("Nothing from it is imported"));
+        assert!(issues[2].file_path.ends_with("ui/index.ts"));
+    }
+
+    #[tokio::test]
+    async fn test_testing_leaks() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = dir.path().join("shared");
+        std::fs::create_dir_all(shared.join("testing")).unwrap();
+        std::fs::write(shared.join("index.ts"), "export * from './user.service';\nexport { MockUserService } from './testing';\nexport * from './store.mock';\n").unwrap();
+        std::fs::write(shared.join("user.service.ts"), "export class UserService {}\n").unwrap();
+        std::fs::write(shared.join("testing/index.ts"), "export * from './user.service.mock';\n").unwrap();
+        std::fs::write(shared.join("testing/user.service.mock.ts"), "export class MockUserService {}\n").unwrap();
+        std::fs::write(shared.join("store.mock.ts"), "export const mockStore = {};\n").unwrap();
+        std::fs::write(dir.path().join("app.ts"), "import { UserService, MockUserService, mockStore } from './shared';\n").unwrap();
+        std::fs::write(dir.path().join("app.spec.ts"), "import { MockUserService } from './shared';\n").unwrap();
+
+        let root = dir.path().to_path_buf();
+        let project = NgProject {
+            root_path: root.clone(),
+            import_graph: Some(DependencyGraphAnalyzer::new().analyze_project(&root).await.unwrap()),
+            ..Default::default()
+        };
+
+        let issues = DependencyAnalyzer::new().analyze_testing_leaks(&project);
+
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].file_path.ends_with("app.ts"));
+        assert!(issues[0].message.contains("MockUserService (from "));
+        assert!(issues[0].message.contains("mockStore (from "));
+        assert!(!issues[0].message.contains("user.service.ts)"));
+        assert!(is_testing_file("src/app/testing/helpers.ts"));
+        assert!(!is_testing_file("src/app/user.service.ts"));
+    }
+
+    #[tokio::test]
+    async fn test_unused_exports() {
+        let dir = tempfile::tempdir().unwrap();
+        let shared = dir.path().join("shared");
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::write(shared.join("index.ts"), "export * from './format';\nexport { Button } from './button';\n").unwrap();
+        std::fs::write(shared.join("format.ts"), "export function formatDate() {}\nexport function formatMoney() {}\n").unwrap();
+        std::fs::write(shared.join("button.ts"), "export class Button {}\nexport class ButtonGroup {}\nexport class SharedModule {}\n").unwrap();
+        std::fs::write(shared.join("math.ts"), "export const sum = 1;\nexport const max = 2;\n").unwrap();
+        std::fs::write(shared.join("chart.ts"), "export class Chart {}\n").unwrap();
+        std::fs::write(shared.join("tokens.ts"), "export const API_URL = '';\n").unwrap();
+        std::fs::write(dir.path().join("app.ts"), concat!(
+            "import { formatDate } from './shared';\n",
+            "import * as math from './shared/math';\n",
+            "import { API_URL } from '@app/shared';\n",
+            "export default class App {}\n",
+            "const chart = () => import('./shared/chart');\n",
+        )).unwrap();
+        std::fs::write(dir.path().join("app.spec.ts"), "import { ButtonGroup } from './shared/button';\nexport const fixture = {};\n").unwrap();
+
+        let root = dir.path().to_path_buf();
+        let project = NgProject {
+            root_path: root.clone(),
+            import_graph: Some(DependencyGraphAnalyzer::new().analyze_project(&root).await.unwrap()),
+            ..Default::default()
+        };
+
+        let (issues, by_directory) = DependencyAnalyzer::new().with_ignored_exports(vec!["*Module".to_string()]).analyze_unused_exports(&project).unwrap();
+
+        let found: Vec<(String, String)> = issues.iter().map(|issue| (relative_issue_path(&issue.file_path, &root), issue.message.clone())).collect();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].0, "shared/button.ts");
+        assert!(found[0].1.starts_with("Exported but never imported anywhere: Button."));
+        assert_eq!(found[1].0, "shared/format.ts");
+        assert!(found[1].1.starts_with("Exported but never imported anywhere: formatMoney."));
+        assert_eq!(by_directory.get("shared"), Some(&2));
+    }
+}
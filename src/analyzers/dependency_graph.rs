@@ -1,67 +1,167 @@
-use crate::ast::{ImportExportGraph, DependencyAnalysis, FileInfo, Dependency, CircularDependency, CycleSeverity};
-use crate::parsers::typescript::TypeScriptParser;
+use crate::ast::{ImportExportGraph, DependencyAnalysis, FileInfo, FileType, Dependency, CircularDependency, CycleSeverity, ImportChain, ImportType, ExportType, UnusedExport};
+use crate::config::build_glob_set;
+use crate::output::i18n::Lang;
+use crate::parsers::typescript::{resolve_relative_path, TypeScriptParser};
+use crate::parsers::source_cache::SourceCache;
+use crate::parsers::entry_points::{is_conventional_entry, workspace_entry_points, ENTRY_FILE_GLOBS};
+use crate::parsers::walk::{walk_files, WalkOptions};
 use anyhow::Result;
+use globset::GlobSet;
 use std::path::{Path, PathBuf};
 use std::collections::{HashMap, HashSet};
-use std::fs;
-use ignore::WalkBuilder;
+use std::sync::Arc;
 
 pub struct DependencyGraphAnalyzer {
     typescript_parser: TypeScriptParser,
+    max_cycles: usize,
+    circular: bool,
+    orphaned: bool,
+    depth: bool,
+    unused_exports: bool,
+    // 未使用の export から除くシンボル名またはファイルの glob（*Module, **/public-api.ts など）
+    ignored_exports: Vec<String>,
+    top_count: usize,
+    walk: WalkOptions,
+    // ProjectParser と共有すると、解析済みのファイルを読み直さない
+    sources: Arc<SourceCache>,
+    // エラーメッセージの言語
+    lang: Lang,
+}
+
+// graph コマンドの表示を絞り込む条件
+#[derive(Debug, Clone, Default)]
+pub struct GraphFilter {
+    pub min_fan_in: u32,
+    pub exclude_patterns: Vec<String>,
+    pub hide_leaf_nodes: bool,
+    // 残すファイルの拡張子 (ts, js, tsx, jsx)。空ならすべて
+    pub extensions: Vec<String>,
+    // 中心にするファイル（パス、ディレクトリ、glob）と、そこから辿る import 元・import 先の段数
+    pub focus: Vec<String>,
+    pub fan_in: u32,
+    pub fan_out: u32,
+}
+
+impl GraphFilter {
+    pub fn is_empty(&self) -> bool {
+        self.min_fan_in == 0 && self.exclude_patterns.is_empty() && !self.hide_leaf_nodes && self.extensions.is_empty() && self.focus.is_empty()
+    }
 }
 
 impl DependencyGraphAnalyzer {
     pub fn new() -> Self {
         Self {
             typescript_parser: TypeScriptParser::new(),
+            max_cycles: 100,
+            circular: true,
+            orphaned: true,
+            depth: true,
+            unused_exports: true,
+            ignored_exports: Vec::new(),
+            top_count: 10,
+            walk: WalkOptions::default(),
+            sources: Arc::new(SourceCache::new()),
+            lang: Lang::En,
+        }
+    }
+
+    // どれも指定されなければすべての分析を行う
+    pub fn with_analyses(mut self, circular: bool, orphaned: bool, depth: bool, unused_exports: bool) -> Self {
+        if circular || orphaned || depth || unused_exports {
+            self.circular = circular;
+            self.orphaned = orphaned;
+            self.depth = depth;
+            self.unused_exports = unused_exports;
         }
+        self
+    }
+
+    pub fn with_ignored_exports(mut self, ignored_exports: Vec<String>) -> Self {
+        self.ignored_exports = ignored_exports;
+        self
+    }
+
+    pub fn with_top_count(mut self, top_count: usize) -> Self {
+        self.top_count = top_count;
+        self
+    }
+
+    pub fn with_walk_options(mut self, walk: WalkOptions) -> Self {
+        self.walk = walk;
+        self
+    }
+
+    pub fn with_max_cycles(mut self, max_cycles: usize) -> Self {
+        self.max_cycles = max_cycles;
+        self
+    }
+
+    pub fn with_source_cache(mut self, sources: Arc<SourceCache>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    pub fn with_lang(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
     }
 
     pub async fn analyze_project(&self, root_path: &PathBuf) -> Result<ImportExportGraph> {
         let mut graph = ImportExportGraph::default();
         let mut file_id_counter = 0;
+        let mut project_files = Vec::new();
+        let mut lazy_targets = Vec::new();
 
         // プロジェクト内のすべてのTypeScriptファイルを走査
-        let walker = WalkBuilder::new(root_path)
-            .hidden(false)
-            .git_ignore(true)
-            .add_custom_ignore_filename(".gitignore")
-            .build();
-
-        for entry in walker {
-            let entry = entry?;
-            let path = entry.path();
-            
+        let paths = walk_files(root_path, &self.walk)?;
+        let sources: Vec<PathBuf> = paths.iter()
+            .filter(|path| matches!(path.extension().and_then(|ext| ext.to_str()), Some("ts" | "js" | "tsx" | "jsx")))
+            .cloned()
+            .collect();
+        self.sources.preload(&sources).await;
+        for path in paths {
+            let path = path.as_path();
+            if path.file_name().is_some_and(|name| name == "project.json") {
+                project_files.push(path.to_path_buf());
+            }
+
             if path.is_file() {
                 if let Some(extension) = path.extension() {
                     if matches!(extension.to_str(), Some("ts") | Some("js") | Some("tsx") | Some("jsx")) {
-                        if let Ok(content) = fs::read_to_string(path) {
-                            if let Ok(module) = self.typescript_parser.parse_file(&content) {
-                                let file_path = path.to_path_buf();
-                                let relative_path = path.strip_prefix(root_path)
-                                    .unwrap_or(path)
-                                    .to_string_lossy()
-                                    .to_string();
-                                
-                                let (imports, exports) = self.typescript_parser.extract_imports_exports(&module, &file_path)?;
-                                
-                                // FileInfo を追加
-                                let file_id = format!("file_{}", file_id_counter);
-                                file_id_counter += 1;
-                                
-                                graph.files.push(FileInfo {
-                                    id: file_id.clone(),
-                                    file_path: file_path.display().to_string(),
-                                    relative_path,
-                                    file_type: self.typescript_parser.get_file_type(&file_path),
-                                    exports: exports.iter().map(|e| e.symbol_name.clone()).collect(),
-                                    imports: imports.iter().map(|i| i.symbol_name.clone()).collect(),
-                                });
-
-                                // Imports と Exports を追加
-                                graph.imports.extend(imports);
-                                graph.exports.extend(exports);
+                        if let Ok((source, module)) = self.sources.module(path, |content| self.typescript_parser.parse_file(content)) {
+                            let content: &str = &source.content;
+                            let file_path = path.to_path_buf();
+                            let relative_path = path.strip_prefix(root_path)
+                                .unwrap_or(path)
+                                .to_string_lossy()
+                                .to_string();
+                            
+                            let (mut imports, exports) = self.typescript_parser.extract_imports_exports(&module, &file_path)?;
+                            if content.contains("import(") {
+                                imports.extend(self.typescript_parser.extract_dynamic_imports(&module, content, path));
                             }
+                            graph.mutable_exports.extend(self.typescript_parser.extract_mutable_exports(&module, content, path));
+                            graph.declared_symbols.extend(self.typescript_parser.extract_declared_symbols(&module, content, path));
+                            if content.contains("loadChildren") || content.contains("loadComponent") {
+                                lazy_targets.extend(self.typescript_parser.extract_lazy_routes(&module, content, path).into_iter().map(|route| route.target));
+                            }
+                            
+                            // FileInfo を追加
+                            let file_id = format!("file_{}", file_id_counter);
+                            file_id_counter += 1;
+                            
+                            graph.files.push(FileInfo {
+                                id: file_id.clone(),
+                                file_path: file_path.display().to_string(),
+                                relative_path,
+                                file_type: self.typescript_parser.get_file_type(&file_path),
+                                exports: exports.iter().map(|e| e.symbol_name.clone()).collect(),
+                                imports: imports.iter().map(|i| i.symbol_name.clone()).collect(),
+                            });
+
+                            // Imports と Exports を追加
+                            graph.imports.extend(imports);
+                            graph.exports.extend(exports);
                         }
                     }
                 }
@@ -70,10 +170,43 @@ impl DependencyGraphAnalyzer {
 
         // 依存関係を構築
         self.build_dependencies(&mut graph, root_path)?;
+        graph.entry_points = self.collect_entry_points(&graph, root_path, &project_files, &lazy_targets)?;
 
         Ok(graph)
     }
 
+    // 孤立ファイルの判定で起点にするファイル。ワークスペースのビルダー設定と tsconfig のエイリアス、
+    // 慣例的なエントリーポイント、遅延ロード先、テストや設定ファイルなどツールが直接読むもの
+    fn collect_entry_points(&self, graph: &ImportExportGraph, root_path: &Path, project_files: &[PathBuf], lazy_targets: &[String]) -> Result<Vec<String>> {
+        let module_paths = module_path_index(graph);
+        let tool_files = build_glob_set(&ENTRY_FILE_GLOBS.map(String::from))?;
+        let mut entry_points: HashSet<String> = lazy_targets.iter()
+            .filter_map(|target| lookup_module(&module_paths, target))
+            .collect();
+
+        // 設定ファイルのパスは正規化済みの絶対パスなので、解析対象のルートからの相対パスに直して引く
+        if let Ok(root) = std::fs::canonicalize(root_path) {
+            for entry in workspace_entry_points(root_path, project_files) {
+                let Ok(relative) = entry.strip_prefix(&root) else { continue };
+                let file_path = root_path.join(relative).display().to_string();
+                if let Some(id) = module_paths.get(&normalize_file_path(&file_path)) {
+                    entry_points.insert(id.clone());
+                }
+            }
+        }
+
+        for file in &graph.files {
+            let relative_path = file.relative_path.replace('\\', "/");
+            let file_name = relative_path.rsplit('/').next().unwrap_or_default();
+            if is_conventional_entry(file_name) || tool_files.is_match(&relative_path) {
+                entry_points.insert(file.id.clone());
+            }
+        }
+
+        // 出力が毎回同じになるよう、ファイルの並び順にそろえる
+        Ok(graph.files.iter().filter(|file| entry_points.contains(&file.id)).map(|file| file.id.clone()).collect())
+    }
+
     fn build_dependencies(&self, graph: &mut ImportExportGraph, _root_path: &PathBuf) -> Result<()> {
         let mut path_to_file_id: HashMap<String, String> = HashMap::new();
         
@@ -82,33 +215,52 @@ impl DependencyGraphAnalyzer {
             let normalized_path = file_info.file_path.replace('\\', "/");
             path_to_file_id.insert(normalized_path, file_info.id.clone());
         }
+        let module_paths = module_path_index(graph);
+
+        // import に加えて export ... from による再エクスポートも依存関係として扱う
+        let references: Vec<(String, String, ImportType, String, Option<u32>)> = graph.imports.iter()
+            .map(|import| (
+                import.file_path.clone(),
+                import.source_module.clone(),
+                import.import_type.clone(),
+                import.symbol_name.clone(),
+                import.line_number,
+            ))
+            .chain(graph.exports.iter().filter_map(|export| {
+                let source_module = export.source_module.clone()?;
+                let import_type = if export.symbol_name == "*" { ImportType::Namespace } else { ImportType::Named };
+                Some((export.file_path.clone(), source_module, import_type, export.symbol_name.clone(), export.line_number))
+            }))
+            .collect();
 
         // 各importに対して依存関係を作成（相対パスのimportのみ処理）
-        for import in &graph.imports {
+        for (file_path, source_module, import_type, symbol_name, line_number) in references {
             // 外部ライブラリのimportは無視
-            if !import.source_module.starts_with('.') {
+            if !source_module.starts_with('.') {
                 continue;
             }
             
-            // ファイル名ベースでマッチングする
-            let mut target_file_id: Option<String> = None;
-            
-            // インポートパスから期待されるファイル名を抽出
-            let import_target = import.source_module.trim_start_matches("./");
-            let expected_filename = format!("{}.ts", import_target);
+            // 相対パスを解決し、見つからなければ従来どおりファイル名で探す
+            let mut target_file_id = resolve_module(&module_paths, &file_path, &source_module);
             
-            // すべてのファイルから一致するものを探す
-            for file_info in &graph.files {
-                if let Some(filename) = Path::new(&file_info.file_path).file_name() {
-                    if filename.to_string_lossy() == expected_filename {
-                        target_file_id = Some(file_info.id.clone());
-                        break;
+            if target_file_id.is_none() {
+                // インポートパスから期待されるファイル名を抽出
+                let import_target = source_module.trim_start_matches("./");
+                let expected_filename = format!("{}.ts", import_target);
+                
+                // すべてのファイルから一致するものを探す
+                for file_info in &graph.files {
+                    if let Some(filename) = Path::new(&file_info.file_path).file_name() {
+                        if filename.to_string_lossy() == expected_filename {
+                            target_file_id = Some(file_info.id.clone());
+                            break;
+                        }
                     }
                 }
             }
             
             if let Some(target_file_id) = target_file_id {
-                let normalized_import_path = import.file_path.replace('\\', "/");
+                let normalized_import_path = file_path.replace('\\', "/");
                 if let Some(source_file_id) = path_to_file_id.get(&normalized_import_path) {
                     // 重複チェック
                     let dependency_exists = graph.dependencies.iter().any(|dep| 
@@ -119,17 +271,22 @@ impl DependencyGraphAnalyzer {
                         graph.dependencies.push(Dependency {
                             from_file: source_file_id.clone(),
                             to_file: target_file_id.clone(),
-                            import_type: import.import_type.clone(),
-                            imported_symbols: vec![import.symbol_name.clone()],
-                            line_number: import.line_number,
+                            import_type,
+                            imported_symbols: vec![symbol_name],
+                            line_number,
                         });
                     } else {
                         // 既存の依存関係にシンボルを追加
                         if let Some(existing_dep) = graph.dependencies.iter_mut().find(|dep| 
                             dep.from_file == *source_file_id && dep.to_file == target_file_id
                         ) {
-                            if !existing_dep.imported_symbols.contains(&import.symbol_name) {
-                                existing_dep.imported_symbols.push(import.symbol_name.clone());
+                            // 静的にも import していれば、遅延ロードの境界ではない
+                            if matches!(existing_dep.import_type, ImportType::Dynamic) && !matches!(import_type, ImportType::Dynamic) {
+                                existing_dep.import_type = import_type;
+                                existing_dep.line_number = line_number;
+                            }
+                            if !existing_dep.imported_symbols.contains(&symbol_name) {
+                                existing_dep.imported_symbols.push(symbol_name);
                             }
                         }
                     }
@@ -140,6 +297,146 @@ impl DependencyGraphAnalyzer {
         Ok(())
     }
 
+    // '@angular/core' や 'rxjs/operators' などの import を、パッケージ単位のノードとして加える
+    pub fn add_external_packages(&self, mut graph: ImportExportGraph) -> ImportExportGraph {
+        let file_ids: HashMap<String, String> = graph.files.iter()
+            .map(|file| (file.file_path.replace('\\', "/"), file.id.clone()))
+            .collect();
+        let references: Vec<(String, String, ImportType, String, Option<u32>)> = graph.imports.iter()
+            .map(|import| (import.file_path.clone(), import.source_module.clone(), import.import_type.clone(), import.symbol_name.clone(), import.line_number))
+            .chain(graph.exports.iter().filter_map(|export| {
+                let source_module = export.source_module.clone()?;
+                Some((export.file_path.clone(), source_module, ImportType::Named, export.symbol_name.clone(), export.line_number))
+            }))
+            .collect();
+
+        for (file_path, source_module, import_type, symbol_name, line_number) in references {
+            let Some(package) = package_name(&source_module) else { continue };
+            let Some(from_file) = file_ids.get(&file_path.replace('\\', "/")) else { continue };
+            let package_id = format!("external:{}", package);
+            if !graph.files.iter().any(|file| file.id == package_id) {
+                graph.files.push(FileInfo {
+                    id: package_id.clone(),
+                    file_path: package.clone(),
+                    relative_path: package.clone(),
+                    file_type: FileType::External,
+                    exports: Vec::new(),
+                    imports: Vec::new(),
+                });
+            }
+
+            match graph.dependencies.iter_mut().find(|dep| dep.from_file == *from_file && dep.to_file == package_id) {
+                Some(dependency) => {
+                    if !dependency.imported_symbols.contains(&symbol_name) {
+                        dependency.imported_symbols.push(symbol_name);
+                    }
+                }
+                None => graph.dependencies.push(Dependency {
+                    from_file: from_file.clone(),
+                    to_file: package_id,
+                    import_type,
+                    imported_symbols: vec![symbol_name],
+                    line_number,
+                }),
+            }
+        }
+
+        graph
+    }
+
+    // 除外パターン → 被参照数の少ないファイルへの辺 → 末端ファイルの順に取り除く
+    pub fn filter_graph(&self, mut graph: ImportExportGraph, filter: &GraphFilter) -> Result<ImportExportGraph> {
+        if filter.is_empty() {
+            return Ok(graph);
+        }
+
+        let excludes = build_glob_set(&filter.exclude_patterns)?;
+        let extensions: Vec<String> = filter.extensions.iter()
+            .map(|extension| extension.trim_start_matches('.').to_lowercase())
+            .collect();
+        let excluded: HashSet<String> = graph.files.iter()
+            .filter(|file| {
+                let extension = Path::new(&file.file_path).extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+                let other_extension = !extensions.is_empty() && !matches!(file.file_type, FileType::External) && !extensions.contains(&extension);
+                other_extension || excludes.is_match(file.relative_path.replace('\\', "/"))
+            })
+            .map(|file| file.id.clone())
+            .collect();
+        graph.dependencies.retain(|dep| !excluded.contains(&dep.from_file) && !excluded.contains(&dep.to_file));
+        let mut removed = excluded;
+
+        if !filter.focus.is_empty() {
+            let neighborhood = self.focus_neighborhood(&graph, &filter.focus, filter.fan_in, filter.fan_out)?;
+            graph.dependencies.retain(|dep| neighborhood.contains(&dep.from_file) && neighborhood.contains(&dep.to_file));
+            removed.extend(graph.files.iter().filter(|f| !neighborhood.contains(&f.id)).map(|f| f.id.clone()));
+        }
+
+        if filter.min_fan_in > 0 {
+            let fan_in = fan_in_counts(&graph);
+            graph.dependencies.retain(|dep| fan_in.get(&dep.to_file).copied().unwrap_or(0) >= filter.min_fan_in);
+            // 辺がなくなったファイルも表示しない
+            let connected: HashSet<&str> = graph.dependencies.iter()
+                .flat_map(|dep| [dep.from_file.as_str(), dep.to_file.as_str()])
+                .collect();
+            removed.extend(graph.files.iter().filter(|f| !connected.contains(f.id.as_str())).map(|f| f.id.clone()));
+        }
+
+        if filter.hide_leaf_nodes {
+            // 何も import せず、1 ファイルからしか import されていないもの
+            let fan_in = fan_in_counts(&graph);
+            let importers: HashSet<&str> = graph.dependencies.iter().map(|dep| dep.from_file.as_str()).collect();
+            let leaves: HashSet<String> = graph.files.iter()
+                .filter(|f| !importers.contains(f.id.as_str()) && fan_in.get(&f.id).copied().unwrap_or(0) <= 1)
+                .map(|f| f.id.clone())
+                .collect();
+            graph.dependencies.retain(|dep| !leaves.contains(&dep.to_file));
+            removed.extend(leaves);
+        }
+
+        let removed_paths: HashSet<String> = graph.files.iter()
+            .filter(|f| removed.contains(&f.id))
+            .map(|f| f.file_path.clone())
+            .collect();
+        graph.files.retain(|f| !removed.contains(&f.id));
+        graph.imports.retain(|i| !removed_paths.contains(&i.file_path));
+        graph.exports.retain(|e| !removed_paths.contains(&e.file_path));
+
+        Ok(graph)
+    }
+
+    // --focus に一致するファイルから、import 元を fan_in 段、import 先を fan_out 段まで辿ったファイルの ID
+    pub fn focus_neighborhood(&self, graph: &ImportExportGraph, focus: &[String], fan_in: u32, fan_out: u32) -> Result<HashSet<String>> {
+        let roots = matching_file_ids(graph, focus)?;
+        if roots.is_empty() {
+            return Err(anyhow::anyhow!("{}", self.lang.trf("error.focus", &[&focus.join(", ")])));
+        }
+
+        let mut downstream: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut upstream: HashMap<&str, Vec<&str>> = HashMap::new();
+        for dep in &graph.dependencies {
+            downstream.entry(dep.from_file.as_str()).or_default().push(dep.to_file.as_str());
+            upstream.entry(dep.to_file.as_str()).or_default().push(dep.from_file.as_str());
+        }
+
+        let mut neighborhood: HashSet<String> = roots.iter().map(|id| id.to_string()).collect();
+        for (adjacency, depth) in [(&upstream, fan_in), (&downstream, fan_out)] {
+            let mut visited: HashSet<&str> = roots.iter().copied().collect();
+            let mut frontier = roots.clone();
+            for _ in 0..depth {
+                frontier = frontier.iter()
+                    .flat_map(|id| adjacency.get(id).into_iter().flatten().copied())
+                    .filter(|next| visited.insert(next))
+                    .collect();
+                if frontier.is_empty() {
+                    break;
+                }
+                neighborhood.extend(frontier.iter().map(|id| id.to_string()));
+            }
+        }
+
+        Ok(neighborhood)
+    }
+
     #[allow(dead_code)]
     fn resolve_import_path(&self, import_path: &str, current_file: &str, _root_path: &PathBuf) -> Option<String> {
         let current_dir = Path::new(current_file).parent().unwrap_or(Path::new(""));
@@ -174,92 +471,118 @@ impl DependencyGraphAnalyzer {
     }
 
     pub fn analyze_dependencies(&self, graph: &ImportExportGraph) -> Result<DependencyAnalysis> {
-        let mut analysis = DependencyAnalysis::default();
-        
-        // 循環依存の検出
-        analysis.circular_dependencies = self.find_circular_dependencies(graph)?;
-        
-        // 孤立したファイルの検出
-        analysis.orphaned_files = self.find_orphaned_files(graph)?;
-        
-        // 依存関係の深さを計算
-        analysis.dependency_depth = self.calculate_dependency_depth(graph)?;
-        
-        // 最も多く利用されているファイルを計算
-        analysis.most_imported_files = self.find_most_imported_files(graph)?;
-        
-        // 最も多くの依存関係を持つファイルを計算
-        analysis.most_dependent_files = self.find_most_dependent_files(graph)?;
+        // 指定されなかった分析は行わず、空のままにする
+        let analysis = DependencyAnalysis {
+            // 循環依存の検出
+            circular_dependencies: if self.circular { self.find_circular_dependencies(graph)? } else { Vec::new() },
+            // 孤立したファイルの検出
+            orphaned_files: if self.orphaned { self.find_orphaned_files(graph)? } else { Vec::new() },
+            // 依存関係の深さを計算
+            dependency_depth: if self.depth { self.calculate_dependency_depth(graph)? } else { HashMap::new() },
+            // 最も多く利用されているファイルを計算
+            most_imported_files: self.find_most_imported_files(graph)?,
+            // 最も多くの依存関係を持つファイルを計算
+            most_dependent_files: self.find_most_dependent_files(graph)?,
+            // 循環をまとめたうえでの最長の import 経路
+            longest_chains: if self.depth { longest_import_chains(graph, self.top_count) } else { Vec::new() },
+            // どこからも import されていない export
+            unused_exports: if self.unused_exports { find_unused_exports(graph, &build_glob_set(&self.ignored_exports)?)? } else { Vec::new() },
+        };
         
         Ok(analysis)
     }
 
+    // 強連結成分ごとに Johnson のアルゴリズムで単純閉路をすべて列挙し、辺の少ない順に並べる
     fn find_circular_dependencies(&self, graph: &ImportExportGraph) -> Result<Vec<CircularDependency>> {
-        let mut circular_deps = Vec::new();
-        let mut visited = HashSet::new();
-        let mut rec_stack = HashSet::new();
-        
-        // 各ファイルからDFSを開始
-        for file in &graph.files {
-            if !visited.contains(&file.id) {
-                let mut path = Vec::new();
-                if let Some(cycle) = self.dfs_find_cycle(&file.id, graph, &mut visited, &mut rec_stack, &mut path) {
-                    let severity = if cycle.len() <= 2 {
-                        CycleSeverity::Critical
-                    } else if cycle.len() <= 4 {
-                        CycleSeverity::Warning
-                    } else {
-                        CycleSeverity::Info
-                    };
-                    
-                    circular_deps.push(CircularDependency {
-                        cycle,
-                        severity,
-                    });
+        let index: HashMap<&str, usize> = graph.files.iter().enumerate().map(|(i, file)| (file.id.as_str(), i)).collect();
+        let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); graph.files.len()];
+        for dependency in static_dependencies(graph) {
+            let (Some(&from), Some(&to)) = (index.get(dependency.from_file.as_str()), index.get(dependency.to_file.as_str())) else { continue };
+            if !adjacency[from].contains(&to) {
+                adjacency[from].push(to);
+            }
+        }
+
+        let mut component_of = vec![usize::MAX; graph.files.len()];
+        for (c, members) in strongly_connected_components(&adjacency).iter().enumerate() {
+            if members.len() > 1 || adjacency[members[0]].contains(&members[0]) {
+                for &file in members {
+                    component_of[file] = c;
                 }
             }
         }
-        
-        Ok(circular_deps)
-    }
-
-    fn dfs_find_cycle(
-        &self,
-        node: &str,
-        graph: &ImportExportGraph,
-        visited: &mut HashSet<String>,
-        rec_stack: &mut HashSet<String>,
-        path: &mut Vec<String>,
-    ) -> Option<Vec<String>> {
-        visited.insert(node.to_string());
-        rec_stack.insert(node.to_string());
-        path.push(node.to_string());
-
-        // 隣接ノードを探索
+
+        let mut cycles: Vec<Vec<usize>> = Vec::new();
+        let mut search = CycleSearch {
+            adjacency: &adjacency,
+            blocked: vec![false; graph.files.len()],
+            blocked_by: vec![Vec::new(); graph.files.len()],
+            stack: Vec::new(),
+            cycles: &mut cycles,
+            limit: self.max_cycles,
+        };
+        // 閉路はその中で最も小さい番号のファイルからだけ数えるので、回転した同じ閉路は出てこない
+        for start in 0..graph.files.len() {
+            if component_of[start] == usize::MAX || search.cycles.len() >= search.limit {
+                continue;
+            }
+            let allowed = |file: usize| file >= start && component_of[file] == component_of[start];
+            for file in (start..graph.files.len()).filter(|&file| allowed(file)) {
+                search.blocked[file] = false;
+                search.blocked_by[file].clear();
+            }
+            search.circuit(start, start, &allowed);
+        }
+
+        let mut seen = HashSet::new();
+        cycles.retain(|cycle| seen.insert(cycle.clone()));
+        cycles.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+
+        Ok(cycles.into_iter()
+            .map(|cycle| {
+                let severity = if cycle.len() <= 2 {
+                    CycleSeverity::Critical
+                } else if cycle.len() <= 4 {
+                    CycleSeverity::Warning
+                } else {
+                    CycleSeverity::Info
+                };
+                // 先頭のファイルに戻るところまでを並べる
+                let mut files: Vec<String> = cycle.iter().map(|&file| graph.files[file].id.clone()).collect();
+                files.push(files[0].clone());
+                CircularDependency { cycle: files, severity }
+            })
+            .collect())
+    }
+
+    // エントリーポイントから import を辿って届かないファイル。起点が 1 つもなければ従来の判定に戻す
+    fn find_orphaned_files(&self, graph: &ImportExportGraph) -> Result<Vec<String>> {
+        if graph.entry_points.is_empty() {
+            return self.find_unreferenced_files(graph);
+        }
+
+        let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
         for dependency in &graph.dependencies {
-            if dependency.from_file == node {
-                let next_node = &dependency.to_file;
-                
-                if !visited.contains(next_node) {
-                    if let Some(cycle) = self.dfs_find_cycle(next_node, graph, visited, rec_stack, path) {
-                        return Some(cycle);
-                    }
-                } else if rec_stack.contains(next_node) {
-                    // 循環を発見
-                    let cycle_start = path.iter().position(|x| x == next_node).unwrap();
-                    let mut cycle = path[cycle_start..].to_vec();
-                    cycle.push(next_node.to_string());
-                    return Some(cycle);
+            adjacency.entry(dependency.from_file.as_str()).or_default().push(dependency.to_file.as_str());
+        }
+        let mut reachable: HashSet<&str> = graph.entry_points.iter().map(String::as_str).collect();
+        let mut queue: Vec<&str> = reachable.iter().copied().collect();
+        while let Some(file) = queue.pop() {
+            for &next in adjacency.get(file).into_iter().flatten() {
+                if reachable.insert(next) {
+                    queue.push(next);
                 }
             }
         }
 
-        rec_stack.remove(node);
-        path.pop();
-        None
+        Ok(graph.files.iter()
+            .filter(|file| !matches!(file.file_type, FileType::External) && !reachable.contains(file.id.as_str()))
+            .map(|file| file.file_path.clone())
+            .collect())
     }
 
-    fn find_orphaned_files(&self, graph: &ImportExportGraph) -> Result<Vec<String>> {
+    // どこからも import されておらず、export もしていないファイル
+    fn find_unreferenced_files(&self, graph: &ImportExportGraph) -> Result<Vec<String>> {
         let mut imported_files = HashSet::new();
         let mut exporting_files = HashSet::new();
         
@@ -302,7 +625,7 @@ impl DependencyGraphAnalyzer {
         
         let mut max_depth = 0;
         
-        for dependency in &graph.dependencies {
+        for dependency in static_dependencies(graph) {
             if dependency.from_file == file_id {
                 let child_depth = self.calculate_file_depth(&dependency.to_file, graph, visited)?;
                 max_depth = max_depth.max(child_depth + 1);
@@ -331,8 +654,8 @@ impl DependencyGraphAnalyzer {
             })
             .collect();
         
-        sorted_files.sort_by(|a, b| b.1.cmp(&a.1));
-        sorted_files.truncate(10);
+        sorted_files.sort_by_key(|b| std::cmp::Reverse(b.1));
+        sorted_files.truncate(self.top_count);
         
         Ok(sorted_files)
     }
@@ -355,15 +678,525 @@ impl DependencyGraphAnalyzer {
             })
             .collect();
         
-        sorted_files.sort_by(|a, b| b.1.cmp(&a.1));
-        sorted_files.truncate(10);
+        sorted_files.sort_by_key(|b| std::cmp::Reverse(b.1));
+        sorted_files.truncate(self.top_count);
         
         Ok(sorted_files)
     }
 }
 
+// '@scope/name/sub' は '@scope/name'、'rxjs/operators' は 'rxjs'。相対パスと絶対パスは None
+pub fn package_name(source_module: &str) -> Option<String> {
+    if source_module.is_empty() || source_module.starts_with('.') || source_module.starts_with('/') {
+        return None;
+    }
+    let segments: Vec<&str> = source_module.split('/').collect();
+    let count = if source_module.starts_with('@') { 2 } else { 1 };
+    Some(segments.iter().take(count).copied().collect::<Vec<_>>().join("/"))
+}
+
+// 動的 import は別のチャンクになるので、循環・依存の深さ・バンドルの見積もりでは辿らない
+// パス・ディレクトリ・glob に一致するファイルの ID。パスは末尾が一致すればよい
+pub fn matching_file_ids<'a>(graph: &'a ImportExportGraph, patterns: &[String]) -> Result<Vec<&'a str>> {
+    let globs = build_glob_set(patterns)?;
+    let prefixes: Vec<String> = patterns.iter()
+        .map(|pattern| pattern.replace('\\', "/").trim_start_matches("./").trim_end_matches('/').to_string())
+        .collect();
+    Ok(graph.files.iter()
+        .filter(|file| {
+            let path = file.relative_path.replace('\\', "/");
+            globs.is_match(&path) || prefixes.iter().any(|prefix| {
+                path == *prefix || path.starts_with(&format!("{}/", prefix)) || path.ends_with(&format!("/{}", prefix))
+            })
+        })
+        .map(|file| file.id.as_str())
+        .collect())
+}
+
+pub fn static_dependencies(graph: &ImportExportGraph) -> impl Iterator<Item = &Dependency> {
+    graph.dependencies.iter().filter(|dependency| !matches!(dependency.import_type, ImportType::Dynamic))
+}
+
+// どこからも import されていない export。名前での import と、使われているバレルの再エクスポートを辿る。
+// 名前空間 import と動的 import はモジュール全体を使うものとして扱う。default export、再エクスポート自体、
+// エントリーポイント（main.ts や tsconfig のエイリアス先など）とテストや設定ファイルの export は対象外で、
+// ignore はシンボル名とファイルの相対パスの両方に照合する
+pub fn find_unused_exports(graph: &ImportExportGraph, ignore: &GlobSet) -> Result<Vec<UnusedExport>> {
+    let module_paths = module_path_index(graph);
+    let tool_files = build_glob_set(&ENTRY_FILE_GLOBS.map(String::from))?;
+    let file_ids: HashMap<String, &str> = graph.files.iter().map(|file| (file.file_path.replace('\\', "/"), file.id.as_str())).collect();
+
+    // import 先のファイル ID → (import 元のファイル ID, 使っているシンボル)。モジュール全体を使う場合は "*"
+    let mut importers: HashMap<String, Vec<(&str, &str)>> = HashMap::new();
+    // tsconfig の paths エイリアス経由の import は辺にならないので、名前だけで照合する
+    let mut aliased_symbols: HashSet<&str> = HashSet::new();
+    for import in &graph.imports {
+        if !import.source_module.starts_with('.') {
+            aliased_symbols.insert(import.symbol_name.as_str());
+            continue;
+        }
+        let Some(from) = file_ids.get(&import.file_path.replace('\\', "/")) else { continue };
+        let Some(target) = resolve_module(&module_paths, &import.file_path, &import.source_module) else { continue };
+        let symbol = match import.import_type {
+            ImportType::Namespace | ImportType::Dynamic => "*",
+            _ => import.symbol_name.as_str(),
+        };
+        importers.entry(target).or_default().push((from, symbol));
+    }
+    let mut reexporters: HashMap<String, Vec<(&str, &str)>> = HashMap::new();
+    for export in &graph.exports {
+        let Some(source_module) = &export.source_module else { continue };
+        let Some(from) = file_ids.get(&export.file_path.replace('\\', "/")) else { continue };
+        let Some(target) = resolve_module(&module_paths, &export.file_path, source_module) else { continue };
+        reexporters.entry(target).or_default().push((from, export.symbol_name.as_str()));
+    }
+
+    let mut declared: HashMap<String, Vec<&str>> = HashMap::new();
+    for export in &graph.exports {
+        if export.source_module.is_none() && !matches!(export.export_type, ExportType::Default) && export.symbol_name != "*" {
+            declared.entry(export.file_path.replace('\\', "/")).or_default().push(export.symbol_name.as_str());
+        }
+    }
+
+    let mut unused = Vec::new();
+    for file in graph.files.iter().filter(|file| !matches!(file.file_type, FileType::External)) {
+        let relative_path = file.relative_path.replace('\\', "/");
+        if graph.entry_points.contains(&file.id) || tool_files.is_match(&relative_path) || ignore.is_match(&relative_path) {
+            continue;
+        }
+        let mut symbols: Vec<&str> = declared.get(&file.file_path.replace('\\', "/")).into_iter().flatten()
+            .copied()
+            .filter(|symbol| !aliased_symbols.contains(symbol) && !ignore.is_match(symbol))
+            .collect();
+        symbols.dedup();
+        for symbol in symbols {
+            if !is_export_used(&importers, &reexporters, &file.id, symbol, &mut HashSet::new()) {
+                unused.push(UnusedExport { file_path: file.file_path.clone(), symbol: symbol.to_string() });
+            }
+        }
+    }
+    Ok(unused)
+}
+
+fn is_export_used<'a>(
+    importers: &HashMap<String, Vec<(&'a str, &'a str)>>,
+    reexporters: &HashMap<String, Vec<(&'a str, &'a str)>>,
+    file_id: &str,
+    symbol: &str,
+    visited: &mut HashSet<String>,
+) -> bool {
+    if !visited.insert(file_id.to_string()) {
+        return false;
+    }
+    if importers.get(file_id).into_iter().flatten().any(|(_, used)| *used == symbol || *used == "*") {
+        return true;
+    }
+    reexporters.get(file_id).into_iter().flatten()
+        .filter(|(_, reexported)| *reexported == symbol || *reexported == "*")
+        .any(|(barrel, _)| is_export_used(importers, reexporters, barrel, symbol, visited))
+}
+
+// "./src/app/a.ts" と "src/app/a.ts" を同じものとして引けるよう、"." を除いたパスからファイル ID を引く
+pub fn module_path_index(graph: &ImportExportGraph) -> HashMap<String, String> {
+    graph.files.iter()
+        .map(|file| (normalize_file_path(&file.file_path), file.id.clone()))
+        .collect()
+}
+
+// import './x' の参照先。x.ts / x.tsx / x/index.ts などを順に試す
+pub fn resolve_module(module_paths: &HashMap<String, String>, file_path: &str, source_module: &str) -> Option<String> {
+    let base = resolve_relative_path(Path::new(&file_path.replace('\\', "/")), source_module);
+    lookup_module(module_paths, &base)
+}
+
+// 解決済みの拡張子なしのパスからファイル ID を引く
+pub fn lookup_module(module_paths: &HashMap<String, String>, base: &str) -> Option<String> {
+    ["", ".ts", ".tsx", ".js", ".jsx", "/index.ts", "/index.tsx", "/index.js"].iter()
+        .find_map(|suffix| module_paths.get(&format!("{}{}", base, suffix)).cloned())
+}
+
+pub fn normalize_file_path(file_path: &str) -> String {
+    let path = Path::new(file_path);
+    let file_name = path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+    resolve_relative_path(path, &file_name)
+}
+
+// 強連結成分に縮約した DAG で、どこからも import されない成分から始まる最長の経路を長い順に返す
+pub fn longest_import_chains(graph: &ImportExportGraph, limit: usize) -> Vec<ImportChain> {
+    let index: HashMap<&str, usize> = graph.files.iter().enumerate().map(|(i, file)| (file.id.as_str(), i)).collect();
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); graph.files.len()];
+    for dependency in static_dependencies(graph) {
+        let (Some(&from), Some(&to)) = (index.get(dependency.from_file.as_str()), index.get(dependency.to_file.as_str())) else { continue };
+        if from != to && !adjacency[from].contains(&to) {
+            adjacency[from].push(to);
+        }
+    }
+
+    // Tarjan の結果は、ある成分から到達できる成分が必ず先に並ぶ
+    let components = strongly_connected_components(&adjacency);
+    let mut component_of = vec![0; graph.files.len()];
+    for (c, members) in components.iter().enumerate() {
+        for &file in members {
+            component_of[file] = c;
+        }
+    }
+
+    let mut length = vec![0u32; components.len()];
+    let mut next: Vec<Option<usize>> = vec![None; components.len()];
+    let mut imported = vec![false; components.len()];
+    for (c, members) in components.iter().enumerate() {
+        for &file in members {
+            for &target in &adjacency[file] {
+                let successor = component_of[target];
+                if successor == c {
+                    continue;
+                }
+                imported[successor] = true;
+                if length[successor] + 1 > length[c] {
+                    length[c] = length[successor] + 1;
+                    next[c] = Some(successor);
+                }
+            }
+        }
+    }
+
+    let label = |c: usize| {
+        let mut paths: Vec<String> = components[c].iter()
+            .map(|&file| graph.files[file].relative_path.replace('\\', "/"))
+            .collect();
+        paths.sort();
+        if paths.len() == 1 {
+            paths.remove(0)
+        } else {
+            format!("{{{}}}", paths.join(", "))
+        }
+    };
+
+    let mut roots: Vec<usize> = (0..components.len()).filter(|&c| !imported[c] && length[c] > 0).collect();
+    roots.sort_by_key(|&c| (std::cmp::Reverse(length[c]), label(c)));
+
+    roots.into_iter()
+        .take(limit)
+        .map(|root| {
+            let mut path = vec![label(root)];
+            let mut current = root;
+            while let Some(successor) = next[current] {
+                path.push(label(successor));
+                current = successor;
+            }
+            let first = components[root].iter().map(|&file| &graph.files[file].file_path).min().cloned().unwrap_or_default();
+            ImportChain {
+                depth: length[root],
+                file_path: first,
+                path,
+            }
+        })
+        .collect()
+}
+
+struct CycleSearch<'a> {
+    adjacency: &'a [Vec<usize>],
+    blocked: Vec<bool>,
+    blocked_by: Vec<Vec<usize>>,
+    stack: Vec<usize>,
+    cycles: &'a mut Vec<Vec<usize>>,
+    limit: usize,
+}
+
+impl CycleSearch<'_> {
+    fn circuit(&mut self, v: usize, start: usize, allowed: &dyn Fn(usize) -> bool) -> bool {
+        let mut found = false;
+        self.stack.push(v);
+        self.blocked[v] = true;
+
+        for &w in &self.adjacency[v] {
+            if self.cycles.len() >= self.limit {
+                break;
+            }
+            if !allowed(w) {
+                continue;
+            }
+            if w == start {
+                self.cycles.push(self.stack.clone());
+                found = true;
+            } else if !self.blocked[w] && self.circuit(w, start, allowed) {
+                found = true;
+            }
+        }
+
+        if found {
+            self.unblock(v);
+        } else {
+            for &w in &self.adjacency[v] {
+                if allowed(w) && !self.blocked_by[w].contains(&v) {
+                    self.blocked_by[w].push(v);
+                }
+            }
+        }
+        self.stack.pop();
+        found
+    }
+
+    fn unblock(&mut self, v: usize) {
+        self.blocked[v] = false;
+        for w in std::mem::take(&mut self.blocked_by[v]) {
+            if self.blocked[w] {
+                self.unblock(w);
+            }
+        }
+    }
+}
+
+pub fn strongly_connected_components(adjacency: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct Tarjan<'a> {
+        adjacency: &'a [Vec<usize>],
+        counter: usize,
+        indices: Vec<Option<usize>>,
+        lowlink: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        components: Vec<Vec<usize>>,
+    }
+
+    impl Tarjan<'_> {
+        fn connect(&mut self, v: usize) {
+            self.indices[v] = Some(self.counter);
+            self.lowlink[v] = self.counter;
+            self.counter += 1;
+            self.stack.push(v);
+            self.on_stack[v] = true;
+
+            for &w in &self.adjacency[v] {
+                match self.indices[w] {
+                    None => {
+                        self.connect(w);
+                        self.lowlink[v] = self.lowlink[v].min(self.lowlink[w]);
+                    }
+                    Some(index) if self.on_stack[w] => self.lowlink[v] = self.lowlink[v].min(index),
+                    Some(_) => {}
+                }
+            }
+
+            if Some(self.lowlink[v]) == self.indices[v] {
+                let mut component = Vec::new();
+                while let Some(w) = self.stack.pop() {
+                    self.on_stack[w] = false;
+                    component.push(w);
+                    if w == v {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        adjacency,
+        counter: 0,
+        indices: vec![None; adjacency.len()],
+        lowlink: vec![0; adjacency.len()],
+        on_stack: vec![false; adjacency.len()],
+        stack: Vec::new(),
+        components: Vec::new(),
+    };
+    for v in 0..adjacency.len() {
+        if tarjan.indices[v].is_none() {
+            tarjan.connect(v);
+        }
+    }
+    tarjan.components
+}
+
+fn fan_in_counts(graph: &ImportExportGraph) -> HashMap<String, u32> {
+    let mut counts = HashMap::new();
+    for dep in &graph.dependencies {
+        *counts.entry(dep.to_file.clone()).or_insert(0) += 1;
+    }
+    counts
+}
+
 impl Default for DependencyGraphAnalyzer {
     fn default() -> Self {
         Self::new()
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Import, ImportType};
+
+    fn graph(files: &[&str], edges: &[(&str, &str)]) -> ImportExportGraph {
+        ImportExportGraph {
+            files: files.iter().map(|path| FileInfo {
+                id: path.to_string(),
+                file_path: path.to_string(),
+                relative_path: path.to_string(),
+                file_type: FileType::TypeScript,
+                exports: Vec::new(),
+                imports: Vec::new(),
+            }).collect(),
+            dependencies: edges.iter().map(|(from, to)| Dependency {
+                from_file: from.to_string(),
+                to_file: to.to_string(),
+                import_type: ImportType::Named,
+                imported_symbols: Vec::new(),
+                line_number: None,
+            }).collect(),
+            exports: Vec::new(),
+            imports: Vec::new(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_all_circular_dependencies() {
+        let files = ["a", "b", "c", "d", "e"];
+        // a <-> b, a -> b -> c -> a, c -> d -> c と、どの閉路にも入らない e
+        let edges = [("a", "b"), ("b", "a"), ("b", "c"), ("c", "a"), ("c", "d"), ("d", "c"), ("d", "e"), ("a", "b")];
+        let graph = graph(&files, &edges);
+
+        let cycles = DependencyGraphAnalyzer::new().find_circular_dependencies(&graph).unwrap();
+        let paths: Vec<String> = cycles.iter().map(|cycle| cycle.cycle.join(">")).collect();
+        assert_eq!(paths, vec!["a>b>a", "c>d>c", "a>b>c>a"]);
+        assert!(matches!(cycles[0].severity, CycleSeverity::Critical));
+        assert!(matches!(cycles[2].severity, CycleSeverity::Warning));
+
+        let capped = DependencyGraphAnalyzer::new().with_max_cycles(2).find_circular_dependencies(&graph).unwrap();
+        assert_eq!(capped.len(), 2);
+    }
+
+    #[test]
+    fn test_external_packages_and_analyses() {
+        let import = |file_path: &str, source_module: &str, symbol_name: &str| Import {
+            file_path: file_path.to_string(),
+            symbol_name: symbol_name.to_string(),
+            source_module: source_module.to_string(),
+            import_type: ImportType::Named,
+            line_number: None,
+        };
+        let mut base = graph(&["src/a.ts", "src/b.ts", "src/legacy.js"], &[("src/a.ts", "src/b.ts")]);
+        base.imports = vec![
+            import("src/a.ts", "@angular/core", "Component"),
+            import("src/a.ts", "@angular/core/testing", "TestBed"),
+            import("src/b.ts", "rxjs/operators", "map"),
+            import("src/a.ts", "./b", "B"),
+        ];
+
+        let analyzer = DependencyGraphAnalyzer::new().with_analyses(false, true, false, false).with_top_count(1);
+        let graph = analyzer.add_external_packages(base);
+        let externals: Vec<&str> = graph.files.iter()
+            .filter(|file| matches!(file.file_type, FileType::External))
+            .map(|file| file.relative_path.as_str())
+            .collect();
+        assert_eq!(externals, vec!["@angular/core", "rxjs"]);
+        let angular = graph.dependencies.iter().find(|dep| dep.to_file == "external:@angular/core").unwrap();
+        assert_eq!(angular.imported_symbols, vec!["Component", "TestBed"]);
+
+        let filter = GraphFilter { extensions: vec!["ts".to_string()], ..Default::default() };
+        let filtered = analyzer.filter_graph(graph, &filter).unwrap();
+        assert_eq!(filtered.files.len(), 4);
+
+        let analysis = analyzer.analyze_dependencies(&filtered).unwrap();
+        assert!(analysis.dependency_depth.is_empty());
+        assert!(analysis.longest_chains.is_empty());
+        assert_eq!(analysis.most_imported_files.len(), 1);
+    }
+
+    #[test]
+    fn test_find_orphaned_files_from_entry_points() {
+        let files = ["src/main.ts", "src/app.ts", "src/admin.ts", "src/dead.ts", "src/also-dead.ts"];
+        let edges = [("src/main.ts", "src/app.ts"), ("src/also-dead.ts", "src/dead.ts")];
+        let mut graph = graph(&files, &edges);
+        let analyzer = DependencyGraphAnalyzer::new();
+
+        // 起点がなければ、import されておらず export もしていないファイルだけ
+        assert_eq!(analyzer.find_orphaned_files(&graph).unwrap().len(), 3);
+
+        // admin.ts は遅延ロード先として起点に入る。import されている dead.ts も起点から届かなければ孤立
+        graph.entry_points = vec!["src/main.ts".to_string(), "src/admin.ts".to_string()];
+        assert_eq!(analyzer.find_orphaned_files(&graph).unwrap(), vec!["src/dead.ts", "src/also-dead.ts"]);
+    }
+
+    #[test]
+    fn test_dynamic_imports_are_lazy_boundaries() {
+        let mut graph = graph(&["src/main.ts", "src/routes.ts", "src/admin.ts"], &[("src/main.ts", "src/routes.ts"), ("src/admin.ts", "src/routes.ts")]);
+        graph.dependencies.push(Dependency {
+            from_file: "src/routes.ts".to_string(),
+            to_file: "src/admin.ts".to_string(),
+            import_type: ImportType::Dynamic,
+            imported_symbols: vec!["*".to_string()],
+            line_number: Some(3),
+        });
+        graph.entry_points = vec!["src/main.ts".to_string()];
+
+        let analysis = DependencyGraphAnalyzer::new().analyze_dependencies(&graph).unwrap();
+        assert!(analysis.circular_dependencies.is_empty());
+        assert!(analysis.orphaned_files.is_empty());
+        assert_eq!(analysis.dependency_depth.get("src/main.ts"), Some(&1));
+    }
+
+    #[test]
+    fn test_filter_graph() {
+        let analyzer = DependencyGraphAnalyzer::new();
+        let files = ["src/a.ts", "src/b.ts", "src/shared.ts", "src/leaf.ts", "src/a.spec.ts"];
+        let edges = [
+            ("src/a.ts", "src/shared.ts"),
+            ("src/b.ts", "src/shared.ts"),
+            ("src/a.ts", "src/leaf.ts"),
+            ("src/a.spec.ts", "src/a.ts"),
+        ];
+
+        let filter = GraphFilter { exclude_patterns: vec!["**/*.spec.ts".to_string()], ..Default::default() };
+        let filtered = analyzer.filter_graph(graph(&files, &edges), &filter).unwrap();
+        assert_eq!(filtered.files.len(), 4);
+        assert_eq!(filtered.dependencies.len(), 3);
+
+        let filter = GraphFilter { min_fan_in: 2, ..Default::default() };
+        let filtered = analyzer.filter_graph(graph(&files, &edges), &filter).unwrap();
+        let ids: Vec<&str> = filtered.files.iter().map(|f| f.id.as_str()).collect();
+        assert_eq!(ids, vec!["src/a.ts", "src/b.ts", "src/shared.ts"]);
+
+        let filter = GraphFilter { hide_leaf_nodes: true, ..Default::default() };
+        let filtered = analyzer.filter_graph(graph(&files, &edges), &filter).unwrap();
+        assert!(filtered.files.iter().all(|f| f.id != "src/leaf.ts"));
+        assert!(filtered.files.iter().any(|f| f.id == "src/shared.ts"));
+        assert_eq!(filtered.dependencies.len(), 3);
+
+        // shared.ts を import しているファイルと、さらにそれを import しているファイルまで
+        let filter = GraphFilter { focus: vec!["src/shared.ts".to_string()], fan_in: 2, fan_out: 0, ..Default::default() };
+        let filtered = analyzer.filter_graph(graph(&files, &edges), &filter).unwrap();
+        let mut ids: Vec<&str> = filtered.files.iter().map(|f| f.id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, vec!["src/a.spec.ts", "src/a.ts", "src/b.ts", "src/shared.ts"]);
+
+        let filter = GraphFilter { focus: vec!["**/a.ts".to_string()], fan_in: 0, fan_out: 1, ..Default::default() };
+        let filtered = analyzer.filter_graph(graph(&files, &edges), &filter).unwrap();
+        assert_eq!(filtered.files.len(), 3);
+        assert_eq!(filtered.dependencies.len(), 2);
+
+        let filter = GraphFilter { focus: vec!["src/missing".to_string()], ..Default::default() };
+        assert!(analyzer.filter_graph(graph(&files, &edges), &filter).is_err());
+    }
+
+    #[test]
+    fn test_longest_import_chains() {
+        let files = ["main.ts", "a.ts", "b.ts", "c.ts", "d.ts", "x.ts"];
+        let edges = [
+            ("main.ts", "a.ts"),
+            ("a.ts", "b.ts"),
+            ("b.ts", "c.ts"),
+            ("c.ts", "b.ts"),
+            ("c.ts", "d.ts"),
+            ("x.ts", "d.ts"),
+        ];
+
+        let chains = longest_import_chains(&graph(&files, &edges), 10);
+
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[0].depth, 3);
+        assert_eq!(chains[0].file_path, "main.ts");
+        assert_eq!(chains[0].path, vec!["main.ts", "a.ts", "{b.ts, c.ts}", "d.ts"]);
+        assert_eq!(chains[1].path, vec!["x.ts", "d.ts"]);
+    }
+}
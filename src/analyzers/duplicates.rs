@@ -0,0 +1,226 @@
+use super::{Analyzer, AnalysisResult};
+use crate::ast::{DeclaredSymbol, NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority};
+use crate::config::{build_glob_set, relative_issue_path};
+use crate::parsers::entry_points::ENTRY_FILE_GLOBS;
+use std::sync::Arc;
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+pub struct DuplicatesAnalyzer {
+    ignored_symbols: Vec<String>,
+    identical_only: bool,
+}
+
+// 同じ名前で複数のファイルに宣言されたシンボル
+struct SymbolCluster<'a> {
+    name: &'a str,
+    symbols: Vec<&'a DeclaredSymbol>,
+    // 本体のハッシュが一致するコピーの数（1 つ目を除く）と、その行数
+    identical_copies: usize,
+    duplicated_lines: u32,
+}
+
+impl DuplicatesAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            ignored_symbols: Vec::new(),
+            identical_only: false,
+        }
+    }
+
+    pub fn with_ignored_symbols(mut self, ignored_symbols: Vec<String>) -> Self {
+        self.ignored_symbols = ignored_symbols;
+        self
+    }
+
+    pub fn with_identical_only(mut self, identical_only: bool) -> Self {
+        self.identical_only = identical_only;
+        self
+    }
+
+    // テストや設定ファイルと ignore に一致するシンボル・ファイルは除く
+    fn clusters<'a>(&self, project: &'a NgProject) -> Result<Vec<SymbolCluster<'a>>> {
+        let Some(graph) = &project.import_graph else {
+            return Ok(Vec::new());
+        };
+        let tool_files = build_glob_set(&ENTRY_FILE_GLOBS.map(String::from))?;
+        let ignore = build_glob_set(&self.ignored_symbols)?;
+
+        let mut by_name: BTreeMap<&str, Vec<&DeclaredSymbol>> = BTreeMap::new();
+        for symbol in &graph.declared_symbols {
+            let relative_path = relative_issue_path(&symbol.file_path, &project.root_path);
+            if tool_files.is_match(&relative_path) || ignore.is_match(&relative_path) || ignore.is_match(&symbol.name) {
+                continue;
+            }
+            let symbols = by_name.entry(symbol.name.as_str()).or_default();
+            // 関数のオーバーロードなど同じファイル内の宣言は 1 つにまとめる
+            if !symbols.iter().any(|existing| existing.file_path == symbol.file_path) {
+                symbols.push(symbol);
+            }
+        }
+
+        let mut clusters = Vec::new();
+        for (name, symbols) in by_name {
+            if symbols.len() < 2 {
+                continue;
+            }
+            let mut by_hash: BTreeMap<&str, Vec<&DeclaredSymbol>> = BTreeMap::new();
+            for symbol in &symbols {
+                by_hash.entry(symbol.body_hash.as_str()).or_default().push(symbol);
+            }
+            let copies: Vec<&Vec<&DeclaredSymbol>> = by_hash.values().filter(|copies| copies.len() > 1).collect();
+            let identical_copies = copies.iter().map(|copies| copies.len() - 1).sum();
+            if self.identical_only && identical_copies == 0 {
+                continue;
+            }
+            // 書式だけが違うコピーは行数も違うので、少ない方で見積もる
+            let duplicated_lines = copies.iter()
+                .map(|copies| copies.iter().map(|symbol| symbol.lines).min().unwrap_or(0) * (copies.len() as u32 - 1))
+                .sum();
+            clusters.push(SymbolCluster { name, symbols, identical_copies, duplicated_lines });
+        }
+        Ok(clusters)
+    }
+
+    fn analyze_clusters(&self, project: &NgProject, clusters: &[SymbolCluster]) -> Vec<Issue> {
+        clusters.iter()
+            .map(|cluster| {
+                let mut kinds: Vec<&str> = cluster.symbols.iter().map(|symbol| symbol.kind.as_str()).collect();
+                kinds.sort_unstable();
+                kinds.dedup();
+                let files: Vec<String> = cluster.symbols.iter().map(|symbol| relative_issue_path(&symbol.file_path, &project.root_path)).collect();
+                let copies = if cluster.identical_copies > 0 {
+                    format!(
+                        "; {} of them are identical copies (~{} duplicated lines). Move it into a shared library and import it from there.",
+                        cluster.identical_copies + 1,
+                        cluster.duplicated_lines
+                    )
+                } else {
+                    ". Check whether one was copied from the other, or rename them if they are unrelated.".to_string()
+                };
+                Issue {
+                    severity: if cluster.identical_copies > 0 { Severity::Warning } else { Severity::Info },
+                    rule: "duplicate-symbol".to_string(),
+                    message: format!(
+                        "'{}' ({}) is declared in {} files: {}{}",
+                        cluster.name,
+                        kinds.join("/"),
+                        files.len(),
+                        files.join(", "),
+                        copies
+                    ),
+                    file_path: cluster.symbols[0].file_path.clone(),
+                    line: cluster.symbols[0].line,
+                    column: None,
+                    fingerprint: None,
+                }
+            })
+            .collect()
+    }
+
+    fn generate_duplicate_recommendations(&self, clusters: &[SymbolCluster], duplicated_lines: u32) -> Vec<Recommendation> {
+        let identical = clusters.iter().filter(|cluster| cluster.identical_copies > 0).count();
+        if identical == 0 {
+            return Vec::new();
+        }
+
+        vec![Recommendation {
+            category: "Architecture".to_string(),
+            title: "Consolidate Copy-Pasted Symbols".to_string(),
+            description: format!(
+                "{} symbol(s) are copied verbatim between files (~{} duplicated lines). Extract them into a shared library so fixes land in one place.",
+                identical, duplicated_lines
+            ),
+            priority: Priority::Medium,
+            file_path: None,
+        }]
+    }
+}
+
+impl Analyzer for DuplicatesAnalyzer {
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
+        let clusters = self.clusters(project)?;
+        let duplicated_lines = clusters.iter().map(|cluster| cluster.duplicated_lines).sum();
+        let issues = self.analyze_clusters(project, &clusters);
+        let recommendations = self.generate_duplicate_recommendations(&clusters, duplicated_lines);
+
+        Ok(AnalysisResult {
+            project: project.clone(),
+            issues,
+            metrics: ProjectMetrics {
+                total_components: project.components.len() as u32,
+                total_services: project.services.len() as u32,
+                total_modules: project.modules.len() as u32,
+                lines_of_code: project.files.iter().map(|f| f.lines.logical).sum(),
+                test_coverage: project.test_coverage,
+                duplicated_lines: project.import_graph.as_ref().map(|_| duplicated_lines),
+                ..Default::default()
+            },
+            recommendations,
+            omitted_issues: vec![],
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "duplicates"
+    }
+
+    fn description(&self) -> &'static str {
+        "Finds classes, interfaces and functions with the same name exported from several files"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::ImportExportGraph;
+    use std::path::PathBuf;
+
+    fn symbol(file_path: &str, name: &str, hash: &str, lines: u32) -> DeclaredSymbol {
+        DeclaredSymbol {
+            file_path: file_path.to_string(),
+            name: name.to_string(),
+            kind: "function".to_string(),
+            line: Some(1),
+            lines,
+            body_hash: hash.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_duplicate_symbols() {
+        let project = NgProject {
+            root_path: PathBuf::from("/work"),
+            import_graph: Some(ImportExportGraph {
+                declared_symbols: vec![
+                    symbol("/work/libs/a/utils.ts", "formatDate", "1", 10),
+                    symbol("/work/libs/b/utils.ts", "formatDate", "1", 10),
+                    symbol("/work/libs/c/date.ts", "formatDate", "1", 10),
+                    symbol("/work/libs/a/user.ts", "User", "2", 3),
+                    symbol("/work/libs/b/user.ts", "User", "3", 4),
+                    symbol("/work/libs/b/user.spec.ts", "toUser", "4", 4),
+                    symbol("/work/libs/b/user.ts", "toUser", "4", 4),
+                    symbol("/work/apps/shop/app.component.ts", "AppComponent", "5", 20),
+                    symbol("/work/apps/admin/app.component.ts", "AppComponent", "6", 20),
+                ],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let analyzer = DuplicatesAnalyzer::new().with_ignored_symbols(vec!["AppComponent".to_string()]);
+        let clusters = analyzer.clusters(&project).unwrap();
+        let found: Vec<(&str, usize, usize, u32)> = clusters.iter()
+            .map(|cluster| (cluster.name, cluster.symbols.len(), cluster.identical_copies, cluster.duplicated_lines))
+            .collect();
+        assert_eq!(found, vec![("User", 2, 0, 0), ("formatDate", 3, 2, 20)]);
+
+        let issues = analyzer.analyze_clusters(&project, &clusters);
+        assert!(matches!(issues[0].severity, Severity::Info));
+        assert!(matches!(issues[1].severity, Severity::Warning));
+        assert!(issues[1].message.contains("libs/a/utils.ts, libs/b/utils.ts, libs/c/date.ts"));
+
+        let identical = analyzer.with_identical_only(true).clusters(&project).unwrap();
+        assert_eq!(identical.len(), 1);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::analyzers::dependency_graph::DependencyGraphAnalyzer;
+use crate::ast::{ChangeDetectionStrategy, HealthCategory, HealthScore, NgProject, Severity};
+use crate::config::HealthWeights;
+use crate::output::summary::RunSummary;
+use anyhow::Result;
+
+// これを超えた分だけ、コンポーネントごとの複雑度の点を 1 につき 10 点下げる
+const COMPLEXITY_THRESHOLD: u32 = 10;
+// 1000 行あたりの重み付き issue 数がこの値のとき 50 点
+const HALF_SCORE_DENSITY: f64 = 10.0;
+
+// issue の密度や --summary top のファイルの順位に使う重み
+pub fn issue_weight(severity: &Severity) -> u32 {
+    match severity {
+        Severity::Error => 10,
+        Severity::Warning => 3,
+        Severity::Info => 1,
+    }
+}
+
+// 評価できないカテゴリ（コンポーネントがない・カバレッジや import グラフを読み込んでいない）と重み 0 のカテゴリは除く
+pub fn compute_health(project: &NgProject, summary: &RunSummary, weights: &HealthWeights) -> Result<HealthScore> {
+    let mut categories = Vec::new();
+    let components = &project.components;
+
+    let lines: u32 = project.files.iter().map(|file| file.lines.logical).sum();
+    let weighted_issues = (summary.errors as u32 * issue_weight(&Severity::Error)
+        + summary.warnings as u32 * issue_weight(&Severity::Warning)
+        + summary.infos as u32 * issue_weight(&Severity::Info)) as f64;
+    let density = if lines == 0 { 0.0 } else { weighted_issues * 1000.0 / lines as f64 };
+    categories.push(HealthCategory {
+        category: "issue_density".to_string(),
+        score: 100.0 * HALF_SCORE_DENSITY / (HALF_SCORE_DENSITY + density),
+        weight: weights.issue_density,
+        detail: format!("{:.1} weighted issues per 1000 lines", density),
+    });
+
+    if !components.is_empty() {
+        let score = components.iter()
+            .map(|c| 100.0 - (c.complexity_score.saturating_sub(COMPLEXITY_THRESHOLD) as f64 * 10.0).min(100.0))
+            .sum::<f64>() / components.len() as f64;
+        let over = components.iter().filter(|c| c.complexity_score > COMPLEXITY_THRESHOLD).count();
+        categories.push(HealthCategory {
+            category: "complexity".to_string(),
+            score,
+            weight: weights.complexity,
+            detail: format!("{} of {} components above complexity {}", over, components.len(), COMPLEXITY_THRESHOLD),
+        });
+
+        let on_push = components.iter().filter(|c| matches!(c.change_detection, ChangeDetectionStrategy::OnPush)).count();
+        categories.push(HealthCategory {
+            category: "onpush".to_string(),
+            score: on_push as f64 * 100.0 / components.len() as f64,
+            weight: weights.onpush,
+            detail: format!("{} of {} components use OnPush", on_push, components.len()),
+        });
+    }
+
+    if let Some(graph) = &project.import_graph {
+        let cycles = DependencyGraphAnalyzer::new()
+            .with_analyses(true, false, false, false)
+            .analyze_dependencies(graph)?
+            .circular_dependencies
+            .len();
+        categories.push(HealthCategory {
+            category: "cycles".to_string(),
+            score: (100.0 - cycles as f64 * 10.0).max(0.0),
+            weight: weights.cycles,
+            detail: format!("{} import cycles", cycles),
+        });
+    }
+
+    if let Some(coverage) = project.test_coverage {
+        categories.push(HealthCategory {
+            category: "coverage".to_string(),
+            score: coverage,
+            weight: weights.coverage,
+            detail: format!("{:.1}% line coverage", coverage),
+        });
+    }
+
+    categories.retain(|category| category.weight > 0.0);
+    let total_weight: f64 = categories.iter().map(|category| category.weight).sum();
+    let score = if total_weight == 0.0 {
+        100.0
+    } else {
+        categories.iter().map(|category| category.score * category.weight).sum::<f64>() / total_weight
+    };
+
+    Ok(HealthScore { score, grade: grade(score).to_string(), categories })
+}
+
+fn grade(score: f64) -> &'static str {
+    match score {
+        s if s >= 90.0 => "A",
+        s if s >= 80.0 => "B",
+        s if s >= 70.0 => "C",
+        s if s >= 60.0 => "D",
+        _ => "F",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::{ComponentBuilder, NgProjectBuilder};
+    use crate::ast::{LineCounts, SourceFile};
+
+    #[test]
+    fn test_compute_health() {
+        let project = NgProjectBuilder::new("/app")
+            .component(ComponentBuilder::new("UserListComponent").complexity(14).on_push())
+            .component(ComponentBuilder::new("UserCardComponent").complexity(3))
+            .with(|project| {
+                project.files = vec![SourceFile {
+                    file_path: "/app/user-list.component.ts".to_string(),
+                    lines: LineCounts { logical: 2000, ..Default::default() },
+                    minified: false,
+                }];
+                project.test_coverage = Some(70.0);
+            })
+            .build();
+        let summary = RunSummary { errors: 1, warnings: 2, infos: 4, files: 1, failed_budgets: 0 };
+
+        let health = compute_health(&project, &summary, &HealthWeights::default()).unwrap();
+        let scores: Vec<(&str, f64)> = health.categories.iter().map(|c| (c.category.as_str(), c.score)).collect();
+        // 20 点 / 2000 行 = 1000 行あたり 10
+        assert_eq!(scores, vec![("issue_density", 50.0), ("complexity", 80.0), ("onpush", 50.0), ("coverage", 70.0)]);
+        // (50*30 + 80*20 + 50*20 + 70*15) / 85
+        assert!((health.score - 5150.0 / 85.0).abs() < 1e-9);
+        assert_eq!(health.grade, "D");
+
+        let weights = HealthWeights { issue_density: 0.0, onpush: 0.0, coverage: 0.0, ..Default::default() };
+        let health = compute_health(&project, &summary, &weights).unwrap();
+        assert_eq!((health.score, health.grade.as_str()), (80.0, "B"));
+    }
+}
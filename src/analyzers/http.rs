@@ -0,0 +1,200 @@
+use super::{Analyzer, AnalysisResult};
+use crate::ast::{NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority, HttpUsage};
+use std::sync::Arc;
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+pub struct HttpAnalyzer;
+
+impl HttpAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    // HttpClient を使うコンポーネントとサービスを (名前, ファイル, 利用状況) で列挙する
+    fn http_users<'a>(&self, project: &'a NgProject) -> Vec<(&'a str, &'a str, &'a HttpUsage)> {
+        project.components.iter()
+            .map(|c| (c.name.as_str(), c.file_path.as_str(), &c.http))
+            .chain(project.services.iter().map(|s| (s.name.as_str(), s.file_path.as_str(), &s.http)))
+            .filter(|(_, _, http)| http.injected)
+            .collect()
+    }
+
+    fn analyze_components(&self, project: &NgProject) -> Vec<Issue> {
+        project.components.iter()
+            .filter(|component| component.http.injected)
+            .map(|component| Issue {
+                severity: Severity::Warning,
+                rule: "http-client-in-component".to_string(),
+                message: format!(
+                    "'{}' injects HttpClient directly. Move the requests into a service so they can be shared, cached and tested.",
+                    component.name
+                ),
+                file_path: component.file_path.clone(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            })
+            .collect()
+    }
+
+    fn analyze_requests(&self, project: &NgProject) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        for (_, file_path, http) in self.http_users(project) {
+            for request in &http.requests {
+                let url = request.url.as_deref().unwrap_or("?");
+
+                if !request.error_handled {
+                    issues.push(Issue {
+                        severity: Severity::Warning,
+                        rule: "http-missing-error-handling".to_string(),
+                        message: format!(
+                            "{} request to '{}' has no catchError in its pipe and no error callback in subscribe",
+                            request.method.to_uppercase(), url
+                        ),
+                        file_path: file_path.to_string(),
+                        line: None,
+                        column: None,
+                        fingerprint: None,
+                    });
+                }
+
+                if is_hardcoded_url(url) {
+                    issues.push(Issue {
+                        severity: Severity::Warning,
+                        rule: "hardcoded-http-url".to_string(),
+                        message: format!("Request URL '{}' is hard-coded. Read the base URL from environment config instead.", url),
+                        file_path: file_path.to_string(),
+                        line: None,
+                        column: None,
+                        fingerprint: None,
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    // 同じエンドポイントを呼んでいるクラスをまとめる
+    fn duplicate_endpoints<'a>(&self, project: &'a NgProject) -> BTreeMap<&'a str, Vec<(&'a str, &'a str)>> {
+        let mut endpoints: BTreeMap<&str, Vec<(&str, &str)>> = BTreeMap::new();
+
+        for (name, file_path, http) in self.http_users(project) {
+            for url in http.requests.iter().filter_map(|request| request.url.as_deref()) {
+                if !has_static_path(url) {
+                    continue;
+                }
+                let callers = endpoints.entry(url).or_default();
+                if !callers.iter().any(|(caller, _)| *caller == name) {
+                    callers.push((name, file_path));
+                }
+            }
+        }
+
+        endpoints.retain(|_, callers| callers.len() > 1);
+        endpoints
+    }
+
+    fn analyze_duplicate_endpoints(&self, duplicates: &BTreeMap<&str, Vec<(&str, &str)>>) -> Vec<Issue> {
+        duplicates.iter()
+            .map(|(url, callers)| Issue {
+                severity: Severity::Info,
+                rule: "duplicate-http-endpoint".to_string(),
+                message: format!(
+                    "Endpoint '{}' is requested from {} classes: {}",
+                    url,
+                    callers.len(),
+                    callers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ")
+                ),
+                file_path: callers[0].1.to_string(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            })
+            .collect()
+    }
+
+    fn generate_http_recommendations(&self, duplicates: &BTreeMap<&str, Vec<(&str, &str)>>) -> Vec<Recommendation> {
+        if duplicates.is_empty() {
+            return Vec::new();
+        }
+
+        vec![Recommendation {
+            category: "HTTP".to_string(),
+            title: "Consolidate API Endpoints".to_string(),
+            description: format!(
+                "{} endpoint(s) are requested from several classes. Move each endpoint into a single API service: {}",
+                duplicates.len(),
+                duplicates.keys().copied().collect::<Vec<_>>().join(", ")
+            ),
+            priority: Priority::Medium,
+            file_path: None,
+        }]
+    }
+}
+
+fn is_hardcoded_url(url: &str) -> bool {
+    ["http://", "https://", "//"].iter().any(|scheme| url.starts_with(scheme))
+}
+
+// ${url} のように引数をそのまま渡しているだけのものは比較しない
+fn has_static_path(url: &str) -> bool {
+    let mut static_part = String::new();
+    let mut rest = url;
+    while let Some(start) = rest.find("${") {
+        static_part.push_str(&rest[..start]);
+        rest = rest[start..].find('}').map(|end| &rest[start + end + 1..]).unwrap_or("");
+    }
+    static_part.push_str(rest);
+    !static_part.trim_matches('/').is_empty()
+}
+
+impl Analyzer for HttpAnalyzer {
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
+        let duplicates = self.duplicate_endpoints(project);
+
+        let mut issues = self.analyze_components(project);
+        issues.extend(self.analyze_requests(project));
+        issues.extend(self.analyze_duplicate_endpoints(&duplicates));
+        let recommendations = self.generate_http_recommendations(&duplicates);
+
+        Ok(AnalysisResult {
+            project: project.clone(),
+            issues,
+            metrics: ProjectMetrics {
+                total_components: project.components.len() as u32,
+                total_services: project.services.len() as u32,
+                total_modules: project.modules.len() as u32,
+                lines_of_code: project.files.iter().map(|f| f.lines.logical).sum(),
+                test_coverage: project.test_coverage,
+                ..Default::default()
+            },
+            recommendations,
+            omitted_issues: vec![],
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    fn description(&self) -> &'static str {
+        "Tracks HttpClient usage: direct use in components, error handling, hard-coded URLs and duplicated endpoints"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url_helpers() {
+        assert!(is_hardcoded_url("https://api.example.com/users"));
+        assert!(!is_hardcoded_url("${environment.apiUrl}/users"));
+        assert!(has_static_path("${environment.apiUrl}/users/${id}"));
+        assert!(!has_static_path("${url}"));
+        assert!(!has_static_path("${this.base}/${path}"));
+    }
+}
@@ -0,0 +1,213 @@
+use super::bundle_estimate::estimate_bundles;
+use super::dependency_graph::matching_file_ids;
+use crate::ast::{AffectedFile, FileInfo, ImpactReport, ImportExportGraph, NgProject, TouchedChunk};
+use crate::output::i18n::Lang;
+use crate::parsers::typescript::resolve_relative_path;
+use anyhow::Result;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::path::Path;
+
+const RESOURCE_EXTENSIONS: [&str; 5] = ["html", "css", "scss", "sass", "less"];
+
+// target はファイルのパス・ディレクトリ・glob、export しているシンボル、コンポーネントのセレクタ。
+// テンプレートやスタイルのファイルは、それを使うコンポーネントの変更として扱う
+pub fn analyze_impact(project: &NgProject, graph: &ImportExportGraph, target: &str, lang: Lang) -> Result<ImpactReport> {
+    let files: HashMap<&str, &FileInfo> = graph.files.iter()
+        .filter(|file| !file.id.starts_with("external:"))
+        .map(|file| (file.id.as_str(), file))
+        .collect();
+    let targets = target_files(project, graph, target)?;
+    if targets.is_empty() {
+        return Err(anyhow::anyhow!("{}", lang.trf("error.impact_target", &[&target])));
+    }
+
+    // 動的 import も、import している側のチャンクを作り直すので辿る
+    let mut upstream: HashMap<&str, Vec<&str>> = HashMap::new();
+    for dependency in &graph.dependencies {
+        upstream.entry(dependency.to_file.as_str()).or_default().push(dependency.from_file.as_str());
+    }
+    let mut reached: HashMap<&str, (u32, &str)> = HashMap::new();
+    let mut queue: VecDeque<&str> = targets.iter().copied().collect();
+    while let Some(id) = queue.pop_front() {
+        let depth = reached.get(id).map_or(0, |(depth, _)| *depth);
+        for &importer in upstream.get(id).into_iter().flatten() {
+            if files.contains_key(importer) && !targets.contains(&importer) && !reached.contains_key(importer) {
+                reached.insert(importer, (depth + 1, id));
+                queue.push_back(importer);
+            }
+        }
+    }
+
+    let path = |id: &str| files.get(id).map(|file| file.relative_path.replace('\\', "/")).unwrap_or_default();
+    let mut affected: Vec<AffectedFile> = reached.iter()
+        .map(|(id, (depth, via))| AffectedFile { file_path: path(id), depth: *depth, via: path(via) })
+        .collect();
+    affected.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.file_path.cmp(&b.file_path)));
+
+    let changed: BTreeSet<&str> = targets.iter().copied().chain(reached.keys().copied()).collect();
+    let changed_paths: BTreeSet<String> = changed.iter().map(|id| path(id)).collect();
+
+    // import していなくても、同じ名前の .spec.ts は変更したファイルのテストとみなす
+    let all_paths: BTreeSet<String> = files.values().map(|file| file.relative_path.replace('\\', "/")).collect();
+    let mut tests: BTreeSet<String> = changed_paths.iter().filter(|path| is_spec(path)).cloned().collect();
+    tests.extend(changed_paths.iter()
+        .filter_map(|path| path.strip_suffix(".ts").map(|stem| format!("{}.spec.ts", stem)))
+        .filter(|spec| all_paths.contains(spec)));
+
+    let rebuild_bytes = changed.iter()
+        .filter_map(|id| files.get(id))
+        .filter(|file| !is_spec(&file.relative_path))
+        .filter_map(|file| std::fs::metadata(&file.file_path).ok())
+        .map(|metadata| metadata.len())
+        .sum();
+
+    let estimate = estimate_bundles(project, graph);
+    let chunks: Vec<TouchedChunk> = estimate.chunks.iter()
+        .map(|chunk| TouchedChunk {
+            name: chunk.name.clone(),
+            route_file: chunk.route_file.as_deref().map(|file| crate::config::relative_issue_path(file, &project.root_path)),
+            line: chunk.line,
+            files: chunk.files.iter().filter(|file| changed_paths.contains(&file.replace('\\', "/"))).count(),
+        })
+        .filter(|chunk| chunk.files > 0)
+        .collect();
+
+    let mut edges: Vec<(String, String)> = graph.dependencies.iter()
+        .filter(|dependency| changed.contains(dependency.from_file.as_str()) && changed.contains(dependency.to_file.as_str()))
+        .map(|dependency| (path(&dependency.from_file), path(&dependency.to_file)))
+        .collect();
+    edges.sort();
+    edges.dedup();
+
+    let risk = risk(changed.len(), files.len(), &chunks);
+    let mut targets: Vec<String> = targets.iter().map(|id| path(id)).collect();
+    targets.sort();
+
+    Ok(ImpactReport {
+        target: target.to_string(),
+        targets,
+        affected,
+        tests: tests.into_iter().collect(),
+        projects: nx_projects(project, &changed, &files),
+        chunks,
+        rebuild_bytes,
+        risk: risk.to_string(),
+        edges,
+    })
+}
+
+fn target_files<'a>(project: &NgProject, graph: &'a ImportExportGraph, target: &str) -> Result<Vec<&'a str>> {
+    let normalized = target.replace('\\', "/");
+    let normalized = normalized.trim_start_matches("./");
+    let by_path = |file_path: &str| graph.files.iter()
+        .find(|file| file.file_path.replace('\\', "/") == file_path.replace('\\', "/"))
+        .map(|file| file.id.as_str());
+
+    // テンプレート・スタイルは、templateUrl・styleUrls で参照しているコンポーネント
+    if Path::new(normalized).extension().and_then(|ext| ext.to_str()).is_some_and(|ext| RESOURCE_EXTENSIONS.contains(&ext)) {
+        return Ok(project.components.iter()
+            .filter(|component| component.template_url.iter().chain(&component.style_urls).any(|url| {
+                let resource = resolve_relative_path(Path::new(&component.file_path), url);
+                resource == normalized || resource.ends_with(&format!("/{}", normalized))
+            }))
+            .filter_map(|component| by_path(&component.file_path))
+            .collect());
+    }
+
+    let mut ids: BTreeSet<&str> = matching_file_ids(graph, &[normalized.to_string()])?.into_iter().collect();
+    // カレントディレクトリからのパスでも指定できる
+    if let Ok(canonical) = std::fs::canonicalize(target) {
+        ids.extend(graph.files.iter()
+            .filter(|file| std::fs::canonicalize(&file.file_path).is_ok_and(|path| path.starts_with(&canonical)))
+            .map(|file| file.id.as_str()));
+    }
+    if ids.is_empty() {
+        // 再エクスポートではなく、宣言しているファイル
+        ids.extend(graph.exports.iter()
+            .filter(|export| export.symbol_name == target && export.source_module.is_none())
+            .filter_map(|export| by_path(&export.file_path)));
+        ids.extend(project.components.iter()
+            .filter(|component| component.selector.as_deref().is_some_and(|selector| selector.split(',').any(|part| part.trim() == target)))
+            .filter_map(|component| by_path(&component.file_path)));
+    }
+    Ok(ids.into_iter().filter(|id| !id.starts_with("external:")).collect())
+}
+
+fn is_spec(path: &str) -> bool {
+    path.ends_with(".spec.ts")
+}
+
+// 変更するファイルの上のディレクトリで、いちばん近い project.json
+fn nx_projects(project: &NgProject, changed: &BTreeSet<&str>, files: &HashMap<&str, &FileInfo>) -> Vec<String> {
+    let mut projects = BTreeSet::new();
+    for file in changed.iter().filter_map(|id| files.get(id)) {
+        let project_dir = Path::new(&file.file_path).ancestors().skip(1)
+            .take_while(|dir| dir.starts_with(&project.root_path))
+            .find(|dir| dir.join("project.json").is_file());
+        if let Some(dir) = project_dir {
+            projects.insert(crate::config::relative_issue_path(&dir.display().to_string(), &project.root_path));
+        }
+    }
+    projects.into_iter().collect()
+}
+
+// 影響するファイルの割合と、初期バンドル・複数のチャンクに及ぶかで見積もる
+fn risk(changed: usize, total: usize, chunks: &[TouchedChunk]) -> &'static str {
+    let share = changed as f64 / total.max(1) as f64;
+    let eager = chunks.iter().any(|chunk| chunk.route_file.is_none());
+    if share >= 0.5 || (eager && share >= 0.25) {
+        "high"
+    } else if eager || chunks.len() > 1 || share >= 0.1 {
+        "medium"
+    } else {
+        "low"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::ProjectParser;
+
+    #[tokio::test]
+    async fn test_analyze_impact() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        let app = src.join("app");
+        std::fs::create_dir_all(app.join("orders")).unwrap();
+        std::fs::create_dir_all(app.join("shared")).unwrap();
+        std::fs::write(src.join("main.ts"), "import { routes } from './app/app.routes';\n").unwrap();
+        std::fs::write(app.join("app.routes.ts"), "export const routes = [\n  { path: 'orders', loadComponent: () => import('./orders/orders.component').then(m => m.OrdersComponent) },\n];\n").unwrap();
+        std::fs::write(app.join("orders/orders.component.ts"), "import { formatPrice } from '../shared/format';\n\n@Component({ selector: 'app-orders', templateUrl: './orders.component.html' })\nexport class OrdersComponent {}\n").unwrap();
+        std::fs::write(app.join("orders/orders.component.html"), "<p>orders</p>\n").unwrap();
+        std::fs::write(app.join("orders/orders.component.spec.ts"), "describe('OrdersComponent', () => {});\n").unwrap();
+        std::fs::write(app.join("shared/format.ts"), "export function formatPrice(price: number) { return `${price}`; }\n").unwrap();
+        std::fs::write(app.join("shared/format.spec.ts"), "import { formatPrice } from './format';\n").unwrap();
+        std::fs::write(app.join("shared/unused.ts"), "export const unused = 1;\n").unwrap();
+
+        let project = ProjectParser::new().parse_project(&dir.path().to_path_buf()).await.unwrap();
+        let graph = project.import_graph.as_ref().unwrap();
+
+        let report = analyze_impact(&project, graph, "formatPrice", Lang::En).unwrap();
+        assert_eq!(report.targets, vec!["src/app/shared/format.ts"]);
+        let affected: Vec<(&str, u32)> = report.affected.iter().map(|file| (file.file_path.as_str(), file.depth)).collect();
+        assert_eq!(affected, vec![
+            ("src/app/orders/orders.component.ts", 1),
+            ("src/app/shared/format.spec.ts", 1),
+            ("src/app/app.routes.ts", 2),
+            ("src/main.ts", 3),
+        ]);
+        assert_eq!(report.tests, vec!["src/app/orders/orders.component.spec.ts", "src/app/shared/format.spec.ts"]);
+        let chunks: Vec<(&str, usize)> = report.chunks.iter().map(|chunk| (chunk.name.as_str(), chunk.files)).collect();
+        assert_eq!(chunks, vec![("eager", 2), ("OrdersComponent", 2)]);
+        assert_eq!(report.risk, "high");
+
+        // テンプレートの変更はコンポーネントの変更として扱う
+        let report = analyze_impact(&project, graph, "orders.component.html", Lang::En).unwrap();
+        assert_eq!(report.targets, vec!["src/app/orders/orders.component.ts"]);
+        assert_eq!(report.affected.len(), 2);
+
+        assert!(analyze_impact(&project, graph, "src/app/shared/unused.ts", Lang::En).unwrap().affected.is_empty());
+        assert!(analyze_impact(&project, graph, "MissingSymbol", Lang::En).is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use crate::ast::{NgClass, NgProject};
+use std::collections::{HashMap, HashSet};
+
+// 名前からクラスを引く。同じ名前が複数あれば最初のもの
+pub fn class_index(project: &NgProject) -> HashMap<&str, &NgClass> {
+    let mut by_name = HashMap::new();
+    for class in &project.classes {
+        by_name.entry(class.name.as_str()).or_insert(class);
+    }
+    by_name
+}
+
+// 自身を除いた祖先。ミックスインは WithDestroy(...) のように 1 段として数え、
+// プロジェクトにない基底クラス（@angular/core など）は名前だけ残してそこで止める
+pub fn ancestors<'a>(class: &'a NgClass, by_name: &HashMap<&str, &'a NgClass>) -> Vec<Ancestor<'a>> {
+    let mut chain = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::from([class.name.as_str()]);
+    let mut current = class;
+    loop {
+        chain.extend(current.heritage.mixins.iter().map(|mixin| Ancestor { name: format!("{}(...)", mixin), class: None }));
+        let Some(base) = current.heritage.base_class.as_deref() else { break };
+        if !seen.insert(base) {
+            break;
+        }
+        let found = by_name.get(base).copied();
+        chain.push(Ancestor { name: base.to_string(), class: found });
+        match found {
+            Some(next) => current = next,
+            None => break,
+        }
+    }
+    chain
+}
+
+pub struct Ancestor<'a> {
+    pub name: String,
+    pub class: Option<&'a NgClass>,
+}
+
+// 他のクラスの基底になっていない、継承の末端のクラス
+pub fn leaf_classes(project: &NgProject) -> Vec<&NgClass> {
+    let bases: HashSet<&str> = project.classes.iter()
+        .filter_map(|class| class.heritage.base_class.as_deref())
+        .collect();
+    project.classes.iter()
+        .filter(|class| class.heritage.extends() && !bases.contains(class.name.as_str()))
+        .collect()
+}
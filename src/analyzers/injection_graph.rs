@@ -0,0 +1,148 @@
+use super::dependency_graph::strongly_connected_components;
+use super::module_graph::{cycle_path, lazy_route_matches};
+use crate::ast::{InjectionEdge, InjectionGraph, InjectionNode, NgProject, ProviderOverride};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+pub fn build_injection_graph(project: &NgProject) -> InjectionGraph {
+    let classes: Vec<(&str, &str, &str, &[String])> = project.components.iter()
+        .map(|component| (component.name.as_str(), "component", component.file_path.as_str(), component.dependencies.as_slice()))
+        .chain(project.services.iter().map(|service| (service.name.as_str(), "service", service.file_path.as_str(), service.dependencies.as_slice())))
+        .collect();
+
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (i, (name, ..)) in classes.iter().enumerate() {
+        index.entry(*name).or_insert(i);
+    }
+
+    // Router や HttpClient などプロジェクト外のサービスは辺にしない
+    let mut edges = Vec::new();
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); classes.len()];
+    for (from, (name, _, _, dependencies)) in classes.iter().enumerate() {
+        if index.get(name) != Some(&from) {
+            continue;
+        }
+        for dependency in dependencies.iter() {
+            let Some(&to) = index.get(dependency.as_str()) else { continue };
+            if classes[to].1 != "service" || adjacency[from].contains(&to) {
+                continue;
+            }
+            adjacency[from].push(to);
+            edges.push(InjectionEdge {
+                from: name.to_string(),
+                to: dependency.clone(),
+            });
+        }
+    }
+
+    let nodes = classes.iter().enumerate()
+        .filter(|(i, (name, ..))| index.get(name) == Some(i))
+        .map(|(_, (name, kind, file_path, _))| {
+            let service = project.services.iter().find(|service| service.name == *name && *kind == "service");
+            let mut injectors: Vec<String> = service.and_then(|service| service.provided_in.clone()).into_iter().collect();
+            if *kind == "service" {
+                injectors.extend(project.modules.iter().filter(|module| module.providers.iter().any(|p| p == name)).map(|module| module.name.clone()));
+                injectors.extend(project.components.iter().filter(|component| component.providers.iter().any(|p| p == name)).map(|component| component.name.clone()));
+            }
+
+            let single_feature = if service.is_some_and(|service| service.provided_in.as_deref() == Some("root")) {
+                let features: BTreeSet<Option<String>> = edges.iter()
+                    .filter(|edge| edge.to == *name)
+                    .filter_map(|edge| index.get(edge.from.as_str()).map(|&from| feature_of(classes[from].2)))
+                    .collect();
+                match features.into_iter().collect::<Vec<_>>().as_slice() {
+                    [Some(feature)] => Some(feature.clone()),
+                    _ => None,
+                }
+            } else {
+                None
+            };
+
+            InjectionNode {
+                name: name.to_string(),
+                kind: kind.to_string(),
+                file_path: file_path.to_string(),
+                injectors,
+                single_feature,
+            }
+        })
+        .collect();
+
+    let mut cycles: Vec<Vec<String>> = strongly_connected_components(&adjacency).into_iter()
+        .filter(|members| members.len() > 1 || adjacency[members[0]].contains(&members[0]))
+        .map(|members| {
+            let start = *members.iter().min().unwrap();
+            let mut cycle: Vec<String> = cycle_path(start, &members, &adjacency).into_iter()
+                .map(|i| classes[i].0.to_string())
+                .collect();
+            // ファイルの読み込み順によらないよう、名前の最も小さいクラスから始める
+            let first = cycle.iter().enumerate().min_by_key(|(_, name)| name.as_str()).map(|(i, _)| i).unwrap_or(0);
+            cycle.rotate_left(first);
+            cycle
+        })
+        .collect();
+    cycles.sort();
+
+    let mut provider_overrides = project.provider_overrides.clone();
+    provider_overrides.sort_by(|a, b| a.token.cmp(&b.token).then_with(|| a.file_path.cmp(&b.file_path)).then_with(|| a.line.cmp(&b.line)));
+
+    InjectionGraph { nodes, edges, cycles, provider_overrides }
+}
+
+// bootstrapApplication や ApplicationConfig、遅延読み込みされない NgModule の providers はルートインジェクターに入る
+pub fn is_root_injector(project: &NgProject, provider_override: &ProviderOverride) -> bool {
+    match provider_override.scope.as_str() {
+        "application" => true,
+        "module" => !project.modules.iter()
+            .filter(|module| module.name == provider_override.injector)
+            .any(|module| project.lazy_routes.iter().any(|route| lazy_route_matches(route, module))),
+        _ => false,
+    }
+}
+
+// src/app/orders/list/list.component.ts なら orders。app 直下のファイルは機能に属さない
+fn feature_of(file_path: &str) -> Option<String> {
+    let segments: Vec<String> = Path::new(file_path).components()
+        .map(|component| component.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let app = segments.iter().position(|segment| segment == "app")?;
+    // 最後の要素はファイル名
+    segments.get(app + 1).filter(|_| app + 2 < segments.len()).cloned()
+}
+
+impl InjectionGraph {
+    pub fn multi_provided(&self) -> impl Iterator<Item = &InjectionNode> {
+        self.nodes.iter().filter(|node| node.injectors.len() > 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::ProjectParser;
+
+    #[tokio::test]
+    async fn test_build_injection_graph() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("src/app");
+        std::fs::create_dir_all(app.join("orders")).unwrap();
+        std::fs::write(app.join("session.service.ts"), "@Injectable({ providedIn: 'root' })\nexport class SessionService {\n  constructor(private audit: AuditService) {}\n}\n").unwrap();
+        std::fs::write(app.join("audit.service.ts"), "@Injectable({ providedIn: 'root' })\nexport class AuditService {\n  private session = inject(SessionService);\n}\n").unwrap();
+        std::fs::write(app.join("orders/orders.service.ts"), "@Injectable({ providedIn: 'root' })\nexport class OrdersService {\n  constructor(private http: HttpClient) {}\n}\n").unwrap();
+        std::fs::write(
+            app.join("orders/orders.component.ts"),
+            "@Component({ selector: 'app-orders', template: '', providers: [SessionService] })\nexport class OrdersComponent {\n  private orders = inject(OrdersService);\n  constructor(session: SessionService) {}\n}\n",
+        ).unwrap();
+
+        let project = ProjectParser::new().parse_project(&dir.path().to_path_buf()).await.unwrap();
+        let graph = build_injection_graph(&project);
+        let node = |name: &str| graph.nodes.iter().find(|node| node.name == name).unwrap();
+
+        assert_eq!(graph.edges.len(), 4);
+        assert_eq!(node("SessionService").injectors, vec!["root", "OrdersComponent"]);
+        assert_eq!(graph.multi_provided().count(), 1);
+        assert_eq!(node("OrdersService").single_feature.as_deref(), Some("orders"));
+        assert_eq!(node("AuditService").single_feature, None);
+        assert_eq!(graph.cycles, vec![vec!["AuditService", "SessionService"]]);
+    }
+}
@@ -0,0 +1,252 @@
+use crate::analyzers::dependency_graph::{lookup_module, module_path_index, normalize_file_path, resolve_module};
+use crate::ast::{DeepImport, Export, ExportType, ImportExportGraph, ImportType, LibraryApi, LibraryApiReport, PrivateImport, PublicApiSymbol};
+use crate::config::relative_issue_path;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ng-package.json で entryFile を省略したときの ng-packagr の既定値
+const DEFAULT_ENTRY_FILE: &str = "src/public-api.ts";
+
+// (ライブラリ, import したファイル ID, 参照先のモジュール) -> (解決したファイル ID, シンボル)
+type DeepImportIndex = BTreeMap<(usize, String, String), (Option<String>, Vec<String>)>;
+
+struct Library {
+    name: String,
+    dir: String,
+    entry_file: String,
+    entry_id: Option<String>,
+}
+
+// ng_packages はワークスペース内の ng-package.json。入れ子のものはセカンダリエントリーポイントとして扱う
+pub fn analyze_library_api(root_path: &Path, graph: &ImportExportGraph, ng_packages: &[PathBuf]) -> LibraryApiReport {
+    let module_paths = module_path_index(graph);
+    let relative_paths: HashMap<&str, String> = graph.files.iter()
+        .map(|file| (file.id.as_str(), file.relative_path.replace('\\', "/")))
+        .collect();
+    let mut exports_by_file: HashMap<&str, Vec<&Export>> = HashMap::new();
+    for export in &graph.exports {
+        if let Some(id) = module_paths.get(&normalize_file_path(&export.file_path)) {
+            exports_by_file.entry(id.as_str()).or_default().push(export);
+        }
+    }
+
+    let dirs: Vec<PathBuf> = ng_packages.iter().filter_map(|package| package.parent().map(Path::to_path_buf)).collect();
+    let libraries: Vec<Library> = ng_packages.iter()
+        .filter_map(|package| {
+            let dir = package.parent()?;
+            let config: serde_json::Value = serde_json::from_str(&fs::read_to_string(package).ok()?).ok()?;
+            let entry = config.pointer("/lib/entryFile").and_then(|entry| entry.as_str()).unwrap_or(DEFAULT_ENTRY_FILE);
+            let entry_path = normalize_file_path(&dir.join(entry).display().to_string());
+            Some(Library {
+                name: library_name(dir, &dirs),
+                dir: normalize_file_path(&package.display().to_string()).trim_end_matches("ng-package.json").to_string(),
+                entry_id: lookup_module(&module_paths, &entry_path),
+                entry_file: relative_issue_path(&dir.join(entry).display().to_string(), root_path),
+            })
+        })
+        .collect();
+
+    // ファイルはいちばん深いライブラリのもの。入れ子のセカンダリエントリーポイントは親に含めない
+    let owners: HashMap<&str, usize> = graph.files.iter()
+        .filter_map(|file| {
+            let path = normalize_file_path(&file.file_path);
+            libraries.iter().enumerate()
+                .filter(|(_, library)| path.starts_with(&library.dir))
+                .max_by_key(|(_, library)| library.dir.len())
+                .map(|(index, _)| (file.id.as_str(), index))
+        })
+        .collect();
+
+    // import 文ごとにシンボルをまとめる
+    let mut deep: DeepImportIndex = BTreeMap::new();
+    for import in &graph.imports {
+        if import.file_path.ends_with(".spec.ts") {
+            continue;
+        }
+        let Some(importer) = module_paths.get(&normalize_file_path(&import.file_path)) else { continue };
+        let importer_owner = owners.get(importer.as_str()).copied();
+        let (library, target) = if import.source_module.starts_with('.') {
+            let Some(target) = resolve_module(&module_paths, &import.file_path, &import.source_module) else { continue };
+            let Some(library) = owners.get(target.as_str()).copied() else { continue };
+            if libraries[library].entry_id.as_deref() == Some(target.as_str()) {
+                continue;
+            }
+            (library, Some(target))
+        } else if libraries.iter().all(|library| library.name != import.source_module) {
+            // @org/ui/src/lib/button のようにパッケージ名の先を直接指定しているもの
+            let Some((library, rest)) = libraries.iter().enumerate()
+                .filter_map(|(index, library)| import.source_module.strip_prefix(&format!("{}/", library.name)).map(|rest| (index, library, rest)))
+                .max_by_key(|(_, library, _)| library.name.len())
+                .map(|(index, _, rest)| (index, rest.to_string()))
+            else { continue };
+            (library, lookup_module(&module_paths, &format!("{}{}", libraries[library].dir, rest)))
+        } else {
+            continue;
+        };
+        if importer_owner == Some(library) {
+            continue;
+        }
+        let entry = deep.entry((library, importer.clone(), import.source_module.clone())).or_insert((target, Vec::new()));
+        if matches!(import.import_type, ImportType::Named) && !entry.1.contains(&import.symbol_name) {
+            entry.1.push(import.symbol_name.clone());
+        }
+    }
+
+    let mut report = LibraryApiReport::default();
+    for (index, library) in libraries.iter().enumerate() {
+        let mut public_api = Vec::new();
+        if let Some(entry_id) = &library.entry_id {
+            collect_public_api(entry_id, &exports_by_file, &module_paths, &mut HashSet::new(), &mut public_api);
+        }
+        let mut seen = HashSet::new();
+        public_api.retain(|(name, _)| seen.insert(name.clone()));
+        let public_names: HashSet<&str> = public_api.iter().map(|(name, _)| name.as_str()).collect();
+
+        let mut deep_imports = Vec::new();
+        let mut private: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+        for ((_, importer, source_module), (target, symbols)) in deep.iter().filter(|((library, _, _), _)| *library == index) {
+            let importer_path = relative_paths[importer.as_str()].clone();
+            let target_path = target.as_ref().map(|target| relative_paths[target.as_str()].clone());
+            if let Some(target_path) = &target_path {
+                for symbol in symbols.iter().filter(|symbol| !public_names.contains(symbol.as_str())) {
+                    private.entry((symbol.clone(), target_path.clone())).or_default().insert(importer_path.clone());
+                }
+            }
+            deep_imports.push(DeepImport {
+                file_path: importer_path,
+                source_module: source_module.clone(),
+                target_file: target_path,
+                symbols: symbols.clone(),
+            });
+        }
+
+        report.libraries.push(LibraryApi {
+            name: library.name.clone(),
+            root: relative_issue_path(library.dir.trim_end_matches('/'), root_path),
+            entry_file: library.entry_file.clone(),
+            public_api: public_api.into_iter()
+                .map(|(name, id)| PublicApiSymbol { name, file_path: relative_paths[id.as_str()].clone() })
+                .collect(),
+            deep_imports,
+            private_imports: private.into_iter()
+                .map(|((symbol, file_path), importers)| PrivateImport { symbol, file_path, importers: importers.into_iter().collect() })
+                .collect(),
+        });
+    }
+    report.libraries.sort_by(|a, b| a.name.cmp(&b.name));
+    report
+}
+
+// package.json の name。なければ親のライブラリ名にディレクトリを続けるか、ディレクトリ名
+fn library_name(dir: &Path, dirs: &[PathBuf]) -> String {
+    let package_name = fs::read_to_string(dir.join("package.json")).ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|package| package.get("name").and_then(|name| name.as_str()).map(str::to_string));
+    if let Some(name) = package_name {
+        return name;
+    }
+    let parent = dirs.iter()
+        .filter(|other| other.as_path() != dir && dir.starts_with(other))
+        .max_by_key(|other| other.components().count());
+    match parent {
+        Some(parent) => {
+            let relative = dir.strip_prefix(parent).unwrap_or(dir).display().to_string().replace('\\', "/");
+            format!("{}/{}", library_name(parent, dirs), relative)
+        }
+        None => dir.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default(),
+    }
+}
+
+// エントリーファイルから export * / export { X } from をたどり、(シンボル, 宣言しているファイル ID) を集める
+fn collect_public_api(
+    file_id: &str,
+    exports_by_file: &HashMap<&str, Vec<&Export>>,
+    module_paths: &HashMap<String, String>,
+    visited: &mut HashSet<String>,
+    public_api: &mut Vec<(String, String)>,
+) {
+    if !visited.insert(file_id.to_string()) {
+        return;
+    }
+    for export in exports_by_file.get(file_id).into_iter().flatten() {
+        let target = export.source_module.as_deref()
+            .and_then(|source| resolve_module(module_paths, &export.file_path, source));
+        match (&export.export_type, target) {
+            (ExportType::Namespace, Some(target)) => collect_public_api(&target, exports_by_file, module_paths, visited, public_api),
+            (ExportType::ReExport, Some(target)) => {
+                let declaring = declaring_file(&target, &export.symbol_name, exports_by_file, module_paths, &mut HashSet::new());
+                public_api.push((export.symbol_name.clone(), declaring.unwrap_or(target)));
+            }
+            _ => public_api.push((export.symbol_name.clone(), file_id.to_string())),
+        }
+    }
+}
+
+// 再エクスポートをたどって symbol を宣言しているファイル
+fn declaring_file(
+    file_id: &str,
+    symbol: &str,
+    exports_by_file: &HashMap<&str, Vec<&Export>>,
+    module_paths: &HashMap<String, String>,
+    visited: &mut HashSet<String>,
+) -> Option<String> {
+    if !visited.insert(file_id.to_string()) {
+        return None;
+    }
+    let exports = exports_by_file.get(file_id)?;
+    if exports.iter().any(|export| export.symbol_name == symbol && export.source_module.is_none()) {
+        return Some(file_id.to_string());
+    }
+    exports.iter()
+        .filter(|export| export.symbol_name == symbol || matches!(export.export_type, ExportType::Namespace))
+        .filter_map(|export| resolve_module(module_paths, &export.file_path, export.source_module.as_deref()?))
+        .find_map(|target| declaring_file(&target, symbol, exports_by_file, module_paths, visited))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzers::dependency_graph::DependencyGraphAnalyzer;
+
+    #[tokio::test]
+    async fn test_library_api() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |path: &str, content: &str| {
+            let path = dir.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write("projects/ui/ng-package.json", r#"{ "lib": { "entryFile": "src/public-api.ts" } }"#);
+        write("projects/ui/package.json", r#"{ "name": "@acme/ui" }"#);
+        write("projects/ui/src/public-api.ts", "export * from './lib/button';\nexport { formatLabel } from './lib/label';\n");
+        write("projects/ui/src/lib/button.ts", "export class ButtonComponent {}\n");
+        write("projects/ui/src/lib/label.ts", "export function formatLabel() {}\nexport function parseLabel() {}\n");
+        write("projects/ui/testing/ng-package.json", "{}");
+        write("projects/ui/testing/src/public-api.ts", "export class UiHarness {}\n");
+        write("src/app/app.ts", "import { ButtonComponent } from '@acme/ui';\nimport { UiHarness } from '@acme/ui/testing';\nimport { parseLabel, formatLabel } from '../../projects/ui/src/lib/label';\n");
+        write("src/app/other.ts", "import { ButtonComponent } from '@acme/ui/src/lib/button';\n");
+
+        let root = dir.path().to_path_buf();
+        let graph = DependencyGraphAnalyzer::new().analyze_project(&root).await.unwrap();
+        let packages = vec![root.join("projects/ui/ng-package.json"), root.join("projects/ui/testing/ng-package.json")];
+        let report = analyze_library_api(&root, &graph, &packages);
+
+        let names: Vec<&str> = report.libraries.iter().map(|library| library.name.as_str()).collect();
+        assert_eq!(names, vec!["@acme/ui", "@acme/ui/testing"]);
+        let ui = &report.libraries[0];
+        assert_eq!(ui.root, "projects/ui");
+        let public_api: Vec<(&str, &str)> = ui.public_api.iter().map(|symbol| (symbol.name.as_str(), symbol.file_path.as_str())).collect();
+        assert_eq!(public_api, vec![("ButtonComponent", "projects/ui/src/lib/button.ts"), ("formatLabel", "projects/ui/src/lib/label.ts")]);
+
+        let deep: Vec<(&str, Option<&str>)> = ui.deep_imports.iter().map(|deep| (deep.file_path.as_str(), deep.target_file.as_deref())).collect();
+        assert_eq!(deep, vec![
+            ("src/app/app.ts", Some("projects/ui/src/lib/label.ts")),
+            ("src/app/other.ts", Some("projects/ui/src/lib/button.ts")),
+        ]);
+        assert_eq!(ui.private_imports.len(), 1);
+        assert_eq!(ui.private_imports[0].symbol, "parseLabel");
+        assert_eq!(ui.private_imports[0].importers, vec!["src/app/app.ts"]);
+        assert!(report.libraries[1].deep_imports.is_empty());
+    }
+}
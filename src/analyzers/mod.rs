@@ -1,18 +1,47 @@
-use crate::ast::{AnalysisResult, NgProject, Issue};
-use async_trait::async_trait;
+use crate::ast::{AdoptionMetrics, AnalysisResult, ChangeDetectionStrategy, NgProject, Issue};
+use crate::config::{relative_issue_path, Config};
+use crate::output::i18n::Lang;
+use crate::output::progress::Progress;
+use crate::parsers::project::ProjectInputs;
+use crate::parsers::source_cache::SourceCache;
 use anyhow::Result;
 use rayon::prelude::*;
 use std::collections::HashMap;
+use std::path::Path;
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
 
+pub mod boundaries;
+pub mod clones;
 pub mod component;
+pub mod component_tree;
+pub mod bundle_estimate;
+pub mod impact;
+pub mod inheritance;
+pub mod library_api;
+pub mod module_graph;
+pub mod injection_graph;
+pub mod package_usage;
+pub mod service_api;
+pub mod usages;
 pub mod dependency;
+pub mod duplicates;
 pub mod performance;
 pub mod state;
+pub mod testing;
+pub mod http;
+pub mod styles;
 pub mod dependency_graph;
+pub mod zoneless;
+pub mod type_safety;
+pub mod environment;
+pub mod budgets;
+pub mod health;
 
-#[async_trait]
+// CPU だけを使うので同期で実装し、エンジンが rayon で並列に走らせる。
+// 結果はプロジェクトを複製せず、同じ Arc を持つ
 pub trait Analyzer: Send + Sync {
-    async fn analyze(&self, project: &NgProject) -> Result<AnalysisResult>;
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult>;
     #[allow(dead_code)]
     fn name(&self) -> &'static str;
     #[allow(dead_code)]
@@ -34,8 +63,14 @@ pub struct AstNode {
     pub column: u32,
 }
 
+// audit --full や bench で実行するアナライザー
+pub const ANALYZER_NAMES: [&str; 13] = ["component", "dependency", "state", "performance", "testing", "http", "styles", "boundaries", "duplicates", "clones", "zoneless", "type-safety", "environment"];
+
 pub struct AnalysisEngine {
     analyzers: HashMap<String, Box<dyn Analyzer>>,
+    config: Option<Config>,
+    progress: Arc<Progress>,
+    lang: Lang,
 }
 
 impl AnalysisEngine {
@@ -46,28 +81,485 @@ impl AnalysisEngine {
         analyzers.insert("dependency".to_string(), Box::new(dependency::DependencyAnalyzer::new()));
         analyzers.insert("state".to_string(), Box::new(state::StateAnalyzer::new()));
         analyzers.insert("performance".to_string(), Box::new(performance::PerformanceAnalyzer::new()));
+        analyzers.insert("testing".to_string(), Box::new(testing::TestingAnalyzer::new()));
+        analyzers.insert("http".to_string(), Box::new(http::HttpAnalyzer::new()));
+        analyzers.insert("styles".to_string(), Box::new(styles::StylesAnalyzer::new()));
+        analyzers.insert("boundaries".to_string(), Box::new(boundaries::BoundariesAnalyzer::new()));
+        analyzers.insert("duplicates".to_string(), Box::new(duplicates::DuplicatesAnalyzer::new()));
+        analyzers.insert("clones".to_string(), Box::new(clones::ClonesAnalyzer::new()));
+        analyzers.insert("zoneless".to_string(), Box::new(zoneless::ZonelessAnalyzer::new()));
+        analyzers.insert("type-safety".to_string(), Box::new(type_safety::TypeSafetyAnalyzer::new()));
+        analyzers.insert("environment".to_string(), Box::new(environment::EnvironmentAnalyzer::new()));
         
-        Self { analyzers }
+        Self { analyzers, config: None, progress: Arc::new(Progress::hidden()), lang: Lang::En }
+    }
+
+    pub fn with_progress(mut self, progress: Arc<Progress>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    // 設定の誤りを伝えるメッセージの言語。with_config より前に呼ぶ
+    pub fn with_lang(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    pub fn with_config(mut self, config: Option<Config>) -> Self {
+        if let Some(config) = &config {
+            let allowed_outputs = string_list_option(config, "native-event-output-name", "allowed_names");
+            let max_file_lines = config.rule_option("max-file-lines", "max_lines")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(400) as u32;
+            let max_selection_branches = config.rule_option("component-selection-chain", "max_branches")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(3) as u32;
+            let adoption_gates = component::ADOPTION_GATES.iter()
+                .filter_map(|(option, _, _)| {
+                    config.rule_option("adoption-threshold", option)
+                        .and_then(|value| value.as_f64())
+                        .map(|min| (option.to_string(), min))
+                })
+                .collect();
+            let min_coverage = config.rule_option("low-test-coverage", "min_coverage")
+                .and_then(|value| value.as_f64())
+                .unwrap_or(50.0);
+            let coverage_min_complexity = config.rule_option("low-test-coverage", "min_complexity")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(5) as u32;
+            let max_nesting_depth = config.rule_option("deep-component-nesting", "max_depth")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(5) as u32;
+            let max_inline_lines = config.rule_option("single-use-component", "max_lines")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(50) as u32;
+            let max_render_depth = config.rule_option("deep-render-tree", "max_depth")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(8) as u32;
+            let max_api_size = config.rule_option("component-api-surface", "max_api_size")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(20) as usize;
+            let max_inheritance_depth = config.rule_option("deep-inheritance-chain", "max_depth")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(2) as u32;
+            self.analyzers.insert(
+                "component".to_string(),
+                Box::new(
+                    component::ComponentAnalyzer::new()
+                        .with_allowed_event_outputs(allowed_outputs)
+                        .with_max_file_lines(max_file_lines)
+                        .with_max_selection_branches(max_selection_branches)
+                        .with_coverage_thresholds(min_coverage, coverage_min_complexity)
+                        .with_adoption_gates(adoption_gates)
+                        .with_component_tree_limits(max_nesting_depth, max_inline_lines)
+                        .with_max_render_depth(max_render_depth)
+                        .with_max_api_size(max_api_size)
+                        .with_max_inheritance_depth(max_inheritance_depth)
+                        .with_unused_binding_ignores(
+                            string_list_option(config, "unused-input", "ignore"),
+                            string_list_option(config, "unused-output", "ignore"),
+                        ),
+                ),
+            );
+
+            let ignored_packages = string_list_option(config, "duplicate-dependency-version", "ignore_packages");
+            let max_import_depth = config.rule_option("deep-import-chain", "max_depth")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(12) as u32;
+            let max_import_chains = config.rule_option("deep-import-chain", "max_chains")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(5) as usize;
+            let max_constructor_services = config.rule_option("constructor-initialization-chain", "max_services")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(3) as usize;
+            let preferred_injection = config.rule_option("inconsistent-injection-style", "preferred_style")
+                .and_then(|value| value.as_str())
+                .unwrap_or("inject")
+                .to_string();
+            self.analyzers.insert(
+                "dependency".to_string(),
+                Box::new(
+                    dependency::DependencyAnalyzer::new()
+                        .with_ignored_packages(ignored_packages)
+                        .with_import_depth_budget(max_import_depth, max_import_chains)
+                        .with_max_constructor_services(max_constructor_services)
+                        .with_ignored_exports(string_list_option(config, "unused-export", "ignore"))
+                        .with_preferred_injection(preferred_injection),
+                ),
+            );
+
+            let min_consumers = config.rule_option("shared-mutable-export", "min_consumers")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(2) as usize;
+            self.analyzers.insert(
+                "state".to_string(),
+                Box::new(state::StateAnalyzer::new().with_min_consumers(min_consumers)),
+            );
+
+            let allowed_urls = string_list_option(config, "window-location-navigation", "allowed_urls");
+            let max_mark_for_check = config.rule_option("manual-change-detection", "max_mark_for_check")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(5) as usize;
+            let defer_min_lines = config.rule_option("defer-heavy-component", "min_lines")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(200) as u32;
+            let fold_line = config.rule_option("defer-below-the-fold", "fold_line")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(40) as u32;
+            let below_fold_min_lines = config.rule_option("defer-below-the-fold", "min_lines")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(300) as u32;
+            self.analyzers.insert(
+                "performance".to_string(),
+                Box::new(
+                    performance::PerformanceAnalyzer::new()
+                        .with_allowed_navigation_urls(allowed_urls)
+                        .with_max_mark_for_check(max_mark_for_check)
+                        .with_defer_min_lines(defer_min_lines)
+                        .with_below_fold_limits(fold_line, below_fold_min_lines),
+                ),
+            );
+
+            let max_stylesheet_lines = config.rule_option("large-component-stylesheet", "max_lines")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(200) as u32;
+            let max_important = config.rule_option("excessive-important", "max_important")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(3) as u32;
+            self.analyzers.insert(
+                "styles".to_string(),
+                Box::new(
+                    styles::StylesAnalyzer::new()
+                        .with_max_lines(max_stylesheet_lines)
+                        .with_max_important(max_important),
+                ),
+            );
+
+            self.analyzers.insert(
+                "boundaries".to_string(),
+                Box::new(boundaries::BoundariesAnalyzer::new().with_layers(config.boundaries.layers.clone()).with_lang(self.lang)),
+            );
+
+            let identical_only = config.rule_option("duplicate-symbol", "identical_only")
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false);
+            self.analyzers.insert(
+                "duplicates".to_string(),
+                Box::new(
+                    duplicates::DuplicatesAnalyzer::new()
+                        .with_ignored_symbols(string_list_option(config, "duplicate-symbol", "ignore"))
+                        .with_identical_only(identical_only),
+                ),
+            );
+
+            let min_clone_tokens = config.rule_option("code-clone", "min_tokens")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(100) as usize;
+            self.analyzers.insert(
+                "clones".to_string(),
+                Box::new(clones::ClonesAnalyzer::new().with_min_tokens(min_clone_tokens)),
+            );
+
+            let max_any = config.rule_option("explicit-any", "max_any")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(3) as u32;
+            let max_non_null = config.rule_option("non-null-assertion", "max_non_null")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(5) as u32;
+            let max_ts_ignore = config.rule_option("ts-ignore-comment", "max_ts_ignore")
+                .and_then(|value| value.as_u64())
+                .unwrap_or(0) as u32;
+            let min_target = config.rule_option("outdated-compile-target", "min_target")
+                .and_then(|value| value.as_str())
+                .unwrap_or("ES2022")
+                .to_string();
+            self.analyzers.insert(
+                "type-safety".to_string(),
+                Box::new(
+                    type_safety::TypeSafetyAnalyzer::new()
+                        .with_thresholds(max_any, max_non_null, max_ts_ignore)
+                        .with_min_target(min_target),
+                ),
+            );
+
+            self.analyzers.insert(
+                "environment".to_string(),
+                Box::new(environment::EnvironmentAnalyzer::new().with_allowed_files(string_list_option(config, "environment-direct-access", "allowed_files"))),
+            );
+        }
+        self.config = config;
+        self
+    }
+
+    pub fn run_analysis(&self, project: &Arc<NgProject>, analyzer_names: &[String]) -> Result<Vec<AnalysisResult>> {
+        self.run(project, analyzer_names, None)
     }
 
-    pub async fn run_analysis(&self, project: &NgProject, analyzer_names: &[String]) -> Result<Vec<AnalysisResult>> {
+    // アナライザーが 1 つ終わるたびに、設定を適用した issue を sender に送る。戻るときに sender を drop する
+    pub fn stream_analysis(&self, project: &Arc<NgProject>, analyzer_names: &[String], sender: Sender<Vec<Issue>>) -> Result<Vec<AnalysisResult>> {
+        self.run(project, analyzer_names, Some(&sender))
+    }
+
+    fn run(&self, project: &Arc<NgProject>, analyzer_names: &[String], sender: Option<&Sender<Vec<Issue>>>) -> Result<Vec<AnalysisResult>> {
+        self.progress.start("progress.analyzing", analyzer_names.len());
         let results: Result<Vec<_>, _> = analyzer_names
             .par_iter()
             .map(|name| {
                 let analyzer = self.analyzers.get(name)
-                    .ok_or_else(|| anyhow::anyhow!("Unknown analyzer: {}", name))?;
+                    .ok_or_else(|| anyhow::anyhow!("{}", self.lang.trf("error.unknown_analyzer", &[name])))?;
                 
-                tokio::task::block_in_place(|| {
-                    tokio::runtime::Handle::current().block_on(analyzer.analyze(project))
-                })
+                let result = analyzer.analyze(project).and_then(|result| self.finish_result(project, result));
+                self.progress.inc(name);
+                if let (Ok(result), Some(sender)) = (&result, sender) {
+                    // 書き込み側が止まっていても解析は続ける
+                    let _ = sender.send(result.issues.clone());
+                }
+                result
             })
             .collect();
-
+        self.progress.finish();
         results
     }
 
+    fn finish_result(&self, project: &NgProject, mut result: AnalysisResult) -> Result<AnalysisResult> {
+        if let Some(config) = &self.config {
+            let issues = std::mem::take(&mut result.issues);
+            result.issues = config.apply_to_issues(issues, &project.root_path)?;
+        }
+
+        assign_fingerprints(&mut result.issues, &project.root_path, &project.sources);
+
+        Ok(result)
+    }
+
     #[allow(dead_code)]
     pub fn list_analyzers(&self) -> Vec<&str> {
         self.analyzers.keys().map(|s| s.as_str()).collect()
     }
-}
\ No newline at end of file
+}
+
+fn percentage(part: usize, total: usize) -> Option<f64> {
+    (total > 0).then(|| part as f64 * 100.0 / total as f64)
+}
+
+// standalone / signal inputs / OnPush / typed forms への移行率
+pub fn adoption_metrics(project: &NgProject) -> AdoptionMetrics {
+    let components = &project.components;
+    let inputs: Vec<_> = components.iter().flat_map(|c| &c.inputs).collect();
+    let forms = components.iter().map(|c| c.forms).chain(project.services.iter().map(|s| s.forms));
+    let (typed_forms, untyped_forms) = forms.fold((0, 0), |(typed, untyped), usage| {
+        (typed + usage.typed as usize, untyped + usage.untyped as usize)
+    });
+
+    AdoptionMetrics {
+        standalone_percentage: percentage(components.iter().filter(|c| c.standalone).count(), components.len()),
+        signal_inputs_percentage: percentage(inputs.iter().filter(|input| input.signal).count(), inputs.len()),
+        onpush_percentage: percentage(
+            components.iter().filter(|c| matches!(c.change_detection, ChangeDetectionStrategy::OnPush)).count(),
+            components.len(),
+        ),
+        typed_forms_percentage: percentage(typed_forms, typed_forms + untyped_forms),
+    }
+}
+
+// HTML テンプレートを解析した結果を使うルールと、テンプレート・スタイルのファイル一覧を使うルール
+const TEMPLATE_RULES: [(&str, &str); 15] = [
+    ("component", "component-selection-chain"),
+    ("component", "deep-component-nesting"),
+    ("component", "deep-render-tree"),
+    ("component", "legacy-control-flow"),
+    ("component", "single-use-component"),
+    ("component", "unused-input"),
+    ("component", "unused-output"),
+    ("dependency", "recursive-component-composition"),
+    ("performance", "defer-below-the-fold"),
+    ("performance", "defer-heavy-component"),
+    ("performance", "for-missing-track"),
+    ("performance", "heavy-pipe-in-loop"),
+    ("performance", "missing-trackby"),
+    ("performance", "template-method-call"),
+    ("styles", "unused-component-class"),
+];
+const RESOURCE_FILE_RULES: [(&str, &str); 2] = [
+    ("component", "orphan-component-resource"),
+    ("component", "orphan-resource-file"),
+];
+const IMPORT_GRAPH_RULES: [(&str, &str); 9] = [
+    ("boundaries", "layer-boundary-violation"),
+    ("dependency", "deep-import-chain"),
+    ("dependency", "wildcard-reexport"),
+    ("dependency", "testing-code-in-production"),
+    ("dependency", "unused-export"),
+    ("duplicates", "duplicate-symbol"),
+    ("performance", "lazy-route-eager-import"),
+    ("performance", "lazy-route-shared-code"),
+    ("state", "shared-mutable-export"),
+];
+const STYLESHEET_RULES: [(&str, &str); 6] = [
+    ("styles", "large-component-stylesheet"),
+    ("styles", "ng-deep-usage"),
+    ("styles", "excessive-important"),
+    ("styles", "duplicate-component-selector"),
+    ("styles", "empty-component-stylesheet"),
+    ("styles", "unused-component-class"),
+];
+
+pub fn required_inputs(analyzer_names: &[String], config: Option<&Config>) -> ProjectInputs {
+    let needs = |rules: &[(&str, &str)]| {
+        rules.iter().any(|(analyzer, rule)| {
+            analyzer_names.iter().any(|name| name == analyzer) && config.is_none_or(|c| c.rule_may_run(rule))
+        })
+    };
+
+    ProjectInputs {
+        templates: needs(&TEMPLATE_RULES),
+        resource_files: needs(&RESOURCE_FILE_RULES),
+        stylesheets: needs(&STYLESHEET_RULES),
+        import_graph: needs(&IMPORT_GRAPH_RULES) || config.is_some_and(|c| c.budgets.max_cycles.is_some()),
+    }
+}
+
+fn string_list_option(config: &Config, rule: &str, option: &str) -> Vec<String> {
+    config.rule_option(rule, option)
+        .and_then(|value| value.as_array())
+        .map(|values| values.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+        .unwrap_or_default()
+}
+
+// 行番号がずれても同じ問題を追跡できるように、行番号そのものではなく行の内容を使う。
+// 同じルール・ファイルで内容が同じ行は、上から何番目かで区別する
+pub fn assign_fingerprints(issues: &mut [Issue], root_path: &Path, sources: &SourceCache) {
+    let snippets: Vec<String> = issues.iter().map(|issue| issue_snippet(issue, sources)).collect();
+    let mut order: Vec<usize> = (0..issues.len()).collect();
+    order.sort_by_key(|&index| (issues[index].line, issues[index].column));
+
+    let mut occurrences: HashMap<(&str, &str, &str), usize> = HashMap::new();
+    let mut fingerprints = vec![String::new(); issues.len()];
+    for index in order {
+        let issue = &issues[index];
+        let occurrence = occurrences.entry((&issue.rule, &issue.file_path, &snippets[index])).or_default();
+        fingerprints[index] = issue_fingerprint(issue, root_path, &snippets[index], *occurrence);
+        *occurrence += 1;
+    }
+
+    for (issue, fingerprint) in issues.iter_mut().zip(fingerprints) {
+        issue.fingerprint = Some(fingerprint);
+    }
+}
+
+fn issue_snippet(issue: &Issue, sources: &SourceCache) -> String {
+    issue.line
+        .and_then(|line| {
+            let source = sources.read(Path::new(&issue.file_path)).ok()?;
+            let text = source.content.lines().nth(line.checked_sub(1)? as usize)?;
+            Some(text.split_whitespace().collect::<Vec<_>>().join(" "))
+        })
+        .unwrap_or_else(|| issue.message.clone())
+}
+
+pub fn issue_fingerprint(issue: &Issue, root_path: &Path, snippet: &str, occurrence: usize) -> String {
+    // 最初の 1 つは番号を入れず、これまでの fingerprint と同じ値にする
+    let occurrence = occurrence.to_string();
+    let occurrence = (occurrence != "0").then_some(occurrence.as_str());
+
+    // FNV-1a (64bit): Rust のバージョンやプラットフォームに依存しない
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let parts = [issue.rule.as_str(), &relative_issue_path(&issue.file_path, root_path), snippet];
+    for part in parts.into_iter().chain(occurrence) {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Severity;
+
+    fn issue(file_path: &str, message: &str) -> Issue {
+        Issue {
+            severity: Severity::Warning,
+            rule: "component-complexity".to_string(),
+            message: message.to_string(),
+            file_path: file_path.to_string(),
+            line: None,
+            column: None,
+            fingerprint: None,
+        }
+    }
+
+    #[test]
+    fn test_issue_fingerprint_is_stable_across_roots() {
+        let a = issue_fingerprint(&issue("/a/src/app.component.ts", "too complex"), Path::new("/a"), "too complex", 0);
+        let b = issue_fingerprint(&issue("/b/src/app.component.ts", "too complex"), Path::new("/b"), "too complex", 0);
+        let c = issue_fingerprint(&issue("/a/src/app.component.ts", "other"), Path::new("/a"), "other", 0);
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.len(), 16);
+    }
+
+    // 同じ内容の行で見つかった issue も、別の fingerprint になる
+    #[test]
+    fn test_identical_lines_get_distinct_fingerprints() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("user.component.ts");
+        std::fs::write(&path, "ngOnInit() {\n  this.users$.subscribe();\n  this.users$.subscribe();\n}\n").unwrap();
+        let leak = |line: u32| Issue {
+            rule: "memory-leak-risk".to_string(),
+            line: Some(line),
+            ..issue(path.to_str().unwrap(), "subscribe without unsubscribe")
+        };
+
+        let mut issues = vec![leak(3), leak(2)];
+        assign_fingerprints(&mut issues, dir.path(), &SourceCache::new());
+        let first = issues[1].fingerprint.clone().unwrap();
+        assert_ne!(issues[0].fingerprint.as_ref(), Some(&first));
+        assert_eq!(first, issue_fingerprint(&issues[1], dir.path(), "this.users$.subscribe();", 0));
+    }
+
+    #[test]
+    fn test_required_inputs() {
+        let names = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        let inputs = required_inputs(&names(&["http"]), None);
+        assert!(!inputs.templates && !inputs.resource_files && !inputs.stylesheets && !inputs.import_graph);
+
+        let inputs = required_inputs(&names(&["state"]), None);
+        assert!(!inputs.templates && !inputs.stylesheets && inputs.import_graph);
+
+        let inputs = required_inputs(&names(&["performance"]), None);
+        assert!(inputs.templates && !inputs.stylesheets && inputs.import_graph);
+
+        let inputs = required_inputs(&names(&["dependency"]), None);
+        assert!(inputs.templates && !inputs.resource_files);
+
+        let inputs = required_inputs(&names(&["styles"]), None);
+        assert!(inputs.templates && inputs.stylesheets);
+
+        let mut config = Config::default();
+        config.rules.insert("recursive-component-composition".to_string(), crate::config::RuleConfig {
+            enabled: false,
+            severity: "warning".to_string(),
+            options: HashMap::new(),
+        });
+        let inputs = required_inputs(&names(&["dependency"]), Some(&config));
+        assert!(!inputs.templates);
+    }
+    // tokio のランタイムの外でも、複数のアナライザーを並列に実行できる
+    #[test]
+    fn test_run_analysis_shares_project() {
+        let project = Arc::new(crate::ast::builder::NgProjectBuilder::new("/app")
+            .component(crate::ast::builder::ComponentBuilder::new("UserListComponent").complexity(30))
+            .build());
+        let names: Vec<String> = ANALYZER_NAMES.iter().map(|name| name.to_string()).collect();
+
+        let results = AnalysisEngine::new().run_analysis(&project, &names).unwrap();
+        assert_eq!(results.len(), ANALYZER_NAMES.len());
+        assert!(results.iter().all(|result| Arc::ptr_eq(&result.project, &project)));
+    }
+}
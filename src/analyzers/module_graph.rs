@@ -0,0 +1,159 @@
+use super::dependency_graph::{normalize_file_path, strongly_connected_components};
+use crate::ast::{LazyRoute, ModuleGraph, ModuleGraphEdge, ModuleGraphNode, NgModule, NgProject};
+use std::collections::{HashMap, VecDeque};
+
+// loadChildren の遷移先がこのモジュールか。クラス名が取れない場合はファイルパスで比べる
+pub fn lazy_route_matches(route: &LazyRoute, module: &NgModule) -> bool {
+    match &route.module {
+        Some(name) => *name == module.name,
+        None => {
+            let target = module.file_path.strip_suffix(".ts").unwrap_or(&module.file_path);
+            target == route.target || target.ends_with(&format!("/{}", route.target))
+        }
+    }
+}
+
+// 遅延読み込みされる機能のディレクトリ（ルートの対象ファイルのディレクトリ）。ルート定義と同じディレクトリを指すものは機能とみなさない
+pub fn lazy_feature_dir(route: &LazyRoute) -> Option<&str> {
+    let (dir, _) = route.target.rsplit_once('/')?;
+    let route_file = normalize_file_path(&route.file_path);
+    let same_dir = route_file.strip_prefix(&format!("{}/", dir)).is_some_and(|rest| !rest.contains('/'));
+    (!same_dir).then_some(dir)
+}
+
+pub fn build_module_graph(project: &NgProject) -> ModuleGraph {
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for (i, module) in project.modules.iter().enumerate() {
+        index.entry(module.name.as_str()).or_insert(i);
+    }
+
+    // CommonModule などプロジェクト外のモジュールは辺にしない
+    let mut edges: Vec<ModuleGraphEdge> = Vec::new();
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); project.modules.len()];
+    for (from, module) in project.modules.iter().enumerate() {
+        if index.get(module.name.as_str()) != Some(&from) {
+            continue;
+        }
+        for import in &module.imports {
+            let Some(&to) = index.get(import.as_str()) else { continue };
+            if adjacency[from].contains(&to) {
+                continue;
+            }
+            adjacency[from].push(to);
+            edges.push(ModuleGraphEdge {
+                from: module.name.clone(),
+                to: import.clone(),
+                kind: "imports".to_string(),
+                line: None,
+            });
+        }
+    }
+
+    // ルート定義を持つファイルのモジュールから、遅延読み込みされるモジュールへの辺
+    for route in &project.lazy_routes {
+        let Some(owner) = project.modules.iter().find(|module| module.file_path == route.file_path) else { continue };
+        let Some(target) = project.modules.iter().find(|module| lazy_route_matches(route, module)) else { continue };
+        if edges.iter().any(|edge| edge.kind == "lazy" && edge.from == owner.name && edge.to == target.name) {
+            continue;
+        }
+        edges.push(ModuleGraphEdge {
+            from: owner.name.clone(),
+            to: target.name.clone(),
+            kind: "lazy".to_string(),
+            line: route.line,
+        });
+    }
+
+    let nodes = project.modules.iter().enumerate()
+        .filter(|(i, module)| index.get(module.name.as_str()) == Some(i))
+        .map(|(_, module)| ModuleGraphNode {
+            name: module.name.clone(),
+            file_path: module.file_path.clone(),
+            root: !module.bootstrap.is_empty(),
+            lazy: project.lazy_routes.iter().any(|route| lazy_route_matches(route, module)),
+        })
+        .collect();
+
+    let mut cycles: Vec<Vec<String>> = strongly_connected_components(&adjacency).into_iter()
+        .filter(|members| members.len() > 1 || adjacency[members[0]].contains(&members[0]))
+        .map(|members| {
+            let start = *members.iter().min().unwrap();
+            cycle_path(start, &members, &adjacency).into_iter()
+                .map(|i| project.modules[i].name.clone())
+                .collect()
+        })
+        .collect();
+    cycles.sort();
+
+    ModuleGraph { nodes, edges, cycles }
+}
+
+// 強連結成分の中で start から start に戻る最短の経路
+pub fn cycle_path(start: usize, members: &[usize], adjacency: &[Vec<usize>]) -> Vec<usize> {
+    let mut previous: HashMap<usize, usize> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(current) = queue.pop_front() {
+        for &next in &adjacency[current] {
+            if next == start {
+                let mut path = vec![current];
+                while let Some(&p) = previous.get(path.last().unwrap()) {
+                    path.push(p);
+                }
+                path.reverse();
+                return path;
+            }
+            if members.contains(&next) && !previous.contains_key(&next) {
+                previous.insert(next, current);
+                queue.push_back(next);
+            }
+        }
+    }
+    vec![start]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_module_graph() {
+        let module = |name: &str, imports: &[&str]| NgModule {
+            name: name.to_string(),
+            file_path: format!("src/app/{}.module.ts", name.trim_end_matches("Module").to_lowercase()),
+            imports: imports.iter().map(|i| i.to_string()).collect(),
+            exports: vec![],
+            declarations: vec![],
+            providers: vec![],
+            bootstrap: vec![],
+            for_root_imports: vec![],
+        };
+
+        let project = NgProject {
+            modules: vec![
+                module("AppModule", &["CommonModule", "AppRoutingModule", "SharedModule"]),
+                module("AppRoutingModule", &["RouterModule"]),
+                module("SharedModule", &["WidgetsModule"]),
+                module("WidgetsModule", &["FormsKitModule"]),
+                module("FormsKitModule", &["SharedModule"]),
+                module("AdminModule", &["SharedModule"]),
+            ],
+            lazy_routes: vec![LazyRoute {
+                module: Some("AdminModule".to_string()),
+                target: "src/app/admin.module".to_string(),
+                file_path: "src/app/approuting.module.ts".to_string(),
+                line: Some(3),
+            }],
+            ..Default::default()
+        };
+
+        let graph = build_module_graph(&project);
+
+        assert_eq!(graph.nodes.len(), 6);
+        assert!(graph.nodes.iter().find(|node| node.name == "AdminModule").unwrap().lazy);
+        assert_eq!(graph.edges.iter().filter(|edge| edge.kind == "imports").count(), 6);
+        let lazy: Vec<_> = graph.edges.iter().filter(|edge| edge.kind == "lazy").collect();
+        assert_eq!(lazy.len(), 1);
+        assert_eq!((lazy[0].from.as_str(), lazy[0].to.as_str(), lazy[0].line), ("AppRoutingModule", "AdminModule", Some(3)));
+        assert_eq!(graph.cycles, vec![vec!["SharedModule", "WidgetsModule", "FormsKitModule"]]);
+    }
+}
@@ -0,0 +1,159 @@
+use super::dependency_graph::package_name;
+use crate::ast::{ImportExportGraph, ImportType, PackageUsage, PackageUsageReport, SymbolUsage, WorkspacePackages};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+const FRAMEWORK_PACKAGES: [&str; 3] = ["tslib", "zone.js", "rxjs-compat"];
+
+// import 元ファイル、サブパス、シンボルごとの import 元ファイル
+type PackageReferences = (BTreeSet<String>, BTreeSet<String>, BTreeMap<String, BTreeSet<String>>);
+
+pub fn build_package_usage(graph: &ImportExportGraph, workspace: Option<&WorkspacePackages>) -> PackageUsageReport {
+    let relative_paths: HashMap<String, &str> = graph.files.iter()
+        .map(|file| (file.file_path.replace('\\', "/"), file.relative_path.as_str()))
+        .collect();
+
+    // import { map } from 'rxjs/operators' と export { X } from 'lib' をまとめて数える
+    let references = graph.imports.iter()
+        .map(|import| {
+            let symbol = match import.import_type {
+                ImportType::Named => import.symbol_name.as_str(),
+                ImportType::Default => "default",
+                ImportType::Namespace | ImportType::Dynamic => "*",
+            };
+            (import.file_path.as_str(), import.source_module.as_str(), symbol)
+        })
+        .chain(graph.exports.iter().filter_map(|export| {
+            Some((export.file_path.as_str(), export.source_module.as_deref()?, export.symbol_name.as_str()))
+        }));
+
+    let mut by_package: BTreeMap<String, PackageReferences> = BTreeMap::new();
+    for (file_path, source_module, symbol) in references {
+        let Some(package) = package_name(source_module) else { continue };
+        if source_module.starts_with("node:") {
+            continue;
+        }
+        let file = relative_paths.get(&file_path.replace('\\', "/")).map(|path| path.to_string()).unwrap_or_else(|| file_path.to_string());
+        let (files, entry_points, symbols) = by_package.entry(package.clone()).or_default();
+        files.insert(file.clone());
+        if source_module != package {
+            entry_points.insert(source_module.to_string());
+        }
+        symbols.entry(symbol.to_string()).or_default().insert(file);
+    }
+
+    let declared: Option<BTreeSet<&str>> = workspace
+        .filter(|workspace| !workspace.manifests.is_empty())
+        .map(|workspace| workspace.manifests.iter().flat_map(|manifest| &manifest.dependencies).map(|dependency| dependency.name.as_str()).collect());
+
+    let mut packages: Vec<PackageUsage> = by_package.into_iter()
+        .map(|(name, (files, entry_points, symbols))| {
+            let mut symbols: Vec<SymbolUsage> = symbols.into_iter()
+                .map(|(name, files)| SymbolUsage { name, files: files.len() })
+                .collect();
+            symbols.sort_by(|a, b| b.files.cmp(&a.files).then_with(|| a.name.cmp(&b.name)));
+            PackageUsage {
+                framework: name.starts_with("@angular/") || FRAMEWORK_PACKAGES.contains(&name.as_str()),
+                declared: declared.as_ref().map(|declared| declared.contains(name.as_str())),
+                name,
+                files: files.into_iter().collect(),
+                entry_points: entry_points.into_iter().collect(),
+                symbols,
+            }
+        })
+        .collect();
+    packages.sort_by(|a, b| b.files.len().cmp(&a.files.len()).then_with(|| a.name.cmp(&b.name)));
+
+    PackageUsageReport {
+        total_files: graph.files.iter().filter(|file| !file.id.starts_with("external:")).count(),
+        packages,
+    }
+}
+
+impl PackageUsageReport {
+    // フレームワーク以外で import の多いライブラリ
+    pub fn dominant(&self, limit: usize) -> impl Iterator<Item = &PackageUsage> {
+        self.libraries().take(limit)
+    }
+
+    // 1 ファイルからしか使われておらず、置き換えや削除を検討できるパッケージ
+    pub fn removal_candidates(&self) -> impl Iterator<Item = &PackageUsage> {
+        self.libraries().filter(|package| package.files.len() == 1)
+    }
+
+    // package.json にないものはパスのエイリアスの可能性が高いので除く
+    fn libraries(&self) -> impl Iterator<Item = &PackageUsage> {
+        self.packages.iter().filter(|package| !package.framework && package.declared != Some(false))
+    }
+
+    // tsconfig の paths のエイリアスなど、package.json にないもの
+    pub fn undeclared(&self) -> impl Iterator<Item = &PackageUsage> {
+        self.packages.iter().filter(|package| package.declared == Some(false))
+    }
+}
+
+impl PackageUsage {
+    pub fn share(&self, total_files: usize) -> f64 {
+        if total_files == 0 {
+            0.0
+        } else {
+            self.files.len() as f64 * 100.0 / total_files as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Import, PackageManifest, DeclaredDependency};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_build_package_usage() {
+        let import = |file_path: &str, source_module: &str, symbol_name: &str, import_type: ImportType| Import {
+            file_path: file_path.to_string(),
+            symbol_name: symbol_name.to_string(),
+            source_module: source_module.to_string(),
+            import_type,
+            line_number: None,
+        };
+        let graph = ImportExportGraph {
+            imports: vec![
+                import("a.ts", "@angular/core", "Component", ImportType::Named),
+                import("a.ts", "rxjs/operators", "map", ImportType::Named),
+                import("b.ts", "rxjs/operators", "map", ImportType::Named),
+                import("b.ts", "rxjs", "Observable", ImportType::Named),
+                import("b.ts", "moment", "moment", ImportType::Default),
+                import("c.ts", "lodash/debounce", "debounce", ImportType::Default),
+                import("c.ts", "./local", "Local", ImportType::Named),
+            ],
+            ..Default::default()
+        };
+        let dependency = |name: &str| DeclaredDependency {
+            name: name.to_string(),
+            range: "^1.0.0".to_string(),
+            resolved: None,
+        };
+        let workspace = WorkspacePackages {
+            root_path: PathBuf::from("."),
+            lockfile: None,
+            manifests: vec![PackageManifest {
+                file_path: "package.json".to_string(),
+                name: None,
+                dependencies: vec![dependency("@angular/core"), dependency("rxjs"), dependency("moment")],
+            }],
+            installed: vec![],
+        };
+
+        let report = build_package_usage(&graph, Some(&workspace));
+
+        let names: Vec<&str> = report.packages.iter().map(|package| package.name.as_str()).collect();
+        assert_eq!(names, vec!["rxjs", "@angular/core", "lodash", "moment"]);
+        let rxjs = &report.packages[0];
+        assert_eq!(rxjs.entry_points, vec!["rxjs/operators"]);
+        assert_eq!((rxjs.symbols[0].name.as_str(), rxjs.symbols[0].files), ("map", 2));
+        assert_eq!(report.dominant(1).next().unwrap().name, "rxjs");
+        let candidates: Vec<&str> = report.removal_candidates().map(|package| package.name.as_str()).collect();
+        assert_eq!(candidates, vec!["moment"]);
+        assert_eq!(report.undeclared().map(|package| package.name.as_str()).collect::<Vec<_>>(), vec!["lodash"]);
+    }
+}
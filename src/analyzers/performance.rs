@@ -1,13 +1,67 @@
 use super::{Analyzer, AnalysisResult};
-use crate::ast::{NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority};
-use async_trait::async_trait;
+use super::bundle_estimate::{estimate_bundles, lazy_boundary_imports};
+use super::component_tree::{selector_index, subtree_lines};
+use crate::ast::{CdHotspot, ChangeDetectionStrategy, NgComponent, NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority, TemplateControlFlow, TemplateLoop};
+use crate::config::relative_issue_path;
+use crate::parsers::typescript::{identity_member, TypeScriptParser};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Arc;
 use anyhow::Result;
 
-pub struct PerformanceAnalyzer;
+const EXTERNAL_URL_PREFIXES: [&str; 5] = ["http://", "https://", "//", "mailto:", "tel:"];
+// 変更検知のホットスポットとして一覧にする数
+const CD_HOTSPOT_COUNT: usize = 10;
+
+pub struct PerformanceAnalyzer {
+    allowed_navigation_urls: Vec<String>,
+    max_mark_for_check: usize,
+    defer_min_lines: u32,
+    fold_line: u32,
+    below_fold_min_lines: u32,
+}
+
+// @defer の候補。lines は子孫コンポーネントを含む論理行数
+struct DeferCandidate<'a> {
+    parent: &'a NgComponent,
+    child: &'a NgComponent,
+    tag: &'a str,
+    line: u32,
+    lines: u32,
+    nested: usize,
+}
 
 impl PerformanceAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self {
+            allowed_navigation_urls: Vec::new(),
+            max_mark_for_check: 5,
+            defer_min_lines: 200,
+            fold_line: 40,
+            below_fold_min_lines: 300,
+        }
+    }
+
+    pub fn with_below_fold_limits(mut self, fold_line: u32, min_lines: u32) -> Self {
+        self.fold_line = fold_line;
+        self.below_fold_min_lines = min_lines;
+        self
+    }
+
+    pub fn with_defer_min_lines(mut self, min_lines: u32) -> Self {
+        self.defer_min_lines = min_lines;
+        self
+    }
+
+    pub fn with_max_mark_for_check(mut self, max_calls: usize) -> Self {
+        self.max_mark_for_check = max_calls;
+        self
+    }
+
+    pub fn with_allowed_navigation_urls(mut self, urls: Vec<String>) -> Self {
+        self.allowed_navigation_urls = urls;
+        self
     }
 
     fn analyze_bundle_size_impact(&self, project: &NgProject) -> Vec<Issue> {
@@ -25,6 +79,7 @@ impl PerformanceAnalyzer {
                     file_path: component.file_path.clone(),
                     line: None,
                     column: None,
+                    fingerprint: None,
                 });
             }
 
@@ -40,6 +95,7 @@ impl PerformanceAnalyzer {
                         file_path: component.file_path.clone(),
                         line: None,
                         column: None,
+                        fingerprint: None,
                     });
                 }
             }
@@ -48,6 +104,63 @@ impl PerformanceAnalyzer {
         issues
     }
 
+    // 初期バンドルに含まれないのに複数の遅延読み込みルートが静的に import しているファイル
+    fn analyze_lazy_route_bundles(&self, project: &NgProject) -> Vec<Issue> {
+        let Some(graph) = &project.import_graph else {
+            return Vec::new();
+        };
+
+        let estimate = estimate_bundles(project, graph);
+        estimate.shared.iter()
+            .map(|shared| Issue {
+                severity: Severity::Info,
+                rule: "lazy-route-shared-code".to_string(),
+                message: format!(
+                    "'{}' ({} bytes) is not in the initial bundle but is pulled into {} lazy routes ({}). Consider moving it into a shared library so it is loaded once as a common chunk.",
+                    shared.file_path,
+                    shared.bytes,
+                    shared.chunks.len(),
+                    shared.chunks.join(", ")
+                ),
+                file_path: graph.files.iter()
+                    .find(|file| file.relative_path == shared.file_path)
+                    .map(|file| file.file_path.clone())
+                    .unwrap_or_else(|| shared.file_path.clone()),
+                line: None,
+                column: None,
+                fingerprint: None,
+            })
+            .collect()
+    }
+
+    // 初期バンドルから遅延読み込みされる機能のファイルを静的に import していて、遅延読み込みが効いていないもの
+    fn analyze_lazy_route_boundaries(&self, project: &NgProject) -> Vec<Issue> {
+        let Some(graph) = &project.import_graph else {
+            return Vec::new();
+        };
+
+        lazy_boundary_imports(project, graph).into_iter()
+            .map(|violation| {
+                let route_location = match violation.route_line {
+                    Some(line) => format!("{}:{}", relative_issue_path(&violation.route_file, &project.root_path), line),
+                    None => relative_issue_path(&violation.route_file, &project.root_path),
+                };
+                Issue {
+                    severity: Severity::Warning,
+                    rule: "lazy-route-eager-import".to_string(),
+                    message: format!(
+                        "'{}' is part of the lazily loaded route '{}' ({}) but is imported statically from the initial bundle, adding about {} bytes to it. Import it only inside the feature or move the shared code out of the feature directory.",
+                        violation.target, violation.route, route_location, violation.bytes
+                    ),
+                    file_path: violation.importer,
+                    line: violation.line,
+                    column: None,
+                    fingerprint: None,
+                }
+            })
+            .collect()
+    }
+
     fn analyze_change_detection_performance(&self, project: &NgProject) -> Vec<Issue> {
         let mut issues = Vec::new();
 
@@ -71,6 +184,7 @@ impl PerformanceAnalyzer {
                     file_path: project.root_path.display().to_string().replace('\\', "/"),
                     line: None,
                     column: None,
+                    fingerprint: None,
                 });
             }
         }
@@ -88,6 +202,7 @@ impl PerformanceAnalyzer {
                     file_path: component.file_path.clone(),
                     line: None,
                     column: None,
+                    fingerprint: None,
                 });
             }
         }
@@ -109,6 +224,7 @@ impl PerformanceAnalyzer {
                 file_path: project.root_path.display().to_string(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
@@ -129,6 +245,7 @@ impl PerformanceAnalyzer {
                 file_path: project.root_path.display().to_string(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
@@ -158,6 +275,7 @@ impl PerformanceAnalyzer {
                     file_path: component.file_path.clone(),
                     line: None,
                     column: None,
+                    fingerprint: None,
                 });
             }
         }
@@ -165,6 +283,49 @@ impl PerformanceAnalyzer {
         issues
     }
 
+    // window / document のリスナーはコンポーネントが破棄されても残り、コンポーネントごとリークする
+    fn analyze_global_listeners(&self, project: &NgProject) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        for component in &project.components {
+            for listener in &component.host.listeners {
+                if listener.target == "host" {
+                    continue;
+                }
+                if !listener.removed {
+                    issues.push(Issue {
+                        severity: Severity::Warning,
+                        rule: "global-listener-leak".to_string(),
+                        message: format!(
+                            "Component '{}' adds a '{}' listener to {} with addEventListener but never removes it in ngOnDestroy. Remove it there, pass an AbortSignal, or use @HostListener('{}:{}').",
+                            component.name, listener.event, listener.target, listener.target, listener.event
+                        ),
+                        file_path: component.file_path.clone(),
+                        line: None,
+                        column: None,
+                        fingerprint: None,
+                    });
+                }
+                if matches!(listener.event.as_str(), "scroll" | "resize") && !listener.throttled {
+                    issues.push(Issue {
+                        severity: Severity::Warning,
+                        rule: "unthrottled-scroll-listener".to_string(),
+                        message: format!(
+                            "Component '{}' handles every '{}' event on {} ({}) without debouncing. Throttle the handler with requestAnimationFrame or debounceTime/auditTime.",
+                            component.name, listener.event, listener.target, listener.source
+                        ),
+                        file_path: component.file_path.clone(),
+                        line: None,
+                        column: None,
+                        fingerprint: None,
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
     fn analyze_excessive_watchers(&self, project: &NgProject) -> Vec<Issue> {
         let mut issues = Vec::new();
 
@@ -182,6 +343,7 @@ impl PerformanceAnalyzer {
                     file_path: component.file_path.clone(),
                     line: None,
                     column: None,
+                    fingerprint: None,
                 });
             }
         }
@@ -189,7 +351,385 @@ impl PerformanceAnalyzer {
         issues
     }
 
-    fn generate_performance_recommendations(&self, project: &NgProject) -> Vec<Recommendation> {
+    fn analyze_location_navigation(&self, project: &NgProject) -> Vec<Issue> {
+        if !self.is_routed_app(project) {
+            return Vec::new();
+        }
+
+        let classes = project.components.iter()
+            .map(|c| (&c.name, &c.file_path, &c.location_navigations))
+            .chain(project.services.iter().map(|s| (&s.name, &s.file_path, &s.location_navigations)));
+
+        let mut issues = Vec::new();
+        for (name, file_path, navigations) in classes {
+            // 外部 URL への遷移は Router では扱えないので除外
+            let internal: Vec<_> = navigations.iter()
+                .filter(|nav| !nav.target.as_deref().is_some_and(|target| self.is_allowed_url(target)))
+                .collect();
+            if internal.is_empty() {
+                continue;
+            }
+
+            let usages: Vec<String> = internal.iter()
+                .map(|nav| match nav.method.as_str() {
+                    "assign" | "replace" => format!("location.{}()", nav.method),
+                    "location" => "window.location =".to_string(),
+                    _ => "location.href =".to_string(),
+                })
+                .collect();
+
+            issues.push(Issue {
+                severity: Severity::Warning,
+                rule: "window-location-navigation".to_string(),
+                message: format!(
+                    "'{}' navigates with {}, which reloads the whole application. Use Router.navigate() or Router.navigateByUrl() to keep SPA state.",
+                    name,
+                    usages.join(", ")
+                ),
+                file_path: file_path.clone(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        issues
+    }
+
+    fn analyze_manual_change_detection(&self, project: &NgProject) -> Vec<Issue> {
+        let classes = project.components.iter()
+            .map(|c| (&c.name, &c.file_path, &c.change_detection_calls))
+            .chain(project.services.iter().map(|s| (&s.name, &s.file_path, &s.change_detection_calls)));
+
+        let mut issues = Vec::new();
+        for (name, file_path, calls) in classes {
+            let count = |method: &str| calls.iter().filter(|call| call.method == method).count();
+            let ticks = count("tick");
+            let mark_for_checks = count("markForCheck");
+            let in_loop = calls.iter().filter(|call| call.in_loop).count();
+
+            if ticks == 0 && in_loop == 0 && mark_for_checks <= self.max_mark_for_check {
+                continue;
+            }
+
+            let usages: Vec<String> = ["tick", "detectChanges", "markForCheck"].iter()
+                .map(|method| (method, count(method)))
+                .filter(|(_, n)| *n > 0)
+                .map(|(method, n)| format!("{}() x{}", method, n))
+                .collect();
+
+            issues.push(Issue {
+                severity: Severity::Warning,
+                rule: "manual-change-detection".to_string(),
+                message: format!(
+                    "'{}' triggers change detection manually: {} ({} inside loops or intervals). This usually hides a data-flow problem; prefer OnPush with the async pipe or signals.",
+                    name,
+                    usages.join(", "),
+                    in_loop
+                ),
+                file_path: file_path.clone(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        issues
+    }
+
+    // @for は track が必須。ないとテンプレートのコンパイルが通らない
+    fn analyze_for_track(&self, project: &NgProject) -> Vec<Issue> {
+        let mut keys = IdentityKeys::new(project);
+        let mut issues = Vec::new();
+        for flow in project.components.iter().filter_map(|c| c.control_flow.as_ref()) {
+            let blocks = flow.blocks.iter()
+                .filter(|block| block.kind == "for")
+                .filter(|block| !block.expression.split(';').any(|part| part.trim_start().starts_with("track ")));
+            for block in blocks {
+                let found = flow.loops.iter().find(|found| found.kind == "for" && found.line == block.line);
+                let track = match found.and_then(|found| keys.track_expression(found)) {
+                    Some(track) => format!("`track {}`", track),
+                    None => "`track item.id` (or `track $index` for static lists)".to_string(),
+                };
+                issues.push(Issue {
+                    severity: Severity::Error,
+                    rule: "for-missing-track".to_string(),
+                    message: format!(
+                        "@for ({}) has no track expression. Add {} so rows are reused instead of re-created.",
+                        block.expression, track
+                    ),
+                    file_path: flow.file_path.clone(),
+                    line: Some(block.line),
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+        issues
+    }
+
+    // trackBy のない *ngFor。要素の型に id などがあれば、それで見分けるよう提案する
+    fn analyze_ng_for_track_by(&self, project: &NgProject) -> Vec<Issue> {
+        let mut keys = IdentityKeys::new(project);
+        let mut issues = Vec::new();
+        for flow in project.components.iter().filter_map(|c| c.control_flow.as_ref()) {
+            for found in flow.loops.iter().filter(|found| found.kind == "ngFor" && found.track.is_none()) {
+                let suggestion = match (keys.track_expression(found), &found.item_type) {
+                    (Some(track), Some(item_type)) => format!("Add `trackBy: trackById` and return {} ({}.{}) from it.", track, item_type, track.rsplit('.').next().unwrap_or_default()),
+                    (None, Some(item_type)) => format!("{} has no id-like member; return a stable key of each {} from a trackBy function.", item_type, found.item),
+                    _ => "Add `trackBy: trackById` returning a stable id of each item.".to_string(),
+                };
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "missing-trackby".to_string(),
+                    message: format!(
+                        "*ngFor=\"let {} of {}\" has no trackBy, so rows are re-created whenever the list changes. {} `ng-analyzer fix --rules missing-trackby` adds a trackById stub.",
+                        found.item, found.iterable, suggestion
+                    ),
+                    file_path: flow.file_path.clone(),
+                    line: Some(found.line),
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+        issues
+    }
+
+    // pure: false のパイプは変更検知のたびに transform が走る
+    fn analyze_impure_pipes(&self, project: &NgProject) -> Vec<Issue> {
+        let flows: Vec<&TemplateControlFlow> = project.components.iter().filter_map(|c| c.control_flow.as_ref()).collect();
+        project.pipes.iter()
+            .filter(|pipe| !pipe.pure)
+            .map(|pipe| {
+                let counts: Vec<usize> = flows.iter().filter_map(|flow| flow.pipe_counts().get(pipe.pipe_name.as_str()).copied()).collect();
+                Issue {
+                    severity: Severity::Warning,
+                    rule: "impure-pipe".to_string(),
+                    message: format!(
+                        "Pipe '{}' ({}) is impure (pure: false), so transform() runs on every change detection cycle. It is used {} times in {} templates. Make it pure and pass new references when the input changes, or move the state into a computed signal.",
+                        pipe.pipe_name, pipe.name, counts.iter().sum::<usize>(), counts.len()
+                    ),
+                    file_path: pipe.file_path.clone(),
+                    line: pipe.line,
+                    column: None,
+                    fingerprint: None,
+                }
+            })
+            .collect()
+    }
+
+    // 配列を並べ替え・絞り込むパイプを *ngFor / @for の中で使うと、行ごとに配列全体をたどる
+    fn analyze_heavy_pipes_in_loops(&self, project: &NgProject) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        for flow in project.components.iter().filter_map(|c| c.control_flow.as_ref()) {
+            for pipe in project.pipes.iter().filter(|pipe| !pipe.array_operations.is_empty() && !pipe.pipe_name.is_empty()) {
+                let lines: Vec<u32> = flow.pipes.iter()
+                    .filter(|usage| usage.in_loop && usage.name == pipe.pipe_name)
+                    .map(|usage| usage.line)
+                    .collect();
+                let Some(&line) = lines.first() else { continue };
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "heavy-pipe-in-loop".to_string(),
+                    message: format!(
+                        "Pipe '{}' ({}) calls {} in transform() and is used {} times inside *ngFor / @for, so it walks the array for every row. Compute the list once outside the loop, e.g. with a computed signal.",
+                        pipe.pipe_name, pipe.name, pipe.array_operations.join(", "), lines.len()
+                    ),
+                    file_path: flow.file_path.clone(),
+                    line: Some(line),
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+        issues
+    }
+
+    // テンプレートから呼ぶコンポーネントのメソッドは変更検知のたびに実行される。signal の読み出しは除く
+    fn analyze_template_method_calls(&self, project: &NgProject) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        for component in &project.components {
+            let Some(flow) = &component.control_flow else { continue };
+            for method in &component.public_methods {
+                let calls: Vec<_> = flow.calls.iter().filter(|call| call.name == *method).collect();
+                let Some(first) = calls.first() else { continue };
+                let in_loop = calls.iter().any(|call| call.in_loop);
+                issues.push(Issue {
+                    severity: if in_loop { Severity::Warning } else { Severity::Info },
+                    rule: "template-method-call".to_string(),
+                    message: format!(
+                        "{}.{}() is called {} times from the template{} and runs on every change detection cycle. Replace it with a pure pipe or a computed signal.",
+                        component.name, method, calls.len(), if in_loop { " (inside *ngFor / @for)" } else { "" }
+                    ),
+                    file_path: flow.file_path.clone(),
+                    line: Some(first.line),
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+        issues
+    }
+
+    // @if / @switch の分岐でだけ描画される大きな standalone コンポーネント。@defer で別チャンクに分けられる
+    fn analyze_defer_opportunities(&self, project: &NgProject) -> Vec<Issue> {
+        let by_tag = selector_index(project);
+
+        let mut issues = Vec::new();
+        for component in &project.components {
+            let Some(flow) = &component.control_flow else { continue };
+            for block in &flow.blocks {
+                if block.deferred || !matches!(block.kind.as_str(), "if" | "switch") {
+                    continue;
+                }
+                for tag in &block.children {
+                    let Some(child) = by_tag.get(tag.as_str()) else { continue };
+                    if child.name == component.name || !child.standalone || child.lines.logical < self.defer_min_lines {
+                        continue;
+                    }
+                    let trigger = match block.kind.as_str() {
+                        "if" => format!("@defer (when {})", block.expression),
+                        _ => "@defer (on idle)".to_string(),
+                    };
+                    issues.push(Issue {
+                        severity: Severity::Info,
+                        rule: "defer-heavy-component".to_string(),
+                        message: format!(
+                            "<{}> ({}, {} lines) is only rendered inside @{} ({}). Wrap it in `{}` so it is split into its own chunk and loaded on demand.",
+                            tag, child.name, child.lines.logical, block.kind, block.expression, trigger
+                        ),
+                        file_path: flow.file_path.clone(),
+                        line: Some(block.line),
+                        column: None,
+                        fingerprint: None,
+                    });
+                }
+            }
+        }
+
+        issues
+    }
+
+    // テンプレートの fold_line 行目より後ろ（最初の画面の外）で描画される大きな standalone コンポーネント。
+    // @if / @switch の中にあるものは defer-heavy-component で扱う
+    fn below_fold_candidates<'a>(&self, project: &'a NgProject) -> Vec<DeferCandidate<'a>> {
+        let by_tag = selector_index(project);
+
+        let mut candidates: Vec<DeferCandidate> = Vec::new();
+        for parent in &project.components {
+            let Some(flow) = &parent.control_flow else { continue };
+            let conditional = |tag: &str| flow.blocks.iter()
+                .any(|block| !block.deferred && matches!(block.kind.as_str(), "if" | "switch") && block.children.iter().any(|child| child == tag));
+            for element in flow.elements.iter().filter(|element| !element.deferred && self.is_below_fold(flow, element.line)) {
+                let Some(child) = by_tag.get(element.tag.as_str()) else { continue };
+                if child.name == parent.name || !child.standalone || conditional(&element.tag) {
+                    continue;
+                }
+                if candidates.iter().any(|c| c.parent.name == parent.name && c.child.name == child.name) {
+                    continue;
+                }
+                let (lines, nested) = subtree_lines(child, &by_tag);
+                if lines >= self.below_fold_min_lines {
+                    candidates.push(DeferCandidate { parent, child, tag: &element.tag, line: element.line, lines, nested });
+                }
+            }
+        }
+        candidates
+    }
+
+    fn is_below_fold(&self, flow: &TemplateControlFlow, line: u32) -> bool {
+        line + 1 - flow.start_line.max(1) > self.fold_line
+    }
+
+    fn analyze_below_fold(&self, project: &NgProject, candidates: &[DeferCandidate]) -> Vec<Issue> {
+        let mut issues: Vec<Issue> = candidates.iter()
+            .filter_map(|candidate| {
+                let flow = candidate.parent.control_flow.as_ref()?;
+                Some(Issue {
+                    severity: Severity::Info,
+                    rule: "defer-below-the-fold".to_string(),
+                    message: format!(
+                        "<{}> ({}, ~{} lines including {} nested components) is rendered below the fold at template line {}. Wrap it in `@defer (on viewport)` with a @placeholder so it is loaded when scrolled into view.",
+                        candidate.tag,
+                        candidate.child.name,
+                        candidate.lines,
+                        candidate.nested,
+                        candidate.line + 1 - flow.start_line.max(1)
+                    ),
+                    file_path: flow.file_path.clone(),
+                    line: Some(candidate.line),
+                    column: None,
+                    fingerprint: None,
+                })
+            })
+            .collect();
+
+        // NgOptimizedImage の ngSrc は既定で遅延読み込みになる
+        for flow in project.components.iter().filter_map(|c| c.control_flow.as_ref()) {
+            let lines: Vec<String> = flow.elements.iter()
+                .filter(|element| element.tag == "img" && !element.lazy && !element.deferred && self.is_below_fold(flow, element.line))
+                .map(|element| element.line.to_string())
+                .collect();
+            if lines.is_empty() {
+                continue;
+            }
+            issues.push(Issue {
+                severity: Severity::Info,
+                rule: "defer-below-the-fold".to_string(),
+                message: format!(
+                    "{} <img> element(s) below the fold (lines {}) are loaded eagerly. Add loading=\"lazy\" or use NgOptimizedImage (ngSrc).",
+                    lines.len(),
+                    lines.join(", ")
+                ),
+                file_path: flow.file_path.clone(),
+                line: lines[0].parse().ok(),
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        issues
+    }
+
+    fn is_routed_app(&self, project: &NgProject) -> bool {
+        let uses_router = |deps: &[String]| deps.iter().any(|d| d == "Router" || d == "ActivatedRoute");
+
+        project.modules.iter().any(|m| m.file_path.contains("routing") || m.imports.iter().any(|i| i == "RouterModule"))
+            || project.components.iter().any(|c| uses_router(&c.dependencies) || !c.router_event_subscriptions.is_empty())
+            || project.services.iter().any(|s| uses_router(&s.dependencies))
+    }
+
+    fn is_allowed_url(&self, target: &str) -> bool {
+        EXTERNAL_URL_PREFIXES.iter().any(|prefix| target.starts_with(prefix))
+            || self.allowed_navigation_urls.iter().any(|prefix| target.starts_with(prefix.as_str()))
+    }
+
+    // Default のコンポーネントを変更検知のコストの大きい順に並べる
+    fn cd_hotspots(&self, project: &NgProject) -> Vec<CdHotspot> {
+        let by_tag = selector_index(project);
+        let mut memo: HashMap<&str, (u32, u32)> = HashMap::new();
+        let mut hotspots: Vec<CdHotspot> = project.components.iter()
+            .filter(|component| matches!(component.change_detection, ChangeDetectionStrategy::Default))
+            .map(|component| {
+                let (subtree_bindings, depth) = default_subtree(component, &by_tag, &mut memo, &mut HashSet::new());
+                CdHotspot {
+                    component: component.name.clone(),
+                    file_path: component.file_path.clone(),
+                    bindings: checked_expressions(component),
+                    subtree_bindings,
+                    depth,
+                    cost: subtree_bindings * (depth + 1),
+                }
+            })
+            .filter(|hotspot| hotspot.cost > 0)
+            .collect();
+        hotspots.sort_by(|a, b| b.cost.cmp(&a.cost).then_with(|| a.component.cmp(&b.component)));
+        hotspots.truncate(CD_HOTSPOT_COUNT);
+        hotspots
+    }
+
+    fn generate_performance_recommendations(&self, project: &NgProject, hotspots: &[CdHotspot]) -> Vec<Recommendation> {
         let mut recommendations = Vec::new();
 
         let onpush_candidates = project.components.iter()
@@ -198,12 +738,21 @@ impl PerformanceAnalyzer {
             .count();
 
         if onpush_candidates > 0 {
+            // 見積もったコストの大きいものから順に OnPush にする
+            let priorities = if hotspots.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    " Start with the components with the highest estimated change detection cost: {}.",
+                    hotspots.iter().take(5).map(|hotspot| format!("{} (cost {})", hotspot.component, hotspot.cost)).collect::<Vec<_>>().join(", ")
+                )
+            };
             recommendations.push(Recommendation {
                 category: "Performance".to_string(),
                 title: "Implement OnPush Change Detection".to_string(),
                 description: format!(
-                    "Implement OnPush change detection in {} components to improve performance and reduce unnecessary re-renders.",
-                    onpush_candidates
+                    "Implement OnPush change detection in {} components to improve performance and reduce unnecessary re-renders.{}",
+                    onpush_candidates, priorities
                 ),
                 priority: Priority::High,
                 file_path: None,
@@ -243,7 +792,7 @@ impl PerformanceAnalyzer {
         }
 
         let inline_template_components = project.components.iter()
-            .filter(|c| c.template.as_ref().map_or(false, |t| t.len() > 500))
+            .filter(|c| c.template.as_ref().is_some_and(|t| t.len() > 500))
             .count();
 
         if inline_template_components > 0 {
@@ -262,17 +811,33 @@ impl PerformanceAnalyzer {
         recommendations
     }
 
-    fn calculate_performance_metrics(&self, project: &NgProject) -> ProjectMetrics {
-        let total_components = project.components.len() as u32;
-        let onpush_components = project.components.iter()
-            .filter(|c| matches!(c.change_detection, crate::ast::ChangeDetectionStrategy::OnPush))
-            .count() as u32;
+    fn generate_defer_recommendations(&self, candidates: &[DeferCandidate]) -> Vec<Recommendation> {
+        let mut by_child: Vec<(&str, u32)> = Vec::new();
+        for candidate in candidates {
+            if !by_child.iter().any(|(name, _)| *name == candidate.child.name) {
+                by_child.push((&candidate.child.name, candidate.lines));
+            }
+        }
+        if by_child.is_empty() {
+            return Vec::new();
+        }
+        by_child.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
 
-        let onpush_percentage = if total_components > 0 {
-            (onpush_components as f64 / total_components as f64) * 100.0
-        } else {
-            0.0
-        };
+        let listed: Vec<String> = by_child.iter().map(|(name, lines)| format!("{} (~{} lines)", name, lines)).collect();
+        vec![Recommendation {
+            category: "Performance".to_string(),
+            title: "Defer Below-the-Fold Components".to_string(),
+            description: format!(
+                "Wrap these components in `@defer (on viewport)` where they are rendered below the fold to shrink the initial chunk: {}.",
+                listed.join(", ")
+            ),
+            priority: Priority::Medium,
+            file_path: None,
+        }]
+    }
+
+    fn calculate_performance_metrics(&self, project: &NgProject, cd_hotspots: Vec<CdHotspot>) -> ProjectMetrics {
+        let total_components = project.components.len() as u32;
 
         let average_complexity = if total_components > 0 {
             project.components.iter()
@@ -287,31 +852,124 @@ impl PerformanceAnalyzer {
             total_services: project.services.len() as u32,
             total_modules: project.modules.len() as u32,
             average_complexity,
-            lines_of_code: 0,
-            test_coverage: Some(onpush_percentage),
+            lines_of_code: project.files.iter().map(|f| f.lines.logical).sum(),
+            test_coverage: project.test_coverage,
+            feature_testability: Vec::new(),
+            adoption: Some(super::adoption_metrics(project)),
+            render_depth: None,
+            duplicated_lines: None,
+            duplication_percentage: None,
+            zoneless_readiness: Vec::new(),
+            api_surface: None,
+            type_safety: None,
+            cd_hotspots,
+        }
+    }
+}
+
+fn checked_expressions(component: &NgComponent) -> u32 {
+    component.template_complexity.as_ref().map_or(0, |complexity| complexity.checked_expressions())
+}
+
+// OnPush の子の手前までの (子孫を含む式の数, Default の子孫の段数)。同じ子を何度も描画していればその回数分を数える
+fn default_subtree<'a>(
+    component: &'a NgComponent,
+    by_tag: &HashMap<&str, &'a NgComponent>,
+    memo: &mut HashMap<&'a str, (u32, u32)>,
+    visiting: &mut HashSet<&'a str>,
+) -> (u32, u32) {
+    if let Some(found) = memo.get(component.name.as_str()) {
+        return *found;
+    }
+    if !visiting.insert(component.name.as_str()) {
+        return (0, 0);
+    }
+    let mut bindings = checked_expressions(component);
+    let mut depth = 0;
+    for usage in &component.template_usages {
+        let Some(child) = by_tag.get(usage.tag.as_str()).copied() else { continue };
+        if child.name == component.name || !matches!(child.change_detection, ChangeDetectionStrategy::Default) {
+            continue;
         }
+        let (child_bindings, child_depth) = default_subtree(child, by_tag, memo, visiting);
+        bindings += child_bindings * usage.count.max(1);
+        depth = depth.max(child_depth + 1);
+    }
+    visiting.remove(component.name.as_str());
+    memo.insert(component.name.as_str(), (bindings, depth));
+    (bindings, depth)
+}
+
+// 繰り返す要素の型から、見分けるのに使うメンバーを探す。型ごとに 1 度だけ宣言を読む
+struct IdentityKeys<'a> {
+    project: &'a NgProject,
+    parser: TypeScriptParser,
+    members: HashMap<String, Option<Vec<String>>>,
+}
+
+impl<'a> IdentityKeys<'a> {
+    fn new(project: &'a NgProject) -> Self {
+        Self { project, parser: TypeScriptParser::new(), members: HashMap::new() }
+    }
+
+    // order.id のような track の式
+    fn track_expression(&mut self, found: &TemplateLoop) -> Option<String> {
+        let item_type = found.item_type.as_deref()?;
+        if !self.members.contains_key(item_type) {
+            let members = self.type_members(item_type);
+            self.members.insert(item_type.to_string(), members);
+        }
+        let members = self.members[item_type].as_deref()?;
+        identity_member(members).map(|member| format!("{}.{}", found.item, member))
+    }
+
+    fn type_members(&self, name: &str) -> Option<Vec<String>> {
+        let declaration = Regex::new(&format!(r"\b(?:interface|class|type)\s+{}\b", regex::escape(name))).ok()?;
+        self.project.files.iter().find_map(|file| {
+            let path = Path::new(&file.file_path);
+            if !self.project.sources.read(path).is_ok_and(|source| declaration.is_match(&source.content)) {
+                return None;
+            }
+            let (_, module) = self.project.sources.module(path, |content| self.parser.parse_file(content)).ok()?;
+            self.parser.extract_type_members(&module, name)
+        })
     }
 }
 
-#[async_trait]
 impl Analyzer for PerformanceAnalyzer {
-    async fn analyze(&self, project: &NgProject) -> Result<AnalysisResult> {
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
         let mut all_issues = Vec::new();
 
         all_issues.extend(self.analyze_bundle_size_impact(project));
+        all_issues.extend(self.analyze_lazy_route_bundles(project));
+        all_issues.extend(self.analyze_lazy_route_boundaries(project));
         all_issues.extend(self.analyze_change_detection_performance(project));
         all_issues.extend(self.analyze_lazy_loading_opportunities(project));
         all_issues.extend(self.analyze_memory_leaks_risk(project));
+        all_issues.extend(self.analyze_global_listeners(project));
         all_issues.extend(self.analyze_excessive_watchers(project));
+        all_issues.extend(self.analyze_location_navigation(project));
+        all_issues.extend(self.analyze_manual_change_detection(project));
+        all_issues.extend(self.analyze_for_track(project));
+        all_issues.extend(self.analyze_ng_for_track_by(project));
+        all_issues.extend(self.analyze_impure_pipes(project));
+        all_issues.extend(self.analyze_heavy_pipes_in_loops(project));
+        all_issues.extend(self.analyze_template_method_calls(project));
+        all_issues.extend(self.analyze_defer_opportunities(project));
+        let candidates = self.below_fold_candidates(project);
+        all_issues.extend(self.analyze_below_fold(project, &candidates));
 
-        let recommendations = self.generate_performance_recommendations(project);
-        let metrics = self.calculate_performance_metrics(project);
+        let cd_hotspots = self.cd_hotspots(project);
+        let mut recommendations = self.generate_performance_recommendations(project, &cd_hotspots);
+        recommendations.extend(self.generate_defer_recommendations(&candidates));
+        let metrics = self.calculate_performance_metrics(project, cd_hotspots);
 
         Ok(AnalysisResult {
             project: project.clone(),
             issues: all_issues,
             metrics,
             recommendations,
+            omitted_issues: vec![],
         })
     }
 
@@ -322,4 +980,203 @@ impl Analyzer for PerformanceAnalyzer {
     fn description(&self) -> &'static str {
         "Analyzes performance implications, change detection strategies, and optimization opportunities"
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::{ComponentBuilder, NgProjectBuilder};
+    use crate::ast::{ControlFlowBlock, EventListenerUsage, TemplateElement};
+
+    fn block(kind: &str, expression: &str, line: u32, children: &[&str], deferred: bool) -> ControlFlowBlock {
+        ControlFlowBlock {
+            kind: kind.to_string(),
+            expression: expression.to_string(),
+            line,
+            children: children.iter().map(|c| c.to_string()).collect(),
+            deferred,
+        }
+    }
+
+    #[test]
+    fn test_cd_hotspots() {
+        let project = NgProjectBuilder::new(".")
+            .component(ComponentBuilder::new("ShellComponent").complexity(9).template_complexity(1, 1, 4, 0).uses("app-list", "element", 1).uses("app-footer", "element", 1))
+            .component(ComponentBuilder::new("ListComponent").selector("app-list").template_complexity(1, 1, 2, 0).uses("app-row", "element", 2))
+            .component(ComponentBuilder::new("RowComponent").selector("app-row").template_complexity(0, 0, 6, 0))
+            .component(ComponentBuilder::new("FooterComponent").selector("app-footer").on_push().template_complexity(0, 0, 10, 0))
+            .build();
+        let analyzer = PerformanceAnalyzer::new();
+
+        let hotspots = analyzer.cd_hotspots(&project);
+        let found: Vec<(&str, u32, u32, u32)> = hotspots.iter()
+            .map(|hotspot| (hotspot.component.as_str(), hotspot.subtree_bindings, hotspot.depth, hotspot.cost))
+            .collect();
+        // FooterComponent は OnPush なので、ShellComponent の子孫にも数えない
+        assert_eq!(found, vec![("ShellComponent", 20, 2, 60), ("ListComponent", 15, 1, 30), ("RowComponent", 6, 0, 6)]);
+
+        let recommendations = analyzer.generate_performance_recommendations(&project, &hotspots);
+        assert!(recommendations[0].description.ends_with("cost: ShellComponent (cost 60), ListComponent (cost 30), RowComponent (cost 6)."));
+    }
+
+    #[test]
+    fn test_control_flow_rules() {
+        let dashboard = ComponentBuilder::new("DashboardComponent")
+            .with(|c| c.control_flow = Some(TemplateControlFlow {
+                file_path: "dashboard.component.html".to_string(),
+                blocks: vec![
+                    block("for", "row of rows", 3, &[], false),
+                    block("for", "row of rows; track row.id", 6, &[], false),
+                    block("if", "showChart", 9, &["app-chart", "app-legend"], false),
+                    block("if", "showReport", 12, &["app-report"], true),
+                ],
+                ..Default::default()
+            }));
+        let project = NgProjectBuilder::new(".")
+            .component(dashboard)
+            .component(ComponentBuilder::new("ChartComponent").standalone().lines(450, 380))
+            .component(ComponentBuilder::new("LegendComponent").standalone().lines(40, 30))
+            .component(ComponentBuilder::new("ReportComponent").standalone().lines(600, 500))
+            .build();
+        let analyzer = PerformanceAnalyzer::new();
+
+        let issues = analyzer.analyze_for_track(&project);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(3));
+
+        let issues = analyzer.analyze_defer_opportunities(&project);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.starts_with("<app-chart> (ChartComponent, 380 lines)"));
+        assert!(issues[0].message.contains("`@defer (when showChart)`"));
+    }
+
+    #[tokio::test]
+    async fn test_pipe_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("sort-by.pipe.ts"), "@Pipe({ name: 'sortBy', pure: false })\nexport class SortByPipe implements PipeTransform {\n  transform(items: Item[], key: string) {\n    return [...items].sort((a, b) => a[key] - b[key]);\n  }\n}\n").unwrap();
+        std::fs::write(dir.path().join("list.component.ts"), "@Component({ selector: 'app-list', templateUrl: './list.component.html' })\nexport class ListComponent {\n  count = signal(0);\n  total() { return 1; }\n  label(item: Item) { return item.name; }\n  save() {}\n}\n").unwrap();
+        std::fs::write(dir.path().join("list.component.html"), "<p>{{ total() }} / {{ count() }}</p>\n<li *ngFor=\"let group of groups | sortBy:'name'\" [title]=\"label(group)\">\n  {{ group.items | sortBy:'price' | json }}\n</li>\n<button (click)=\"save()\">{{ 'a|b' }}</button>\n").unwrap();
+
+        let project = crate::parsers::ProjectParser::new().parse_project(&dir.path().to_path_buf()).await.unwrap();
+        assert_eq!(project.pipes.len(), 1);
+        assert_eq!(project.pipes[0].array_operations, vec!["sort"]);
+        let analyzer = PerformanceAnalyzer::new();
+
+        let issues = analyzer.analyze_impure_pipes(&project);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(2));
+        assert!(issues[0].message.contains("used 2 times in 1 templates"));
+
+        // *ngFor の式のパイプは繰り返しの外
+        let issues = analyzer.analyze_heavy_pipes_in_loops(&project);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, Some(3));
+        assert!(issues[0].message.contains("used 1 times inside"));
+
+        let issues = analyzer.analyze_template_method_calls(&project);
+        let found: Vec<(&str, Option<u32>)> = issues.iter()
+            .map(|issue| (issue.message.split(' ').next().unwrap(), issue.line))
+            .collect();
+        assert_eq!(found, vec![("ListComponent.total()", Some(1)), ("ListComponent.label()", Some(2))]);
+        assert!(matches!(issues[1].severity, Severity::Warning));
+    }
+
+    #[tokio::test]
+    async fn test_track_by_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("order.ts"), "export interface Order {\n  id: number;\n  total: number;\n}\n").unwrap();
+        std::fs::write(dir.path().join("orders.component.ts"), "import { Order } from './order';\n\n@Component({ selector: 'app-orders', templateUrl: './orders.component.html' })\nexport class OrdersComponent {\n  orders = signal<Order[]>([]);\n  orders$: Observable<Order[]>;\n  tags: string[] = [];\n}\n").unwrap();
+        std::fs::write(dir.path().join("orders.component.html"), "<li *ngFor=\"let order of orders$ | async\">{{ order.total }}</li>\n<li *ngFor=\"let tag of tags; trackBy: trackByTag\">{{ tag }}</li>\n<ng-template ngFor let-row [ngForOf]=\"rows\"></ng-template>\n@for (order of orders()) {\n  <p>{{ order.total }}</p>\n}\n").unwrap();
+
+        let project = crate::parsers::ProjectParser::new().parse_project(&dir.path().to_path_buf()).await.unwrap();
+        let loops = &project.components[0].control_flow.as_ref().unwrap().loops;
+        let found: Vec<(&str, &str, Option<&str>, Option<&str>)> = loops.iter()
+            .map(|found| (found.kind.as_str(), found.item.as_str(), found.track.as_deref(), found.item_type.as_deref()))
+            .collect();
+        assert_eq!(found, vec![
+            ("ngFor", "order", None, Some("Order")),
+            ("ngFor", "tag", Some("trackByTag"), Some("string")),
+            ("ngFor", "row", None, None),
+            ("for", "order", None, Some("Order")),
+        ]);
+        let analyzer = PerformanceAnalyzer::new();
+
+        let issues = analyzer.analyze_ng_for_track_by(&project);
+        assert_eq!(issues.iter().map(|issue| issue.line).collect::<Vec<_>>(), vec![Some(1), Some(3)]);
+        assert!(issues[0].message.contains("return order.id (Order.id)"));
+
+        let issues = analyzer.analyze_for_track(&project);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("Add `track order.id`"));
+    }
+
+    #[test]
+    fn test_global_listeners() {
+        let listener = |target: &str, event: &str, source: &str, removed: bool, throttled: bool| EventListenerUsage {
+            target: target.to_string(),
+            event: event.to_string(),
+            source: source.to_string(),
+            removed,
+            throttled,
+        };
+        let project = NgProjectBuilder::new(".")
+            .component(ComponentBuilder::new("HeaderComponent").with(|c| c.host.listeners = vec![
+                listener("window", "scroll", "HostListener", true, false),
+                listener("window", "resize", "addEventListener", false, true),
+                listener("document", "click", "addEventListener", true, false),
+                listener("host", "scroll", "HostListener", true, false),
+            ]))
+            .build();
+
+        let issues = PerformanceAnalyzer::new().analyze_global_listeners(&project);
+        let found: Vec<(&str, bool)> = issues.iter()
+            .map(|issue| (issue.rule.as_str(), issue.message.contains("'scroll'")))
+            .collect();
+        assert_eq!(found, vec![("unthrottled-scroll-listener", true), ("global-listener-leak", false)]);
+    }
+
+    fn element(tag: &str, line: u32, deferred: bool) -> TemplateElement {
+        TemplateElement { tag: tag.to_string(), line, deferred, lazy: false }
+    }
+
+    #[test]
+    fn test_below_fold_candidates() {
+        let product = ComponentBuilder::new("ProductComponent")
+            .uses("app-reviews", "embeds", 1)
+            .uses("app-gallery", "embeds", 1)
+            .with(|c| c.control_flow = Some(TemplateControlFlow {
+                file_path: "product.component.ts".to_string(),
+                start_line: 10,
+                elements: vec![
+                    element("app-gallery", 15, false),
+                    element("app-reviews", 70, false),
+                    element("app-reviews", 90, false),
+                    element("app-related", 95, true),
+                    element("img", 80, false),
+                    TemplateElement { lazy: true, ..element("img", 85, false) },
+                ],
+                ..Default::default()
+            }));
+        let project = NgProjectBuilder::new(".")
+            .component(product)
+            .component(ComponentBuilder::new("GalleryComponent").standalone().lines(500, 400))
+            .component(ComponentBuilder::new("ReviewsComponent").standalone().lines(200, 150).uses("app-rating", "embeds", 5))
+            .component(ComponentBuilder::new("RatingComponent").standalone().lines(200, 160))
+            .component(ComponentBuilder::new("RelatedComponent").standalone().lines(900, 800))
+            .build();
+        let analyzer = PerformanceAnalyzer::new();
+
+        let candidates = analyzer.below_fold_candidates(&project);
+        let found: Vec<(&str, u32, u32, usize)> = candidates.iter()
+            .map(|c| (c.child.name.as_str(), c.line, c.lines, c.nested))
+            .collect();
+        assert_eq!(found, vec![("ReviewsComponent", 70, 310, 1)]);
+
+        let issues = analyzer.analyze_below_fold(&project, &candidates);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.contains("at template line 61"));
+        assert!(issues[1].message.starts_with("1 <img> element(s) below the fold (lines 80)"));
+
+        let recommendations = analyzer.generate_defer_recommendations(&candidates);
+        assert!(recommendations[0].description.ends_with("ReviewsComponent (~310 lines)."));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::ast::{NgProject, ServiceApi, ServiceApiMethod, ServiceApiReport};
+use crate::config::relative_issue_path;
+use std::collections::{BTreeSet, HashMap};
+
+pub fn build_service_api(project: &NgProject) -> ServiceApiReport {
+    // (サービス, メソッド) ごとの呼び出し元。サービスの中からの自分自身の呼び出しは数えない
+    let mut callers: HashMap<(&str, &str), BTreeSet<&str>> = HashMap::new();
+    let calls = project.components.iter()
+        .map(|component| (component.name.as_str(), &component.service_calls))
+        .chain(project.services.iter().map(|service| (service.name.as_str(), &service.service_calls)));
+    for (caller, service_calls) in calls {
+        for call in service_calls.iter().filter(|call| call.service != caller) {
+            callers.entry((call.service.as_str(), call.method.as_str())).or_default().insert(caller);
+        }
+    }
+
+    let mut services: Vec<ServiceApi> = project.services.iter()
+        .map(|service| ServiceApi {
+            name: service.name.clone(),
+            file_path: relative_issue_path(&service.file_path, &project.root_path),
+            methods: service.methods.iter()
+                .filter(|method| method.public)
+                .map(|method| ServiceApiMethod {
+                    name: method.name.clone(),
+                    parameters: method.parameters.clone(),
+                    return_type: method.return_type.clone(),
+                    callers: callers.get(&(service.name.as_str(), method.name.as_str()))
+                        .map(|callers| callers.iter().map(|caller| caller.to_string()).collect())
+                        .unwrap_or_default(),
+                })
+                .collect(),
+        })
+        .collect();
+    services.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.file_path.cmp(&b.file_path)));
+
+    ServiceApiReport { services }
+}
+
+impl ServiceApiReport {
+    pub fn total_methods(&self) -> usize {
+        self.services.iter().map(|service| service.methods.len()).sum()
+    }
+
+    // どのコンポーネント・サービスからも呼ばれていない公開メソッド
+    pub fn unused(&self) -> impl Iterator<Item = (&ServiceApi, &ServiceApiMethod)> {
+        self.services.iter()
+            .flat_map(|service| service.methods.iter().filter(|method| method.callers.is_empty()).map(move |method| (service, method)))
+    }
+}
+
+impl ServiceApiMethod {
+    // load(id: string, force?: boolean): Observable<User>
+    pub fn signature(&self) -> String {
+        let parameters: Vec<String> = self.parameters.iter()
+            .map(|parameter| format!("{}{}: {}", parameter.name, if parameter.optional && !parameter.name.starts_with("...") { "?" } else { "" }, parameter.param_type))
+            .collect();
+        match &self.return_type {
+            Some(return_type) => format!("{}({}): {}", self.name, parameters.join(", "), return_type),
+            None => format!("{}({})", self.name, parameters.join(", ")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::ProjectParser;
+
+    #[tokio::test]
+    async fn test_build_service_api() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("src/app");
+        std::fs::create_dir_all(&app).unwrap();
+        std::fs::write(
+            app.join("user.service.ts"),
+            "@Injectable({ providedIn: 'root' })\nexport class UserService {\n  load(id: string, force?: boolean): Observable<User[]> { return this.cache(id); }\n  save({ id, name }: User, ...tags: string[]) {}\n  reset(): void {}\n  private cache(id: string) {}\n  static create() {}\n}\n",
+        ).unwrap();
+        std::fs::write(
+            app.join("session.service.ts"),
+            "@Injectable({ providedIn: 'root' })\nexport class SessionService {\n  private users = inject(UserService);\n  restore() { this.users.load('me'); }\n}\n",
+        ).unwrap();
+        std::fs::write(
+            app.join("user-list.component.ts"),
+            "@Component({ selector: 'app-user-list', template: '' })\nexport class UserListComponent {\n  constructor(private userService: UserService, private session: SessionService) {\n    session.restore();\n  }\n  refresh() { this.userService.load('1', true).subscribe(); this.userService.save(this.user); }\n}\n",
+        ).unwrap();
+
+        let project = ProjectParser::new().parse_project(&dir.path().to_path_buf()).await.unwrap();
+        let report = build_service_api(&project);
+        let user_service = &report.services[1];
+
+        assert_eq!(user_service.name, "UserService");
+        let signatures: Vec<String> = user_service.methods.iter().map(|method| method.signature()).collect();
+        assert_eq!(signatures, vec![
+            "load(id: string, force?: boolean): Observable<User[]>",
+            "save({ id, name }: User, ...tags: string[])",
+            "reset(): void",
+        ]);
+        assert_eq!(user_service.methods[0].callers, vec!["SessionService", "UserListComponent"]);
+        assert_eq!(report.services[0].methods[0].callers, vec!["UserListComponent"]);
+        let unused: Vec<(&str, &str)> = report.unused().map(|(service, method)| (service.name.as_str(), method.name.as_str())).collect();
+        assert_eq!(unused, vec![("UserService", "reset")]);
+    }
+}
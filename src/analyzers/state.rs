@@ -1,13 +1,22 @@
 use super::{Analyzer, AnalysisResult};
-use crate::ast::{NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority};
-use async_trait::async_trait;
+use crate::ast::{NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority, ImportExportGraph, ImportType, MutableExport};
+use crate::config::relative_issue_path;
+use std::sync::Arc;
 use anyhow::Result;
+use std::collections::{HashMap, HashSet};
 
-pub struct StateAnalyzer;
+pub struct StateAnalyzer {
+    min_consumers: usize,
+}
 
 impl StateAnalyzer {
     pub fn new() -> Self {
-        Self
+        Self { min_consumers: 2 }
+    }
+
+    pub fn with_min_consumers(mut self, min_consumers: usize) -> Self {
+        self.min_consumers = min_consumers;
+        self
     }
 
     fn analyze_state_management(&self, project: &NgProject) -> Vec<Issue> {
@@ -26,6 +35,7 @@ impl StateAnalyzer {
                 file_path: project.root_path.display().to_string(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
@@ -43,6 +53,7 @@ impl StateAnalyzer {
                         file_path: service.file_path.clone(),
                         line: None,
                         column: None,
+                        fingerprint: None,
                     });
                 }
             }
@@ -110,6 +121,7 @@ impl StateAnalyzer {
                         file_path: component.file_path.clone(),
                         line: None,
                         column: None,
+                        fingerprint: None,
                     });
                 }
             }
@@ -118,6 +130,180 @@ impl StateAnalyzer {
         issues
     }
 
+    // 同じルートにパラメータだけ変えて遷移するとコンポーネントは再利用され、ngOnInit は再び呼ばれない
+    fn analyze_route_params(&self, project: &NgProject) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        for component in &project.components {
+            let params = &component.route_params;
+            if params.snapshot_reads.is_empty() || params.observed {
+                continue;
+            }
+            let Some(route) = project.parameterized_routes.iter().find(|route| route.component == component.name) else {
+                continue;
+            };
+            issues.push(Issue {
+                severity: Severity::Warning,
+                rule: "stale-route-params".to_string(),
+                message: format!(
+                    "Component '{}' reads {} from route.snapshot once, but is routed at '{}'. Navigating to the same route with different params reuses the component and leaves the view stale; subscribe to route.paramMap or bind the params with withComponentInputBinding().",
+                    component.name,
+                    params.snapshot_reads.iter().map(|name| format!("'{}'", name)).collect::<Vec<_>>().join(", "),
+                    route.path
+                ),
+                file_path: component.file_path.clone(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        issues
+    }
+
+    fn analyze_router_events(&self, project: &NgProject) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        let mut handlers: HashMap<&str, Vec<&str>> = HashMap::new();
+
+        for component in &project.components {
+            let subscriptions = &component.router_event_subscriptions;
+
+            let unfiltered = subscriptions.iter().filter(|s| !s.filters_event_type).count();
+            if unfiltered > 0 {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "unfiltered-router-events".to_string(),
+                    message: format!(
+                        "Component '{}' subscribes to router.events {} time(s) without filtering by event type. Use filter(e => e instanceof NavigationEnd) to avoid running on every router event.",
+                        component.name, unfiltered
+                    ),
+                    file_path: component.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+
+            let leaking = subscriptions.iter().filter(|s| !s.has_teardown).count();
+            if leaking > 0 {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "router-events-without-teardown".to_string(),
+                    message: format!(
+                        "Component '{}' subscribes to router.events {} time(s) without teardown. Router events outlive the component; use takeUntilDestroyed() or unsubscribe in ngOnDestroy.",
+                        component.name, leaking
+                    ),
+                    file_path: component.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+
+            for subscription in subscriptions {
+                if subscription.handler_signature.is_empty() {
+                    continue;
+                }
+                let names = handlers.entry(subscription.handler_signature.as_str()).or_default();
+                if !names.contains(&component.name.as_str()) {
+                    names.push(component.name.as_str());
+                }
+            }
+        }
+
+        let mut duplicated: Vec<_> = handlers.into_values()
+            .filter(|names| names.len() > 1)
+            .collect();
+        duplicated.sort();
+
+        for names in duplicated {
+            let file_path = project.components.iter()
+                .find(|c| c.name == names[0])
+                .map(|c| c.file_path.clone())
+                .unwrap_or_default();
+
+            issues.push(Issue {
+                severity: Severity::Info,
+                rule: "duplicated-router-event-logic".to_string(),
+                message: format!(
+                    "Components {} handle router events with the same logic. Consider moving it into a shared service.",
+                    names.join(", ")
+                ),
+                file_path,
+                line: None,
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        issues
+    }
+
+    fn analyze_store_immutability(&self, project: &NgProject) -> Vec<Issue> {
+        let classes = project.components.iter()
+            .map(|c| (&c.name, &c.file_path, &c.store_mutations))
+            .chain(project.services.iter().map(|s| (&s.name, &s.file_path, &s.store_mutations)));
+
+        classes
+            .filter(|(_, _, mutations)| !mutations.is_empty())
+            .map(|(name, file_path, mutations)| {
+                let targets: Vec<String> = mutations.iter()
+                    .map(|m| format!("{} ({})", m.target, m.operation))
+                    .collect();
+                Issue {
+                    severity: Severity::Error,
+                    rule: "store-state-mutation".to_string(),
+                    message: format!(
+                        "'{}' mutates state obtained from the store: {}. Mutating selected state breaks memoized selectors and OnPush change detection; dispatch an action or copy the value instead.",
+                        name,
+                        targets.join(", ")
+                    ),
+                    file_path: file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                }
+            })
+            .collect()
+    }
+
+    // 書き換えられる export を複数のファイルが import していると、モジュールが状態の置き場所になる
+    fn analyze_shared_mutable_exports(&self, project: &NgProject) -> Vec<Issue> {
+        let Some(graph) = &project.import_graph else {
+            return Vec::new();
+        };
+
+        graph.mutable_exports.iter()
+            .filter_map(|export| {
+                let consumers = mutable_export_consumers(graph, export);
+                if consumers.len() < self.min_consumers.max(1) {
+                    return None;
+                }
+                let consumers: Vec<String> = consumers.iter().map(|path| relative_issue_path(path, &project.root_path)).collect();
+                let suggestion = match export.kind.as_str() {
+                    "let" | "var" => "declare it with const",
+                    _ => "wrap it in Object.freeze",
+                };
+                Some(Issue {
+                    severity: Severity::Warning,
+                    rule: "shared-mutable-export".to_string(),
+                    message: format!(
+                        "Exported mutable {} '{}' is shared by {} files: {}. Module-level state is invisible to change detection and leaks between tests; {} or move the state into a service.",
+                        export.kind,
+                        export.name,
+                        consumers.len(),
+                        consumers.join(", "),
+                        suggestion
+                    ),
+                    file_path: export.file_path.clone(),
+                    line: export.line,
+                    column: None,
+                    fingerprint: None,
+                })
+            })
+            .collect()
+    }
+
     fn analyze_change_detection_impact(&self, project: &NgProject) -> Vec<Issue> {
         let mut issues = Vec::new();
 
@@ -147,15 +333,29 @@ impl StateAnalyzer {
                 file_path: project.root_path.display().to_string(),
                 line: None,
                 column: None,
+                fingerprint: None,
             });
         }
 
         issues
     }
 
-    fn generate_state_recommendations(&self, project: &NgProject) -> Vec<Recommendation> {
+    fn generate_state_recommendations(&self, project: &NgProject, shared_exports: usize) -> Vec<Recommendation> {
         let mut recommendations = Vec::new();
 
+        if shared_exports > 0 {
+            recommendations.push(Recommendation {
+                category: "State Management".to_string(),
+                title: "Encapsulate Shared Module State".to_string(),
+                description: format!(
+                    "{} exported mutable values are imported by several files. Make them const or frozen, or expose the state through an injectable service so that changes are explicit.",
+                    shared_exports
+                ),
+                priority: Priority::Medium,
+                file_path: None,
+            });
+        }
+
         let state_services = self.identify_state_services(project);
         let has_ngrx = self.has_ngrx_pattern(project);
 
@@ -210,22 +410,28 @@ impl StateAnalyzer {
     }
 }
 
-#[async_trait]
 impl Analyzer for StateAnalyzer {
-    async fn analyze(&self, project: &NgProject) -> Result<AnalysisResult> {
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
         let mut all_issues = Vec::new();
 
         all_issues.extend(self.analyze_state_management(project));
         all_issues.extend(self.analyze_reactive_patterns(project));
+        all_issues.extend(self.analyze_router_events(project));
+        all_issues.extend(self.analyze_route_params(project));
+        all_issues.extend(self.analyze_store_immutability(project));
+        let shared_exports = self.analyze_shared_mutable_exports(project);
+        let shared_export_count = shared_exports.len();
+        all_issues.extend(shared_exports);
         all_issues.extend(self.analyze_change_detection_impact(project));
 
-        let recommendations = self.generate_state_recommendations(project);
+        let recommendations = self.generate_state_recommendations(project, shared_export_count);
 
         Ok(AnalysisResult {
             project: project.clone(),
             issues: all_issues,
             metrics: ProjectMetrics::default(),
             recommendations,
+            omitted_issues: vec![],
         })
     }
 
@@ -236,4 +442,136 @@ impl Analyzer for StateAnalyzer {
     fn description(&self) -> &'static str {
         "Analyzes state management patterns, reactive programming, and change detection strategies"
     }
-}
\ No newline at end of file
+}
+
+// export を import しているファイル。バレルの export { x } from や export * from は辿って、その先の利用者を数える
+fn mutable_export_consumers(graph: &ImportExportGraph, export: &MutableExport) -> Vec<String> {
+    let paths: HashMap<&str, String> = graph.files.iter().map(|file| (file.id.as_str(), file.file_path.replace('\\', "/"))).collect();
+    let Some(id) = graph.files.iter().find(|file| file.file_path.replace('\\', "/") == export.file_path).map(|file| file.id.as_str()) else {
+        return Vec::new();
+    };
+
+    let mut consumers = Vec::new();
+    let mut visited: HashSet<&str> = HashSet::from([id]);
+    let mut queue = vec![id];
+    while let Some(target) = queue.pop() {
+        for dependency in graph.dependencies.iter().filter(|dependency| dependency.to_file == target) {
+            let Some(from_path) = paths.get(dependency.from_file.as_str()) else { continue };
+            // import * as state from './state' は名前空間の別名がシンボルとして入っている
+            let uses_namespace = graph.imports.iter().any(|import| {
+                import.file_path == *from_path && matches!(import.import_type, ImportType::Namespace) && dependency.imported_symbols.contains(&import.symbol_name)
+            });
+            if !uses_namespace && !dependency.imported_symbols.iter().any(|symbol| symbol == &export.name || symbol == "*") {
+                continue;
+            }
+            if !visited.insert(dependency.from_file.as_str()) {
+                continue;
+            }
+            let reexports = graph.exports.iter().any(|e| {
+                e.file_path == *from_path && e.source_module.is_some() && (e.symbol_name == export.name || e.symbol_name == "*")
+            });
+            if reexports {
+                queue.push(dependency.from_file.as_str());
+            } else {
+                consumers.push(from_path.clone());
+            }
+        }
+    }
+    consumers.sort();
+    consumers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::{ComponentBuilder, NgProjectBuilder};
+    use crate::ast::ParameterizedRoute;
+    use crate::ast::{Dependency, Export, ExportType, FileInfo, FileType, Import};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_shared_mutable_exports() {
+        let files = ["src/state/session.ts", "src/state/index.ts", "src/a.ts", "src/b.ts", "src/c.ts"];
+        let dependency = |from: usize, to: usize, symbols: &[&str]| Dependency {
+            from_file: files[from].to_string(),
+            to_file: files[to].to_string(),
+            import_type: ImportType::Named,
+            imported_symbols: symbols.iter().map(|symbol| symbol.to_string()).collect(),
+            line_number: None,
+        };
+        let mutable_export = |name: &str, kind: &str| MutableExport {
+            file_path: files[0].to_string(),
+            name: name.to_string(),
+            kind: kind.to_string(),
+            line: Some(1),
+        };
+        let project = NgProject {
+            root_path: PathBuf::from("."),
+            import_graph: Some(ImportExportGraph {
+                files: files.iter().map(|path| FileInfo {
+                    id: path.to_string(),
+                    file_path: path.to_string(),
+                    relative_path: path.to_string(),
+                    file_type: FileType::TypeScript,
+                    exports: Vec::new(),
+                    imports: Vec::new(),
+                }).collect(),
+                // a.ts と b.ts はバレル経由、c.ts は名前空間で直接 import する
+                dependencies: vec![
+                    dependency(1, 0, &["*"]),
+                    dependency(2, 1, &["cache", "counter"]),
+                    dependency(3, 1, &["cache"]),
+                    dependency(4, 0, &["session"]),
+                ],
+                exports: vec![Export {
+                    file_path: files[1].to_string(),
+                    symbol_name: "*".to_string(),
+                    export_type: ExportType::ReExport,
+                    source_module: Some("./session".to_string()),
+                    line_number: None,
+                }],
+                imports: vec![Import {
+                    file_path: files[4].to_string(),
+                    symbol_name: "session".to_string(),
+                    source_module: "./state/session".to_string(),
+                    import_type: ImportType::Namespace,
+                    line_number: None,
+                }],
+                mutable_exports: vec![mutable_export("cache", "object"), mutable_export("counter", "let"), mutable_export("unused", "let")],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let issues = StateAnalyzer::new().analyze_shared_mutable_exports(&project);
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.starts_with("Exported mutable object 'cache' is shared by 3 files: src/a.ts, src/b.ts, src/c.ts."));
+        assert!(issues[1].message.contains("'counter' is shared by 2 files: src/a.ts, src/c.ts"));
+
+        assert_eq!(StateAnalyzer::new().with_min_consumers(3).analyze_shared_mutable_exports(&project).len(), 1);
+    }
+
+    #[test]
+    fn test_stale_route_params() {
+        let reads_snapshot = |name: &str, param: &str, observed: bool| ComponentBuilder::new(name).with(|c| {
+            c.route_params.snapshot_reads = vec![param.to_string()];
+            c.route_params.observed = observed;
+        });
+        let project = NgProjectBuilder::new("/app")
+            .component(reads_snapshot("OrderDetailComponent", "id", false))
+            .component(reads_snapshot("UserEditComponent", "id", true))
+            .component(reads_snapshot("SettingsComponent", "tab", false))
+            .with(|p| {
+                p.parameterized_routes = vec![
+                    ParameterizedRoute { component: "OrderDetailComponent".to_string(), path: "orders/:id".to_string() },
+                    ParameterizedRoute { component: "UserEditComponent".to_string(), path: "users/:id".to_string() },
+                ];
+            })
+            .build();
+
+        let issues = StateAnalyzer::new().analyze_route_params(&project);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("'OrderDetailComponent' reads 'id'"));
+        assert!(issues[0].message.contains("'orders/:id'"));
+    }
+}
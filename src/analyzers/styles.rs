@@ -0,0 +1,337 @@
+use super::{Analyzer, AnalysisResult};
+use crate::ast::{NgComponent, NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority, Stylesheet};
+use std::sync::Arc;
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+
+pub struct StylesAnalyzer {
+    max_lines: u32,
+    max_important: u32,
+}
+
+impl StylesAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            max_lines: 200,
+            max_important: 3,
+        }
+    }
+
+    pub fn with_max_lines(mut self, max_lines: u32) -> Self {
+        self.max_lines = max_lines;
+        self
+    }
+
+    pub fn with_max_important(mut self, max_important: u32) -> Self {
+        self.max_important = max_important;
+        self
+    }
+
+    // 複数のコンポーネントが同じファイルを styleUrls に指定していても一度だけ返す
+    fn stylesheets<'a>(&self, project: &'a NgProject) -> Vec<&'a Stylesheet> {
+        let mut seen = HashSet::new();
+        project.components.iter()
+            .flat_map(|component| &component.stylesheets)
+            .filter(|stylesheet| stylesheet.inline || seen.insert(stylesheet.file_path.as_str()))
+            .collect()
+    }
+
+    fn analyze_stylesheet(&self, stylesheet: &Stylesheet) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        let issue = |severity, rule: &str, message: String, line| Issue {
+            severity,
+            rule: rule.to_string(),
+            message,
+            file_path: stylesheet.file_path.clone(),
+            line,
+            column: None,
+            fingerprint: None,
+        };
+        let source = if stylesheet.inline { "Inline styles" } else { "Stylesheet" };
+
+        if stylesheet.lines > self.max_lines {
+            issues.push(issue(
+                Severity::Warning,
+                "large-component-stylesheet",
+                format!(
+                    "{} has {} lines (max {}). Split it up or move shared rules into a global stylesheet.",
+                    source, stylesheet.lines, self.max_lines
+                ),
+                None,
+            ));
+        }
+
+        for line in &stylesheet.ng_deep_lines {
+            issues.push(issue(
+                Severity::Warning,
+                "ng-deep-usage",
+                "::ng-deep is deprecated and applies the rule outside the component. Use a global stylesheet or CSS custom properties instead.".to_string(),
+                Some(*line),
+            ));
+        }
+
+        if stylesheet.important_count > self.max_important {
+            issues.push(issue(
+                Severity::Warning,
+                "excessive-important",
+                format!(
+                    "{} uses !important {} times (max {}). Raise selector specificity or fix the rule order instead.",
+                    source, stylesheet.important_count, self.max_important
+                ),
+                None,
+            ));
+        }
+
+        if !stylesheet.inline && stylesheet.declarations == 0 {
+            issues.push(issue(
+                Severity::Info,
+                "empty-component-stylesheet",
+                "Style file contains no declarations. Remove it and its styleUrls entry.".to_string(),
+                None,
+            ));
+        }
+
+        issues
+    }
+
+    // セレクタごとに、それを定義しているスタイル (コンポーネント名, ファイル) をまとめる
+    fn duplicate_selectors<'a>(&self, project: &'a NgProject) -> BTreeMap<&'a str, Vec<(&'a str, &'a str)>> {
+        let mut selectors: BTreeMap<&str, Vec<(&str, &str)>> = BTreeMap::new();
+
+        for component in &project.components {
+            for stylesheet in &component.stylesheets {
+                // :host はどのコンポーネントにもあるので比較しない
+                for selector in stylesheet.selectors.iter().filter(|s| !s.starts_with(":host")) {
+                    let owners = selectors.entry(selector.as_str()).or_default();
+                    if !owners.iter().any(|(_, path)| *path == stylesheet.file_path) {
+                        owners.push((component.name.as_str(), stylesheet.file_path.as_str()));
+                    }
+                }
+            }
+        }
+
+        selectors.retain(|_, owners| owners.len() > 1);
+        selectors
+    }
+
+    fn analyze_duplicate_selectors(&self, duplicates: &BTreeMap<&str, Vec<(&str, &str)>>) -> Vec<Issue> {
+        duplicates.iter()
+            .map(|(selector, owners)| Issue {
+                severity: Severity::Info,
+                rule: "duplicate-component-selector".to_string(),
+                message: format!(
+                    "Selector '{}' is styled in {} components: {}",
+                    selector,
+                    owners.len(),
+                    owners.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(", ")
+                ),
+                file_path: owners[0].1.to_string(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            })
+            .collect()
+    }
+
+    // テンプレートで使われていないクラス (クラス名, 最初の行) と、使われないクラスを含むセレクタだけのルールのバイト数
+    fn unused_classes(&self, used: &[String], stylesheet: &Stylesheet) -> (Vec<(String, u32)>, u32) {
+        let is_used = |class: &str| {
+            used.iter().any(|u| u == class || (u.ends_with(['-', '_']) && class.starts_with(u.as_str())))
+        };
+
+        let mut unused: Vec<(String, u32)> = Vec::new();
+        let mut dead_bytes = 0;
+        for rule in &stylesheet.rules {
+            let mut dead_selectors = 0;
+            for selector in &rule.selectors {
+                let mut dead = false;
+                for class in selector_classes(selector).into_iter().filter(|class| !is_used(class)) {
+                    dead = true;
+                    if !unused.iter().any(|(name, _)| name == class) {
+                        unused.push((class.to_string(), rule.line));
+                    }
+                }
+                if dead {
+                    dead_selectors += 1;
+                }
+            }
+            if dead_selectors == rule.selectors.len() {
+                dead_bytes += rule.bytes;
+            }
+        }
+
+        (unused, dead_bytes)
+    }
+
+    fn analyze_unused_classes(&self, component: &NgComponent) -> (Vec<Issue>, u32) {
+        let Some(used) = &component.used_classes else {
+            return (Vec::new(), 0);
+        };
+
+        let mut issues = Vec::new();
+        let mut total_bytes = 0;
+        for stylesheet in &component.stylesheets {
+            let (unused, dead_bytes) = self.unused_classes(used, stylesheet);
+            if unused.is_empty() {
+                continue;
+            }
+            total_bytes += dead_bytes;
+            issues.push(Issue {
+                severity: Severity::Info,
+                rule: "unused-component-class".to_string(),
+                message: format!(
+                    "{} class(es) styled for {} are never used in its template: {} (~{} bytes of dead styles)",
+                    unused.len(),
+                    component.name,
+                    unused.iter().map(|(class, _)| format!(".{}", class)).collect::<Vec<_>>().join(", "),
+                    dead_bytes
+                ),
+                file_path: stylesheet.file_path.clone(),
+                line: unused.iter().map(|(_, line)| *line).min(),
+                column: None,
+                fingerprint: None,
+            });
+        }
+
+        (issues, total_bytes)
+    }
+
+    fn generate_style_recommendations(&self, duplicates: &BTreeMap<&str, Vec<(&str, &str)>>, dead_bytes: u32) -> Vec<Recommendation> {
+        let mut recommendations = Vec::new();
+
+        if !duplicates.is_empty() {
+            recommendations.push(Recommendation {
+                category: "Styles".to_string(),
+                title: "Share Duplicated Component Styles".to_string(),
+                description: format!(
+                    "{} selector(s) are styled in several components. Move the common rules into a shared partial or a global stylesheet.",
+                    duplicates.len()
+                ),
+                priority: Priority::Low,
+                file_path: None,
+            });
+        }
+
+        if dead_bytes > 0 {
+            recommendations.push(Recommendation {
+                category: "Styles".to_string(),
+                title: "Remove Unused Component Styles".to_string(),
+                description: format!(
+                    "About {} bytes of component styles target classes that no template uses. Delete the rules or check whether the classes are added some other way.",
+                    dead_bytes
+                ),
+                priority: Priority::Low,
+                file_path: None,
+            });
+        }
+
+        recommendations
+    }
+}
+
+impl Analyzer for StylesAnalyzer {
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
+        let duplicates = self.duplicate_selectors(project);
+
+        let mut issues: Vec<Issue> = self.stylesheets(project).into_iter()
+            .flat_map(|stylesheet| self.analyze_stylesheet(stylesheet))
+            .collect();
+        issues.extend(self.analyze_duplicate_selectors(&duplicates));
+        let mut dead_bytes = 0;
+        for component in &project.components {
+            let (unused, bytes) = self.analyze_unused_classes(component);
+            issues.extend(unused);
+            dead_bytes += bytes;
+        }
+        let recommendations = self.generate_style_recommendations(&duplicates, dead_bytes);
+
+        Ok(AnalysisResult {
+            project: project.clone(),
+            issues,
+            metrics: ProjectMetrics {
+                total_components: project.components.len() as u32,
+                total_services: project.services.len() as u32,
+                total_modules: project.modules.len() as u32,
+                lines_of_code: project.files.iter().map(|f| f.lines.logical).sum(),
+                test_coverage: project.test_coverage,
+                ..Default::default()
+            },
+            recommendations,
+            omitted_issues: vec![],
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "styles"
+    }
+
+    fn description(&self) -> &'static str {
+        "Checks component stylesheets: size, ::ng-deep, !important, duplicated selectors, unused classes and empty style files"
+    }
+}
+
+// セレクタ自身の要素に付くクラス。:host(...) や :not(...) の中と ::ng-deep より後ろは他のテンプレートの要素なので除く
+fn selector_classes(selector: &str) -> Vec<&str> {
+    // SCSS の補間を含むセレクタはクラス名が分からない
+    if selector.contains("#{") {
+        return Vec::new();
+    }
+    let own = selector.split("::ng-deep").next().unwrap_or_default();
+
+    let mut classes = Vec::new();
+    let mut depth = 0;
+    for (i, c) in own.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            '.' if depth == 0 => {
+                let rest = &own[i + 1..];
+                let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_')).unwrap_or(rest.len());
+                if end > 0 && !classes.contains(&&rest[..end]) {
+                    classes.push(&rest[..end]);
+                }
+            }
+            _ => {}
+        }
+    }
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::styles::parse_stylesheet;
+
+    #[test]
+    fn test_analyze_stylesheet() {
+        let analyzer = StylesAnalyzer::new().with_max_important(1);
+        let stylesheet = parse_stylesheet(
+            ".a { color: red !important; }\n::ng-deep .b { margin: 0 !important; }\n",
+            "a.component.scss",
+            false,
+        );
+        let rules: Vec<String> = analyzer.analyze_stylesheet(&stylesheet).into_iter().map(|issue| issue.rule).collect();
+        assert_eq!(rules, vec!["ng-deep-usage", "excessive-important"]);
+
+        let empty = parse_stylesheet("", "b.component.scss", false);
+        let issues = analyzer.analyze_stylesheet(&empty);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].rule, "empty-component-stylesheet");
+    }
+
+    #[test]
+    fn test_unused_classes() {
+        let analyzer = StylesAnalyzer::new();
+        let stylesheet = parse_stylesheet(
+            ".card { padding: 8px; }\n.card__legacy { color: red; }\n.btn-primary, .old { margin: 0; }\n:host(.compact) .card ::ng-deep .mat-icon { width: 1px; }\n.stale:hover { color: blue; }\n",
+            "card.component.scss",
+            false,
+        );
+        let used = vec!["card".to_string(), "btn-".to_string()];
+
+        let (unused, dead_bytes) = analyzer.unused_classes(&used, &stylesheet);
+        assert_eq!(unused, vec![("card__legacy".to_string(), 2), ("old".to_string(), 3), ("stale".to_string(), 5)]);
+        // .card__legacy と .stale:hover のルールだけが丸ごと不要
+        assert_eq!(dead_bytes, 26 + 26);
+    }
+}
@@ -0,0 +1,197 @@
+use super::{Analyzer, AnalysisResult};
+use crate::ast::{NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority, FeatureTestability, SpecFile};
+use crate::config::relative_issue_path;
+use std::sync::Arc;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub struct TestingAnalyzer;
+
+impl TestingAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    // コンポーネントとサービスを (名前, ファイル) で列挙する
+    fn artifacts<'a>(&self, project: &'a NgProject) -> Vec<(&'a str, &'a str)> {
+        project.components.iter()
+            .map(|c| (c.name.as_str(), c.file_path.as_str()))
+            .chain(project.services.iter().map(|s| (s.name.as_str(), s.file_path.as_str())))
+            .collect()
+    }
+
+    fn analyze_spec_files(&self, project: &NgProject) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        for (name, file_path) in self.artifacts(project) {
+            if find_spec(project, file_path).is_none() {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "missing-spec".to_string(),
+                    // fingerprint はメッセージから作るので、実行場所に依存しないルートからの相対パスにする
+                    message: format!("'{}' has no spec file. Add {}.", name, relative_issue_path(&spec_path(file_path), &project.root_path)),
+                    file_path: file_path.to_string(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        for spec in &project.specs {
+            if spec.tests == 0 && spec.disabled == 0 {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "empty-spec".to_string(),
+                    message: "Spec file contains no it() or test() blocks".to_string(),
+                    file_path: spec.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+
+            if spec.disabled > 0 {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "disabled-tests".to_string(),
+                    message: format!(
+                        "Spec file has {} disabled test(s) (xit, xdescribe or .skip). Re-enable or delete them.",
+                        spec.disabled
+                    ),
+                    file_path: spec.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        issues
+    }
+
+    // 機能フォルダごとに、実行されるテストを持つ spec があるコンポーネント・サービスの割合を出す
+    fn feature_testability(&self, project: &NgProject) -> Vec<FeatureTestability> {
+        let mut folders: BTreeMap<String, FeatureTestability> = BTreeMap::new();
+
+        for (_, file_path) in self.artifacts(project) {
+            let folder = feature_folder(file_path, &project.root_path);
+            let entry = folders.entry(folder.clone()).or_insert_with(|| FeatureTestability {
+                folder,
+                artifacts: 0,
+                with_spec: 0,
+                tests: 0,
+                disabled_tests: 0,
+                score: 0.0,
+            });
+            entry.artifacts += 1;
+            if let Some(spec) = find_spec(project, file_path) {
+                entry.tests += spec.tests;
+                entry.disabled_tests += spec.disabled;
+                if spec.tests > 0 {
+                    entry.with_spec += 1;
+                }
+            }
+        }
+
+        folders.into_values()
+            .map(|mut feature| {
+                feature.score = feature.with_spec as f64 * 100.0 / feature.artifacts as f64;
+                feature
+            })
+            .collect()
+    }
+
+    fn generate_testing_recommendations(&self, features: &[FeatureTestability]) -> Vec<Recommendation> {
+        let weakest: Vec<&str> = features.iter()
+            .filter(|feature| feature.score < 50.0)
+            .map(|feature| feature.folder.as_str())
+            .collect();
+
+        if weakest.is_empty() {
+            return Vec::new();
+        }
+
+        vec![Recommendation {
+            category: "Testing".to_string(),
+            title: "Improve Testability".to_string(),
+            description: format!(
+                "Less than half of the components and services have running tests in: {}",
+                weakest.join(", ")
+            ),
+            priority: Priority::Medium,
+            file_path: None,
+        }]
+    }
+}
+
+fn spec_path(file_path: &str) -> String {
+    match file_path.strip_suffix(".ts") {
+        Some(stem) => format!("{}.spec.ts", stem),
+        None => format!("{}.spec.ts", file_path),
+    }
+}
+
+fn find_spec<'a>(project: &'a NgProject, file_path: &str) -> Option<&'a SpecFile> {
+    let expected = spec_path(file_path);
+    project.specs.iter().find(|spec| spec.file_path == expected)
+}
+
+// src/app/orders/list/list.component.ts -> orders
+pub fn feature_folder(file_path: &str, root_path: &Path) -> String {
+    let relative = relative_issue_path(file_path, root_path);
+    let mut directories: Vec<&str> = relative.split('/').filter(|s| !s.is_empty() && *s != ".").collect();
+    directories.pop();
+
+    directories.into_iter()
+        .find(|segment| !matches!(*segment, "src" | "app"))
+        .unwrap_or(".")
+        .to_string()
+}
+
+impl Analyzer for TestingAnalyzer {
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
+        let issues = self.analyze_spec_files(project);
+        let feature_testability = self.feature_testability(project);
+        let recommendations = self.generate_testing_recommendations(&feature_testability);
+
+        Ok(AnalysisResult {
+            project: project.clone(),
+            issues,
+            metrics: ProjectMetrics {
+                total_components: project.components.len() as u32,
+                total_services: project.services.len() as u32,
+                total_modules: project.modules.len() as u32,
+                lines_of_code: project.files.iter().map(|f| f.lines.logical).sum(),
+                test_coverage: project.test_coverage,
+                feature_testability,
+                ..Default::default()
+            },
+            recommendations,
+            omitted_issues: vec![],
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "testing"
+    }
+
+    fn description(&self) -> &'static str {
+        "Matches components and services to their spec files and scores testability per feature folder"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feature_folder() {
+        let root = Path::new("/repo/src");
+
+        assert_eq!(feature_folder("/repo/src/app/orders/list/list.component.ts", root), "orders");
+        assert_eq!(feature_folder("/repo/src/app/app.component.ts", root), ".");
+        assert_eq!(spec_path("/repo/src/app/app.component.ts"), "/repo/src/app/app.component.spec.ts");
+    }
+}
@@ -0,0 +1,300 @@
+use super::{Analyzer, AnalysisResult};
+use crate::ast::{NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority, FileTypeSafety, TypeEscapes, TypeSafetyMetrics};
+use crate::config::{build_glob_set, relative_issue_path};
+use crate::parsers::entry_points::ENTRY_FILE_GLOBS;
+use crate::parsers::tsconfig::{es_target_year, TsConfig};
+use crate::parsers::typescript::TypeScriptParser;
+use std::sync::Arc;
+use anyhow::Result;
+use std::path::Path;
+
+// any・as any・非 null アサーション・@ts-ignore をファイルごとに数え、tsconfig のコンパイラオプションを確認する
+pub struct TypeSafetyAnalyzer {
+    max_any: u32,
+    max_non_null: u32,
+    max_ts_ignore: u32,
+    min_target: String,
+}
+
+impl TypeSafetyAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            max_any: 3,
+            max_non_null: 5,
+            max_ts_ignore: 0,
+            // Angular CLI の既定の target
+            min_target: "ES2022".to_string(),
+        }
+    }
+
+    pub fn with_thresholds(mut self, max_any: u32, max_non_null: u32, max_ts_ignore: u32) -> Self {
+        self.max_any = max_any;
+        self.max_non_null = max_non_null;
+        self.max_ts_ignore = max_ts_ignore;
+        self
+    }
+
+    pub fn with_min_target(mut self, min_target: String) -> Self {
+        self.min_target = min_target;
+        self
+    }
+
+    // テスト・設定ファイル・型定義と minify 済みのファイルは数えない。解析できないファイルは飛ばす
+    fn count_files(&self, project: &NgProject) -> Result<Vec<FileTypeSafety>> {
+        let tool_files = build_glob_set(&ENTRY_FILE_GLOBS.map(String::from))?;
+        let parser = TypeScriptParser::new();
+        Ok(project.files.iter()
+            .filter(|file| !file.minified && !tool_files.is_match(relative_issue_path(&file.file_path, &project.root_path)))
+            .filter_map(|file| {
+                let (source, module) = project.sources.module(Path::new(&file.file_path), |content| parser.parse_file(content)).ok()?;
+                let content: &str = &source.content;
+                Some(FileTypeSafety {
+                    file_path: file.file_path.clone(),
+                    escapes: parser.extract_type_escapes(&module, content),
+                })
+            })
+            .collect())
+    }
+
+    fn analyze_files(&self, files: &[FileTypeSafety]) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        for file in files {
+            let escapes = &file.escapes;
+            if escapes.any_total() > self.max_any {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "explicit-any".to_string(),
+                    message: format!(
+                        "File uses any {} times ({} annotations, {} casts), above the limit of {}. Replace them with concrete types or unknown.",
+                        escapes.any_total(), escapes.any_annotations, escapes.any_casts, self.max_any
+                    ),
+                    file_path: file.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+
+            if escapes.non_null_assertions > self.max_non_null {
+                issues.push(Issue {
+                    severity: Severity::Info,
+                    rule: "non-null-assertion".to_string(),
+                    message: format!(
+                        "File has {} non-null assertions (!), above the limit of {}. Narrow the type with a check or optional chaining instead.",
+                        escapes.non_null_assertions, self.max_non_null
+                    ),
+                    file_path: file.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+
+            if escapes.ts_ignores > self.max_ts_ignore {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "ts-ignore-comment".to_string(),
+                    message: format!(
+                        "File has {} @ts-ignore comments, above the limit of {}. Fix the type error or use @ts-expect-error with a reason.",
+                        escapes.ts_ignores, self.max_ts_ignore
+                    ),
+                    file_path: file.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        issues
+    }
+
+    // tsconfig の設定はプロジェクト全体の問題として、tsconfig のパスで報告する
+    fn analyze_tsconfig(&self, project: &NgProject, tsconfig: &TsConfig) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        let file_path = tsconfig.path.display().to_string().replace('\\', "/");
+        let mut push = |severity: Severity, rule: &str, message: String| issues.push(Issue {
+            severity,
+            rule: rule.to_string(),
+            message,
+            file_path: file_path.clone(),
+            line: None,
+            column: None,
+            fingerprint: None,
+        });
+
+        if !tsconfig.strict() {
+            push(
+                Severity::Warning,
+                "strict-mode-disabled",
+                "TypeScript strict mode is not enabled. Set \"strict\": true in compilerOptions to catch null and implicit any errors at compile time.".to_string(),
+            );
+        }
+
+        if !project.components.is_empty() && !tsconfig.strict_templates() {
+            push(
+                Severity::Warning,
+                "strict-templates-disabled",
+                "strictTemplates is not enabled in angularCompilerOptions, so bindings in templates are not type-checked against component inputs. Set \"strictTemplates\": true.".to_string(),
+            );
+        }
+
+        // skipLibCheck は自分で書いた .d.ts の型エラーも隠してしまう
+        let declarations: Vec<String> = project.files.iter()
+            .filter(|file| file.file_path.ends_with(".d.ts"))
+            .map(|file| relative_issue_path(&file.file_path, &project.root_path))
+            .collect();
+        if tsconfig.skip_lib_check() && !declarations.is_empty() {
+            push(
+                Severity::Info,
+                "skip-lib-check",
+                format!(
+                    "skipLibCheck is enabled, so the project's own declaration files are not type-checked: {}. Move them to .ts files or disable skipLibCheck.",
+                    declarations.join(", ")
+                ),
+            );
+        }
+
+        let min_year = es_target_year(&self.min_target).unwrap_or(2022);
+        if let Some(target) = tsconfig.target().filter(|target| es_target_year(target).is_some_and(|year| year < min_year)) {
+            push(
+                Severity::Warning,
+                "outdated-compile-target",
+                format!(
+                    "compilerOptions.target is {}, older than {} supported by current Angular versions. Raise the target so the compiler does not downlevel native class fields and async/await.",
+                    target, self.min_target
+                ),
+            );
+        }
+
+        issues
+    }
+
+    fn type_safety_metrics(&self, mut files: Vec<FileTypeSafety>, strict: Option<bool>) -> TypeSafetyMetrics {
+        let mut totals = TypeEscapes::default();
+        for file in &files {
+            totals.add(&file.escapes);
+        }
+        let clean = files.iter().filter(|file| file.escapes.any_total() == 0 && file.escapes.ts_ignores == 0).count();
+        let score = if files.is_empty() { 100.0 } else { clean as f64 * 100.0 / files.len() as f64 };
+
+        files.retain(|file| file.escapes != TypeEscapes::default());
+        files.sort_by(|a, b| {
+            let weight = |file: &FileTypeSafety| file.escapes.any_total() + file.escapes.ts_ignores + file.escapes.non_null_assertions;
+            weight(b).cmp(&weight(a)).then_with(|| a.file_path.cmp(&b.file_path))
+        });
+
+        TypeSafetyMetrics { strict, score, totals, files }
+    }
+
+    fn generate_type_safety_recommendations(&self, metrics: &TypeSafetyMetrics) -> Vec<Recommendation> {
+        let mut recommendations = Vec::new();
+
+        if metrics.totals.any_total() > 0 || metrics.totals.ts_ignores > 0 {
+            recommendations.push(Recommendation {
+                category: "Code Quality".to_string(),
+                title: "Reduce Type Escape Hatches".to_string(),
+                description: format!(
+                    "The project has {} any annotations, {} any casts and {} @ts-ignore comments ({:.0}% of files are free of them). Start with the files that have the most.",
+                    metrics.totals.any_annotations, metrics.totals.any_casts, metrics.totals.ts_ignores, metrics.score
+                ),
+                priority: if metrics.strict == Some(false) { Priority::High } else { Priority::Medium },
+                file_path: None,
+            });
+        }
+
+        recommendations
+    }
+}
+
+impl Analyzer for TypeSafetyAnalyzer {
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
+        let files = self.count_files(project)?;
+        let tsconfig = TsConfig::find(&project.root_path);
+
+        let mut issues = self.analyze_files(&files);
+        if let Some(tsconfig) = &tsconfig {
+            issues.extend(self.analyze_tsconfig(project, tsconfig));
+        }
+        let type_safety = self.type_safety_metrics(files, tsconfig.as_ref().map(TsConfig::strict));
+        let recommendations = self.generate_type_safety_recommendations(&type_safety);
+
+        Ok(AnalysisResult {
+            project: project.clone(),
+            issues,
+            metrics: ProjectMetrics {
+                total_components: project.components.len() as u32,
+                total_services: project.services.len() as u32,
+                total_modules: project.modules.len() as u32,
+                lines_of_code: project.files.iter().map(|f| f.lines.logical).sum(),
+                test_coverage: project.test_coverage,
+                type_safety: Some(type_safety),
+                ..Default::default()
+            },
+            recommendations,
+            omitted_issues: vec![],
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "type-safety"
+    }
+
+    fn description(&self) -> &'static str {
+        "Counts any annotations, as any casts, non-null assertions and @ts-ignore comments per file and checks tsconfig compiler options"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::{ComponentBuilder, NgProjectBuilder};
+    use crate::ast::SourceFile;
+
+    #[test]
+    fn test_type_safety() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::write(root.join("tsconfig.base.json"), "{ \"compilerOptions\": { \"strict\": false, \"skipLibCheck\": true, \"target\": \"es2017\" } }").unwrap();
+        std::fs::write(root.join("src/tsconfig.json"), "{ \"extends\": \"../tsconfig.base.json\" }").unwrap();
+        std::fs::write(
+            root.join("src/user.service.ts"),
+            "export class UserService {\n  cache: any;\n  load(id: any): Array<any> {\n    // @ts-ignore\n    return (this.cache as any)[id]!.items!;\n  }\n}\n",
+        ).unwrap();
+        std::fs::write(root.join("src/app.ts"), "export const title: string = 'app';\n").unwrap();
+        std::fs::write(root.join("src/app.spec.ts"), "const fixture: any = null;\n").unwrap();
+        std::fs::write(root.join("src/typings.d.ts"), "declare const VERSION: string;\n").unwrap();
+
+        let files = ["src/user.service.ts", "src/app.ts", "src/app.spec.ts", "src/typings.d.ts"];
+        let project = NgProjectBuilder::new(root.join("src"))
+            .component(ComponentBuilder::new("AppComponent"))
+            .with(|project| project.files = files.iter()
+                .map(|file| SourceFile { file_path: root.join(file).display().to_string(), lines: Default::default(), minified: false })
+                .collect())
+            .build();
+
+        let analyzer = TypeSafetyAnalyzer::new().with_thresholds(2, 1, 0);
+        let counted = analyzer.count_files(&project).unwrap();
+        assert_eq!(counted.len(), 2);
+        assert_eq!(counted[0].escapes, TypeEscapes { any_annotations: 3, any_casts: 1, non_null_assertions: 2, ts_ignores: 1 });
+
+        let issues = analyzer.analyze_files(&counted);
+        let rules: Vec<&str> = issues.iter().map(|issue| issue.rule.as_str()).collect();
+        assert_eq!(rules, vec!["explicit-any", "non-null-assertion", "ts-ignore-comment"]);
+
+        let tsconfig = TsConfig::find(&project.root_path).unwrap();
+        assert!(tsconfig.path.ends_with("src/tsconfig.json"));
+        let issues = analyzer.analyze_tsconfig(&project, &tsconfig);
+        let rules: Vec<&str> = issues.iter().map(|issue| issue.rule.as_str()).collect();
+        assert_eq!(rules, vec!["strict-mode-disabled", "strict-templates-disabled", "skip-lib-check", "outdated-compile-target"]);
+        assert!(issues[0].file_path.ends_with("src/tsconfig.json"));
+        assert!(issues[2].message.contains("typings.d.ts"));
+
+        let metrics = analyzer.type_safety_metrics(counted, Some(false));
+        assert_eq!(metrics.score, 50.0);
+        assert_eq!(metrics.files.len(), 1);
+        assert_eq!(metrics.totals.any_total(), 4);
+    }
+}
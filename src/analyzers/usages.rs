@@ -0,0 +1,202 @@
+use crate::ast::{NgProject, SymbolReference, UsageReport, UsageTarget};
+use crate::config::relative_issue_path;
+use crate::parsers::typescript::{resolve_relative_path, TypeScriptParser};
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+// 出力で並べる順
+const USAGE_KINDS: [&str; 4] = ["template", "import", "injection", "route"];
+
+// symbol はコンポーネント・サービス・ガードのクラス名か、コンポーネントのセレクタ（app-card、<app-card>、[appTooltip]）
+pub fn find_usages(project: &NgProject, symbol: &str) -> UsageReport {
+    let symbol = symbol.trim().trim_start_matches('<').trim_end_matches('>');
+    let mut targets: Vec<UsageTarget> = project.components.iter()
+        .filter(|component| component.name == symbol || selectors(component.selector.as_deref()).any(|selector| selector == symbol))
+        .map(|component| UsageTarget {
+            kind: "component".to_string(),
+            name: component.name.clone(),
+            selector: component.selector.clone(),
+            file_path: component.file_path.clone(),
+        })
+        .collect();
+    targets.extend(project.services.iter()
+        .filter(|service| service.name == symbol)
+        .map(|service| UsageTarget { kind: "service".to_string(), name: service.name.clone(), selector: None, file_path: service.file_path.clone() }));
+    targets.extend(project.guards.iter()
+        .filter(|guard| guard.name == symbol)
+        .map(|guard| UsageTarget { kind: "guard".to_string(), name: guard.name.clone(), selector: None, file_path: guard.file_path.clone() }));
+
+    // 解析していない種類のクラス（ディレクティブ・パイプなど）も、名前だけで参照を探す
+    let mut names: BTreeSet<&str> = targets.iter().map(|target| target.name.as_str()).collect();
+    if targets.is_empty() && Regex::new(r"^[A-Za-z_$][\w$]*$").unwrap().is_match(symbol) {
+        names.insert(symbol);
+    }
+
+    let mut usages = template_usages(project, &targets);
+    let declaring_files: BTreeSet<&str> = targets.iter().map(|target| target.file_path.as_str()).collect();
+    let imported_from_declaring = imported_from(project, &declaring_files);
+    let parser = TypeScriptParser::new();
+    for file in &project.files {
+        let path = Path::new(&file.file_path);
+        let Ok((source, module)) = project.sources.module(path, |content| parser.parse_file(content)) else { continue };
+        for name in &names {
+            let references = parser.extract_symbol_references(&module, &source.content, path, name);
+            // 同じ名前の別のクラスを相対パスで import しているファイルは除く
+            let mut imports = references.iter().filter_map(|reference| reference.source_module.as_deref()).peekable();
+            let other_class = !declaring_files.is_empty()
+                && imports.peek().is_some()
+                && imports.all(|module| module.starts_with('.'))
+                && !imported_from_declaring.contains(&(file.file_path.as_str(), *name));
+            if !other_class {
+                usages.extend(references);
+            }
+        }
+    }
+
+    for usage in &mut usages {
+        usage.file_path = relative_issue_path(&usage.file_path, &project.root_path);
+    }
+    for target in &mut targets {
+        target.file_path = relative_issue_path(&target.file_path, &project.root_path);
+    }
+    usages.sort_by(|a, b| {
+        let order = |kind: &str| USAGE_KINDS.iter().position(|known| *known == kind);
+        order(&a.kind).cmp(&order(&b.kind))
+            .then_with(|| a.file_path.cmp(&b.file_path))
+            .then_with(|| a.line.cmp(&b.line))
+    });
+
+    UsageReport { symbol: symbol.to_string(), targets, usages }
+}
+
+impl UsageReport {
+    pub fn count(&self, kind: &str) -> usize {
+        self.usages.iter().filter(|usage| usage.kind == kind).count()
+    }
+}
+
+fn selectors(selector: Option<&str>) -> impl Iterator<Item = &str> {
+    selector.into_iter().flat_map(|selector| selector.split(',')).map(str::trim).filter(|selector| !selector.is_empty())
+}
+
+// import グラフで、宣言しているファイルからその名前を import しているファイル
+fn imported_from<'a>(project: &'a NgProject, declaring_files: &BTreeSet<&str>) -> BTreeSet<(&'a str, &'a str)> {
+    let Some(graph) = &project.import_graph else { return BTreeSet::new() };
+    let paths: HashMap<&str, &str> = graph.files.iter().map(|file| (file.id.as_str(), file.file_path.as_str())).collect();
+    graph.dependencies.iter()
+        .filter(|dependency| paths.get(dependency.to_file.as_str()).is_some_and(|path| declaring_files.contains(path)))
+        .filter_map(|dependency| paths.get(dependency.from_file.as_str()).map(|from| (from, &dependency.imported_symbols)))
+        .flat_map(|(from, symbols)| symbols.iter().map(move |symbol| (*from, symbol.as_str())))
+        .collect()
+}
+
+// 対象のコンポーネントのセレクタを、各コンポーネントのテンプレート（インラインと templateUrl）から探す
+fn template_usages(project: &NgProject, targets: &[UsageTarget]) -> Vec<SymbolReference> {
+    let patterns: Vec<Regex> = targets.iter()
+        .flat_map(|target| selectors(target.selector.as_deref()))
+        .filter_map(selector_pattern)
+        .collect();
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+
+    let mut usages = Vec::new();
+    for component in &project.components {
+        let component_path = Path::new(&component.file_path);
+        let (file_path, content, first_line) = match (&component.template, &component.template_url) {
+            (Some(template), _) => {
+                let Ok(source) = project.sources.read(component_path) else { continue };
+                let offset = source.content.find(template.as_str()).unwrap_or_default();
+                (component.file_path.clone(), template.clone(), source.content[..offset].matches('\n').count() as u32)
+            }
+            (None, Some(url)) => {
+                let file_path = resolve_relative_path(component_path, url);
+                let Ok(source) = project.sources.read(Path::new(&file_path)) else { continue };
+                (file_path, source.content.to_string(), 0)
+            }
+            (None, None) => continue,
+        };
+        let lines: Vec<&str> = content.lines().collect();
+        for pattern in &patterns {
+            for found in pattern.captures_iter(&content).filter_map(|captures| captures.get(1)) {
+                let line = content[..found.start()].matches('\n').count();
+                usages.push(SymbolReference {
+                    kind: "template".to_string(),
+                    file_path: file_path.clone(),
+                    line: Some(first_line + line as u32 + 1),
+                    text: lines.get(line).map(|text| text.trim().to_string()).unwrap_or_default(),
+                    owner: Some(component.name.clone()),
+                    source_module: None,
+                });
+            }
+        }
+    }
+    usages
+}
+
+// 要素のセレクタ（app-card）は開始タグに、属性のセレクタ（[appTooltip]、button[appTooltip]）はタグの属性に一致させる
+fn selector_pattern(selector: &str) -> Option<Regex> {
+    let (element, attribute) = match selector.split_once('[') {
+        Some((element, attribute)) => (element, attribute.split(['=', ']']).next()),
+        None => (selector, None),
+    };
+    let element = element.split([':', '.']).next().unwrap_or_default();
+    let pattern = match (element, attribute) {
+        ("", None) => return None,
+        (element, None) => format!(r"<({})(?:[\s/>]|$)", regex::escape(element)),
+        ("", Some(attribute)) => format!(r"<[\w-]+[^>]*?[\s\[(*]({})[\]\s=/>)]", regex::escape(attribute)),
+        (element, Some(attribute)) => format!(r"<{}[^>]*?[\s\[(*]({})[\]\s=/>)]", regex::escape(element), regex::escape(attribute)),
+    };
+    Regex::new(&pattern).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::ProjectParser;
+
+    #[tokio::test]
+    async fn test_find_usages() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("src/app");
+        std::fs::create_dir_all(app.join("card")).unwrap();
+        std::fs::write(app.join("card/card.component.ts"), "import { Component } from '@angular/core';\nimport { CartService } from '../cart.service';\n\n@Component({ selector: 'app-card', templateUrl: './card.component.html' })\nexport class CardComponent {\n  constructor(private cart: CartService) {}\n}\n").unwrap();
+        std::fs::write(app.join("card/card.component.html"), "<div>card</div>\n").unwrap();
+        std::fs::write(app.join("cart.service.ts"), "@Injectable({ providedIn: 'root' })\nexport class CartService {}\n").unwrap();
+        std::fs::write(app.join("list.component.ts"), "import { CardComponent } from './card/card.component';\nimport { CartService } from './cart.service';\n\n@Component({\n  selector: 'app-list',\n  imports: [CardComponent],\n  template: '<app-card></app-card><app-card-title></app-card-title>',\n})\nexport class ListComponent {\n  private cart = inject(CartService);\n}\n").unwrap();
+        std::fs::write(app.join("legacy.component.ts"), "import { CartService } from './legacy/cart.service';\n\nexport class LegacyComponent {\n  private cart = inject(CartService);\n}\n").unwrap();
+        std::fs::write(app.join("app.routes.ts"), "import { CardComponent } from './card/card.component';\n\nexport const routes: Routes = [\n  { path: 'card', component: CardComponent },\n  { path: 'lazy', loadComponent: () => import('./card/card.component').then(m => m.CardComponent) },\n];\n").unwrap();
+
+        let project = ProjectParser::new().parse_project(&dir.path().to_path_buf()).await.unwrap();
+        let summary = |report: &UsageReport| -> Vec<String> {
+            report.usages.iter().map(|usage| format!("{} {}:{}", usage.kind, usage.file_path, usage.line.unwrap_or(0))).collect()
+        };
+
+        let report = find_usages(&project, "<app-card>");
+        assert_eq!(report.targets.len(), 1);
+        assert_eq!(report.targets[0].name, "CardComponent");
+        assert_eq!(summary(&report), vec![
+            "template src/app/list.component.ts:7",
+            "import src/app/app.routes.ts:1",
+            "import src/app/list.component.ts:1",
+            "route src/app/app.routes.ts:4",
+            "route src/app/app.routes.ts:5",
+        ]);
+        assert_eq!(report.usages[0].owner.as_deref(), Some("ListComponent"));
+
+        // 別のファイルの同じ名前のクラスを import しているものは数えない
+        let report = find_usages(&project, "CartService");
+        assert_eq!(summary(&report), vec![
+            "import src/app/card/card.component.ts:2",
+            "import src/app/list.component.ts:2",
+            "injection src/app/card/card.component.ts:6",
+            "injection src/app/list.component.ts:10",
+        ]);
+        assert_eq!(report.usages[2].owner.as_deref(), Some("CardComponent"));
+
+        let pattern = selector_pattern("button[appTooltip]").unwrap();
+        assert!(pattern.is_match("<button class=\"x\" appTooltip=\"hi\">"));
+        assert!(!pattern.is_match("<a appTooltip>"));
+    }
+}
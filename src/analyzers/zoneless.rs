@@ -0,0 +1,231 @@
+use super::{Analyzer, AnalysisResult};
+use super::testing::feature_folder;
+use crate::ast::{NgComponent, NgProject, Issue, Severity, ProjectMetrics, Recommendation, Priority, ChangeDetectionStrategy, ZonelessReadiness};
+use std::sync::Arc;
+use anyhow::Result;
+use std::collections::BTreeMap;
+
+// provideZonelessChangeDetection() に切り替えたときに画面が更新されなくなる書き方を探す
+pub struct ZonelessAnalyzer;
+
+impl ZonelessAnalyzer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn analyze_components(&self, project: &NgProject) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        for component in &project.components {
+            let timer_updates = timer_blockers(component);
+            if !timer_updates.is_empty() {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "zoneless-timer-update".to_string(),
+                    message: format!(
+                        "'{}' updates {} from setTimeout/setInterval callbacks without signals or markForCheck(). Without zone.js the view will not refresh; store the value in a signal instead.",
+                        component.name,
+                        quoted(timer_updates)
+                    ),
+                    file_path: component.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+
+            let async_updates = default_cd_blockers(component);
+            if !async_updates.is_empty() {
+                issues.push(Issue {
+                    severity: Severity::Warning,
+                    rule: "zoneless-default-cd-mutation".to_string(),
+                    message: format!(
+                        "'{}' uses Default change detection and mutates {} in subscribe/then callbacks, relying on zone.js to refresh the view. Use signals or the async pipe.",
+                        component.name,
+                        quoted(async_updates)
+                    ),
+                    file_path: component.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+
+            if is_default_cd(component) && !is_blocked(component) {
+                issues.push(Issue {
+                    severity: Severity::Info,
+                    rule: "zoneless-missing-onpush".to_string(),
+                    message: format!(
+                        "'{}' has no zone-dependent updates but still uses Default change detection. Switch to OnPush to confirm it renders correctly without zone.js.",
+                        component.name
+                    ),
+                    file_path: component.file_path.clone(),
+                    line: None,
+                    column: None,
+                    fingerprint: None,
+                });
+            }
+        }
+
+        issues
+    }
+
+    fn zoneless_readiness(&self, project: &NgProject) -> Vec<ZonelessReadiness> {
+        let mut folders: BTreeMap<String, (ZonelessReadiness, f64)> = BTreeMap::new();
+
+        for component in &project.components {
+            let folder = feature_folder(&component.file_path, &project.root_path);
+            let (entry, points) = folders.entry(folder.clone()).or_insert_with(|| (
+                ZonelessReadiness { folder, components: 0, ready: 0, blocked: 0, score: 0.0 },
+                0.0,
+            ));
+            entry.components += 1;
+            if is_blocked(component) {
+                entry.blocked += 1;
+            } else if is_default_cd(component) {
+                *points += 0.5;
+            } else {
+                entry.ready += 1;
+                *points += 1.0;
+            }
+        }
+
+        folders.into_values()
+            .map(|(mut feature, points)| {
+                feature.score = points * 100.0 / feature.components as f64;
+                feature
+            })
+            .collect()
+    }
+
+    fn generate_zoneless_recommendations(&self, features: &[ZonelessReadiness]) -> Vec<Recommendation> {
+        let blocked: Vec<String> = features.iter()
+            .filter(|feature| feature.blocked > 0)
+            .map(|feature| format!("{} ({:.0}%)", feature.folder, feature.score))
+            .collect();
+
+        if blocked.is_empty() {
+            return Vec::new();
+        }
+
+        vec![Recommendation {
+            category: "Performance".to_string(),
+            title: "Prepare for Zoneless Change Detection".to_string(),
+            description: format!(
+                "Components that rely on zone.js to refresh the view block provideZonelessChangeDetection() in: {}. Move their state into signals and switch them to OnPush.",
+                blocked.join(", ")
+            ),
+            priority: Priority::Medium,
+            file_path: None,
+        }]
+    }
+}
+
+fn is_default_cd(component: &NgComponent) -> bool {
+    matches!(component.change_detection, ChangeDetectionStrategy::Default)
+}
+
+// markForCheck() / detectChanges() を呼んでいれば zone.js がなくても画面は更新される
+fn has_manual_change_detection(component: &NgComponent) -> bool {
+    component.change_detection_calls.iter().any(|call| matches!(call.method.as_str(), "markForCheck" | "detectChanges"))
+}
+
+fn timer_blockers(component: &NgComponent) -> &[String] {
+    if has_manual_change_detection(component) {
+        return &[];
+    }
+    &component.zone.timer_updates
+}
+
+fn default_cd_blockers(component: &NgComponent) -> &[String] {
+    if !is_default_cd(component) || has_manual_change_detection(component) {
+        return &[];
+    }
+    &component.zone.async_updates
+}
+
+fn is_blocked(component: &NgComponent) -> bool {
+    !timer_blockers(component).is_empty() || !default_cd_blockers(component).is_empty()
+}
+
+fn quoted(fields: &[String]) -> String {
+    fields.iter().map(|field| format!("'{}'", field)).collect::<Vec<_>>().join(", ")
+}
+
+impl Analyzer for ZonelessAnalyzer {
+    fn analyze(&self, project: &Arc<NgProject>) -> Result<AnalysisResult> {
+        let issues = self.analyze_components(project);
+        let zoneless_readiness = self.zoneless_readiness(project);
+        let recommendations = self.generate_zoneless_recommendations(&zoneless_readiness);
+
+        Ok(AnalysisResult {
+            project: project.clone(),
+            issues,
+            metrics: ProjectMetrics {
+                total_components: project.components.len() as u32,
+                total_services: project.services.len() as u32,
+                total_modules: project.modules.len() as u32,
+                lines_of_code: project.files.iter().map(|f| f.lines.logical).sum(),
+                test_coverage: project.test_coverage,
+                zoneless_readiness,
+                ..Default::default()
+            },
+            recommendations,
+            omitted_issues: vec![],
+        })
+    }
+
+    fn name(&self) -> &'static str {
+        "zoneless"
+    }
+
+    fn description(&self) -> &'static str {
+        "Reports components that rely on zone.js to refresh the view and scores zoneless readiness per feature folder"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::{ComponentBuilder, NgProjectBuilder};
+    use crate::ast::ChangeDetectionCall;
+
+    #[test]
+    fn test_zoneless_readiness() {
+        let project = NgProjectBuilder::new("/app")
+            .component(ComponentBuilder::new("ClockComponent")
+                .file_path("/app/src/app/dashboard/clock.component.ts")
+                .on_push()
+                .with(|c| c.zone.timer_updates = vec!["now".to_string()]))
+            .component(ComponentBuilder::new("ChartComponent")
+                .file_path("/app/src/app/dashboard/chart.component.ts")
+                .on_push())
+            .component(ComponentBuilder::new("UserListComponent")
+                .file_path("/app/src/app/users/user-list.component.ts")
+                .with(|c| c.zone.async_updates = vec!["users".to_string()]))
+            .component(ComponentBuilder::new("UserCardComponent")
+                .file_path("/app/src/app/users/user-card.component.ts")
+                .with(|c| {
+                    c.zone.async_updates = vec!["user".to_string()];
+                    c.change_detection_calls = vec![ChangeDetectionCall { method: "markForCheck".to_string(), in_loop: false }];
+                }))
+            .build();
+
+        let analyzer = ZonelessAnalyzer::new();
+        let issues = analyzer.analyze_components(&project);
+        let found: Vec<(&str, &str)> = issues.iter()
+            .map(|issue| (issue.rule.as_str(), issue.file_path.rsplit('/').next().unwrap()))
+            .collect();
+        assert_eq!(found, vec![
+            ("zoneless-timer-update", "clock.component.ts"),
+            ("zoneless-default-cd-mutation", "user-list.component.ts"),
+            ("zoneless-missing-onpush", "user-card.component.ts"),
+        ]);
+
+        let readiness = analyzer.zoneless_readiness(&project);
+        let scores: Vec<(&str, u32, u32, f64)> = readiness.iter()
+            .map(|feature| (feature.folder.as_str(), feature.ready, feature.blocked, feature.score))
+            .collect();
+        assert_eq!(scores, vec![("dashboard", 1, 1, 50.0), ("users", 0, 1, 25.0)]);
+    }
+}
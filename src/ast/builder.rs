@@ -0,0 +1,390 @@
+use super::*;
+
+// テストやプラグインから NgProject を組み立てるためのビルダー。
+// 指定しなかったフィールドはパーサーが何も見つけなかったときと同じ値になる
+pub struct NgProjectBuilder {
+    project: NgProject,
+}
+
+#[allow(dead_code)]
+impl NgProjectBuilder {
+    pub fn new(root_path: impl Into<PathBuf>) -> Self {
+        Self {
+            project: NgProject {
+                root_path: root_path.into(),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn component(mut self, component: impl Into<NgComponent>) -> Self {
+        self.project.components.push(component.into());
+        self
+    }
+
+    pub fn service(mut self, service: impl Into<NgService>) -> Self {
+        self.project.services.push(service.into());
+        self
+    }
+
+    pub fn module(mut self, module: NgModule) -> Self {
+        self.project.modules.push(module);
+        self
+    }
+
+    pub fn resource_file(mut self, path: &str) -> Self {
+        self.project.resource_files.push(path.to_string());
+        self
+    }
+
+    // ビルダーにないフィールドを直接書き換える
+    pub fn with(mut self, f: impl FnOnce(&mut NgProject)) -> Self {
+        f(&mut self.project);
+        self
+    }
+
+    pub fn build(self) -> NgProject {
+        self.project
+    }
+}
+
+// 既定値は OnPush でも standalone でもない、中身が空のコンポーネント。
+// file_path と selector は名前から user-list.component.ts / app-user-list のように決める
+pub struct ComponentBuilder {
+    component: NgComponent,
+}
+
+#[allow(dead_code)]
+impl ComponentBuilder {
+    pub fn new(name: &str) -> Self {
+        let kebab = kebab_case(name.strip_suffix("Component").unwrap_or(name));
+        Self {
+            component: NgComponent {
+                name: name.to_string(),
+                file_path: format!("{}.component.ts", kebab),
+                selector: Some(format!("app-{}", kebab)),
+                template_url: None,
+                template: None,
+                style_urls: vec![],
+                inputs: vec![],
+                outputs: vec![],
+                lifecycle_hooks: vec![],
+                dependencies: vec![],
+                change_detection: ChangeDetectionStrategy::Default,
+                complexity_score: 1,
+                router_event_subscriptions: vec![],
+                store_mutations: vec![],
+                location_navigations: vec![],
+                lines: LineCounts::default(),
+                selection_chains: vec![],
+                test_coverage: None,
+                change_detection_calls: vec![],
+                standalone: false,
+                forms: FormUsage::default(),
+                template_usages: vec![],
+                http: HttpUsage::default(),
+                inline_styles: vec![],
+                stylesheets: vec![],
+                providers: vec![],
+                used_classes: None,
+                injection: InjectionUsage::default(),
+                control_flow: None,
+                zone: ZoneUsage::default(),
+                public_methods: vec![],
+                route_params: RouteParamUsage::default(),
+                host: HostUsage::default(),
+                service_calls: vec![],
+                template_complexity: None,
+                heritage: ClassHeritage::default(),
+                element_bindings: vec![],
+            },
+        }
+    }
+
+    pub fn file_path(mut self, file_path: &str) -> Self {
+        self.component.file_path = file_path.to_string();
+        self
+    }
+
+    pub fn selector(mut self, selector: &str) -> Self {
+        self.component.selector = Some(selector.to_string());
+        self
+    }
+
+    pub fn template(mut self, template: &str) -> Self {
+        self.component.template = Some(template.to_string());
+        self
+    }
+
+    pub fn template_url(mut self, template_url: &str) -> Self {
+        self.component.template_url = Some(template_url.to_string());
+        self
+    }
+
+    pub fn style_urls(mut self, style_urls: &[&str]) -> Self {
+        self.component.style_urls = strings(style_urls);
+        self
+    }
+
+    pub fn inputs(mut self, names: &[&str]) -> Self {
+        self.component.inputs = names.iter()
+            .map(|name| NgInput { name: name.to_string(), alias: None, input_type: "any".to_string(), signal: false, optional: false })
+            .collect();
+        self
+    }
+
+    pub fn output(mut self, name: &str, alias: Option<&str>) -> Self {
+        self.component.outputs.push(NgOutput {
+            name: name.to_string(),
+            alias: alias.map(String::from),
+            output_type: "EventEmitter<any>".to_string(),
+        });
+        self
+    }
+
+    pub fn lifecycle_hooks(mut self, hooks: &[&str]) -> Self {
+        self.component.lifecycle_hooks = strings(hooks);
+        self
+    }
+
+    pub fn public_methods(mut self, methods: &[&str]) -> Self {
+        self.component.public_methods = strings(methods);
+        self
+    }
+
+    pub fn dependencies(mut self, dependencies: &[&str]) -> Self {
+        self.component.dependencies = strings(dependencies);
+        self
+    }
+
+    pub fn on_push(mut self) -> Self {
+        self.component.change_detection = ChangeDetectionStrategy::OnPush;
+        self
+    }
+
+    pub fn standalone(mut self) -> Self {
+        self.component.standalone = true;
+        self
+    }
+
+    pub fn complexity(mut self, complexity_score: u32) -> Self {
+        self.component.complexity_score = complexity_score;
+        self
+    }
+
+    pub fn template_complexity(mut self, structural: u32, max_depth: u32, interpolations: u32, pipes: u32) -> Self {
+        self.component.template_complexity = Some(TemplateComplexity::new(structural, max_depth, interpolations, pipes));
+        self
+    }
+
+    pub fn extends(mut self, base_class: &str) -> Self {
+        self.component.heritage.base_class = Some(base_class.to_string());
+        self
+    }
+
+    pub fn lines(mut self, physical: u32, logical: u32) -> Self {
+        self.component.lines = LineCounts { physical, logical };
+        self
+    }
+
+    // テンプレートで使っている要素。kind は embeds / projected / template
+    pub fn uses(mut self, tag: &str, kind: &str, count: u32) -> Self {
+        self.component.template_usages.push(TemplateUsage { tag: tag.to_string(), kind: kind.to_string(), count });
+        self
+    }
+
+    // <tag [input]="..." (output)="..."> をテンプレートで使う
+    pub fn binds(mut self, tag: &str, inputs: &[&str], outputs: &[&str]) -> Self {
+        self.component.element_bindings.push(ElementBindings { tag: tag.to_string(), inputs: strings(inputs), outputs: strings(outputs) });
+        self
+    }
+
+    pub fn with(mut self, f: impl FnOnce(&mut NgComponent)) -> Self {
+        f(&mut self.component);
+        self
+    }
+
+    pub fn build(self) -> NgComponent {
+        self.component
+    }
+}
+
+impl From<ComponentBuilder> for NgComponent {
+    fn from(builder: ComponentBuilder) -> Self {
+        builder.build()
+    }
+}
+
+// 既定値は providedIn: 'root' の @Injectable。file_path は名前から user.service.ts のように決める
+pub struct ServiceBuilder {
+    service: NgService,
+}
+
+#[allow(dead_code)]
+impl ServiceBuilder {
+    pub fn new(name: &str) -> Self {
+        let kebab = kebab_case(name.strip_suffix("Service").unwrap_or(name));
+        Self {
+            service: NgService {
+                name: name.to_string(),
+                file_path: format!("{}.service.ts", kebab),
+                provided_in: Some("root".to_string()),
+                injectable: true,
+                dependencies: vec![],
+                methods: vec![],
+                constructor_statements: 0,
+                store_mutations: vec![],
+                location_navigations: vec![],
+                lines: LineCounts::default(),
+                test_coverage: None,
+                change_detection_calls: vec![],
+                forms: FormUsage::default(),
+                http: HttpUsage::default(),
+                injection: InjectionUsage::default(),
+                service_calls: vec![],
+                heritage: ClassHeritage::default(),
+            },
+        }
+    }
+
+    pub fn file_path(mut self, file_path: &str) -> Self {
+        self.service.file_path = file_path.to_string();
+        self
+    }
+
+    pub fn provided_in(mut self, provided_in: Option<&str>) -> Self {
+        self.service.provided_in = provided_in.map(String::from);
+        self
+    }
+
+    pub fn dependencies(mut self, dependencies: &[&str]) -> Self {
+        self.service.dependencies = strings(dependencies);
+        self
+    }
+
+    pub fn constructor_statements(mut self, constructor_statements: u32) -> Self {
+        self.service.constructor_statements = constructor_statements;
+        self
+    }
+
+    pub fn lines(mut self, physical: u32, logical: u32) -> Self {
+        self.service.lines = LineCounts { physical, logical };
+        self
+    }
+
+    pub fn with(mut self, f: impl FnOnce(&mut NgService)) -> Self {
+        f(&mut self.service);
+        self
+    }
+
+    pub fn build(self) -> NgService {
+        self.service
+    }
+}
+
+impl From<ServiceBuilder> for NgService {
+    fn from(builder: ServiceBuilder) -> Self {
+        builder.build()
+    }
+}
+
+// 既定値はメッセージも位置もない Warning
+pub struct IssueBuilder {
+    issue: Issue,
+}
+
+#[allow(dead_code)]
+impl IssueBuilder {
+    pub fn new(rule: &str, file_path: &str) -> Self {
+        Self {
+            issue: Issue {
+                severity: Severity::Warning,
+                rule: rule.to_string(),
+                message: String::new(),
+                file_path: file_path.to_string(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            },
+        }
+    }
+
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.issue.severity = severity;
+        self
+    }
+
+    pub fn message(mut self, message: &str) -> Self {
+        self.issue.message = message.to_string();
+        self
+    }
+
+    pub fn line(mut self, line: u32) -> Self {
+        self.issue.line = Some(line);
+        self
+    }
+
+    pub fn build(self) -> Issue {
+        self.issue
+    }
+}
+
+impl From<IssueBuilder> for Issue {
+    fn from(builder: IssueBuilder) -> Self {
+        builder.build()
+    }
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+// UserList -> user-list
+fn kebab_case(name: &str) -> String {
+    let mut kebab = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                kebab.push('-');
+            }
+            kebab.push(c.to_ascii_lowercase());
+        } else {
+            kebab.push(c);
+        }
+    }
+    kebab
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_builder_defaults() {
+        let project = NgProjectBuilder::new("/app")
+            .component(ComponentBuilder::new("UserListComponent").on_push().dependencies(&["UserService"]))
+            .service(ServiceBuilder::new("UserService").dependencies(&["HttpClient"]))
+            .build();
+
+        let component = &project.components[0];
+        assert_eq!(component.file_path, "user-list.component.ts");
+        assert_eq!(component.selector.as_deref(), Some("app-user-list"));
+        assert!(matches!(component.change_detection, ChangeDetectionStrategy::OnPush));
+        assert_eq!(component.dependencies, vec!["UserService"]);
+
+        let service = &project.services[0];
+        assert_eq!(service.file_path, "user.service.ts");
+        assert_eq!(service.provided_in.as_deref(), Some("root"));
+        assert_eq!(project.root_path, PathBuf::from("/app"));
+    }
+
+    #[test]
+    fn test_issue_builder_defaults() {
+        let issue = IssueBuilder::new("missing-onpush", "user-list.component.ts").line(3).build();
+
+        assert!(matches!(issue.severity, Severity::Warning));
+        assert_eq!((issue.rule.as_str(), issue.file_path.as_str()), ("missing-onpush", "user-list.component.ts"));
+        assert_eq!((issue.line, issue.column, issue.fingerprint), (Some(3), None, None));
+        assert!(issue.message.is_empty());
+    }
+}
@@ -1,8 +1,11 @@
 use std::path::PathBuf;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+pub mod builder;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct NgProject {
     pub root_path: PathBuf,
     pub components: Vec<NgComponent>,
@@ -10,6 +13,131 @@ pub struct NgProject {
     pub modules: Vec<NgModule>,
     pub pipes: Vec<NgPipe>,
     pub directives: Vec<NgDirective>,
+    pub guards: Vec<NgGuard>,
+    pub files: Vec<SourceFile>,
+    pub test_coverage: Option<f64>,
+    pub specs: Vec<SpecFile>,
+    pub workspace_packages: Option<WorkspacePackages>,
+    pub lazy_routes: Vec<LazyRoute>,
+    // bootstrapApplication で起動するコンポーネント。NgModule の bootstrap は modules に含まれる
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bootstrap_components: Vec<String>,
+    // ルート定義の component で表示されるコンポーネント（loadComponent は lazy_routes に含まれる）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routed_components: Vec<String>,
+    // path に :id などのパラメータを持つルートと、そこに表示されるコンポーネント（loadComponent を含む）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parameterized_routes: Vec<ParameterizedRoute>,
+    // ErrorHandler や HTTP_INTERCEPTORS など、フレームワークのトークンを上書きしている provider
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provider_overrides: Vec<ProviderOverride>,
+    // providers に並ぶすべてのクラスとトークン（provider_overrides を含む）。同じものを何度も登録していないかを見るのに使う
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provider_registrations: Vec<ProviderOverride>,
+    // テンプレートとスタイルのファイル（.html / .css / .scss / .sass / .less）
+    pub resource_files: Vec<String>,
+    // resource_files のうち、angular.json の styles・index・assets か、他のスタイルの @import / @use から参照されているもの
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub referenced_resources: Vec<String>,
+    // ファイル単位の import グラフ。レポートには含めない
+    #[serde(skip)]
+    pub import_graph: Option<ImportExportGraph>,
+    // UTF-8 として読めず、変換してから解析したファイル
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub encoding_warnings: Vec<EncodingWarning>,
+    // extends を持つクラスと、その基底クラス。継承の深さや親のライフサイクルフックを辿るのに使う
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub classes: Vec<NgClass>,
+    // 解析中に読んだソースと構文木。アナライザーがファイルを読み直すときに使う
+    #[serde(skip)]
+    pub sources: Arc<crate::parsers::source_cache::SourceCache>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ParameterizedRoute {
+    pub component: String,
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderOverride {
+    pub token: String,
+    // useClass / useExisting / useFactory / useValue に渡しているもの
+    pub implementation: Option<String>,
+    pub multi: bool,
+    // application（bootstrapApplication や ApplicationConfig）/ module / component / route
+    pub scope: String,
+    // provider を並べているクラスや変数、ルートのパス
+    pub injector: String,
+    pub file_path: String,
+    pub line: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncodingWarning {
+    pub file_path: String,
+    pub encoding: String,
+}
+
+// loadChildren / loadComponent で遅延読み込みされるモジュールやコンポーネント
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LazyRoute {
+    pub module: Option<String>,
+    pub target: String,
+    pub file_path: String,
+    pub line: Option<u32>,
+}
+
+// package.json とロックファイルから読んだワークスペースの依存関係
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspacePackages {
+    pub root_path: PathBuf,
+    pub lockfile: Option<String>,
+    pub manifests: Vec<PackageManifest>,
+    pub installed: Vec<InstalledPackage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageManifest {
+    pub file_path: String,
+    pub name: Option<String>,
+    pub dependencies: Vec<DeclaredDependency>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeclaredDependency {
+    pub name: String,
+    pub range: String,
+    pub resolved: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledPackage {
+    pub name: String,
+    pub version: String,
+    pub location: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpecFile {
+    pub file_path: String,
+    pub tests: u32,
+    pub disabled: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceFile {
+    pub file_path: String,
+    pub lines: LineCounts,
+    // 1 行が極端に長い minify 済みのファイル。行数や複雑度の指標からは外す
+    #[serde(default)]
+    pub minified: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct LineCounts {
+    pub physical: u32,
+    pub logical: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +154,216 @@ pub struct NgComponent {
     pub dependencies: Vec<String>,
     pub change_detection: ChangeDetectionStrategy,
     pub complexity_score: u32,
+    pub router_event_subscriptions: Vec<RouterEventSubscription>,
+    pub store_mutations: Vec<StoreMutation>,
+    pub location_navigations: Vec<LocationNavigation>,
+    pub lines: LineCounts,
+    pub selection_chains: Vec<SelectionChain>,
+    pub test_coverage: Option<f64>,
+    pub change_detection_calls: Vec<ChangeDetectionCall>,
+    pub standalone: bool,
+    pub forms: FormUsage,
+    pub template_usages: Vec<TemplateUsage>,
+    pub http: HttpUsage,
+    pub inline_styles: Vec<String>,
+    pub stylesheets: Vec<Stylesheet>,
+    // providers と viewProviders に並ぶクラスやトークン
+    pub providers: Vec<String>,
+    // テンプレートと .ts の文字列リテラルに現れるクラス名。- / _ で終わるものは btn-{{type}} などの接頭辞。
+    // テンプレートを読んでいない場合と ViewEncapsulation.None の場合は None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used_classes: Option<Vec<String>>,
+    #[serde(default)]
+    pub injection: InjectionUsage,
+    // テンプレートを読んでいない場合は None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub control_flow: Option<TemplateControlFlow>,
+    #[serde(default)]
+    pub zone: ZoneUsage,
+    // private / protected / static とライフサイクルフックを除いたメソッド
+    #[serde(default)]
+    pub public_methods: Vec<String>,
+    #[serde(default)]
+    pub route_params: RouteParamUsage,
+    #[serde(default)]
+    pub host: HostUsage,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub service_calls: Vec<ServiceCall>,
+    // テンプレートを読んでいない場合は None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_complexity: Option<TemplateComplexity>,
+    #[serde(default, skip_serializing_if = "ClassHeritage::is_empty")]
+    pub heritage: ClassHeritage,
+    // テンプレートで使っているカスタム要素に束縛している入力と出力
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub element_bindings: Vec<ElementBindings>,
+}
+
+impl NgComponent {
+    // クラスとテンプレートを合わせた複雑度
+    pub fn total_complexity(&self) -> u32 {
+        self.complexity_score + self.template_complexity.as_ref().map_or(0, |template| template.score)
+    }
+}
+
+// テンプレートの複雑度。score は構造ディレクティブ・制御フローのブロック 1 つにつき 1、
+// 入れ子の 2 段目から 1 段ごとに 2、パイプ 1 つにつき 1、補間 5 つにつき 1 を足したもの
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct TemplateComplexity {
+    pub score: u32,
+    // *ngIf などの構造ディレクティブと @if などのブロック
+    pub structural: u32,
+    // 構造ディレクティブとブロックの入れ子のいちばん深い段数
+    pub max_depth: u32,
+    pub interpolations: u32,
+    // 補間と束縛の式で使っているパイプ（| async | json は 2）
+    pub pipes: u32,
+    // [value]・[(ngModel)] などのプロパティ束縛。score には含めない
+    #[serde(default)]
+    pub bindings: u32,
+}
+
+impl TemplateComplexity {
+    pub fn new(structural: u32, max_depth: u32, interpolations: u32, pipes: u32) -> Self {
+        let score = structural + max_depth.saturating_sub(1) * 2 + pipes + interpolations / 5;
+        Self { score, structural, max_depth, interpolations, pipes, bindings: 0 }
+    }
+
+    pub fn with_bindings(mut self, bindings: u32) -> Self {
+        self.bindings = bindings;
+        self
+    }
+
+    // 変更検知のたびに評価する式の数（補間・プロパティ束縛・構造ディレクティブとブロックの条件）
+    pub fn checked_expressions(&self) -> u32 {
+        self.interpolations + self.bindings + self.structural
+    }
+}
+
+// テンプレートの制御フロー。line はインラインテンプレートなら .ts ファイルの行番号
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct TemplateControlFlow {
+    pub file_path: String,
+    pub blocks: Vec<ControlFlowBlock>,
+    // *ngIf / *ngFor / *ngSwitchCase / *ngSwitchDefault の数
+    pub structural_directives: BTreeMap<String, u32>,
+    // テンプレートの 1 行目の行番号
+    #[serde(default)]
+    pub start_line: u32,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub elements: Vec<TemplateElement>,
+    // 補間・束縛・ブロックの式で使っているパイプ
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pipes: Vec<TemplateExpressionCall>,
+    // 補間・束縛・ブロックの式での関数呼び出し。イベントの束縛と、. の後のメソッドは含まない
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<TemplateExpressionCall>,
+    // *ngFor と @for
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub loops: Vec<TemplateLoop>,
+}
+
+impl TemplateControlFlow {
+    // パイプ名ごとの使用回数
+    pub fn pipe_counts(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for pipe in &self.pipes {
+            *counts.entry(pipe.name.as_str()).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+// *ngFor="let order of orders; trackBy: trackById" と @for (order of orders; track order.id)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TemplateLoop {
+    // ngFor / for
+    pub kind: String,
+    pub item: String,
+    pub iterable: String,
+    // trackBy の関数名か track の式
+    pub track: Option<String>,
+    pub line: u32,
+    // iterable がコンポーネントのプロパティのとき、その配列の要素の型名
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub item_type: Option<String>,
+}
+
+impl TemplateLoop {
+    // orders・orders()・orders$ | async のように、コンポーネントのプロパティをそのまま繰り返すときのプロパティ名
+    pub fn property(&self) -> Option<&str> {
+        let head = self.iterable.split('|').next()?.trim();
+        let head = head.strip_prefix("this.").unwrap_or(head);
+        let head = head.strip_suffix("()").unwrap_or(head).trim_end();
+        let mut chars = head.chars();
+        let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+        valid.then_some(head)
+    }
+}
+
+// テンプレートの式に書いたパイプや関数。in_loop は *ngFor・@for の繰り返しの中
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TemplateExpressionCall {
+    pub name: String,
+    pub line: u32,
+    pub in_loop: bool,
+}
+
+// テンプレート内のカスタム要素と <img> の位置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TemplateElement {
+    pub tag: String,
+    pub line: u32,
+    // @defer の中にある
+    pub deferred: bool,
+    // loading="lazy" か ngSrc（NgOptimizedImage）を指定した <img>
+    pub lazy: bool,
+}
+
+// @if / @for / @switch / @defer ブロック。@else・@empty・@case の中身も元のブロックに含める
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ControlFlowBlock {
+    pub kind: String,
+    // 括弧の中身（@for なら `item of items; track item.id`）
+    pub expression: String,
+    pub line: u32,
+    // ブロック内のカスタム要素。入れ子の @defer の中身は除く
+    pub children: Vec<String>,
+    // @defer の中にあるブロック
+    pub deferred: bool,
+}
+
+// テンプレート内で使われているカスタム要素。kind は embeds / projected / template
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TemplateUsage {
+    pub tag: String,
+    pub kind: String,
+    pub count: u32,
+}
+
+// [(value)] は inputs の value と outputs の valueChange になる
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ElementBindings {
+    pub tag: String,
+    pub inputs: Vec<String>,
+    pub outputs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionChain {
+    pub kind: String,
+    pub discriminator: String,
+    pub branches: u32,
+    pub file_path: String,
+    pub line: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RouterEventSubscription {
+    pub filters_event_type: bool,
+    pub has_teardown: bool,
+    pub handler_signature: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +374,172 @@ pub struct NgService {
     pub injectable: bool,
     pub dependencies: Vec<String>,
     pub methods: Vec<NgMethod>,
+    pub constructor_statements: u32,
+    pub store_mutations: Vec<StoreMutation>,
+    pub location_navigations: Vec<LocationNavigation>,
+    pub lines: LineCounts,
+    pub test_coverage: Option<f64>,
+    pub change_detection_calls: Vec<ChangeDetectionCall>,
+    pub forms: FormUsage,
+    pub http: HttpUsage,
+    #[serde(default)]
+    pub injection: InjectionUsage,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub service_calls: Vec<ServiceCall>,
+    #[serde(default, skip_serializing_if = "ClassHeritage::is_empty")]
+    pub heritage: ClassHeritage,
+}
+
+// extends と implements。名前は型引数を除いたもの
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct ClassHeritage {
+    // extends WithDestroy(BaseComponent) のようなミックスインでは、一番内側の引数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_class: Option<String>,
+    // 外側から順に並べたミックスイン関数
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mixins: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub implements: Vec<String>,
+    // super.ngOnInit() のように、同じ名前の親のメソッドを呼んでいるメソッド
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub super_calls: Vec<String>,
+}
+
+impl ClassHeritage {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn extends(&self) -> bool {
+        self.base_class.is_some() || !self.mixins.is_empty()
+    }
+}
+
+// プロジェクト内で宣言されたクラス。decorator は Component・Directive・Injectable など
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NgClass {
+    pub name: String,
+    pub file_path: String,
+    pub line: u32,
+    pub is_abstract: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decorator: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub lifecycle_hooks: Vec<String>,
+    #[serde(default, skip_serializing_if = "ClassHeritage::is_empty")]
+    pub heritage: ClassHeritage,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreMutation {
+    pub target: String,
+    pub operation: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationNavigation {
+    pub method: String,
+    pub target: Option<String>,
+}
+
+// styleUrls のファイルまたはインラインの styles。インラインの場合 file_path はコンポーネントの .ts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Stylesheet {
+    pub file_path: String,
+    pub inline: bool,
+    pub lines: u32,
+    pub declarations: u32,
+    pub ng_deep_lines: Vec<u32>,
+    pub important_count: u32,
+    // ネストを展開し空白を正規化したセレクタ
+    pub selectors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<StyleRule>,
+}
+
+// 宣言を持つルール。bytes はセレクタと宣言から見積もった出力の大きさ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StyleRule {
+    pub selectors: Vec<String>,
+    pub line: u32,
+    pub bytes: u32,
+}
+
+// コンストラクタ引数の型と inject() の引数。どちらの書き方で注入しているかを見るために分けて持つ
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InjectionUsage {
+    pub constructor: Vec<String>,
+    pub inject: Vec<String>,
+}
+
+// zone.js の変更検知に頼った更新。非同期のコールバックの中で代入・push などをしている this のフィールド名
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ZoneUsage {
+    // setTimeout / setInterval / requestAnimationFrame と interval() / timer() の購読
+    pub timer_updates: Vec<String>,
+    // subscribe / then / addEventListener など
+    pub async_updates: Vec<String>,
+}
+
+// @HostListener / @HostBinding と、nativeElement や window / document への直接のアクセス
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HostUsage {
+    pub listeners: Vec<EventListenerUsage>,
+    // @HostBinding('class.active') の class.active。引数がなければプロパティ名
+    pub bindings: Vec<String>,
+    // nativeElement を通した DOM の変更。style.color / innerHTML / appendChild など
+    pub native_element_writes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventListenerUsage {
+    // host / window / document / body
+    pub target: String,
+    pub event: String,
+    // HostListener / addEventListener / fromEvent
+    pub source: String,
+    // Angular が外す HostListener と fromEvent、ngOnDestroy の removeEventListener か AbortSignal で外すもの
+    pub removed: bool,
+    // debounce / throttle / requestAnimationFrame などで間引いている
+    pub throttled: bool,
+}
+
+// ActivatedRoute のパラメータの読み方
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RouteParamUsage {
+    // コンストラクタ・ngOnInit・フィールドの初期化で snapshot から一度だけ読むパラメータ名。名前がわからなければ *
+    pub snapshot_reads: Vec<String>,
+    // paramMap / params を Observable として使っている
+    pub observed: bool,
+}
+
+// HttpClient の注入と、this.http.get(...) などのリクエスト
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HttpUsage {
+    pub injected: bool,
+    pub requests: Vec<HttpRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpRequest {
+    pub method: String,
+    // 引数の式を ${environment.apiUrl}/users のように文字列化したもの
+    pub url: Option<String>,
+    pub error_handled: bool,
+}
+
+// new FormGroup(...) などの生成箇所を型付き / Untyped で数える
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct FormUsage {
+    pub typed: u32,
+    pub untyped: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangeDetectionCall {
+    pub method: String,
+    pub in_loop: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -47,6 +551,13 @@ pub struct NgModule {
     pub declarations: Vec<String>,
     pub providers: Vec<String>,
     pub bootstrap: Vec<String>,
+    pub for_root_imports: Vec<ForRootImport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForRootImport {
+    pub module: String,
+    pub line: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +565,14 @@ pub struct NgPipe {
     pub name: String,
     pub file_path: String,
     pub pure: bool,
+    // @Pipe の name。テンプレートで | の後に書く名前
+    #[serde(default)]
+    pub pipe_name: String,
+    #[serde(default)]
+    pub line: Option<u32>,
+    // transform の中で配列全体をたどる呼び出し（sort・filter など）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub array_operations: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -65,11 +584,26 @@ pub struct NgDirective {
     pub outputs: Vec<NgOutput>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NgGuard {
+    pub name: String,
+    pub file_path: String,
+    pub kind: String,
+    pub functional: bool,
+    #[serde(skip)]
+    pub body_tokens: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NgInput {
     pub name: String,
     pub alias: Option<String>,
     pub input_type: String,
+    #[serde(default)]
+    pub signal: bool,
+    // name?: string のように ? が付いている
+    #[serde(default)]
+    pub optional: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -85,6 +619,9 @@ pub struct NgMethod {
     pub parameters: Vec<Parameter>,
     pub return_type: Option<String>,
     pub complexity_score: u32,
+    // private / protected / static でないもの
+    #[serde(default)]
+    pub public: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -94,18 +631,100 @@ pub struct Parameter {
     pub optional: bool,
 }
 
+// 注入したサービスのメソッドの呼び出し。this.userService.load() なら UserService と load
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceCall {
+    pub service: String,
+    pub method: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ChangeDetectionStrategy {
     Default,
     OnPush,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct AnalysisResult {
-    pub project: NgProject,
+    // 同じ実行のアナライザーの結果はすべて同じプロジェクトを共有する。
+    // JSON では結果ごとに出さず、先頭の project に ProjectInfo を 1 回だけ出す（--include-project で結果ごとに全体を出す）
+    #[serde(skip)]
+    pub project: Arc<NgProject>,
     pub issues: Vec<Issue>,
     pub metrics: ProjectMetrics,
     pub recommendations: Vec<Recommendation>,
+    // --max-issues-per-rule で省いた issue の件数
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub omitted_issues: Vec<OmittedIssues>,
+}
+
+// JSON レポートの先頭に出す、解析したプロジェクトの概要
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProjectInfo {
+    // ルートのディレクトリ名
+    pub name: String,
+    pub root_path: PathBuf,
+    pub files: usize,
+    pub components: usize,
+    pub services: usize,
+    pub modules: usize,
+    pub pipes: usize,
+    pub directives: usize,
+}
+
+impl ProjectInfo {
+    pub fn new(project: &NgProject) -> Self {
+        Self {
+            name: project.root_path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+            root_path: project.root_path.clone(),
+            files: project.files.len(),
+            components: project.components.len(),
+            services: project.services.len(),
+            modules: project.modules.len(),
+            pipes: project.pipes.len(),
+            directives: project.directives.len(),
+        }
+    }
+}
+
+// .ng-analyzer.json の budgets の評価結果。actual は最も悪いモジュール・コンポーネントの値（平均と割合はプロジェクト全体）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BudgetResult {
+    // max_inputs など、設定ファイルのキー
+    pub budget: String,
+    pub limit: f64,
+    pub actual: f64,
+    pub passed: bool,
+    // actual の値になったモジュール・コンポーネント
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub worst: Option<String>,
+}
+
+// issue の密度・複雑度・OnPush・循環・カバレッジから出すプロジェクト全体の健全性
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HealthScore {
+    // 0〜100。評価できたカテゴリの重み付き平均
+    pub score: f64,
+    // A〜F
+    pub grade: String,
+    pub categories: Vec<HealthCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HealthCategory {
+    // issue_density など、設定ファイルの health のキー
+    pub category: String,
+    pub score: f64,
+    pub weight: f64,
+    // スコアの元になった値
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OmittedIssues {
+    pub rule: String,
+    pub severity: Severity,
+    pub count: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,9 +735,11 @@ pub struct Issue {
     pub file_path: String,
     pub line: Option<u32>,
     pub column: Option<u32>,
+    #[serde(default)]
+    pub fingerprint: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Severity {
     Error,
     Warning,
@@ -133,6 +754,138 @@ pub struct ProjectMetrics {
     pub average_complexity: f64,
     pub lines_of_code: u32,
     pub test_coverage: Option<f64>,
+    #[serde(default)]
+    pub feature_testability: Vec<FeatureTestability>,
+    #[serde(default)]
+    pub adoption: Option<AdoptionMetrics>,
+    #[serde(default)]
+    pub render_depth: Option<RenderDepth>,
+    // 複数のファイルに同じ内容で宣言されたシンボルの、余分なコピーの行数
+    #[serde(default)]
+    pub duplicated_lines: Option<u32>,
+    // コピペされたブロックの 2 つ目以降の出現が占める行の割合（%）
+    #[serde(default)]
+    pub duplication_percentage: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub zoneless_readiness: Vec<ZonelessReadiness>,
+    #[serde(default)]
+    pub api_surface: Option<ApiSurface>,
+    #[serde(default)]
+    pub type_safety: Option<TypeSafetyMetrics>,
+    // 変更検知のコストが大きいと見積もった Default のコンポーネント。コストの大きい順
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cd_hotspots: Vec<CdHotspot>,
+}
+
+// any や ! など、型検査を素通りさせる書き方の数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TypeEscapes {
+    // : any や Array<any> などの型注釈
+    pub any_annotations: u32,
+    // as any / <any>
+    pub any_casts: u32,
+    pub non_null_assertions: u32,
+    pub ts_ignores: u32,
+}
+
+impl TypeEscapes {
+    // ! を除いた、any で型検査を外している箇所
+    pub fn any_total(&self) -> u32 {
+        self.any_annotations + self.any_casts
+    }
+
+    pub fn add(&mut self, other: &TypeEscapes) {
+        self.any_annotations += other.any_annotations;
+        self.any_casts += other.any_casts;
+        self.non_null_assertions += other.non_null_assertions;
+        self.ts_ignores += other.ts_ignores;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileTypeSafety {
+    pub file_path: String,
+    #[serde(flatten)]
+    pub escapes: TypeEscapes,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TypeSafetyMetrics {
+    // tsconfig の compilerOptions.strict。tsconfig が見つからない場合は None
+    pub strict: Option<bool>,
+    // any・as any・@ts-ignore を 1 つも含まないファイルの割合 (%)
+    pub score: f64,
+    pub totals: TypeEscapes,
+    // どれか 1 つでも含むファイル。多い順
+    pub files: Vec<FileTypeSafety>,
+}
+
+// bootstrap されるコンポーネントからたどった描画ツリーの深さ。起動するコンポーネントが見つからなければ None
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RenderDepth {
+    pub max_depth: u32,
+    // 葉のコンポーネントまでの経路を深い順に。ルートから並ぶ
+    pub deepest_chains: Vec<Vec<String>>,
+}
+
+// 移行状況の把握用。対象がなければ None
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AdoptionMetrics {
+    pub standalone_percentage: Option<f64>,
+    pub signal_inputs_percentage: Option<f64>,
+    pub onpush_percentage: Option<f64>,
+    pub typed_forms_percentage: Option<f64>,
+}
+
+// コンポーネントの API（入力 + 出力 + public メソッドの数）の分布。コンポーネントがなければ None
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ApiSurface {
+    pub average: f64,
+    pub max: u32,
+    pub distribution: Vec<ApiSurfaceBucket>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiSurfaceBucket {
+    // 0-5 / 6-10 / 11-20 / 21+
+    pub range: String,
+    pub components: u32,
+}
+
+// cost は bindings と Default の子孫の式の合計に (子孫の段数 + 1) を掛けたもの。
+// 子孫の段数が深いほど、1 回の変更検知でたどるビューが増える
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CdHotspot {
+    pub component: String,
+    pub file_path: String,
+    // 自身のテンプレートで変更検知のたびに評価する式
+    pub bindings: u32,
+    // OnPush の子で止めた、子孫を含む式の合計
+    pub subtree_bindings: u32,
+    // その下に続く Default の子孫の段数
+    pub depth: u32,
+    pub cost: u32,
+}
+
+// 機能フォルダごとの zoneless への移行準備度。score は OnPush で妨げのないコンポーネントを 1、
+// Default のままのものを 0.5、妨げのあるものを 0 とした平均（%）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ZonelessReadiness {
+    pub folder: String,
+    pub components: u32,
+    pub ready: u32,
+    pub blocked: u32,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FeatureTestability {
+    pub folder: String,
+    pub artifacts: u32,
+    pub with_spec: u32,
+    pub tests: u32,
+    pub disabled_tests: u32,
+    pub score: f64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -151,12 +904,333 @@ pub enum Priority {
     Low,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ImportExportGraph {
     pub files: Vec<FileInfo>,
     pub dependencies: Vec<Dependency>,
     pub exports: Vec<Export>,
     pub imports: Vec<Import>,
+    // 孤立ファイルの判定で起点にするファイル ID（ワークスペースのエントリーポイント、遅延ロード先、テストなど）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entry_points: Vec<String>,
+    // export let や凍結されていないオブジェクトの export const など、書き換えられるモジュールの状態
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mutable_exports: Vec<MutableExport>,
+    // export したクラス・インターフェース・型・enum・関数と、その本体のハッシュ
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub declared_symbols: Vec<DeclaredSymbol>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MutableExport {
+    pub file_path: String,
+    pub name: String,
+    // let / var / object / array / Map / Set
+    pub kind: String,
+    pub line: Option<u32>,
+}
+
+// environment.ts などのオブジェクトリテラルにある文字列の値。key は firebase.apiKey のようにネストをたどった名前
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigValue {
+    pub key: String,
+    pub value: String,
+    pub line: Option<u32>,
+}
+
+// environments/environment* から import したシンボルと、そこから読んでいるフィールド
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnvironmentAccess {
+    pub source_module: String,
+    pub fields: Vec<String>,
+    pub line: Option<u32>,
+}
+
+// body_hash は空白とコメントを除いた宣言全体の FNV-1a。lines は宣言の論理行数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeclaredSymbol {
+    pub file_path: String,
+    pub name: String,
+    // class / interface / type / enum / function
+    pub kind: String,
+    pub line: Option<u32>,
+    pub lines: u32,
+    pub body_hash: String,
+}
+
+// テンプレートのセレクタから組み立てたコンポーネントの親子関係
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ComponentTree {
+    pub nodes: Vec<ComponentTreeNode>,
+    pub edges: Vec<ComponentTreeEdge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentTreeNode {
+    pub name: String,
+    pub selector: Option<String>,
+    pub file_path: String,
+    // どのテンプレートからも使われないコンポーネント（ルートやルーティング先）からの最長の深さ
+    pub depth: u32,
+    // 最長の経路での親
+    pub deepest_parent: Option<String>,
+    // 全テンプレートでの出現回数
+    pub usages: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentTreeEdge {
+    pub parent: String,
+    pub child: String,
+    pub kind: String,
+    pub count: u32,
+}
+
+// NgModule の imports と loadChildren による遅延読み込みの関係
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ModuleGraph {
+    pub nodes: Vec<ModuleGraphNode>,
+    pub edges: Vec<ModuleGraphEdge>,
+    // imports だけで循環しているモジュール。先頭のモジュールに戻る順に並ぶ
+    pub cycles: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleGraphNode {
+    pub name: String,
+    pub file_path: String,
+    pub root: bool,
+    pub lazy: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleGraphEdge {
+    pub from: String,
+    pub to: String,
+    // imports または lazy
+    pub kind: String,
+    pub line: Option<u32>,
+}
+
+// コンストラクタ引数と inject() から組み立てた、クラスがどのサービスを注入しているかの関係
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct InjectionGraph {
+    pub nodes: Vec<InjectionNode>,
+    pub edges: Vec<InjectionEdge>,
+    pub cycles: Vec<Vec<String>>,
+    // フレームワークのトークンを上書きしている provider。トークンごとにまとめて並べる
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provider_overrides: Vec<ProviderOverride>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionNode {
+    pub name: String,
+    // component または service
+    pub kind: String,
+    pub file_path: String,
+    // providedIn の値と、providers に並べているモジュールやコンポーネント
+    pub injectors: Vec<String>,
+    // providedIn: 'root' なのに 1 つの機能フォルダからしか注入されていない場合のフォルダ名
+    pub single_feature: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InjectionEdge {
+    pub from: String,
+    pub to: String,
+}
+
+// 静的な import を辿って見積もった、初期バンドルと遅延読み込みのルートごとのファイル
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BundleEstimate {
+    pub chunks: Vec<BundleChunk>,
+    // 初期バンドルに含まれず、複数のルートから読み込まれるファイル
+    pub shared: Vec<SharedBundleFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleChunk {
+    // 初期バンドルは "eager"、ルートは遅延読み込みされるモジュールやコンポーネントの名前
+    pub name: String,
+    pub entry: String,
+    // loadChildren / loadComponent を書いているファイルと行
+    pub route_file: Option<String>,
+    pub line: Option<u32>,
+    // 初期バンドルに含まれるファイルは除く
+    pub files: Vec<String>,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedBundleFile {
+    pub file_path: String,
+    pub bytes: u64,
+    pub chunks: Vec<String>,
+}
+
+// impact コマンドの結果。変更するファイルを import しているファイルを辿った範囲と、テスト・遅延読み込みのチャンクへの影響
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ImpactReport {
+    pub target: String,
+    // 変更するファイル
+    pub targets: Vec<String>,
+    pub affected: Vec<AffectedFile>,
+    // 影響を受ける .spec.ts（import しているものと、同じ名前のもの）
+    pub tests: Vec<String>,
+    // 影響を受けるファイルを含む Nx のプロジェクト（project.json のあるディレクトリ）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub projects: Vec<String>,
+    pub chunks: Vec<TouchedChunk>,
+    // targets と affected のうちテスト以外のファイルの合計バイト数
+    pub rebuild_bytes: u64,
+    // low / medium / high
+    pub risk: String,
+    // targets と affected の間の import。mermaid で描く
+    #[serde(skip)]
+    pub edges: Vec<(String, String)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectedFile {
+    pub file_path: String,
+    // 何段の import を経て影響するか
+    pub depth: u32,
+    // 1 段手前のファイル（このファイルが import している）
+    pub via: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TouchedChunk {
+    pub name: String,
+    pub route_file: Option<String>,
+    pub line: Option<u32>,
+    // チャンクに含まれる変更・影響のあるファイルの数
+    pub files: usize,
+}
+
+// library コマンドの結果。ng-packagr のライブラリ（ng-package.json）ごとの公開 API と、それを迂回している import
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LibraryApiReport {
+    pub libraries: Vec<LibraryApi>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryApi {
+    // package.json の name。セカンダリエントリーポイントは @org/ui/testing のように親の名前に続ける
+    pub name: String,
+    pub root: String,
+    pub entry_file: String,
+    pub public_api: Vec<PublicApiSymbol>,
+    // ライブラリの外から、エントリーポイント以外のファイルを import しているもの
+    pub deep_imports: Vec<DeepImport>,
+    // 公開 API に含まれないのに、ライブラリの外から直接 import されているシンボル
+    pub private_imports: Vec<PrivateImport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicApiSymbol {
+    pub name: String,
+    // 宣言しているファイル
+    pub file_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeepImport {
+    pub file_path: String,
+    pub source_module: String,
+    // 解決できなかった場合は None
+    pub target_file: Option<String>,
+    pub symbols: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateImport {
+    pub symbol: String,
+    pub file_path: String,
+    pub importers: Vec<String>,
+}
+
+// usages コマンドの結果。シンボル（クラス名・セレクタ）が指すクラスと、それを参照している箇所
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UsageReport {
+    pub symbol: String,
+    pub targets: Vec<UsageTarget>,
+    pub usages: Vec<SymbolReference>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UsageTarget {
+    // component / service / guard
+    pub kind: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+    pub file_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolReference {
+    // template / import / injection / route
+    pub kind: String,
+    pub file_path: String,
+    pub line: Option<u32>,
+    // 参照している行（前後の空白は除く）
+    pub text: String,
+    // テンプレートを持つコンポーネント、注入しているクラス
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    // import の from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_module: Option<String>,
+}
+
+// サービスごとの公開メソッドと、それを呼んでいるコンポーネント・サービス
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ServiceApiReport {
+    pub services: Vec<ServiceApi>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceApi {
+    pub name: String,
+    pub file_path: String,
+    pub methods: Vec<ServiceApiMethod>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServiceApiMethod {
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<String>,
+    pub callers: Vec<String>,
+}
+
+// import 文から集計した npm パッケージごとの利用状況
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PackageUsageReport {
+    pub total_files: usize,
+    pub packages: Vec<PackageUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageUsage {
+    pub name: String,
+    // import しているファイル (相対パス)
+    pub files: Vec<String>,
+    // rxjs/operators や lodash/debounce などのサブパス
+    pub entry_points: Vec<String>,
+    pub symbols: Vec<SymbolUsage>,
+    // @angular/* や tslib などフレームワーク本体
+    pub framework: bool,
+    // package.json に宣言されているか。package.json が見つからない場合は None
+    pub declared: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolUsage {
+    pub name: String,
+    pub files: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -183,6 +1257,8 @@ pub struct Export {
     pub file_path: String,
     pub symbol_name: String,
     pub export_type: ExportType,
+    // export { X } from './x' / export * from './x' の参照先
+    pub source_module: Option<String>,
     pub line_number: Option<u32>,
 }
 
@@ -201,6 +1277,8 @@ pub enum FileType {
     JavaScript,
     Declaration,
     Module,
+    // graph コマンドで表示する node_modules のパッケージ
+    External,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -219,13 +1297,31 @@ pub enum ExportType {
     ReExport,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DependencyAnalysis {
     pub circular_dependencies: Vec<CircularDependency>,
     pub orphaned_files: Vec<String>,
     pub dependency_depth: HashMap<String, u32>,
     pub most_imported_files: Vec<(String, u32)>,
     pub most_dependent_files: Vec<(String, u32)>,
+    pub longest_chains: Vec<ImportChain>,
+    #[serde(default)]
+    pub unused_exports: Vec<UnusedExport>,
+}
+
+// どこからも import されていない export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusedExport {
+    pub file_path: String,
+    pub symbol: String,
+}
+
+// 循環をひとまとめにした import の最長経路。循環しているファイル群は {a.ts, b.ts} と表記する
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportChain {
+    pub depth: u32,
+    pub file_path: String,
+    pub path: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -240,50 +1336,3 @@ pub enum CycleSeverity {
     Warning,
     Info,
 }
-
-impl Default for NgProject {
-    fn default() -> Self {
-        Self {
-            root_path: PathBuf::new(),
-            components: Vec::new(),
-            services: Vec::new(),
-            modules: Vec::new(),
-            pipes: Vec::new(),
-            directives: Vec::new(),
-        }
-    }
-}
-
-impl Default for AnalysisResult {
-    fn default() -> Self {
-        Self {
-            project: NgProject::default(),
-            issues: Vec::new(),
-            metrics: ProjectMetrics::default(),
-            recommendations: Vec::new(),
-        }
-    }
-}
-
-impl Default for ImportExportGraph {
-    fn default() -> Self {
-        Self {
-            files: Vec::new(),
-            dependencies: Vec::new(),
-            exports: Vec::new(),
-            imports: Vec::new(),
-        }
-    }
-}
-
-impl Default for DependencyAnalysis {
-    fn default() -> Self {
-        Self {
-            circular_dependencies: Vec::new(),
-            orphaned_files: Vec::new(),
-            dependency_depth: HashMap::new(),
-            most_imported_files: Vec::new(),
-            most_dependent_files: Vec::new(),
-        }
-    }
-}
\ No newline at end of file
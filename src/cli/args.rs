@@ -1,17 +1,21 @@
 use std::path::PathBuf;
+use crate::analyzers::ANALYZER_NAMES;
 use crate::ast::Severity;
+use crate::output::schema::SchemaVersion;
+use crate::output::grouping::IssueGrouping;
+use crate::output::i18n::Lang;
+use crate::output::log::LogFormat;
+use crate::output::top::SummaryMode;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub enum OutputFormat {
+    #[default]
     Json,
     Html,
     Table,
-}
-
-impl Default for OutputFormat {
-    fn default() -> Self {
-        OutputFormat::Json
-    }
+    Csv,
+    // 1 行に 1 issue の JSON
+    Ndjson,
 }
 
 #[derive(Debug, Clone)]
@@ -25,8 +29,28 @@ pub struct AnalysisConfig {
     pub max_complexity: u32,
     #[allow(dead_code)]
     pub max_depth: u32,
-    #[allow(dead_code)]
     pub config_file: Option<PathBuf>,
+    pub coverage: Option<PathBuf>,
+    pub timing: bool,
+    // --rule-stats のときの、うるさいルールとみなす割合 (%)
+    pub rule_stats: Option<f64>,
+    pub max_issues_per_rule: Option<usize>,
+    pub follow_symlinks: bool,
+    // --schema で固定した JSON 出力の版
+    pub schema: Option<SchemaVersion>,
+    // --history で指標を追記する JSONL ファイル
+    pub history: Option<PathBuf>,
+    // --summary top のとき、個々の issue の代わりに上位の一覧だけを出す
+    pub summary_mode: Option<SummaryMode>,
+    // --group-by で issue をまとめる単位（テーブルと HTML）
+    pub group_by: Option<IssueGrouping>,
+    pub log_format: LogFormat,
+    // 標準エラーが端末のときに進捗バーを出す
+    pub progress: bool,
+    // メッセージとレポートの見出しの言語
+    pub lang: Lang,
+    // JSON の結果ごとにプロジェクト全体を含める
+    pub include_project: bool,
     #[allow(dead_code)]
     pub verbose: bool,
 }
@@ -42,6 +66,19 @@ impl Default for AnalysisConfig {
             max_complexity: 10,
             max_depth: 5,
             config_file: None,
+            coverage: None,
+            timing: false,
+            rule_stats: None,
+            max_issues_per_rule: None,
+            follow_symlinks: false,
+            schema: None,
+            history: None,
+            summary_mode: None,
+            group_by: None,
+            log_format: LogFormat::Text,
+            progress: false,
+            lang: Lang::En,
+            include_project: false,
             verbose: false,
         }
     }
@@ -55,12 +92,13 @@ impl AnalysisConfig {
         depth: u32,
         output: String,
         errors_only: bool,
-        verbose: bool,
-        _quiet: bool,
+        common: Self,
     ) -> Self {
         let output_format = match output.as_str() {
             "html" => OutputFormat::Html,
             "table" => OutputFormat::Table,
+            "csv" => OutputFormat::Csv,
+            "ndjson" => OutputFormat::Ndjson,
             _ => OutputFormat::Json,
         };
         
@@ -71,8 +109,7 @@ impl AnalysisConfig {
             severity: if errors_only { Severity::Warning } else { Severity::Info },
             max_complexity,
             max_depth: depth,
-            verbose,
-            ..Default::default()
+            ..common
         }
     }
 
@@ -80,12 +117,13 @@ impl AnalysisConfig {
     pub fn from_deps_args(
         path: PathBuf,
         format: String,
-        verbose: bool,
-        _quiet: bool,
+        common: Self,
     ) -> Self {
         let output_format = match format.as_str() {
             "html" => OutputFormat::Html,
             "table" => OutputFormat::Table,
+            "csv" => OutputFormat::Csv,
+            "ndjson" => OutputFormat::Ndjson,
             _ => OutputFormat::Json,
         };
         
@@ -93,8 +131,7 @@ impl AnalysisConfig {
             path,
             analyzers: vec!["dependency".to_string()],
             output_format,
-            verbose,
-            ..Default::default()
+            ..common
         }
     }
 
@@ -102,12 +139,13 @@ impl AnalysisConfig {
     pub fn from_state_args(
         path: PathBuf,
         format: String,
-        verbose: bool,
-        _quiet: bool,
+        common: Self,
     ) -> Self {
         let output_format = match format.as_str() {
             "html" => OutputFormat::Html,
             "table" => OutputFormat::Table,
+            "csv" => OutputFormat::Csv,
+            "ndjson" => OutputFormat::Ndjson,
             _ => OutputFormat::Json,
         };
         
@@ -115,8 +153,7 @@ impl AnalysisConfig {
             path,
             analyzers: vec!["state".to_string()],
             output_format,
-            verbose,
-            ..Default::default()
+            ..common
         }
     }
 
@@ -124,12 +161,13 @@ impl AnalysisConfig {
     pub fn from_performance_args(
         path: PathBuf,
         format: String,
-        verbose: bool,
-        _quiet: bool,
+        common: Self,
     ) -> Self {
         let output_format = match format.as_str() {
             "html" => OutputFormat::Html,
             "table" => OutputFormat::Table,
+            "csv" => OutputFormat::Csv,
+            "ndjson" => OutputFormat::Ndjson,
             _ => OutputFormat::Json,
         };
         
@@ -137,12 +175,12 @@ impl AnalysisConfig {
             path,
             analyzers: vec!["performance".to_string()],
             output_format,
-            verbose,
-            ..Default::default()
+            ..common
         }
     }
 
     #[allow(dead_code)]
+    #[allow(clippy::too_many_arguments)]
     pub fn from_audit_args(
         path: PathBuf,
         full: bool,
@@ -151,16 +189,10 @@ impl AnalysisConfig {
         output_dir: PathBuf,
         formats: Vec<String>,
         severity: String,
-        verbose: bool,
-        _quiet: bool,
+        common: Self,
     ) -> Self {
         let analyzers = if full {
-            vec![
-                "component".to_string(),
-                "dependency".to_string(),
-                "state".to_string(),
-                "performance".to_string(),
-            ]
+            ANALYZER_NAMES.iter().map(|name| name.to_string()).collect()
         } else {
             analyzers.unwrap_or_else(|| vec!["component".to_string()])
         };
@@ -175,6 +207,8 @@ impl AnalysisConfig {
         let output_format = match formats.first().map(|s| s.as_str()) {
             Some("html") => OutputFormat::Html,
             Some("table") => OutputFormat::Table,
+            Some("csv") => OutputFormat::Csv,
+            Some("ndjson") => OutputFormat::Ndjson,
             _ => OutputFormat::Json,
         };
 
@@ -185,22 +219,22 @@ impl AnalysisConfig {
             output_dir: Some(output_dir),
             severity: severity_threshold,
             config_file: config,
-            verbose,
-            ..Default::default()
+            ..common
         }
     }
 
     #[allow(dead_code)]
     pub fn should_include_issue(&self, severity: &Severity) -> bool {
-        match (&self.severity, severity) {
-            (Severity::Error, Severity::Error) => true,
-            (Severity::Warning, Severity::Error | Severity::Warning) => true,
-            (Severity::Info, _) => true,
-            _ => false,
-        }
+        matches!(
+            (&self.severity, severity),
+            (Severity::Error, Severity::Error)
+                | (Severity::Warning, Severity::Error | Severity::Warning)
+                | (Severity::Info, _)
+        )
     }
     
     #[allow(dead_code)]
+    #[allow(clippy::too_many_arguments)]
     pub fn from_search_args(
         path: PathBuf,
         _keyword: String,
@@ -216,6 +250,8 @@ impl AnalysisConfig {
         let output_format = match output.as_str() {
             "html" => OutputFormat::Html,
             "table" => OutputFormat::Table,
+            "csv" => OutputFormat::Csv,
+            "ndjson" => OutputFormat::Ndjson,
             _ => OutputFormat::Json,
         };
         
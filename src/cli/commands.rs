@@ -15,6 +15,58 @@ pub struct Cli {
 
     #[arg(short, long, global = true)]
     pub quiet: bool,
+
+    /// Print how long each phase took and which inputs were loaded
+    #[arg(long, global = true)]
+    pub timing: bool,
+
+    /// Print issue counts per rule and highlight rules producing most of the issues
+    #[arg(long, global = true)]
+    pub rule_stats: bool,
+
+    /// Share of all issues (%) above which --rule-stats marks a rule as noisy
+    #[arg(long, global = true, default_value = "30")]
+    pub noisy_threshold: f64,
+
+    /// Report at most N issues per rule; the rest are counted in the summary
+    #[arg(long, global = true)]
+    pub max_issues_per_rule: Option<usize>,
+
+    /// Follow symbolic links (loops and files reached twice are skipped)
+    #[arg(long, global = true)]
+    pub follow_symlinks: bool,
+
+    /// Pin JSON output to a published schema version (v1); fields outside that schema are left out
+    #[arg(long, global = true)]
+    pub schema: Option<String>,
+
+    /// Append a timestamped metrics snapshot of each analysis run to this JSONL file
+    #[arg(long, global = true)]
+    pub history: Option<PathBuf>,
+
+    /// Replace the report with a concise summary (top: worst files, most complex components, most violated rules, largest cycles)
+    #[arg(long, global = true)]
+    pub summary: Option<String>,
+
+    /// Group issues in table and HTML output (rule, file, directory), showing counts per group
+    #[arg(long, global = true)]
+    pub group_by: Option<String>,
+
+    /// Format of progress messages (text: printed with --verbose, json: one event per line on stderr with per-phase timings)
+    #[arg(long, global = true, default_value = "text")]
+    pub log_format: String,
+
+    /// Language of CLI messages and report headings (en, ja). Defaults to the locale (LC_ALL, LC_MESSAGES, LANG)
+    #[arg(long, global = true)]
+    pub lang: Option<String>,
+
+    /// Embed the full parsed project in every JSON result, as older versions did (by default only a project summary is written once)
+    #[arg(long, global = true)]
+    pub include_project: bool,
+
+    /// Disable colored output (also off when NO_COLOR is set or stdout is not a terminal)
+    #[arg(long, global = true)]
+    pub no_color: bool,
 }
 
 #[derive(Subcommand)]
@@ -32,13 +84,17 @@ pub enum Commands {
         #[arg(short, long, default_value = "5")]
         depth: u32,
         
-        /// Output format (json, table, html)
+        /// Output format (json, table, html, csv, ndjson)
         #[arg(short, long, default_value = "json")]
         output: String,
         
         /// Show only errors and warnings
         #[arg(long)]
         errors_only: bool,
+
+        /// lcov coverage report (e.g. coverage/lcov.info)
+        #[arg(long)]
+        coverage: Option<PathBuf>,
     },
     
     /// Analyze dependencies and architectural patterns
@@ -58,7 +114,7 @@ pub enum Commands {
         #[arg(long)]
         depth: bool,
         
-        /// Output format (json, table, html)
+        /// Output format (json, table, html, csv, ndjson)
         #[arg(short, long, default_value = "table")]
         format: String,
     },
@@ -80,7 +136,7 @@ pub enum Commands {
         #[arg(long)]
         change_detection: bool,
         
-        /// Output format (json, table, html)
+        /// Output format (json, table, html, csv, ndjson)
         #[arg(short, long, default_value = "json")]
         format: String,
     },
@@ -102,7 +158,7 @@ pub enum Commands {
         #[arg(long)]
         memory_leaks: bool,
         
-        /// Output format (json, table, html)
+        /// Output format (json, table, html, csv, ndjson)
         #[arg(short, long, default_value = "json")]
         format: String,
     },
@@ -128,15 +184,98 @@ pub enum Commands {
         #[arg(short, long, default_value = "./reports")]
         output_dir: PathBuf,
         
-        /// Output formats (json, html, table)
+        /// Output formats (json, html, table, csv, ndjson)
         #[arg(long, value_delimiter = ',', default_values = ["json"])]
         formats: Vec<String>,
         
         /// Severity threshold (error, warning, info)
         #[arg(long, default_value = "info")]
         severity: String,
+
+        /// lcov coverage report (e.g. coverage/lcov.info)
+        #[arg(long)]
+        coverage: Option<PathBuf>,
     },
     
+    /// Measure parse and per-analyzer timings over repeated runs
+    Bench {
+        /// Path to analyze
+        path: PathBuf,
+
+        /// Number of measured runs
+        #[arg(long, default_value = "5")]
+        iterations: u32,
+
+        /// Untimed runs before measuring, to warm up file system caches
+        #[arg(long, default_value = "1")]
+        warmup: u32,
+
+        /// Analyzers to measure (comma-separated, default: all)
+        #[arg(long, value_delimiter = ',')]
+        analyzers: Option<Vec<String>>,
+
+        /// Configuration file path
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+    },
+
+    /// Compare analysis output of the bundled fixture projects against golden files
+    Golden {
+        /// Directory containing the golden files
+        #[arg(long = "golden", default_value = "tests/golden")]
+        golden: PathBuf,
+
+        /// Directory containing the fixture projects (one project per subdirectory)
+        #[arg(long, default_value = "tests/fixtures/projects")]
+        fixtures: PathBuf,
+
+        /// Rewrite the golden files with the current output
+        #[arg(long)]
+        update: bool,
+    },
+
+    /// Apply safe automated fixes for mechanical issues
+    Fix {
+        /// Path to fix
+        path: PathBuf,
+
+        /// Show a diff of the changes without writing files
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Fixes to apply (comma-separated, default: all)
+        #[arg(long, value_delimiter = ',')]
+        rules: Option<Vec<String>>,
+    },
+
+    /// Render metrics recorded with --history as sparklines or an HTML chart
+    Trends {
+        /// History file written by --history
+        history: PathBuf,
+
+        /// Output format (text, html)
+        #[arg(short, long, default_value = "text")]
+        format: String,
+
+        /// Output file path
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Show only the N most recent runs
+        #[arg(long)]
+        last: Option<usize>,
+    },
+
+    /// Print the JSON Schema of a machine-readable output
+    Schema {
+        /// Output to describe (issues, graph, metrics)
+        kind: String,
+    },
+
     /// Initialize configuration file
     Init {
         /// Output configuration file path
@@ -164,9 +303,9 @@ pub enum Commands {
         /// Path to search in
         path: PathBuf,
         
-        /// Keyword to search for
-        #[arg(short, long)]
-        keyword: String,
+        /// Keyword to search for (not needed with --structural)
+        #[arg(short, long, required_unless_present = "structural")]
+        keyword: Option<String>,
         
         /// File types to search in (html, ts, js, all)
         #[arg(short, long, default_value = "all")]
@@ -212,20 +351,87 @@ pub enum Commands {
         #[arg(long)]
         function_name: bool,
         
-        /// Structural search pattern (for complex patterns)
+        /// Structural search pattern matched against the TypeScript AST, with ast-grep style metavariables (e.g. 'this.$X.subscribe($$$)', '@Input() $NAME: any')
         #[arg(long)]
         structural: Option<String>,
+
+        /// Only search within these Angular artifacts, using the parsed project (components, templates, styles, services, modules, guards, specs)
+        #[arg(long = "in", value_delimiter = ',')]
+        within: Vec<String>,
+
+        /// Only search components whose selector matches this glob, plus their templates unless --in is given (e.g. app-card, 'app-*')
+        #[arg(long)]
+        selector: Option<String>,
+
+        /// Only search classes and members carrying this decorator (e.g. Input, Injectable)
+        #[arg(long)]
+        decorator: Option<String>,
     },
     
+    /// Show which files, tests and lazy-loaded chunks are affected if a file or exported symbol changes
+    Impact {
+        /// File path, directory or glob, exported symbol or component selector (templates and styles count as their component)
+        target: String,
+
+        /// Path to analyze
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Output format (table, json, mermaid)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+
+        /// Output file path
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Find references to a component (class name or selector), service or guard: template usages, imports, injection sites and routes
+    Usages {
+        /// Class name or component selector (e.g. CardComponent, app-card, UserService)
+        symbol: String,
+
+        /// Path to search in
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+
+        /// Output file path
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// List the public API of each Angular library (ng-package.json) and find imports that bypass its entry point
+    Library {
+        /// Workspace path to analyze
+        #[arg(default_value = ".")]
+        path: PathBuf,
+
+        /// Output format (table, json)
+        #[arg(short, long, default_value = "table")]
+        format: String,
+
+        /// Output file path
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
     /// Analyze TypeScript import/export relationships and generate dependency graphs
     Graph {
         /// Path to analyze
         path: PathBuf,
         
-        /// Output format (dot, mermaid, json, table)
+        /// Output format (dot, mermaid, json, table; graphml, svg and html for --kind files; table and json for --kind services)
         #[arg(short, long, default_value = "mermaid")]
         format: String,
         
+        /// Graph to draw: files (TypeScript imports), components (component tree built from templates), modules (NgModule imports and lazy routes), injection (services injected by each class), packages (files importing each npm package), bundles (source size pulled in by each lazy route) or services (public methods of each service and their callers)
+        #[arg(long, default_value = "files")]
+        kind: String,
+        
         /// Output file path
         #[arg(short, long)]
         output: Option<PathBuf>,
@@ -234,6 +440,10 @@ pub enum Commands {
         #[arg(long)]
         circular: bool,
         
+        /// Maximum number of circular dependencies to enumerate
+        #[arg(long, default_value = "100")]
+        max_cycles: usize,
+        
         /// Include orphaned file detection
         #[arg(long)]
         orphaned: bool,
@@ -241,6 +451,14 @@ pub enum Commands {
         /// Include dependency depth analysis
         #[arg(long)]
         depth: bool,
+
+        /// Include exported symbols that are never imported anywhere
+        #[arg(long)]
+        unused_exports: bool,
+
+        /// Symbol name or file glob to leave out of the unused export report (repeatable, e.g. "*Module", "**/public-api.ts")
+        #[arg(long = "ignore-export")]
+        ignored_exports: Vec<String>,
         
         /// Maximum number of files to show in top lists
         #[arg(long, default_value = "10")]
@@ -253,6 +471,30 @@ pub enum Commands {
         /// Exclude node_modules and other directories
         #[arg(long)]
         exclude_external: bool,
+        
+        /// Only keep edges to files imported by at least this many files
+        #[arg(long, default_value = "0")]
+        min_fan_in: u32,
+        
+        /// Glob pattern of files to drop from the graph (repeatable, e.g. "**/*.spec.ts")
+        #[arg(long = "exclude-pattern")]
+        exclude_patterns: Vec<String>,
+        
+        /// Hide files that import nothing and are imported by at most one file
+        #[arg(long)]
+        hide_leaf_nodes: bool,
+
+        /// Only show the neighborhood of these files (path, directory or glob; repeatable)
+        #[arg(long)]
+        focus: Vec<String>,
+
+        /// With --focus, levels of importers to include
+        #[arg(long, default_value = "1")]
+        fan_in: u32,
+
+        /// With --focus, levels of imported files to include
+        #[arg(long, default_value = "1")]
+        fan_out: u32,
     },
 }
 
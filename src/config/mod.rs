@@ -1,10 +1,14 @@
 pub mod rules;
 
+use crate::ast::{Issue, Severity};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::fs;
 use anyhow::Result;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+
+pub const DEFAULT_CONFIG_FILE: &str = ".ng-analyzer.json";
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -12,6 +16,111 @@ pub struct Config {
     pub ignore: Vec<String>,
     pub output: OutputConfig,
     pub rules: HashMap<String, RuleConfig>,
+    #[serde(default)]
+    pub overrides: Vec<ConfigOverride>,
+    #[serde(default, skip_serializing_if = "BoundariesConfig::is_empty")]
+    pub boundaries: BoundariesConfig,
+    #[serde(default, skip_serializing_if = "BudgetsConfig::is_empty")]
+    pub budgets: BudgetsConfig,
+    #[serde(default, skip_serializing_if = "HealthWeights::is_default")]
+    pub health: HealthWeights,
+}
+
+// 健全性スコアのカテゴリごとの重み。0 にしたカテゴリはスコアに含めない
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HealthWeights {
+    pub issue_density: f64,
+    pub complexity: f64,
+    pub onpush: f64,
+    pub cycles: f64,
+    pub coverage: f64,
+}
+
+impl Default for HealthWeights {
+    fn default() -> Self {
+        Self {
+            issue_density: 30.0,
+            complexity: 20.0,
+            onpush: 20.0,
+            cycles: 15.0,
+            coverage: 15.0,
+        }
+    }
+}
+
+impl HealthWeights {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+// 解析後にプロジェクト全体で評価する予算。指定したものだけを評価し、1 つでも超えると終了コードが 1 になる
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BudgetsConfig {
+    // 1 つの NgModule の declarations に並ぶコンポーネントの数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_components_per_module: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_average_complexity: Option<f64>,
+    // インラインか templateUrl のテンプレートの物理行数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_template_lines: Option<u32>,
+    // 1 つのコンポーネントの @Input と input() の数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_inputs: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_onpush_percentage: Option<f64>,
+    // ファイル単位の import の循環の数
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cycles: Option<u32>,
+}
+
+impl BudgetsConfig {
+    pub fn is_empty(&self) -> bool {
+        self.max_components_per_module.is_none()
+            && self.max_average_complexity.is_none()
+            && self.max_template_lines.is_none()
+            && self.max_inputs.is_none()
+            && self.min_onpush_percentage.is_none()
+            && self.max_cycles.is_none()
+    }
+}
+
+// boundaries アナライザーのレイヤー定義。pattern はプロジェクトルートからのディレクトリの glob
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BoundariesConfig {
+    #[serde(default)]
+    pub layers: Vec<LayerConfig>,
+}
+
+impl BoundariesConfig {
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerConfig {
+    pub name: String,
+    pub pattern: String,
+    // import してよい他のレイヤー。同じディレクトリ内の import は常に許可される
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigOverride {
+    pub files: Vec<String>,
+    pub rules: HashMap<String, RuleOverride>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RuleOverride {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub severity: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,28 +183,117 @@ impl Default for Config {
                 include_metrics: true,
             },
             rules: create_recommended_rules(),
+            overrides: Vec::new(),
+            boundaries: BoundariesConfig::default(),
+            budgets: BudgetsConfig::default(),
+            health: HealthWeights::default(),
         }
     }
 }
 
 impl Config {
-    #[allow(dead_code)]
-    pub fn load_from_file(path: &PathBuf) -> Result<Self> {
+    pub fn load_from_file(path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(path)?;
-        let config: Config = serde_json::from_str(&content)?;
+        let config: Config = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Invalid configuration file {}: {}", path.display(), e))?;
         Ok(config)
     }
 
+    // 明示的な指定がなければカレントディレクトリの .ng-analyzer.json を使用する
+    pub fn discover(config_file: Option<&Path>) -> Result<Option<Self>> {
+        match config_file {
+            Some(path) => Ok(Some(Self::load_from_file(path)?)),
+            None => {
+                let default_path = Path::new(DEFAULT_CONFIG_FILE);
+                if default_path.exists() {
+                    Ok(Some(Self::load_from_file(default_path)?))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+
+    pub fn rule_option(&self, rule: &str, option: &str) -> Option<&serde_json::Value> {
+        self.rules.get(rule)?.options.get(option)
+    }
+
+    // トップレベルで無効でも、overrides で一部のファイルだけ有効にされていれば実行される
+    pub fn rule_may_run(&self, rule: &str) -> bool {
+        self.rules.get(rule).is_none_or(|rule_config| rule_config.enabled)
+            || self.overrides.iter().any(|o| o.rules.get(rule).and_then(|r| r.enabled) == Some(true))
+    }
+
+    // 各 issue のファイルに応じて、無効化されたルールを除外し重要度を上書きする
+    pub fn apply_to_issues(&self, issues: Vec<Issue>, root_path: &Path) -> Result<Vec<Issue>> {
+        let overrides = self.overrides.iter()
+            .map(|config_override| Ok((build_glob_set(&config_override.files)?, config_override)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut resolved = Vec::with_capacity(issues.len());
+        for mut issue in issues {
+            let relative_path = relative_issue_path(&issue.file_path, root_path);
+            if let Some(severity) = self.resolve_rule(&issue.rule, &relative_path, &issue.severity, &overrides) {
+                issue.severity = severity;
+                resolved.push(issue);
+            }
+        }
+
+        Ok(resolved)
+    }
+
+    // トップレベルの rules を適用した後、マッチする overrides を順番に適用する
+    fn resolve_rule(
+        &self,
+        rule: &str,
+        relative_path: &str,
+        severity: &Severity,
+        overrides: &[(GlobSet, &ConfigOverride)],
+    ) -> Option<Severity> {
+        let mut enabled = true;
+        let mut effective = severity.clone();
+
+        if let Some(rule_config) = self.rules.get(rule) {
+            enabled = rule_config.enabled;
+            effective = parse_severity(&rule_config.severity).unwrap_or(effective);
+        }
+
+        for (globs, config_override) in overrides {
+            if !globs.is_match(relative_path) {
+                continue;
+            }
+            if let Some(rule_override) = config_override.rules.get(rule) {
+                if let Some(rule_enabled) = rule_override.enabled {
+                    enabled = rule_enabled;
+                }
+                if let Some(rule_severity) = &rule_override.severity {
+                    effective = parse_severity(rule_severity).unwrap_or(effective);
+                }
+            }
+        }
+
+        if enabled {
+            Some(effective)
+        } else {
+            None
+        }
+    }
+
     pub fn save_to_file(&self, path: &PathBuf) -> Result<()> {
         let content = serde_json::to_string_pretty(self)?;
         fs::write(path, content)?;
         Ok(())
     }
 
+    #[allow(dead_code)]
     pub fn new() -> Self {
         Self {
             profiles: HashMap::new(),
             rules: HashMap::new(),
+            overrides: Vec::new(),
+            boundaries: BoundariesConfig::default(),
+            budgets: BudgetsConfig::default(),
+            health: HealthWeights::default(),
             ignore: Vec::new(),
             output: OutputConfig {
                 formats: vec!["json".to_string()],
@@ -123,6 +321,32 @@ impl Config {
     }
 }
 
+pub fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern)?);
+    }
+    Ok(builder.build()?)
+}
+
+pub fn relative_issue_path(file_path: &str, root_path: &Path) -> String {
+    let path = Path::new(file_path);
+    path.strip_prefix(root_path)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+        .replace('\\', "/")
+}
+
+fn parse_severity(severity: &str) -> Option<Severity> {
+    match severity.to_lowercase().as_str() {
+        "error" => Some(Severity::Error),
+        "warning" => Some(Severity::Warning),
+        "info" => Some(Severity::Info),
+        _ => None,
+    }
+}
+
 fn create_strict_rules() -> HashMap<String, RuleConfig> {
     let mut rules = HashMap::new();
     
@@ -237,4 +461,51 @@ fn create_relaxed_rules() -> HashMap<String, RuleConfig> {
     });
     
     rules
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(rule: &str, file_path: &str) -> Issue {
+        Issue {
+            severity: Severity::Warning,
+            rule: rule.to_string(),
+            message: String::new(),
+            file_path: file_path.to_string(),
+            line: None,
+            column: None,
+            fingerprint: None,
+        }
+    }
+
+    #[test]
+    fn test_overrides_apply_per_file() {
+        let mut config = Config::new();
+        let mut legacy_rules = HashMap::new();
+        legacy_rules.insert("component-complexity".to_string(), RuleOverride {
+            enabled: None,
+            severity: Some("info".to_string()),
+        });
+        legacy_rules.insert("too-many-inputs".to_string(), RuleOverride {
+            enabled: Some(false),
+            severity: None,
+        });
+        config.overrides.push(ConfigOverride {
+            files: vec!["**/legacy/**".to_string()],
+            rules: legacy_rules,
+        });
+
+        let issues = vec![
+            issue("component-complexity", "/app/src/legacy/old.component.ts"),
+            issue("too-many-inputs", "/app/src/legacy/old.component.ts"),
+            issue("too-many-inputs", "/app/src/features/new.component.ts"),
+        ];
+
+        let resolved = config.apply_to_issues(issues, Path::new("/app")).unwrap();
+
+        assert_eq!(resolved.len(), 2);
+        assert!(matches!(resolved[0].severity, Severity::Info));
+        assert_eq!(resolved[1].file_path, "/app/src/features/new.component.ts");
+        assert!(matches!(resolved[1].severity, Severity::Warning));
+    }
+}
@@ -72,97 +72,740 @@ pub fn get_all_rule_definitions() -> Vec<RuleDefinition> {
                 },
             ],
         },
+        RuleDefinition {
+            name: "component-api-surface".to_string(),
+            description: "Flags components whose inputs, outputs and public methods together exceed the limit, suggesting composition".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_api_size".to_string(),
+                    description: "Maximum combined number of inputs, outputs and public methods".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(20)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "component-resource-outside-folder".to_string(),
+            description: "Flags templateUrl or styleUrls that point outside the component's folder".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "orphan-component-resource".to_string(),
+            description: "Flags .html and style files in component folders that no component references".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "orphan-resource-file".to_string(),
+            description: "Flags .html and style files outside component folders that no component, angular.json global style, asset or stylesheet import references".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "deep-component-nesting".to_string(),
+            description: "Flags components nested too deep in the template hierarchy built from selectors".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_depth".to_string(),
+                    description: "Maximum number of parent components above a component".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(5)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "unused-input".to_string(),
+            description: "Flags Inputs that no template using the component's selector binds".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "ignore".to_string(),
+                    description: "Glob patterns matched against component names and file paths to leave out, such as published library components (e.g. \"Lib*Component\", \"projects/ui/**\")".to_string(),
+                    option_type: "array".to_string(),
+                    default_value: serde_json::Value::Array(vec![]),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "unused-output".to_string(),
+            description: "Flags Outputs that no template using the component's selector listens to".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "ignore".to_string(),
+                    description: "Glob patterns matched against component names and file paths to leave out, such as published library components (e.g. \"Lib*Component\", \"projects/ui/**\")".to_string(),
+                    option_type: "array".to_string(),
+                    default_value: serde_json::Value::Array(vec![]),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "deep-inheritance-chain".to_string(),
+            description: "Flags classes with too many levels of base classes and mixins".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_depth".to_string(),
+                    description: "Maximum number of base classes and mixins above a class".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(2)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "extends-concrete-component".to_string(),
+            description: "Flags components that extend another non-abstract @Component class".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "missing-super-lifecycle-call".to_string(),
+            description: "Flags lifecycle hooks that override a base class hook without calling super".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "error".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "deep-render-tree".to_string(),
+            description: "Flags components rendered too deep below the bootstrap component, following templates and router outlets".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_depth".to_string(),
+                    description: "Maximum number of levels between the bootstrap component and a rendered component".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(8)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "legacy-control-flow".to_string(),
+            description: "Suggests migrating *ngIf / *ngFor / *ngSwitch templates to the built-in @if / @for / @switch blocks".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "native-element-manipulation".to_string(),
+            description: "Flags components that change styles, attributes or children through ElementRef.nativeElement instead of Renderer2".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "single-use-component".to_string(),
+            description: "Flags small components used in exactly one template that could be inlined into their parent".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_lines".to_string(),
+                    description: "Only flag components with at most this many lines of code".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(50)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "component-selection-chain".to_string(),
+            description: "Flags switch/if-else chains that pick a child component by type and suggests ngComponentOutlet".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_branches".to_string(),
+                    description: "Maximum number of component branches allowed in one chain".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(3)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "low-test-coverage".to_string(),
+            description: "Flags complex components with low or no line coverage in the lcov report passed via --coverage".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "min_coverage".to_string(),
+                    description: "Minimum line coverage percentage for complex components".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(50)),
+                    possible_values: None,
+                },
+                ConfigurableOption {
+                    name: "min_complexity".to_string(),
+                    description: "Complexity score from which a component is checked".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(5)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "adoption-threshold".to_string(),
+            description: "Fails when standalone, signal input, OnPush or typed forms adoption is below the configured percentage".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "error".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "min_standalone_percentage".to_string(),
+                    description: "Minimum percentage of standalone components".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Null,
+                    possible_values: None,
+                },
+                ConfigurableOption {
+                    name: "min_signal_inputs_percentage".to_string(),
+                    description: "Minimum percentage of inputs declared with input()".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Null,
+                    possible_values: None,
+                },
+                ConfigurableOption {
+                    name: "min_onpush_percentage".to_string(),
+                    description: "Minimum percentage of OnPush components".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Null,
+                    possible_values: None,
+                },
+                ConfigurableOption {
+                    name: "min_typed_forms_percentage".to_string(),
+                    description: "Minimum percentage of typed reactive form constructions".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Null,
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "max-file-lines".to_string(),
+            description: "Checks if a TypeScript file exceeds the maximum number of lines of code".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_lines".to_string(),
+                    description: "Maximum allowed lines of code per file, excluding blank and comment lines".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(400)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "native-event-output-name".to_string(),
+            description: "Flags @Output() names that shadow native DOM events such as click or change".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "allowed_names".to_string(),
+                    description: "Output names that may intentionally reuse a native event name".to_string(),
+                    option_type: "array".to_string(),
+                    default_value: serde_json::Value::Array(vec![]),
+                    possible_values: None,
+                },
+            ],
+        },
         RuleDefinition {
             name: "missing-cleanup-pattern".to_string(),
             description: "Checks for proper cleanup patterns in components".to_string(),
             category: "Memory Management".to_string(),
             default_severity: "warning".to_string(),
-            configurable_options: vec![],
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "template-conflict".to_string(),
+            description: "Checks for conflicting template definitions".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "error".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "circular-dependency".to_string(),
+            description: "Detects circular dependencies between components and services".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "error".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "recursive-component-composition".to_string(),
+            description: "Detects components that render each other recursively through templates, ng-template outlets or projected content".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "duplicate-dependency-version".to_string(),
+            description: "Detects libraries installed in several major versions across the workspace lockfile".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "ignore_packages".to_string(),
+                    description: "Package names whose duplicated major versions are accepted".to_string(),
+                    option_type: "array".to_string(),
+                    default_value: serde_json::Value::Array(vec![]),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "forroot-in-feature-module".to_string(),
+            description: "Detects forRoot() imports in lazy-loaded or feature modules instead of the root module".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "error".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "root-service-single-lazy-feature".to_string(),
+            description: "Flags providedIn: 'root' services injected only from a single lazy-loaded feature".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "component-provided-service".to_string(),
+            description: "Flags services listed in component providers although they are already provided in root or by a module".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "provided-in-any".to_string(),
+            description: "Flags services using the deprecated providedIn: 'any'".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "framework-provider-override".to_string(),
+            description: "Flags framework tokens such as ErrorHandler or RouteReuseStrategy overridden more than once in the root injector or in component, route or lazy-module injectors".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "http-interceptor-order".to_string(),
+            description: "Flags HTTP_INTERCEPTORS provided without multi: true or registered from several root providers whose order depends on module import order".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "duplicate-provider".to_string(),
+            description: "Flags services registered several times in the root injector or in both the root injector and a route or lazy module injector".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "duplicate-forroot-import".to_string(),
+            description: "Detects the same module's forRoot() imported by more than one NgModule".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "error".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "duplicate-interceptor".to_string(),
+            description: "Flags the same HTTP interceptor registered more than once through HTTP_INTERCEPTORS or withInterceptors".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "circular-module-import".to_string(),
+            description: "Detects NgModules that import each other in a cycle".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "error".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "unused-dependency".to_string(),
+            description: "Identifies unused dependencies".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "inconsistent-injection-style".to_string(),
+            description: "Flags components and services that inject some dependencies through the constructor and others with inject()".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "preferred_style".to_string(),
+                    description: "Injection style to migrate mixed classes to".to_string(),
+                    option_type: "string".to_string(),
+                    default_value: serde_json::Value::String("inject".to_string()),
+                    possible_values: Some(vec![
+                        serde_json::Value::String("inject".to_string()),
+                        serde_json::Value::String("constructor".to_string()),
+                    ]),
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "deep-dependency-chain".to_string(),
+            description: "Checks for overly deep dependency chains".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_depth".to_string(),
+                    description: "Maximum allowed dependency depth".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(5)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "deep-import-chain".to_string(),
+            description: "Flags the longest file import chains (cycles counted as one step) that exceed the depth budget".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_depth".to_string(),
+                    description: "Maximum number of imports from an entry file to the deepest file it reaches".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(12)),
+                    possible_values: None,
+                },
+                ConfigurableOption {
+                    name: "max_chains".to_string(),
+                    description: "Number of longest chains to report".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(5)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "wildcard-reexport".to_string(),
+            description: "Flags `export * from` in barrel files and lists the symbols actually imported through them".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "testing-code-in-production".to_string(),
+            description: "Detects production files importing mocks or testing utilities through barrel re-exports".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "unused-export".to_string(),
+            description: "Detects exported symbols that are never imported anywhere in the workspace".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "ignore".to_string(),
+                    description: "Glob patterns matched against symbol names and file paths to leave out (e.g. \"*Module\", \"**/public-api.ts\")".to_string(),
+                    option_type: "array".to_string(),
+                    default_value: serde_json::Value::Array(vec![]),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "duplicate-symbol".to_string(),
+            description: "Detects classes, interfaces and functions with the same name exported from several files, and whether their bodies are identical copies".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "ignore".to_string(),
+                    description: "Glob patterns matched against symbol names and file paths to leave out (e.g. \"AppComponent\", \"apps/**\")".to_string(),
+                    option_type: "array".to_string(),
+                    default_value: serde_json::Value::Array(vec![]),
+                    possible_values: None,
+                },
+                ConfigurableOption {
+                    name: "identical_only".to_string(),
+                    description: "Only report symbols whose bodies are identical apart from whitespace and comments".to_string(),
+                    option_type: "boolean".to_string(),
+                    default_value: serde_json::Value::Bool(false),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "code-clone".to_string(),
+            description: "Detects blocks of code duplicated across the project by comparing token sequences (imports are ignored)".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "min_tokens".to_string(),
+                    description: "Minimum number of identical tokens for a block to count as a clone".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(100)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "constructor-initialization-chain".to_string(),
+            description: "Detects components whose instantiation transitively constructs many services doing work in constructors".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_services".to_string(),
+                    description: "Maximum number of services with constructor logic that one component constructs transitively".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(3)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "duplicated-guard-logic".to_string(),
+            description: "Detects route guards and resolvers with near-duplicate bodies".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "consider-state-management".to_string(),
+            description: "Suggests centralized state management for complex applications".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "state_service_threshold".to_string(),
+                    description: "Number of state services before suggesting centralized management".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(3)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "missing-unsubscribe-pattern".to_string(),
+            description: "Checks for proper observable unsubscription patterns".to_string(),
+            category: "Memory Management".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "store-state-mutation".to_string(),
+            description: "Flags mutation of objects obtained from NgRx store selections".to_string(),
+            category: "State Management".to_string(),
+            default_severity: "error".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "shared-mutable-export".to_string(),
+            description: "Flags exported let/var and non-frozen object, array, Map or Set constants imported by several files".to_string(),
+            category: "State Management".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "min_consumers".to_string(),
+                    description: "Minimum number of importing files before a mutable export is reported".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(2)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "unfiltered-router-events".to_string(),
+            description: "Flags router.events subscriptions that do not filter by event type (e.g. NavigationEnd)".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "router-events-without-teardown".to_string(),
+            description: "Flags router.events subscriptions in components that are never torn down".to_string(),
+            category: "Memory Management".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "stale-route-params".to_string(),
+            description: "Flags components routed on a path with parameters that read the params from route.snapshot once instead of subscribing to paramMap".to_string(),
+            category: "Code Quality".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "duplicated-router-event-logic".to_string(),
+            description: "Detects identical router event handling repeated across components".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "high-default-change-detection".to_string(),
+            description: "Warns about high usage of default change detection".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "threshold_percentage".to_string(),
+                    description: "Percentage threshold for default change detection usage".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(70)),
+                    possible_values: None,
+                },
+            ],
         },
         RuleDefinition {
-            name: "template-conflict".to_string(),
-            description: "Checks for conflicting template definitions".to_string(),
-            category: "Code Quality".to_string(),
-            default_severity: "error".to_string(),
-            configurable_options: vec![],
+            name: "consider-lazy-loading".to_string(),
+            description: "Suggests implementing lazy loading for large applications".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "component_threshold".to_string(),
+                    description: "Number of components before suggesting lazy loading".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(10)),
+                    possible_values: None,
+                },
+            ],
         },
         RuleDefinition {
-            name: "circular-dependency".to_string(),
-            description: "Detects circular dependencies between components and services".to_string(),
-            category: "Architecture".to_string(),
-            default_severity: "error".to_string(),
+            name: "lazy-route-eager-import".to_string(),
+            description: "Flags files of a lazily loaded route that the initial bundle imports statically, defeating lazy loading".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "warning".to_string(),
             configurable_options: vec![],
         },
         RuleDefinition {
-            name: "unused-dependency".to_string(),
-            description: "Identifies unused dependencies".to_string(),
-            category: "Code Quality".to_string(),
-            default_severity: "warning".to_string(),
+            name: "lazy-route-shared-code".to_string(),
+            description: "Flags files outside the initial bundle that several lazy routes import and that belong in a shared chunk".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "info".to_string(),
             configurable_options: vec![],
         },
         RuleDefinition {
-            name: "deep-dependency-chain".to_string(),
-            description: "Checks for overly deep dependency chains".to_string(),
-            category: "Architecture".to_string(),
+            name: "window-location-navigation".to_string(),
+            description: "Flags window.location navigation inside routed apps where Router.navigate should be used".to_string(),
+            category: "Performance".to_string(),
             default_severity: "warning".to_string(),
             configurable_options: vec![
                 ConfigurableOption {
-                    name: "max_depth".to_string(),
-                    description: "Maximum allowed dependency depth".to_string(),
-                    option_type: "number".to_string(),
-                    default_value: serde_json::Value::Number(serde_json::Number::from(5)),
+                    name: "allowed_urls".to_string(),
+                    description: "URL prefixes that may be opened with window.location in addition to absolute external URLs".to_string(),
+                    option_type: "array".to_string(),
+                    default_value: serde_json::Value::Array(vec![]),
                     possible_values: None,
                 },
             ],
         },
         RuleDefinition {
-            name: "consider-state-management".to_string(),
-            description: "Suggests centralized state management for complex applications".to_string(),
-            category: "Architecture".to_string(),
-            default_severity: "info".to_string(),
+            name: "manual-change-detection".to_string(),
+            description: "Flags ApplicationRef.tick(), detectChanges() in loops or intervals, and excessive markForCheck() calls".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "warning".to_string(),
             configurable_options: vec![
                 ConfigurableOption {
-                    name: "state_service_threshold".to_string(),
-                    description: "Number of state services before suggesting centralized management".to_string(),
+                    name: "max_mark_for_check".to_string(),
+                    description: "Maximum markForCheck() call sites per class before it is reported".to_string(),
                     option_type: "number".to_string(),
-                    default_value: serde_json::Value::Number(serde_json::Number::from(3)),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(5)),
                     possible_values: None,
                 },
             ],
         },
         RuleDefinition {
-            name: "missing-unsubscribe-pattern".to_string(),
-            description: "Checks for proper observable unsubscription patterns".to_string(),
-            category: "Memory Management".to_string(),
+            name: "for-missing-track".to_string(),
+            description: "Flags @for blocks without a track expression".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "error".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "missing-trackby".to_string(),
+            description: "Flags *ngFor loops without trackBy and suggests the id-like member of the item type".to_string(),
+            category: "Performance".to_string(),
             default_severity: "warning".to_string(),
             configurable_options: vec![],
         },
         RuleDefinition {
-            name: "high-default-change-detection".to_string(),
-            description: "Warns about high usage of default change detection".to_string(),
+            name: "impure-pipe".to_string(),
+            description: "Flags pipes declared with pure: false and counts their usages in templates".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "heavy-pipe-in-loop".to_string(),
+            description: "Flags pipes that sort or filter arrays in transform() and are used inside *ngFor / @for".to_string(),
             category: "Performance".to_string(),
             default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "template-method-call".to_string(),
+            description: "Flags component methods called from template bindings and interpolations".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "defer-heavy-component".to_string(),
+            description: "Suggests @defer for large standalone components rendered only inside @if / @switch branches".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "info".to_string(),
             configurable_options: vec![
                 ConfigurableOption {
-                    name: "threshold_percentage".to_string(),
-                    description: "Percentage threshold for default change detection usage".to_string(),
+                    name: "min_lines".to_string(),
+                    description: "Minimum lines of code of the child component before it is suggested".to_string(),
                     option_type: "number".to_string(),
-                    default_value: serde_json::Value::Number(serde_json::Number::from(70)),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(200)),
                     possible_values: None,
                 },
             ],
         },
         RuleDefinition {
-            name: "consider-lazy-loading".to_string(),
-            description: "Suggests implementing lazy loading for large applications".to_string(),
+            name: "defer-below-the-fold".to_string(),
+            description: "Suggests @defer (on viewport) for large standalone components and loading=\"lazy\" for images rendered below the fold".to_string(),
             category: "Performance".to_string(),
             default_severity: "info".to_string(),
             configurable_options: vec![
                 ConfigurableOption {
-                    name: "component_threshold".to_string(),
-                    description: "Number of components before suggesting lazy loading".to_string(),
+                    name: "fold_line".to_string(),
+                    description: "Template line after which elements are considered below the fold".to_string(),
                     option_type: "number".to_string(),
-                    default_value: serde_json::Value::Number(serde_json::Number::from(10)),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(40)),
+                    possible_values: None,
+                },
+                ConfigurableOption {
+                    name: "min_lines".to_string(),
+                    description: "Minimum lines of code of the component including its nested components".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(300)),
                     possible_values: None,
                 },
             ],
@@ -174,6 +817,20 @@ pub fn get_all_rule_definitions() -> Vec<RuleDefinition> {
             default_severity: "warning".to_string(),
             configurable_options: vec![],
         },
+        RuleDefinition {
+            name: "global-listener-leak".to_string(),
+            description: "Flags window/document listeners added with addEventListener that are not removed in ngOnDestroy or tied to an AbortSignal".to_string(),
+            category: "Memory Management".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "unthrottled-scroll-listener".to_string(),
+            description: "Flags window/document scroll and resize listeners whose handlers are not debounced or throttled".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
         RuleDefinition {
             name: "excessive-bindings".to_string(),
             description: "Checks for excessive property and event bindings".to_string(),
@@ -189,6 +846,251 @@ pub fn get_all_rule_definitions() -> Vec<RuleDefinition> {
                 },
             ],
         },
+        RuleDefinition {
+            name: "missing-spec".to_string(),
+            description: "Flags components and services without a matching .spec.ts file".to_string(),
+            category: "Testing".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "empty-spec".to_string(),
+            description: "Flags spec files that contain no it() or test() blocks".to_string(),
+            category: "Testing".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "disabled-tests".to_string(),
+            description: "Flags spec files with xit, xdescribe or .skip tests".to_string(),
+            category: "Testing".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "http-client-in-component".to_string(),
+            description: "Flags components that inject HttpClient instead of going through a service".to_string(),
+            category: "HTTP".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "http-missing-error-handling".to_string(),
+            description: "Flags HttpClient requests without catchError or a subscribe error callback".to_string(),
+            category: "HTTP".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "hardcoded-http-url".to_string(),
+            description: "Flags absolute request URLs that should come from environment config".to_string(),
+            category: "HTTP".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "duplicate-http-endpoint".to_string(),
+            description: "Flags endpoints requested from several components or services".to_string(),
+            category: "HTTP".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "large-component-stylesheet".to_string(),
+            description: "Flags component stylesheets with too many lines".to_string(),
+            category: "Styles".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_lines".to_string(),
+                    description: "Maximum number of lines per component stylesheet".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(200)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "ng-deep-usage".to_string(),
+            description: "Flags ::ng-deep, which is deprecated and leaks styles out of the component".to_string(),
+            category: "Styles".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "excessive-important".to_string(),
+            description: "Flags component stylesheets that rely on many !important declarations".to_string(),
+            category: "Styles".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_important".to_string(),
+                    description: "Maximum number of !important declarations per stylesheet".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(3)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "duplicate-component-selector".to_string(),
+            description: "Flags selectors styled in several components' stylesheets".to_string(),
+            category: "Styles".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "empty-component-stylesheet".to_string(),
+            description: "Flags style files referenced by a component that contain no declarations".to_string(),
+            category: "Styles".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "unused-component-class".to_string(),
+            description: "Flags classes styled in a component's stylesheets that its template never uses, with the estimated dead-style bytes".to_string(),
+            category: "Styles".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "zoneless-timer-update".to_string(),
+            description: "Flags components that update fields from setTimeout/setInterval callbacks without signals or markForCheck(), which stops refreshing the view without zone.js".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "zoneless-default-cd-mutation".to_string(),
+            description: "Flags Default change detection components that mutate fields in subscribe/then callbacks and rely on zone.js to refresh the view".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "zoneless-missing-onpush".to_string(),
+            description: "Flags components without zone-dependent updates that still use Default change detection".to_string(),
+            category: "Performance".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "explicit-any".to_string(),
+            description: "Flags files with more any annotations and as any casts than allowed".to_string(),
+            category: "Type Safety".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_any".to_string(),
+                    description: "Maximum number of any annotations and casts per file".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(3)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "non-null-assertion".to_string(),
+            description: "Flags files with more non-null assertions (!) than allowed".to_string(),
+            category: "Type Safety".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_non_null".to_string(),
+                    description: "Maximum number of non-null assertions per file".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(5)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "ts-ignore-comment".to_string(),
+            description: "Flags files with more @ts-ignore comments than allowed".to_string(),
+            category: "Type Safety".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "max_ts_ignore".to_string(),
+                    description: "Maximum number of @ts-ignore comments per file".to_string(),
+                    option_type: "number".to_string(),
+                    default_value: serde_json::Value::Number(serde_json::Number::from(0)),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "strict-mode-disabled".to_string(),
+            description: "Flags projects whose tsconfig does not enable strict mode, following extends".to_string(),
+            category: "Type Safety".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "strict-templates-disabled".to_string(),
+            description: "Flags Angular projects whose tsconfig does not enable strictTemplates in angularCompilerOptions".to_string(),
+            category: "Type Safety".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "skip-lib-check".to_string(),
+            description: "Flags skipLibCheck when the project has its own .d.ts files, which then go unchecked".to_string(),
+            category: "Type Safety".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "outdated-compile-target".to_string(),
+            description: "Flags a compilerOptions.target older than the supported Angular baseline".to_string(),
+            category: "Type Safety".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "min_target".to_string(),
+                    description: "Oldest ES target to accept".to_string(),
+                    option_type: "string".to_string(),
+                    default_value: serde_json::Value::String("ES2022".to_string()),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "environment-secret".to_string(),
+            description: "Flags API keys, tokens and passwords committed in environment*.ts files".to_string(),
+            category: "Security".to_string(),
+            default_severity: "error".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "environment-prod-import".to_string(),
+            description: "Flags app code that imports environment.prod directly instead of relying on fileReplacements".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "warning".to_string(),
+            configurable_options: vec![],
+        },
+        RuleDefinition {
+            name: "environment-direct-access".to_string(),
+            description: "Flags files that read environment values outside an InjectionToken or config service".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "info".to_string(),
+            configurable_options: vec![
+                ConfigurableOption {
+                    name: "allowed_files".to_string(),
+                    description: "Glob patterns of files allowed to read the environment directly".to_string(),
+                    option_type: "array".to_string(),
+                    default_value: serde_json::Value::Array(vec![]),
+                    possible_values: None,
+                },
+            ],
+        },
+        RuleDefinition {
+            name: "layer-boundary-violation".to_string(),
+            description: "Flags imports between the layers configured in boundaries.layers that are not in the importing layer's allow list".to_string(),
+            category: "Architecture".to_string(),
+            default_severity: "error".to_string(),
+            configurable_options: vec![],
+        },
     ]
 }
 
@@ -0,0 +1,102 @@
+use super::{find_component, find_property, literal_value, span_range, FixContext, FixOutput, Fixer, TextEdit};
+use anyhow::Result;
+use swc_common::Spanned;
+
+pub struct InlineTemplateFixer {
+    max_inline_template_length: usize,
+}
+
+impl InlineTemplateFixer {
+    pub fn new() -> Self {
+        Self {
+            max_inline_template_length: 500,
+        }
+    }
+}
+
+impl Fixer for InlineTemplateFixer {
+    fn name(&self) -> &'static str {
+        "inline-template-too-large"
+    }
+
+    fn description(&self) -> &'static str {
+        "Moves large inline templates into a sibling .html file referenced by templateUrl"
+    }
+
+    fn fix(&self, ctx: &FixContext) -> Result<FixOutput> {
+        let mut output = FixOutput::default();
+
+        let component = match find_component(ctx.module) {
+            Some(component) => component,
+            None => return Ok(output),
+        };
+
+        let template_prop = match find_property(component.metadata, "template") {
+            Some(prop) => prop,
+            None => return Ok(output),
+        };
+
+        let template = match literal_value(&template_prop.value) {
+            Some(template) if template.len() > self.max_inline_template_length => template,
+            _ => return Ok(output),
+        };
+
+        let file_stem = match ctx.path.file_stem().and_then(|s| s.to_str()) {
+            Some(stem) => stem,
+            None => return Ok(output),
+        };
+        let template_file_name = format!("{}.html", file_stem);
+        let template_path = ctx.path.with_file_name(&template_file_name);
+
+        if ctx.file_exists(&template_path) {
+            return Ok(output);
+        }
+
+        let (start, end) = span_range(template_prop.span());
+        output.edits.push(TextEdit::replace(
+            start,
+            end,
+            format!("templateUrl: './{}'", template_file_name),
+        ));
+        output.new_files.push((template_path, format!("{}\n", template.trim())));
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::apply_edits;
+    use crate::parsers::typescript::TypeScriptParser;
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    #[test]
+    fn test_inline_template_fix_moves_template_to_file() {
+        // バッククォートと ${ はエスケープを外した形で .html に書く
+        let rows = "<li><code>\\`{{ item.name }}\\`</code> \\${{ item.price }}</li>\n".repeat(20);
+        let content = format!(
+            "import {{ Component }} from '@angular/core';\n\n@Component({{\n  selector: 'app-user',\n  template: `\n<ul>\n{}</ul>\n`,\n}})\nexport class UserComponent {{}}\n",
+            rows
+        );
+        let module = TypeScriptParser::new().parse_file(&content).unwrap();
+        let files = BTreeMap::new();
+        let ctx = FixContext {
+            path: Path::new("src/app/user.component.ts"),
+            content: &content,
+            module: &module,
+            files: &files,
+        };
+
+        let output = InlineTemplateFixer::new().fix(&ctx).unwrap();
+        assert_eq!(
+            apply_edits(&content, &output.edits).unwrap(),
+            "import { Component } from '@angular/core';\n\n@Component({\n  selector: 'app-user',\n  templateUrl: './user.component.html',\n})\nexport class UserComponent {}\n"
+        );
+        assert_eq!(output.new_files.len(), 1);
+        let (path, html) = &output.new_files[0];
+        assert_eq!(path, Path::new("src/app/user.component.html"));
+        assert_eq!(html, &format!("<ul>\n{}</ul>\n", "<li><code>`{{ item.name }}`</code> ${{ item.price }}</li>\n".repeat(20)));
+    }
+}
@@ -0,0 +1,520 @@
+use crate::output::i18n::Lang;
+use crate::parsers::typescript::TypeScriptParser;
+use crate::parsers::walk::{walk_files, WalkOptions};
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use swc_common::Span;
+use swc_ecma_ast::*;
+
+pub mod inline_template;
+pub mod onpush;
+pub mod take_until_destroyed;
+pub mod track_by;
+
+#[derive(Debug, Clone)]
+pub struct TextEdit {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+impl TextEdit {
+    pub fn insert(at: usize, text: impl Into<String>) -> Self {
+        Self {
+            start: at,
+            end: at,
+            text: text.into(),
+        }
+    }
+
+    pub fn replace(start: usize, end: usize, text: impl Into<String>) -> Self {
+        Self {
+            start,
+            end,
+            text: text.into(),
+        }
+    }
+}
+
+// 編集対象外の部分はバイト単位でそのまま保持する
+pub fn apply_edits(content: &str, edits: &[TextEdit]) -> Result<String> {
+    let mut sorted: Vec<&TextEdit> = edits.iter().collect();
+    sorted.sort_by_key(|edit| (edit.start, edit.end));
+
+    let mut output = String::with_capacity(content.len());
+    let mut cursor = 0;
+
+    for edit in sorted {
+        if edit.start < cursor || edit.end > content.len() || edit.start > edit.end {
+            return Err(anyhow::anyhow!(
+                "Overlapping or out-of-range edit at {}..{}",
+                edit.start, edit.end
+            ));
+        }
+        output.push_str(&content[cursor..edit.start]);
+        output.push_str(&edit.text);
+        cursor = edit.end;
+    }
+
+    output.push_str(&content[cursor..]);
+    Ok(output)
+}
+
+pub struct FixContext<'a> {
+    pub path: &'a Path,
+    pub content: &'a str,
+    pub module: &'a Module,
+    files: &'a BTreeMap<PathBuf, FileChange>,
+}
+
+impl<'a> FixContext<'a> {
+    // 既に他の修正で変更されたファイルは変更後の内容を返す
+    pub fn read_file(&self, path: &Path) -> Result<String> {
+        match self.files.get(path) {
+            Some(change) => Ok(change.updated.clone()),
+            None => Ok(fs::read_to_string(path)?),
+        }
+    }
+
+    pub fn file_exists(&self, path: &Path) -> bool {
+        self.files.contains_key(path) || path.exists()
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct FixOutput {
+    pub edits: Vec<TextEdit>,
+    pub file_edits: Vec<(PathBuf, Vec<TextEdit>)>,
+    pub new_files: Vec<(PathBuf, String)>,
+}
+
+impl FixOutput {
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty() && self.file_edits.is_empty() && self.new_files.is_empty()
+    }
+}
+
+pub trait Fixer: Send + Sync {
+    fn name(&self) -> &'static str;
+    #[allow(dead_code)]
+    fn description(&self) -> &'static str;
+    fn fix(&self, ctx: &FixContext) -> Result<FixOutput>;
+}
+
+#[derive(Debug, Clone)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub original: Option<String>,
+    pub updated: String,
+    pub fixes: Vec<&'static str>,
+}
+
+impl FileChange {
+    pub fn is_new_file(&self) -> bool {
+        self.original.is_none()
+    }
+}
+
+pub struct FixEngine {
+    fixers: Vec<Box<dyn Fixer>>,
+    typescript_parser: TypeScriptParser,
+    walk: WalkOptions,
+}
+
+impl FixEngine {
+    pub fn new() -> Self {
+        Self {
+            fixers: vec![
+                Box::new(onpush::OnPushFixer),
+                Box::new(track_by::TrackByFixer),
+                Box::new(inline_template::InlineTemplateFixer::new()),
+                Box::new(take_until_destroyed::TakeUntilDestroyedFixer),
+            ],
+            typescript_parser: TypeScriptParser::new(),
+            walk: WalkOptions::default(),
+        }
+    }
+
+    pub fn with_walk_options(mut self, walk: WalkOptions) -> Self {
+        self.walk = walk;
+        self
+    }
+
+    pub fn with_fixers(names: &[String], lang: Lang) -> Result<Self> {
+        let mut engine = Self::new();
+        for name in names {
+            if !engine.fixers.iter().any(|fixer| fixer.name() == name) {
+                return Err(anyhow::anyhow!("{}", lang.trf("error.unknown_fix", &[name])));
+            }
+        }
+        engine.fixers.retain(|fixer| names.iter().any(|name| name == fixer.name()));
+        Ok(engine)
+    }
+
+    pub fn list_fixers(&self) -> Vec<&'static str> {
+        self.fixers.iter().map(|fixer| fixer.name()).collect()
+    }
+
+    pub fn fix_project(&self, root_path: &Path) -> Result<Vec<FileChange>> {
+        let mut files: BTreeMap<PathBuf, FileChange> = BTreeMap::new();
+
+        for path in walk_files(root_path, &self.walk)? {
+            let path = path.as_path();
+            // foo.component.spec.ts などのテストは対象外
+            if path.file_name().and_then(|name| name.to_str()).is_some_and(|name| name.ends_with(".component.ts")) {
+                self.fix_file(path, &mut files)?;
+            }
+        }
+
+        Ok(files.into_values().filter(|change| change.original.as_ref() != Some(&change.updated)).collect())
+    }
+
+    // 修正ごとに再パースし、常に最新の内容に対するスパンで編集する
+    fn fix_file(&self, path: &Path, files: &mut BTreeMap<PathBuf, FileChange>) -> Result<()> {
+        for fixer in &self.fixers {
+            let content = match files.get(path) {
+                Some(change) => change.updated.clone(),
+                None => fs::read_to_string(path)?,
+            };
+            let module = match self.typescript_parser.parse_file(&content) {
+                Ok(module) => module,
+                Err(_) => return Ok(()),
+            };
+
+            let output = {
+                let ctx = FixContext {
+                    path,
+                    content: &content,
+                    module: &module,
+                    files,
+                };
+                fixer.fix(&ctx)?
+            };
+
+            if output.is_empty() {
+                continue;
+            }
+
+            if !output.edits.is_empty() {
+                let updated = apply_edits(&content, &output.edits)?;
+                record_change(files, path, content, updated, fixer.name());
+            }
+
+            for (file_path, edits) in output.file_edits {
+                let current = match files.get(&file_path) {
+                    Some(change) => change.updated.clone(),
+                    None => fs::read_to_string(&file_path)?,
+                };
+                let updated = apply_edits(&current, &edits)?;
+                record_change(files, &file_path, current, updated, fixer.name());
+            }
+
+            for (file_path, new_content) in output.new_files {
+                files.insert(file_path.clone(), FileChange {
+                    path: file_path,
+                    original: None,
+                    updated: new_content,
+                    fixes: vec![fixer.name()],
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn record_change(
+    files: &mut BTreeMap<PathBuf, FileChange>,
+    path: &Path,
+    current: String,
+    updated: String,
+    fix_name: &'static str,
+) {
+    let change = files.entry(path.to_path_buf()).or_insert_with(|| FileChange {
+        path: path.to_path_buf(),
+        original: Some(current),
+        updated: String::new(),
+        fixes: Vec::new(),
+    });
+    change.updated = updated;
+    if !change.fixes.contains(&fix_name) {
+        change.fixes.push(fix_name);
+    }
+}
+
+pub fn write_changes(changes: &[FileChange]) -> Result<()> {
+    for change in changes {
+        if let Some(parent) = change.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&change.path, &change.updated)?;
+    }
+    Ok(())
+}
+
+pub fn render_diff(change: &FileChange) -> String {
+    let path = change.path.display().to_string().replace('\\', "/");
+    let path = path.trim_start_matches('/');
+    let original = change.original.as_deref().unwrap_or("");
+    let old_header = if change.is_new_file() {
+        "/dev/null".to_string()
+    } else {
+        format!("a/{}", path)
+    };
+
+    similar::TextDiff::from_lines(original, &change.updated)
+        .unified_diff()
+        .context_radius(3)
+        .header(&old_header, &format!("b/{}", path))
+        .to_string()
+}
+
+// --- 修正ロジック間で共有する AST ヘルパー ---
+
+pub fn span_range(span: Span) -> (usize, usize) {
+    (span.lo.0 as usize, span.hi.0 as usize)
+}
+
+pub struct ComponentDecl<'a> {
+    pub class: &'a Class,
+    pub metadata: &'a ObjectLit,
+}
+
+pub fn find_component(module: &Module) -> Option<ComponentDecl<'_>> {
+    for item in &module.body {
+        if let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) = item {
+            if let Decl::Class(class_decl) = &export_decl.decl {
+                for decorator in &class_decl.class.decorators {
+                    if let Expr::Call(call_expr) = &*decorator.expr {
+                        if let Callee::Expr(expr) = &call_expr.callee {
+                            if let Expr::Ident(ident) = &**expr {
+                                if ident.sym.as_ref() == "Component" {
+                                    if let Some(Expr::Object(metadata)) = call_expr.args.first().map(|arg| &*arg.expr) {
+                                        return Some(ComponentDecl {
+                                            class: &class_decl.class,
+                                            metadata,
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+pub fn find_property<'a>(object: &'a ObjectLit, name: &str) -> Option<&'a KeyValueProp> {
+    object.props.iter().find_map(|prop| match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::KeyValue(kv) => match &kv.key {
+                PropName::Ident(ident) if ident.sym.as_ref() == name => Some(kv),
+                PropName::Str(s) if s.value.as_ref() == name => Some(kv),
+                _ => None,
+            },
+            _ => None,
+        },
+        _ => None,
+    })
+}
+
+// オブジェクトリテラルの末尾にプロパティを追加する編集を作成する
+pub fn append_property(content: &str, object: &ObjectLit, property: &str) -> TextEdit {
+    match object.props.last() {
+        Some(last) => {
+            let (_, last_end) = span_range(prop_span(last));
+            let indent = line_indent(content, last_end);
+            TextEdit::insert(last_end, format!(",\n{}{}", indent, property))
+        }
+        None => {
+            let (start, _) = span_range(object.span);
+            TextEdit::insert(start + 1, format!(" {} ", property))
+        }
+    }
+}
+
+fn prop_span(prop: &PropOrSpread) -> Span {
+    use swc_common::Spanned;
+    prop.span()
+}
+
+pub fn line_indent(content: &str, position: usize) -> String {
+    let line_start = content[..position].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    content[line_start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect()
+}
+
+pub fn class_has_member(class: &Class, name: &str) -> bool {
+    class.body.iter().any(|member| {
+        let key = match member {
+            ClassMember::Method(method) => &method.key,
+            ClassMember::ClassProp(prop) => &prop.key,
+            _ => return false,
+        };
+        matches!(key, PropName::Ident(ident) if ident.sym.as_ref() == name)
+    })
+}
+
+// `from` から `names` を import する編集を作成する（既に import 済みのものは除く）
+pub fn ensure_named_imports(module: &Module, from: &str, names: &[&str]) -> Vec<TextEdit> {
+    let mut last_import_end = None;
+
+    for item in &module.body {
+        if let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = item {
+            last_import_end = Some(span_range(import_decl.span).1);
+            if import_decl.src.value.as_ref() != from {
+                continue;
+            }
+
+            let imported: Vec<String> = import_decl.specifiers.iter()
+                .filter_map(|specifier| match specifier {
+                    ImportSpecifier::Named(named) => Some(named.local.sym.to_string()),
+                    _ => None,
+                })
+                .collect();
+            let missing: Vec<&str> = names.iter()
+                .copied()
+                .filter(|name| !imported.iter().any(|i| i == name))
+                .collect();
+            if missing.is_empty() {
+                return Vec::new();
+            }
+
+            if let Some(ImportSpecifier::Named(last)) = import_decl.specifiers.last() {
+                let (_, end) = span_range(last.span);
+                return vec![TextEdit::insert(end, format!(", {}", missing.join(", ")))];
+            }
+        }
+    }
+
+    let statement = format!("import {{ {} }} from '{}';", names.join(", "), from);
+    match last_import_end {
+        Some(end) => vec![TextEdit::insert(end, format!("\n{}", statement))],
+        None => vec![TextEdit::insert(0, format!("{}\n", statement))],
+    }
+}
+
+// 文字列リテラル／テンプレートリテラルの中身（引用符を除いた生のソース範囲）
+pub fn literal_body_range(content: &str, expr: &Expr) -> Option<(usize, usize)> {
+    match expr {
+        Expr::Lit(Lit::Str(str_lit)) => {
+            let (start, end) = span_range(str_lit.span);
+            (end >= start + 2 && end <= content.len()).then_some((start + 1, end - 1))
+        }
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() => {
+            let (start, end) = span_range(tpl.span);
+            (end >= start + 2 && end <= content.len()).then_some((start + 1, end - 1))
+        }
+        _ => None,
+    }
+}
+
+pub fn literal_value(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(str_lit)) => Some(str_lit.value.to_string()),
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() => Some(
+            tpl.quasis.iter()
+                .map(|quasi| quasi.cooked.as_ref().map(|c| c.to_string()).unwrap_or_else(|| quasi.raw.to_string()))
+                .collect(),
+        ),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_edits_preserves_untouched_text() {
+        let content = "const a = 1;\nconst b = 2;\n";
+        let edits = vec![
+            TextEdit::replace(6, 7, "x"),
+            TextEdit::insert(content.len(), "const c = 3;\n"),
+        ];
+
+        let updated = apply_edits(content, &edits).unwrap();
+
+        assert_eq!(updated, "const x = 1;\nconst b = 2;\nconst c = 3;\n");
+    }
+
+    #[test]
+    fn test_onpush_fix_inserts_strategy_and_import() {
+        let content = r#"import { Component } from '@angular/core';
+
+@Component({
+  selector: 'app-user',
+  templateUrl: './user.component.html',
+})
+export class UserComponent {}
+"#;
+        let parser = TypeScriptParser::new();
+        let module = parser.parse_file(content).unwrap();
+        let files = BTreeMap::new();
+        let ctx = FixContext {
+            path: Path::new("user.component.ts"),
+            content,
+            module: &module,
+            files: &files,
+        };
+
+        let output = onpush::OnPushFixer.fix(&ctx).unwrap();
+        let updated = apply_edits(content, &output.edits).unwrap();
+
+        assert!(updated.contains("import { Component, ChangeDetectionStrategy } from '@angular/core';"));
+        assert!(updated.contains("  templateUrl: './user.component.html',\n  changeDetection: ChangeDetectionStrategy.OnPush,\n})"));
+    }
+
+    #[test]
+    fn test_track_by_fix_uses_item_key() {
+        let content = r#"import { Component } from '@angular/core';
+
+interface Order {
+  orderId: string;
+  total: number;
+}
+
+@Component({
+  selector: 'app-orders',
+  template: '<li *ngFor="let order of orders">{{ order.total }}</li>',
+})
+export class OrdersComponent {
+  orders: Order[] = [];
+}
+"#;
+        let parser = TypeScriptParser::new();
+        let module = parser.parse_file(content).unwrap();
+        let files = BTreeMap::new();
+        let ctx = FixContext {
+            path: Path::new("orders.component.ts"),
+            content,
+            module: &module,
+            files: &files,
+        };
+
+        let output = track_by::TrackByFixer.fix(&ctx).unwrap();
+        let updated = apply_edits(content, &output.edits).unwrap();
+
+        assert!(updated.contains(r#"*ngFor="let order of orders; trackBy: trackById""#));
+        assert!(updated.contains("  trackById(index: number, item: Order): unknown {\n    return item.orderId;\n  }\n}"));
+    }
+
+    #[test]
+    fn test_fix_project_skips_spec_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let component = "import { Component } from '@angular/core';\n\n@Component({ selector: 'app-user', template: '' })\nexport class UserComponent {}\n";
+        fs::write(dir.path().join("user.component.ts"), component).unwrap();
+        fs::write(dir.path().join("user.component.spec.ts"), component).unwrap();
+
+        let changes = FixEngine::new().fix_project(dir.path()).unwrap();
+
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].path.ends_with("user.component.ts"));
+    }
+}
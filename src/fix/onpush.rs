@@ -0,0 +1,40 @@
+use super::{append_property, ensure_named_imports, find_component, find_property, FixContext, FixOutput, Fixer};
+use anyhow::Result;
+
+pub struct OnPushFixer;
+
+impl Fixer for OnPushFixer {
+    fn name(&self) -> &'static str {
+        "change-detection-strategy"
+    }
+
+    fn description(&self) -> &'static str {
+        "Adds `changeDetection: ChangeDetectionStrategy.OnPush` to components using the default strategy"
+    }
+
+    fn fix(&self, ctx: &FixContext) -> Result<FixOutput> {
+        let mut output = FixOutput::default();
+
+        let component = match find_component(ctx.module) {
+            Some(component) => component,
+            None => return Ok(output),
+        };
+
+        if find_property(component.metadata, "changeDetection").is_some() {
+            return Ok(output);
+        }
+
+        output.edits.push(append_property(
+            ctx.content,
+            component.metadata,
+            "changeDetection: ChangeDetectionStrategy.OnPush",
+        ));
+        output.edits.extend(ensure_named_imports(
+            ctx.module,
+            "@angular/core",
+            &["ChangeDetectionStrategy"],
+        ));
+
+        Ok(output)
+    }
+}
@@ -0,0 +1,239 @@
+use super::{class_has_member, ensure_named_imports, find_component, span_range, FixContext, FixOutput, Fixer, TextEdit};
+use crate::parsers::typescript::TEARDOWN_OPERATORS;
+use anyhow::Result;
+use swc_common::Spanned;
+use swc_ecma_ast::*;
+use swc_ecma_visit::{Visit, VisitWith};
+
+pub struct TakeUntilDestroyedFixer;
+
+impl Fixer for TakeUntilDestroyedFixer {
+    fn name(&self) -> &'static str {
+        "missing-unsubscribe-pattern"
+    }
+
+    fn description(&self) -> &'static str {
+        "Pipes unmanaged subscriptions through takeUntilDestroyed()"
+    }
+
+    fn fix(&self, ctx: &FixContext) -> Result<FixOutput> {
+        let mut output = FixOutput::default();
+
+        let component = match find_component(ctx.module) {
+            Some(component) => component,
+            None => return Ok(output),
+        };
+
+        // 手動で解除しているクラスは触らない
+        let (class_start, class_end) = span_range(component.class.span);
+        if ctx.content[class_start..class_end].contains(".unsubscribe(") {
+            return Ok(output);
+        }
+
+        let mut needs_destroy_ref = false;
+        for member in &component.class.body {
+            let mut collector = SubscribeCollector::default();
+            let in_injection_context = match member {
+                ClassMember::Constructor(constructor) => {
+                    constructor.visit_with(&mut collector);
+                    true
+                }
+                ClassMember::ClassProp(prop) => {
+                    prop.visit_with(&mut collector);
+                    true
+                }
+                ClassMember::Method(method) => {
+                    method.visit_with(&mut collector);
+                    false
+                }
+                _ => continue,
+            };
+
+            let operator = if in_injection_context {
+                "takeUntilDestroyed()"
+            } else {
+                needs_destroy_ref = true;
+                "takeUntilDestroyed(this.destroyRef)"
+            };
+
+            for site in collector.sites {
+                output.edits.push(site.edit(operator));
+            }
+        }
+
+        if output.edits.is_empty() {
+            return Ok(output);
+        }
+
+        output.edits.extend(ensure_named_imports(
+            ctx.module,
+            "@angular/core/rxjs-interop",
+            &["takeUntilDestroyed"],
+        ));
+
+        if needs_destroy_ref && !class_has_member(component.class, "destroyRef") {
+            let body_start = ctx.content[class_start..class_end]
+                .find('{')
+                .map(|i| class_start + i + 1)
+                .unwrap_or(class_start);
+            output.edits.push(TextEdit::insert(
+                body_start,
+                "\n  private readonly destroyRef = inject(DestroyRef);\n",
+            ));
+            output.edits.extend(ensure_named_imports(
+                ctx.module,
+                "@angular/core",
+                &["inject", "DestroyRef"],
+            ));
+        }
+
+        Ok(output)
+    }
+}
+
+enum SubscribeSite {
+    // `source.subscribe(...)` → `source.pipe(op).subscribe(...)`
+    Bare { source_end: usize },
+    // `source.pipe(a, b).subscribe(...)` → `source.pipe(a, b, op).subscribe(...)`
+    Piped { insert_at: usize, has_args: bool },
+}
+
+impl SubscribeSite {
+    fn edit(&self, operator: &str) -> TextEdit {
+        match self {
+            SubscribeSite::Bare { source_end } => TextEdit::insert(*source_end, format!(".pipe({})", operator)),
+            SubscribeSite::Piped { insert_at, has_args: true } => TextEdit::insert(*insert_at, format!(", {}", operator)),
+            SubscribeSite::Piped { insert_at, has_args: false } => TextEdit::insert(*insert_at, operator.to_string()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct SubscribeCollector {
+    sites: Vec<SubscribeSite>,
+}
+
+impl SubscribeCollector {
+    fn is_teardown_operator(arg: &ExprOrSpread) -> bool {
+        if let Expr::Call(call) = &*arg.expr {
+            if let Callee::Expr(callee) = &call.callee {
+                if let Expr::Ident(ident) = &**callee {
+                    return TEARDOWN_OPERATORS.contains(&ident.sym.as_ref());
+                }
+            }
+        }
+        false
+    }
+}
+
+impl Visit for SubscribeCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        call.visit_children_with(self);
+
+        let member = match &call.callee {
+            Callee::Expr(callee) => match &**callee {
+                Expr::Member(member) => member,
+                _ => return,
+            },
+            _ => return,
+        };
+        if !matches!(&member.prop, MemberProp::Ident(ident) if ident.sym.as_ref() == "subscribe") {
+            return;
+        }
+
+        if let Expr::Call(source_call) = &*member.obj {
+            if let Callee::Expr(source_callee) = &source_call.callee {
+                if let Expr::Member(pipe_member) = &**source_callee {
+                    if matches!(&pipe_member.prop, MemberProp::Ident(ident) if ident.sym.as_ref() == "pipe") {
+                        if source_call.args.iter().any(Self::is_teardown_operator) {
+                            return;
+                        }
+                        let site = match source_call.args.last() {
+                            Some(last) => SubscribeSite::Piped {
+                                insert_at: span_range(last.span()).1,
+                                has_args: true,
+                            },
+                            None => SubscribeSite::Piped {
+                                insert_at: span_range(source_call.span).1 - 1,
+                                has_args: false,
+                            },
+                        };
+                        self.sites.push(site);
+                        return;
+                    }
+                }
+            }
+        }
+
+        self.sites.push(SubscribeSite::Bare {
+            source_end: span_range(member.obj.span()).1,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fix::apply_edits;
+    use crate::parsers::typescript::TypeScriptParser;
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    fn fix(content: &str) -> String {
+        let module = TypeScriptParser::new().parse_file(content).unwrap();
+        let files = BTreeMap::new();
+        let ctx = FixContext {
+            path: Path::new("user.component.ts"),
+            content,
+            module: &module,
+            files: &files,
+        };
+        let output = TakeUntilDestroyedFixer.fix(&ctx).unwrap();
+        apply_edits(content, &output.edits).unwrap()
+    }
+
+    #[test]
+    fn test_take_until_destroyed_fix() {
+        let content = r#"import { Component } from '@angular/core';
+
+@Component({ selector: 'app-user', template: '' })
+export class UserComponent {
+  constructor(private users: UserService) {
+    this.users.current$.subscribe(user => this.user = user);
+  }
+
+  refresh() {
+    this.users.list$.pipe(map(users => users.length)).subscribe(count => this.count = count);
+  }
+}
+"#;
+        assert_eq!(fix(content), r#"import { Component, inject, DestroyRef } from '@angular/core';
+import { takeUntilDestroyed } from '@angular/core/rxjs-interop';
+
+@Component({ selector: 'app-user', template: '' })
+export class UserComponent {
+  private readonly destroyRef = inject(DestroyRef);
+
+  constructor(private users: UserService) {
+    this.users.current$.pipe(takeUntilDestroyed()).subscribe(user => this.user = user);
+  }
+
+  refresh() {
+    this.users.list$.pipe(map(users => users.length), takeUntilDestroyed(this.destroyRef)).subscribe(count => this.count = count);
+  }
+}
+"#);
+
+        // すでに解除している購読は変えない
+        let torn_down = r#"import { Component } from '@angular/core';
+
+@Component({ selector: 'app-user', template: '' })
+export class UserComponent {
+  constructor(private users: UserService) {
+    this.users.current$.pipe(take(1)).subscribe(user => this.user = user);
+  }
+}
+"#;
+        assert_eq!(fix(torn_down), torn_down);
+    }
+}
@@ -0,0 +1,131 @@
+use super::{
+    class_has_member, find_component, find_property, literal_body_range, span_range, FixContext, FixOutput,
+    Fixer, TextEdit,
+};
+use crate::parsers::html::HtmlParser;
+use crate::parsers::typescript::{element_type, identity_member, TypeScriptParser};
+use anyhow::Result;
+use regex::Regex;
+use std::path::Path;
+use swc_ecma_ast::{ImportSpecifier, ModuleDecl, ModuleItem};
+
+pub const TRACK_BY_METHOD: &str = "trackById";
+
+pub struct TrackByFixer;
+
+impl TrackByFixer {
+    // テンプレート内の trackBy のない *ngFor に trackBy を追加する編集を作成する
+    pub fn template_edits(template: &str, offset: usize) -> Vec<TextEdit> {
+        let ng_for = Regex::new(r#"\*ngFor\s*=\s*("([^"]*)"|'([^']*)')"#).unwrap();
+
+        ng_for.captures_iter(template)
+            .filter_map(|cap| {
+                let value = cap.get(2).or_else(|| cap.get(3))?;
+                if value.as_str().contains("trackBy") {
+                    return None;
+                }
+                let insert_at = offset + value.start() + value.as_str().trim_end().len();
+                Some(TextEdit::insert(insert_at, format!("; trackBy: {}", TRACK_BY_METHOD)))
+            })
+            .collect()
+    }
+
+    // trackBy を足す *ngFor がすべて同じ型の配列を繰り返し、その型に id などがあれば (型名, メンバー)
+    fn item_key(ctx: &FixContext, templates: &[String]) -> Option<(String, String)> {
+        let parser = TypeScriptParser::new();
+        let property_types = parser.extract_property_types(ctx.module);
+        let mut item_types: Vec<String> = Vec::new();
+        for template in templates {
+            for found in HtmlParser::new().parse_template(template).loops.iter().filter(|found| found.kind == "ngFor" && found.track.is_none()) {
+                let item_type = found.property()
+                    .and_then(|property| property_types.iter().find(|(name, _)| name == property))
+                    .and_then(|(_, type_text)| element_type(type_text))?;
+                if !item_types.contains(&item_type) {
+                    item_types.push(item_type);
+                }
+            }
+        }
+        let [item_type] = item_types.as_slice() else { return None };
+
+        // 同じファイルか、相対パスで import したファイルで宣言している型
+        let members = parser.extract_type_members(ctx.module, item_type).or_else(|| {
+            let source = ctx.module.body.iter().find_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(import)) if import.specifiers.iter().any(|specifier| {
+                    matches!(specifier, ImportSpecifier::Named(named) if named.local.sym.as_ref() == item_type)
+                }) => Some(import.src.value.to_string()),
+                _ => None,
+            })?;
+            let path = ctx.path.parent()?.join(format!("{}.ts", source.strip_prefix("./").unwrap_or(&source)));
+            let module = parser.parse_file(&ctx.read_file(&path).ok()?).ok()?;
+            parser.extract_type_members(&module, item_type)
+        })?;
+        identity_member(&members).map(|member| (item_type.clone(), member.to_string()))
+    }
+}
+
+impl Fixer for TrackByFixer {
+    fn name(&self) -> &'static str {
+        "missing-trackby"
+    }
+
+    fn description(&self) -> &'static str {
+        "Adds `trackBy` to *ngFor loops and a trackById stub to the component class"
+    }
+
+    fn fix(&self, ctx: &FixContext) -> Result<FixOutput> {
+        let mut output = FixOutput::default();
+        let mut templates = Vec::new();
+
+        let component = match find_component(ctx.module) {
+            Some(component) => component,
+            None => return Ok(output),
+        };
+
+        if let Some(template_prop) = find_property(component.metadata, "template") {
+            if let Some((start, end)) = literal_body_range(ctx.content, &template_prop.value) {
+                output.edits.extend(Self::template_edits(&ctx.content[start..end], start));
+                templates.push(ctx.content[start..end].to_string());
+            }
+        }
+
+        if let Some(template_url_prop) = find_property(component.metadata, "templateUrl") {
+            if let Some(template_url) = super::literal_value(&template_url_prop.value) {
+                let template_path = ctx.path.parent()
+                    .unwrap_or(Path::new(""))
+                    .join(template_url.trim_start_matches("./"));
+                if let Ok(template) = ctx.read_file(&template_path) {
+                    let edits = Self::template_edits(&template, 0);
+                    if !edits.is_empty() {
+                        output.file_edits.push((template_path, edits));
+                    }
+                    templates.push(template);
+                }
+            }
+        }
+
+        if output.is_empty() || class_has_member(component.class, TRACK_BY_METHOD) {
+            return Ok(output);
+        }
+
+        let (_, class_end) = span_range(component.class.span);
+        let closing_brace = class_end - 1;
+        // 要素の型が分かれば、その型の id などを返す
+        let stub = match Self::item_key(ctx, &templates) {
+            Some((item_type, member)) => format!(
+                "\n  {}(index: number, item: {}): unknown {{\n    return item.{};\n  }}\n",
+                TRACK_BY_METHOD, item_type, member
+            ),
+            None => format!(
+                "\n  {}(index: number, item: any): unknown {{\n    return item?.id ?? index;\n  }}\n",
+                TRACK_BY_METHOD
+            ),
+        };
+        let needs_separator = !ctx.content[..closing_brace].trim_end().ends_with('{');
+        output.edits.push(TextEdit::insert(
+            closing_brace,
+            if needs_separator { stub } else { stub.trim_start_matches('\n').to_string() },
+        ));
+
+        Ok(output)
+    }
+}
@@ -2,59 +2,118 @@ mod ast;
 mod analyzers;
 mod cli;
 mod config;
+mod fix;
 mod output;
 mod parsers;
 mod search;
 
-use crate::analyzers::AnalysisEngine;
+use crate::analyzers::{AnalysisEngine, ANALYZER_NAMES};
+use crate::analyzers::budgets::evaluate_budgets;
+use crate::analyzers::bundle_estimate::estimate_bundles;
+use crate::analyzers::health::compute_health;
+use crate::analyzers::impact::analyze_impact;
+use crate::analyzers::library_api::analyze_library_api;
+use crate::analyzers::component_tree::build_component_tree;
+use crate::analyzers::module_graph::build_module_graph;
+use crate::analyzers::injection_graph::{build_injection_graph, is_root_injector};
+use crate::analyzers::package_usage::build_package_usage;
+use crate::analyzers::service_api::build_service_api;
+use crate::analyzers::usages::find_usages;
 use crate::cli::{Cli, Commands, AnalysisConfig};
-use crate::config::Config;
-use crate::output::create_formatter;
-use crate::parsers::ProjectParser;
-use crate::search::{SearchConfig, SimpleSearchEngine, SearchType};
-use crate::analyzers::dependency_graph::DependencyGraphAnalyzer;
+use crate::config::{relative_issue_path, Config};
+use crate::fix::FixEngine;
+use crate::output::{budget_limit, budget_value, truncate_end, truncate_start, CsvFormatter, HtmlFormatter, JsonFormatter, OutputFormatter, TableFormatter};
+use crate::parsers::{encoding, lcov, packages, ProjectParser};
+use crate::parsers::project::{ProjectInputs, MINIFIED_LINE_LENGTH};
+use crate::parsers::walk::{walk_files, WalkOptions};
+use crate::search::simple::highlight;
+use crate::search::scope::{ArtifactKind, SemanticFilter};
+use crate::search::{SearchConfig, SearchEngine};
+use crate::analyzers::dependency_graph::{DependencyGraphAnalyzer, GraphFilter};
+use crate::output::bench::{BenchReport, TimingSummary};
+use crate::output::golden::{check_fixtures, GoldenStatus};
+use crate::output::grouping::{collapse_duplicate_issues, IssueGrouping};
+use crate::output::i18n::Lang;
+use crate::output::history::{append_history, format_trends_text, read_history, render_trends_html, HistoryEntry};
 use crate::output::graph::GraphFormatter;
+use crate::output::issue_limit::limit_issues_per_rule;
+use crate::output::log::{LogFormat, Logger};
+use crate::output::ndjson::NdjsonStream;
+use crate::output::progress::Progress;
+use crate::output::rule_stats::{rule_statistics, RuleStatistic};
+use crate::output::schema::{SchemaKind, SchemaVersion, SCHEMA_VERSION};
+use crate::output::summary::RunSummary;
+use crate::output::top::{SummaryMode, TopOffenders};
 use anyhow::Result;
+use serde_json::json;
+use std::io::IsTerminal;
 use std::path::PathBuf;
-use std::time::Instant;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 use std::fs;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse_args();
+    // ほかのオプションの誤りもこの言語で伝えるので、最初に決める
+    let lang = cli.lang.as_deref().map(Lang::parse).transpose()?.unwrap_or_else(Lang::detect);
+    let schema = cli.schema.as_deref().map(|version| SchemaVersion::parse(version, lang)).transpose()?;
+    let summary_mode = cli.summary.as_deref().map(|mode| SummaryMode::parse(mode, lang)).transpose()?;
+    let group_by = cli.group_by.as_deref().map(|grouping| IssueGrouping::parse(grouping, lang)).transpose()?;
+    let log_format = LogFormat::parse(&cli.log_format, lang)?;
+    let color_enabled = !cli.no_color && std::env::var_os("NO_COLOR").is_none();
+    let color = color_enabled && std::io::stdout().is_terminal();
+    // JSON のログや --quiet のときは、行を書き換える進捗バーを出さない
+    let progress = !cli.quiet && log_format == LogFormat::Text && std::io::stderr().is_terminal();
 
     let start_time = Instant::now();
 
-    match cli.command {
+    // 解析系のコマンドで共通のグローバルオプション。新しいオプションはここに足す
+    let common = AnalysisConfig {
+        timing: cli.timing,
+        rule_stats: cli.rule_stats.then_some(cli.noisy_threshold),
+        max_issues_per_rule: cli.max_issues_per_rule,
+        follow_symlinks: cli.follow_symlinks,
+        schema,
+        history: cli.history.clone(),
+        summary_mode,
+        group_by,
+        log_format,
+        lang,
+        include_project: cli.include_project,
+        progress,
+        verbose: cli.verbose,
+        ..AnalysisConfig::default()
+    };
+
+    let summary = match cli.command {
         Commands::Component {
             path,
             max_complexity,
             depth,
             output,
             errors_only,
+            coverage,
         } => {
-            let config = AnalysisConfig::from_component_args(
+            let mut config = AnalysisConfig::from_component_args(
                 path,
                 max_complexity,
                 depth,
                 output,
                 errors_only,
-                cli.verbose,
-                cli.quiet,
+                common,
             );
-            run_analysis(config).await?;
+            config.coverage = coverage;
+            run_analysis(config).await?
         }
         Commands::Deps { path, format, .. } => {
-            let config = AnalysisConfig::from_deps_args(path, format, cli.verbose, cli.quiet);
-            run_analysis(config).await?;
+            run_analysis(AnalysisConfig::from_deps_args(path, format, common)).await?
         }
         Commands::State { path, format, .. } => {
-            let config = AnalysisConfig::from_state_args(path, format, cli.verbose, cli.quiet);
-            run_analysis(config).await?;
+            run_analysis(AnalysisConfig::from_state_args(path, format, common)).await?
         }
         Commands::Performance { path, format, .. } => {
-            let config = AnalysisConfig::from_performance_args(path, format, cli.verbose, cli.quiet);
-            run_analysis(config).await?;
+            run_analysis(AnalysisConfig::from_performance_args(path, format, common)).await?
         }
         Commands::Audit {
             path,
@@ -64,8 +123,9 @@ async fn main() -> Result<()> {
             output_dir,
             formats,
             severity,
+            coverage,
         } => {
-            let analysis_config = AnalysisConfig::from_audit_args(
+            let mut analysis_config = AnalysisConfig::from_audit_args(
                 path,
                 full,
                 analyzers,
@@ -73,16 +133,38 @@ async fn main() -> Result<()> {
                 output_dir,
                 formats,
                 severity,
-                cli.verbose,
-                cli.quiet,
+                common,
             );
-            run_analysis(analysis_config).await?;
+            analysis_config.coverage = coverage;
+            run_analysis(analysis_config).await?
+        }
+        Commands::Bench {
+            path,
+            iterations,
+            warmup,
+            analyzers,
+            config,
+            format,
+        } => {
+            run_bench(path, iterations, warmup, analyzers, config, format, cli.follow_symlinks, cli.verbose, lang).await?
+        }
+        Commands::Golden { golden, fixtures, update } => {
+            run_golden(fixtures, golden, update, lang, cli.quiet).await?
+        }
+        Commands::Fix { path, dry_run, rules } => {
+            run_fix(path, dry_run, rules, WalkOptions::new(cli.follow_symlinks, Config::discover(None)?.as_ref()), cli.verbose, lang, cli.quiet)?
+        }
+        Commands::Trends { history, format, output, last } => {
+            run_trends(history, format, output, last, lang, cli.quiet)?
+        }
+        Commands::Schema { kind } => {
+            print_schema(&kind, schema, lang)?
         }
         Commands::Init { output, profile } => {
-            initialize_config(output, &profile)?;
+            initialize_config(output, &profile, lang)?
         }
         Commands::List { details, category } => {
-            list_analyzers(details, category)?;
+            list_analyzers(details, category, lang)?
         }
         Commands::Search {
             path,
@@ -98,11 +180,14 @@ async fn main() -> Result<()> {
             html_class: _,
             html_text: _,
             function_name: _,
-            structural: _,
+            structural,
+            within,
+            selector,
+            decorator,
         } => {
-            let search_config = SearchConfig::new(
+            let mut search_config = SearchConfig::new(
                 path,
-                keyword,
+                keyword.unwrap_or_default(),
                 Some(file_type),
                 file_pattern,
                 case_sensitive,
@@ -111,70 +196,188 @@ async fn main() -> Result<()> {
                 output,
                 cli.verbose,
             );
+            search_config.follow_symlinks = cli.follow_symlinks;
+            search_config.ignore = Config::discover(None)?.map(|config| config.ignore).unwrap_or_default();
+            search_config.structural = structural;
+            search_config.lang = lang;
+            search_config.filter = SemanticFilter {
+                kinds: within.iter().map(|kind| ArtifactKind::parse(kind.trim(), lang)).collect::<Result<_>>()?,
+                selector,
+                decorator,
+            };
             
             // TODO: 検索タイプの処理は後で実装
             // 今は基本的な検索のみ実装
-            run_search(search_config).await?;
+            run_search(search_config, color, lang).await?
+        }
+        Commands::Impact { target, path, format, output } => {
+            run_impact(target, path, format, output, WalkOptions::new(cli.follow_symlinks, Config::discover(None)?.as_ref()), lang, cli.quiet).await?
+        }
+        Commands::Usages { symbol, path, format, output } => {
+            run_usages(symbol, path, format, output, WalkOptions::new(cli.follow_symlinks, Config::discover(None)?.as_ref()), lang, cli.quiet).await?
+        }
+        Commands::Library { path, format, output } => {
+            run_library(path, format, output, WalkOptions::new(cli.follow_symlinks, Config::discover(None)?.as_ref()), lang, cli.quiet).await?
         }
         Commands::Graph {
             path,
             format,
+            kind,
             output,
             circular,
+            max_cycles,
             orphaned,
             depth,
+            unused_exports,
+            ignored_exports,
             top_count,
             extensions,
             exclude_external,
+            min_fan_in,
+            exclude_patterns,
+            hide_leaf_nodes,
+            focus,
+            fan_in,
+            fan_out,
         } => {
+            let filter = GraphFilter {
+                min_fan_in,
+                exclude_patterns,
+                hide_leaf_nodes,
+                extensions: extensions.unwrap_or_default(),
+                focus,
+                fan_in,
+                fan_out,
+            };
             run_graph_analysis(
                 path,
                 format,
+                kind,
                 output,
                 circular,
+                max_cycles,
                 orphaned,
                 depth,
+                unused_exports,
+                ignored_exports,
                 top_count,
-                extensions,
                 exclude_external,
+                filter,
+                schema,
+                WalkOptions::new(cli.follow_symlinks, Config::discover(None)?.as_ref()),
                 cli.verbose,
+                lang,
                 cli.quiet,
-            ).await?;
+            ).await?
         }
-    }
+    };
 
+    // json・ndjson・csv を標準出力に書いても壊れないよう、サマリーは標準エラーに出す
     if !cli.quiet {
-        let duration = start_time.elapsed();
-        println!("Analysis completed in {:.2}s", duration.as_secs_f64());
+        eprintln!("{}", summary.format_line(start_time.elapsed(), color_enabled && std::io::stderr().is_terminal()));
+    }
+
+    // CI で失敗させるため、予算を超えたら終了コードを 1 にする
+    if summary.failed_budgets > 0 {
+        std::process::exit(1);
     }
 
     Ok(())
 }
 
-async fn run_analysis(config: AnalysisConfig) -> Result<()> {
-    if config.verbose {
-        println!("🔍 Starting Angular project analysis...");
-        println!("📁 Analyzing path: {}", config.path.display());
+async fn run_analysis(config: AnalysisConfig) -> Result<RunSummary> {
+    let lang = config.lang;
+    if config.max_issues_per_rule == Some(0) {
+        return Err(anyhow::anyhow!("{}", lang.trf("error.at_least_one", &[&"--max-issues-per-rule"])));
     }
 
-    let parser = ProjectParser::new();
-    let project = parser.parse_project(&config.path).await?;
+    let logger = Logger::new(config.log_format, config.verbose);
+    logger.info(
+        "analysis_started",
+        &lang.trf("analysis.started", &[&config.path.display()]),
+        json!({ "path": config.path, "analyzers": config.analyzers }),
+    );
 
-    if config.verbose {
-        println!(
-            "📊 Found {} components, {} services, {} modules",
-            project.components.len(),
-            project.services.len(),
-            project.modules.len()
+    let engine_config = Config::discover(config.config_file.as_deref())?;
+    let budgets_config = engine_config.as_ref().map(|c| c.budgets.clone()).unwrap_or_default();
+    let health_weights = engine_config.as_ref().map(|c| c.health.clone()).unwrap_or_default();
+
+    let parse_start = Instant::now();
+    let mut inputs = analyzers::required_inputs(&config.analyzers, engine_config.as_ref());
+    // 最大の循環を出すため、--summary top では import グラフを必ず組み立てる
+    inputs.import_graph |= config.summary_mode == Some(SummaryMode::Top);
+    let progress = Arc::new(Progress::new(config.progress).with_lang(lang));
+    let parser = ProjectParser::new()
+        .with_inputs(inputs)
+        .with_walk_options(WalkOptions::new(config.follow_symlinks, engine_config.as_ref()))
+        .with_progress(progress.clone());
+    let mut project = parser.parse_project(&config.path).await?;
+
+    // 出力を壊さないように警告は標準エラーに出す
+    for warning in &project.encoding_warnings {
+        logger.warn(
+            "encoding_fallback",
+            &lang.trf("analysis.encoding_fallback", &[&warning.file_path, &encoding_name(&warning.encoding, lang)]),
+            json!({ "file": warning.file_path, "encoding": warning.encoding }),
         );
     }
+    for file in project.files.iter().filter(|file| file.minified) {
+        logger.warn(
+            "minified_file",
+            &lang.trf("analysis.minified", &[&file.file_path, &MINIFIED_LINE_LENGTH]),
+            json!({ "file": file.file_path }),
+        );
+    }
+
+    if let Some(coverage_path) = &config.coverage {
+        let records = lcov::read_lcov(coverage_path)?;
+        lcov::apply_coverage(&mut project, &records);
+        logger.info(
+            "coverage_loaded",
+            &lang.trf("analysis.coverage_loaded", &[&records.len(), &coverage_path.display()]),
+            json!({ "files": records.len(), "path": coverage_path }),
+        );
+    }
+
+    logger.info(
+        "project_parsed",
+        &lang.trf("analysis.project_parsed", &[&project.components.len(), &project.services.len(), &project.modules.len()]),
+        json!({
+            "files": project.files.len(),
+            "components": project.components.len(),
+            "services": project.services.len(),
+            "modules": project.modules.len(),
+        }),
+    );
 
-    let engine = AnalysisEngine::new();
-    let results = engine.run_analysis(&project, &config.analyzers).await?;
+    let parse_time = parse_start.elapsed();
+    logger.phase("parse", parse_time, json!({ "files": project.files.len() }));
+    let project = Arc::new(project);
+
+    let analysis_start = Instant::now();
+    let engine = AnalysisEngine::new().with_lang(lang).with_config(engine_config).with_progress(progress);
+    // ndjson はアナライザーが終わるたびに標準出力へ書き出す。--summary top では issue の代わりに表を出す
+    let mut results = if matches!(config.output_format, crate::cli::args::OutputFormat::Ndjson) && config.summary_mode.is_none() {
+        let (sender, stream) = NdjsonStream::spawn(std::io::stdout(), config.max_issues_per_rule);
+        let results = engine.stream_analysis(&project, &config.analyzers, sender);
+        if let Err(error) = stream.finish() {
+            // head などが途中で読むのをやめたら、残りは書かずに終える
+            if error.downcast_ref::<std::io::Error>().map(|error| error.kind()) != Some(std::io::ErrorKind::BrokenPipe) {
+                return Err(error);
+            }
+        }
+        results?
+    } else {
+        engine.run_analysis(&project, &config.analyzers)?
+    };
+    let collapsed_issues = collapse_duplicate_issues(&mut results);
+    let analysis_time = analysis_start.elapsed();
+    logger.phase("analysis", analysis_time, json!({ "analyzers": config.analyzers }));
+    let output_start = Instant::now();
 
     if results.is_empty() {
-        println!("⚠️  No analysis results generated");
-        return Ok(());
+        logger.warn("no_results", lang.tr("analysis.no_results"), json!({}));
+        return Ok(RunSummary::files(project.files.len()));
     }
 
     let total_issues: usize = results.iter().map(|r| r.issues.len()).sum();
@@ -187,105 +390,462 @@ async fn run_analysis(config: AnalysisConfig) -> Result<()> {
                 .count()
         })
         .sum();
+    let error_count = results
+        .iter()
+        .flat_map(|r| &r.issues)
+        .filter(|issue| matches!(issue.severity, ast::Severity::Error))
+        .count();
+    let warning_count = results
+        .iter()
+        .flat_map(|r| &r.issues)
+        .filter(|issue| matches!(issue.severity, ast::Severity::Warning))
+        .count();
+    let budgets = evaluate_budgets(&project, &budgets_config)?;
+    // 件数の集計は上限で削る前に済ませておく
+    let summary = RunSummary {
+        failed_budgets: budgets.iter().filter(|budget| !budget.passed).count(),
+        ..RunSummary::from_results(&results, project.files.len())
+    };
+    if let Some(history) = &config.history {
+        append_history(history, &HistoryEntry::new(&project, &summary, &config.analyzers, SystemTime::now()))?;
+        logger.info("history_appended", &lang.trf("analysis.history_appended", &[&history.display()]), json!({ "path": history }));
+    }
+    let health = compute_health(&project, &summary, &health_weights)?;
+    let top_offenders = match config.summary_mode {
+        Some(SummaryMode::Top) => Some(TopOffenders::collect(&project, &results)?),
+        None => None,
+    };
+    let statistics = config.rule_stats.map(|noisy_threshold| (rule_statistics(&results), noisy_threshold));
 
-    match config.output_format {
-        crate::cli::args::OutputFormat::Json => {
-            let formatter = create_formatter("json")?;
-            let output = formatter.format(&results)?;
-            println!("{}", output);
-        }
-        crate::cli::args::OutputFormat::Table => {
-            let formatter = create_formatter("table")?;
-            let output = formatter.format(&results)?;
-            println!("{}", output);
-        }
-        crate::cli::args::OutputFormat::Html => {
-            let formatter = create_formatter("html")?;
-            let output = formatter.format(&results)?;
-            if let Some(output_dir) = &config.output_dir {
-                std::fs::create_dir_all(output_dir)?;
-                let output_file = output_dir.join("analysis-report.html");
-                std::fs::write(&output_file, output)?;
-                if config.verbose {
-                    println!("📄 HTML report generated: {}", output_file.display());
-                }
-            } else {
+    if let Some(max_issues) = config.max_issues_per_rule {
+        limit_issues_per_rule(&mut results, max_issues);
+    }
+
+    if let Some(top_offenders) = &top_offenders {
+        // JSON 以外はどの形式でもテキストの表で出す
+        match config.output_format {
+            crate::cli::args::OutputFormat::Json => println!("{}", top_offenders.format_json()?),
+            _ => print!("{}", top_offenders.format_table(lang)),
+        }
+    } else {
+        match config.output_format {
+            crate::cli::args::OutputFormat::Json => {
+                let formatter = JsonFormatter::new().with_schema(config.schema).with_include_project(config.include_project).with_health(Some(health.clone())).with_budgets(budgets.clone());
+                let output = formatter.format(&results)?;
                 println!("{}", output);
             }
+            crate::cli::args::OutputFormat::Table => {
+                let formatter = TableFormatter::new().with_lang(lang).with_health(Some(health.clone())).with_budgets(budgets.clone()).with_group_by(config.group_by);
+                let output = formatter.format(&results)?;
+                println!("{}", output);
+            }
+            crate::cli::args::OutputFormat::Html => {
+                let formatter = HtmlFormatter::new().with_lang(lang).with_health(Some(health.clone())).with_budgets(budgets.clone()).with_group_by(config.group_by);
+                let output = formatter.format(&results)?;
+                if let Some(output_dir) = &config.output_dir {
+                    std::fs::create_dir_all(output_dir)?;
+                    let output_file = output_dir.join("analysis-report.html");
+                    std::fs::write(&output_file, output)?;
+                    logger.info("report_written", &lang.trf("analysis.html_written", &[&output_file.display()]), json!({ "path": output_file }));
+                } else {
+                    println!("{}", output);
+                }
+            }
+            crate::cli::args::OutputFormat::Csv => {
+                let formatter = CsvFormatter::new().with_budgets(budgets.clone());
+                if let Some(output_dir) = &config.output_dir {
+                    std::fs::create_dir_all(output_dir)?;
+                    let output_file = output_dir.join("analysis-report.csv");
+                    formatter.write_to_file(&results, &output_file)?;
+                    logger.info("report_written", &lang.trf("analysis.csv_written", &[&output_file.display()]), json!({ "path": output_file }));
+                    let metrics_file = CsvFormatter::metrics_path(&output_file);
+                    logger.info("report_written", &lang.trf("analysis.csv_metrics_written", &[&metrics_file.display()]), json!({ "path": metrics_file }));
+                    if !budgets.is_empty() {
+                        let budgets_file = CsvFormatter::budgets_path(&output_file);
+                        logger.info("report_written", &lang.trf("analysis.csv_budgets_written", &[&budgets_file.display()]), json!({ "path": budgets_file }));
+                    }
+                } else {
+                    println!("{}", formatter.format(&results)?);
+                }
+            }
+            // 解析中に書き出し済み
+            crate::cli::args::OutputFormat::Ndjson => {}
         }
     }
 
-    if config.verbose {
-        println!("\n📈 Analysis Summary:");
-        println!("   Total issues found: {}", total_issues);
-        println!("   Issues shown: {}", filtered_issues);
+    logger.phase("output", output_start.elapsed(), json!({}));
 
-        if filtered_issues > 0 {
-            let error_count = results
-                .iter()
-                .flat_map(|r| &r.issues)
-                .filter(|issue| matches!(issue.severity, ast::Severity::Error))
-                .count();
-            let warning_count = results
-                .iter()
-                .flat_map(|r| &r.issues)
-                .filter(|issue| matches!(issue.severity, ast::Severity::Warning))
-                .count();
+    let omitted_issues: usize = results.iter().flat_map(|r| &r.omitted_issues).map(|omitted| omitted.count).sum();
+    let recommendation_count: usize = results.iter().map(|r| r.recommendations.len()).sum();
+    let mut lines = vec![
+        lang.tr("analysis.summary").to_string(),
+        lang.trf("analysis.total_issues", &[&total_issues]),
+        lang.trf("analysis.issues_shown", &[&filtered_issues]),
+    ];
+    if collapsed_issues > 0 {
+        lines.push(lang.trf("analysis.collapsed", &[&collapsed_issues]));
+    }
+    lines.push(lang.trf("analysis.health", &[&format!("{:.0}", health.score), &health.grade]));
+    if omitted_issues > 0 {
+        lines.push(lang.trf("analysis.omitted", &[&omitted_issues]));
+    }
+    if error_count > 0 {
+        lines.push(lang.trf("analysis.errors", &[&error_count]));
+    }
+    if warning_count > 0 {
+        lines.push(lang.trf("analysis.warnings", &[&warning_count]));
+    }
+    if recommendation_count > 0 {
+        lines.push(lang.trf("analysis.recommendations", &[&recommendation_count]));
+    }
+    logger.info(
+        "analysis_finished",
+        &lines.join("\n"),
+        json!({
+            "issues": total_issues,
+            "errors": error_count,
+            "warnings": warning_count,
+            "collapsed_issues": collapsed_issues,
+            "omitted_issues": omitted_issues,
+            "recommendations": recommendation_count,
+            "health_score": health.score,
+            "health_grade": health.grade,
+        }),
+    );
+
+    // どの出力形式でも CI のログで分かるように、超えた予算は標準エラーにも出す
+    for budget in budgets.iter().filter(|budget| !budget.passed) {
+        logger.warn(
+            "budget_exceeded",
+            &lang.trf(
+                "analysis.budget_exceeded",
+                &[
+                    &budget.budget,
+                    &budget_value(budget, budget.actual),
+                    &budget_limit(budget),
+                    &budget.worst.as_deref().map(|worst| lang.trf("analysis.budget_worst", &[&worst])).unwrap_or_default(),
+                ],
+            ),
+            json!({ "budget": budget.budget, "limit": budget.limit, "actual": budget.actual, "worst": budget.worst }),
+        );
+    }
+
+    if config.timing {
+        print_timing(&parser, &project, parse_time, analysis_time, output_start.elapsed(), lang);
+    }
 
-            if error_count > 0 {
-                println!("   ❌ Errors: {}", error_count);
+    if let Some((statistics, noisy_threshold)) = statistics {
+        print_rule_stats(&statistics, noisy_threshold, lang);
+    }
+
+    Ok(summary)
+}
+
+fn encoding_name(encoding: &str, lang: Lang) -> String {
+    if encoding == encoding::LOSSY_UTF8 {
+        lang.tr("analysis.encoding_lossy").to_string()
+    } else {
+        encoding.to_string()
+    }
+}
+
+// JSON などの出力を壊さないよう標準エラーに出す
+fn print_timing(parser: &ProjectParser, project: &ast::NgProject, parse: Duration, analysis: Duration, output: Duration, lang: Lang) {
+    let inputs = parser.inputs();
+    let loaded = parser.loaded_inputs(project);
+    let skipped = |loaded: bool, count: usize| {
+        if loaded {
+            count.to_string()
+        } else {
+            lang.tr("timing.skipped").to_string()
+        }
+    };
+    let seconds = |duration: Duration| format!("{:>8.3}", duration.as_secs_f64());
+
+    eprintln!("{}", lang.tr("timing.title"));
+    eprintln!("{}", lang.trf("timing.parse", &[&seconds(parse)]));
+    eprintln!("{}", lang.trf("timing.analysis", &[&seconds(analysis)]));
+    eprintln!("{}", lang.trf("timing.output", &[&seconds(output)]));
+    eprintln!("{}", lang.tr("timing.inputs"));
+    eprintln!("{}", lang.trf("timing.typescript", &[&loaded.typescript_files]));
+    eprintln!("{}", lang.trf("timing.templates", &[&skipped(inputs.templates, loaded.templates)]));
+    eprintln!("{}", lang.trf("timing.resource_files", &[&skipped(inputs.resource_files, loaded.resource_files)]));
+    eprintln!("{}", lang.trf("timing.stylesheets", &[&skipped(inputs.stylesheets, loaded.stylesheets)]));
+    eprintln!("{}", lang.trf("timing.import_graph", &[&skipped(inputs.import_graph, loaded.import_graph_files)]));
+}
+
+// --timing と同じく標準エラーに出す
+fn print_rule_stats(statistics: &[RuleStatistic], noisy_threshold: f64, lang: Lang) {
+    if statistics.is_empty() {
+        return;
+    }
+
+    eprintln!("{}", lang.tr("rule_stats.title"));
+    eprintln!(
+        "   {:<40} {:>7} {:>7} {:>9} {:>7}",
+        lang.tr("rule_stats.rule"),
+        lang.tr("rule_stats.issues"),
+        lang.tr("rule_stats.files"),
+        lang.tr("rule_stats.per_file"),
+        lang.tr("rule_stats.share")
+    );
+    for statistic in statistics {
+        eprintln!(
+            "   {:<40} {:>7} {:>7} {:>9.1} {:>6.1}%",
+            statistic.rule,
+            statistic.issues,
+            statistic.files,
+            statistic.per_file(),
+            statistic.share
+        );
+    }
+
+    let noisy: Vec<_> = statistics.iter().filter(|statistic| statistic.share > noisy_threshold).collect();
+    if !noisy.is_empty() {
+        eprintln!("{}", lang.trf("rule_stats.noisy", &[&noisy_threshold]));
+        for statistic in noisy {
+            eprintln!("{}", lang.trf("rule_stats.noisy_rule", &[&statistic.rule, &format!("{:.1}", statistic.share)]));
+        }
+    }
+}
+
+// 計測中は解析結果を出力せず、フェーズとアナライザーごとの所要時間だけを集計する
+#[allow(clippy::too_many_arguments)]
+async fn run_bench(
+    path: PathBuf,
+    iterations: u32,
+    warmup: u32,
+    analyzers: Option<Vec<String>>,
+    config_file: Option<PathBuf>,
+    format: String,
+    follow_symlinks: bool,
+    verbose: bool,
+    lang: Lang,
+) -> Result<RunSummary> {
+    if iterations == 0 {
+        return Err(anyhow::anyhow!("{}", lang.trf("error.at_least_one", &[&"--iterations"])));
+    }
+    if format != "table" && format != "json" {
+        return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"bench", &format, &"table, json"])));
+    }
+
+    let analyzer_names = analyzers.unwrap_or_else(|| ANALYZER_NAMES.iter().map(|name| name.to_string()).collect());
+    let engine_config = Config::discover(config_file.as_deref())?;
+    let inputs = analyzers::required_inputs(&analyzer_names, engine_config.as_ref());
+    let walk = WalkOptions::new(follow_symlinks, engine_config.as_ref());
+    let engine = AnalysisEngine::new().with_lang(lang).with_config(engine_config);
+
+    let mut parse_samples = Vec::new();
+    let mut analysis_samples = Vec::new();
+    let mut analyzer_samples: Vec<Vec<Duration>> = vec![Vec::new(); analyzer_names.len()];
+    let mut files = 0;
+    for run in 0..warmup + iterations {
+        let measured = run >= warmup;
+
+        let parse_start = Instant::now();
+        let project = Arc::new(ProjectParser::new().with_inputs(inputs).with_walk_options(walk.clone()).parse_project(&path).await?);
+        let parse_time = parse_start.elapsed();
+        files = project.files.len();
+
+        // アナライザーごとの時間を測るため、1 つずつ順に実行する
+        let mut analysis_time = Duration::ZERO;
+        for (i, name) in analyzer_names.iter().enumerate() {
+            let analyzer_start = Instant::now();
+            engine.run_analysis(&project, std::slice::from_ref(name))?;
+            let elapsed = analyzer_start.elapsed();
+            analysis_time += elapsed;
+            if measured {
+                analyzer_samples[i].push(elapsed);
             }
-            if warning_count > 0 {
-                println!("   ⚠️  Warnings: {}", warning_count);
+        }
+
+        if verbose {
+            eprintln!(
+                "   {} {}: parse {:.2} ms, analysis {:.2} ms",
+                if measured { "run" } else { "warm-up" },
+                if measured { run - warmup + 1 } else { run + 1 },
+                parse_time.as_secs_f64() * 1000.0,
+                analysis_time.as_secs_f64() * 1000.0
+            );
+        }
+        if measured {
+            parse_samples.push(parse_time);
+            analysis_samples.push(analysis_time);
+        }
+    }
+
+    let total_samples: Vec<Duration> = parse_samples.iter().zip(&analysis_samples).map(|(parse, analysis)| *parse + *analysis).collect();
+    let report = BenchReport {
+        schema_version: SCHEMA_VERSION,
+        path,
+        iterations,
+        warmup,
+        files,
+        phases: vec![
+            TimingSummary::from_samples("parse", &parse_samples),
+            TimingSummary::from_samples("analysis", &analysis_samples),
+            TimingSummary::from_samples("total", &total_samples),
+        ],
+        analyzers: analyzer_names.iter().zip(&analyzer_samples)
+            .map(|(name, samples)| TimingSummary::from_samples(name, samples))
+            .collect(),
+    };
+
+    match format.as_str() {
+        "json" => println!("{}", report.format_json()?),
+        _ => println!("{}", report.format_table(lang)),
+    }
+
+    Ok(RunSummary::files(files))
+}
+
+// fixture ごとの結果を出し、一致しないものがあれば diff を出して失敗させる
+async fn run_golden(fixtures: PathBuf, golden: PathBuf, update: bool, lang: Lang, quiet: bool) -> Result<RunSummary> {
+    let outcomes = check_fixtures(&fixtures, &golden, update, lang).await?;
+
+    let mut failed = Vec::new();
+    for outcome in &outcomes {
+        match &outcome.status {
+            GoldenStatus::Matched => {
+                if !quiet {
+                    println!("✅ {}", outcome.fixture);
+                }
+            }
+            GoldenStatus::Updated => println!("{}", lang.trf("golden.updated", &[&outcome.fixture])),
+            GoldenStatus::Mismatched(diff) => {
+                println!("❌ {}", outcome.fixture);
+                println!("{}", diff);
+                failed.push(outcome.fixture.as_str());
             }
+            GoldenStatus::Missing => {
+                println!("{}", lang.trf("golden.missing", &[&outcome.fixture]));
+                failed.push(outcome.fixture.as_str());
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(anyhow::anyhow!("{}", lang.trf("golden.failed", &[&failed.join(", ")])));
+    }
+    Ok(RunSummary::files(outcomes.len()))
+}
+
+fn run_fix(
+    path: PathBuf,
+    dry_run: bool,
+    rules: Option<Vec<String>>,
+    walk: WalkOptions,
+    verbose: bool,
+    lang: Lang,
+    quiet: bool,
+) -> Result<RunSummary> {
+    let engine = match rules {
+        Some(rules) => FixEngine::with_fixers(&rules, lang)?,
+        None => FixEngine::new(),
+    }
+    .with_walk_options(walk);
+
+    if verbose {
+        println!("{}", lang.trf("fix.applying", &[&engine.list_fixers().join(", "), &path.display()]));
+    }
+
+    let changes = engine.fix_project(&path)?;
+
+    if changes.is_empty() {
+        if !quiet {
+            println!("{}", lang.tr("fix.nothing"));
         }
+        return Ok(RunSummary::files(changes.len()));
+    }
 
-        let recommendation_count: usize = results.iter().map(|r| r.recommendations.len()).sum();
-        if recommendation_count > 0 {
-            println!("   💡 Recommendations: {}", recommendation_count);
+    if dry_run {
+        for change in &changes {
+            print!("{}", fix::render_diff(change));
         }
+    } else {
+        fix::write_changes(&changes)?;
     }
 
-    Ok(())
+    if !quiet {
+        for change in &changes {
+            println!(
+                "   {} {} ({})",
+                if change.is_new_file() { "+" } else { "~" },
+                change.path.display(),
+                change.fixes.join(", ")
+            );
+        }
+        println!("{}", lang.trf(if dry_run { "fix.would_update" } else { "fix.updated" }, &[&changes.len()]));
+    }
+
+    Ok(RunSummary::files(changes.len()))
 }
 
-fn initialize_config(output_path: PathBuf, profile: &str) -> Result<()> {
+fn run_trends(history: PathBuf, format: String, output: Option<PathBuf>, last: Option<usize>, lang: Lang, quiet: bool) -> Result<RunSummary> {
+    let mut entries = read_history(&history, lang)?;
+    if let Some(last) = last {
+        entries.drain(..entries.len().saturating_sub(last));
+    }
+
+    let output_content = match format.as_str() {
+        "text" => format_trends_text(&entries, lang),
+        "html" => render_trends_html(&entries, lang),
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"trends", &format, &"text, html"]))),
+    };
+
+    if let Some(output_path) = output {
+        fs::write(&output_path, &output_content)?;
+        if !quiet {
+            println!("{}", lang.trf("trends.written", &[&output_path.display()]));
+        }
+    } else {
+        print!("{}", output_content);
+    }
+
+    Ok(RunSummary::default())
+}
+
+// --schema を指定しなければ最新の版を出す
+fn print_schema(kind: &str, schema: Option<SchemaVersion>, lang: Lang) -> Result<RunSummary> {
+    let kind = SchemaKind::parse(kind, lang)?;
+    let document = schema.unwrap_or(SchemaVersion::V1).document(kind);
+    println!("{}", serde_json::to_string_pretty(&document)?);
+    Ok(RunSummary::default())
+}
+
+fn initialize_config(output_path: PathBuf, profile: &str, lang: Lang) -> Result<RunSummary> {
     if output_path.exists() {
-        println!("⚠️  Configuration file already exists at: {}", output_path.display());
-        println!("   Use --force to overwrite (not implemented yet)");
-        return Ok(());
+        println!("{}", lang.trf("init.exists", &[&output_path.display()]));
+        return Ok(RunSummary::default());
     }
 
     Config::create_default_config_file(&output_path, profile)?;
 
-    println!("✅ Configuration file created: {}", output_path.display());
-    println!("   Profile: {}", profile);
-    println!("   You can now customize the rules and settings in this file.");
+    println!("{}", lang.trf("init.created", &[&output_path.display(), &profile]));
 
-    Ok(())
+    Ok(RunSummary::default())
 }
 
-fn list_analyzers(details: bool, category: Option<String>) -> Result<()> {
+fn list_analyzers(details: bool, category: Option<String>, lang: Lang) -> Result<RunSummary> {
     use crate::config::rules::{get_all_rule_definitions, get_available_categories, get_rules_by_category};
 
     if let Some(cat) = category {
         let rules = get_rules_by_category(&cat);
         if rules.is_empty() {
-            println!("❌ No rules found for category: {}", cat);
-            return Ok(());
+            println!("{}", lang.trf("list.no_rules", &[&cat]));
+            return Ok(RunSummary::default());
         }
 
-        println!("📋 Rules in category '{}':", cat);
+        println!("{}", lang.trf("list.category_rules", &[&cat]));
         for rule in rules {
             println!("   • {}", rule.name);
             if details {
-                println!("     Description: {}", rule.description);
-                println!("     Default severity: {}", rule.default_severity);
+                println!("{}", lang.trf("list.description", &[&rule.description]));
+                println!("{}", lang.trf("list.default_severity", &[&rule.default_severity]));
                 if !rule.configurable_options.is_empty() {
-                    println!("     Configurable options:");
+                    println!("{}", lang.tr("list.options"));
                     for option in &rule.configurable_options {
-                        println!("       - {}: {} (default: {})", 
-                            option.name, option.description, option.default_value);
+                        println!("{}", lang.trf("list.option", &[&option.name, &option.description, &option.default_value]));
                     }
                 }
                 println!();
@@ -293,157 +853,661 @@ fn list_analyzers(details: bool, category: Option<String>) -> Result<()> {
         }
     } else {
         let categories = get_available_categories();
-        println!("📋 Available categories:");
+        println!("{}", lang.tr("list.categories"));
         for category in &categories {
             println!("   • {}", category);
         }
 
         if details {
-            println!("\n📋 Available analyzers:");
-            println!("   • component - Analyzes Angular components for best practices and performance");
-            println!("   • dependency - Analyzes dependency relationships and circular dependencies");
-            println!("   • state - Analyzes state management patterns and reactive programming");
-            println!("   • performance - Analyzes performance implications and optimization opportunities");
+            println!("{}", lang.tr("list.analyzers"));
+            for name in ANALYZER_NAMES {
+                println!("   • {} - {}", name, lang.tr(&format!("list.analyzer.{}", name)));
+            }
 
-            println!("\n📋 All available rules:");
+            println!("{}", lang.tr("list.rules"));
             let rules = get_all_rule_definitions();
             for rule in rules {
                 println!("   • {} ({})", rule.name, rule.category);
                 println!("     {}", rule.description);
-                println!("     Default severity: {}", rule.default_severity);
+                println!("{}", lang.trf("list.default_severity", &[&rule.default_severity]));
                 println!();
             }
         }
     }
 
-    Ok(())
+    Ok(RunSummary::default())
 }
 
-async fn run_search(config: SearchConfig) -> Result<()> {
-    let _engine = SimpleSearchEngine::new(
-        config.keyword.clone(),
-        config.case_sensitive,
-        config.line_numbers,
-        config.context,
-    );
-    
-    // TODO: この部分は後で実装する必要があります
-    // 今は仮の実装として空のベクトルを返します
-    let results: Vec<crate::search::simple::SearchResult> = Vec::new();
+async fn run_search(config: SearchConfig, color: bool, lang: Lang) -> Result<RunSummary> {
+    let search_start = Instant::now();
+    let (results, files_searched) = SearchEngine::new(config.clone()).search().await?;
+    let elapsed = search_start.elapsed().as_secs_f64();
+    if config.verbose {
+        println!("{}", lang.trf("search.searched", &[
+            &files_searched,
+            &format!("{:.2}", elapsed),
+            &format!("{:.0}", files_searched as f64 / elapsed.max(f64::EPSILON)),
+        ]));
+    }
+    let results: Vec<crate::search::simple::SearchResult> = results.into_iter()
+        .map(|result| crate::search::simple::SearchResult {
+            file_path: result.file_path.display().to_string(),
+            matches: result.matches,
+        })
+        .collect();
     
     if results.is_empty() {
         if config.verbose {
-            println!("⚠️  No matches found");
+            println!("{}", lang.tr("search.no_matches"));
         }
-        return Ok(());
+        return Ok(RunSummary::files(results.len()));
     }
     
     if config.verbose {
         let total_matches: usize = results.iter().map(|r| r.total_matches()).sum();
-        println!("🔍 Found {} matches in {} files", total_matches, results.len());
+        println!("{}", lang.trf("search.found", &[&total_matches, &results.len()]));
     }
     
     match config.output_format.as_str() {
         "json" => {
-            let json_output = serde_json::to_string_pretty(&results)?;
+            let json_output = serde_json::to_string_pretty(&serde_json::json!({
+                "schema_version": SCHEMA_VERSION,
+                "results": results,
+            }))?;
             println!("{}", json_output);
         }
         "table" => {
-            print_table_format(&results, &config);
+            print_table_format(&results, &config, color, lang);
+            print_match_summary(&results, lang);
         }
         _ => {
-            print_simple_format(&results, &config);
+            print_simple_format(&results, &config, color, lang);
+            print_match_summary(&results, lang);
         }
     }
     
-    Ok(())
+    Ok(RunSummary::files(results.len()))
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn run_graph_analysis(
     path: PathBuf,
     format: String,
+    kind: String,
     output: Option<PathBuf>,
-    _circular: bool,
-    _orphaned: bool,
-    _depth: bool,
-    _top_count: u32,
-    _extensions: Option<Vec<String>>,
-    _exclude_external: bool,
+    circular: bool,
+    max_cycles: usize,
+    orphaned: bool,
+    depth: bool,
+    unused_exports: bool,
+    ignored_exports: Vec<String>,
+    top_count: u32,
+    exclude_external: bool,
+    filter: GraphFilter,
+    schema: Option<SchemaVersion>,
+    walk: WalkOptions,
     _verbose: bool,
+    lang: Lang,
     quiet: bool,
-) -> Result<()> {
+) -> Result<RunSummary> {
+    // 公開しているグラフのスキーマは --kind files の JSON だけ
+    if schema.is_some() && kind != "files" && format == "json" {
+        return Err(anyhow::anyhow!("{}", lang.tr("error.schema_graph_only")));
+    }
+
+    match kind.as_str() {
+        "files" => {}
+        "components" => return run_component_tree(path, format, output, walk, lang, quiet).await,
+        "modules" => return run_module_graph(path, format, output, walk, lang, quiet).await,
+        "injection" => return run_injection_graph(path, format, output, walk, lang, quiet).await,
+        "packages" => return run_package_usage(path, format, output, top_count as usize, walk, lang, quiet).await,
+        "bundles" => return run_bundle_estimate(path, format, output, top_count as usize, walk, lang, quiet).await,
+        "services" => return run_service_api(path, format, output, walk, lang, quiet).await,
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported", &[&"--kind", &kind, &"files, components, modules, injection, packages, bundles, services"]))),
+    }
+
     if !quiet {
-        println!("🔍 TypeScript依存関係グラフ分析を開始しています...");
-        println!("📁 分析対象パス: {}", path.display());
+        println!("{}", lang.tr("graph.files.started"));
+        println!("{}", lang.trf("graph.path", &[&path.display()]));
     }
 
-    let analyzer = DependencyGraphAnalyzer::new();
-    let graph = analyzer.analyze_project(&path).await?;
+    let analyzer = DependencyGraphAnalyzer::new()
+        .with_walk_options(walk)
+        .with_lang(lang)
+        .with_max_cycles(max_cycles)
+        .with_analyses(circular, orphaned, depth, unused_exports)
+        .with_ignored_exports(ignored_exports)
+        .with_top_count(top_count as usize);
+    let mut graph = analyzer.analyze_project(&path).await?;
+    if !exclude_external {
+        graph = analyzer.add_external_packages(graph);
+    }
 
     if !quiet {
+        println!("{}", lang.trf("graph.files.found", &[&graph.files.len(), &graph.dependencies.len()]));
+    }
+
+    let (file_count, dependency_count) = (graph.files.len(), graph.dependencies.len());
+    let graph = analyzer.filter_graph(graph, &filter)?;
+    if !quiet && !filter.is_empty() {
         println!(
-            "📊 {}個のファイルと{}個の依存関係を発見しました",
-            graph.files.len(),
-            graph.dependencies.len()
+            "{}",
+            lang.trf("graph.files.filtered", &[&file_count, &graph.files.len(), &dependency_count, &graph.dependencies.len()])
         );
     }
 
     let analysis = analyzer.analyze_dependencies(&graph)?;
     
     if !quiet {
-        println!("🔍 依存関係分析を実行しています...");
+        println!("{}", lang.tr("graph.files.analyzing"));
         
         if !analysis.circular_dependencies.is_empty() {
-            println!("⚠️  {}個の循環依存を発見しました", analysis.circular_dependencies.len());
+            println!("{}", lang.trf("graph.files.cycles_found", &[&analysis.circular_dependencies.len()]));
+            if analysis.circular_dependencies.len() >= max_cycles {
+                println!("{}", lang.trf("graph.files.cycles_limited", &[&max_cycles]));
+            }
         }
         
         if !analysis.orphaned_files.is_empty() {
-            println!("🔍 {}個の孤立ファイルを発見しました", analysis.orphaned_files.len());
+            println!("{}", lang.trf("graph.files.orphans_found", &[&analysis.orphaned_files.len()]));
+        }
+
+        if !analysis.unused_exports.is_empty() {
+            println!("{}", lang.trf("graph.files.unused_found", &[&analysis.unused_exports.len()]));
         }
     }
 
-    let formatter = GraphFormatter::new();
+    let formatter = GraphFormatter::new().with_lang(lang).with_top_count(top_count as usize).with_schema(schema);
     let output_content = match format.as_str() {
         "dot" => formatter.format_dot(&graph, &analysis)?,
+        "graphml" => formatter.format_graphml(&graph, &analysis)?,
+        "svg" => formatter.format_svg(&graph, &analysis)?,
+        "html" => formatter.format_html(&graph, &analysis)?,
         "mermaid" => formatter.format_mermaid(&graph, &analysis)?,
         "json" => formatter.format_json(&graph, &analysis)?,
         "table" => formatter.format_table(&graph, &analysis)?,
-        _ => return Err(anyhow::anyhow!("サポートされていない出力形式: {}", format)),
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"graph --kind files", &format, &"dot, graphml, svg, html, mermaid, json, table"]))),
     };
 
     if let Some(output_path) = output {
         fs::write(&output_path, &output_content)?;
         if !quiet {
-            println!("📄 グラフが出力されました: {}", output_path.display());
+            println!("{}", lang.trf("graph.written", &[&output_path.display()]));
         }
     } else {
         println!("{}", output_content);
     }
 
     if !quiet {
-        println!("\n📈 分析サマリー:");
-        println!("   総ファイル数: {}", graph.files.len());
-        println!("   総依存関係数: {}", graph.dependencies.len());
-        println!("   循環依存数: {}", analysis.circular_dependencies.len());
-        println!("   孤立ファイル数: {}", analysis.orphaned_files.len());
+        println!("{}", lang.tr("graph.summary"));
+        println!("{}", lang.trf("graph.files.total_files", &[&graph.files.len()]));
+        println!("{}", lang.trf("graph.files.total_dependencies", &[&graph.dependencies.len()]));
+        // --circular などで分析を絞った場合は、行った分析の結果だけを表示する
+        let all = !(circular || orphaned || depth || unused_exports);
+        if all || circular {
+            println!("{}", lang.trf("graph.files.cycles", &[&analysis.circular_dependencies.len()]));
+        }
+        if all || orphaned {
+            println!("{}", lang.trf("graph.files.orphans", &[&analysis.orphaned_files.len()]));
+        }
+        if all || unused_exports {
+            println!("{}", lang.trf("graph.files.unused", &[&analysis.unused_exports.len()]));
+        }
+        if all || depth {
+            if let Some(chain) = analysis.longest_chains.first() {
+                println!("{}", lang.trf("graph.files.longest_chain", &[&chain.depth]));
+            }
+        }
         
         if !analysis.most_imported_files.is_empty() {
-            println!("   最もインポートされているファイル:");
+            println!("{}", lang.tr("graph.files.most_imported"));
             for (file_path, count) in analysis.most_imported_files.iter().take(3) {
-                println!("     - {} ({}回)", file_path, count);
+                println!("{}", lang.trf("graph.files.imported_count", &[&file_path, &count]));
             }
         }
     }
 
-    Ok(())
+    Ok(RunSummary::files(graph.files.len()))
 }
 
-fn print_simple_format(results: &[crate::search::simple::SearchResult], config: &SearchConfig) {
+// テンプレートで使われているセレクタからコンポーネントツリーを組み立てて出力する
+async fn run_component_tree(path: PathBuf, format: String, output: Option<PathBuf>, walk: WalkOptions, lang: Lang, quiet: bool) -> Result<RunSummary> {
+    if !quiet {
+        println!("{}", lang.tr("graph.tree.started"));
+        println!("{}", lang.trf("graph.path", &[&path.display()]));
+    }
+
+    let parser = ProjectParser::new().with_inputs(ProjectInputs {
+        templates: true,
+        resource_files: false,
+        stylesheets: false,
+        import_graph: false,
+    }).with_walk_options(walk);
+    let project = parser.parse_project(&path).await?;
+    let tree = build_component_tree(&project);
+
+    let formatter = GraphFormatter::new().with_lang(lang);
+    let output_content = match format.as_str() {
+        "dot" => formatter.format_component_tree_dot(&tree)?,
+        "mermaid" => formatter.format_component_tree_mermaid(&tree)?,
+        "json" => formatter.format_component_tree_json(&tree)?,
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"graph --kind components", &format, &"dot, mermaid, json"]))),
+    };
+
+    if let Some(output_path) = output {
+        fs::write(&output_path, &output_content)?;
+        if !quiet {
+            println!("{}", lang.trf("graph.written", &[&output_path.display()]));
+        }
+    } else {
+        println!("{}", output_content);
+    }
+
+    if !quiet {
+        println!("{}", lang.tr("graph.summary"));
+        println!("{}", lang.trf("graph.tree.components", &[&tree.nodes.len()]));
+        println!("{}", lang.trf("graph.tree.edges", &[&tree.edges.len()]));
+        if let Some(deepest) = tree.nodes.iter().max_by_key(|node| node.depth).filter(|node| node.depth > 0) {
+            println!("{}", lang.trf("graph.tree.deepest", &[&deepest.depth, &tree.deepest_path(&deepest.name).join(" > ")]));
+        }
+    }
+
+    Ok(RunSummary::files(project.files.len()))
+}
+
+async fn run_module_graph(path: PathBuf, format: String, output: Option<PathBuf>, walk: WalkOptions, lang: Lang, quiet: bool) -> Result<RunSummary> {
+    if !quiet {
+        println!("{}", lang.tr("graph.modules.started"));
+        println!("{}", lang.trf("graph.path", &[&path.display()]));
+    }
+
+    let parser = ProjectParser::new().with_inputs(ProjectInputs {
+        templates: false,
+        resource_files: false,
+        stylesheets: false,
+        import_graph: false,
+    }).with_walk_options(walk);
+    let project = parser.parse_project(&path).await?;
+    let graph = build_module_graph(&project);
+
+    let formatter = GraphFormatter::new().with_lang(lang);
+    let output_content = match format.as_str() {
+        "dot" => formatter.format_module_graph_dot(&graph)?,
+        "mermaid" => formatter.format_module_graph_mermaid(&graph)?,
+        "json" => formatter.format_module_graph_json(&graph)?,
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"graph --kind modules", &format, &"dot, mermaid, json"]))),
+    };
+
+    if let Some(output_path) = output {
+        fs::write(&output_path, &output_content)?;
+        if !quiet {
+            println!("{}", lang.trf("graph.written", &[&output_path.display()]));
+        }
+    } else {
+        println!("{}", output_content);
+    }
+
+    if !quiet {
+        println!("{}", lang.tr("graph.summary"));
+        println!("{}", lang.trf("graph.modules.modules", &[&graph.nodes.len()]));
+        println!("{}", lang.trf("graph.modules.imports", &[&graph.edges.iter().filter(|edge| edge.kind == "imports").count()]));
+        println!("{}", lang.trf("graph.modules.lazy", &[&graph.edges.iter().filter(|edge| edge.kind == "lazy").count()]));
+        if !graph.cycles.is_empty() {
+            println!("{}", lang.tr("graph.modules.cycles"));
+            for cycle in &graph.cycles {
+                println!("   {} -> {}", cycle.join(" -> "), cycle[0]);
+            }
+        }
+    }
+
+    Ok(RunSummary::files(project.files.len()))
+}
+
+async fn run_injection_graph(path: PathBuf, format: String, output: Option<PathBuf>, walk: WalkOptions, lang: Lang, quiet: bool) -> Result<RunSummary> {
+    if !quiet {
+        println!("{}", lang.tr("graph.injection.started"));
+        println!("{}", lang.trf("graph.path", &[&path.display()]));
+    }
+
+    let parser = ProjectParser::new().with_inputs(ProjectInputs {
+        templates: false,
+        resource_files: false,
+        stylesheets: false,
+        import_graph: false,
+    }).with_walk_options(walk);
+    let project = parser.parse_project(&path).await?;
+    let graph = build_injection_graph(&project);
+
+    let formatter = GraphFormatter::new().with_lang(lang);
+    let output_content = match format.as_str() {
+        "dot" => formatter.format_injection_graph_dot(&graph)?,
+        "mermaid" => formatter.format_injection_graph_mermaid(&graph)?,
+        "json" => formatter.format_injection_graph_json(&graph)?,
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"graph --kind injection", &format, &"dot, mermaid, json"]))),
+    };
+
+    if let Some(output_path) = output {
+        fs::write(&output_path, &output_content)?;
+        if !quiet {
+            println!("{}", lang.trf("graph.written", &[&output_path.display()]));
+        }
+    } else {
+        println!("{}", output_content);
+    }
+
+    if !quiet {
+        println!("{}", lang.tr("graph.summary"));
+        println!("{}", lang.trf("graph.injection.services", &[&graph.nodes.iter().filter(|node| node.kind == "service").count()]));
+        println!("{}", lang.trf("graph.injection.edges", &[&graph.edges.len()]));
+
+        let multi_provided: Vec<_> = graph.multi_provided().collect();
+        if !multi_provided.is_empty() {
+            println!("{}", lang.tr("graph.injection.multiple"));
+            for node in multi_provided {
+                println!("   {}: {}", node.name, node.injectors.join(", "));
+            }
+        }
+
+        let single_feature: Vec<_> = graph.nodes.iter().filter(|node| node.single_feature.is_some()).collect();
+        if !single_feature.is_empty() {
+            println!("{}", lang.tr("graph.injection.single_feature"));
+            for node in single_feature {
+                println!("   {}: {}", node.name, node.single_feature.as_deref().unwrap_or_default());
+            }
+        }
+
+        if !graph.cycles.is_empty() {
+            println!("{}", lang.tr("graph.injection.cycles"));
+            for cycle in &graph.cycles {
+                println!("   {} -> {}", cycle.join(" -> "), cycle[0]);
+            }
+        }
+
+        if !graph.provider_overrides.is_empty() {
+            println!("{}", lang.tr("graph.injection.overrides"));
+            for provider_override in &graph.provider_overrides {
+                println!(
+                    "   {}: {} ({}{}, {}:{}){}",
+                    provider_override.token,
+                    provider_override.implementation.as_deref().unwrap_or("?"),
+                    provider_override.injector,
+                    if provider_override.multi { ", multi" } else { "" },
+                    relative_issue_path(&provider_override.file_path, &project.root_path),
+                    provider_override.line.unwrap_or(0),
+                    if is_root_injector(&project, provider_override) { "" } else { lang.tr("graph.injection.non_root") }
+                );
+            }
+        }
+    }
+
+    Ok(RunSummary::files(project.files.len()))
+}
+
+async fn run_service_api(path: PathBuf, format: String, output: Option<PathBuf>, walk: WalkOptions, lang: Lang, quiet: bool) -> Result<RunSummary> {
+    if !quiet {
+        println!("{}", lang.tr("graph.service_api.started"));
+        println!("{}", lang.trf("graph.path", &[&path.display()]));
+    }
+
+    let parser = ProjectParser::new().with_inputs(ProjectInputs {
+        templates: false,
+        resource_files: false,
+        stylesheets: false,
+        import_graph: false,
+    }).with_walk_options(walk);
+    let project = parser.parse_project(&path).await?;
+    let report = build_service_api(&project);
+
+    let formatter = GraphFormatter::new().with_lang(lang);
+    let output_content = match format.as_str() {
+        "table" => formatter.format_service_api_table(&report)?,
+        "json" => formatter.format_service_api_json(&report)?,
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"graph --kind services", &format, &"table, json"]))),
+    };
+
+    if let Some(output_path) = output {
+        fs::write(&output_path, &output_content)?;
+        if !quiet {
+            println!("{}", lang.trf("graph.written", &[&output_path.display()]));
+        }
+    } else {
+        println!("{}", output_content);
+    }
+
+    if !quiet {
+        println!("{}", lang.tr("graph.summary"));
+        println!("{}", lang.trf("graph.injection.services", &[&report.services.len()]));
+        println!("{}", lang.trf("graph.service_api.methods", &[&report.total_methods()]));
+        let unused: Vec<String> = report.unused().map(|(service, method)| format!("{}.{}", service.name, method.name)).collect();
+        if !unused.is_empty() {
+            println!("{}", lang.tr("graph.service_api.unused"));
+            println!("   {}", unused.join(", "));
+        }
+    }
+
+    Ok(RunSummary::files(project.files.len()))
+}
+
+async fn run_impact(target: String, path: PathBuf, format: String, output: Option<PathBuf>, walk: WalkOptions, lang: Lang, quiet: bool) -> Result<RunSummary> {
+    if !quiet {
+        println!("{}", lang.trf("impact.started", &[&target]));
+        println!("{}", lang.trf("graph.path", &[&path.display()]));
+    }
+
+    let parser = ProjectParser::new().with_inputs(ProjectInputs {
+        templates: false,
+        resource_files: false,
+        stylesheets: false,
+        import_graph: true,
+    }).with_walk_options(walk);
+    let project = parser.parse_project(&path).await?;
+    let report = match &project.import_graph {
+        Some(graph) => analyze_impact(&project, graph, &target, lang)?,
+        None => return Err(anyhow::anyhow!("{}", lang.trf("error.import_graph", &[&path.display()]))),
+    };
+
+    let formatter = GraphFormatter::new().with_lang(lang);
+    let output_content = match format.as_str() {
+        "table" => formatter.format_impact_table(&report)?,
+        "json" => formatter.format_impact_json(&report)?,
+        "mermaid" => formatter.format_impact_mermaid(&report)?,
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"impact", &format, &"table, json, mermaid"]))),
+    };
+
+    if let Some(output_path) = output {
+        fs::write(&output_path, &output_content)?;
+        if !quiet {
+            println!("{}", lang.trf("impact.written", &[&output_path.display()]));
+        }
+    } else {
+        println!("{}", output_content);
+    }
+
+    if !quiet {
+        println!("{}", lang.tr("graph.summary"));
+        println!("{}", lang.trf("impact.affected", &[&report.affected.len()]));
+        println!("{}", lang.trf("impact.tests", &[&report.tests.len()]));
+        println!("{}", lang.trf("impact.chunks", &[&report.chunks.len()]));
+        println!("{}", lang.trf("impact.risk", &[&report.risk]));
+    }
+
+    Ok(RunSummary::files(project.files.len()))
+}
+
+async fn run_usages(symbol: String, path: PathBuf, format: String, output: Option<PathBuf>, walk: WalkOptions, lang: Lang, quiet: bool) -> Result<RunSummary> {
+    if !quiet {
+        println!("{}", lang.trf("usages.started", &[&symbol]));
+        println!("{}", lang.trf("graph.path", &[&path.display()]));
+    }
+
+    let parser = ProjectParser::new().with_inputs(ProjectInputs {
+        templates: true,
+        resource_files: false,
+        stylesheets: false,
+        import_graph: true,
+    }).with_walk_options(walk);
+    let project = parser.parse_project(&path).await?;
+    let report = find_usages(&project, &symbol);
+
+    let formatter = GraphFormatter::new().with_lang(lang);
+    let output_content = match format.as_str() {
+        "table" => formatter.format_usages_table(&report)?,
+        "json" => formatter.format_usages_json(&report)?,
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"usages", &format, &"table, json"]))),
+    };
+
+    if let Some(output_path) = output {
+        fs::write(&output_path, &output_content)?;
+        if !quiet {
+            println!("{}", lang.trf("usages.written", &[&output_path.display()]));
+        }
+    } else {
+        println!("{}", output_content);
+    }
+
+    if !quiet {
+        println!("{}", lang.tr("graph.summary"));
+        println!("{}", lang.trf("usages.total", &[&report.usages.len()]));
+        if report.targets.is_empty() {
+            println!("{}", lang.trf("usages.not_found", &[&report.symbol]));
+        }
+    }
+
+    Ok(RunSummary::files(project.files.len()))
+}
+
+async fn run_library(path: PathBuf, format: String, output: Option<PathBuf>, walk: WalkOptions, lang: Lang, quiet: bool) -> Result<RunSummary> {
+    if !quiet {
+        println!("{}", lang.tr("library.started"));
+        println!("{}", lang.trf("graph.path", &[&path.display()]));
+    }
+
+    let ng_packages: Vec<PathBuf> = walk_files(&path, &walk)?.into_iter()
+        .filter(|file| file.file_name().is_some_and(|name| name == "ng-package.json"))
+        .collect();
+    let graph = DependencyGraphAnalyzer::new().with_walk_options(walk).analyze_project(&path).await?;
+    let report = analyze_library_api(&path, &graph, &ng_packages);
+
+    let formatter = GraphFormatter::new().with_lang(lang);
+    let output_content = match format.as_str() {
+        "table" => formatter.format_library_api_table(&report)?,
+        "json" => formatter.format_library_api_json(&report)?,
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"library", &format, &"table, json"]))),
+    };
+
+    if let Some(output_path) = output {
+        fs::write(&output_path, &output_content)?;
+        if !quiet {
+            println!("{}", lang.trf("library.written", &[&output_path.display()]));
+        }
+    } else {
+        println!("{}", output_content);
+    }
+
+    if !quiet {
+        println!("{}", lang.tr("graph.summary"));
+        let deep_imports: usize = report.libraries.iter().map(|library| library.deep_imports.len()).sum();
+        let private_imports: usize = report.libraries.iter().map(|library| library.private_imports.len()).sum();
+        println!("{}", lang.trf("library.total", &[&report.libraries.len(), &deep_imports, &private_imports]));
+        if ng_packages.is_empty() {
+            println!("{}", lang.tr("library.not_found"));
+        }
+    }
+
+    Ok(RunSummary::files(graph.files.len()))
+}
+
+async fn run_package_usage(path: PathBuf, format: String, output: Option<PathBuf>, top_count: usize, walk: WalkOptions, lang: Lang, quiet: bool) -> Result<RunSummary> {
+    if !quiet {
+        println!("{}", lang.tr("graph.packages.started"));
+        println!("{}", lang.trf("graph.path", &[&path.display()]));
+    }
+
+    let graph = DependencyGraphAnalyzer::new().with_walk_options(walk).analyze_project(&path).await?;
+    let workspace = packages::load_workspace(&path);
+    let report = build_package_usage(&graph, workspace.as_ref());
+
+    let formatter = GraphFormatter::new().with_lang(lang).with_top_count(top_count);
+    let output_content = match format.as_str() {
+        "table" => formatter.format_package_usage_table(&report)?,
+        "mermaid" => formatter.format_package_usage_mermaid(&report)?,
+        "json" => formatter.format_package_usage_json(&report)?,
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"graph --kind packages", &format, &"table, mermaid, json"]))),
+    };
+
+    if let Some(output_path) = output {
+        fs::write(&output_path, &output_content)?;
+        if !quiet {
+            println!("{}", lang.trf("graph.written", &[&output_path.display()]));
+        }
+    } else {
+        println!("{}", output_content);
+    }
+
+    if !quiet {
+        println!("{}", lang.tr("graph.summary"));
+        println!("{}", lang.trf("graph.packages.packages", &[&report.packages.len()]));
+        let candidates: Vec<&str> = report.removal_candidates().map(|package| package.name.as_str()).collect();
+        if !candidates.is_empty() {
+            println!("{}", lang.tr("graph.packages.single_file"));
+            println!("   {}", candidates.join(", "));
+        }
+    }
+
+    Ok(RunSummary::files(report.total_files))
+}
+
+async fn run_bundle_estimate(path: PathBuf, format: String, output: Option<PathBuf>, top_count: usize, walk: WalkOptions, lang: Lang, quiet: bool) -> Result<RunSummary> {
+    if !quiet {
+        println!("{}", lang.tr("graph.bundles.started"));
+        println!("{}", lang.trf("graph.path", &[&path.display()]));
+    }
+
+    let parser = ProjectParser::new().with_inputs(ProjectInputs {
+        templates: false,
+        resource_files: false,
+        stylesheets: false,
+        import_graph: true,
+    }).with_walk_options(walk);
+    let project = parser.parse_project(&path).await?;
+    let estimate = project.import_graph.as_ref()
+        .map(|graph| estimate_bundles(&project, graph))
+        .unwrap_or_default();
+
+    let formatter = GraphFormatter::new().with_lang(lang).with_top_count(top_count);
+    let output_content = match format.as_str() {
+        "table" => formatter.format_bundle_estimate_table(&estimate)?,
+        "mermaid" => formatter.format_bundle_estimate_mermaid(&estimate)?,
+        "json" => formatter.format_bundle_estimate_json(&estimate)?,
+        _ => return Err(anyhow::anyhow!("{}", lang.trf("error.unsupported_format", &[&"graph --kind bundles", &format, &"table, mermaid, json"]))),
+    };
+
+    if let Some(output_path) = output {
+        fs::write(&output_path, &output_content)?;
+        if !quiet {
+            println!("{}", lang.trf("graph.written", &[&output_path.display()]));
+        }
+    } else {
+        println!("{}", output_content);
+    }
+
+    if !quiet {
+        println!("{}", lang.tr("graph.summary"));
+        println!("{}", lang.trf("graph.bundles.routes", &[&estimate.routes().count()]));
+        if !estimate.shared.is_empty() {
+            println!("{}", lang.trf("graph.bundles.shared", &[&estimate.shared.len()]));
+        }
+    }
+
+    Ok(RunSummary::files(project.files.len()))
+}
+
+fn print_simple_format(results: &[crate::search::simple::SearchResult], config: &SearchConfig, color: bool, lang: Lang) {
     for result in results {
         println!("\n📄 {}", result.file_path);
-        println!("   {} matches found", result.total_matches());
+        println!("{}", lang.trf("search.file_matches", &[&result.total_matches()]));
         
-        for search_match in &result.matches {
+        for (search_match, ranges) in result.matched_lines() {
             if config.line_numbers {
                 println!("   {}:", search_match.line_number);
             }
@@ -454,7 +1518,10 @@ fn print_simple_format(results: &[crate::search::simple::SearchResult], config:
             }
             
             // Print the matching line
-            println!("   → {}", search_match.line_content);
+            println!("   → {}", highlight(&search_match.line_content, &ranges, color));
+            for (name, source) in &search_match.bindings {
+                println!("     ${} = {}", name, source);
+            }
             
             // Print context after
             for context_line in &search_match.context_after {
@@ -464,17 +1531,13 @@ fn print_simple_format(results: &[crate::search::simple::SearchResult], config:
     }
 }
 
-fn print_table_format(results: &[crate::search::simple::SearchResult], config: &SearchConfig) {
-    println!("{:<40} {:<6} {:<80}", "File", "Line", "Content");
+fn print_table_format(results: &[crate::search::simple::SearchResult], config: &SearchConfig, color: bool, lang: Lang) {
+    println!("{:<40} {:<6} {:<80}", lang.tr("search.file"), lang.tr("search.line"), lang.tr("search.content"));
     println!("{}", "-".repeat(126));
     
     for result in results {
-        for search_match in &result.matches {
-            let file = if result.file_path.len() > 35 {
-                format!("...{}", &result.file_path[result.file_path.len()-32..])
-            } else {
-                result.file_path.clone()
-            };
+        for (search_match, ranges) in result.matched_lines() {
+            let file = truncate_start(&result.file_path, 35);
             
             let line = if config.line_numbers {
                 search_match.line_number.to_string()
@@ -482,14 +1545,23 @@ fn print_table_format(results: &[crate::search::simple::SearchResult], config: &
                 "-".to_string()
             };
             
-            let content = if search_match.line_content.len() > 75 {
-                format!("{}...", &search_match.line_content[..72])
-            } else {
-                search_match.line_content.clone()
-            };
+            // 省略した部分にかかる範囲は強調しない
+            let content = truncate_end(&search_match.line_content, 75);
+            let kept = if content.len() < search_match.line_content.len() { content.len() - 3 } else { content.len() };
+            let ranges: Vec<(usize, usize)> = ranges.into_iter().filter(|&(_, end)| end <= kept).collect();
             
             println!("{:<40} {:<6} {:<80}", 
-                     file, line, content);
+                     file, line, highlight(&content, &ranges, color));
         }
     }
-}
\ No newline at end of file
+}
+
+// ファイルごとのマッチ数と合計
+fn print_match_summary(results: &[crate::search::simple::SearchResult], lang: Lang) {
+    println!("{}", lang.tr("search.per_file"));
+    for result in results {
+        println!("   {:>5}  {}", result.total_matches(), result.file_path);
+    }
+    let total_matches: usize = results.iter().map(|result| result.total_matches()).sum();
+    println!("{}", lang.trf("search.total", &[&total_matches, &results.len()]));
+}
@@ -0,0 +1,107 @@
+use crate::output::i18n::Lang;
+use anyhow::Result;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::time::Duration;
+use tabled::{Table, Tabled};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TimingSummary {
+    pub name: String,
+    pub runs: usize,
+    pub mean_ms: f64,
+    pub stddev_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+}
+
+impl TimingSummary {
+    // 標準偏差は標本標準偏差 (n - 1 で割る)
+    pub fn from_samples(name: &str, samples: &[Duration]) -> Self {
+        let millis: Vec<f64> = samples.iter().map(|sample| sample.as_secs_f64() * 1000.0).collect();
+        let runs = millis.len();
+        let mean = if runs == 0 { 0.0 } else { millis.iter().sum::<f64>() / runs as f64 };
+        let stddev = if runs < 2 {
+            0.0
+        } else {
+            (millis.iter().map(|ms| (ms - mean).powi(2)).sum::<f64>() / (runs - 1) as f64).sqrt()
+        };
+
+        Self {
+            name: name.to_string(),
+            runs,
+            mean_ms: mean,
+            stddev_ms: stddev,
+            min_ms: if runs == 0 { 0.0 } else { millis.iter().cloned().fold(f64::INFINITY, f64::min) },
+            max_ms: millis.iter().cloned().fold(0.0, f64::max),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchReport {
+    pub schema_version: u32,
+    pub path: PathBuf,
+    pub iterations: u32,
+    pub warmup: u32,
+    pub files: usize,
+    pub phases: Vec<TimingSummary>,
+    pub analyzers: Vec<TimingSummary>,
+}
+
+#[derive(Tabled)]
+struct TimingRow {
+    name: String,
+    mean: String,
+    stddev: String,
+    min: String,
+    max: String,
+}
+
+impl BenchReport {
+    pub fn format_table(&self, lang: Lang) -> String {
+        let rows = |summaries: &[TimingSummary]| -> Vec<TimingRow> {
+            summaries.iter().map(|summary| TimingRow {
+                name: summary.name.clone(),
+                mean: format!("{:.2} ms", summary.mean_ms),
+                stddev: format!("{:.2} ms", summary.stddev_ms),
+                min: format!("{:.2} ms", summary.min_ms),
+                max: format!("{:.2} ms", summary.max_ms),
+            }).collect()
+        };
+
+        let mut output = String::new();
+        output.push_str(&format!(
+            "{}\n\n",
+            lang.trf("bench.title", &[&self.path.display(), &self.files, &self.iterations, &self.warmup])
+        ));
+        output.push_str(&format!("{}\n", lang.tr("bench.phases")));
+        output.push_str(&Table::new(rows(&self.phases)).to_string());
+        output.push_str(&format!("\n\n{}\n", lang.tr("bench.analyzers")));
+        output.push_str(&Table::new(rows(&self.analyzers)).to_string());
+        output.push('\n');
+        output
+    }
+
+    pub fn format_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_summary() {
+        let samples: Vec<Duration> = [10, 12, 14, 16].iter().map(|ms| Duration::from_millis(*ms)).collect();
+
+        let summary = TimingSummary::from_samples("parse", &samples);
+
+        assert_eq!(summary.runs, 4);
+        assert!((summary.mean_ms - 13.0).abs() < 1e-9);
+        assert!((summary.stddev_ms - (20.0f64 / 3.0).sqrt()).abs() < 1e-9);
+        assert_eq!((summary.min_ms, summary.max_ms), (10.0, 16.0));
+        assert_eq!(TimingSummary::from_samples("single", &samples[..1]).stddev_ms, 0.0);
+    }
+}
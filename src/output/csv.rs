@@ -0,0 +1,161 @@
+use super::OutputFormatter;
+use super::budget_limit;
+use crate::ast::{AnalysisResult, BudgetResult, Issue, NgProject};
+use anyhow::Result;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub struct CsvFormatter {
+    budgets: Vec<BudgetResult>,
+}
+
+impl CsvFormatter {
+    pub fn new() -> Self {
+        Self { budgets: Vec::new() }
+    }
+
+    pub fn with_budgets(mut self, budgets: Vec<BudgetResult>) -> Self {
+        self.budgets = budgets;
+        self
+    }
+
+    pub fn format_budgets(&self) -> String {
+        let mut output = String::new();
+        push_row(&mut output, &["budget", "limit", "actual", "passed", "worst"]);
+        for budget in &self.budgets {
+            push_row(&mut output, &[
+                &budget.budget,
+                &budget_limit(budget),
+                &budget.actual.to_string(),
+                &budget.passed.to_string(),
+                budget.worst.as_deref().unwrap_or(""),
+            ]);
+        }
+        output
+    }
+
+    pub fn format_metrics(&self, results: &[AnalysisResult]) -> Result<String> {
+        let mut output = String::new();
+        push_row(&mut output, &[
+            "result", "project", "total_components", "total_services", "total_modules",
+            "average_complexity", "lines_of_code", "test_coverage",
+            "standalone_percentage", "signal_inputs_percentage", "onpush_percentage", "typed_forms_percentage", "issues",
+        ]);
+
+        for (i, result) in results.iter().enumerate() {
+            let metrics = &result.metrics;
+            let adoption = metrics.adoption.clone().unwrap_or_default();
+            push_row(&mut output, &[
+                &(i + 1).to_string(),
+                &result.project.root_path.display().to_string(),
+                &metrics.total_components.to_string(),
+                &metrics.total_services.to_string(),
+                &metrics.total_modules.to_string(),
+                &format!("{:.2}", metrics.average_complexity),
+                &metrics.lines_of_code.to_string(),
+                &percentage_field(metrics.test_coverage),
+                &percentage_field(adoption.standalone_percentage),
+                &percentage_field(adoption.signal_inputs_percentage),
+                &percentage_field(adoption.onpush_percentage),
+                &percentage_field(adoption.typed_forms_percentage),
+                &result.total_issues().to_string(),
+            ]);
+        }
+
+        Ok(output)
+    }
+
+    // report.csv -> report-metrics.csv
+    pub fn metrics_path(path: &Path) -> PathBuf {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("analysis-report");
+        path.with_file_name(format!("{}-metrics.csv", stem))
+    }
+
+    // report.csv -> report-budgets.csv
+    pub fn budgets_path(path: &Path) -> PathBuf {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("analysis-report");
+        path.with_file_name(format!("{}-budgets.csv", stem))
+    }
+}
+
+impl OutputFormatter for CsvFormatter {
+    fn format(&self, results: &[AnalysisResult]) -> Result<String> {
+        let mut output = String::new();
+        push_row(&mut output, &["severity", "rule", "message", "file", "line", "component", "fingerprint"]);
+
+        for result in results {
+            for issue in &result.issues {
+                push_row(&mut output, &[
+                    &format!("{:?}", issue.severity),
+                    &issue.rule,
+                    &issue.message,
+                    &issue.file_path,
+                    &issue.line.map(|l| l.to_string()).unwrap_or_default(),
+                    owning_class(&result.project, issue).unwrap_or(""),
+                    issue.fingerprint.as_deref().unwrap_or(""),
+                ]);
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn write_to_file(&self, results: &[AnalysisResult], path: &Path) -> Result<()> {
+        fs::write(path, self.format(results)?)?;
+        fs::write(Self::metrics_path(path), self.format_metrics(results)?)?;
+        if !self.budgets.is_empty() {
+            fs::write(Self::budgets_path(path), self.format_budgets())?;
+        }
+        Ok(())
+    }
+}
+
+// issue のファイルに対応するコンポーネント（なければサービス）名
+fn owning_class<'a>(project: &'a NgProject, issue: &Issue) -> Option<&'a str> {
+    project.components.iter()
+        .find(|c| c.file_path == issue.file_path)
+        .map(|c| c.name.as_str())
+        .or_else(|| {
+            project.services.iter()
+                .find(|s| s.file_path == issue.file_path)
+                .map(|s| s.name.as_str())
+        })
+}
+
+fn percentage_field(value: Option<f64>) -> String {
+    value.map(|v| format!("{:.2}", v)).unwrap_or_default()
+}
+
+fn push_row(output: &mut String, fields: &[&str]) {
+    let row: Vec<String> = fields.iter().map(|field| escape_field(field)).collect();
+    output.push_str(&row.join(","));
+    output.push_str("\r\n");
+}
+
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_field() {
+        assert_eq!(escape_field("plain"), "plain");
+        assert_eq!(escape_field("a, b"), "\"a, b\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_metrics_path() {
+        assert_eq!(
+            CsvFormatter::metrics_path(Path::new("reports/analysis-report.csv")),
+            PathBuf::from("reports/analysis-report-metrics.csv")
+        );
+    }
+}
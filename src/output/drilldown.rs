@@ -0,0 +1,188 @@
+use crate::ast::{AnalysisResult, Issue, NgComponent, Severity, ChangeDetectionStrategy};
+use crate::config::relative_issue_path;
+use std::collections::BTreeMap;
+
+#[derive(Default)]
+pub struct DirectoryNode<'a> {
+    pub name: String,
+    pub directories: BTreeMap<String, DirectoryNode<'a>>,
+    pub files: BTreeMap<String, FileEntry<'a>>,
+}
+
+#[derive(Default)]
+pub struct FileEntry<'a> {
+    pub issues: Vec<&'a Issue>,
+    pub components: Vec<&'a NgComponent>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DirectoryStats {
+    pub files: usize,
+    pub components: usize,
+    pub onpush_components: usize,
+    pub total_complexity: u32,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+}
+
+impl DirectoryStats {
+    pub fn onpush_percentage(&self) -> f64 {
+        if self.components == 0 {
+            0.0
+        } else {
+            self.onpush_components as f64 * 100.0 / self.components as f64
+        }
+    }
+
+    pub fn average_complexity(&self) -> f64 {
+        if self.components == 0 {
+            0.0
+        } else {
+            self.total_complexity as f64 / self.components as f64
+        }
+    }
+
+    fn add(&mut self, other: &DirectoryStats) {
+        self.files += other.files;
+        self.components += other.components;
+        self.onpush_components += other.onpush_components;
+        self.total_complexity += other.total_complexity;
+        self.errors += other.errors;
+        self.warnings += other.warnings;
+        self.infos += other.infos;
+    }
+}
+
+impl<'a> FileEntry<'a> {
+    pub fn stats(&self) -> DirectoryStats {
+        let mut stats = DirectoryStats {
+            files: 1,
+            components: self.components.len(),
+            ..Default::default()
+        };
+        for component in &self.components {
+            if matches!(component.change_detection, ChangeDetectionStrategy::OnPush) {
+                stats.onpush_components += 1;
+            }
+            stats.total_complexity += component.complexity_score;
+        }
+        for issue in &self.issues {
+            match issue.severity {
+                Severity::Error => stats.errors += 1,
+                Severity::Warning => stats.warnings += 1,
+                Severity::Info => stats.infos += 1,
+            }
+        }
+        stats
+    }
+}
+
+impl<'a> DirectoryNode<'a> {
+    // 全アナライザーの結果をディレクトリ単位の木にまとめる
+    pub fn build(results: &'a [AnalysisResult]) -> Self {
+        let mut root = DirectoryNode {
+            name: ".".to_string(),
+            ..Default::default()
+        };
+
+        let Some(first) = results.first() else {
+            return root;
+        };
+        let root_path = &first.project.root_path;
+
+        // 各結果は同じプロジェクトを持つので、コンポーネントは最初の結果から取る
+        for component in &first.project.components {
+            let path = relative_issue_path(&component.file_path, root_path);
+            root.file_mut(&path).components.push(component);
+        }
+        for issue in results.iter().flat_map(|r| &r.issues) {
+            let path = relative_issue_path(&issue.file_path, root_path);
+            // プロジェクト全体に対する issue は特定のファイルに紐付かないので除外
+            if path.is_empty() {
+                continue;
+            }
+            root.file_mut(&path).issues.push(issue);
+        }
+
+        root
+    }
+
+    fn file_mut(&mut self, path: &str) -> &mut FileEntry<'a> {
+        let mut segments: Vec<&str> = path.trim_start_matches("./").split('/').filter(|s| !s.is_empty()).collect();
+        let file_name = segments.pop().unwrap_or(path).to_string();
+
+        let mut node = self;
+        for segment in segments {
+            node = node.directories.entry(segment.to_string()).or_insert_with(|| DirectoryNode {
+                name: segment.to_string(),
+                ..Default::default()
+            });
+        }
+        node.files.entry(file_name).or_default()
+    }
+
+    pub fn stats(&self) -> DirectoryStats {
+        let mut stats = DirectoryStats::default();
+        for directory in self.directories.values() {
+            stats.add(&directory.stats());
+        }
+        for file in self.files.values() {
+            stats.add(&file.stats());
+        }
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::ComponentBuilder;
+    use crate::ast::{NgProject, ProjectMetrics};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    fn component(file_path: &str, on_push: bool, complexity_score: u32) -> NgComponent {
+        let builder = ComponentBuilder::new("TestComponent")
+            .file_path(file_path)
+            .complexity(complexity_score);
+        if on_push { builder.on_push().build() } else { builder.build() }
+    }
+
+    #[test]
+    fn test_directory_stats_roll_up() {
+        let result = AnalysisResult {
+            project: Arc::new(NgProject {
+                root_path: PathBuf::from("/app"),
+                components: vec![
+                    component("/app/orders/list.component.ts", true, 4),
+                    component("/app/orders/detail/detail.component.ts", false, 8),
+                    component("/app/shared/button.component.ts", true, 2),
+                ],
+                ..Default::default()
+            }),
+            issues: vec![Issue {
+                severity: Severity::Warning,
+                rule: "component-complexity".to_string(),
+                message: "too complex".to_string(),
+                file_path: "/app/orders/detail/detail.component.ts".to_string(),
+                line: None,
+                column: None,
+                fingerprint: None,
+            }],
+            metrics: ProjectMetrics::default(),
+            recommendations: vec![],
+            omitted_issues: vec![],
+        };
+        let results = vec![result];
+
+        let tree = DirectoryNode::build(&results);
+        let orders = tree.directories["orders"].stats();
+
+        assert_eq!(orders.files, 2);
+        assert_eq!(orders.onpush_percentage(), 50.0);
+        assert_eq!(orders.average_complexity(), 6.0);
+        assert_eq!(orders.warnings, 1);
+        assert_eq!(tree.stats().components, 3);
+    }
+}
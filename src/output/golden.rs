@@ -0,0 +1,143 @@
+use crate::analyzers::{AnalysisEngine, ANALYZER_NAMES};
+use crate::output::i18n::Lang;
+use crate::parsers::ProjectParser;
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+// パーサーとアナライザーの出力を固定するためのゴールデンファイル比較
+// fixtures 配下のディレクトリ 1 つが 1 プロジェクトで、<golden>/<名前>.json と突き合わせる
+pub struct GoldenOutcome {
+    pub fixture: String,
+    pub status: GoldenStatus,
+}
+
+pub enum GoldenStatus {
+    Matched,
+    Updated,
+    // ゴールデンファイルとの unified diff
+    Mismatched(String),
+    Missing,
+}
+
+pub async fn check_fixtures(fixtures_dir: &Path, golden_dir: &Path, update: bool, lang: Lang) -> Result<Vec<GoldenOutcome>> {
+    let mut fixtures: Vec<PathBuf> = fs::read_dir(fixtures_dir)
+        .with_context(|| lang.trf("error.fixtures_unreadable", &[&fixtures_dir.display()]))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    fixtures.sort();
+    if fixtures.is_empty() {
+        return Err(anyhow::anyhow!("{}", lang.trf("error.no_fixtures", &[&fixtures_dir.display()])));
+    }
+
+    let mut outcomes = Vec::new();
+    for fixture in fixtures {
+        let name = fixture.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let actual = snapshot(&fixture).await?;
+        let golden_path = golden_dir.join(format!("{}.json", name));
+        let expected = fs::read_to_string(&golden_path).ok();
+
+        let status = match expected {
+            Some(expected) if expected == actual => GoldenStatus::Matched,
+            _ if update => {
+                fs::create_dir_all(golden_dir)?;
+                fs::write(&golden_path, &actual)?;
+                GoldenStatus::Updated
+            }
+            Some(expected) => GoldenStatus::Mismatched(
+                similar::TextDiff::from_lines(&expected, &actual)
+                    .unified_diff()
+                    .context_radius(3)
+                    .header(&golden_path.display().to_string(), "actual")
+                    .to_string(),
+            ),
+            None => GoldenStatus::Missing,
+        };
+        outcomes.push(GoldenOutcome { fixture: name, status });
+    }
+    Ok(outcomes)
+}
+
+// 全アナライザーを 1 つずつ実行し、プロジェクトは 1 回だけ含めた正規化済み JSON を返す
+pub async fn snapshot(fixture: &Path) -> Result<String> {
+    // 絶対パスで解析して、出力からそのパスを取り除く。相対パスのままだとパッケージ名などと区別できない
+    let fixture = fs::canonicalize(fixture)?;
+    let project = Arc::new(ProjectParser::new().parse_project(&fixture).await?);
+    let engine = AnalysisEngine::new();
+
+    let mut analyzers = Map::new();
+    for name in ANALYZER_NAMES {
+        let results = engine.run_analysis(&project, &[name.to_string()])?;
+        for result in results {
+            analyzers.insert(name.to_string(), json!({
+                "issues": result.issues,
+                "metrics": result.metrics,
+                "recommendations": result.recommendations,
+            }));
+        }
+    }
+
+    let mut value = json!({
+        "project": project,
+        "analyzers": analyzers,
+    });
+    canonicalize(&mut value, &fixture.display().to_string());
+    Ok(format!("{}\n", serde_json::to_string_pretty(&value)?))
+}
+
+// 実行場所に依存しないよう、fixture のパスを取り除いて区切りを / にそろえる
+// オブジェクトのキーは serde_json の Map が並べ替える
+fn canonicalize(value: &mut Value, root: &str) {
+    match value {
+        Value::String(text) => {
+            let normalized = text.replace('\\', "/");
+            let root = root.replace('\\', "/");
+            if normalized == root {
+                *text = ".".to_string();
+            } else if normalized.contains(&root) {
+                *text = normalized.replace(&format!("{}/", root), "").replace(&root, ".");
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| canonicalize(item, root)),
+        Value::Object(entries) => entries.values_mut().for_each(|item| canonicalize(item, root)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonicalize() {
+        let mut value = json!({
+            "root_path": "/work/app",
+            "files": ["/work/app/src/main.ts", "\\work\\app\\src\\app.ts"],
+            "message": "Imported by /work/app/src/main.ts",
+            "count": 3,
+        });
+        canonicalize(&mut value, "/work/app");
+        assert_eq!(value["root_path"], ".");
+        assert_eq!(value["files"][0], "src/main.ts");
+        assert_eq!(value["files"][1], "src/app.ts");
+        assert_eq!(value["message"], "Imported by src/main.ts");
+    }
+
+    // tests/golden が古くなったら `ng-analyzer golden --update` で作り直す
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_golden_files() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let outcomes = check_fixtures(&root.join("tests/fixtures/projects"), &root.join("tests/golden"), false, Lang::En).await.unwrap();
+        assert_eq!(outcomes.len(), 3);
+        for outcome in outcomes {
+            match outcome.status {
+                GoldenStatus::Matched => {}
+                GoldenStatus::Mismatched(diff) => panic!("{} がゴールデンファイルと一致しません\n{}", outcome.fixture, diff),
+                _ => panic!("{} のゴールデンファイルがありません", outcome.fixture),
+            }
+        }
+    }
+}
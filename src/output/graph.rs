@@ -1,12 +1,36 @@
-use crate::ast::{ImportExportGraph, DependencyAnalysis};
+use crate::ast::{BundleEstimate, ComponentTree, ComponentTreeEdge, FileInfo, ImpactReport, ImportExportGraph, DependencyAnalysis, InjectionGraph, InjectionNode, LibraryApiReport, ModuleGraph, ModuleGraphEdge, PackageUsageReport, ServiceApiReport, UsageReport};
+use super::graph_html::render_graph_html;
+use super::i18n::Lang;
+use super::schema::{SchemaKind, SchemaVersion, SCHEMA_VERSION};
+use super::svg::{self, escape_xml, SvgEdge, SvgNode};
 use anyhow::Result;
+use std::collections::BTreeMap;
 use std::path::Path;
 
-pub struct GraphFormatter;
+pub struct GraphFormatter {
+    top_count: usize,
+    schema: Option<SchemaVersion>,
+    lang: Lang,
+}
 
 impl GraphFormatter {
     pub fn new() -> Self {
-        Self
+        Self { top_count: 10, schema: None, lang: Lang::default() }
+    }
+
+    pub fn with_lang(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    pub fn with_top_count(mut self, top_count: usize) -> Self {
+        self.top_count = top_count;
+        self
+    }
+
+    pub fn with_schema(mut self, schema: Option<SchemaVersion>) -> Self {
+        self.schema = schema;
+        self
     }
 
     pub fn format_dot(&self, graph: &ImportExportGraph, analysis: &DependencyAnalysis) -> Result<String> {
@@ -20,18 +44,10 @@ impl GraphFormatter {
         // ノード（ファイル）を定義
         for file in &graph.files {
             let node_id = self.sanitize_node_id(&file.id);
-            let file_name = Path::new(&file.file_path)
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or(&file.relative_path);
+            let file_name = self.file_label(file);
             
             // ファイルタイプによって色を変更
-            let color = match file.file_type {
-                crate::ast::FileType::TypeScript => "lightblue",
-                crate::ast::FileType::JavaScript => "lightgreen",
-                crate::ast::FileType::Declaration => "lightyellow",
-                crate::ast::FileType::Module => "lightgray",
-            };
+            let color = self.file_color(file);
             
             output.push_str(&format!(
                 "    {} [label=\"{}\", fillcolor={}, tooltip=\"{}\"];\n",
@@ -39,7 +55,7 @@ impl GraphFormatter {
             ));
         }
         
-        output.push_str("\n");
+        output.push('\n');
         
         // エッジ（依存関係）を定義
         for dependency in &graph.dependencies {
@@ -47,7 +63,7 @@ impl GraphFormatter {
             let to_node = self.sanitize_node_id(&dependency.to_file);
             
             let label = if dependency.imported_symbols.len() > 3 {
-                format!("{}個のシンボル", dependency.imported_symbols.len())
+                self.lang.trf("graph.label.symbols", &[&dependency.imported_symbols.len()])
             } else {
                 dependency.imported_symbols.join(", ")
             };
@@ -59,16 +75,23 @@ impl GraphFormatter {
                 crate::ast::ImportType::Dynamic => "red",
             };
             
+            // 動的 import は遅延ロードの境界として破線で描く
+            if matches!(dependency.import_type, crate::ast::ImportType::Dynamic) {
+                output.push_str(&format!(
+                    "    {} -> {} [label=\"lazy\", color={}, style=dashed, tooltip=\"Type: {:?}\"];\n",
+                    from_node, to_node, color, dependency.import_type
+                ));
+                continue;
+            }
             output.push_str(&format!(
-                "    {} -> {} [label=\"{}\", color={}, tooltip=\"{}\"];\n",
-                from_node, to_node, label, color, 
-                format!("Type: {:?}", dependency.import_type)
+                "    {} -> {} [label=\"{}\", color={}, tooltip=\"Type: {:?}\"];\n",
+                from_node, to_node, label, color, dependency.import_type
             ));
         }
         
         // 循環依存を強調
         if !analysis.circular_dependencies.is_empty() {
-            output.push_str("\n    // 循環依存\n");
+            output.push_str(&format!("\n    // {}\n", self.lang.tr("graph.label.cycles")));
             for circular in &analysis.circular_dependencies {
                 for i in 0..circular.cycle.len() - 1 {
                     let from_node = self.sanitize_node_id(&circular.cycle[i]);
@@ -86,6 +109,74 @@ impl GraphFormatter {
         Ok(output)
     }
 
+    // Gephi や yEd で開ける GraphML
+    pub fn format_graphml(&self, graph: &ImportExportGraph, analysis: &DependencyAnalysis) -> Result<String> {
+        let mut output = String::new();
+
+        output.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        output.push_str("<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n");
+        output.push_str("  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n");
+        output.push_str("  <key id=\"path\" for=\"node\" attr.name=\"path\" attr.type=\"string\"/>\n");
+        output.push_str("  <key id=\"file_type\" for=\"node\" attr.name=\"file_type\" attr.type=\"string\"/>\n");
+        output.push_str("  <key id=\"import_type\" for=\"edge\" attr.name=\"import_type\" attr.type=\"string\"/>\n");
+        output.push_str("  <key id=\"symbols\" for=\"edge\" attr.name=\"symbols\" attr.type=\"string\"/>\n");
+        output.push_str("  <key id=\"circular\" for=\"edge\" attr.name=\"circular\" attr.type=\"boolean\"/>\n");
+        output.push_str("  <graph id=\"dependency_graph\" edgedefault=\"directed\">\n");
+
+        for file in &graph.files {
+            output.push_str(&format!(
+                "    <node id=\"{}\"><data key=\"label\">{}</data><data key=\"path\">{}</data><data key=\"file_type\">{:?}</data></node>\n",
+                escape_xml(&file.id), escape_xml(self.file_label(file)), escape_xml(&file.relative_path), file.file_type
+            ));
+        }
+
+        for (i, dependency) in graph.dependencies.iter().enumerate() {
+            output.push_str(&format!(
+                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\"><data key=\"import_type\">{:?}</data><data key=\"symbols\">{}</data><data key=\"circular\">{}</data></edge>\n",
+                i,
+                escape_xml(&dependency.from_file),
+                escape_xml(&dependency.to_file),
+                dependency.import_type,
+                escape_xml(&dependency.imported_symbols.join(", ")),
+                self.is_circular_dependency(analysis, &dependency.from_file, &dependency.to_file)
+            ));
+        }
+
+        output.push_str("  </graph>\n");
+        output.push_str("</graphml>\n");
+
+        Ok(output)
+    }
+
+    // dot があれば Graphviz で描画し、なければ組み込みのレイアウトで描く
+    pub fn format_svg(&self, graph: &ImportExportGraph, analysis: &DependencyAnalysis) -> Result<String> {
+        if let Some(svg) = svg::render_with_dot(&self.format_dot(graph, analysis)?) {
+            return Ok(svg);
+        }
+
+        let nodes: Vec<SvgNode> = graph.files.iter()
+            .map(|file| SvgNode {
+                id: file.id.clone(),
+                label: self.file_label(file).to_string(),
+                fill: self.file_color(file),
+            })
+            .collect();
+        let edges: Vec<SvgEdge> = graph.dependencies.iter()
+            .map(|dependency| SvgEdge {
+                from: dependency.from_file.clone(),
+                to: dependency.to_file.clone(),
+                highlight: self.is_circular_dependency(analysis, &dependency.from_file, &dependency.to_file),
+            })
+            .collect();
+
+        Ok(svg::render_layered(&nodes, &edges))
+    }
+
+    // ブラウザで開いてズームや検索ができる HTML
+    pub fn format_html(&self, graph: &ImportExportGraph, analysis: &DependencyAnalysis) -> Result<String> {
+        render_graph_html(graph, analysis, self.lang)
+    }
+
     pub fn format_mermaid(&self, graph: &ImportExportGraph, analysis: &DependencyAnalysis) -> Result<String> {
         let mut output = String::new();
         
@@ -94,10 +185,7 @@ impl GraphFormatter {
         // ノード（ファイル）を定義
         for file in &graph.files {
             let node_id = self.sanitize_node_id(&file.id);
-            let file_name = Path::new(&file.file_path)
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or(&file.relative_path);
+            let file_name = self.file_label(file);
             
             // ファイルタイプによってスタイルを変更
             let style = match file.file_type {
@@ -105,6 +193,7 @@ impl GraphFormatter {
                 crate::ast::FileType::JavaScript => "fill:#e8f5e8,stroke:#2e7d32",
                 crate::ast::FileType::Declaration => "fill:#fff3e0,stroke:#e65100",
                 crate::ast::FileType::Module => "fill:#f5f5f5,stroke:#424242",
+                crate::ast::FileType::External => "fill:#ffffff,stroke:#9e9e9e,stroke-dasharray:3",
             };
             
             output.push_str(&format!(
@@ -118,7 +207,7 @@ impl GraphFormatter {
             ));
         }
         
-        output.push_str("\n");
+        output.push('\n');
         
         // エッジ（依存関係）を定義
         for dependency in &graph.dependencies {
@@ -126,11 +215,15 @@ impl GraphFormatter {
             let to_node = self.sanitize_node_id(&dependency.to_file);
             
             let label = if dependency.imported_symbols.len() > 3 {
-                format!("{}個", dependency.imported_symbols.len())
+                self.lang.trf("graph.label.symbols_short", &[&dependency.imported_symbols.len()])
             } else {
                 dependency.imported_symbols.join(",")
             };
             
+            if matches!(dependency.import_type, crate::ast::ImportType::Dynamic) {
+                output.push_str(&format!("    {} -.->|lazy| {}\n", from_node, to_node));
+                continue;
+            }
             output.push_str(&format!(
                 "    {} -->|{}| {}\n",
                 from_node, label, to_node
@@ -139,14 +232,14 @@ impl GraphFormatter {
         
         // 循環依存を強調
         if !analysis.circular_dependencies.is_empty() {
-            output.push_str("\n    %% 循環依存\n");
+            output.push_str(&format!("\n    %% {}\n", self.lang.tr("graph.label.cycles")));
             for circular in &analysis.circular_dependencies {
                 for i in 0..circular.cycle.len() - 1 {
                     let from_node = self.sanitize_node_id(&circular.cycle[i]);
                     let to_node = self.sanitize_node_id(&circular.cycle[i + 1]);
                     output.push_str(&format!(
-                        "    {} -.->|循環| {}\n",
-                        from_node, to_node
+                        "    {} -.->|{}| {}\n",
+                        from_node, self.lang.tr("graph.label.cycle"), to_node
                     ));
                     output.push_str(&format!(
                         "    linkStyle {} stroke:#ff0000,stroke-width:3px\n",
@@ -161,15 +254,21 @@ impl GraphFormatter {
 
     pub fn format_json(&self, graph: &ImportExportGraph, analysis: &DependencyAnalysis) -> Result<String> {
         let combined_output = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
             "graph": graph,
             "analysis": analysis,
             "summary": {
                 "total_files": graph.files.len(),
                 "total_dependencies": graph.dependencies.len(),
                 "circular_dependencies": analysis.circular_dependencies.len(),
-                "orphaned_files": analysis.orphaned_files.len()
+                "orphaned_files": analysis.orphaned_files.len(),
+                "unused_exports": analysis.unused_exports.len()
             }
         });
+        let combined_output = match self.schema {
+            Some(version) => version.pin(SchemaKind::Graph, combined_output),
+            None => combined_output,
+        };
         
         Ok(serde_json::to_string_pretty(&combined_output)?)
     }
@@ -177,69 +276,891 @@ impl GraphFormatter {
     pub fn format_table(&self, graph: &ImportExportGraph, analysis: &DependencyAnalysis) -> Result<String> {
         let mut output = String::new();
         
-        output.push_str("# 依存関係グラフ分析結果\n\n");
+        let lang = self.lang;
+        output.push_str(&format!("{}\n\n", lang.tr("graph.table.title")));
         
         // サマリー
-        output.push_str("## サマリー\n");
-        output.push_str(&format!("- 総ファイル数: {}\n", graph.files.len()));
-        output.push_str(&format!("- 総依存関係数: {}\n", graph.dependencies.len()));
-        output.push_str(&format!("- 循環依存数: {}\n", analysis.circular_dependencies.len()));
-        output.push_str(&format!("- 孤立ファイル数: {}\n", analysis.orphaned_files.len()));
-        output.push_str("\n");
+        output.push_str(&format!("{}\n", lang.tr("graph.table.summary")));
+        output.push_str(&format!("{}\n", lang.trf("graph.table.files", &[&graph.files.len()])));
+        output.push_str(&format!("{}\n", lang.trf("graph.table.dependencies", &[&graph.dependencies.len()])));
+        output.push_str(&format!("{}\n", lang.trf("graph.table.cycles", &[&analysis.circular_dependencies.len()])));
+        output.push_str(&format!("{}\n", lang.trf("graph.table.orphans", &[&analysis.orphaned_files.len()])));
+        output.push_str(&format!("{}\n", lang.trf("graph.table.unused", &[&analysis.unused_exports.len()])));
+        output.push('\n');
         
         // 循環依存
         if !analysis.circular_dependencies.is_empty() {
-            output.push_str("## 循環依存\n");
+            output.push_str(&format!("{}\n", lang.tr("graph.table.cycles_heading")));
             for (i, circular) in analysis.circular_dependencies.iter().enumerate() {
+                let files: Vec<&str> = circular.cycle.iter()
+                    .map(|id| graph.files.iter().find(|file| file.id == *id).map_or(id.as_str(), |file| file.relative_path.as_str()))
+                    .collect();
                 output.push_str(&format!(
-                    "{}. {} (重要度: {:?})\n",
-                    i + 1,
-                    circular.cycle.join(" -> "),
-                    circular.severity
+                    "{}\n",
+                    lang.trf("graph.table.cycle", &[&(i + 1), &files.join(" -> "), &format!("{:?}", circular.severity)])
                 ));
             }
-            output.push_str("\n");
+            output.push('\n');
         }
         
         // 最もインポートされているファイル
         if !analysis.most_imported_files.is_empty() {
-            output.push_str("## 最もインポートされているファイル\n");
+            output.push_str(&format!("{}\n", lang.tr("graph.table.most_imported")));
             for (file_path, count) in &analysis.most_imported_files {
-                output.push_str(&format!("- {} ({}回)\n", file_path, count));
+                output.push_str(&format!("{}\n", lang.trf("graph.table.imported_count", &[file_path, count])));
             }
-            output.push_str("\n");
+            output.push('\n');
         }
         
         // 最も依存関係が多いファイル
         if !analysis.most_dependent_files.is_empty() {
-            output.push_str("## 最も依存関係が多いファイル\n");
+            output.push_str(&format!("{}\n", lang.tr("graph.table.most_dependent")));
             for (file_path, count) in &analysis.most_dependent_files {
-                output.push_str(&format!("- {} ({}個の依存関係)\n", file_path, count));
+                output.push_str(&format!("{}\n", lang.trf("graph.table.dependency_count", &[file_path, count])));
             }
-            output.push_str("\n");
+            output.push('\n');
         }
         
         // 孤立ファイル
         if !analysis.orphaned_files.is_empty() {
-            output.push_str("## 孤立ファイル\n");
+            output.push_str(&format!("{}\n", lang.tr("graph.table.orphans_heading")));
             for file_path in &analysis.orphaned_files {
                 output.push_str(&format!("- {}\n", file_path));
             }
-            output.push_str("\n");
+            output.push('\n');
         }
         
-        // 依存関係の深さ
-        output.push_str("## 依存関係の深さ\n");
-        let mut depth_entries: Vec<_> = analysis.dependency_depth.iter().collect();
-        depth_entries.sort_by(|a, b| b.1.cmp(a.1));
+        // どこからも import されていない export。ディレクトリごとの件数とファイルごとのシンボル
+        if !analysis.unused_exports.is_empty() {
+            let mut by_file: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+            for unused in &analysis.unused_exports {
+                let path = graph.files.iter().find(|file| file.file_path == unused.file_path).map_or(unused.file_path.as_str(), |file| file.relative_path.as_str());
+                by_file.entry(path).or_default().push(unused.symbol.as_str());
+            }
+            let mut by_directory: BTreeMap<String, usize> = BTreeMap::new();
+            for (path, symbols) in &by_file {
+                let directory = Path::new(path).parent().map(|dir| dir.display().to_string().replace('\\', "/")).unwrap_or_default();
+                *by_directory.entry(if directory.is_empty() { ".".to_string() } else { directory }).or_default() += symbols.len();
+            }
+
+            output.push_str(&format!("{}\n", lang.tr("graph.table.unused_heading")));
+            output.push_str(&format!("{}\n", lang.tr("graph.table.unused_columns")));
+            output.push_str("|---|---|\n");
+            for (directory, count) in &by_directory {
+                output.push_str(&format!("| {} | {} |\n", directory, count));
+            }
+            output.push('\n');
+            for (path, symbols) in &by_file {
+                output.push_str(&format!("- {}: {}\n", path, symbols.join(", ")));
+            }
+            output.push('\n');
+        }
+
+        // 循環をまとめたうえでの最長の import 経路
+        if !analysis.longest_chains.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("graph.table.longest_chains")));
+            for chain in &analysis.longest_chains {
+                output.push_str(&format!("{}\n", lang.trf("graph.table.chain", &[&chain.depth, &chain.path.join(" -> ")])));
+            }
+            output.push('\n');
+        }
         
-        for (file_path, depth) in depth_entries.iter().take(10) {
-            output.push_str(&format!("- {} (深さ: {})\n", file_path, depth));
+        // 依存関係の深さ（--depth 以外の分析だけを指定した場合は空）
+        if !analysis.dependency_depth.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("graph.table.depth_heading")));
+            let mut depth_entries: Vec<_> = analysis.dependency_depth.iter().collect();
+            depth_entries.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+            for (file_path, depth) in depth_entries.iter().take(self.top_count) {
+                output.push_str(&format!("{}\n", lang.trf("graph.table.depth", &[file_path, depth])));
+            }
         }
         
         Ok(output)
     }
 
+    pub fn format_component_tree_dot(&self, tree: &ComponentTree) -> Result<String> {
+        let mut output = String::new();
+
+        output.push_str("digraph component_tree {\n");
+        output.push_str("    rankdir=TB;\n");
+        output.push_str("    node [shape=rectangle, style=filled, fillcolor=lightblue];\n");
+        output.push_str("    edge [fontsize=10];\n\n");
+
+        for node in &tree.nodes {
+            output.push_str(&format!(
+                "    {} [label=\"{}\", tooltip=\"{}\"];\n",
+                self.sanitize_node_id(&node.name),
+                node.selector.as_deref().unwrap_or(&node.name),
+                node.file_path
+            ));
+        }
+
+        output.push('\n');
+
+        // embeds は実線、projected / template は破線
+        for edge in &tree.edges {
+            let style = if edge.kind == "embeds" { "solid" } else { "dashed" };
+            output.push_str(&format!(
+                "    {} -> {} [label=\"{}\", style={}];\n",
+                self.sanitize_node_id(&edge.parent),
+                self.sanitize_node_id(&edge.child),
+                self.component_edge_label(edge),
+                style
+            ));
+        }
+
+        output.push_str("}\n");
+
+        Ok(output)
+    }
+
+    pub fn format_component_tree_mermaid(&self, tree: &ComponentTree) -> Result<String> {
+        let mut output = String::new();
+
+        output.push_str("graph TD\n");
+
+        for node in &tree.nodes {
+            output.push_str(&format!(
+                "    {}[\"{}\"]\n",
+                self.sanitize_node_id(&node.name),
+                node.selector.as_deref().unwrap_or(&node.name)
+            ));
+        }
+
+        output.push('\n');
+
+        for edge in &tree.edges {
+            let arrow = if edge.kind == "embeds" { "-->" } else { "-.->" };
+            output.push_str(&format!(
+                "    {} {}|{}| {}\n",
+                self.sanitize_node_id(&edge.parent),
+                arrow,
+                self.component_edge_label(edge),
+                self.sanitize_node_id(&edge.child)
+            ));
+        }
+
+        Ok(output)
+    }
+
+    pub fn format_component_tree_json(&self, tree: &ComponentTree) -> Result<String> {
+        let combined_output = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "tree": tree,
+            "summary": {
+                "total_components": tree.nodes.len(),
+                "total_edges": tree.edges.len(),
+                "max_depth": tree.nodes.iter().map(|node| node.depth).max().unwrap_or(0)
+            }
+        });
+
+        Ok(serde_json::to_string_pretty(&combined_output)?)
+    }
+
+    pub fn format_module_graph_dot(&self, graph: &ModuleGraph) -> Result<String> {
+        let mut output = String::new();
+
+        output.push_str("digraph module_graph {\n");
+        output.push_str("    rankdir=LR;\n");
+        output.push_str("    node [shape=box, style=filled];\n\n");
+
+        // ルートモジュールは緑、遅延読み込みされるモジュールは黄色
+        for node in &graph.nodes {
+            let color = if node.root {
+                "lightgreen"
+            } else if node.lazy {
+                "lightyellow"
+            } else {
+                "lightblue"
+            };
+            output.push_str(&format!(
+                "    {} [label=\"{}\", tooltip=\"{}\", fillcolor={}];\n",
+                self.sanitize_node_id(&node.name),
+                node.name,
+                node.file_path,
+                color
+            ));
+        }
+
+        output.push('\n');
+
+        // 遅延読み込みは破線、循環している import は赤
+        for edge in &graph.edges {
+            let attributes = if edge.kind == "lazy" {
+                "label=\"lazy\", style=dashed".to_string()
+            } else if self.is_cycle_edge(graph, edge) {
+                "color=red, penwidth=2".to_string()
+            } else {
+                "color=black".to_string()
+            };
+            output.push_str(&format!(
+                "    {} -> {} [{}];\n",
+                self.sanitize_node_id(&edge.from),
+                self.sanitize_node_id(&edge.to),
+                attributes
+            ));
+        }
+
+        output.push_str("}\n");
+
+        Ok(output)
+    }
+
+    pub fn format_module_graph_mermaid(&self, graph: &ModuleGraph) -> Result<String> {
+        let mut output = String::new();
+
+        output.push_str("graph LR\n");
+
+        for node in &graph.nodes {
+            output.push_str(&format!("    {}[\"{}\"]\n", self.sanitize_node_id(&node.name), node.name));
+        }
+
+        output.push('\n');
+
+        for edge in &graph.edges {
+            let arrow = if edge.kind == "lazy" { "-.->|lazy|" } else { "-->" };
+            output.push_str(&format!(
+                "    {} {} {}\n",
+                self.sanitize_node_id(&edge.from),
+                arrow,
+                self.sanitize_node_id(&edge.to)
+            ));
+        }
+
+        let lazy: Vec<String> = graph.nodes.iter().filter(|node| node.lazy).map(|node| self.sanitize_node_id(&node.name)).collect();
+        let cyclic: Vec<String> = graph.cycles.iter().flatten().map(|name| self.sanitize_node_id(name)).collect();
+        if !lazy.is_empty() || !cyclic.is_empty() {
+            output.push('\n');
+        }
+        if !lazy.is_empty() {
+            output.push_str("    classDef lazy fill:#fff3cd,stroke:#856404\n");
+            output.push_str(&format!("    class {} lazy\n", lazy.join(",")));
+        }
+        if !cyclic.is_empty() {
+            output.push_str("    classDef cycle fill:#f8d7da,stroke:#721c24\n");
+            output.push_str(&format!("    class {} cycle\n", cyclic.join(",")));
+        }
+
+        Ok(output)
+    }
+
+    pub fn format_module_graph_json(&self, graph: &ModuleGraph) -> Result<String> {
+        let combined_output = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "graph": graph,
+            "summary": {
+                "total_modules": graph.nodes.len(),
+                "total_imports": graph.edges.iter().filter(|edge| edge.kind == "imports").count(),
+                "lazy_boundaries": graph.edges.iter().filter(|edge| edge.kind == "lazy").count(),
+                "circular_imports": graph.cycles.len()
+            }
+        });
+
+        Ok(serde_json::to_string_pretty(&combined_output)?)
+    }
+
+    pub fn format_injection_graph_dot(&self, graph: &InjectionGraph) -> Result<String> {
+        let mut output = String::new();
+
+        output.push_str("digraph injection_graph {\n");
+        output.push_str("    rankdir=LR;\n");
+        output.push_str("    node [style=filled];\n\n");
+
+        // 複数のインジェクターで提供されるサービスはオレンジ、1 つの機能でしか使われない root サービスは黄色
+        for node in &graph.nodes {
+            let shape = if node.kind == "component" { "box" } else { "ellipse" };
+            let color = if node.injectors.len() > 1 {
+                "orange"
+            } else if node.single_feature.is_some() {
+                "lightyellow"
+            } else if node.kind == "component" {
+                "lightblue"
+            } else {
+                "lightgreen"
+            };
+            output.push_str(&format!(
+                "    {} [label=\"{}\", tooltip=\"{}\", shape={}, fillcolor={}];\n",
+                self.sanitize_node_id(&node.name),
+                self.injection_node_label(node).replace('"', "\\\""),
+                node.file_path,
+                shape,
+                color
+            ));
+        }
+
+        output.push('\n');
+
+        for edge in &graph.edges {
+            let in_cycle = graph.cycles.iter().any(|cycle| {
+                cycle.iter().zip(cycle.iter().cycle().skip(1)).any(|(from, to)| *from == edge.from && *to == edge.to)
+            });
+            output.push_str(&format!(
+                "    {} -> {} [{}];\n",
+                self.sanitize_node_id(&edge.from),
+                self.sanitize_node_id(&edge.to),
+                if in_cycle { "color=red, penwidth=2" } else { "color=black" }
+            ));
+        }
+
+        output.push_str("}\n");
+
+        Ok(output)
+    }
+
+    pub fn format_injection_graph_mermaid(&self, graph: &InjectionGraph) -> Result<String> {
+        let mut output = String::new();
+
+        output.push_str("graph LR\n");
+
+        for node in &graph.nodes {
+            let label = self.injection_node_label(node).replace('"', "'");
+            if node.kind == "component" {
+                output.push_str(&format!("    {}[\"{}\"]\n", self.sanitize_node_id(&node.name), label));
+            } else {
+                output.push_str(&format!("    {}([\"{}\"])\n", self.sanitize_node_id(&node.name), label));
+            }
+        }
+
+        output.push('\n');
+
+        for edge in &graph.edges {
+            output.push_str(&format!(
+                "    {} --> {}\n",
+                self.sanitize_node_id(&edge.from),
+                self.sanitize_node_id(&edge.to)
+            ));
+        }
+
+        let classes = [
+            ("multiProvided", "fill:#ffe0b2,stroke:#e65100", graph.multi_provided().map(|node| node.name.as_str()).collect::<Vec<_>>()),
+            ("singleFeature", "fill:#fff3cd,stroke:#856404", graph.nodes.iter().filter(|node| node.single_feature.is_some() && node.injectors.len() <= 1).map(|node| node.name.as_str()).collect()),
+            ("cycle", "fill:#f8d7da,stroke:#721c24", graph.cycles.iter().flatten().map(String::as_str).collect()),
+        ];
+        if classes.iter().any(|(_, _, names)| !names.is_empty()) {
+            output.push('\n');
+        }
+        for (class, style, names) in &classes {
+            if names.is_empty() {
+                continue;
+            }
+            output.push_str(&format!("    classDef {} {}\n", class, style));
+            output.push_str(&format!(
+                "    class {} {}\n",
+                names.iter().map(|name| self.sanitize_node_id(name)).collect::<Vec<_>>().join(","),
+                class
+            ));
+        }
+
+        Ok(output)
+    }
+
+    pub fn format_injection_graph_json(&self, graph: &InjectionGraph) -> Result<String> {
+        let combined_output = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "graph": graph,
+            "summary": {
+                "total_components": graph.nodes.iter().filter(|node| node.kind == "component").count(),
+                "total_services": graph.nodes.iter().filter(|node| node.kind == "service").count(),
+                "total_injections": graph.edges.len(),
+                "multi_provided_services": graph.multi_provided().count(),
+                "single_feature_root_services": graph.nodes.iter().filter(|node| node.single_feature.is_some()).count(),
+                "injection_cycles": graph.cycles.len()
+            }
+        });
+
+        Ok(serde_json::to_string_pretty(&combined_output)?)
+    }
+
+    pub fn format_bundle_estimate_table(&self, estimate: &BundleEstimate) -> Result<String> {
+        let mut output = String::new();
+
+        let lang = self.lang;
+        output.push_str(&format!("{}\n\n", lang.tr("bundles.title")));
+        output.push_str(&format!("{}\n\n", lang.tr("bundles.note")));
+
+        output.push_str(&format!("{}\n", lang.tr("bundles.chunks")));
+        output.push_str(&format!("{}\n", lang.tr("bundles.columns")));
+        output.push_str("|---|---|---|---|---|\n");
+        for chunk in &estimate.chunks {
+            let route = match (&chunk.route_file, chunk.line) {
+                (Some(file), Some(line)) => format!("{}:{}", file, line),
+                (Some(file), None) => file.clone(),
+                _ => "-".to_string(),
+            };
+            output.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                chunk.name, chunk.entry, chunk.files.len(), format_bytes(chunk.bytes), route
+            ));
+        }
+        output.push('\n');
+
+        if !estimate.shared.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("bundles.shared")));
+            for shared in estimate.shared.iter().take(self.top_count) {
+                output.push_str(&format!(
+                    "{}\n",
+                    lang.trf("bundles.shared_file", &[&shared.file_path, &format_bytes(shared.bytes), &shared.chunks.len(), &shared.chunks.join(", ")])
+                ));
+            }
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    pub fn format_bundle_estimate_mermaid(&self, estimate: &BundleEstimate) -> Result<String> {
+        let mut output = String::new();
+
+        output.push_str("pie title Source bytes per chunk\n");
+        for chunk in &estimate.chunks {
+            output.push_str(&format!("    \"{}\" : {}\n", chunk.name.replace('"', "'"), chunk.bytes));
+        }
+
+        Ok(output)
+    }
+
+    pub fn format_bundle_estimate_json(&self, estimate: &BundleEstimate) -> Result<String> {
+        let combined_output = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "estimate": estimate,
+            "summary": {
+                "eager_bytes": estimate.eager().map(|chunk| chunk.bytes).unwrap_or(0),
+                "lazy_routes": estimate.routes().count(),
+                "lazy_bytes": estimate.routes().map(|chunk| chunk.bytes).sum::<u64>(),
+                "shared_files": estimate.shared.len()
+            }
+        });
+
+        Ok(serde_json::to_string_pretty(&combined_output)?)
+    }
+
+    pub fn format_package_usage_table(&self, report: &PackageUsageReport) -> Result<String> {
+        let mut output = String::new();
+
+        let lang = self.lang;
+        output.push_str(&format!("{}\n\n", lang.tr("packages.title")));
+
+        output.push_str(&format!("{}\n", lang.tr("graph.table.summary")));
+        output.push_str(&format!("{}\n", lang.trf("graph.table.files", &[&report.total_files])));
+        output.push_str(&format!("{}\n", lang.trf("packages.packages", &[&report.packages.len()])));
+        output.push('\n');
+
+        output.push_str(&format!("{}\n", lang.tr("packages.per_package")));
+        for package in &report.packages {
+            let mut notes = Vec::new();
+            if package.framework {
+                notes.push(lang.tr("packages.framework").to_string());
+            }
+            if !package.entry_points.is_empty() {
+                notes.push(package.entry_points.join(", "));
+            }
+            output.push_str(&format!(
+                "{}\n",
+                lang.trf("packages.package", &[
+                    &package.name,
+                    &package.files.len(),
+                    &format!("{:.1}", package.share(report.total_files)),
+                    &if notes.is_empty() { String::new() } else { format!(" [{}]", notes.join("; ")) },
+                ])
+            ));
+        }
+        output.push('\n');
+
+        // lodash や moment、rxjs のオペレーターなど、フレームワーク以外でよく使われるもの
+        let dominant: Vec<_> = report.dominant(self.top_count).collect();
+        if !dominant.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("packages.dominant")));
+            for package in dominant {
+                let symbols: Vec<String> = package.symbols.iter()
+                    .take(self.top_count)
+                    .map(|symbol| format!("{} ({})", symbol.name, symbol.files))
+                    .collect();
+                output.push_str(&format!("- {}: {}\n", package.name, symbols.join(", ")));
+            }
+            output.push('\n');
+        }
+
+        let candidates: Vec<_> = report.removal_candidates().collect();
+        if !candidates.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("packages.candidates")));
+            for package in candidates {
+                output.push_str(&format!("- {} ({})\n", package.name, package.files[0]));
+            }
+            output.push('\n');
+        }
+
+        let undeclared: Vec<_> = report.undeclared().collect();
+        if !undeclared.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("packages.undeclared")));
+            for package in undeclared {
+                output.push_str(&format!("{}\n", lang.trf("packages.files", &[&package.name, &package.files.len()])));
+            }
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    pub fn format_package_usage_mermaid(&self, report: &PackageUsageReport) -> Result<String> {
+        let mut output = String::new();
+
+        output.push_str("pie title Files importing each package\n");
+        for package in report.packages.iter().take(self.top_count) {
+            output.push_str(&format!("    \"{}\" : {}\n", package.name.replace('"', "'"), package.files.len()));
+        }
+
+        Ok(output)
+    }
+
+    pub fn format_package_usage_json(&self, report: &PackageUsageReport) -> Result<String> {
+        let combined_output = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "report": report,
+            "summary": {
+                "total_files": report.total_files,
+                "total_packages": report.packages.len(),
+                "dominant_packages": report.dominant(self.top_count).map(|package| &package.name).collect::<Vec<_>>(),
+                "removal_candidates": report.removal_candidates().map(|package| &package.name).collect::<Vec<_>>(),
+                "undeclared_packages": report.undeclared().map(|package| &package.name).collect::<Vec<_>>()
+            }
+        });
+
+        Ok(serde_json::to_string_pretty(&combined_output)?)
+    }
+
+    pub fn format_service_api_table(&self, report: &ServiceApiReport) -> Result<String> {
+        let mut output = String::new();
+
+        let lang = self.lang;
+        output.push_str(&format!("{}\n\n", lang.tr("service_api.title")));
+
+        output.push_str(&format!("{}\n", lang.tr("graph.table.summary")));
+        output.push_str(&format!("{}\n", lang.trf("service_api.services", &[&report.services.len()])));
+        output.push_str(&format!("{}\n", lang.trf("service_api.methods", &[&report.total_methods()])));
+        output.push_str(&format!("{}\n", lang.trf("service_api.unused_count", &[&report.unused().count()])));
+        output.push('\n');
+
+        for service in &report.services {
+            output.push_str(&format!("## {} ({})\n", service.name, service.file_path));
+            if service.methods.is_empty() {
+                output.push_str(&format!("{}\n", lang.tr("service_api.no_methods")));
+            }
+            for method in &service.methods {
+                let callers = if method.callers.is_empty() {
+                    lang.tr("service_api.no_callers").to_string()
+                } else {
+                    method.callers.join(", ")
+                };
+                output.push_str(&format!("- {} ← {}\n", method.signature(), callers));
+            }
+            output.push('\n');
+        }
+
+        let unused: Vec<_> = report.unused().collect();
+        if !unused.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("service_api.unused")));
+            for (service, method) in unused {
+                output.push_str(&format!("- {}.{}\n", service.name, method.name));
+            }
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    pub fn format_service_api_json(&self, report: &ServiceApiReport) -> Result<String> {
+        let combined_output = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "report": report,
+            "summary": {
+                "total_services": report.services.len(),
+                "total_methods": report.total_methods(),
+                "unused_methods": report.unused().map(|(service, method)| format!("{}.{}", service.name, method.name)).collect::<Vec<_>>()
+            }
+        });
+
+        Ok(serde_json::to_string_pretty(&combined_output)?)
+    }
+
+    pub fn format_impact_table(&self, report: &ImpactReport) -> Result<String> {
+        let mut output = String::new();
+
+        let lang = self.lang;
+        output.push_str(&format!("{}\n\n", lang.trf("impact.title", &[&report.target])));
+
+        output.push_str(&format!("{}\n", lang.tr("graph.table.summary")));
+        output.push_str(&format!("{}\n", lang.trf("impact.table.targets", &[&report.targets.len()])));
+        output.push_str(&format!("{}\n", lang.trf("impact.table.affected", &[&report.affected.len()])));
+        output.push_str(&format!("{}\n", lang.trf("impact.table.rebuild", &[&format_bytes(report.rebuild_bytes)])));
+        output.push_str(&format!("{}\n", lang.trf("impact.table.tests", &[&report.tests.len()])));
+        output.push_str(&format!("{}\n", lang.trf("impact.table.chunks", &[&report.chunks.len()])));
+        output.push_str(&format!("{}\n", lang.trf("impact.table.risk", &[&report.risk])));
+        output.push('\n');
+
+        output.push_str(&format!("{}\n", lang.tr("impact.targets_heading")));
+        for target in &report.targets {
+            output.push_str(&format!("- {}\n", target));
+        }
+        output.push('\n');
+
+        if !report.affected.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("impact.affected_heading")));
+            for file in &report.affected {
+                output.push_str(&format!("{}\n", lang.trf("impact.affected_file", &[&file.file_path, &file.depth, &file.via])));
+            }
+            output.push('\n');
+        }
+
+        if !report.tests.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("impact.tests_heading")));
+            for test in &report.tests {
+                output.push_str(&format!("- {}\n", test));
+            }
+            output.push('\n');
+        }
+
+        if !report.projects.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("impact.projects_heading")));
+            for project in &report.projects {
+                output.push_str(&format!("- {}\n", project));
+            }
+            output.push('\n');
+        }
+
+        if !report.chunks.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("impact.chunks_heading")));
+            for chunk in &report.chunks {
+                let files = lang.trf("impact.chunk_files", &[&chunk.files]);
+                match (&chunk.route_file, chunk.line) {
+                    (Some(route_file), Some(line)) => output.push_str(&format!("- {} ({}:{}): {}\n", chunk.name, route_file, line, files)),
+                    (Some(route_file), None) => output.push_str(&format!("- {} ({}): {}\n", chunk.name, route_file, files)),
+                    (None, _) => output.push_str(&format!("- {}: {}\n", chunk.name, files)),
+                }
+            }
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    pub fn format_impact_mermaid(&self, report: &ImpactReport) -> Result<String> {
+        let mut output = String::new();
+
+        output.push_str("graph RL\n");
+
+        for file in report.targets.iter().chain(report.affected.iter().map(|file| &file.file_path)) {
+            output.push_str(&format!("    {}[\"{}\"]\n", self.sanitize_node_id(file), file));
+        }
+
+        output.push('\n');
+
+        for (from, to) in &report.edges {
+            output.push_str(&format!("    {} --> {}\n", self.sanitize_node_id(from), self.sanitize_node_id(to)));
+        }
+
+        let targets: Vec<String> = report.targets.iter().map(|file| self.sanitize_node_id(file)).collect();
+        output.push('\n');
+        output.push_str("    classDef target fill:#f8d7da,stroke:#721c24\n");
+        output.push_str(&format!("    class {} target\n", targets.join(",")));
+
+        Ok(output)
+    }
+
+    pub fn format_impact_json(&self, report: &ImpactReport) -> Result<String> {
+        let combined_output = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "report": report,
+            "summary": {
+                "targets": report.targets.len(),
+                "affected_files": report.affected.len(),
+                "rebuild_bytes": report.rebuild_bytes,
+                "tests": report.tests.len(),
+                "chunks": report.chunks.len(),
+                "risk": report.risk
+            }
+        });
+
+        Ok(serde_json::to_string_pretty(&combined_output)?)
+    }
+
+    pub fn format_usages_table(&self, report: &UsageReport) -> Result<String> {
+        let mut output = String::new();
+
+        let lang = self.lang;
+        output.push_str(&format!("{}\n\n", lang.trf("usages.title", &[&report.symbol])));
+
+        output.push_str(&format!("{}\n", lang.tr("usages.targets")));
+        if report.targets.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("usages.no_target")));
+        }
+        for target in &report.targets {
+            match &target.selector {
+                Some(selector) => output.push_str(&format!("- {} {} <{}> ({})\n", target.kind, target.name, selector, target.file_path)),
+                None => output.push_str(&format!("- {} {} ({})\n", target.kind, target.name, target.file_path)),
+            }
+        }
+        output.push('\n');
+
+        let labels = ["template", "import", "injection", "route"].map(|kind| (kind, lang.tr(&format!("usages.kind.{}", kind))));
+        output.push_str(&format!("{}\n", lang.tr("graph.table.summary")));
+        for (kind, label) in labels {
+            output.push_str(&format!("- {}: {}\n", label, report.count(kind)));
+        }
+        output.push('\n');
+
+        for (kind, label) in labels.into_iter().filter(|(kind, _)| report.count(kind) > 0) {
+            output.push_str(&format!("## {}\n", label));
+            for usage in report.usages.iter().filter(|usage| usage.kind == kind) {
+                let location = match usage.line {
+                    Some(line) => format!("{}:{}", usage.file_path, line),
+                    None => usage.file_path.clone(),
+                };
+                match &usage.owner {
+                    Some(owner) => output.push_str(&format!("- {} ({})\n  {}\n", location, owner, usage.text)),
+                    None => output.push_str(&format!("- {}\n  {}\n", location, usage.text)),
+                }
+            }
+            output.push('\n');
+        }
+
+        Ok(output)
+    }
+
+    pub fn format_usages_json(&self, report: &UsageReport) -> Result<String> {
+        let combined_output = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "report": report,
+            "summary": {
+                "total_usages": report.usages.len(),
+                "templates": report.count("template"),
+                "imports": report.count("import"),
+                "injections": report.count("injection"),
+                "routes": report.count("route")
+            }
+        });
+
+        Ok(serde_json::to_string_pretty(&combined_output)?)
+    }
+
+    pub fn format_library_api_table(&self, report: &LibraryApiReport) -> Result<String> {
+        let mut output = String::new();
+
+        let lang = self.lang;
+        output.push_str(&format!("{}\n\n", lang.tr("library.title")));
+        if report.libraries.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("library.none")));
+        }
+
+        for library in &report.libraries {
+            output.push_str(&format!("## {} ({})\n", library.name, library.root));
+            output.push_str(&format!("{}\n", lang.trf("library.entry_file", &[&library.entry_file])));
+            output.push_str(&format!("{}\n", lang.trf("library.public_symbols", &[&library.public_api.len()])));
+            output.push_str(&format!("{}\n", lang.trf("library.deep_imports", &[&library.deep_imports.len()])));
+            output.push_str(&format!("{}\n\n", lang.trf("library.private_imports", &[&library.private_imports.len()])));
+
+            if !library.public_api.is_empty() {
+                output.push_str(&format!("{}\n", lang.tr("library.public_api_heading")));
+                for symbol in &library.public_api {
+                    output.push_str(&format!("- {} ({})\n", symbol.name, symbol.file_path));
+                }
+                output.push('\n');
+            }
+
+            if !library.deep_imports.is_empty() {
+                output.push_str(&format!("{}\n", lang.tr("library.deep_imports_heading")));
+                for deep in &library.deep_imports {
+                    let target = deep.target_file.as_deref().unwrap_or(lang.tr("library.unresolved"));
+                    output.push_str(&format!("- {}: '{}' -> {}\n", deep.file_path, deep.source_module, target));
+                    if !deep.symbols.is_empty() {
+                        output.push_str(&format!("  {}\n", deep.symbols.join(", ")));
+                    }
+                }
+                output.push('\n');
+            }
+
+            if !library.private_imports.is_empty() {
+                output.push_str(&format!("{}\n", lang.tr("library.private_imports_heading")));
+                for private in &library.private_imports {
+                    output.push_str(&format!("- {} ({})\n", private.symbol, private.file_path));
+                    for importer in &private.importers {
+                        output.push_str(&format!("  <- {}\n", importer));
+                    }
+                }
+                output.push('\n');
+            }
+        }
+
+        Ok(output)
+    }
+
+    pub fn format_library_api_json(&self, report: &LibraryApiReport) -> Result<String> {
+        let combined_output = serde_json::json!({
+            "schema_version": SCHEMA_VERSION,
+            "report": report,
+            "summary": {
+                "libraries": report.libraries.len(),
+                "public_symbols": report.libraries.iter().map(|library| library.public_api.len()).sum::<usize>(),
+                "deep_imports": report.libraries.iter().map(|library| library.deep_imports.len()).sum::<usize>(),
+                "private_imports": report.libraries.iter().map(|library| library.private_imports.len()).sum::<usize>()
+            }
+        });
+
+        Ok(serde_json::to_string_pretty(&combined_output)?)
+    }
+
+    fn injection_node_label(&self, node: &InjectionNode) -> String {
+        let mut label = node.name.clone();
+        if !node.injectors.is_empty() {
+            label.push_str(&format!(" ({})", node.injectors.join(", ")));
+        }
+        if let Some(feature) = &node.single_feature {
+            label.push_str(&format!(" only {}", feature));
+        }
+        label
+    }
+
+    fn file_color(&self, file: &FileInfo) -> &'static str {
+        match file.file_type {
+            crate::ast::FileType::TypeScript => "lightblue",
+            crate::ast::FileType::JavaScript => "lightgreen",
+            crate::ast::FileType::Declaration => "lightyellow",
+            crate::ast::FileType::Module => "lightgray",
+            crate::ast::FileType::External => "white",
+        }
+    }
+
+    fn is_circular_dependency(&self, analysis: &DependencyAnalysis, from: &str, to: &str) -> bool {
+        analysis.circular_dependencies.iter().any(|circular| {
+            circular.cycle.windows(2).any(|pair| pair[0] == from && pair[1] == to)
+        })
+    }
+
+    fn is_cycle_edge(&self, graph: &ModuleGraph, edge: &ModuleGraphEdge) -> bool {
+        graph.cycles.iter().any(|cycle| {
+            cycle.iter().zip(cycle.iter().cycle().skip(1)).any(|(from, to)| *from == edge.from && *to == edge.to)
+        })
+    }
+
+    fn component_edge_label(&self, edge: &ComponentTreeEdge) -> String {
+        if edge.count > 1 {
+            format!("{} x{}", edge.kind, edge.count)
+        } else {
+            edge.kind.clone()
+        }
+    }
+
+    // 外部パッケージはパッケージ名をそのまま表示する
+    fn file_label<'a>(&self, file: &'a FileInfo) -> &'a str {
+        if matches!(file.file_type, crate::ast::FileType::External) {
+            return &file.relative_path;
+        }
+        Path::new(&file.file_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&file.relative_path)
+    }
+
     fn sanitize_node_id(&self, id: &str) -> String {
         id.chars()
             .map(|c| if c.is_alphanumeric() || c == '_' { c } else { '_' })
@@ -247,6 +1168,17 @@ impl GraphFormatter {
     }
 }
 
+// 12345 -> "12.1 KB"
+fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
 impl Default for GraphFormatter {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,375 @@
+use crate::ast::{DependencyAnalysis, FileType, ImportExportGraph};
+use super::i18n::Lang;
+use anyhow::Result;
+use serde_json::json;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+// 外部のライブラリを読み込まない 1 ファイルの HTML。力学モデルで配置し、ズーム・検索・ディレクトリの絞り込み・循環の強調ができる
+pub fn render_graph_html(graph: &ImportExportGraph, analysis: &DependencyAnalysis, lang: Lang) -> Result<String> {
+    let index: HashMap<&str, usize> = graph.files.iter().enumerate().map(|(i, file)| (file.id.as_str(), i)).collect();
+
+    let nodes: Vec<_> = graph.files.iter()
+        .map(|file| {
+            let path = file.relative_path.replace('\\', "/");
+            json!({
+                "label": Path::new(&path).file_name().and_then(|name| name.to_str()).unwrap_or(&path),
+                "path": path,
+                "dir": directory(&path, &file.file_type),
+                "type": file_type_name(&file.file_type),
+            })
+        })
+        .collect();
+
+    let edges: Vec<_> = graph.dependencies.iter()
+        .filter_map(|dependency| {
+            let source = index.get(dependency.from_file.as_str())?;
+            let target = index.get(dependency.to_file.as_str())?;
+            Some(json!({ "source": source, "target": target, "symbols": dependency.imported_symbols.join(", ") }))
+        })
+        .collect();
+
+    let cycles: Vec<_> = analysis.circular_dependencies.iter()
+        .map(|circular| {
+            let files: Vec<usize> = circular.cycle.iter().filter_map(|id| index.get(id.as_str()).copied()).collect();
+            json!({ "files": files, "severity": format!("{:?}", circular.severity) })
+        })
+        .collect();
+
+    let directories: BTreeSet<String> = graph.files.iter()
+        .map(|file| directory(&file.relative_path.replace('\\', "/"), &file.file_type))
+        .collect();
+
+    let data = json!({ "nodes": nodes, "edges": edges, "cycles": cycles, "directories": directories });
+    // </script> で埋め込みが途切れないようにする
+    let data = serde_json::to_string(&data)?.replace("</", "<\\/");
+
+    Ok(format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"UTF-8\">\n<title>Dependency Graph</title>\n{}\n</head>\n<body>\n{}\n<script>\nconst GRAPH = {};\n{}\n</script>\n</body>\n</html>\n",
+        lang.code(), STYLE, body(lang), data, SCRIPT
+    ))
+}
+
+// 絞り込みに使うディレクトリ。深すぎると選択肢が増えるので先頭の 2 階層まで
+fn directory(path: &str, file_type: &FileType) -> String {
+    if matches!(file_type, FileType::External) {
+        return "(external)".to_string();
+    }
+    let parts: Vec<&str> = path.split('/').collect();
+    match parts.len() {
+        0 | 1 => ".".to_string(),
+        len => parts[..(len - 1).min(2)].join("/"),
+    }
+}
+
+fn file_type_name(file_type: &FileType) -> &'static str {
+    match file_type {
+        FileType::TypeScript => "typescript",
+        FileType::JavaScript => "javascript",
+        FileType::Declaration => "declaration",
+        FileType::Module => "module",
+        FileType::External => "external",
+    }
+}
+
+const STYLE: &str = r#"<style>
+  html, body { margin: 0; height: 100%; font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; font-size: 13px; color: #333; }
+  body { display: flex; }
+  #sidebar { width: 280px; padding: 12px; box-sizing: border-box; border-right: 1px solid #ddd; overflow-y: auto; background: #fafafa; }
+  #sidebar h2 { font-size: 14px; margin: 16px 0 6px; }
+  #sidebar input, #sidebar select { width: 100%; box-sizing: border-box; padding: 4px; margin-bottom: 6px; }
+  #canvas-wrap { flex: 1; position: relative; }
+  canvas { display: block; width: 100%; height: 100%; cursor: grab; }
+  #info { position: absolute; left: 12px; bottom: 12px; background: rgba(255,255,255,0.9); border: 1px solid #ddd; padding: 6px 8px; max-width: 60%; }
+  .legend span { display: inline-block; width: 10px; height: 10px; margin-right: 4px; border: 1px solid #999; }
+  .cycle { padding: 4px 6px; margin-bottom: 4px; border: 1px solid #e0b4b4; background: #fff6f6; cursor: pointer; word-break: break-all; }
+  .cycle.active { background: #f8d7da; border-color: #d9534f; }
+  .muted { color: #888; }
+</style>"#;
+
+fn body(lang: Lang) -> String {
+    format!(
+        r#"<div id="sidebar">
+  <h2>{}</h2>
+  <input id="search" type="search" placeholder="{}">
+  <h2>{}</h2>
+  <select id="directory"><option value="">{}</option></select>
+  <h2>{}</h2>
+  <div class="legend" id="legend"></div>
+  <h2>{} <span id="cycle-count" class="muted"></span></h2>
+  <div id="cycles"></div>
+  <p class="muted">{}</p>
+</div>
+<div id="canvas-wrap">
+  <canvas id="graph"></canvas>
+  <div id="info"></div>
+</div>"#,
+        lang.tr("graph.html.search"),
+        lang.tr("graph.html.search_placeholder"),
+        lang.tr("graph.html.directory"),
+        lang.tr("graph.html.all"),
+        lang.tr("graph.html.legend"),
+        lang.tr("graph.html.cycles"),
+        lang.tr("graph.html.help")
+    )
+}
+
+const SCRIPT: &str = r#"(() => {
+  const COLORS = { typescript: '#9ecae1', javascript: '#a1d99b', declaration: '#fdf6b2', module: '#d9d9d9', external: '#ffffff' };
+  const canvas = document.getElementById('graph');
+  const ctx = canvas.getContext('2d');
+  const info = document.getElementById('info');
+  const nodes = GRAPH.nodes.map((node, i) => ({ ...node, index: i, x: Math.cos(i) * 10 * Math.sqrt(i + 1), y: Math.sin(i) * 10 * Math.sqrt(i + 1), vx: 0, vy: 0 }));
+  const edges = GRAPH.edges;
+  const view = { x: 0, y: 0, scale: 1 };
+  let search = '';
+  let directory = '';
+  let activeCycle = null;
+  let alpha = 1;
+
+  const legend = document.getElementById('legend');
+  Object.entries(COLORS).forEach(([type, color]) => {
+    legend.insertAdjacentHTML('beforeend', `<div><span style="background:${color}"></span>${type}</div>`);
+  });
+  const select = document.getElementById('directory');
+  GRAPH.directories.forEach(dir => select.insertAdjacentHTML('beforeend', `<option>${escapeHtml(dir)}</option>`));
+  const cycleList = document.getElementById('cycles');
+  document.getElementById('cycle-count').textContent = `(${GRAPH.cycles.length})`;
+  GRAPH.cycles.forEach((cycle, i) => {
+    const item = document.createElement('div');
+    item.className = 'cycle';
+    item.textContent = `${cycle.severity}: ` + [...new Set(cycle.files)].map(file => nodes[file].label).join(' → ');
+    item.addEventListener('click', () => {
+      activeCycle = activeCycle === i ? null : i;
+      document.querySelectorAll('.cycle').forEach((el, j) => el.classList.toggle('active', j === activeCycle));
+      if (activeCycle !== null) focusOn(cycle.files.map(file => nodes[file]));
+      draw();
+    });
+    cycleList.appendChild(item);
+  });
+
+  function escapeHtml(text) {
+    return text.replace(/[&<>"']/g, c => ({ '&': '&amp;', '<': '&lt;', '>': '&gt;', '"': '&quot;', "'": '&#39;' }[c]));
+  }
+
+  function visible(node) {
+    return !directory || node.dir === directory || node.dir.startsWith(directory + '/');
+  }
+
+  function matches(node) {
+    return search && node.path.toLowerCase().includes(search);
+  }
+
+  function cycleEdges() {
+    const set = new Set();
+    if (activeCycle === null) return set;
+    const files = GRAPH.cycles[activeCycle].files;
+    for (let i = 0; i + 1 < files.length; i++) set.add(files[i] + ':' + files[i + 1]);
+    return set;
+  }
+
+  // 斥力・辺のばね・中心への引力だけの簡単な力学モデル
+  function tick() {
+    const shown = nodes.filter(visible);
+    for (let i = 0; i < shown.length; i++) {
+      for (let j = i + 1; j < shown.length; j++) {
+        const a = shown[i], b = shown[j];
+        let dx = a.x - b.x, dy = a.y - b.y;
+        const dist2 = Math.max(dx * dx + dy * dy, 1);
+        const force = 800 / dist2;
+        dx *= force; dy *= force;
+        a.vx += dx; a.vy += dy; b.vx -= dx; b.vy -= dy;
+      }
+    }
+    edges.forEach(edge => {
+      const a = nodes[edge.source], b = nodes[edge.target];
+      if (!visible(a) || !visible(b)) return;
+      const dx = b.x - a.x, dy = b.y - a.y;
+      const dist = Math.max(Math.sqrt(dx * dx + dy * dy), 1);
+      const force = (dist - 80) * 0.02 / dist;
+      a.vx += dx * force; a.vy += dy * force; b.vx -= dx * force; b.vy -= dy * force;
+    });
+    shown.forEach(node => {
+      if (node === dragging) return;
+      node.vx -= node.x * 0.002; node.vy -= node.y * 0.002;
+      node.x += Math.max(-20, Math.min(20, node.vx * alpha));
+      node.y += Math.max(-20, Math.min(20, node.vy * alpha));
+      node.vx *= 0.6; node.vy *= 0.6;
+    });
+    alpha *= 0.985;
+  }
+
+  function resize() {
+    canvas.width = canvas.clientWidth * devicePixelRatio;
+    canvas.height = canvas.clientHeight * devicePixelRatio;
+    draw();
+  }
+
+  function toScreen(node) {
+    return [(node.x - view.x) * view.scale + canvas.clientWidth / 2, (node.y - view.y) * view.scale + canvas.clientHeight / 2];
+  }
+
+  function toWorld(x, y) {
+    return [(x - canvas.clientWidth / 2) / view.scale + view.x, (y - canvas.clientHeight / 2) / view.scale + view.y];
+  }
+
+  function draw() {
+    ctx.setTransform(devicePixelRatio, 0, 0, devicePixelRatio, 0, 0);
+    ctx.clearRect(0, 0, canvas.clientWidth, canvas.clientHeight);
+    const highlighted = cycleEdges();
+    const cycleFiles = new Set(activeCycle === null ? [] : GRAPH.cycles[activeCycle].files);
+    const dimmed = node => (activeCycle !== null && !cycleFiles.has(node.index)) || (search && !matches(node));
+
+    edges.forEach(edge => {
+      const a = nodes[edge.source], b = nodes[edge.target];
+      if (!visible(a) || !visible(b)) return;
+      const [x1, y1] = toScreen(a), [x2, y2] = toScreen(b);
+      const hot = highlighted.has(edge.source + ':' + edge.target);
+      ctx.strokeStyle = hot ? '#d9534f' : (dimmed(a) && dimmed(b) ? 'rgba(0,0,0,0.05)' : 'rgba(0,0,0,0.25)');
+      ctx.lineWidth = hot ? 3 : 1;
+      ctx.beginPath(); ctx.moveTo(x1, y1); ctx.lineTo(x2, y2); ctx.stroke();
+      // 矢印
+      const angle = Math.atan2(y2 - y1, x2 - x1);
+      const [tx, ty] = [x2 - Math.cos(angle) * 7, y2 - Math.sin(angle) * 7];
+      ctx.beginPath();
+      ctx.moveTo(tx, ty);
+      ctx.lineTo(tx - Math.cos(angle - 0.4) * 6, ty - Math.sin(angle - 0.4) * 6);
+      ctx.lineTo(tx - Math.cos(angle + 0.4) * 6, ty - Math.sin(angle + 0.4) * 6);
+      ctx.closePath();
+      ctx.fillStyle = ctx.strokeStyle;
+      ctx.fill();
+    });
+
+    nodes.forEach(node => {
+      if (!visible(node)) return;
+      const [x, y] = toScreen(node);
+      ctx.globalAlpha = dimmed(node) ? 0.2 : 1;
+      ctx.beginPath(); ctx.arc(x, y, 6, 0, Math.PI * 2);
+      ctx.fillStyle = COLORS[node.type] || '#fff';
+      ctx.fill();
+      ctx.strokeStyle = matches(node) || cycleFiles.has(node.index) ? '#d9534f' : '#555';
+      ctx.lineWidth = matches(node) || cycleFiles.has(node.index) ? 2 : 1;
+      ctx.stroke();
+      if (view.scale > 0.6 || matches(node) || cycleFiles.has(node.index)) {
+        ctx.fillStyle = '#333';
+        ctx.fillText(node.label, x + 8, y + 4);
+      }
+      ctx.globalAlpha = 1;
+    });
+  }
+
+  function focusOn(targets) {
+    if (targets.length === 0) return;
+    view.x = targets.reduce((sum, node) => sum + node.x, 0) / targets.length;
+    view.y = targets.reduce((sum, node) => sum + node.y, 0) / targets.length;
+    view.scale = Math.max(view.scale, 1);
+  }
+
+  function nodeAt(x, y) {
+    const [wx, wy] = toWorld(x, y);
+    const radius = 8 / view.scale;
+    return nodes.find(node => visible(node) && Math.abs(node.x - wx) < radius && Math.abs(node.y - wy) < radius);
+  }
+
+  let dragging = null;
+  let panning = null;
+  canvas.addEventListener('mousedown', event => {
+    dragging = nodeAt(event.offsetX, event.offsetY) || null;
+    if (!dragging) panning = { x: event.offsetX, y: event.offsetY, viewX: view.x, viewY: view.y };
+  });
+  canvas.addEventListener('mousemove', event => {
+    if (dragging) {
+      [dragging.x, dragging.y] = toWorld(event.offsetX, event.offsetY);
+      alpha = Math.max(alpha, 0.3);
+    } else if (panning) {
+      view.x = panning.viewX - (event.offsetX - panning.x) / view.scale;
+      view.y = panning.viewY - (event.offsetY - panning.y) / view.scale;
+    } else {
+      const node = nodeAt(event.offsetX, event.offsetY);
+      if (node) {
+        const imports = edges.filter(edge => edge.source === node.index).length;
+        const importedBy = edges.filter(edge => edge.target === node.index).length;
+        info.textContent = `${node.path} (${node.type}) — imports ${imports}, imported by ${importedBy}`;
+      }
+    }
+    draw();
+  });
+  window.addEventListener('mouseup', () => { dragging = null; panning = null; });
+  canvas.addEventListener('wheel', event => {
+    event.preventDefault();
+    const [wx, wy] = toWorld(event.offsetX, event.offsetY);
+    view.scale = Math.max(0.05, Math.min(8, view.scale * (event.deltaY < 0 ? 1.1 : 1 / 1.1)));
+    const [nx, ny] = toWorld(event.offsetX, event.offsetY);
+    view.x += wx - nx; view.y += wy - ny;
+    draw();
+  }, { passive: false });
+
+  document.getElementById('search').addEventListener('input', event => {
+    search = event.target.value.trim().toLowerCase();
+    if (search) focusOn(nodes.filter(node => visible(node) && matches(node)));
+    draw();
+  });
+  select.addEventListener('change', event => {
+    directory = event.target.value;
+    alpha = 1;
+  });
+
+  function loop() {
+    if (alpha > 0.01) {
+      tick();
+      draw();
+    }
+    requestAnimationFrame(loop);
+  }
+
+  window.addEventListener('resize', resize);
+  info.textContent = `${nodes.length} files, ${edges.length} dependencies, ${GRAPH.cycles.length} cycles`;
+  view.scale = nodes.length > 200 ? 0.4 : 1;
+  resize();
+  loop();
+})();"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{CircularDependency, CycleSeverity, Dependency, FileInfo, ImportType};
+
+    #[test]
+    fn test_render_graph_html() {
+        let file = |id: &str, path: &str| FileInfo {
+            id: id.to_string(),
+            file_path: format!("/repo/{}", path),
+            relative_path: path.to_string(),
+            file_type: FileType::TypeScript,
+            exports: vec![],
+            imports: vec![],
+        };
+        let dependency = |from: &str, to: &str| Dependency {
+            from_file: from.to_string(),
+            to_file: to.to_string(),
+            import_type: ImportType::Named,
+            imported_symbols: vec!["</script>".to_string()],
+            line_number: None,
+        };
+        let graph = ImportExportGraph {
+            files: vec![file("file_0", "app/core/a.ts"), file("file_1", "app/core/b.ts"), file("file_2", "main.ts")],
+            dependencies: vec![dependency("file_0", "file_1"), dependency("file_1", "file_0"), dependency("file_2", "file_0")],
+            ..Default::default()
+        };
+        let analysis = DependencyAnalysis {
+            circular_dependencies: vec![CircularDependency {
+                cycle: vec!["file_0".to_string(), "file_1".to_string(), "file_0".to_string()],
+                severity: CycleSeverity::Warning,
+            }],
+            ..Default::default()
+        };
+
+        let html = render_graph_html(&graph, &analysis, Lang::Ja).unwrap();
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert_eq!(html.matches("</script>").count(), 1);
+        assert!(html.contains(r#""directories":[".","app/core"]"#));
+        assert!(html.contains(r#""cycles":[{"files":[0,1,0],"severity":"Warning"}]"#));
+        assert!(html.contains("<html lang=\"ja\">") && html.contains("<h2>循環依存 "));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::ast::{AnalysisResult, Issue, Severity};
+use crate::config::relative_issue_path;
+use crate::output::i18n::Lang;
+use anyhow::Result;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+// --group-by で選ぶ、テーブルと HTML で issue をまとめる単位
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IssueGrouping {
+    Rule,
+    File,
+    Directory,
+}
+
+impl IssueGrouping {
+    pub fn parse(grouping: &str, lang: Lang) -> Result<Self> {
+        match grouping {
+            "rule" => Ok(Self::Rule),
+            "file" => Ok(Self::File),
+            "directory" => Ok(Self::Directory),
+            _ => Err(anyhow::anyhow!("{}", lang.trf("error.unsupported", &[&"--group-by", &grouping, &"rule, file, directory"]))),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Rule => "rule",
+            Self::File => "file",
+            Self::Directory => "directory",
+        }
+    }
+
+    fn key(&self, issue: &Issue, root_path: &Path) -> String {
+        match self {
+            Self::Rule => issue.rule.clone(),
+            Self::File => relative_issue_path(&issue.file_path, root_path),
+            Self::Directory => {
+                let path = relative_issue_path(&issue.file_path, root_path);
+                match path.rsplit_once('/') {
+                    Some((directory, _)) => directory.to_string(),
+                    None => ".".to_string(),
+                }
+            }
+        }
+    }
+}
+
+pub struct IssueGroup<'a> {
+    pub key: String,
+    pub issues: Vec<&'a Issue>,
+}
+
+impl IssueGroup<'_> {
+    pub fn count(&self, severity: Severity) -> usize {
+        self.issues.iter().filter(|issue| issue.severity == severity).count()
+    }
+}
+
+// 件数の多い順。同数ならキーの順
+pub fn group_issues<'a>(issues: &'a [Issue], grouping: IssueGrouping, root_path: &Path) -> Vec<IssueGroup<'a>> {
+    let mut groups: BTreeMap<String, Vec<&Issue>> = BTreeMap::new();
+    for issue in issues {
+        groups.entry(grouping.key(issue, root_path)).or_default().push(issue);
+    }
+    let mut groups: Vec<IssueGroup> = groups.into_iter().map(|(key, issues)| IssueGroup { key, issues }).collect();
+    groups.sort_by(|a, b| b.issues.len().cmp(&a.issues.len()).then_with(|| a.key.cmp(&b.key)));
+    groups
+}
+
+// ルール・ファイル・位置・メッセージが同じなら同じ issue とみなす
+pub fn duplicate_key(issue: &Issue) -> (String, String, Option<u32>, Option<u32>, String) {
+    (issue.rule.clone(), issue.file_path.clone(), issue.line, issue.column, issue.message.clone())
+}
+
+// 複数のアナライザーが同じ issue を出したら、最初のアナライザーの分だけ残す。消した件数を返す
+pub fn collapse_duplicate_issues(results: &mut [AnalysisResult]) -> usize {
+    let mut seen = HashSet::new();
+    let mut removed = 0;
+    for result in results.iter_mut() {
+        let before = result.issues.len();
+        result.issues.retain(|issue| seen.insert(duplicate_key(issue)));
+        removed += before - result.issues.len();
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::IssueBuilder;
+    use crate::ast::ProjectMetrics;
+
+    fn issue(severity: Severity, rule: &str, file_path: &str) -> Issue {
+        IssueBuilder::new(rule, file_path).severity(severity).message(&format!("{} in {}", rule, file_path)).build()
+    }
+
+    #[test]
+    fn test_group_and_collapse_issues() {
+        let issues = vec![
+            issue(Severity::Info, "change-detection-strategy", "/app/src/users/list.component.ts"),
+            issue(Severity::Info, "change-detection-strategy", "/app/src/users/card.component.ts"),
+            issue(Severity::Warning, "component-complexity", "/app/src/users/list.component.ts"),
+            issue(Severity::Error, "environment-secret", "/app/src/environment.ts"),
+        ];
+        let root = Path::new("/app");
+
+        let by_rule: Vec<(String, usize)> = group_issues(&issues, IssueGrouping::Rule, root).into_iter().map(|group| (group.key, group.issues.len())).collect();
+        assert_eq!(by_rule, vec![
+            ("change-detection-strategy".to_string(), 2),
+            ("component-complexity".to_string(), 1),
+            ("environment-secret".to_string(), 1),
+        ]);
+        let by_directory = group_issues(&issues, IssueGrouping::Directory, root);
+        assert_eq!((by_directory[0].key.as_str(), by_directory[0].count(Severity::Info)), ("src/users", 2));
+        assert_eq!(group_issues(&issues, IssueGrouping::File, root)[0].key, "src/users/list.component.ts");
+
+        let result = |issues: Vec<Issue>| AnalysisResult {
+            project: Default::default(),
+            issues,
+            metrics: ProjectMetrics::default(),
+            recommendations: vec![],
+            omitted_issues: vec![],
+        };
+        let mut results = vec![result(issues[..2].to_vec()), result(issues[1..].to_vec())];
+        assert_eq!(collapse_duplicate_issues(&mut results), 1);
+        assert_eq!((results[0].issues.len(), results[1].issues.len()), (2, 2));
+    }
+}
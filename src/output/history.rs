@@ -0,0 +1,256 @@
+use crate::ast::{ChangeDetectionStrategy, NgProject};
+use crate::output::i18n::Lang;
+use crate::output::summary::RunSummary;
+use crate::output::svg::escape_xml;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// --history のファイルに 1 行ずつ追記する、1 回の実行の指標
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    // UTC の RFC 3339 (2024-05-01T09:30:00Z)
+    pub timestamp: String,
+    pub analyzers: Vec<String>,
+    pub files: usize,
+    pub components: usize,
+    pub average_complexity: f64,
+    // コンポーネントがなければ null
+    pub onpush_percentage: Option<f64>,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+}
+
+impl HistoryEntry {
+    // 実行した解析器に関係なく比べられるよう、複雑度と OnPush はプロジェクトから直接数える
+    pub fn new(project: &NgProject, summary: &RunSummary, analyzers: &[String], time: SystemTime) -> Self {
+        let components = &project.components;
+        let average_complexity = if components.is_empty() {
+            0.0
+        } else {
+            components.iter().map(|c| c.complexity_score as f64).sum::<f64>() / components.len() as f64
+        };
+        let on_push = components.iter().filter(|c| matches!(c.change_detection, ChangeDetectionStrategy::OnPush)).count();
+
+        Self {
+            timestamp: utc_timestamp(time),
+            analyzers: analyzers.to_vec(),
+            files: summary.files,
+            components: components.len(),
+            average_complexity,
+            onpush_percentage: (!components.is_empty()).then(|| on_push as f64 * 100.0 / components.len() as f64),
+            errors: summary.errors,
+            warnings: summary.warnings,
+            infos: summary.infos,
+        }
+    }
+}
+
+pub fn append_history(path: &Path, entry: &HistoryEntry) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(entry)?)?;
+    Ok(())
+}
+
+// 空行は読み飛ばす。壊れた行は何行目かを示して失敗する
+pub fn read_history(path: &Path, lang: Lang) -> Result<Vec<HistoryEntry>> {
+    let content = std::fs::read_to_string(path).with_context(|| lang.trf("error.history_unreadable", &[&path.display()]))?;
+    content.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).with_context(|| lang.trf("error.history_entry", &[&path.display(), &(index + 1)]))
+        })
+        .collect()
+}
+
+// グラフにする系列。値のない回は None
+fn series(entries: &[HistoryEntry], lang: Lang) -> Vec<(&'static str, Vec<Option<f64>>)> {
+    let values = |value: fn(&HistoryEntry) -> Option<f64>| entries.iter().map(value).collect::<Vec<_>>();
+    vec![
+        (lang.tr("trends.series.complexity"), values(|entry| Some(entry.average_complexity))),
+        (lang.tr("trends.series.onpush"), values(|entry| entry.onpush_percentage)),
+        (lang.tr("trends.series.errors"), values(|entry| Some(entry.errors as f64))),
+        (lang.tr("trends.series.warnings"), values(|entry| Some(entry.warnings as f64))),
+        (lang.tr("trends.series.infos"), values(|entry| Some(entry.infos as f64))),
+        (lang.tr("trends.series.components"), values(|entry| Some(entry.components as f64))),
+    ]
+}
+
+// 最小値から最大値までを 8 段階で表す。値がすべて同じなら一番低い段で描く
+pub fn sparkline(values: &[Option<f64>]) -> String {
+    let present = values.iter().flatten();
+    let min = present.clone().cloned().fold(f64::INFINITY, f64::min);
+    let max = present.cloned().fold(f64::NEG_INFINITY, f64::max);
+    values.iter()
+        .map(|value| match value {
+            Some(value) if max > min => {
+                let level = ((value - min) / (max - min) * (SPARK_CHARS.len() - 1) as f64).round() as usize;
+                SPARK_CHARS[level.min(SPARK_CHARS.len() - 1)]
+            }
+            Some(_) => SPARK_CHARS[0],
+            None => ' ',
+        })
+        .collect()
+}
+
+pub fn format_trends_text(entries: &[HistoryEntry], lang: Lang) -> String {
+    let (Some(first), Some(last)) = (entries.first(), entries.last()) else {
+        return format!("{}\n", lang.tr("trends.empty"));
+    };
+
+    let range = lang.trf("trends.range", &[&entries.len(), &first.timestamp, &last.timestamp]);
+    let mut output = format!("{}\n\n", lang.trf("trends.heading", &[&range]));
+    let series = series(entries, lang);
+    let width = series.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+    for (name, values) in &series {
+        let present: Vec<f64> = values.iter().flatten().cloned().collect();
+        let change = match (present.first(), present.last()) {
+            (Some(first), Some(last)) => format!("{} -> {} ({:+.1})", format_value(*first), format_value(*last), last - first),
+            _ => "-".to_string(),
+        };
+        output.push_str(&format!("{:<width$}  {}  {}\n", name, sparkline(values), change, width = width));
+    }
+    output
+}
+
+// 外部のライブラリを読み込まない 1 ファイルの HTML。系列ごとに折れ線の SVG を並べる
+pub fn render_trends_html(entries: &[HistoryEntry], lang: Lang) -> String {
+    let charts: String = series(entries, lang).iter()
+        .map(|(name, values)| format!("<section>\n<h2>{}</h2>\n{}\n</section>\n", escape_xml(name), line_chart(entries, values)))
+        .collect();
+    let range = match (entries.first(), entries.last()) {
+        (Some(first), Some(last)) => lang.trf("trends.range", &[&entries.len(), &first.timestamp, &last.timestamp]),
+        _ => lang.tr("trends.empty").to_string(),
+    };
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"UTF-8\">\n<title>{}</title>\n{}\n</head>\n<body>\n<h1>{}</h1>\n<p>{}</p>\n{}</body>\n</html>\n",
+        lang.code(),
+        lang.tr("trends.title"),
+        STYLE,
+        lang.tr("trends.title"),
+        escape_xml(&range),
+        charts
+    )
+}
+
+fn line_chart(entries: &[HistoryEntry], values: &[Option<f64>]) -> String {
+    const WIDTH: f64 = 640.0;
+    const HEIGHT: f64 = 160.0;
+    const PADDING: f64 = 24.0;
+
+    let present: Vec<f64> = values.iter().flatten().cloned().collect();
+    let min = present.iter().cloned().fold(f64::INFINITY, f64::min).min(0.0);
+    let max = present.iter().cloned().fold(f64::NEG_INFINITY, f64::max).max(min + 1.0);
+    let step = if values.len() > 1 { (WIDTH - PADDING * 2.0) / (values.len() - 1) as f64 } else { 0.0 };
+    let point = |index: usize, value: f64| {
+        (PADDING + step * index as f64, HEIGHT - PADDING - (value - min) / (max - min) * (HEIGHT - PADDING * 2.0))
+    };
+
+    let mut points = Vec::new();
+    let mut dots = String::new();
+    for (index, value) in values.iter().enumerate() {
+        let Some(value) = value else { continue };
+        let (x, y) = point(index, *value);
+        points.push(format!("{:.1},{:.1}", x, y));
+        dots.push_str(&format!(
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"3\"><title>{}: {}</title></circle>",
+            x, y, escape_xml(&entries[index].timestamp), format_value(*value)
+        ));
+    }
+
+    format!(
+        "<svg viewBox=\"0 0 {w} {h}\" width=\"{w}\" height=\"{h}\"><text x=\"2\" y=\"{top}\">{max}</text><text x=\"2\" y=\"{bottom}\">{min}</text><polyline points=\"{points}\"/>{dots}</svg>",
+        w = WIDTH,
+        h = HEIGHT,
+        top = PADDING - 8.0,
+        bottom = HEIGHT - 4.0,
+        max = format_value(max),
+        min = format_value(min),
+        points = points.join(" "),
+        dots = dots
+    )
+}
+
+fn format_value(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+// chrono を使わずに UTC の日時にする (Howard Hinnant の civil_from_days)
+pub fn utc_timestamp(time: SystemTime) -> String {
+    let seconds = time.duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or(0) as i64;
+    let (days, rest) = (seconds.div_euclid(86_400), seconds.rem_euclid(86_400));
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", year, month, day, rest / 3_600, rest % 3_600 / 60, rest % 60)
+}
+
+const STYLE: &str = r#"<style>
+body { font-family: -apple-system, BlinkMacSystemFont, "Segoe UI", sans-serif; margin: 2rem; color: #222; }
+section { margin-bottom: 1.5rem; }
+h2 { font-size: 1rem; margin: 0 0 0.25rem; }
+svg { background: #fafafa; border: 1px solid #ddd; }
+svg text { font-size: 10px; fill: #777; }
+polyline { fill: none; stroke: #1976d2; stroke-width: 2; }
+circle { fill: #1976d2; }
+</style>"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::{ComponentBuilder, NgProjectBuilder};
+    use std::time::Duration;
+
+    #[test]
+    fn test_history_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("reports/history.jsonl");
+        let project = NgProjectBuilder::new(dir.path())
+            .component(ComponentBuilder::new("UserListComponent").complexity(8).on_push())
+            .component(ComponentBuilder::new("UserCardComponent").complexity(2))
+            .build();
+        let analyzers = vec!["component".to_string()];
+
+        let first = HistoryEntry::new(
+            &project,
+            &RunSummary { errors: 2, warnings: 5, infos: 1, files: 10, failed_budgets: 0 },
+            &analyzers,
+            UNIX_EPOCH + Duration::from_secs(1_714_555_800),
+        );
+        assert_eq!(first.timestamp, "2024-05-01T09:30:00Z");
+        assert_eq!((first.components, first.average_complexity, first.onpush_percentage), (2, 5.0, Some(50.0)));
+
+        let second = HistoryEntry { errors: 0, warnings: 3, ..first.clone() };
+        append_history(&path, &first).unwrap();
+        append_history(&path, &second).unwrap();
+        assert_eq!(read_history(&path, Lang::En).unwrap(), vec![first.clone(), second.clone()]);
+
+        assert_eq!(sparkline(&[Some(1.0), Some(5.0), None, Some(3.0)]), "▁█ ▅");
+        assert_eq!(sparkline(&[Some(2.0), Some(2.0)]), "▁▁");
+        let text = format_trends_text(&[first.clone(), second.clone()], Lang::En);
+        assert!(text.contains("Errors"));
+        assert!(text.contains("2 -> 0 (-2.0)"));
+        assert!(format_trends_text(&[first, second], Lang::Ja).starts_with("推移: 2 回の実行"));
+    }
+}
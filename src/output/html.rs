@@ -1,12 +1,19 @@
-use super::OutputFormatter;
-use crate::ast::{AnalysisResult, Severity};
+use super::{adoption_rows, budget_limit, budget_value, largest_components, largest_files, OutputFormatter};
+use super::drilldown::{DirectoryNode, DirectoryStats, FileEntry};
+use super::grouping::{group_issues, IssueGrouping};
+use super::i18n::Lang;
+use crate::ast::{AnalysisResult, BudgetResult, HealthScore, Issue, Severity};
 use anyhow::Result;
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 
 pub struct HtmlFormatter {
     include_css: bool,
     include_js: bool,
+    health: Option<HealthScore>,
+    budgets: Vec<BudgetResult>,
+    group_by: Option<IssueGrouping>,
+    lang: Lang,
 }
 
 impl HtmlFormatter {
@@ -14,6 +21,10 @@ impl HtmlFormatter {
         Self {
             include_css: true,
             include_js: false,
+            health: None,
+            budgets: Vec::new(),
+            group_by: None,
+            lang: Lang::En,
         }
     }
 
@@ -22,9 +33,33 @@ impl HtmlFormatter {
         Self {
             include_css: false,
             include_js: false,
+            health: None,
+            budgets: Vec::new(),
+            group_by: None,
+            lang: Lang::En,
         }
     }
 
+    pub fn with_health(mut self, health: Option<HealthScore>) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub fn with_budgets(mut self, budgets: Vec<BudgetResult>) -> Self {
+        self.budgets = budgets;
+        self
+    }
+
+    pub fn with_group_by(mut self, group_by: Option<IssueGrouping>) -> Self {
+        self.group_by = group_by;
+        self
+    }
+
+    pub fn with_lang(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
+    }
+
     fn generate_css(&self) -> &'static str {
         r#"
         <style>
@@ -57,6 +92,29 @@ impl HtmlFormatter {
                 opacity: 0.9;
                 margin-top: 10px;
             }
+
+            .health-score {
+                margin-top: 20px;
+                font-size: 1.5rem;
+            }
+
+            .health-grade {
+                display: inline-block;
+                width: 56px;
+                height: 56px;
+                line-height: 56px;
+                border-radius: 50%;
+                text-align: center;
+                font-size: 2rem;
+                font-weight: bold;
+                margin-right: 10px;
+                background: #dc3545;
+            }
+
+            .health-grade.grade-a { background: #28a745; }
+            .health-grade.grade-b { background: #6cb23a; }
+            .health-grade.grade-c { background: #ffc107; color: #333; }
+            .health-grade.grade-d { background: #fd7e14; }
             
             .analysis-section {
                 background: white;
@@ -150,6 +208,92 @@ impl HtmlFormatter {
                 color: #868e96;
             }
             
+            .drilldown details {
+                margin-left: 20px;
+                padding: 4px 0;
+            }
+            
+            .drilldown > details {
+                margin-left: 0;
+            }
+            
+            .drilldown summary {
+                cursor: pointer;
+                display: flex;
+                justify-content: space-between;
+                gap: 20px;
+            }
+            
+            .drilldown-name {
+                font-weight: 600;
+                color: #495057;
+            }
+            
+            .drilldown-stats {
+                font-size: 0.85rem;
+                color: #6c757d;
+            }
+            
+            .issue-group {
+                margin-bottom: 10px;
+            }
+
+            .issue-group summary {
+                cursor: pointer;
+                padding: 8px 0;
+            }
+
+            .issue-group-counts {
+                font-size: 0.85rem;
+                color: #6c757d;
+                margin-left: 8px;
+            }
+
+            .drilldown-issues {
+                margin: 8px 0 8px 20px;
+                color: #6c757d;
+            }
+            
+            .coverage-bar {
+                display: inline-block;
+                width: 60px;
+                height: 8px;
+                background-color: #f8d7da;
+                border-radius: 4px;
+                overflow: hidden;
+                vertical-align: middle;
+            }
+            
+            .coverage-bar > span {
+                display: block;
+                height: 100%;
+                background-color: #28a745;
+            }
+            
+            .size-table {
+                width: 100%;
+                border-collapse: collapse;
+                font-size: 0.9rem;
+            }
+            
+            .size-table th, .size-table td {
+                text-align: left;
+                padding: 8px 12px;
+                border-bottom: 1px solid #dee2e6;
+            }
+            
+            .size-table th {
+                background-color: #f8f9fa;
+                color: #495057;
+            }
+            
+            .issue-fingerprint {
+                font-family: monospace;
+                font-size: 0.75rem;
+                color: #adb5bd;
+                margin-top: 5px;
+            }
+            
             .metrics-grid {
                 display: grid;
                 grid-template-columns: repeat(auto-fit, minmax(250px, 1fr));
@@ -242,7 +386,13 @@ impl HtmlFormatter {
                 color: #28a745;
                 font-size: 1.2rem;
             }
-            
+
+            .omitted-issues {
+                margin-top: 15px;
+                color: #6c757d;
+                font-style: italic;
+            }
+
             .footer {
                 text-align: center;
                 padding: 20px;
@@ -299,6 +449,83 @@ impl HtmlFormatter {
         }
     }
 
+    fn render_directory(&self, html: &mut String, node: &DirectoryNode, depth: usize) {
+        let indent = "    ".repeat(depth + 3);
+        let open = if depth == 0 { " open" } else { "" };
+
+        html.push_str(&format!("{}<details class=\"drilldown-dir\"{}>\n", indent, open));
+        html.push_str(&format!("{}    <summary><span class=\"drilldown-name\">📁 {}/</span>{}</summary>\n",
+            indent, escape_html(&node.name), self.render_stats(&node.stats())));
+
+        for directory in node.directories.values() {
+            self.render_directory(html, directory, depth + 1);
+        }
+        for (name, file) in &node.files {
+            self.render_file(html, name, file, depth + 1);
+        }
+
+        html.push_str(&format!("{}</details>\n", indent));
+    }
+
+    fn render_file(&self, html: &mut String, name: &str, file: &FileEntry, depth: usize) {
+        let indent = "    ".repeat(depth + 3);
+
+        html.push_str(&format!("{}<details class=\"drilldown-file\">\n", indent));
+        html.push_str(&format!("{}    <summary><span class=\"drilldown-name\">📄 {}</span>{}</summary>\n",
+            indent, escape_html(name), self.render_stats(&file.stats())));
+
+        if file.issues.is_empty() {
+            html.push_str(&format!("{}    <div class=\"no-issues\">{}</div>\n", indent, self.lang.tr("report.no_issues")));
+        } else {
+            html.push_str(&format!("{}    <ul class=\"drilldown-issues\">\n", indent));
+            for issue in &file.issues {
+                html.push_str(&format!("{}        <li><span class=\"issue-severity {}\">{:?}</span> <strong>{}</strong> {}</li>\n",
+                    indent,
+                    self.severity_to_css_class(&issue.severity),
+                    issue.severity,
+                    escape_html(&issue.rule),
+                    escape_html(&issue.message)));
+            }
+            html.push_str(&format!("{}    </ul>\n", indent));
+        }
+
+        html.push_str(&format!("{}</details>\n", indent));
+    }
+
+    fn render_stats(&self, stats: &DirectoryStats) -> String {
+        let onpush = if stats.components > 0 {
+            format!(
+                "<span class=\"coverage-bar\"><span style=\"width: {:.0}%\"></span></span> {:.0}% OnPush · avg complexity {:.1}",
+                stats.onpush_percentage(), stats.onpush_percentage(), stats.average_complexity()
+            )
+        } else {
+            "no components".to_string()
+        };
+
+        format!(
+            "<span class=\"drilldown-stats\">{} files · {} components · {} · {} errors · {} warnings · {} info</span>",
+            stats.files, stats.components, onpush, stats.errors, stats.warnings, stats.infos
+        )
+    }
+
+    fn issue_card(&self, issue: &Issue, indent: &str) -> String {
+        let mut html = String::new();
+        html.push_str(&format!("{}<div class=\"issue-card {}\" data-fingerprint=\"{}\">\n", indent, self.severity_to_class(&issue.severity), issue.fingerprint.as_deref().unwrap_or("")));
+        html.push_str(&format!("{}    <div class=\"issue-severity {}\">{:?}</div>\n", indent, self.severity_to_css_class(&issue.severity), issue.severity));
+        html.push_str(&format!("{}    <div class=\"issue-rule\">{}</div>\n", indent, issue.rule));
+        html.push_str(&format!("{}    <div class=\"issue-message\">{}</div>\n", indent, issue.message));
+        html.push_str(&format!("{}    <div class=\"issue-location\">{}{}</div>\n",
+            indent,
+            std::path::Path::new(&issue.file_path).file_name().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| issue.file_path.clone()),
+            issue.line.map(|l| format!(":{}", l)).unwrap_or_default()
+        ));
+        if let Some(fingerprint) = &issue.fingerprint {
+            html.push_str(&format!("{}    <div class=\"issue-fingerprint\">{}</div>\n", indent, fingerprint));
+        }
+        html.push_str(&format!("{}</div>\n", indent));
+        html
+    }
+
     fn priority_to_card_class(&self, priority: &crate::ast::Priority) -> &'static str {
         match priority {
             crate::ast::Priority::High => "high",
@@ -310,14 +537,15 @@ impl HtmlFormatter {
 
 impl OutputFormatter for HtmlFormatter {
     fn format(&self, results: &[AnalysisResult]) -> Result<String> {
+        let lang = self.lang;
         let mut html = String::new();
         
         html.push_str("<!DOCTYPE html>\n");
-        html.push_str("<html lang=\"en\">\n");
+        html.push_str(&format!("<html lang=\"{}\">\n", lang.code()));
         html.push_str("<head>\n");
         html.push_str("    <meta charset=\"UTF-8\">\n");
         html.push_str("    <meta name=\"viewport\" content=\"width=device-width, initial-scale=1.0\">\n");
-        html.push_str("    <title>Angular Analysis Report</title>\n");
+        html.push_str(&format!("    <title>{}</title>\n", lang.tr("report.title")));
         
         if self.include_css {
             html.push_str(self.generate_css());
@@ -327,46 +555,122 @@ impl OutputFormatter for HtmlFormatter {
         html.push_str("<body>\n");
         
         html.push_str("    <div class=\"header\">\n");
-        html.push_str("        <h1>Angular Analysis Report</h1>\n");
-        html.push_str("        <div class=\"subtitle\">Generated by ng-analyzer</div>\n");
+        html.push_str(&format!("        <h1>{}</h1>\n", lang.tr("report.title")));
+        html.push_str(&format!("        <div class=\"subtitle\">{}</div>\n", lang.tr("report.generated_by")));
+        if let Some(health) = &self.health {
+            html.push_str(&format!(
+                "        <div class=\"health-score\"><span class=\"health-grade grade-{}\">{}</span> {} {:.0}/100</div>\n",
+                health.grade.to_lowercase(),
+                health.grade,
+                lang.tr("report.health_score"),
+                health.score
+            ));
+        }
         html.push_str("    </div>\n");
 
-        for (_i, result) in results.iter().enumerate() {
-            html.push_str(&format!("    <div class=\"analysis-section\">\n"));
-            html.push_str(&format!("        <div class=\"section-header\">\n"));
-            html.push_str(&format!("            <h2>Project: {}</h2>\n", result.project.root_path.display()));
+        if let Some(health) = self.health.as_ref().filter(|health| !health.categories.is_empty()) {
+            html.push_str("    <div class=\"analysis-section\">\n");
+            html.push_str("        <div class=\"section-header\">\n");
+            html.push_str(&format!("            <h2>{}</h2>\n", lang.tr("report.health")));
+            html.push_str("        </div>\n");
+            html.push_str("        <div class=\"section-content\">\n");
+            html.push_str("            <div class=\"metrics-grid\">\n");
+            for category in &health.categories {
+                html.push_str("                <div class=\"metric-card\">\n");
+                html.push_str(&format!("                    <div class=\"metric-value\">{:.0}</div>\n", category.score));
+                html.push_str(&format!("                    <div class=\"metric-label\">{} (weight {})</div>\n", category.category, category.weight));
+                html.push_str(&format!("                    <div class=\"metric-label\">{}</div>\n", escape_html(&category.detail)));
+                html.push_str("                </div>\n");
+            }
+            html.push_str("            </div>\n");
+            html.push_str("        </div>\n");
+            html.push_str("    </div>\n");
+        }
+
+        if !self.budgets.is_empty() {
+            html.push_str("    <div class=\"analysis-section\">\n");
+            html.push_str("        <div class=\"section-header\">\n");
+            html.push_str(&format!("            <h2>{}</h2>\n", lang.tr("report.budgets")));
+            html.push_str("        </div>\n");
+            html.push_str("        <div class=\"section-content\">\n");
+            html.push_str("            <table class=\"size-table\">\n");
+            html.push_str("                <tr><th>Budget</th><th>Limit</th><th>Actual</th><th>Status</th><th>Worst</th></tr>\n");
+            for budget in &self.budgets {
+                let (status_class, status) = if budget.passed { ("severity-info", "pass") } else { ("severity-error", "FAIL") };
+                html.push_str(&format!(
+                    "                <tr><td>{}</td><td>{}</td><td>{}</td><td><span class=\"{}\">{}</span></td><td>{}</td></tr>\n",
+                    budget.budget,
+                    escape_html(&budget_limit(budget)),
+                    budget_value(budget, budget.actual),
+                    status_class,
+                    status,
+                    escape_html(budget.worst.as_deref().unwrap_or("-"))
+                ));
+            }
+            html.push_str("            </table>\n");
+            html.push_str("        </div>\n");
+            html.push_str("    </div>\n");
+        }
+
+        let tree = DirectoryNode::build(results);
+        if !tree.directories.is_empty() || !tree.files.is_empty() {
+            html.push_str("    <div class=\"analysis-section\">\n");
+            html.push_str("        <div class=\"section-header\">\n");
+            html.push_str(&format!("            <h2>{}</h2>\n", lang.tr("report.drilldown")));
+            html.push_str("        </div>\n");
+            html.push_str("        <div class=\"section-content drilldown\">\n");
+            self.render_directory(&mut html, &tree, 0);
+            html.push_str("        </div>\n");
+            html.push_str("    </div>\n");
+        }
+
+        for result in results {
+            html.push_str("    <div class=\"analysis-section\">\n");
+            html.push_str("        <div class=\"section-header\">\n");
+            html.push_str(&format!("            <h2>{}: {}</h2>\n", lang.tr("report.project"), result.project.root_path.display()));
             html.push_str("        </div>\n");
 
             if !result.issues.is_empty() {
                 html.push_str("        <div class=\"section-content\">\n");
-                html.push_str("            <h3>Issues</h3>\n");
-                html.push_str("            <div class=\"issues-grid\">\n");
-                
-                for issue in &result.issues {
-                    let severity_class = self.severity_to_class(&issue.severity);
-                    let severity_css_class = self.severity_to_css_class(&issue.severity);
-                    
-                    html.push_str(&format!("                <div class=\"issue-card {}\">\n", severity_class));
-                    html.push_str(&format!("                    <div class=\"issue-severity {}\">{:?}</div>\n", severity_css_class, issue.severity));
-                    html.push_str(&format!("                    <div class=\"issue-rule\">{}</div>\n", issue.rule));
-                    html.push_str(&format!("                    <div class=\"issue-message\">{}</div>\n", issue.message));
-                    html.push_str(&format!("                    <div class=\"issue-location\">{}{}</div>\n", 
-                        std::path::Path::new(&issue.file_path).file_name().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| issue.file_path.clone()),
-                        issue.line.map(|l| format!(":{}", l)).unwrap_or_else(|| "".to_string())
-                    ));
-                    html.push_str("                </div>\n");
+                html.push_str(&format!("            <h3>{}</h3>\n", lang.tr("report.issues")));
+                if let Some(group_by) = self.group_by {
+                    // 件数だけを見せ、開いたときに個々の issue を表示する
+                    for group in group_issues(&result.issues, group_by, &result.project.root_path) {
+                        html.push_str("            <details class=\"issue-group\">\n");
+                        html.push_str(&format!(
+                            "                <summary><strong>{}</strong> <span class=\"issue-group-counts\">{} issues · {} errors · {} warnings · {} info</span></summary>\n",
+                            escape_html(&group.key),
+                            group.issues.len(),
+                            group.count(Severity::Error),
+                            group.count(Severity::Warning),
+                            group.count(Severity::Info)
+                        ));
+                        html.push_str("                <div class=\"issues-grid\">\n");
+                        for issue in &group.issues {
+                            html.push_str(&self.issue_card(issue, "                    "));
+                        }
+                        html.push_str("                </div>\n");
+                        html.push_str("            </details>\n");
+                    }
+                } else {
+                    html.push_str("            <div class=\"issues-grid\">\n");
+                    for issue in &result.issues {
+                        html.push_str(&self.issue_card(issue, "                "));
+                    }
+                    html.push_str("            </div>\n");
+                }
+                for (rule, count) in result.omitted_per_rule() {
+                    html.push_str(&format!("            <div class=\"omitted-issues\">{}</div>\n", lang.trf("report.omitted", &[&count, &escape_html(rule)])));
                 }
-                
-                html.push_str("            </div>\n");
                 html.push_str("        </div>\n");
             } else {
                 html.push_str("        <div class=\"section-content\">\n");
-                html.push_str("            <div class=\"no-issues\">✅ No issues found!</div>\n");
+                html.push_str(&format!("            <div class=\"no-issues\">{}</div>\n", lang.tr("report.no_issues")));
                 html.push_str("        </div>\n");
             }
 
             html.push_str("        <div class=\"section-content\">\n");
-            html.push_str("            <h3>Metrics</h3>\n");
+            html.push_str(&format!("            <h3>{}</h3>\n", lang.tr("report.metrics")));
             html.push_str("            <div class=\"metrics-grid\">\n");
             
             html.push_str("                <div class=\"metric-card\">\n");
@@ -389,12 +693,162 @@ impl OutputFormatter for HtmlFormatter {
             html.push_str("                    <div class=\"metric-label\">Avg Complexity</div>\n");
             html.push_str("                </div>\n");
             
+            html.push_str("                <div class=\"metric-card\">\n");
+            html.push_str(&format!("                    <div class=\"metric-value\">{}</div>\n", result.metrics.lines_of_code));
+            html.push_str("                    <div class=\"metric-label\">Lines of Code</div>\n");
+            html.push_str("                </div>\n");
+
+            if let Some(coverage) = result.metrics.test_coverage {
+                html.push_str("                <div class=\"metric-card\">\n");
+                html.push_str(&format!("                    <div class=\"metric-value\">{:.1}%</div>\n", coverage));
+                html.push_str("                    <div class=\"metric-label\">Test Coverage</div>\n");
+                html.push_str("                </div>\n");
+            }
+            
             html.push_str("            </div>\n");
             html.push_str("        </div>\n");
 
+            if let Some(adoption) = &result.metrics.adoption {
+                html.push_str("        <div class=\"section-content\">\n");
+                html.push_str(&format!("            <h3>{}</h3>\n", lang.tr("report.adoption")));
+                html.push_str("            <table class=\"size-table\">\n");
+                for (label, value) in adoption_rows(adoption) {
+                    let cell = match value {
+                        Some(value) => format!("<span class=\"coverage-bar\"><span style=\"width: {:.0}%\"></span></span> {:.1}%", value, value),
+                        None => "-".to_string(),
+                    };
+                    html.push_str(&format!("                <tr><th>{}</th><td>{}</td></tr>\n", label, cell));
+                }
+                html.push_str("            </table>\n");
+                html.push_str("        </div>\n");
+            }
+
+            if let Some(api_surface) = &result.metrics.api_surface {
+                html.push_str("        <div class=\"section-content\">\n");
+                html.push_str(&format!("            <h3>{}</h3>\n", lang.trf("report.api_surface", &[&format!("{:.1}", api_surface.average), &api_surface.max])));
+                html.push_str("            <table class=\"size-table\">\n");
+                html.push_str("                <tr><th>Inputs + Outputs + Public Methods</th><th>Components</th></tr>\n");
+                for bucket in &api_surface.distribution {
+                    html.push_str(&format!("                <tr><td>{}</td><td>{}</td></tr>\n", bucket.range, bucket.components));
+                }
+                html.push_str("            </table>\n");
+                html.push_str("        </div>\n");
+            }
+
+            let files = largest_files(&result.project);
+            if !files.is_empty() {
+                html.push_str("        <div class=\"section-content\">\n");
+                html.push_str(&format!("            <h3>{}</h3>\n", lang.tr("report.largest_files")));
+                html.push_str("            <table class=\"size-table\">\n");
+                html.push_str("                <tr><th>File</th><th>Lines of Code</th><th>Physical Lines</th></tr>\n");
+                for file in files {
+                    html.push_str(&format!("                <tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                        escape_html(&file.file_path), file.lines.logical, file.lines.physical));
+                }
+                html.push_str("            </table>\n");
+                html.push_str("        </div>\n");
+            }
+
+            let components = largest_components(&result.project);
+            if !components.is_empty() {
+                html.push_str("        <div class=\"section-content\">\n");
+                html.push_str(&format!("            <h3>{}</h3>\n", lang.tr("report.largest_components")));
+                html.push_str("            <table class=\"size-table\">\n");
+                html.push_str("                <tr><th>Component</th><th>Lines of Code</th><th>Complexity</th><th>Template</th><th>File</th></tr>\n");
+                for component in components {
+                    let template = component.template_complexity.as_ref().map_or("-".to_string(), |template| template.score.to_string());
+                    html.push_str(&format!("                <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                        escape_html(&component.name), component.lines.logical, component.complexity_score, template, escape_html(&component.file_path)));
+                }
+                html.push_str("            </table>\n");
+                html.push_str("        </div>\n");
+            }
+
+            if let Some(render_depth) = result.metrics.render_depth.as_ref().filter(|depth| depth.max_depth > 0) {
+                html.push_str("        <div class=\"section-content\">\n");
+                html.push_str(&format!("            <h3>{}</h3>\n", lang.trf("report.render_chains_max", &[&render_depth.max_depth])));
+                html.push_str("            <table class=\"size-table\">\n");
+                html.push_str("                <tr><th>Depth</th><th>Chain</th></tr>\n");
+                for chain in &render_depth.deepest_chains {
+                    html.push_str(&format!("                <tr><td>{}</td><td>{}</td></tr>\n", chain.len() - 1, escape_html(&chain.join(" > "))));
+                }
+                html.push_str("            </table>\n");
+                html.push_str("        </div>\n");
+            }
+
+            if !result.metrics.feature_testability.is_empty() {
+                html.push_str("        <div class=\"section-content\">\n");
+                html.push_str(&format!("            <h3>{}</h3>\n", lang.tr("report.testability")));
+                html.push_str("            <table class=\"size-table\">\n");
+                html.push_str("                <tr><th>Feature</th><th>Components &amp; Services</th><th>Tested</th><th>Tests</th><th>Disabled</th><th>Score</th></tr>\n");
+                for feature in &result.metrics.feature_testability {
+                    html.push_str(&format!(
+                        "                <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><span class=\"coverage-bar\"><span style=\"width: {:.0}%\"></span></span> {:.0}%</td></tr>\n",
+                        escape_html(&feature.folder), feature.artifacts, feature.with_spec, feature.tests, feature.disabled_tests, feature.score, feature.score
+                    ));
+                }
+                html.push_str("            </table>\n");
+                html.push_str("        </div>\n");
+            }
+
+            if !result.metrics.cd_hotspots.is_empty() {
+                html.push_str("        <div class=\"section-content\">\n");
+                html.push_str(&format!("            <h3>{}</h3>\n", lang.tr("report.cd_hotspots")));
+                html.push_str("            <table class=\"size-table\">\n");
+                html.push_str("                <tr><th>Component</th><th>Bindings</th><th>Subtree</th><th>Depth</th><th>Cost</th></tr>\n");
+                for hotspot in &result.metrics.cd_hotspots {
+                    html.push_str(&format!(
+                        "                <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                        escape_html(&hotspot.component), hotspot.bindings, hotspot.subtree_bindings, hotspot.depth, hotspot.cost
+                    ));
+                }
+                html.push_str("            </table>\n");
+                html.push_str("        </div>\n");
+            }
+
+            if !result.metrics.zoneless_readiness.is_empty() {
+                html.push_str("        <div class=\"section-content\">\n");
+                html.push_str(&format!("            <h3>{}</h3>\n", lang.tr("report.zoneless")));
+                html.push_str("            <table class=\"size-table\">\n");
+                html.push_str("                <tr><th>Feature</th><th>Components</th><th>Ready</th><th>Blocked</th><th>Score</th></tr>\n");
+                for feature in &result.metrics.zoneless_readiness {
+                    html.push_str(&format!(
+                        "                <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td><span class=\"coverage-bar\"><span style=\"width: {:.0}%\"></span></span> {:.0}%</td></tr>\n",
+                        escape_html(&feature.folder), feature.components, feature.ready, feature.blocked, feature.score, feature.score
+                    ));
+                }
+                html.push_str("            </table>\n");
+                html.push_str("        </div>\n");
+            }
+
+            if let Some(type_safety) = &result.metrics.type_safety {
+                let strict = match type_safety.strict {
+                    Some(true) => "enabled",
+                    Some(false) => "disabled",
+                    None => "unknown",
+                };
+                html.push_str("        <div class=\"section-content\">\n");
+                html.push_str(&format!(
+                    "            <h3>{} <span class=\"coverage-bar\"><span style=\"width: {:.0}%\"></span></span> {:.0}% (strict: {})</h3>\n",
+                    lang.tr("report.type_safety"), type_safety.score, type_safety.score, strict
+                ));
+                if !type_safety.files.is_empty() {
+                    html.push_str("            <table class=\"size-table\">\n");
+                    html.push_str("                <tr><th>File</th><th>any</th><th>as any</th><th>!</th><th>@ts-ignore</th></tr>\n");
+                    for file in &type_safety.files {
+                        html.push_str(&format!(
+                            "                <tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                            escape_html(&file.file_path), file.escapes.any_annotations, file.escapes.any_casts, file.escapes.non_null_assertions, file.escapes.ts_ignores
+                        ));
+                    }
+                    html.push_str("            </table>\n");
+                }
+                html.push_str("        </div>\n");
+            }
+
             if !result.recommendations.is_empty() {
                 html.push_str("        <div class=\"section-content\">\n");
-                html.push_str("            <h3>Recommendations</h3>\n");
+                html.push_str(&format!("            <h3>{}</h3>\n", lang.tr("report.recommendations")));
                 html.push_str("            <div class=\"recommendations-grid\">\n");
                 
                 for rec in &result.recommendations {
@@ -430,9 +884,16 @@ impl OutputFormatter for HtmlFormatter {
         Ok(html)
     }
 
-    fn write_to_file(&self, results: &[AnalysisResult], path: &PathBuf) -> Result<()> {
+    fn write_to_file(&self, results: &[AnalysisResult], path: &Path) -> Result<()> {
         let content = self.format(results)?;
         fs::write(path, content)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
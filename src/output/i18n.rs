@@ -0,0 +1,400 @@
+use anyhow::Result;
+use std::fmt::Display;
+
+// --lang で選ぶ CLI のメッセージとレポートの見出しの言語
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Lang {
+    #[default]
+    En,
+    Ja,
+}
+
+impl Lang {
+    pub fn parse(lang: &str) -> Result<Self> {
+        match lang {
+            "en" => Ok(Self::En),
+            "ja" => Ok(Self::Ja),
+            _ => Err(anyhow::anyhow!("Unsupported --lang: {} (en, ja)", lang)),
+        }
+    }
+
+    // --lang がなければ LC_ALL・LC_MESSAGES・LANG の順に見て、ja で始まれば日本語にする
+    pub fn detect() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        Self::from_locale(&locale)
+    }
+
+    fn from_locale(locale: &str) -> Self {
+        if locale.to_lowercase().starts_with("ja") {
+            Self::Ja
+        } else {
+            Self::En
+        }
+    }
+
+    // html lang 属性の値
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::En => "en",
+            Self::Ja => "ja",
+        }
+    }
+
+    // カタログにないキーはデバッグビルドでは panic し、リリースビルドでは空文字にする
+    pub fn tr(self, key: &str) -> &'static str {
+        MESSAGES.iter()
+            .find(|(id, _, _)| *id == key)
+            .map(|(_, en, ja)| match self {
+                Self::En => *en,
+                Self::Ja => *ja,
+            })
+            .unwrap_or_else(|| {
+                debug_assert!(false, "missing message: {}", key);
+                ""
+            })
+    }
+
+    // {} を先頭から順に args で置き換える
+    pub fn trf(self, key: &str, args: &[&dyn Display]) -> String {
+        let mut args = args.iter();
+        let mut output = String::new();
+        let mut rest = self.tr(key);
+        while let Some(index) = rest.find("{}") {
+            output.push_str(&rest[..index]);
+            if let Some(arg) = args.next() {
+                output.push_str(&arg.to_string());
+            }
+            rest = &rest[index + 2..];
+        }
+        output.push_str(rest);
+        output
+    }
+}
+
+// (キー, 英語, 日本語)
+const MESSAGES: &[(&str, &str, &str)] = &[
+    // 解析コマンド
+    ("analysis.started", "🔍 Starting Angular project analysis...\n📁 Analyzing path: {}", "🔍 Angular プロジェクトの分析を開始しています...\n📁 分析対象パス: {}"),
+    ("analysis.encoding_fallback", "⚠️  {}: not valid UTF-8, analyzed as {}", "⚠️  {}: UTF-8 として読めないため {} で解析しました"),
+    ("analysis.minified", "ℹ️  {}: has lines longer than {} characters, treated as minified and left out of complexity and line metrics", "ℹ️  {}: {} 文字を超える行があるため minify 済みとみなし、複雑度と行数の指標から除外しました"),
+    ("analysis.coverage_loaded", "🧪 Loaded coverage for {} files from {}", "🧪 {} ファイルのカバレッジを {} から読み込みました"),
+    ("analysis.project_parsed", "📊 Found {} components, {} services, {} modules", "📊 コンポーネント {} 個、サービス {} 個、モジュール {} 個を検出しました"),
+    ("analysis.no_results", "⚠️  No analysis results generated", "⚠️  分析結果がありません"),
+    ("analysis.history_appended", "🕒 Appended metrics snapshot to {}", "🕒 指標を {} に追記しました"),
+    ("analysis.html_written", "📄 HTML report generated: {}", "📄 HTML レポートが出力されました: {}"),
+    ("analysis.csv_written", "📄 CSV report generated: {}", "📄 CSV レポートが出力されました: {}"),
+    ("analysis.csv_metrics_written", "📄 CSV metrics generated: {}", "📄 CSV の指標が出力されました: {}"),
+    ("analysis.csv_budgets_written", "📄 CSV budgets generated: {}", "📄 CSV の予算が出力されました: {}"),
+    ("analysis.summary", "\n📈 Analysis Summary:", "\n📈 分析サマリー:"),
+    ("analysis.total_issues", "   Total issues found: {}", "   検出した issue の総数: {}"),
+    ("analysis.issues_shown", "   Issues shown: {}", "   表示した issue (Error と Warning): {}"),
+    ("analysis.collapsed", "   Duplicates reported by several analyzers (collapsed): {}", "   複数のアナライザーが報告した重複（まとめた数）: {}"),
+    ("analysis.health", "   🩺 Health score: {}/100 ({})", "   🩺 健全性スコア: {}/100 ({})"),
+    ("analysis.omitted", "   Omitted by --max-issues-per-rule: {}", "   --max-issues-per-rule で省いた数: {}"),
+    ("analysis.errors", "   ❌ Errors: {}", "   ❌ エラー: {}"),
+    ("analysis.warnings", "   ⚠️  Warnings: {}", "   ⚠️  警告: {}"),
+    ("analysis.recommendations", "   💡 Recommendations: {}", "   💡 推奨事項: {}"),
+    ("analysis.budget_exceeded", "❌ Budget {} exceeded: {} (limit {}){}", "❌ 予算 {} を超えています: {} (上限 {}){}"),
+    ("analysis.budget_worst", " in {}", " ({})"),
+    ("analysis.encoding_lossy", "UTF-8 (invalid bytes replaced with U+FFFD)", "UTF-8 (不正なバイト列を U+FFFD に置換)"),
+    // --timing と --rule-stats
+    ("timing.title", "⏱️  Timing:", "⏱️  所要時間:"),
+    ("timing.parse", "   parse     {}s", "   構文解析  {}s"),
+    ("timing.analysis", "   analysis  {}s", "   解析      {}s"),
+    ("timing.output", "   output    {}s", "   出力      {}s"),
+    ("timing.inputs", "📥 Inputs loaded:", "📥 読み込んだ入力:"),
+    ("timing.typescript", "   TypeScript files: {}", "   TypeScript ファイル: {}"),
+    ("timing.templates", "   Templates: {}", "   テンプレート: {}"),
+    ("timing.resource_files", "   Template/style files: {}", "   テンプレート・スタイルのファイル: {}"),
+    ("timing.stylesheets", "   Component stylesheets: {}", "   コンポーネントのスタイルシート: {}"),
+    ("timing.import_graph", "   Import graph files: {}", "   import グラフのファイル: {}"),
+    ("timing.skipped", "skipped (no rule needs them)", "読み込みなし（必要とするルールがありません）"),
+    ("rule_stats.title", "📊 Issues per rule:", "📊 ルールごとの issue:"),
+    ("rule_stats.rule", "rule", "ルール"),
+    ("rule_stats.issues", "issues", "件数"),
+    ("rule_stats.files", "files", "ファイル"),
+    ("rule_stats.per_file", "per file", "1 件あたり"),
+    ("rule_stats.share", "share", "割合"),
+    ("rule_stats.noisy", "🔊 Noisy rules (more than {}% of all issues):", "🔊 issue の多いルール（全体の {}% 超）:"),
+    ("rule_stats.noisy_rule", "   {} ({}%) - consider raising its options, lowering its severity or disabling it in .ng-analyzer.json", "   {} ({}%) - .ng-analyzer.json でオプションを緩めるか、重要度を下げるか、無効にすることを検討してください"),
+    // golden・list
+    ("golden.updated", "📝 {} (updated)", "📝 {}（更新しました）"),
+    ("golden.missing", "❌ {} (golden file not found)", "❌ {}（ゴールデンファイルがありません）"),
+    ("golden.failed", "Output does not match the golden files: {} (run with --update if the change is intended)", "ゴールデンファイルと一致しません: {} (意図した変更なら --update で更新してください)"),
+    ("list.no_rules", "❌ No rules found for category: {}", "❌ カテゴリ {} のルールはありません"),
+    ("list.category_rules", "📋 Rules in category '{}':", "📋 カテゴリ '{}' のルール:"),
+    ("list.description", "     Description: {}", "     説明: {}"),
+    ("list.default_severity", "     Default severity: {}", "     既定の重要度: {}"),
+    ("list.options", "     Configurable options:", "     設定できるオプション:"),
+    ("list.option", "       - {}: {} (default: {})", "       - {}: {} (既定値: {})"),
+    ("list.categories", "📋 Available categories:", "📋 カテゴリ一覧:"),
+    ("list.analyzers", "\n📋 Available analyzers:", "\n📋 アナライザー一覧:"),
+    ("list.rules", "\n📋 All available rules:", "\n📋 ルール一覧:"),
+    ("list.analyzer.component", "Analyzes Angular components for best practices and performance", "Angular コンポーネントのベストプラクティスとパフォーマンスを分析します"),
+    ("list.analyzer.dependency", "Analyzes dependency relationships and circular dependencies", "依存関係と循環依存を分析します"),
+    ("list.analyzer.state", "Analyzes state management patterns and reactive programming", "状態管理のパターンとリアクティブプログラミングを分析します"),
+    ("list.analyzer.performance", "Analyzes performance implications and optimization opportunities", "パフォーマンスへの影響と最適化の余地を分析します"),
+    ("list.analyzer.testing", "Matches components and services to spec files and scores testability", "コンポーネントとサービスを spec ファイルと対応付け、テストしやすさを採点します"),
+    ("list.analyzer.http", "Tracks HttpClient usage, error handling, hard-coded URLs and duplicated endpoints", "HttpClient の利用、エラー処理、ハードコードした URL、重複したエンドポイントを調べます"),
+    ("list.analyzer.styles", "Checks component stylesheets: size, ::ng-deep, !important, duplicated selectors, unused classes and empty style files", "コンポーネントのスタイルシートの大きさ、::ng-deep、!important、重複したセレクター、未使用のクラス、空のスタイルファイルを調べます"),
+    ("list.analyzer.boundaries", "Reports imports that cross the layers configured in boundaries.layers", "boundaries.layers で設定したレイヤーをまたぐ import を報告します"),
+    ("list.analyzer.duplicates", "Finds classes, interfaces and functions with the same name exported from several files", "複数のファイルから同じ名前で export しているクラス・インターフェース・関数を探します"),
+    ("list.analyzer.clones", "Finds duplicated blocks of code (copy-paste) across the project by comparing token sequences", "トークン列を比べて、プロジェクト内で重複したコード（コピー＆ペースト）を探します"),
+    ("list.analyzer.zoneless", "Reports components that rely on zone.js to refresh the view and scores zoneless readiness per feature folder", "画面の更新を zone.js に頼っているコンポーネントを報告し、機能フォルダーごとに zoneless への対応度を採点します"),
+    ("list.analyzer.type-safety", "Counts any annotations, as any casts, non-null assertions and @ts-ignore comments per file and checks tsconfig compiler options", "ファイルごとに any の注釈・as any・非 null アサーション・@ts-ignore を数え、tsconfig のコンパイラオプションを調べます"),
+    ("list.analyzer.environment", "Flags secrets in environment files, direct environment.prod imports and configuration read outside an injection token or config service", "environment ファイルの秘密情報、environment.prod の直接 import、インジェクショントークンや設定サービスを通さない設定の読み取りを報告します"),
+    // fix・init・trends
+    ("fix.applying", "🔧 Applying fixes: {}\n📁 Fixing path: {}", "🔧 適用する修正: {}\n📁 修正対象パス: {}"),
+    ("fix.nothing", "✅ Nothing to fix", "✅ 修正するものはありません"),
+    ("fix.would_update", "🔧 Would update {} files", "🔧 {} ファイルを更新します（dry run）"),
+    ("fix.updated", "🔧 Updated {} files", "🔧 {} ファイルを更新しました"),
+    ("init.exists", "⚠️  Configuration file already exists at: {}\n   Use --force to overwrite (not implemented yet)", "⚠️  設定ファイルはすでにあります: {}\n   上書きするには --force を指定してください（未実装）"),
+    ("init.created", "✅ Configuration file created: {}\n   Profile: {}\n   You can now customize the rules and settings in this file.", "✅ 設定ファイルを作成しました: {}\n   プロファイル: {}\n   このファイルでルールや設定を調整できます。"),
+    ("trends.written", "📄 Trends written to: {}", "📄 トレンドが出力されました: {}"),
+    ("trends.heading", "Trends: {}", "推移: {}"),
+    ("trends.title", "Angular Analysis Trends", "Angular 分析の推移"),
+    ("trends.range", "{} runs from {} to {}", "{} 回の実行 ({} から {})"),
+    ("trends.empty", "No history entries", "履歴がありません"),
+    ("trends.series.complexity", "Average complexity", "平均の複雑度"),
+    ("trends.series.onpush", "OnPush adoption (%)", "OnPush の採用率 (%)"),
+    ("trends.series.errors", "Errors", "エラー"),
+    ("trends.series.warnings", "Warnings", "警告"),
+    ("trends.series.infos", "Infos", "情報"),
+    ("trends.series.components", "Components", "コンポーネント"),
+    // search
+    ("search.no_matches", "⚠️  No matches found", "⚠️  一致するものはありません"),
+    ("search.found", "🔍 Found {} matches in {} files", "🔍 {} 件見つかりました ({} ファイル)"),
+    ("search.file_matches", "   {} matches found", "   {} 件"),
+    ("search.file", "File", "ファイル"),
+    ("search.line", "Line", "行"),
+    ("search.content", "Content", "内容"),
+    ("search.per_file", "\nMatches per file:", "\nファイルごとのマッチ数:"),
+    ("search.total", "Total: {} matches in {} files", "合計: {} 件 ({} ファイル)"),
+    ("search.searched", "🔍 Searched {} files in {}s ({} files/sec)", "🔍 {} ファイルを {}s で検索しました ({} ファイル/秒)"),
+    // 進捗・bench・--summary top
+    ("progress.parsing", "Parsing", "構文解析"),
+    ("progress.import_graph", "Building import graph", "import グラフを作成"),
+    ("progress.analyzing", "Analyzing", "解析"),
+    ("bench.title", "Benchmark: {} ({} files, {} iterations after {} warm-up runs)", "ベンチマーク: {} ({} ファイル、{} 回計測、ウォームアップ {} 回)"),
+    ("bench.phases", "Phases:", "フェーズ:"),
+    ("bench.analyzers", "Analyzers:", "アナライザー:"),
+    ("top.files", "Top {} files by weighted issues (error 10, warning 3, info 1):", "重み付けした issue の多いファイル上位 {} 件 (error 10, warning 3, info 1):"),
+    ("top.components", "Top {} most complex components:", "複雑度の高いコンポーネント上位 {} 件:"),
+    ("top.rules", "Top {} most violated rules:", "違反の多いルール上位 {} 件:"),
+    ("top.cycles", "Largest import cycles (top {}):", "大きい import の循環 (上位 {} 件):"),
+    ("top.none", "  (none)", "  (なし)"),
+    ("top.cycle", "  [{} files] {}", "  [{} ファイル] {}"),
+    // エラー
+    ("error.unknown_analyzer", "Unknown analyzer: {}", "不明なアナライザー: {}"),
+    ("error.unknown_fix", "Unknown fix: {}", "不明な修正: {}"),
+    ("error.at_least_one", "{} must be at least 1", "{} には 1 以上を指定してください"),
+    ("error.unsupported", "Unsupported {}: {} ({})", "サポートされていない {}: {} ({})"),
+    ("error.unsupported_format", "Unsupported output format for {}: {} ({})", "{} でサポートされていない出力形式: {} ({})"),
+    ("error.schema_graph_only", "--schema can only be used with the JSON output of graph --kind files", "--schema は graph --kind files の JSON 出力にだけ指定できます"),
+    ("error.import_graph", "Could not build the import graph: {}", "import グラフを作れませんでした: {}"),
+    ("error.focus", "No files match --focus: {}", "--focus に一致するファイルがありません: {}"),
+    ("error.impact_target", "Impact target not found: {} (a file path or glob, an exported symbol, or a selector)", "impact の対象が見つかりません: {} (ファイルのパス・glob、export しているシンボル、セレクタ)"),
+    ("error.structural_pattern", "A structural pattern must be a single TypeScript expression, class member, statement or decorator: {}", "構造検索のパターンには式・クラスのメンバー・文・デコレーターのどれか 1 つを TypeScript として書いてください: {}"),
+    ("error.boundaries_unknown_layer", "boundaries: layer '{}' allows undefined layer '{}'", "boundaries: レイヤー '{}' の allow に未定義のレイヤー '{}' があります"),
+    ("error.boundaries_pattern", "boundaries: invalid pattern for layer '{}': {}", "boundaries: レイヤー '{}' の pattern が不正です: {}"),
+    ("error.fixtures_unreadable", "Failed to read the fixture directory: {}", "fixture ディレクトリを読めません: {}"),
+    ("error.no_fixtures", "No fixture projects found: {}", "fixture プロジェクトがありません: {}"),
+    ("error.history_unreadable", "Failed to read {}", "{} を読めません"),
+    ("error.history_entry", "{}:{} is not a valid history entry", "{}:{} を履歴として読めません"),
+    // graph
+    ("graph.path", "📁 Analyzing path: {}", "📁 分析対象パス: {}"),
+    ("graph.written", "📄 Graph written to: {}", "📄 グラフが出力されました: {}"),
+    ("graph.summary", "\n📈 Summary:", "\n📈 分析サマリー:"),
+    ("graph.files.started", "🔍 Starting TypeScript dependency graph analysis...", "🔍 TypeScript依存関係グラフ分析を開始しています..."),
+    ("graph.files.found", "📊 Found {} files and {} dependencies", "📊 {}個のファイルと{}個の依存関係を発見しました"),
+    ("graph.files.filtered", "🧹 Filters applied: {} → {} files, {} → {} dependencies", "🧹 フィルター適用: {} → {} ファイル, {} → {} 依存関係"),
+    ("graph.files.analyzing", "🔍 Running dependency analysis...", "🔍 依存関係分析を実行しています..."),
+    ("graph.files.cycles_found", "⚠️  Found {} circular dependencies", "⚠️  {}個の循環依存を発見しました"),
+    ("graph.files.cycles_limited", "   (stopped listing cycles at the limit of {}; change it with --max-cycles)", "   (上限の {} 件に達したため、残りの循環は列挙していません。--max-cycles で変更できます)"),
+    ("graph.files.orphans_found", "🔍 Found {} orphaned files", "🔍 {}個の孤立ファイルを発見しました"),
+    ("graph.files.unused_found", "🔍 Found {} unused exports", "🔍 {}個の未使用の export を発見しました"),
+    ("graph.files.total_files", "   Files: {}", "   総ファイル数: {}"),
+    ("graph.files.total_dependencies", "   Dependencies: {}", "   総依存関係数: {}"),
+    ("graph.files.cycles", "   Circular dependencies: {}", "   循環依存数: {}"),
+    ("graph.files.orphans", "   Orphaned files: {}", "   孤立ファイル数: {}"),
+    ("graph.files.unused", "   Unused exports: {}", "   未使用の export 数: {}"),
+    ("graph.files.longest_chain", "   Longest dependency chain: {}", "   最長の依存チェーン: {}"),
+    ("graph.files.most_imported", "   Most imported files:", "   最もインポートされているファイル:"),
+    ("graph.files.imported_count", "     - {} ({} imports)", "     - {} ({}回)"),
+    ("graph.tree.started", "🔍 Building the component tree...", "🔍 コンポーネントツリーを組み立てています..."),
+    ("graph.tree.components", "   Components: {}", "   コンポーネント数: {}"),
+    ("graph.tree.edges", "   Parent-child relations: {}", "   親子関係数: {}"),
+    ("graph.tree.deepest", "   Deepest level: {} ({})", "   最も深い階層: {} ({})"),
+    ("graph.modules.started", "🔍 Analyzing NgModule dependencies...", "🔍 NgModule の依存関係を分析しています..."),
+    ("graph.modules.modules", "   Modules: {}", "   モジュール数: {}"),
+    ("graph.modules.imports", "   Imports: {}", "   import 数: {}"),
+    ("graph.modules.lazy", "   Lazy-loading boundaries: {}", "   遅延読み込みの境界: {}"),
+    ("graph.modules.cycles", "\n❌ Circular NgModule imports:", "\n❌ 循環している NgModule の import:"),
+    ("graph.injection.started", "🔍 Analyzing service injection...", "🔍 サービスの注入関係を分析しています..."),
+    ("graph.injection.services", "   Services: {}", "   サービス数: {}"),
+    ("graph.injection.edges", "   Injections: {}", "   注入関係数: {}"),
+    ("graph.injection.multiple", "\n⚠️  Services provided by several injectors (each injector creates its own instance):", "\n⚠️  複数のインジェクターで提供されているサービス（インジェクターごとに別のインスタンスになります）:"),
+    ("graph.injection.single_feature", "\n💡 providedIn: 'root' services injected from a single feature only:", "\n💡 1 つの機能からしか注入されていない providedIn: 'root' のサービス:"),
+    ("graph.injection.cycles", "\n❌ Circular injections:", "\n❌ 循環している注入:"),
+    ("graph.injection.overrides", "\n🔧 Providers overriding framework tokens:", "\n🔧 フレームワークのトークンを上書きしている provider:"),
+    ("graph.injection.non_root", " ⚠️ not the root injector", " ⚠️ ルート以外のインジェクター"),
+    ("graph.service_api.started", "🔍 Analyzing public service APIs...", "🔍 サービスの公開 API を分析しています..."),
+    ("graph.service_api.services", "   Services: {}", "   サービス数: {}"),
+    ("graph.service_api.methods", "   Public methods: {}", "   公開メソッド数: {}"),
+    ("graph.service_api.unused", "\n🗑️  Public methods never called:", "\n🗑️  どこからも呼ばれていない公開メソッド:"),
+    ("graph.packages.started", "🔍 Analyzing external package usage...", "🔍 外部パッケージの利用状況を分析しています..."),
+    ("graph.packages.packages", "   Packages: {}", "   パッケージ数: {}"),
+    ("graph.packages.single_file", "\n🗑️  Packages imported from a single file only:", "\n🗑️  1 ファイルからしか import されていないパッケージ:"),
+    ("graph.bundles.started", "🔍 Estimating bundles per lazy-loaded route...", "🔍 遅延読み込みのルートごとのバンドルを見積もっています..."),
+    ("graph.bundles.routes", "   Lazy-loaded routes: {}", "   遅延読み込みのルート数: {}"),
+    ("graph.bundles.shared", "   Files duplicated across routes: {}", "   複数のルートで重複しているファイル: {}"),
+    ("impact.started", "🔍 Computing the impact of changing {}...", "🔍 {} を変更したときの影響を計算しています..."),
+    ("impact.written", "📄 Impact report written to: {}", "📄 影響の一覧が出力されました: {}"),
+    ("impact.affected", "   Affected files: {}", "   影響を受けるファイル数: {}"),
+    ("impact.tests", "   Tests to run: {}", "   実行するテスト数: {}"),
+    ("impact.chunks", "   Chunks touched: {}", "   影響を受けるチャンク数: {}"),
+    ("impact.risk", "   Risk: {}", "   リスク: {}"),
+    ("impact.title", "# Impact of Changing {}", "# {} を変更したときの影響"),
+    ("impact.table.targets", "- Files changed: {}", "- 変更するファイル数: {}"),
+    ("impact.table.affected", "- Affected files: {}", "- 影響を受けるファイル数: {}"),
+    ("impact.table.rebuild", "- Rebuilt source: {}", "- 再ビルドの対象: {}"),
+    ("impact.table.tests", "- Tests to run: {}", "- 実行するテスト数: {}"),
+    ("impact.table.chunks", "- Chunks touched: {}", "- 影響を受けるチャンク数: {}"),
+    ("impact.table.risk", "- Risk: {}", "- リスク: {}"),
+    ("impact.targets_heading", "## Files Changed", "## 変更するファイル"),
+    ("impact.affected_heading", "## Affected Files", "## 影響を受けるファイル"),
+    ("impact.affected_file", "- {} (level {}: imports {})", "- {} ({} 段目: {} を import)"),
+    ("impact.tests_heading", "## Tests to Run", "## 実行するテスト"),
+    ("impact.projects_heading", "## Affected Projects", "## 影響を受けるプロジェクト"),
+    ("impact.chunks_heading", "## Chunks Touched", "## 影響を受けるチャンク"),
+    ("impact.chunk_files", "{} files", "{} ファイル"),
+    ("usages.started", "🔍 Finding usages of {}...", "🔍 {} の参照を探しています..."),
+    ("usages.written", "📄 Usages written to: {}", "📄 参照の一覧が出力されました: {}"),
+    ("usages.total", "   Usages: {}", "   参照数: {}"),
+    ("usages.not_found", "\n⚠️  {} is not a parsed component, service or guard; matched by name only", "\n⚠️  {} は解析したコンポーネント・サービス・ガードにないため、名前だけで探しました"),
+    ("usages.title", "# Usages of {}", "# {} の参照"),
+    ("usages.targets", "## Targets", "## 対象"),
+    ("usages.no_target", "- Not a parsed component, service or guard (matched by name only)", "- 解析したコンポーネント・サービス・ガードには見つかりません（名前だけで参照を探しました）"),
+    ("usages.kind.template", "Templates", "テンプレート"),
+    ("usages.kind.import", "Imports", "import"),
+    ("usages.kind.injection", "Injections", "注入"),
+    ("usages.kind.route", "Routes", "ルート"),
+    ("library.started", "📚 Analyzing library public APIs...", "📚 ライブラリの公開 API を解析しています..."),
+    ("library.written", "📄 Library API report written to: {}", "📄 ライブラリの公開 API が出力されました: {}"),
+    ("library.total", "   Libraries: {}, deep imports: {}, non-public imports: {}", "   ライブラリ数: {}、ディープインポート: {}、公開されていないシンボルの import: {}"),
+    ("library.not_found", "\n⚠️  No ng-package.json found; this workspace has no buildable libraries", "\n⚠️  ng-package.json が見つからないため、ビルド可能なライブラリはありません"),
+    ("library.title", "# Library Public APIs", "# ライブラリの公開 API"),
+    ("library.none", "No library with an ng-package.json was found", "ng-package.json のあるライブラリが見つかりません"),
+    ("library.entry_file", "- Entry point: {}", "- エントリーポイント: {}"),
+    ("library.public_symbols", "- Public symbols: {}", "- 公開シンボル: {}"),
+    ("library.deep_imports", "- Deep imports: {}", "- ディープインポート: {}"),
+    ("library.private_imports", "- Non-public imports: {}", "- 公開されていないシンボルの import: {}"),
+    ("library.public_api_heading", "### Public API", "### 公開 API"),
+    ("library.deep_imports_heading", "### Deep Imports (bypassing the entry point)", "### ディープインポート（エントリーポイントを経由しない import）"),
+    ("library.unresolved", "unresolved", "解決できません"),
+    ("library.private_imports_heading", "### Symbols Not in the Public API", "### 公開 API にないシンボル"),
+    // graph などの表とグラフのラベル
+    ("graph.table.title", "# Dependency Graph Analysis", "# 依存関係グラフ分析結果"),
+    ("graph.table.summary", "## Summary", "## サマリー"),
+    ("graph.table.files", "- Files: {}", "- 総ファイル数: {}"),
+    ("graph.table.dependencies", "- Dependencies: {}", "- 総依存関係数: {}"),
+    ("graph.table.cycles", "- Circular dependencies: {}", "- 循環依存数: {}"),
+    ("graph.table.orphans", "- Orphaned files: {}", "- 孤立ファイル数: {}"),
+    ("graph.table.unused", "- Unused exports: {}", "- 未使用の export 数: {}"),
+    ("graph.table.cycles_heading", "## Circular Dependencies", "## 循環依存"),
+    ("graph.table.cycle", "{}. {} (severity: {})", "{}. {} (重要度: {})"),
+    ("graph.table.most_imported", "## Most Imported Files", "## 最もインポートされているファイル"),
+    ("graph.table.imported_count", "- {} ({} imports)", "- {} ({}回)"),
+    ("graph.table.most_dependent", "## Files With the Most Dependencies", "## 最も依存関係が多いファイル"),
+    ("graph.table.dependency_count", "- {} ({} dependencies)", "- {} ({}個の依存関係)"),
+    ("graph.table.orphans_heading", "## Orphaned Files", "## 孤立ファイル"),
+    ("graph.table.unused_heading", "## Unused Exports", "## 未使用の export"),
+    ("graph.table.unused_columns", "| Directory | Count |", "| ディレクトリ | 件数 |"),
+    ("graph.table.longest_chains", "## Longest Dependency Chains", "## 最長の依存チェーン"),
+    ("graph.table.chain", "- depth {}: {}", "- 深さ {}: {}"),
+    ("graph.table.depth_heading", "## Dependency Depth", "## 依存関係の深さ"),
+    ("graph.table.depth", "- {} (depth: {})", "- {} (深さ: {})"),
+    ("graph.label.symbols", "{} symbols", "{}個のシンボル"),
+    ("graph.label.symbols_short", "{} symbols", "{}個"),
+    ("graph.label.cycles", "circular dependencies", "循環依存"),
+    ("graph.label.cycle", "cycle", "循環"),
+    ("graph.html.search", "Search", "検索"),
+    ("graph.html.search_placeholder", "File name or path", "ファイル名・パス"),
+    ("graph.html.directory", "Directory", "ディレクトリ"),
+    ("graph.html.all", "All", "すべて"),
+    ("graph.html.legend", "Legend", "凡例"),
+    ("graph.html.cycles", "Circular Dependencies", "循環依存"),
+    ("graph.html.help", "Scroll to zoom, drag the background to pan and drag a node to move it.", "ホイールでズーム、背景のドラッグで移動、ノードのドラッグで配置を変更できます。"),
+    ("bundles.title", "# Estimated Bundles per Lazy-Loaded Route", "# 遅延読み込みのルートごとのバンドル見積もり"),
+    ("bundles.note", "Only project sources reached through static imports are counted (node_modules and built sizes are not included).", "静的な import を辿ったプロジェクト内のソースのみを数えています（node_modules とビルド後のサイズは含みません）。"),
+    ("bundles.chunks", "## Chunks", "## チャンク"),
+    ("bundles.columns", "| Chunk | Entry | Files | Source size | Route definition |", "| チャンク | エントリ | ファイル数 | ソースサイズ | ルート定義 |"),
+    ("bundles.shared", "## Files to Consider Moving to a Shared Chunk", "## 共有チャンクへの移動を検討するファイル"),
+    ("bundles.shared_file", "- {} ({}, {} routes: {})", "- {} ({}, {}ルート: {})"),
+    ("packages.title", "# External Package Usage", "# 外部パッケージの利用状況"),
+    ("packages.packages", "- Packages: {}", "- パッケージ数: {}"),
+    ("packages.per_package", "## Files Importing Each Package", "## パッケージごとの import 元ファイル数"),
+    ("packages.framework", "framework", "フレームワーク"),
+    ("packages.package", "- {} ({} files, {}%){}", "- {} ({}ファイル, {}%){}"),
+    ("packages.dominant", "## Most Used Libraries", "## よく使われているライブラリ"),
+    ("packages.candidates", "## Removal Candidates (imported from a single file)", "## 削除候補（1 ファイルからのみ import）"),
+    ("packages.undeclared", "## Imports Not Declared in package.json", "## package.json に宣言されていない import"),
+    ("packages.files", "- {} ({} files)", "- {} ({}ファイル)"),
+    ("service_api.title", "# Public Service APIs", "# サービスの公開 API"),
+    ("service_api.services", "- Services: {}", "- サービス数: {}"),
+    ("service_api.methods", "- Public methods: {}", "- 公開メソッド数: {}"),
+    ("service_api.unused_count", "- Public methods never called: {}", "- 呼ばれていない公開メソッド数: {}"),
+    ("service_api.no_methods", "- No public methods", "- 公開メソッドなし"),
+    ("service_api.no_callers", "no callers", "呼び出し元なし"),
+    ("service_api.unused", "## Public Methods Never Called", "## 呼ばれていない公開メソッド"),
+    // レポートの見出し
+    ("report.title", "Angular Analysis Report", "Angular 分析レポート"),
+    ("report.generated_by", "Generated by ng-analyzer", "ng-analyzer で生成"),
+    ("report.health_score", "Health Score", "健全性スコア"),
+    ("report.health", "Health", "健全性"),
+    ("report.grade", "grade", "評価"),
+    ("report.budgets", "Budgets", "予算"),
+    ("report.drilldown", "Directory Drilldown", "ディレクトリ別"),
+    ("report.result", "Analysis Result", "分析結果"),
+    ("report.project", "Project", "プロジェクト"),
+    ("report.issues", "Issues", "Issue"),
+    ("report.issues_grouped", "Issues (grouped by {})", "Issue（{} ごと）"),
+    ("report.no_issues", "✅ No issues found!", "✅ issue は見つかりませんでした"),
+    ("report.omitted", "… and {} more {} issues (raise --max-issues-per-rule to see them)", "… ほか {} 件の {}（--max-issues-per-rule を増やすと表示されます）"),
+    ("report.metrics", "Metrics", "指標"),
+    ("report.adoption", "Migration Adoption", "移行の進み具合"),
+    ("report.api_surface", "Component API Surface (average {}, max {})", "コンポーネントの API の大きさ（平均 {}、最大 {}）"),
+    ("report.largest_files", "Largest Files", "大きいファイル"),
+    ("report.largest_components", "Largest Components", "大きいコンポーネント"),
+    ("report.render_chains", "Deepest Render Chains", "深い描画チェーン"),
+    ("report.render_chains_max", "Deepest Render Chains (max depth {})", "深い描画チェーン（最大の深さ {}）"),
+    ("report.type_safety", "Type Safety", "型安全"),
+    ("report.type_escapes", "Type Escapes by File", "ファイルごとの型検査の抜け道"),
+    ("report.testability", "Feature Testability", "機能ごとのテストしやすさ"),
+    ("report.zoneless", "Zoneless Readiness", "Zoneless 対応"),
+    ("report.cd_hotspots", "Change Detection Hotspots", "変更検知のホットスポット"),
+    ("report.recommendations", "Recommendations", "推奨事項"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages() {
+        assert_eq!(Lang::from_locale("ja_JP.UTF-8"), Lang::Ja);
+        assert_eq!(Lang::from_locale("en_US.UTF-8"), Lang::En);
+        assert_eq!(Lang::from_locale(""), Lang::En);
+        assert_eq!(Lang::Ja.trf("graph.files.cycles_found", &[&3]), "⚠️  3個の循環依存を発見しました");
+        assert_eq!(Lang::En.trf("graph.tree.deepest", &[&4, &"App > Shell"]), "   Deepest level: 4 (App > Shell)");
+
+        // 両方の言語で置き換える {} の数がそろっていること
+        for (key, en, ja) in MESSAGES {
+            assert_eq!(en.matches("{}").count(), ja.matches("{}").count(), "{}", key);
+            assert_eq!(MESSAGES.iter().filter(|(id, _, _)| id == key).count(), 1, "{}", key);
+        }
+    }
+}
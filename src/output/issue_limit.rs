@@ -0,0 +1,78 @@
+use crate::ast::{AnalysisResult, OmittedIssues};
+use std::collections::HashMap;
+
+// ルールごとに先頭の max 件だけ残し、残りは件数だけ記録する
+pub fn limit_issues_per_rule(results: &mut [AnalysisResult], max: usize) {
+    for result in results.iter_mut() {
+        let mut shown: HashMap<String, usize> = HashMap::new();
+        let mut kept = Vec::new();
+        for issue in std::mem::take(&mut result.issues) {
+            let count = shown.entry(issue.rule.clone()).or_default();
+            if *count < max {
+                *count += 1;
+                kept.push(issue);
+                continue;
+            }
+            match result.omitted_issues.iter_mut().find(|omitted| omitted.rule == issue.rule && omitted.severity == issue.severity) {
+                Some(omitted) => omitted.count += 1,
+                None => result.omitted_issues.push(OmittedIssues {
+                    rule: issue.rule,
+                    severity: issue.severity,
+                    count: 1,
+                }),
+            }
+        }
+        result.issues = kept;
+    }
+}
+
+impl AnalysisResult {
+    // 省いた分も含めた件数
+    pub fn total_issues(&self) -> usize {
+        self.issues.len() + self.omitted_issues.iter().map(|omitted| omitted.count).sum::<usize>()
+    }
+
+    // 「… and 412 more」の注記用に、ルールごとの省略件数を出現順に返す
+    pub fn omitted_per_rule(&self) -> Vec<(&str, usize)> {
+        let mut per_rule: Vec<(&str, usize)> = Vec::new();
+        for omitted in &self.omitted_issues {
+            match per_rule.iter_mut().find(|(rule, _)| *rule == omitted.rule) {
+                Some((_, count)) => *count += omitted.count,
+                None => per_rule.push((omitted.rule.as_str(), omitted.count)),
+            }
+        }
+        per_rule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::IssueBuilder;
+    use crate::ast::Severity;
+
+    #[test]
+    fn test_limit_issues_per_rule() {
+        let issue = |rule: &str, severity: Severity| IssueBuilder::new(rule, "a.ts").severity(severity).build();
+        let mut results = vec![AnalysisResult {
+            issues: vec![
+                issue("ng-deep-usage", Severity::Warning),
+                issue("missing-onpush", Severity::Info),
+                issue("ng-deep-usage", Severity::Warning),
+                issue("ng-deep-usage", Severity::Error),
+                issue("ng-deep-usage", Severity::Warning),
+                issue("ng-deep-usage", Severity::Warning),
+            ],
+            ..Default::default()
+        }];
+
+        limit_issues_per_rule(&mut results, 2);
+
+        let result = &results[0];
+        assert_eq!(result.issues.len(), 3);
+        assert_eq!(result.total_issues(), 6);
+        assert_eq!(result.omitted_per_rule(), vec![("ng-deep-usage", 3)]);
+        let errors: usize = result.omitted_issues.iter().filter(|omitted| omitted.severity == Severity::Error).map(|omitted| omitted.count).sum();
+        assert_eq!(errors, 1);
+    }
+}
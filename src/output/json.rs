@@ -1,18 +1,27 @@
 use super::OutputFormatter;
-use crate::ast::AnalysisResult;
+use super::schema::{analysis_document, SchemaVersion};
+use crate::ast::{AnalysisResult, BudgetResult, HealthScore};
 use anyhow::Result;
 use serde_json;
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 
 pub struct JsonFormatter {
     pretty: bool,
+    schema: Option<SchemaVersion>,
+    health: Option<HealthScore>,
+    budgets: Vec<BudgetResult>,
+    include_project: bool,
 }
 
 impl JsonFormatter {
     pub fn new() -> Self {
         Self {
             pretty: true,
+            schema: None,
+            health: None,
+            budgets: Vec::new(),
+            include_project: false,
         }
     }
 
@@ -20,22 +29,49 @@ impl JsonFormatter {
     pub fn new_compact() -> Self {
         Self {
             pretty: false,
+            schema: None,
+            health: None,
+            budgets: Vec::new(),
+            include_project: false,
         }
     }
+
+    // --schema で指定した版のフィールドだけを出力する
+    pub fn with_schema(mut self, schema: Option<SchemaVersion>) -> Self {
+        self.schema = schema;
+        self
+    }
+
+    pub fn with_health(mut self, health: Option<HealthScore>) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub fn with_budgets(mut self, budgets: Vec<BudgetResult>) -> Self {
+        self.budgets = budgets;
+        self
+    }
+
+    // --include-project で結果ごとにプロジェクト全体を含める
+    pub fn with_include_project(mut self, include_project: bool) -> Self {
+        self.include_project = include_project;
+        self
+    }
 }
 
 impl OutputFormatter for JsonFormatter {
     fn format(&self, results: &[AnalysisResult]) -> Result<String> {
+        let document = analysis_document(results, self.health.as_ref(), &self.budgets, self.include_project, self.schema);
         let output = if self.pretty {
-            serde_json::to_string_pretty(results)?
+            serde_json::to_string_pretty(&document)?
         } else {
-            serde_json::to_string(results)?
+            serde_json::to_string(&document)?
         };
         
         Ok(output)
     }
 
-    fn write_to_file(&self, results: &[AnalysisResult], path: &PathBuf) -> Result<()> {
+    fn write_to_file(&self, results: &[AnalysisResult], path: &Path) -> Result<()> {
         let content = self.format(results)?;
         fs::write(path, content)?;
         Ok(())
@@ -0,0 +1,93 @@
+use crate::output::history::utc_timestamp;
+use crate::output::i18n::Lang;
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::time::{Duration, SystemTime};
+
+// --log-format で選ぶ経過の出し方。出すのはフェーズの開始・終了と警告だけで、
+// span の入れ子や subscriber の差し替えは要らないので tracing は使わない
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LogFormat {
+    // --verbose のときだけ、人が読む形で標準出力に出す
+    #[default]
+    Text,
+    // 1 行 1 イベントの JSON を常に標準エラーに出す。標準出力のレポートとは混ざらない
+    Json,
+}
+
+impl LogFormat {
+    pub fn parse(format: &str, lang: Lang) -> Result<Self> {
+        match format {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(anyhow::anyhow!("{}", lang.trf("error.unsupported", &[&"--log-format", &format, &"text, json"]))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Logger {
+    format: LogFormat,
+    verbose: bool,
+}
+
+impl Logger {
+    pub fn new(format: LogFormat, verbose: bool) -> Self {
+        Self { format, verbose }
+    }
+
+    pub fn info(&self, event: &str, message: &str, fields: Value) {
+        match self.format {
+            LogFormat::Text if self.verbose => println!("{}", message),
+            LogFormat::Text => {}
+            LogFormat::Json => eprintln!("{}", log_line("info", event, message, fields)),
+        }
+    }
+
+    // 警告は --verbose に関係なく出す
+    pub fn warn(&self, event: &str, message: &str, fields: Value) {
+        match self.format {
+            LogFormat::Text => eprintln!("{}", message),
+            LogFormat::Json => eprintln!("{}", log_line("warn", event, message, fields)),
+        }
+    }
+
+    // フェーズの所要時間。テキストでは --timing の表に任せて何も出さない
+    pub fn phase(&self, phase: &str, elapsed: Duration, fields: Value) {
+        if self.format == LogFormat::Json {
+            let mut fields = fields;
+            fields["phase"] = json!(phase);
+            fields["elapsed_ms"] = json!(elapsed.as_secs_f64() * 1000.0);
+            eprintln!("{}", log_line("info", "phase_finished", &format!("{} finished", phase), fields));
+        }
+    }
+}
+
+// {"timestamp":"...","level":"info","event":"...","message":"...", ...fields}
+fn log_line(level: &str, event: &str, message: &str, fields: Value) -> String {
+    let mut line = json!({
+        "timestamp": utc_timestamp(SystemTime::now()),
+        "level": level,
+        "event": event,
+        "message": message,
+    });
+    if let (Some(line), Value::Object(fields)) = (line.as_object_mut(), fields) {
+        line.extend(fields);
+    }
+    line.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_line() {
+        let line: Value = serde_json::from_str(&log_line("info", "project_parsed", "📊 Found 3 components", json!({ "components": 3 }))).unwrap();
+        assert_eq!(line["level"], "info");
+        assert_eq!(line["event"], "project_parsed");
+        assert_eq!(line["components"], 3);
+        assert!(line["timestamp"].as_str().unwrap().ends_with('Z'));
+        assert!(LogFormat::parse("xml", Lang::En).is_err());
+    }
+}
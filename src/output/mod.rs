@@ -1,25 +1,107 @@
+pub mod bench;
+pub mod csv;
+pub mod drilldown;
+pub mod golden;
+pub mod grouping;
+pub mod history;
 pub mod html;
+pub mod i18n;
+pub mod issue_limit;
 pub mod json;
+pub mod log;
+pub mod ndjson;
 pub mod table;
 pub mod graph;
+pub mod graph_html;
+pub mod progress;
+pub mod rule_stats;
+pub mod schema;
+pub mod summary;
+pub mod svg;
+pub mod top;
 
-use crate::ast::AnalysisResult;
+use crate::ast::{AdoptionMetrics, AnalysisResult, BudgetResult, NgComponent, NgProject, SourceFile};
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+pub use csv::CsvFormatter;
 pub use json::JsonFormatter;
 pub use html::HtmlFormatter;
+pub use ndjson::NdjsonFormatter;
 pub use table::TableFormatter;
 
+pub const LARGEST_ENTRIES: usize = 10;
+
+// 文字数で切り詰める。バイト位置で切るとマルチバイト文字の途中で panic する
+pub fn truncate_end(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(max_chars.saturating_sub(3)).collect();
+    format!("{}...", kept)
+}
+
+// パスなど末尾が大事な文字列は先頭を省く
+pub fn truncate_start(text: &str, max_chars: usize) -> String {
+    let count = text.chars().count();
+    if count <= max_chars {
+        return text.to_string();
+    }
+    let kept: String = text.chars().skip(count - max_chars.saturating_sub(3)).collect();
+    format!("...{}", kept)
+}
+
+pub fn largest_files(project: &NgProject) -> Vec<&SourceFile> {
+    let mut files: Vec<_> = project.files.iter().collect();
+    files.sort_by(|a, b| b.lines.logical.cmp(&a.lines.logical).then_with(|| a.file_path.cmp(&b.file_path)));
+    files.truncate(LARGEST_ENTRIES);
+    files
+}
+
+pub fn largest_components(project: &NgProject) -> Vec<&NgComponent> {
+    let mut components: Vec<_> = project.components.iter().collect();
+    components.sort_by(|a, b| b.lines.logical.cmp(&a.lines.logical).then_with(|| a.name.cmp(&b.name)));
+    components.truncate(LARGEST_ENTRIES);
+    components
+}
+
+pub fn adoption_rows(adoption: &AdoptionMetrics) -> [(&'static str, Option<f64>); 4] {
+    [
+        ("Standalone Components", adoption.standalone_percentage),
+        ("Signal Inputs", adoption.signal_inputs_percentage),
+        ("OnPush Components", adoption.onpush_percentage),
+        ("Typed Forms", adoption.typed_forms_percentage),
+    ]
+}
+
+// 予算の上限・下限と実際の値。min_ で始まるものは下限、割合は %、平均は小数 1 桁で表示する
+pub fn budget_limit(budget: &BudgetResult) -> String {
+    let comparison = if budget.budget.starts_with("min_") { ">=" } else { "<=" };
+    format!("{} {}", comparison, budget_value(budget, budget.limit))
+}
+
+pub fn budget_value(budget: &BudgetResult, value: f64) -> String {
+    if budget.budget.ends_with("_percentage") {
+        format!("{:.1}%", value)
+    } else if budget.budget.contains("average") {
+        format!("{:.1}", value)
+    } else {
+        format!("{}", value.round())
+    }
+}
+
 pub trait OutputFormatter {
     fn format(&self, results: &[AnalysisResult]) -> Result<String>;
-    fn write_to_file(&self, results: &[AnalysisResult], path: &PathBuf) -> Result<()>;
+    #[allow(dead_code)]
+    fn write_to_file(&self, results: &[AnalysisResult], path: &Path) -> Result<()>;
 }
 
+#[allow(dead_code)]
 pub struct MultiFormatter {
     formatters: Vec<(String, Box<dyn OutputFormatter>)>,
 }
 
+#[allow(dead_code)]
 impl MultiFormatter {
     pub fn new() -> Self {
         Self {
@@ -48,10 +130,13 @@ pub fn create_formatter(format: &str) -> Result<Box<dyn OutputFormatter>> {
         "json" => Ok(Box::new(JsonFormatter::new())),
         "html" => Ok(Box::new(HtmlFormatter::new())),
         "table" => Ok(Box::new(TableFormatter::new())),
+        "csv" => Ok(Box::new(CsvFormatter::new())),
+        "ndjson" => Ok(Box::new(NdjsonFormatter::new())),
         _ => Err(anyhow::anyhow!("Unsupported format: {}", format)),
     }
 }
 
+#[allow(dead_code)]
 pub fn create_multi_formatter(formats: &[String]) -> Result<MultiFormatter> {
     let mut multi = MultiFormatter::new();
     
@@ -61,4 +146,16 @@ pub fn create_multi_formatter(formats: &[String]) -> Result<MultiFormatter> {
     }
     
     Ok(multi)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_multibyte() {
+        let message = "コンポーネントの変更検知戦略を OnPush にしてください";
+        assert_eq!(truncate_end(message, 10), "コンポーネント...");
+        assert_eq!(truncate_start("src/アプリ/設定.component.ts", 15), "...component.ts");
+        assert_eq!(truncate_end("short", 10), "short");
+    }
+}
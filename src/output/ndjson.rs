@@ -0,0 +1,134 @@
+use super::OutputFormatter;
+use super::grouping::duplicate_key;
+use crate::ast::{AnalysisResult, Issue};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread::JoinHandle;
+
+// 1 行に 1 つの issue の JSON を書く。レポート全体の文字列や serde_json::Value を組み立てないので、
+// issue が多くてもメモリを使わず jq やログ基盤にそのまま流せる
+pub struct NdjsonFormatter;
+
+impl NdjsonFormatter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn write<W: Write>(&self, results: &[AnalysisResult], writer: &mut W) -> Result<()> {
+        for issue in results.iter().flat_map(|result| &result.issues) {
+            writeln!(writer, "{}", serde_json::to_string(issue)?)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+// アナライザーが終わるたびに受け取った issue を、全体の結果を待たずに書き出す。
+// 重複の除去と --max-issues-per-rule は collapse_duplicate_issues・limit_issues_per_rule と同じ基準で行う
+pub struct NdjsonStream {
+    handle: JoinHandle<Result<()>>,
+}
+
+impl NdjsonStream {
+    pub fn spawn<W: Write + Send + 'static>(writer: W, max_issues_per_rule: Option<usize>) -> (Sender<Vec<Issue>>, Self) {
+        let (sender, receiver) = channel();
+        let handle = std::thread::spawn(move || write_stream(receiver, writer, max_issues_per_rule));
+        (sender, Self { handle })
+    }
+
+    // 送信側がすべて drop されてから呼ぶ
+    pub fn finish(self) -> Result<()> {
+        self.handle.join().map_err(|_| anyhow::anyhow!("ndjson writer thread panicked"))?
+    }
+}
+
+// 書き込みに失敗したら receiver を drop して終える。送信側の send は失敗するだけで解析は続く
+fn write_stream<W: Write>(receiver: Receiver<Vec<Issue>>, writer: W, max_issues_per_rule: Option<usize>) -> Result<()> {
+    let mut writer = BufWriter::new(writer);
+    let mut seen = HashSet::new();
+    let mut shown: HashMap<String, usize> = HashMap::new();
+    for issues in receiver {
+        for issue in issues {
+            if !seen.insert(duplicate_key(&issue)) {
+                continue;
+            }
+            let count = shown.entry(issue.rule.clone()).or_default();
+            if max_issues_per_rule.is_some_and(|max| *count >= max) {
+                continue;
+            }
+            *count += 1;
+            writeln!(writer, "{}", serde_json::to_string(&issue)?)?;
+        }
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+impl OutputFormatter for NdjsonFormatter {
+    fn format(&self, results: &[AnalysisResult]) -> Result<String> {
+        let mut output = Vec::new();
+        self.write(results, &mut output)?;
+        Ok(String::from_utf8(output)?)
+    }
+
+    fn write_to_file(&self, results: &[AnalysisResult], path: &Path) -> Result<()> {
+        self.write(results, &mut BufWriter::new(File::create(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::IssueBuilder;
+    use crate::ast::Issue;
+    use serde_json::Value;
+
+    #[test]
+    fn test_one_issue_per_line() {
+        let issue = |rule: &str, line: Option<u32>| Issue {
+            line,
+            ..IssueBuilder::new(rule, "src/app/app.component.ts").message("line one\nline two").build()
+        };
+        let results = vec![
+            AnalysisResult { issues: vec![issue("component-complexity", Some(3)), issue("missing-onpush", None)], ..Default::default() },
+            AnalysisResult::default(),
+            AnalysisResult { issues: vec![issue("memory-leak-risk", Some(12))], ..Default::default() },
+        ];
+
+        let output = NdjsonFormatter::new().format(&results).unwrap();
+        let lines: Vec<Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["rule"], "component-complexity");
+        assert_eq!(lines[0]["message"], "line one\nline two");
+        assert_eq!(lines[2]["line"], 12);
+
+        // 重複は 1 度だけ、ルールごとに上限まで書く
+        let output = SharedBuffer::default();
+        let (sender, stream) = NdjsonStream::spawn(output.clone(), Some(1));
+        sender.send(vec![issue("component-complexity", Some(3)), issue("component-complexity", Some(4))]).unwrap();
+        sender.send(vec![issue("component-complexity", Some(3)), issue("missing-onpush", None)]).unwrap();
+        drop(sender);
+        stream.finish().unwrap();
+        let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        let rules: Vec<String> = output.lines().map(|line| serde_json::from_str::<Value>(line).unwrap()["rule"].as_str().unwrap().to_string()).collect();
+        assert_eq!(rules, vec!["component-complexity", "missing-onpush"]);
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+}
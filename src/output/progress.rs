@@ -0,0 +1,112 @@
+use crate::output::i18n::Lang;
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const BAR_WIDTH: usize = 30;
+// 再描画の間隔。ファイルごとに描くと端末への書き込みで遅くなる
+const REDRAW_INTERVAL: Duration = Duration::from_millis(80);
+
+// 標準エラーの 1 行に描く進捗バー。出力を壊さないよう、端末のときだけ有効にする。
+// 使うのは件数・ラベル・経過時間だけなので、indicatif に依存せずここで描く
+pub struct Progress {
+    enabled: bool,
+    lang: Lang,
+    label: Mutex<String>,
+    total: AtomicUsize,
+    done: AtomicUsize,
+    last_draw: Mutex<Option<Instant>>,
+}
+
+impl Progress {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            lang: Lang::En,
+            label: Mutex::new(String::new()),
+            total: AtomicUsize::new(0),
+            done: AtomicUsize::new(0),
+            last_draw: Mutex::new(None),
+        }
+    }
+
+    pub fn hidden() -> Self {
+        Self::new(false)
+    }
+
+    pub fn with_lang(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    // 新しいフェーズを始める。key はフェーズ名のメッセージのキーで、total が 0 なら件数だけを表示する
+    pub fn start(&self, key: &str, total: usize) {
+        *self.label.lock().unwrap() = self.lang.tr(key).to_string();
+        self.total.store(total, Ordering::Relaxed);
+        self.done.store(0, Ordering::Relaxed);
+        *self.last_draw.lock().unwrap() = None;
+        self.draw("");
+    }
+
+    // rayon のスレッドからも呼ばれる
+    pub fn inc(&self, item: &str) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        if !self.enabled {
+            return;
+        }
+        let mut last_draw = self.last_draw.lock().unwrap();
+        if done < self.total.load(Ordering::Relaxed) && last_draw.is_some_and(|last| last.elapsed() < REDRAW_INTERVAL) {
+            return;
+        }
+        *last_draw = Some(Instant::now());
+        drop(last_draw);
+        self.draw(item);
+    }
+
+    // 行を消して、後に続く出力と混ざらないようにする
+    pub fn finish(&self) {
+        if self.enabled {
+            eprint!("\r\x1b[2K");
+            let _ = std::io::stderr().flush();
+        }
+    }
+
+    fn draw(&self, item: &str) {
+        if !self.enabled {
+            return;
+        }
+        let line = render_line(&self.label.lock().unwrap(), self.done.load(Ordering::Relaxed), self.total.load(Ordering::Relaxed), item);
+        eprint!("\r\x1b[2K{}", line);
+        let _ = std::io::stderr().flush();
+    }
+}
+
+fn render_line(label: &str, done: usize, total: usize, item: &str) -> String {
+    let item: String = item.chars().rev().take(50).collect::<Vec<_>>().into_iter().rev().collect();
+    if total == 0 {
+        return format!("{} {} {}", label, done, item);
+    }
+    let filled = (done.min(total) * BAR_WIDTH) / total;
+    format!("{} [{}{}] {}/{} {}", label, "#".repeat(filled), "-".repeat(BAR_WIDTH - filled), done, total, item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_progress_line() {
+        assert_eq!(
+            render_line("parse", 3, 12, "src/app/app.component.ts"),
+            format!("parse [{}{}] 3/12 src/app/app.component.ts", "#".repeat(7), "-".repeat(23))
+        );
+        assert_eq!(render_line("walk", 5, 0, ""), "walk 5 ");
+
+        let progress = Progress::hidden();
+        progress.start("progress.analyzing", 2);
+        progress.inc("component");
+        progress.inc("styles");
+        assert_eq!(progress.done.load(Ordering::Relaxed), 2);
+    }
+}
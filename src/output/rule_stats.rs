@@ -0,0 +1,75 @@
+use crate::ast::AnalysisResult;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleStatistic {
+    pub rule: String,
+    pub issues: usize,
+    pub files: usize,
+    // 全 issue に占める割合 (%)
+    pub share: f64,
+}
+
+impl RuleStatistic {
+    pub fn per_file(&self) -> f64 {
+        if self.files == 0 {
+            0.0
+        } else {
+            self.issues as f64 / self.files as f64
+        }
+    }
+}
+
+// issue の多いルール順に並べる
+pub fn rule_statistics(results: &[AnalysisResult]) -> Vec<RuleStatistic> {
+    let mut by_rule: BTreeMap<&str, (usize, HashSet<&str>)> = BTreeMap::new();
+    for issue in results.iter().flat_map(|result| &result.issues) {
+        let (count, files) = by_rule.entry(issue.rule.as_str()).or_default();
+        *count += 1;
+        files.insert(issue.file_path.as_str());
+    }
+
+    let total: usize = by_rule.values().map(|(count, _)| count).sum();
+    let mut statistics: Vec<RuleStatistic> = by_rule.into_iter()
+        .map(|(rule, (issues, files))| RuleStatistic {
+            rule: rule.to_string(),
+            issues,
+            files: files.len(),
+            share: issues as f64 * 100.0 / total as f64,
+        })
+        .collect();
+    statistics.sort_by(|a, b| b.issues.cmp(&a.issues).then_with(|| a.rule.cmp(&b.rule)));
+    statistics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::IssueBuilder;
+    use crate::ast::ProjectMetrics;
+
+    #[test]
+    fn test_rule_statistics() {
+        let issue = |rule: &str, file_path: &str| IssueBuilder::new(rule, file_path).build();
+        let result = |issues| AnalysisResult {
+            project: Default::default(),
+            issues,
+            metrics: ProjectMetrics::default(),
+            recommendations: vec![],
+            omitted_issues: vec![],
+        };
+        let results = vec![
+            result(vec![issue("ng-deep-usage", "a.scss"), issue("ng-deep-usage", "a.scss"), issue("ng-deep-usage", "b.scss")]),
+            result(vec![issue("missing-onpush", "a.ts")]),
+        ];
+
+        let statistics = rule_statistics(&results);
+
+        assert_eq!(statistics.len(), 2);
+        assert_eq!((statistics[0].rule.as_str(), statistics[0].issues, statistics[0].files), ("ng-deep-usage", 3, 2));
+        assert_eq!(statistics[0].per_file(), 1.5);
+        assert_eq!(statistics[0].share, 75.0);
+        assert_eq!(statistics[1].share, 25.0);
+    }
+}
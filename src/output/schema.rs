@@ -0,0 +1,442 @@
+use crate::ast::{AnalysisResult, BudgetResult, HealthScore, ProjectInfo};
+use crate::output::i18n::Lang;
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+// JSON 出力の先頭に付ける schema_version。既存のフィールドの削除・改名・型の変更をするときだけ上げる
+pub const SCHEMA_VERSION: u32 = 1;
+
+// --schema で固定する出力形式。指定すると、その版のスキーマに載っているフィールドだけを出力する
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaVersion {
+    V1,
+}
+
+impl SchemaVersion {
+    pub fn parse(version: &str, lang: Lang) -> Result<Self> {
+        match version.to_lowercase().as_str() {
+            "v1" | "1" => Ok(Self::V1),
+            _ => Err(anyhow::anyhow!("{}", lang.trf("error.unsupported", &[&"--schema", &version, &"v1"]))),
+        }
+    }
+
+    pub fn number(self) -> u32 {
+        match self {
+            Self::V1 => 1,
+        }
+    }
+
+    pub fn document(self, kind: SchemaKind) -> Value {
+        match self {
+            Self::V1 => v1_schema(kind),
+        }
+    }
+
+    // スキーマにないフィールド（解析中の AST など）を取り除き、この版の形に揃える
+    pub fn pin(self, kind: SchemaKind, value: Value) -> Value {
+        let schema = self.document(kind);
+        let mut pinned = project(value, &schema, &schema);
+        if let Value::Object(object) = &mut pinned {
+            object.insert("schema_version".to_string(), json!(self.number()));
+        }
+        pinned
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaKind {
+    // audit・component などの解析結果
+    Issues,
+    // graph --kind files の JSON
+    Graph,
+    // 解析結果の metrics
+    Metrics,
+}
+
+impl SchemaKind {
+    pub fn parse(kind: &str, lang: Lang) -> Result<Self> {
+        match kind {
+            "issues" => Ok(Self::Issues),
+            "graph" => Ok(Self::Graph),
+            "metrics" => Ok(Self::Metrics),
+            _ => Err(anyhow::anyhow!("{}", lang.trf("error.unsupported", &[&"schema", &kind, &"issues, graph, metrics"]))),
+        }
+    }
+}
+
+// budgets は設定ファイルで予算を指定したときだけ出力する。
+// プロジェクトは概要だけを 1 回出し、include_project のときは以前と同じく結果ごとに全体を含める
+pub fn analysis_document(
+    results: &[AnalysisResult],
+    health: Option<&HealthScore>,
+    budgets: &[BudgetResult],
+    include_project: bool,
+    schema: Option<SchemaVersion>,
+) -> Value {
+    let mut document = json!({
+        "schema_version": SCHEMA_VERSION,
+        "results": results,
+    });
+    if let Some(result) = results.first() {
+        document["project"] = json!(ProjectInfo::new(&result.project));
+    }
+    if include_project {
+        for (value, result) in document["results"].as_array_mut().into_iter().flatten().zip(results) {
+            value["project"] = json!(result.project);
+        }
+    }
+    if let Some(health) = health {
+        document["health"] = json!(health);
+    }
+    if !budgets.is_empty() {
+        document["budgets"] = json!(budgets);
+    }
+    match schema {
+        Some(version) => version.pin(SchemaKind::Issues, document),
+        None => document,
+    }
+}
+
+fn project(value: Value, schema: &Value, root: &Value) -> Value {
+    let schema = resolve(schema, root);
+    match value {
+        Value::Object(object) => match schema.get("properties").and_then(Value::as_object) {
+            Some(properties) => {
+                let kept: Map<String, Value> = object.into_iter()
+                    .filter_map(|(key, value)| {
+                        let property = properties.get(&key)?;
+                        Some((key, project(value, property, root)))
+                    })
+                    .collect();
+                Value::Object(kept)
+            }
+            None => Value::Object(object),
+        },
+        Value::Array(items) => match schema.get("items") {
+            Some(item) => Value::Array(items.into_iter().map(|value| project(value, item, root)).collect()),
+            None => Value::Array(items),
+        },
+        value => value,
+    }
+}
+
+// "#/$defs/issue" の形の $ref だけを解決する
+fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    schema.get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|reference| reference.strip_prefix("#/$defs/"))
+        .and_then(|name| root["$defs"].get(name))
+        .unwrap_or(schema)
+}
+
+fn v1_schema(kind: SchemaKind) -> Value {
+    match kind {
+        SchemaKind::Issues => json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$id": "https://github.com/your-org/ng-analyzer/schemas/v1/issues.json",
+            "title": "ng-analyzer analysis results",
+            "type": "object",
+            "required": ["schema_version", "results"],
+            "properties": {
+                "schema_version": { "const": 1 },
+                "project": {
+                    "type": "object",
+                    "description": "Summary of the analyzed project, written once for all results",
+                    "required": ["name", "root_path", "files", "components", "services", "modules", "pipes", "directives"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "root_path": { "type": "string" },
+                        "files": { "type": "integer", "minimum": 0 },
+                        "components": { "type": "integer", "minimum": 0 },
+                        "services": { "type": "integer", "minimum": 0 },
+                        "modules": { "type": "integer", "minimum": 0 },
+                        "pipes": { "type": "integer", "minimum": 0 },
+                        "directives": { "type": "integer", "minimum": 0 }
+                    }
+                },
+                "results": {
+                    "type": "array",
+                    "description": "One entry per analyzer, in the order they were run",
+                    "items": {
+                        "type": "object",
+                        "required": ["issues", "metrics", "recommendations"],
+                        "properties": {
+                            "issues": { "type": "array", "items": { "$ref": "#/$defs/issue" } },
+                            "metrics": { "$ref": "#/$defs/metrics" },
+                            "recommendations": { "type": "array", "items": { "$ref": "#/$defs/recommendation" } },
+                            "omitted_issues": { "type": "array", "items": { "$ref": "#/$defs/omitted_issues" } }
+                        }
+                    }
+                },
+                "health": {
+                    "type": "object",
+                    "description": "Weighted project health score; categories that could not be evaluated or have weight 0 are left out",
+                    "required": ["score", "grade", "categories"],
+                    "properties": {
+                        "score": { "type": "number", "minimum": 0, "maximum": 100 },
+                        "grade": { "enum": ["A", "B", "C", "D", "F"] },
+                        "categories": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "required": ["category", "score", "weight", "detail"],
+                                "properties": {
+                                    "category": { "enum": ["issue_density", "complexity", "onpush", "cycles", "coverage"] },
+                                    "score": { "type": "number", "minimum": 0, "maximum": 100 },
+                                    "weight": { "type": "number" },
+                                    "detail": { "type": "string" }
+                                }
+                            }
+                        }
+                    }
+                },
+                "budgets": {
+                    "type": "array",
+                    "description": "Budgets configured in .ng-analyzer.json, in configuration key order",
+                    "items": {
+                        "type": "object",
+                        "required": ["budget", "limit", "actual", "passed"],
+                        "properties": {
+                            "budget": { "type": "string" },
+                            "limit": { "type": "number" },
+                            "actual": { "type": "number" },
+                            "passed": { "type": "boolean" },
+                            "worst": { "type": ["string", "null"] }
+                        }
+                    }
+                }
+            },
+            "$defs": {
+                "severity": { "enum": ["Error", "Warning", "Info"] },
+                "issue": {
+                    "type": "object",
+                    "required": ["severity", "rule", "message", "file_path"],
+                    "properties": {
+                        "severity": { "$ref": "#/$defs/severity" },
+                        "rule": { "type": "string" },
+                        "message": { "type": "string" },
+                        "file_path": { "type": "string" },
+                        "line": { "type": ["integer", "null"], "minimum": 1 },
+                        "column": { "type": ["integer", "null"], "minimum": 1 },
+                        "fingerprint": { "type": ["string", "null"] }
+                    }
+                },
+                "recommendation": {
+                    "type": "object",
+                    "required": ["category", "title", "description", "priority"],
+                    "properties": {
+                        "category": { "type": "string" },
+                        "title": { "type": "string" },
+                        "description": { "type": "string" },
+                        "priority": { "enum": ["High", "Medium", "Low"] },
+                        "file_path": { "type": ["string", "null"] }
+                    }
+                },
+                "omitted_issues": {
+                    "type": "object",
+                    "required": ["rule", "severity", "count"],
+                    "properties": {
+                        "rule": { "type": "string" },
+                        "severity": { "$ref": "#/$defs/severity" },
+                        "count": { "type": "integer", "minimum": 0 }
+                    }
+                },
+                "metrics": metrics_properties()
+            }
+        }),
+        SchemaKind::Graph => json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$id": "https://github.com/your-org/ng-analyzer/schemas/v1/graph.json",
+            "title": "ng-analyzer file dependency graph (graph --kind files)",
+            "type": "object",
+            "required": ["schema_version", "graph", "analysis", "summary"],
+            "properties": {
+                "schema_version": { "const": 1 },
+                "graph": {
+                    "type": "object",
+                    "required": ["files", "dependencies"],
+                    "properties": {
+                        "files": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "required": ["id", "file_path", "relative_path", "file_type"],
+                                "properties": {
+                                    "id": { "type": "string" },
+                                    "file_path": { "type": "string" },
+                                    "relative_path": { "type": "string" },
+                                    "file_type": { "enum": ["TypeScript", "JavaScript", "Declaration", "Module", "External"] },
+                                    "exports": { "type": "array", "items": { "type": "string" } },
+                                    "imports": { "type": "array", "items": { "type": "string" } }
+                                }
+                            }
+                        },
+                        "dependencies": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "required": ["from_file", "to_file", "import_type"],
+                                "properties": {
+                                    "from_file": { "type": "string" },
+                                    "to_file": { "type": "string" },
+                                    "import_type": { "enum": ["Default", "Named", "Namespace", "Dynamic"] },
+                                    "imported_symbols": { "type": "array", "items": { "type": "string" } },
+                                    "line_number": { "type": ["integer", "null"] }
+                                }
+                            }
+                        }
+                    }
+                },
+                "analysis": {
+                    "type": "object",
+                    "properties": {
+                        "circular_dependencies": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "required": ["cycle", "severity"],
+                                "properties": {
+                                    "cycle": { "type": "array", "items": { "type": "string" } },
+                                    "severity": { "enum": ["Critical", "Warning", "Info"] }
+                                }
+                            }
+                        },
+                        "orphaned_files": { "type": "array", "items": { "type": "string" } },
+                        "unused_exports": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "required": ["file_path", "symbol"],
+                                "properties": {
+                                    "file_path": { "type": "string" },
+                                    "symbol": { "type": "string" }
+                                }
+                            }
+                        }
+                    }
+                },
+                "summary": {
+                    "type": "object",
+                    "required": ["total_files", "total_dependencies", "circular_dependencies", "orphaned_files", "unused_exports"],
+                    "properties": {
+                        "total_files": { "type": "integer" },
+                        "total_dependencies": { "type": "integer" },
+                        "circular_dependencies": { "type": "integer" },
+                        "orphaned_files": { "type": "integer" },
+                        "unused_exports": { "type": "integer" }
+                    }
+                }
+            }
+        }),
+        SchemaKind::Metrics => {
+            let mut schema = json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "$id": "https://github.com/your-org/ng-analyzer/schemas/v1/metrics.json",
+                "title": "ng-analyzer project metrics",
+            });
+            if let (Value::Object(schema), Value::Object(metrics)) = (&mut schema, metrics_properties()) {
+                schema.extend(metrics);
+            }
+            schema
+        }
+    }
+}
+
+fn metrics_properties() -> Value {
+    json!({
+        "type": "object",
+        "required": ["total_components", "total_services", "total_modules", "average_complexity", "lines_of_code"],
+        "properties": {
+            "total_components": { "type": "integer", "minimum": 0 },
+            "total_services": { "type": "integer", "minimum": 0 },
+            "total_modules": { "type": "integer", "minimum": 0 },
+            "average_complexity": { "type": "number" },
+            "lines_of_code": { "type": "integer", "minimum": 0 },
+            "test_coverage": { "type": ["number", "null"] },
+            "duplicated_lines": { "type": ["integer", "null"] },
+            "duplication_percentage": { "type": ["number", "null"] },
+            "type_safety": {
+                "type": ["object", "null"],
+                "required": ["strict", "score", "totals", "files"],
+                "properties": {
+                    "strict": { "type": ["boolean", "null"] },
+                    "score": { "type": "number" },
+                    "totals": {
+                        "type": "object",
+                        "properties": {
+                            "any_annotations": { "type": "integer" },
+                            "any_casts": { "type": "integer" },
+                            "non_null_assertions": { "type": "integer" },
+                            "ts_ignores": { "type": "integer" }
+                        }
+                    },
+                    "files": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["file_path"],
+                            "properties": {
+                                "file_path": { "type": "string" },
+                                "any_annotations": { "type": "integer" },
+                                "any_casts": { "type": "integer" },
+                                "non_null_assertions": { "type": "integer" },
+                                "ts_ignores": { "type": "integer" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::{ComponentBuilder, NgProjectBuilder};
+    use crate::ast::{Issue, ProjectMetrics, Severity};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_pin_v1_drops_internal_fields() {
+        let results = vec![AnalysisResult {
+            project: Arc::new(NgProjectBuilder::new("/work/app").component(ComponentBuilder::new("UserListComponent")).build()),
+            issues: vec![Issue {
+                severity: Severity::Warning,
+                rule: "component-complexity".to_string(),
+                message: "too complex".to_string(),
+                file_path: "app.component.ts".to_string(),
+                line: Some(3),
+                column: None,
+                fingerprint: None,
+            }],
+            metrics: ProjectMetrics { total_components: 2, duplicated_lines: Some(10), ..Default::default() },
+            recommendations: vec![],
+            omitted_issues: vec![],
+        }];
+
+        let latest = analysis_document(&results, None, &[], false, None);
+        assert_eq!(latest["schema_version"], 1);
+        assert_eq!(latest["project"]["name"], "app");
+        assert_eq!(latest["project"]["components"], 1);
+        assert!(latest["results"][0].get("project").is_none());
+
+        let full = analysis_document(&results, None, &[], true, None);
+        assert_eq!(full["results"][0]["project"]["components"][0]["name"], "UserListComponent");
+
+        let pinned = analysis_document(&results, None, &[], true, Some(SchemaVersion::parse("v1", Lang::En).unwrap()));
+        let result = &pinned["results"][0];
+        assert_eq!(pinned["schema_version"], 1);
+        assert_eq!(pinned["project"]["root_path"], "/work/app");
+        assert!(result.get("project").is_none());
+        assert_eq!(result["issues"][0]["rule"], "component-complexity");
+        assert_eq!(result["issues"][0]["line"], 3);
+        assert_eq!(result["metrics"]["total_components"], 2);
+        assert_eq!(result["metrics"]["duplicated_lines"], 10);
+        assert!(result["metrics"].get("adoption").is_none());
+
+        assert!(SchemaVersion::parse("v2", Lang::En).is_err());
+        assert!(SchemaKind::parse("bundles", Lang::En).is_err());
+        assert_eq!(SchemaVersion::V1.document(SchemaKind::Metrics)["properties"]["lines_of_code"]["type"], "integer");
+    }
+}
@@ -0,0 +1,73 @@
+use crate::ast::{AnalysisResult, Severity};
+use std::time::Duration;
+
+// どのコマンドでも最後に出す 1 行のサマリー。ログから拾えるように形式を変えない
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunSummary {
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+    pub files: usize,
+    // .ng-analyzer.json の budgets のうち超えたものの数。1 つでもあれば終了コードを 1 にする
+    pub failed_budgets: usize,
+}
+
+impl RunSummary {
+    pub fn files(files: usize) -> Self {
+        Self { files, ..Default::default() }
+    }
+
+    // --max-issues-per-rule で削る前の結果から数える
+    pub fn from_results(results: &[AnalysisResult], files: usize) -> Self {
+        let count = |severity: Severity| results.iter().flat_map(|result| &result.issues).filter(|issue| issue.severity == severity).count();
+        Self {
+            errors: count(Severity::Error),
+            warnings: count(Severity::Warning),
+            infos: count(Severity::Info),
+            files,
+            failed_budgets: 0,
+        }
+    }
+
+    // ✖ 3 errors, 12 warnings, 40 infos in 214 files (2.1s)
+    pub fn format_line(&self, elapsed: Duration, color: bool) -> String {
+        let plural = |count: usize, word: &str| format!("{} {}{}", count, word, if count == 1 { "" } else { "s" });
+        let (symbol, ansi) = if self.errors > 0 {
+            ("✖", "31")
+        } else if self.warnings > 0 {
+            ("⚠", "33")
+        } else {
+            ("✔", "32")
+        };
+        let line = format!(
+            "{} {}, {}, {} in {} ({:.1}s)",
+            symbol,
+            plural(self.errors, "error"),
+            plural(self.warnings, "warning"),
+            plural(self.infos, "info"),
+            plural(self.files, "file"),
+            elapsed.as_secs_f64()
+        );
+        if color {
+            format!("\x1b[{}m{}\x1b[0m", ansi, line)
+        } else {
+            line
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_line() {
+        let summary = RunSummary { errors: 3, warnings: 12, infos: 40, files: 214, failed_budgets: 0 };
+        assert_eq!(summary.format_line(Duration::from_millis(2100), false), "✖ 3 errors, 12 warnings, 40 infos in 214 files (2.1s)");
+        assert_eq!(summary.format_line(Duration::from_millis(2100), true), "\x1b[31m✖ 3 errors, 12 warnings, 40 infos in 214 files (2.1s)\x1b[0m");
+
+        let summary = RunSummary { warnings: 1, infos: 1, ..RunSummary::files(1) };
+        assert_eq!(summary.format_line(Duration::ZERO, false), "⚠ 0 errors, 1 warning, 1 info in 1 file (0.0s)");
+        assert!(RunSummary::default().format_line(Duration::ZERO, false).starts_with("✔ "));
+    }
+}
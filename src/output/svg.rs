@@ -0,0 +1,158 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const NODE_HEIGHT: usize = 30;
+const LAYER_GAP: usize = 60;
+const NODE_GAP: usize = 20;
+const MARGIN: usize = 20;
+
+pub struct SvgNode {
+    pub id: String,
+    pub label: String,
+    pub fill: &'static str,
+}
+
+pub struct SvgEdge {
+    pub from: String,
+    pub to: String,
+    pub highlight: bool,
+}
+
+// Graphviz の dot があればそのレイアウトを使う
+pub fn render_with_dot(dot_source: &str) -> Option<String> {
+    let mut child = Command::new("dot")
+        .arg("-Tsvg")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    child.stdin.take()?.write_all(dot_source.as_bytes()).ok()?;
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+// dot がない環境向けの簡易レイアウト。import 元から順に段を下げて並べ、循環は先に見つかった側から切る
+pub fn render_layered(nodes: &[SvgNode], edges: &[SvgEdge]) -> String {
+    let layers = assign_layers(nodes, edges);
+    let mut rows: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (index, layer) in layers.iter().enumerate() {
+        rows.entry(*layer).or_default().push(index);
+    }
+
+    // ノードごとの左上の座標と幅
+    let mut boxes: Vec<(usize, usize, usize)> = vec![(0, 0, 0); nodes.len()];
+    let mut width = 0;
+    for (layer, members) in &rows {
+        let y = MARGIN + layer * (NODE_HEIGHT + LAYER_GAP);
+        let mut x = MARGIN;
+        for index in members {
+            let node_width = nodes[*index].label.chars().count() * 7 + 20;
+            boxes[*index] = (x, y, node_width);
+            x += node_width + NODE_GAP;
+        }
+        width = width.max(x - NODE_GAP + MARGIN);
+    }
+    let height = MARGIN * 2 + rows.len().max(1) * (NODE_HEIGHT + LAYER_GAP) - LAYER_GAP;
+
+    let positions: HashMap<&str, usize> = nodes.iter().enumerate().map(|(index, node)| (node.id.as_str(), index)).collect();
+    let mut output = String::new();
+    output.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" viewBox=\"0 0 {} {}\" font-family=\"sans-serif\" font-size=\"12\">\n",
+        width, height, width, height
+    ));
+    output.push_str("  <defs>\n");
+    output.push_str("    <marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto-start-reverse\"><path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"#555\"/></marker>\n");
+    output.push_str("    <marker id=\"arrow-highlight\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto-start-reverse\"><path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"red\"/></marker>\n");
+    output.push_str("  </defs>\n");
+
+    for edge in edges {
+        let (Some(from), Some(to)) = (positions.get(edge.from.as_str()), positions.get(edge.to.as_str())) else { continue };
+        let (from_x, from_y, from_width) = boxes[*from];
+        let (to_x, to_y, to_width) = boxes[*to];
+        // 下の段へは箱の下辺から上辺へ、上の段や同じ段へは上辺から下辺へ引く
+        let (y1, y2) = if to_y > from_y { (from_y + NODE_HEIGHT, to_y) } else { (from_y, to_y + NODE_HEIGHT) };
+        let (stroke, marker, stroke_width) = if edge.highlight { ("red", "arrow-highlight", 2) } else { ("#555", "arrow", 1) };
+        output.push_str(&format!(
+            "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" stroke=\"{}\" stroke-width=\"{}\" marker-end=\"url(#{})\"/>\n",
+            from_x + from_width / 2, y1, to_x + to_width / 2, y2, stroke, stroke_width, marker
+        ));
+    }
+
+    for (node, (x, y, node_width)) in nodes.iter().zip(&boxes) {
+        output.push_str(&format!(
+            "  <g><title>{}</title><rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#333\"/><text x=\"{}\" y=\"{}\" text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text></g>\n",
+            escape_xml(&node.id), x, y, node_width, NODE_HEIGHT, node.fill, x + node_width / 2, y + NODE_HEIGHT / 2, escape_xml(&node.label)
+        ));
+    }
+
+    output.push_str("</svg>\n");
+    output
+}
+
+// 入ってくる辺がすべて配置済みになったノードから順に、import 元より 1 段下に置く
+fn assign_layers(nodes: &[SvgNode], edges: &[SvgEdge]) -> Vec<usize> {
+    let index: HashMap<&str, usize> = nodes.iter().enumerate().map(|(i, node)| (node.id.as_str(), i)).collect();
+    let mut incoming: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    for edge in edges {
+        if let (Some(from), Some(to)) = (index.get(edge.from.as_str()), index.get(edge.to.as_str())) {
+            if from != to {
+                incoming[*to].push(*from);
+            }
+        }
+    }
+
+    let mut layers: Vec<Option<usize>> = vec![None; nodes.len()];
+    while layers.iter().any(Option::is_none) {
+        let ready: Vec<usize> = (0..nodes.len())
+            .filter(|i| layers[*i].is_none() && incoming[*i].iter().all(|from| layers[*from].is_some()))
+            .collect();
+        // 残りが循環だけになったら、配置済みのノードから import されているものを優先して、未配置の辺を無視して置く
+        let ready = if ready.is_empty() {
+            let unplaced = || (0..nodes.len()).filter(|i| layers[*i].is_none());
+            let next = unplaced().find(|i| incoming[*i].iter().any(|from| layers[*from].is_some()));
+            vec![next.or_else(|| unplaced().next()).unwrap_or(0)]
+        } else {
+            ready
+        };
+        for i in ready {
+            let layer = incoming[i].iter().filter_map(|from| layers[*from]).map(|layer| layer + 1).max().unwrap_or(0);
+            layers[i] = Some(layer);
+        }
+    }
+    layers.into_iter().map(|layer| layer.unwrap_or(0)).collect()
+}
+
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_layered() {
+        let node = |id: &str| SvgNode { id: id.to_string(), label: format!("{}.ts", id), fill: "lightblue" };
+        let edge = |from: &str, to: &str, highlight: bool| SvgEdge { from: from.to_string(), to: to.to_string(), highlight };
+        let nodes = vec![node("main"), node("a"), node("b"), node("c")];
+        let edges = vec![edge("main", "a", false), edge("a", "b", true), edge("b", "a", true), edge("main", "c", false)];
+
+        assert_eq!(assign_layers(&nodes, &edges), vec![0, 1, 2, 1]);
+
+        let svg = render_layered(&nodes, &edges);
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches("<rect").count(), 4);
+        assert_eq!(svg.matches("<line").count(), 4);
+        assert_eq!(svg.matches("stroke=\"red\"").count(), 2);
+        assert_eq!(escape_xml("<a & 'b'>"), "&lt;a &amp; &apos;b&apos;&gt;");
+    }
+}
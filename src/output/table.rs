@@ -1,13 +1,19 @@
-use super::OutputFormatter;
-use crate::ast::AnalysisResult;
+use super::{adoption_rows, budget_limit, budget_value, largest_components, largest_files, truncate_end, truncate_start, OutputFormatter};
+use super::grouping::{group_issues, IssueGrouping};
+use super::i18n::Lang;
+use crate::ast::{AnalysisResult, BudgetResult, HealthScore, Issue, Severity};
 use anyhow::Result;
 use std::fs;
-use std::path::PathBuf;
+use std::path::Path;
 use tabled::{Table, Tabled};
 
 pub struct TableFormatter {
     show_recommendations: bool,
     show_metrics: bool,
+    health: Option<HealthScore>,
+    budgets: Vec<BudgetResult>,
+    group_by: Option<IssueGrouping>,
+    lang: Lang,
 }
 
 impl TableFormatter {
@@ -15,6 +21,10 @@ impl TableFormatter {
         Self {
             show_recommendations: true,
             show_metrics: true,
+            health: None,
+            budgets: Vec::new(),
+            group_by: None,
+            lang: Lang::En,
         }
     }
 
@@ -23,8 +33,32 @@ impl TableFormatter {
         Self {
             show_recommendations: false,
             show_metrics: false,
+            health: None,
+            budgets: Vec::new(),
+            group_by: None,
+            lang: Lang::En,
         }
     }
+
+    pub fn with_health(mut self, health: Option<HealthScore>) -> Self {
+        self.health = health;
+        self
+    }
+
+    pub fn with_budgets(mut self, budgets: Vec<BudgetResult>) -> Self {
+        self.budgets = budgets;
+        self
+    }
+
+    pub fn with_group_by(mut self, group_by: Option<IssueGrouping>) -> Self {
+        self.group_by = group_by;
+        self
+    }
+
+    pub fn with_lang(mut self, lang: Lang) -> Self {
+        self.lang = lang;
+        self
+    }
 }
 
 #[derive(Tabled)]
@@ -34,6 +68,62 @@ struct IssueRow {
     message: String,
     file: String,
     line: String,
+    // ベースラインや課題トラッカーと突き合わせるための値
+    fingerprint: String,
+}
+
+#[derive(Tabled)]
+struct IssueGroupRow {
+    group: String,
+    issues: usize,
+    errors: usize,
+    warnings: usize,
+    infos: usize,
+    example: String,
+}
+
+#[derive(Tabled)]
+struct TestabilityRow {
+    feature: String,
+    artifacts: u32,
+    tested: u32,
+    tests: u32,
+    disabled: u32,
+    score: String,
+}
+
+#[derive(Tabled)]
+struct CdHotspotRow {
+    component: String,
+    bindings: u32,
+    subtree: u32,
+    depth: u32,
+    cost: u32,
+    file: String,
+}
+
+#[derive(Tabled)]
+struct ZonelessRow {
+    feature: String,
+    components: u32,
+    ready: u32,
+    blocked: u32,
+    score: String,
+}
+
+#[derive(Tabled)]
+struct TypeEscapeRow {
+    file: String,
+    any: u32,
+    casts: u32,
+    non_null: u32,
+    ts_ignore: u32,
+}
+
+#[derive(Tabled)]
+struct RenderChainRow {
+    depth: usize,
+    chain: String,
 }
 
 #[derive(Tabled)]
@@ -42,6 +132,39 @@ struct MetricRow {
     value: String,
 }
 
+#[derive(Tabled)]
+struct FileSizeRow {
+    file: String,
+    lines: u32,
+    physical: u32,
+}
+
+#[derive(Tabled)]
+struct ComponentSizeRow {
+    component: String,
+    lines: u32,
+    complexity: u32,
+    template: String,
+    file: String,
+}
+
+#[derive(Tabled)]
+struct HealthRow {
+    category: String,
+    score: String,
+    weight: String,
+    detail: String,
+}
+
+#[derive(Tabled)]
+struct BudgetRow {
+    budget: String,
+    limit: String,
+    actual: String,
+    status: String,
+    worst: String,
+}
+
 #[derive(Tabled)]
 struct RecommendationRow {
     category: String,
@@ -50,45 +173,85 @@ struct RecommendationRow {
     description: String,
 }
 
+fn file_name(issue: &Issue) -> &str {
+    std::path::Path::new(&issue.file_path).file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or(&issue.file_path)
+}
+
+// グループの先頭の issue を 1 行で示す
+fn example(issue: &Issue) -> String {
+    let location = format!("{}{}", file_name(issue), issue.line.map(|line| format!(":{}", line)).unwrap_or_default());
+    truncate_end(&format!("{} {}", location, issue.message), 80)
+}
+
 impl OutputFormatter for TableFormatter {
     fn format(&self, results: &[AnalysisResult]) -> Result<String> {
+        let lang = self.lang;
         let mut output = String::new();
 
+        // 一番目立つように先頭に出す
+        if let Some(health) = &self.health {
+            output.push_str(&format!("{}: {:.0}/100 ({} {})\n", lang.tr("report.health_score"), health.score, lang.tr("report.grade"), health.grade));
+            let health_rows: Vec<HealthRow> = health.categories.iter().map(|category| HealthRow {
+                category: category.category.clone(),
+                score: format!("{:.0}", category.score),
+                weight: format!("{}", category.weight),
+                detail: category.detail.clone(),
+            }).collect();
+            output.push_str(&Table::new(health_rows).to_string());
+            output.push_str("\n\n");
+        }
+
         for (i, result) in results.iter().enumerate() {
             if i > 0 {
                 output.push_str("\n\n");
             }
 
-            output.push_str(&format!("=== Analysis Result {} ===\n", i + 1));
-            output.push_str(&format!("Project: {}\n\n", result.project.root_path.display()));
+            output.push_str(&format!("=== {} {} ===\n", lang.tr("report.result"), i + 1));
+            output.push_str(&format!("{}: {}\n\n", lang.tr("report.project"), result.project.root_path.display()));
 
-            if !result.issues.is_empty() {
-                output.push_str("Issues:\n");
+            if let (Some(group_by), false) = (self.group_by, result.issues.is_empty()) {
+                output.push_str(&format!("{}:\n", lang.trf("report.issues_grouped", &[&group_by.name()])));
+                let group_rows: Vec<IssueGroupRow> = group_issues(&result.issues, group_by, &result.project.root_path).into_iter().map(|group| {
+                    IssueGroupRow {
+                        group: truncate_start(&group.key, 60),
+                        issues: group.issues.len(),
+                        errors: group.count(Severity::Error),
+                        warnings: group.count(Severity::Warning),
+                        infos: group.count(Severity::Info),
+                        example: group.issues.first().map(|issue| example(issue)).unwrap_or_default(),
+                    }
+                }).collect();
+                output.push_str(&Table::new(group_rows).to_string());
+                output.push('\n');
+                for (rule, count) in result.omitted_per_rule() {
+                    output.push_str(&format!("{}\n", lang.trf("report.omitted", &[&count, &rule])));
+                }
+            } else if !result.issues.is_empty() {
+                output.push_str(&format!("{}:\n", lang.tr("report.issues")));
                 let issue_rows: Vec<IssueRow> = result.issues.iter().map(|issue| {
                     IssueRow {
                         severity: format!("{:?}", issue.severity),
                         rule: issue.rule.clone(),
-                        message: if issue.message.len() > 80 {
-                            format!("{}...", &issue.message[..77])
-                        } else {
-                            issue.message.clone()
-                        },
-                        file: std::path::Path::new(&issue.file_path).file_name()
-                            .and_then(|s| s.to_str())
-                            .unwrap_or(&issue.file_path)
-                            .to_string(),
+                        message: truncate_end(&issue.message, 80),
+                        file: file_name(issue).to_string(),
                         line: issue.line.map(|l| l.to_string()).unwrap_or_else(|| "-".to_string()),
+                        fingerprint: issue.fingerprint.clone().unwrap_or_else(|| "-".to_string()),
                     }
                 }).collect();
 
                 let issues_table = Table::new(issue_rows).to_string();
                 output.push_str(&issues_table);
                 output.push('\n');
+                for (rule, count) in result.omitted_per_rule() {
+                    output.push_str(&format!("{}\n", lang.trf("report.omitted", &[&count, &rule])));
+                }
             }
 
             if self.show_metrics {
-                output.push_str("\nMetrics:\n");
-                let metric_rows = vec![
+                output.push_str(&format!("\n{}:\n", lang.tr("report.metrics")));
+                let mut metric_rows = vec![
                     MetricRow {
                         metric: "Total Components".to_string(),
                         value: result.metrics.total_components.to_string(),
@@ -105,25 +268,188 @@ impl OutputFormatter for TableFormatter {
                         metric: "Average Complexity".to_string(),
                         value: format!("{:.2}", result.metrics.average_complexity),
                     },
+                    MetricRow {
+                        metric: "Lines of Code".to_string(),
+                        value: result.metrics.lines_of_code.to_string(),
+                    },
                 ];
+                if let Some(coverage) = result.metrics.test_coverage {
+                    metric_rows.push(MetricRow {
+                        metric: "Test Coverage".to_string(),
+                        value: format!("{:.1}%", coverage),
+                    });
+                }
+                if let Some(adoption) = &result.metrics.adoption {
+                    for (label, value) in adoption_rows(adoption) {
+                        metric_rows.push(MetricRow {
+                            metric: label.to_string(),
+                            value: value.map(|v| format!("{:.1}%", v)).unwrap_or_else(|| "-".to_string()),
+                        });
+                    }
+                }
+                if let Some(api_surface) = &result.metrics.api_surface {
+                    metric_rows.push(MetricRow {
+                        metric: "Average API Surface".to_string(),
+                        value: format!("{:.1}", api_surface.average),
+                    });
+                    metric_rows.push(MetricRow {
+                        metric: "API Surface Distribution".to_string(),
+                        value: api_surface.distribution.iter()
+                            .map(|bucket| format!("{}: {}", bucket.range, bucket.components))
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                    });
+                }
+                if let Some(render_depth) = &result.metrics.render_depth {
+                    metric_rows.push(MetricRow {
+                        metric: "Max Render Depth".to_string(),
+                        value: render_depth.max_depth.to_string(),
+                    });
+                }
+                if let Some(type_safety) = &result.metrics.type_safety {
+                    metric_rows.push(MetricRow {
+                        metric: "Type Safety Score".to_string(),
+                        value: format!("{:.1}%", type_safety.score),
+                    });
+                    metric_rows.push(MetricRow {
+                        metric: "Strict Mode".to_string(),
+                        value: match type_safety.strict {
+                            Some(true) => "enabled",
+                            Some(false) => "disabled",
+                            None => "-",
+                        }.to_string(),
+                    });
+                    metric_rows.push(MetricRow {
+                        metric: "Type Escapes".to_string(),
+                        value: format!(
+                            "any: {}, as any: {}, !: {}, @ts-ignore: {}",
+                            type_safety.totals.any_annotations, type_safety.totals.any_casts,
+                            type_safety.totals.non_null_assertions, type_safety.totals.ts_ignores
+                        ),
+                    });
+                }
+                if let Some(duplicated_lines) = result.metrics.duplicated_lines {
+                    metric_rows.push(MetricRow {
+                        metric: "Duplicated Lines".to_string(),
+                        value: duplicated_lines.to_string(),
+                    });
+                }
+                if let Some(percentage) = result.metrics.duplication_percentage {
+                    metric_rows.push(MetricRow {
+                        metric: "Duplication".to_string(),
+                        value: format!("{:.1}%", percentage),
+                    });
+                }
 
                 let metrics_table = Table::new(metric_rows).to_string();
                 output.push_str(&metrics_table);
                 output.push('\n');
+
+                let files = largest_files(&result.project);
+                if !files.is_empty() {
+                    output.push_str(&format!("\n{}:\n", lang.tr("report.largest_files")));
+                    let file_rows: Vec<FileSizeRow> = files.iter().map(|file| FileSizeRow {
+                        file: file.file_path.clone(),
+                        lines: file.lines.logical,
+                        physical: file.lines.physical,
+                    }).collect();
+                    output.push_str(&Table::new(file_rows).to_string());
+                    output.push('\n');
+                }
+
+                let components = largest_components(&result.project);
+                if !components.is_empty() {
+                    output.push_str(&format!("\n{}:\n", lang.tr("report.largest_components")));
+                    let component_rows: Vec<ComponentSizeRow> = components.iter().map(|component| ComponentSizeRow {
+                        component: component.name.clone(),
+                        lines: component.lines.logical,
+                        complexity: component.complexity_score,
+                        template: component.template_complexity.as_ref().map_or("-".to_string(), |template| template.score.to_string()),
+                        file: std::path::Path::new(&component.file_path).file_name()
+                            .and_then(|s| s.to_str())
+                            .unwrap_or(&component.file_path)
+                            .to_string(),
+                    }).collect();
+                    output.push_str(&Table::new(component_rows).to_string());
+                    output.push('\n');
+                }
+
+                if let Some(render_depth) = result.metrics.render_depth.as_ref().filter(|depth| depth.max_depth > 0) {
+                    output.push_str(&format!("\n{}:\n", lang.tr("report.render_chains")));
+                    let chain_rows: Vec<RenderChainRow> = render_depth.deepest_chains.iter().map(|chain| RenderChainRow {
+                        depth: chain.len() - 1,
+                        chain: truncate_start(&chain.join(" > "), 100),
+                    }).collect();
+                    output.push_str(&Table::new(chain_rows).to_string());
+                    output.push('\n');
+                }
+
+                if !result.metrics.cd_hotspots.is_empty() {
+                    output.push_str(&format!("\n{}:\n", lang.tr("report.cd_hotspots")));
+                    let hotspot_rows: Vec<CdHotspotRow> = result.metrics.cd_hotspots.iter().map(|hotspot| CdHotspotRow {
+                        component: hotspot.component.clone(),
+                        bindings: hotspot.bindings,
+                        subtree: hotspot.subtree_bindings,
+                        depth: hotspot.depth,
+                        cost: hotspot.cost,
+                        file: std::path::Path::new(&hotspot.file_path).file_name()
+                            .and_then(|s| s.to_str())
+                            .unwrap_or(&hotspot.file_path)
+                            .to_string(),
+                    }).collect();
+                    output.push_str(&Table::new(hotspot_rows).to_string());
+                    output.push('\n');
+                }
+
+                if let Some(type_safety) = result.metrics.type_safety.as_ref().filter(|type_safety| !type_safety.files.is_empty()) {
+                    output.push_str(&format!("\n{}:\n", lang.tr("report.type_escapes")));
+                    let escape_rows: Vec<TypeEscapeRow> = type_safety.files.iter().take(10).map(|file| TypeEscapeRow {
+                        file: truncate_start(&file.file_path, 80),
+                        any: file.escapes.any_annotations,
+                        casts: file.escapes.any_casts,
+                        non_null: file.escapes.non_null_assertions,
+                        ts_ignore: file.escapes.ts_ignores,
+                    }).collect();
+                    output.push_str(&Table::new(escape_rows).to_string());
+                    output.push('\n');
+                }
+
+                if !result.metrics.feature_testability.is_empty() {
+                    output.push_str(&format!("\n{}:\n", lang.tr("report.testability")));
+                    let testability_rows: Vec<TestabilityRow> = result.metrics.feature_testability.iter().map(|feature| TestabilityRow {
+                        feature: feature.folder.clone(),
+                        artifacts: feature.artifacts,
+                        tested: feature.with_spec,
+                        tests: feature.tests,
+                        disabled: feature.disabled_tests,
+                        score: format!("{:.0}%", feature.score),
+                    }).collect();
+                    output.push_str(&Table::new(testability_rows).to_string());
+                    output.push('\n');
+                }
+
+                if !result.metrics.zoneless_readiness.is_empty() {
+                    output.push_str(&format!("\n{}:\n", lang.tr("report.zoneless")));
+                    let zoneless_rows: Vec<ZonelessRow> = result.metrics.zoneless_readiness.iter().map(|feature| ZonelessRow {
+                        feature: feature.folder.clone(),
+                        components: feature.components,
+                        ready: feature.ready,
+                        blocked: feature.blocked,
+                        score: format!("{:.0}%", feature.score),
+                    }).collect();
+                    output.push_str(&Table::new(zoneless_rows).to_string());
+                    output.push('\n');
+                }
             }
 
             if self.show_recommendations && !result.recommendations.is_empty() {
-                output.push_str("\nRecommendations:\n");
+                output.push_str(&format!("\n{}:\n", lang.tr("report.recommendations")));
                 let recommendation_rows: Vec<RecommendationRow> = result.recommendations.iter().map(|rec| {
                     RecommendationRow {
                         category: rec.category.clone(),
                         title: rec.title.clone(),
                         priority: format!("{:?}", rec.priority),
-                        description: if rec.description.len() > 100 {
-                            format!("{}...", &rec.description[..97])
-                        } else {
-                            rec.description.clone()
-                        },
+                        description: truncate_end(&rec.description, 100),
                     }
                 }).collect();
 
@@ -133,12 +459,42 @@ impl OutputFormatter for TableFormatter {
             }
         }
 
+        if !self.budgets.is_empty() {
+            output.push_str(&format!("\n\n{}:\n", lang.tr("report.budgets")));
+            let budget_rows: Vec<BudgetRow> = self.budgets.iter().map(|budget| BudgetRow {
+                budget: budget.budget.clone(),
+                limit: budget_limit(budget),
+                actual: budget_value(budget, budget.actual),
+                status: if budget.passed { "pass" } else { "FAIL" }.to_string(),
+                worst: budget.worst.as_deref().map(|worst| truncate_start(worst, 60)).unwrap_or_else(|| "-".to_string()),
+            }).collect();
+            output.push_str(&Table::new(budget_rows).to_string());
+            output.push('\n');
+        }
+
         Ok(output)
     }
 
-    fn write_to_file(&self, results: &[AnalysisResult], path: &PathBuf) -> Result<()> {
+    fn write_to_file(&self, results: &[AnalysisResult], path: &Path) -> Result<()> {
         let content = self.format(results)?;
         fs::write(path, content)?;
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::IssueBuilder;
+
+    #[test]
+    fn test_issue_table_shows_fingerprint() {
+        let issue = Issue { fingerprint: Some("3f6d0c9a1b2e4f57".to_string()), ..IssueBuilder::new("missing-onpush", "/app/user.component.ts").build() };
+        let results = vec![AnalysisResult { issues: vec![issue], ..Default::default() }];
+
+        let output = TableFormatter::new_minimal().format(&results).unwrap();
+
+        assert!(output.contains("fingerprint"));
+        assert!(output.contains("3f6d0c9a1b2e4f57"));
+    }
+}
@@ -0,0 +1,216 @@
+use crate::analyzers::dependency_graph::DependencyGraphAnalyzer;
+use crate::analyzers::health::issue_weight;
+use crate::ast::{AnalysisResult, NgProject, Severity};
+use crate::config::relative_issue_path;
+use crate::output::rule_stats::{rule_statistics, RuleStatistic};
+use crate::output::truncate_start;
+use crate::output::i18n::Lang;
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use tabled::{Table, Tabled};
+
+// 各ランキングに載せる件数
+pub const TOP_COUNT: usize = 10;
+
+// --summary で選べる出力
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SummaryMode {
+    Top,
+}
+
+impl SummaryMode {
+    pub fn parse(mode: &str, lang: Lang) -> Result<Self> {
+        match mode {
+            "top" => Ok(Self::Top),
+            _ => Err(anyhow::anyhow!("{}", lang.trf("error.unsupported", &[&"--summary", &mode, &"top"]))),
+        }
+    }
+}
+
+// 週次のレビューで見る、問題の多いファイル・複雑なコンポーネント・違反の多いルール・大きな循環
+#[derive(Debug, Clone, Serialize)]
+pub struct TopOffenders {
+    pub files: Vec<FileOffender>,
+    pub components: Vec<ComplexComponent>,
+    pub rules: Vec<RuleStatistic>,
+    // import グラフを組み立てなかった場合は空
+    pub cycles: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct FileOffender {
+    pub file: String,
+    // Error 10・Warning 3・Info 1 で重み付けした件数
+    pub weight: u32,
+    pub errors: usize,
+    pub warnings: usize,
+    pub infos: usize,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ComplexComponent {
+    pub name: String,
+    pub file: String,
+    pub complexity: u32,
+}
+
+#[derive(Tabled)]
+struct FileRow {
+    file: String,
+    weight: u32,
+    errors: usize,
+    warnings: usize,
+    infos: usize,
+}
+
+#[derive(Tabled)]
+struct ComponentRow {
+    component: String,
+    complexity: u32,
+    file: String,
+}
+
+#[derive(Tabled)]
+struct RuleRow {
+    rule: String,
+    issues: usize,
+    files: usize,
+    share: String,
+}
+
+impl TopOffenders {
+    // --max-issues-per-rule で削る前の結果から集計する
+    pub fn collect(project: &NgProject, results: &[AnalysisResult]) -> Result<Self> {
+        let root_path = &project.root_path;
+
+        let mut by_file: BTreeMap<String, FileOffender> = BTreeMap::new();
+        for issue in results.iter().flat_map(|result| &result.issues) {
+            let file = relative_issue_path(&issue.file_path, root_path);
+            let offender = by_file.entry(file.clone()).or_insert_with(|| FileOffender { file, weight: 0, errors: 0, warnings: 0, infos: 0 });
+            offender.weight += issue_weight(&issue.severity);
+            match issue.severity {
+                Severity::Error => offender.errors += 1,
+                Severity::Warning => offender.warnings += 1,
+                Severity::Info => offender.infos += 1,
+            }
+        }
+        let mut files: Vec<FileOffender> = by_file.into_values().collect();
+        files.sort_by(|a, b| b.weight.cmp(&a.weight).then_with(|| a.file.cmp(&b.file)));
+        files.truncate(TOP_COUNT);
+
+        let mut components: Vec<ComplexComponent> = project.components.iter()
+            .map(|component| ComplexComponent {
+                name: component.name.clone(),
+                file: relative_issue_path(&component.file_path, root_path),
+                complexity: component.complexity_score,
+            })
+            .collect();
+        components.sort_by(|a, b| b.complexity.cmp(&a.complexity).then_with(|| a.name.cmp(&b.name)));
+        components.truncate(TOP_COUNT);
+
+        let mut rules = rule_statistics(results);
+        rules.truncate(TOP_COUNT);
+
+        let mut cycles = Vec::new();
+        if let Some(graph) = &project.import_graph {
+            let paths: HashMap<&str, &str> = graph.files.iter().map(|file| (file.id.as_str(), file.relative_path.as_str())).collect();
+            cycles = DependencyGraphAnalyzer::new()
+                .with_analyses(true, false, false, false)
+                .analyze_dependencies(graph)?
+                .circular_dependencies
+                .into_iter()
+                .map(|circular| circular.cycle.iter().map(|id| paths.get(id.as_str()).unwrap_or(&id.as_str()).replace('\\', "/")).collect::<Vec<_>>())
+                .collect();
+            cycles.sort_by(|a: &Vec<String>, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+            cycles.truncate(TOP_COUNT);
+        }
+
+        Ok(Self { files, components, rules, cycles })
+    }
+
+    pub fn format_table(&self, lang: Lang) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!("{}\n", lang.trf("top.files", &[&TOP_COUNT])));
+        let file_rows: Vec<FileRow> = self.files.iter().map(|file| FileRow {
+            file: truncate_start(&file.file, 60),
+            weight: file.weight,
+            errors: file.errors,
+            warnings: file.warnings,
+            infos: file.infos,
+        }).collect();
+        output.push_str(&Table::new(file_rows).to_string());
+
+        output.push_str(&format!("\n\n{}\n", lang.trf("top.components", &[&TOP_COUNT])));
+        let component_rows: Vec<ComponentRow> = self.components.iter().map(|component| ComponentRow {
+            component: component.name.clone(),
+            complexity: component.complexity,
+            file: truncate_start(&component.file, 60),
+        }).collect();
+        output.push_str(&Table::new(component_rows).to_string());
+
+        output.push_str(&format!("\n\n{}\n", lang.trf("top.rules", &[&TOP_COUNT])));
+        let rule_rows: Vec<RuleRow> = self.rules.iter().map(|rule| RuleRow {
+            rule: rule.rule.clone(),
+            issues: rule.issues,
+            files: rule.files,
+            share: format!("{:.1}%", rule.share),
+        }).collect();
+        output.push_str(&Table::new(rule_rows).to_string());
+
+        output.push_str(&format!("\n\n{}\n", lang.trf("top.cycles", &[&TOP_COUNT])));
+        if self.cycles.is_empty() {
+            output.push_str(&format!("{}\n", lang.tr("top.none")));
+        }
+        for cycle in &self.cycles {
+            output.push_str(&format!("{}\n", lang.trf("top.cycle", &[&cycle.len(), &cycle.join(" -> ")])));
+        }
+
+        output
+    }
+
+    pub fn format_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::builder::{ComponentBuilder, IssueBuilder, NgProjectBuilder};
+    use crate::ast::ProjectMetrics;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_top_offenders() {
+        let issue = |severity: Severity, rule: &str, file_path: &str| IssueBuilder::new(rule, file_path).severity(severity).build();
+        let project = NgProjectBuilder::new("/app")
+            .component(ComponentBuilder::new("DashboardComponent").complexity(18))
+            .component(ComponentBuilder::new("UserCardComponent").complexity(3))
+            .component(ComponentBuilder::new("ReportComponent").complexity(18))
+            .build();
+        let results = vec![AnalysisResult {
+            project: Arc::new(project.clone()),
+            issues: vec![
+                issue(Severity::Info, "missing-onpush", "/app/a.component.ts"),
+                issue(Severity::Info, "missing-onpush", "/app/a.component.ts"),
+                issue(Severity::Info, "missing-onpush", "/app/b.component.ts"),
+                issue(Severity::Warning, "component-complexity", "/app/b.component.ts"),
+                issue(Severity::Error, "environment-secret", "/app/environment.ts"),
+            ],
+            metrics: ProjectMetrics::default(),
+            recommendations: vec![],
+            omitted_issues: vec![],
+        }];
+
+        let top = TopOffenders::collect(&project, &results).unwrap();
+        let files: Vec<(&str, u32)> = top.files.iter().map(|file| (file.file.as_str(), file.weight)).collect();
+        assert_eq!(files, vec![("environment.ts", 10), ("b.component.ts", 4), ("a.component.ts", 2)]);
+        let components: Vec<&str> = top.components.iter().map(|component| component.name.as_str()).collect();
+        assert_eq!(components, vec!["DashboardComponent", "ReportComponent", "UserCardComponent"]);
+        assert_eq!(top.rules[0].rule, "missing-onpush");
+        assert!(top.cycles.is_empty());
+        assert!(top.format_table(Lang::En).contains("(none)"));
+    }
+}
@@ -0,0 +1,47 @@
+// 不正なバイト列を U+FFFD に置き換えて読んだときの形式。表示するときに --lang の言語にする
+pub const LOSSY_UTF8: &str = "UTF-8 (lossy)";
+
+// ソースファイルのバイト列を文字列にする。BOM を取り除き、UTF-16 は BOM から判定して変換する
+// UTF-8 として不正なバイト列は U+FFFD に置き換え、どの形式として読んだかを返す
+pub fn decode(bytes: Vec<u8>) -> (String, Option<String>) {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return match std::str::from_utf8(rest) {
+            Ok(content) => (content.to_string(), None),
+            Err(_) => (String::from_utf8_lossy(rest).into_owned(), Some(LOSSY_UTF8.to_string())),
+        };
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        let units: Vec<u16> = rest.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect();
+        return (String::from_utf16_lossy(&units), Some("UTF-16LE".to_string()));
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        let units: Vec<u16> = rest.chunks_exact(2).map(|pair| u16::from_be_bytes([pair[0], pair[1]])).collect();
+        return (String::from_utf16_lossy(&units), Some("UTF-16BE".to_string()));
+    }
+
+    match String::from_utf8(bytes) {
+        Ok(content) => (content, None),
+        Err(error) => (String::from_utf8_lossy(error.as_bytes()).into_owned(), Some(LOSSY_UTF8.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode(b"export class A {}".to_vec()), ("export class A {}".to_string(), None));
+        assert_eq!(decode(b"\xEF\xBB\xBFexport class A {}".to_vec()), ("export class A {}".to_string(), None));
+
+        let utf16: Vec<u8> = [0xFF, 0xFE].into_iter()
+            .chain("const a = 'あ';".encode_utf16().flat_map(|unit| unit.to_le_bytes()))
+            .collect();
+        assert_eq!(decode(utf16), ("const a = 'あ';".to_string(), Some("UTF-16LE".to_string())));
+
+        // Shift_JIS の「あ」
+        let (content, encoding) = decode(b"const a = '\x82\xa0';".to_vec());
+        assert_eq!(content, "const a = '\u{FFFD}\u{FFFD}';");
+        assert!(encoding.is_some());
+    }
+}
@@ -0,0 +1,289 @@
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const WORKSPACE_FILES: [&str; 2] = ["angular.json", "workspace.json"];
+const TSCONFIG_FILES: [&str; 2] = ["tsconfig.base.json", "tsconfig.json"];
+// ビルダーの options のうち、バンドルの起点になるファイルを指すもの
+const ENTRY_OPTIONS: [&str; 4] = ["main", "browser", "server", "polyfills"];
+// ワークスペースの設定がなくても起点として扱う Angular CLI の慣例的なファイル名
+const CONVENTIONAL_ENTRIES: [&str; 5] = ["main.ts", "main.server.ts", "server.ts", "polyfills.ts", "test.ts"];
+// ビルダーの options のうち、グローバルに読み込むスタイルや HTML、そのままコピーする assets を指すもの
+const RESOURCE_OPTIONS: [&str; 3] = ["styles", "index", "assets"];
+// ワークスペースの設定がなくてもグローバルに読み込まれる Angular CLI の慣例的なファイル名
+const CONVENTIONAL_RESOURCES: [&str; 5] = ["index.html", "styles.css", "styles.scss", "styles.sass", "styles.less"];
+
+// どこからも import されずにツールが直接読み込むファイル（テスト、各種設定、型定義）
+pub const ENTRY_FILE_GLOBS: [&str; 10] = [
+    "**/*.spec.ts",
+    "**/*.test.ts",
+    "**/*.cy.ts",
+    "**/*.e2e-spec.ts",
+    "**/test-setup.ts",
+    "**/setup-jest.ts",
+    "**/*.config.ts",
+    "**/*.config.js",
+    "**/*.d.ts",
+    "**/*.stories.ts",
+];
+
+// angular.json / workspace.json / project.json のビルダー設定と tsconfig の paths から起点のファイルを集める
+// 返すのは実在するファイルの正規化済みの絶対パス
+pub fn workspace_entry_points(root_path: &Path, project_files: &[PathBuf]) -> Vec<PathBuf> {
+    let Ok(root) = fs::canonicalize(root_path) else {
+        return Vec::new();
+    };
+    let mut entries = Vec::new();
+
+    for (workspace_dir, project) in workspace_projects(&root, project_files) {
+        collect_targets(&project, &workspace_dir, &mut entries);
+    }
+
+    for dir in root.ancestors() {
+        for name in TSCONFIG_FILES {
+            if let Some(value) = read_json(&dir.join(name)) {
+                collect_path_aliases(&value, dir, &mut entries);
+            }
+        }
+    }
+
+    canonical_paths(entries)
+}
+
+// ビルダー設定の styles・index と assets が指すファイルとディレクトリ。正規化済みの絶対パス
+pub fn workspace_resources(root_path: &Path, project_files: &[PathBuf]) -> Vec<PathBuf> {
+    let Ok(root) = fs::canonicalize(root_path) else {
+        return Vec::new();
+    };
+    let mut resources = Vec::new();
+
+    for (workspace_dir, project) in workspace_projects(&root, project_files) {
+        for options in target_options(&project) {
+            let values = RESOURCE_OPTIONS.iter()
+                .filter_map(|key| options.get(key))
+                .flat_map(|value| match value {
+                    Value::Array(values) => values.iter().collect(),
+                    value => vec![value],
+                });
+            // "src/styles.scss" と { "input": "src/theme.scss", "bundleName": "theme" } の両方の書き方がある
+            for value in values {
+                if let Some(path) = value.as_str().or_else(|| value.get("input").and_then(Value::as_str)) {
+                    resources.push(workspace_dir.join(path));
+                }
+            }
+        }
+    }
+
+    canonical_paths(resources)
+}
+
+// 解析対象より上の階層にあるワークスペースの設定も読む（apps/shop だけを解析する場合など）。
+// Nx の project.json のパスはワークスペースのルートからの相対パス
+fn workspace_projects(root: &Path, project_files: &[PathBuf]) -> Vec<(PathBuf, Value)> {
+    let mut projects = Vec::new();
+
+    if let Some((workspace_dir, workspace)) = root.ancestors()
+        .find_map(|dir| WORKSPACE_FILES.iter().find_map(|name| read_json(&dir.join(name)).map(|value| (dir.to_path_buf(), value))))
+    {
+        if let Some(workspace_projects) = workspace.get("projects").and_then(Value::as_object) {
+            for project in workspace_projects.values() {
+                match project {
+                    // workspace.json v2 はプロジェクトのディレクトリだけを持ち、設定は project.json にある
+                    Value::String(dir) => {
+                        if let Some(value) = read_json(&workspace_dir.join(dir).join("project.json")) {
+                            projects.push((workspace_dir.clone(), value));
+                        }
+                    }
+                    value => projects.push((workspace_dir.clone(), value.clone())),
+                }
+            }
+        }
+    }
+
+    for project_file in project_files {
+        let Some(value) = read_json(project_file) else { continue };
+        let workspace_dir = project_file.ancestors()
+            .find(|dir| dir.join("nx.json").is_file() || WORKSPACE_FILES.iter().any(|name| dir.join(name).is_file()))
+            .unwrap_or(root);
+        projects.push((workspace_dir.to_path_buf(), value));
+    }
+
+    projects
+}
+
+fn canonical_paths(paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = paths.into_iter().filter_map(|path| fs::canonicalize(path).ok()).collect();
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+pub fn is_conventional_entry(file_name: &str) -> bool {
+    CONVENTIONAL_ENTRIES.contains(&file_name)
+}
+
+pub fn is_conventional_resource(file_name: &str) -> bool {
+    CONVENTIONAL_RESOURCES.contains(&file_name)
+}
+
+fn collect_targets(project: &Value, workspace_dir: &Path, entries: &mut Vec<PathBuf>) {
+    for options in target_options(project) {
+        for key in ENTRY_OPTIONS {
+            match options.get(key) {
+                Some(Value::String(path)) => entries.push(workspace_dir.join(path)),
+                // polyfills は ["zone.js", "src/polyfills.ts"] のようにパッケージ名とファイルが混ざる
+                Some(Value::Array(paths)) => entries.extend(paths.iter()
+                    .filter_map(Value::as_str)
+                    .filter(|path| path.ends_with(".ts") || path.ends_with(".js"))
+                    .map(|path| workspace_dir.join(path))),
+                _ => {}
+            }
+        }
+        if let Some(entry) = options.pointer("/ssr/entry").and_then(Value::as_str) {
+            entries.push(workspace_dir.join(entry));
+        }
+        // environment.prod.ts などビルド時に差し替えられるファイル
+        if let Some(replacements) = options.get("fileReplacements").and_then(Value::as_array) {
+            entries.extend(replacements.iter()
+                .filter_map(|replacement| replacement.get("with").and_then(Value::as_str))
+                .map(|path| workspace_dir.join(path)));
+        }
+    }
+}
+
+// すべてのターゲットの options と configurations
+fn target_options(project: &Value) -> Vec<&Value> {
+    let Some(targets) = project.get("architect").or_else(|| project.get("targets")).and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    targets.values()
+        .flat_map(|target| {
+            let configurations = target.get("configurations").and_then(Value::as_object).into_iter().flat_map(|c| c.values());
+            target.get("options").into_iter().chain(configurations)
+        })
+        .collect()
+}
+
+// "@app/shared": ["libs/shared/src/index.ts"] のようにファイルを指すエイリアス。ワイルドカードは除く
+fn collect_path_aliases(tsconfig: &Value, tsconfig_dir: &Path, entries: &mut Vec<PathBuf>) {
+    let Some(options) = tsconfig.get("compilerOptions") else { return };
+    let base_dir = tsconfig_dir.join(options.get("baseUrl").and_then(Value::as_str).unwrap_or("."));
+    let Some(paths) = options.get("paths").and_then(Value::as_object) else { return };
+    entries.extend(paths.values()
+        .filter_map(Value::as_array)
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter(|target| !target.contains('*'))
+        .map(|target| base_dir.join(target)));
+}
+
+pub(super) fn read_json(path: &Path) -> Option<Value> {
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&strip_json_comments(&content)).ok()
+}
+
+// tsconfig.json や angular.json はコメントと末尾のカンマを許すので、serde_json で読める形にする
+fn strip_json_comments(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut output = String::with_capacity(content.len());
+    let mut in_string = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if in_string {
+            output.push(c);
+            if c == '\\' && i + 1 < chars.len() {
+                output.push(chars[i + 1]);
+                i += 1;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+            output.push(c);
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            continue;
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+            continue;
+        } else if c == ',' && chars[i + 1..].iter().find(|c| !c.is_whitespace()).is_some_and(|next| *next == '}' || *next == ']') {
+            // 末尾のカンマ
+        } else {
+            output.push(c);
+        }
+        i += 1;
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_workspace_entry_points() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write("angular.json", r#"{
+            "projects": { "shop": { "architect": {
+                "build": {
+                    "options": { "browser": "src/main.ts", "polyfills": ["zone.js", "src/polyfills.ts"], "ssr": { "entry": "src/server.ts" } },
+                    "configurations": { "production": { "fileReplacements": [{ "replace": "src/env.ts", "with": "src/env.prod.ts" }] } }
+                }
+            } } }
+        }"#);
+        write("tsconfig.json", "/* Angular CLI */\n{\n  \"compilerOptions\": {\n    // エイリアス\n    \"paths\": { \"@shop/ui\": [\"libs/ui/index.ts\"], \"@shop/*\": [\"libs/*\"], },\n  },\n}\n");
+        for file in ["src/main.ts", "src/polyfills.ts", "src/server.ts", "src/env.prod.ts", "libs/ui/index.ts", "src/unused.ts"] {
+            write(file, "");
+        }
+
+        let root = fs::canonicalize(root).unwrap();
+        let entries: Vec<String> = workspace_entry_points(&root, &[]).iter()
+            .map(|path| path.strip_prefix(&root).unwrap().display().to_string().replace('\\', "/"))
+            .collect();
+        assert_eq!(entries, vec!["libs/ui/index.ts", "src/env.prod.ts", "src/main.ts", "src/polyfills.ts", "src/server.ts"]);
+    }
+
+    #[test]
+    fn test_workspace_resources() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        };
+        write("angular.json", r#"{
+            "projects": { "shop": { "architect": {
+                "build": {
+                    "options": {
+                        "index": "src/index.html",
+                        "styles": ["src/styles.scss", { "input": "src/theme/dark.scss", "bundleName": "dark", "inject": false }],
+                        "assets": ["src/favicon.ico", { "glob": "**/*", "input": "src/assets" }]
+                    }
+                }
+            } } }
+        }"#);
+        for file in ["src/index.html", "src/styles.scss", "src/theme/dark.scss", "src/favicon.ico", "src/assets/help.html"] {
+            write(file, "");
+        }
+
+        let root = fs::canonicalize(root).unwrap();
+        let resources: Vec<String> = workspace_resources(&root, &[]).iter()
+            .map(|path| path.strip_prefix(&root).unwrap().display().to_string().replace('\\', "/"))
+            .collect();
+        assert_eq!(resources, vec!["src/assets", "src/favicon.ico", "src/index.html", "src/styles.scss", "src/theme/dark.scss"]);
+    }
+}
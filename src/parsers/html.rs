@@ -1,7 +1,5 @@
-use anyhow::Result;
-use html5ever::driver::parse_document;
-use html5ever::tendril::TendrilSink;
-use markup5ever_rcdom::RcDom;
+use crate::ast::{ControlFlowBlock, TemplateComplexity, TemplateElement, TemplateExpressionCall, TemplateLoop};
+use regex::Regex;
 
 #[allow(dead_code)]
 pub struct HtmlParser;
@@ -12,72 +10,1032 @@ impl HtmlParser {
         Self
     }
 
-    #[allow(dead_code)]
-    pub fn parse_template(&self, template: &str) -> Result<TemplateAnalysis> {
-        let mut analysis = TemplateAnalysis {
-            elements: Vec::new(),
-            event_bindings: Vec::new(),
-            property_bindings: Vec::new(),
-            structural_directives: Vec::new(),
-            interpolations: Vec::new(),
-        };
+    pub fn parse_template(&self, template: &str) -> TemplateAnalysis {
+        let tokens = self.tokenize_control_flow(template);
+        let markup = self.tokenize(&tokens.markup);
+        let (pipes, calls) = template_expressions(&markup, &tokens.blocks, &tokens.spans);
+        TemplateAnalysis {
+            loops: template_loops(&markup, &tokens.blocks),
+            complexity: template_complexity(&markup, &tokens.spans),
+            pipes,
+            calls,
+            elements: markup.tags.into_iter().filter(|tag| !tag.closing).collect(),
+            control_flow: tokens.blocks,
+            element_positions: tokens.elements,
+        }
+    }
+
+    // Angular のテンプレートをタグと補間に分ける。属性名は [value]・(click)・*ngIf・#ref などの書き方と
+    // 大文字小文字をそのまま残す。閉じていないタグや壊れた HTML でも読める所まで読む
+    pub fn tokenize(&self, template: &str) -> TemplateTokens {
+        let bytes = template.as_bytes();
+        let mut tokens = TemplateTokens::default();
+        let mut position = 0;
+        while position < bytes.len() {
+            let rest = &template[position..];
+            if rest.starts_with("<!--") {
+                position = rest.find("-->").map_or(bytes.len(), |end| position + end + 3);
+            } else if rest.starts_with("{{") {
+                let interpolation = scan_interpolation(template, position);
+                position = interpolation.end;
+                tokens.interpolations.push(interpolation);
+            } else if let Some((tag, interpolations)) = rest.starts_with('<').then(|| scan_tag(template, position)).flatten() {
+                position = tag.end;
+                // <script> と <style> の中身はテンプレートとして読まない
+                let raw_text = tag.name.to_lowercase();
+                if !tag.closing && !tag.self_closing && RAW_TEXT_ELEMENTS.contains(&raw_text.as_str()) {
+                    let close = format!("</{}", raw_text);
+                    position = template[position..].to_ascii_lowercase().find(&close).map_or(bytes.len(), |end| position + end);
+                }
+                tokens.tags.push(tag);
+                tokens.interpolations.extend(interpolations);
+            } else {
+                position += rest.chars().next().map_or(1, char::len_utf8);
+            }
+        }
+        tokens
+    }
 
-        let dom = parse_document(RcDom::default(), Default::default())
-            .from_utf8()
-            .read_from(&mut template.as_bytes())?;
+    // @if / @for / @switch / @defer のブロック構文は HTML ではないので、タグを読む前に取り出す。
+    // markup はブロックの見出しと閉じ括弧を空白に置き換えたもので、行番号は変わらない
+    pub fn tokenize_control_flow(&self, template: &str) -> ControlFlowTokens {
+        let keyword = Regex::new(r"@(if|else\s+if|else|for|empty|switch|case|default|defer|placeholder|loading|error)\b").unwrap();
+        let component = Regex::new(r"<([a-zA-Z][\w-]*)").unwrap();
+        let bytes = template.as_bytes();
 
-        self.analyze_node(&dom.document, &mut analysis)?;
+        let mut markup = template.as_bytes().to_vec();
+        // (ブロック, 中身の範囲)。@else・@empty・@case の中身は元のブロックの範囲に足す
+        let mut blocks: Vec<(ControlFlowBlock, Vec<(usize, usize)>)> = Vec::new();
+        for found in keyword.captures_iter(template) {
+            let whole = found.get(0).unwrap();
+            // user@example.com などは対象外
+            if whole.start() > 0 && (bytes[whole.start() - 1].is_ascii_alphanumeric() || bytes[whole.start() - 1] == b'.') {
+                continue;
+            }
+            let mut position = skip_whitespace(template, whole.end());
+            let mut expression = "";
+            if bytes.get(position) == Some(&b'(') {
+                let Some(close) = matching_paren(template, position) else { continue };
+                expression = template[position + 1..close].trim();
+                position = skip_whitespace(template, close + 1);
+            }
+            let Some(close) = matching_brace(template, position) else { continue };
+            for i in (whole.start()..=position).chain(std::iter::once(close)) {
+                if markup[i] != b'\n' {
+                    markup[i] = b' ';
+                }
+            }
 
-        Ok(analysis)
+            let body = (position + 1, close);
+            let kind = found[1].split_whitespace().next().unwrap_or_default();
+            match kind {
+                "if" | "for" | "switch" | "defer" => blocks.push((
+                    ControlFlowBlock {
+                        kind: kind.to_string(),
+                        expression: expression.to_string(),
+                        line: line_at(template, whole.start()),
+                        children: Vec::new(),
+                        deferred: false,
+                    },
+                    vec![body],
+                )),
+                // 直前で閉じたブロックの続き
+                "else" | "empty" => {
+                    let previous = blocks.iter_mut().rev().find(|(block, ranges)| {
+                        matches!(block.kind.as_str(), "if" | "for")
+                            && ranges.last().is_some_and(|(_, end)| template[end + 1..whole.start()].trim().is_empty())
+                    });
+                    if let Some((_, ranges)) = previous {
+                        ranges.push(body);
+                    }
+                }
+                // @case / @default は @switch の中身、@placeholder などは @defer の外側として扱う
+                _ => {}
+            }
+        }
+
+        let deferred: Vec<(usize, usize)> = blocks.iter()
+            .filter(|(block, _)| block.kind == "defer")
+            .map(|(_, ranges)| ranges[0])
+            .collect();
+        let spans = blocks.iter()
+            .map(|(_, ranges)| (ranges[0].0, ranges.last().unwrap().1))
+            .collect();
+        let control_flow = blocks.into_iter()
+            .map(|(mut block, ranges)| {
+                let (start, end) = (ranges[0].0, ranges.last().unwrap().1);
+                block.deferred = deferred.iter().any(|(lo, hi)| *lo < start && end <= *hi);
+                for (lo, hi) in ranges {
+                    for caps in component.captures_iter(&template[lo..hi]) {
+                        let at = lo + caps.get(0).unwrap().start();
+                        let nested_defer = deferred.iter().any(|(d_lo, d_hi)| start < *d_lo && *d_lo <= at && at < *d_hi);
+                        let tag = caps[1].to_lowercase();
+                        if is_component_tag(&tag) && !nested_defer && !block.children.contains(&tag) {
+                            block.children.push(tag);
+                        }
+                    }
+                }
+                block
+            })
+            .collect();
+
+        // コメントの中の要素は数えない
+        let elements = self.tokenize(template).tags.into_iter()
+            .filter(|tag| !tag.closing)
+            .filter_map(|tag| {
+                let name = tag.name.to_lowercase();
+                if !(is_component_tag(&name) || name == "img") {
+                    return None;
+                }
+                Some(TemplateElement {
+                    lazy: name == "img" && tag.attributes.iter().any(|attribute| {
+                        attribute.name == "ngSrc" || (attribute.name == "loading" && attribute.value.as_deref() == Some("lazy"))
+                    }),
+                    tag: name,
+                    line: tag.line,
+                    deferred: deferred.iter().any(|(lo, hi)| *lo <= tag.start && tag.start < *hi),
+                })
+            })
+            .collect();
+
+        ControlFlowTokens {
+            markup: String::from_utf8(markup).unwrap_or_else(|_| template.to_string()),
+            blocks: control_flow,
+            spans,
+            elements,
+        }
     }
 
-    #[allow(dead_code)]
-    fn analyze_node(&self, node: &markup5ever_rcdom::Handle, analysis: &mut TemplateAnalysis) -> Result<()> {
-        match &node.data {
-            markup5ever_rcdom::NodeData::Element { name, attrs, .. } => {
-                let element_name = name.local.to_string();
-                analysis.elements.push(element_name);
-                
-                for attr in attrs.borrow().iter() {
-                    let attr_name = attr.name.local.to_string();
-                    let attr_value = attr.value.to_string();
-                    
-                    if attr_name.starts_with("(") && attr_name.ends_with(")") {
-                        analysis.event_bindings.push(format!("{}={}", attr_name, attr_value));
-                    } else if attr_name.starts_with("[") && attr_name.ends_with("]") {
-                        analysis.property_bindings.push(format!("{}={}", attr_name, attr_value));
-                    } else if attr_name.starts_with("*") {
-                        analysis.structural_directives.push(format!("{}={}", attr_name, attr_value));
+    // どの子コンポーネントを描画するかを型などで切り替える分岐 (kind, 判別式, 分岐数, 行番号)
+    pub fn find_selection_chains(&self, template: &str) -> Vec<(String, String, u32, u32)> {
+        let mut chains = Vec::new();
+
+        // @switch (expr) { @case (...) { <app-x /> } ... }
+        let switch_block = Regex::new(r"@switch\s*\(([^)]*)\)\s*\{").unwrap();
+        let case_block = Regex::new(r"@(?:case\s*\([^)]*\)|default)\s*\{").unwrap();
+        for caps in switch_block.captures_iter(template) {
+            let whole = caps.get(0).unwrap();
+            let body_end = matching_brace(template, whole.end() - 1).unwrap_or(template.len());
+            let body = &template[whole.end()..body_end];
+            let branches = case_block.find_iter(body)
+                .filter(|case| starts_with_component(&body[case.end()..]))
+                .count() as u32;
+            chains.push(("@switch".to_string(), caps[1].trim().to_string(), branches, line_at(template, whole.start())));
+        }
+
+        // @if (x.type === 'a') { ... } @else if (x.type === 'b') { ... }
+        let if_block = Regex::new(r"@if\s*\(").unwrap();
+        let else_if_block = Regex::new(r"^\s*@else\s+if\s*\(").unwrap();
+        for found in if_block.find_iter(template) {
+            // @else if の一部は先頭の @if から辿るので除外する
+            if template[..found.start()].trim_end().ends_with("@else") {
+                continue;
+            }
+            let mut position = found.end() - 1;
+            let mut discriminators = Vec::new();
+            let mut branches = 0;
+            while let Some(condition_end) = matching_paren(template, position) {
+                let condition = &template[position + 1..condition_end];
+                let Some(block_start) = template[condition_end..].find('{').map(|i| condition_end + i) else { break };
+                let Some(block_end) = matching_brace(template, block_start) else { break };
+                discriminators.push(discriminator(condition));
+                if starts_with_component(&template[block_start + 1..block_end]) {
+                    branches += 1;
+                }
+                match else_if_block.find(&template[block_end + 1..]) {
+                    Some(next) => position = block_end + 1 + next.end() - 1,
+                    None => break,
+                }
+            }
+            if let Some(Some(first)) = discriminators.first() {
+                if discriminators.iter().all(|d| d.as_ref() == Some(first)) {
+                    chains.push(("@if".to_string(), first.clone(), branches, line_at(template, found.start())));
+                }
+            }
+        }
+
+        // [ngSwitch]="expr" ... *ngSwitchCase
+        let ng_switch = Regex::new(r#"\[ngSwitch\]\s*=\s*"([^"]*)""#).unwrap();
+        let ng_switch_case = Regex::new(r#"<([\w-]+)[^>]*\*ngSwitch(?:Case|Default)"#).unwrap();
+        let switches: Vec<_> = ng_switch.captures_iter(template).collect();
+        for (i, caps) in switches.iter().enumerate() {
+            let start = caps.get(0).unwrap().end();
+            let end = switches.get(i + 1).map(|next| next.get(0).unwrap().start()).unwrap_or(template.len());
+            let scope = &template[start..end];
+            let branches = ng_switch_case.captures_iter(scope)
+                .filter(|case| {
+                    let tag = &case[1];
+                    let rest = &scope[case.get(0).unwrap().end()..];
+                    let children = rest.find('>').map(|i| &rest[i + 1..]).unwrap_or("");
+                    is_component_tag(tag) || (tag == "ng-container" && starts_with_component(children))
+                })
+                .count() as u32;
+            chains.push(("ngSwitch".to_string(), caps[1].trim().to_string(), branches, line_at(template, caps.get(0).unwrap().start())));
+        }
+
+        // 同じ判別式を持つ *ngIf="x.type === 'a'" の並び
+        let ng_if = Regex::new(r#"<([\w-]+)[^>]*\*ngIf\s*=\s*"([^"]*)""#).unwrap();
+        let mut ng_if_groups: Vec<(String, u32, u32)> = Vec::new();
+        for caps in ng_if.captures_iter(template) {
+            if !is_component_tag(&caps[1]) {
+                continue;
+            }
+            let Some(key) = discriminator(&caps[2]) else { continue };
+            match ng_if_groups.iter_mut().find(|(existing, _, _)| *existing == key) {
+                Some(group) => group.1 += 1,
+                None => ng_if_groups.push((key, 1, line_at(template, caps.get(0).unwrap().start()))),
+            }
+        }
+        for (key, branches, line) in ng_if_groups {
+            chains.push(("*ngIf".to_string(), key, branches, line));
+        }
+
+        chains.retain(|(_, _, branches, _)| *branches > 1);
+        chains
+    }
+
+    // カスタム要素の使われ方 (タグ, kind)。ng-template 内は template、他のコンポーネントの中身は projected
+    pub fn find_element_usages(&self, template: &str) -> Vec<(String, String, u32)> {
+        let mut usages: Vec<(String, String, u32)> = Vec::new();
+        let mut stack: Vec<String> = Vec::new();
+        for tag in self.tokenize(template).tags {
+            let name = tag.name.to_lowercase();
+            if tag.closing {
+                if let Some(open) = stack.iter().rposition(|open| *open == name) {
+                    stack.truncate(open);
+                }
+                continue;
+            }
+
+            if is_component_tag(&name) {
+                let kind = if stack.iter().any(|open| open == "ng-template") {
+                    "template"
+                } else if stack.iter().any(|open| is_component_tag(open)) {
+                    "projected"
+                } else {
+                    "embeds"
+                };
+                match usages.iter_mut().find(|(tag, existing, _)| *tag == name && existing == kind) {
+                    Some((_, _, count)) => *count += 1,
+                    None => usages.push((name.clone(), kind.to_string(), 1)),
+                }
+            }
+
+            if !tag.self_closing && !VOID_ELEMENTS.contains(&name.as_str()) {
+                stack.push(name);
+            }
+        }
+
+        usages
+    }
+
+    // カスタム要素ごとに束縛している名前 (タグ, 入力, 出力)。[(value)] は value と valueChange の両方
+    pub fn find_element_bindings(&self, template: &str) -> Vec<(String, Vec<String>, Vec<String>)> {
+        let mut bindings: Vec<(String, Vec<String>, Vec<String>)> = Vec::new();
+        for tag in self.tokenize(template).tags.into_iter().filter(|tag| !tag.closing) {
+            let name = tag.name.to_lowercase();
+            if !is_component_tag(&name) {
+                continue;
+            }
+            let index = match bindings.iter().position(|(existing, _, _)| *existing == name) {
+                Some(index) => index,
+                None => {
+                    bindings.push((name, Vec::new(), Vec::new()));
+                    bindings.len() - 1
+                }
+            };
+            let (_, inputs, outputs) = &mut bindings[index];
+            for attribute in &tag.attributes {
+                let (input, output) = match attribute.kind {
+                    BindingKind::Attribute | BindingKind::Property => (Some(attribute.name.clone()), None),
+                    BindingKind::Event => (None, Some(attribute.name.clone())),
+                    BindingKind::TwoWay => (Some(attribute.name.clone()), Some(format!("{}Change", attribute.name))),
+                    BindingKind::Structural | BindingKind::Reference => (None, None),
+                };
+                for (names, found) in [(&mut *inputs, input), (&mut *outputs, output)] {
+                    if let Some(found) = found.filter(|found| !names.contains(found)) {
+                        names.push(found);
                     }
                 }
             }
-            markup5ever_rcdom::NodeData::Text { contents } => {
-                let text = contents.borrow().to_string();
-                if text.contains("{{") && text.contains("}}") {
-                    analysis.interpolations.push(text);
+        }
+        bindings
+    }
+
+    // class 属性、[class.x]、[ngClass] / [class] の式に現れるクラス名。btn-{{type}} は接頭辞 btn- として返す
+    pub fn find_used_classes(&self, template: &str) -> Vec<String> {
+        let literal = Regex::new(r#"'([^']*)'|"([^"]*)"|`([^`]*)`"#).unwrap();
+        let key = Regex::new(r"[{,]\s*([\w-]+)\s*:").unwrap();
+        let interpolation = Regex::new(r"\{\{.*?\}\}|\$\{[^}]*\}").unwrap();
+
+        let mut classes: Vec<String> = Vec::new();
+        let mut add = |token: &str| {
+            // 補間を含むトークンは {{ より前を接頭辞として扱う
+            let token = token.split("{{").next().unwrap_or_default();
+            if is_class_name(token.trim_end_matches(['-', '_'])) && !classes.iter().any(|c| c == token) {
+                classes.push(token.to_string());
+            }
+        };
+
+        let attributes = self.tokenize(template).tags.into_iter().flat_map(|tag| tag.attributes);
+        for attribute in attributes {
+            let value = interpolation.replace_all(attribute.value.as_deref().unwrap_or_default(), "{{}}");
+            match (attribute.kind, attribute.name.as_str()) {
+                (BindingKind::Attribute, "class") => value.split_whitespace().for_each(&mut add),
+                (BindingKind::Property, name) if name.starts_with("class.") => add(&name["class.".len()..]),
+                (BindingKind::Property, "ngClass" | "class") | (BindingKind::Attribute, "ngClass") => {
+                    for literal in literal.captures_iter(&value) {
+                        let text = literal.get(1).or(literal.get(2)).or(literal.get(3)).map_or("", |m| m.as_str());
+                        text.split_whitespace().for_each(&mut add);
+                    }
+                    for key in key.captures_iter(&value) {
+                        add(&key[1]);
+                    }
                 }
+                _ => {}
             }
+        }
+
+        classes
+    }
+}
+
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source", "track", "wbr",
+];
+
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+// start は < の位置。タグ名が続かない < は文字として扱うので None
+fn scan_tag(template: &str, start: usize) -> Option<(TemplateTag, Vec<Interpolation>)> {
+    let bytes = template.as_bytes();
+    let closing = bytes.get(start + 1) == Some(&b'/');
+    let name_start = start + 1 + closing as usize;
+    if !bytes.get(name_start).is_some_and(u8::is_ascii_alphabetic) {
+        return None;
+    }
+    let name_end = template[name_start..]
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.')))
+        .map_or(bytes.len(), |end| name_start + end);
+
+    let mut attributes = Vec::new();
+    let mut interpolations = Vec::new();
+    let mut position = name_end;
+    let (end, self_closing) = loop {
+        position = skip_whitespace(template, position);
+        match bytes.get(position) {
+            // > のないタグは最後まで読まない
+            None => return None,
+            Some(b'>') => break (position + 1, false),
+            Some(b'/') if bytes.get(position + 1) == Some(&b'>') => break (position + 2, true),
             _ => {}
         }
 
-        for child in node.children.borrow().iter() {
-            self.analyze_node(child, analysis)?;
+        let name_start = position;
+        let name_ends = |at: usize| match bytes.get(at) {
+            None | Some(b'=' | b'>') => true,
+            Some(b'/') => bytes.get(at + 1) == Some(&b'>'),
+            Some(byte) => byte.is_ascii_whitespace(),
+        };
+        while !name_ends(position) {
+            position += 1;
         }
+        if position == name_start {
+            // 名前のない = などは読み飛ばす
+            position += 1;
+            continue;
+        }
+        let raw_name = &template[name_start..position];
 
-        Ok(())
+        let mut value = None;
+        let after_name = skip_whitespace(template, position);
+        if bytes.get(after_name) == Some(&b'=') {
+            let value_start = skip_whitespace(template, after_name + 1);
+            let (lo, hi) = match bytes.get(value_start) {
+                Some(&quote @ (b'"' | b'\'')) => {
+                    let close = value_start + 1 + template[value_start + 1..].find(quote as char)?;
+                    position = close + 1;
+                    (value_start + 1, close)
+                }
+                _ => {
+                    position = template[value_start..]
+                        .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                        .map_or(bytes.len(), |end| value_start + end);
+                    (value_start, position)
+                }
+            };
+            let mut at = lo;
+            while let Some(open) = template[at..hi].find("{{") {
+                let interpolation = scan_interpolation(&template[..hi], at + open);
+                at = interpolation.end;
+                interpolations.push(interpolation);
+            }
+            value = Some(template[lo..hi].to_string());
+        }
+
+        let (kind, name) = binding(raw_name);
+        attributes.push(TemplateAttribute {
+            raw_name: raw_name.to_string(),
+            name: name.to_string(),
+            kind,
+            value,
+            line: line_at(template, name_start),
+        });
+    };
+
+    let tag = TemplateTag {
+        name: template[name_start..name_end].to_string(),
+        attributes: if closing { Vec::new() } else { attributes },
+        closing,
+        self_closing,
+        start,
+        end,
+        line: line_at(template, start),
+    };
+    Some((tag, interpolations))
+}
+
+// start は {{ の位置。式の中の文字列に書いた }} では閉じない
+fn scan_interpolation(template: &str, start: usize) -> Interpolation {
+    let bytes = template.as_bytes();
+    let mut quote = None;
+    let mut position = start + 2;
+    while position < bytes.len() {
+        match (quote, bytes[position]) {
+            (Some(open), byte) if byte == open => quote = None,
+            (None, byte @ (b'"' | b'\'' | b'`')) => quote = Some(byte),
+            (None, b'}') if bytes.get(position + 1) == Some(&b'}') => break,
+            _ => {}
+        }
+        position += 1;
+    }
+    Interpolation {
+        expression: template[start + 2..position].trim().to_string(),
+        start,
+        end: (position + 2).min(bytes.len()),
+        line: line_at(template, start),
+    }
+}
+
+// spans は制御フローのブロックの範囲。入れ子は構造ディレクティブの付いた要素とブロックを合わせて数える
+fn template_complexity(tokens: &TemplateTokens, spans: &[(usize, usize)]) -> TemplateComplexity {
+    let enclosing_blocks = |at: usize| spans.iter().filter(|(lo, hi)| *lo <= at && at < *hi).count() as u32;
+    let mut max_depth = spans.iter().map(|(lo, _)| enclosing_blocks(*lo)).max().unwrap_or(0);
+    let mut structural = spans.len() as u32;
+    let mut pipes: u32 = tokens.interpolations.iter().map(|interpolation| count_pipes(&interpolation.expression)).sum();
+    let mut bindings = 0;
+
+    // (タグ名, 構造ディレクティブが付いているか)
+    let mut stack: Vec<(String, bool)> = Vec::new();
+    for tag in &tokens.tags {
+        let name = tag.name.to_lowercase();
+        if tag.closing {
+            if let Some(open) = stack.iter().rposition(|(open, _)| *open == name) {
+                stack.truncate(open);
+            }
+            continue;
+        }
+
+        let directives = tag.attributes.iter().filter(|attribute| attribute.kind == BindingKind::Structural).count() as u32;
+        bindings += tag.attributes.iter().filter(|attribute| matches!(attribute.kind, BindingKind::Property | BindingKind::TwoWay)).count() as u32;
+        pipes += tag.attributes.iter()
+            .filter(|attribute| matches!(attribute.kind, BindingKind::Property | BindingKind::TwoWay | BindingKind::Structural))
+            .filter_map(|attribute| attribute.value.as_deref())
+            .map(count_pipes)
+            .sum::<u32>();
+        if directives > 0 {
+            structural += directives;
+            let depth = stack.iter().filter(|(_, structural)| *structural).count() as u32 + enclosing_blocks(tag.start) + 1;
+            max_depth = max_depth.max(depth);
+        }
+        if !tag.self_closing && !VOID_ELEMENTS.contains(&name.as_str()) {
+            stack.push((name, directives > 0));
+        }
+    }
+
+    TemplateComplexity::new(structural, max_depth, tokens.interpolations.len() as u32, pipes).with_bindings(bindings)
+}
+
+// 式の中のパイプの数。|| と文字列の中の | は数えない
+fn count_pipes(expression: &str) -> u32 {
+    pipe_names(expression).len() as u32
+}
+
+// items | slice:0:10 | json の slice と json
+fn pipe_names(expression: &str) -> Vec<&str> {
+    let code = blank_strings(expression);
+    let bytes = code.as_bytes();
+    let mut names = Vec::new();
+    for (i, &byte) in bytes.iter().enumerate() {
+        if byte == b'|' && bytes.get(i + 1) != Some(&b'|') && (i == 0 || bytes[i - 1] != b'|') {
+            let rest = expression[i + 1..].trim_start();
+            let end = rest.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '$')).unwrap_or(rest.len());
+            if end > 0 {
+                names.push(&rest[..end]);
+            }
+        }
     }
+    names
+}
+
+// this を省いて呼んでいる関数（total()・isActive(item)）。a.b() の b と $any は除く
+fn called_names(expression: &str) -> Vec<String> {
+    let call = Regex::new(r"(?:^|[^\w$.])([A-Za-z_$][\w$]*)\s*\(").unwrap();
+    call.captures_iter(&blank_strings(expression))
+        .map(|caps| caps[1].to_string())
+        .filter(|name| name != "$any")
+        .collect()
+}
+
+// 文字列リテラルの中身を空白にする。位置は変わらない
+fn blank_strings(expression: &str) -> String {
+    let mut quote = None;
+    expression.chars()
+        .map(|c| match (quote, c) {
+            (Some(open), c) if c == open => {
+                quote = None;
+                c
+            }
+            (Some(_), _) => ' ',
+            (None, '"' | '\'' | '`') => {
+                quote = Some(c);
+                c
+            }
+            _ => c,
+        })
+        .collect()
+}
+
+// 式に書いたパイプと関数呼び出しを、*ngFor・@for の繰り返しの中かどうかと合わせて集める
+fn template_expressions(tokens: &TemplateTokens, blocks: &[ControlFlowBlock], spans: &[(usize, usize)]) -> (Vec<TemplateExpressionCall>, Vec<TemplateExpressionCall>) {
+    // *ngFor の付いた要素と @for の範囲
+    let mut loops: Vec<(usize, usize)> = blocks.iter().zip(spans)
+        .filter(|(block, _)| block.kind == "for")
+        .map(|(_, span)| *span)
+        .collect();
+    let mut stack: Vec<(String, Option<usize>)> = Vec::new();
+    for tag in &tokens.tags {
+        let name = tag.name.to_lowercase();
+        if tag.closing {
+            if let Some(open) = stack.iter().rposition(|(open, _)| *open == name) {
+                loops.extend(stack.drain(open..).filter_map(|(_, start)| start).map(|start| (start, tag.end)));
+            }
+            continue;
+        }
+        let is_loop = tag.attributes.iter().any(|attribute| attribute.kind == BindingKind::Structural && attribute.name == "ngFor");
+        if tag.self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+            if is_loop {
+                loops.push((tag.start, tag.end));
+            }
+        } else {
+            stack.push((name, is_loop.then_some(tag.start)));
+        }
+    }
+    loops.extend(stack.into_iter().filter_map(|(_, start)| start).map(|start| (start, usize::MAX)));
+    // own は *ngFor の式そのもの。繰り返しの外で 1 回だけ評価される
+    let in_loop = |at: usize, own: bool| loops.iter().any(|(lo, hi)| *lo <= at && at < *hi && !(own && *lo == at));
+
+    let mut expressions: Vec<(&str, u32, bool)> = tokens.interpolations.iter()
+        .map(|interpolation| (interpolation.expression.as_str(), interpolation.line, in_loop(interpolation.start, false)))
+        .collect();
+    for tag in tokens.tags.iter().filter(|tag| !tag.closing) {
+        for attribute in &tag.attributes {
+            if !matches!(attribute.kind, BindingKind::Property | BindingKind::TwoWay | BindingKind::Structural) {
+                continue;
+            }
+            let Some(value) = attribute.value.as_deref() else { continue };
+            let own = attribute.kind == BindingKind::Structural && attribute.name == "ngFor";
+            expressions.push((value, attribute.line, in_loop(tag.start, own)));
+        }
+    }
+    for (block, (lo, _)) in blocks.iter().zip(spans) {
+        expressions.push((block.expression.as_str(), block.line, in_loop(lo.saturating_sub(1), false)));
+    }
+    expressions.sort_by_key(|(_, line, _)| *line);
+
+    let mut pipes = Vec::new();
+    let mut calls = Vec::new();
+    for (expression, line, in_loop) in expressions {
+        pipes.extend(pipe_names(expression).into_iter()
+            .map(|name| TemplateExpressionCall { name: name.to_string(), line, in_loop }));
+        calls.extend(called_names(expression).into_iter()
+            .map(|name| TemplateExpressionCall { name, line, in_loop }));
+    }
+    (pipes, calls)
+}
+
+// *ngFor・<ng-template ngFor [ngForOf]>・@for の、繰り返す要素と trackBy / track
+fn template_loops(tokens: &TemplateTokens, blocks: &[ControlFlowBlock]) -> Vec<TemplateLoop> {
+    let ng_for = Regex::new(r"^\s*let\s+([\w$]+)\s+of\s+(.+)$").unwrap();
+    let for_block = Regex::new(r"^\s*([\w$]+)\s+of\s+(.+)$").unwrap();
+    let mut loops = Vec::new();
+
+    for tag in tokens.tags.iter().filter(|tag| !tag.closing) {
+        let attribute = |kind: BindingKind, name: &str| tag.attributes.iter()
+            .find(|attribute| attribute.kind == kind && attribute.name == name)
+            .and_then(|attribute| attribute.value.as_deref());
+        if let Some(value) = attribute(BindingKind::Structural, "ngFor") {
+            let parts: Vec<&str> = value.split(';').collect();
+            let Some(caps) = ng_for.captures(parts[0]) else { continue };
+            // trackBy: trackById と trackBy trackById のどちらも書ける
+            let track = parts.iter().skip(1)
+                .filter_map(|part| part.trim().strip_prefix("trackBy"))
+                .map(|rest| rest.trim_start_matches([':', ' ']).trim().to_string())
+                .next();
+            loops.push(TemplateLoop {
+                kind: "ngFor".to_string(),
+                item: caps[1].to_string(),
+                iterable: caps[2].trim().to_string(),
+                track,
+                line: tag.line,
+                item_type: None,
+            });
+        } else if let Some(iterable) = attribute(BindingKind::Property, "ngForOf") {
+            let item = tag.attributes.iter()
+                .find_map(|attribute| attribute.name.strip_prefix("let-").filter(|_| attribute.value.as_deref().is_none_or(|value| value == "$implicit")));
+            loops.push(TemplateLoop {
+                kind: "ngFor".to_string(),
+                item: item.unwrap_or("item").to_string(),
+                iterable: iterable.trim().to_string(),
+                track: attribute(BindingKind::Property, "ngForTrackBy").map(|track| track.trim().to_string()),
+                line: tag.line,
+                item_type: None,
+            });
+        }
+    }
+
+    for block in blocks.iter().filter(|block| block.kind == "for") {
+        let mut parts = block.expression.split(';');
+        let Some(caps) = parts.next().and_then(|head| for_block.captures(head)) else { continue };
+        loops.push(TemplateLoop {
+            kind: "for".to_string(),
+            item: caps[1].to_string(),
+            iterable: caps[2].trim().to_string(),
+            track: parts.find_map(|part| part.trim().strip_prefix("track ")).map(|track| track.trim().to_string()),
+            line: block.line,
+            item_type: None,
+        });
+    }
+
+    loops.sort_by_key(|found| found.line);
+    loops
+}
+
+// 属性の書き方から束縛の種類と名前を取り出す。bind-・on-・bindon-・ref- の書き方も同じ扱い
+fn binding(raw_name: &str) -> (BindingKind, &str) {
+    let wrapped = |open: &str, close: &str| raw_name.strip_prefix(open).and_then(|name| name.strip_suffix(close));
+    if let Some(name) = wrapped("[(", ")]").or_else(|| raw_name.strip_prefix("bindon-")) {
+        (BindingKind::TwoWay, name)
+    } else if let Some(name) = wrapped("[", "]").or_else(|| raw_name.strip_prefix("bind-")) {
+        (BindingKind::Property, name)
+    } else if let Some(name) = wrapped("(", ")").or_else(|| raw_name.strip_prefix("on-")) {
+        (BindingKind::Event, name)
+    } else if let Some(name) = raw_name.strip_prefix('*') {
+        (BindingKind::Structural, name)
+    } else if let Some(name) = raw_name.strip_prefix('#').or_else(|| raw_name.strip_prefix("ref-")) {
+        (BindingKind::Reference, name)
+    } else {
+        (BindingKind::Attribute, raw_name)
+    }
+}
+
+// `x.type === 'chart'` の `x.type` 部分
+fn discriminator(condition: &str) -> Option<String> {
+    let comparison = Regex::new(r#"([\w$.?!]+(?:\(\))?)\s*===?\s*['"]"#).unwrap();
+    comparison.captures(condition).map(|caps| caps[1].to_string())
+}
+
+fn is_component_tag(tag: &str) -> bool {
+    tag.contains('-') && !tag.starts_with("ng-")
+}
+
+pub fn is_class_name(token: &str) -> bool {
+    let mut chars = token.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '-')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn starts_with_component(body: &str) -> bool {
+    let tag = Regex::new(r"^\s*<([\w-]+)").unwrap();
+    tag.captures(body).is_some_and(|caps| is_component_tag(&caps[1]))
+}
+
+fn skip_whitespace(text: &str, position: usize) -> usize {
+    position + text[position..].len() - text[position..].trim_start().len()
+}
+
+fn matching_brace(text: &str, open: usize) -> Option<usize> {
+    matching(text, open, b'{', b'}')
+}
+
+fn matching_paren(text: &str, open: usize) -> Option<usize> {
+    matching(text, open, b'(', b')')
+}
+
+fn matching(text: &str, open: usize, open_char: u8, close_char: u8) -> Option<usize> {
+    let bytes = text.as_bytes();
+    if bytes.get(open) != Some(&open_char) {
+        return None;
+    }
+    let mut depth = 0;
+    for (i, &byte) in bytes.iter().enumerate().skip(open) {
+        if byte == open_char {
+            depth += 1;
+        } else if byte == close_char {
+            depth -= 1;
+            if depth == 0 {
+                return Some(i);
+            }
+        }
+    }
+    None
+}
+
+pub fn line_at(text: &str, offset: usize) -> u32 {
+    text[..offset.min(text.len())].matches('\n').count() as u32 + 1
+}
+
+pub struct ControlFlowTokens {
+    pub markup: String,
+    pub blocks: Vec<ControlFlowBlock>,
+    // blocks と同じ順の、中身の最初から最後まで（@else などの続きを含む）
+    pub spans: Vec<(usize, usize)>,
+    pub elements: Vec<TemplateElement>,
+}
+
+#[derive(Debug, Default)]
+pub struct TemplateTokens {
+    // 開始タグと閉じタグを出てきた順に
+    pub tags: Vec<TemplateTag>,
+    // テキストと属性値の {{ }}
+    pub interpolations: Vec<Interpolation>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateTag {
+    // 書いたままのタグ名
+    pub name: String,
+    pub attributes: Vec<TemplateAttribute>,
+    pub closing: bool,
+    pub self_closing: bool,
+    // < から > の次までのバイト位置
+    pub start: usize,
+    pub end: usize,
+    pub line: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    Attribute,
+    Property,
+    Event,
+    TwoWay,
+    Structural,
+    Reference,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateAttribute {
+    // [value]・(click)・*ngIf など書いたまま
+    pub raw_name: String,
+    // 束縛の記号を外した名前（value・click・ngIf）
+    pub name: String,
+    pub kind: BindingKind,
+    pub value: Option<String>,
+    pub line: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interpolation {
+    pub expression: String,
+    // {{ から }} の次までのバイト位置
+    pub start: usize,
+    pub end: usize,
+    pub line: u32,
 }
 
 #[derive(Debug)]
 pub struct TemplateAnalysis {
-    #[allow(dead_code)]
-    pub elements: Vec<String>,
-    #[allow(dead_code)]
-    pub event_bindings: Vec<String>,
-    #[allow(dead_code)]
-    pub property_bindings: Vec<String>,
-    #[allow(dead_code)]
-    pub structural_directives: Vec<String>,
-    #[allow(dead_code)]
-    pub interpolations: Vec<String>,
-}
\ No newline at end of file
+    pub complexity: TemplateComplexity,
+    pub loops: Vec<TemplateLoop>,
+    pub pipes: Vec<TemplateExpressionCall>,
+    pub calls: Vec<TemplateExpressionCall>,
+    // 開始タグ
+    pub elements: Vec<TemplateTag>,
+    pub control_flow: Vec<ControlFlowBlock>,
+    pub element_positions: Vec<TemplateElement>,
+}
+
+impl TemplateAnalysis {
+    pub fn bindings(&self, kind: BindingKind) -> impl Iterator<Item = &TemplateAttribute> {
+        self.elements.iter()
+            .flat_map(|tag| &tag.attributes)
+            .filter(move |attribute| attribute.kind == kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_selection_chains() {
+        let template = r#"<h1>Widgets</h1>
+@switch (widget.type) {
+  @case ('chart') { <app-chart-widget [data]="widget" /> }
+  @case ('table') { <app-table-widget [data]="widget" /> }
+  @default { <app-text-widget [data]="widget" /> }
+}
+@if (item.kind === 'video') {
+  <app-video [item]="item" />
+} @else if (item.kind === 'image') {
+  <app-image [item]="item" />
+} @else if (loading) {
+  <p>Loading</p>
+}
+<div [ngSwitch]="mode">
+  <app-editor *ngSwitchCase="'edit'"></app-editor>
+  <ng-container *ngSwitchCase="'view'"><app-viewer></app-viewer></ng-container>
+  <p *ngSwitchDefault>none</p>
+</div>
+"#;
+        let chains = HtmlParser::new().find_selection_chains(template);
+
+        assert_eq!(chains, vec![
+            ("@switch".to_string(), "widget.type".to_string(), 3, 2),
+            ("ngSwitch".to_string(), "mode".to_string(), 2, 14),
+        ]);
+    }
+
+    #[test]
+    fn test_find_element_usages() {
+        let template = r#"<app-header title="a > b" />
+<app-card>
+  <app-avatar [user]="user"></app-avatar>
+</app-card>
+<!-- <app-legacy></app-legacy> -->
+<ng-template #row let-item>
+  <app-row [item]="item"><input type="checkbox"></app-row>
+</ng-template>
+<ng-container *ngTemplateOutlet="row"></ng-container>
+<app-footer></app-footer>
+<app-card></app-card>
+"#;
+        let usages = HtmlParser::new().find_element_usages(template);
+
+        assert_eq!(usages, vec![
+            ("app-header".to_string(), "embeds".to_string(), 1),
+            ("app-card".to_string(), "embeds".to_string(), 2),
+            ("app-avatar".to_string(), "projected".to_string(), 1),
+            ("app-row".to_string(), "template".to_string(), 1),
+            ("app-footer".to_string(), "embeds".to_string(), 1),
+        ]);
+    }
+
+    #[test]
+    fn test_find_element_bindings() {
+        let template = r#"<app-card title="Orders" [items]="orders" (select)="open($event)"></app-card>
+<app-card [(expanded)]="expanded" #card></app-card>
+<input [value]="query" (input)="search()">
+<app-footer *ngIf="ready"></app-footer>
+"#;
+        let bindings = HtmlParser::new().find_element_bindings(template);
+
+        let strings = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        assert_eq!(bindings, vec![
+            ("app-card".to_string(), strings(&["title", "items", "expanded"]), strings(&["select", "expandedChange"])),
+            ("app-footer".to_string(), vec![], vec![]),
+        ]);
+    }
+
+    #[test]
+    fn test_find_used_classes() {
+        let template = r#"<div class="card  card--wide" [class.is-active]="active">
+  <span [ngClass]="{ highlight: selected, 'text-muted': !selected }"></span>
+  <button class="btn btn-{{ kind }}" [ngClass]="large ? 'btn-lg' : ''">{{ label }}</button>
+  <p class="{{ dynamic }}" [class]="'note ' + tone"></p>
+</div>
+"#;
+        let classes = HtmlParser::new().find_used_classes(template);
+
+        assert_eq!(classes, vec![
+            "card", "card--wide", "is-active", "text-muted", "highlight", "btn", "btn-", "btn-lg", "note",
+        ]);
+    }
+
+    #[test]
+    fn test_tokenize_control_flow() {
+        let template = r#"<h1>Orders</h1>
+@if (orders.length > 0) {
+  <app-order-table [orders]="orders" />
+} @else {
+  <app-empty-state />
+}
+@for (order of orders; track order.id) {
+  <app-order-row [order]="order" />
+} @empty {
+  <p>Contact support@example.com</p>
+}
+@defer (on viewport) {
+  @if (showChart) { <app-chart /> }
+} @placeholder {
+  <app-skeleton />
+}
+<li *ngFor="let item of items">{{ item }}</li>
+"#;
+        let analysis = HtmlParser::new().parse_template(template);
+
+        let blocks: Vec<(&str, &str, u32, Vec<&str>, bool)> = analysis.control_flow.iter()
+            .map(|b| (b.kind.as_str(), b.expression.as_str(), b.line, b.children.iter().map(String::as_str).collect(), b.deferred))
+            .collect();
+        assert_eq!(blocks, vec![
+            ("if", "orders.length > 0", 2, vec!["app-order-table", "app-empty-state"], false),
+            ("for", "order of orders; track order.id", 7, vec!["app-order-row"], false),
+            ("defer", "on viewport", 12, vec!["app-chart"], false),
+            ("if", "showChart", 13, vec!["app-chart"], true),
+        ]);
+        let deferred: Vec<(&str, bool)> = analysis.element_positions.iter()
+            .filter(|e| e.line >= 12)
+            .map(|e| (e.tag.as_str(), e.deferred))
+            .collect();
+        assert_eq!(deferred, vec![("app-chart", true), ("app-skeleton", false)]);
+        // ブロック構文を取り除いたテンプレートとして読める
+        let directives: Vec<(&str, Option<&str>)> = analysis.bindings(BindingKind::Structural)
+            .map(|d| (d.name.as_str(), d.value.as_deref()))
+            .collect();
+        assert_eq!(directives, vec![("ngFor", Some("let item of items"))]);
+        assert!(analysis.elements.iter().any(|tag| tag.name == "app-order-row"));
+        // ブロック 4 つと *ngFor、@defer の中の @if で 2 段
+        assert_eq!(analysis.complexity, TemplateComplexity::new(5, 2, 1, 0).with_bindings(2));
+        assert_eq!(analysis.complexity.score, 7);
+    }
+
+    #[test]
+    fn test_template_expressions() {
+        let template = r#"@if (user$ | async; as user) {
+  @for (order of orders | sortBy:'date'; track order.id) {
+    <app-row [price]="order.price | currency" [label]="format(order)" (click)="select(order)" />
+  }
+}
+<p>{{ total() | number:'1.0-0' }} {{ user.name.trim() }}</p>
+"#;
+        let analysis = HtmlParser::new().parse_template(template);
+
+        let pipes: Vec<(&str, u32, bool)> = analysis.pipes.iter().map(|p| (p.name.as_str(), p.line, p.in_loop)).collect();
+        assert_eq!(pipes, vec![("async", 1, false), ("sortBy", 2, false), ("currency", 3, true), ("number", 6, false)]);
+        let calls: Vec<(&str, u32, bool)> = analysis.calls.iter().map(|c| (c.name.as_str(), c.line, c.in_loop)).collect();
+        assert_eq!(calls, vec![("format", 3, true), ("total", 6, false)]);
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let template = r#"<!-- <app-old [x]="y"> -->
+<app-form [formGroup]="form" (ngSubmit)="save($event)" [(ngModel)]="name"
+          *ngIf="user as u" #editor bind-title="t" data-id=42 disabled>
+  <p title="Hi {{ user.name }}">{{ greet('}}') }} and {{ count > 1 ? 'items' : 'item' }}</p>
+  <script>if (a<b) {}</script>
+  <br/>
+</app-form>
+"#;
+        let tokens = HtmlParser::new().tokenize(template);
+
+        let tags: Vec<(&str, bool, bool, u32)> = tokens.tags.iter()
+            .map(|t| (t.name.as_str(), t.closing, t.self_closing, t.line))
+            .collect();
+        assert_eq!(tags, vec![
+            ("app-form", false, false, 2),
+            ("p", false, false, 4),
+            ("p", true, false, 4),
+            ("script", false, false, 5),
+            ("script", true, false, 5),
+            ("br", false, true, 6),
+            ("app-form", true, false, 7),
+        ]);
+        let attributes: Vec<(&str, BindingKind, &str, Option<&str>, u32)> = tokens.tags[0].attributes.iter()
+            .map(|a| (a.raw_name.as_str(), a.kind, a.name.as_str(), a.value.as_deref(), a.line))
+            .collect();
+        assert_eq!(attributes, vec![
+            ("[formGroup]", BindingKind::Property, "formGroup", Some("form"), 2),
+            ("(ngSubmit)", BindingKind::Event, "ngSubmit", Some("save($event)"), 2),
+            ("[(ngModel)]", BindingKind::TwoWay, "ngModel", Some("name"), 2),
+            ("*ngIf", BindingKind::Structural, "ngIf", Some("user as u"), 3),
+            ("#editor", BindingKind::Reference, "editor", None, 3),
+            ("bind-title", BindingKind::Property, "title", Some("t"), 3),
+            ("data-id", BindingKind::Attribute, "data-id", Some("42"), 3),
+            ("disabled", BindingKind::Attribute, "disabled", None, 3),
+        ]);
+
+        let interpolations: Vec<(&str, &str, u32)> = tokens.interpolations.iter()
+            .map(|i| (i.expression.as_str(), &template[i.start..i.end], i.line))
+            .collect();
+        assert_eq!(interpolations, vec![
+            ("user.name", "{{ user.name }}", 4),
+            ("greet('}}')", "{{ greet('}}') }}", 4),
+            ("count > 1 ? 'items' : 'item'", "{{ count > 1 ? 'items' : 'item' }}", 4),
+        ]);
+
+        assert_eq!(count_pipes("orders$ | async | slice:0:3"), 2);
+        assert_eq!(count_pipes("a || b"), 0);
+        assert_eq!(count_pipes("'a|b' | uppercase"), 1);
+    }
+}
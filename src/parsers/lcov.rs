@@ -0,0 +1,121 @@
+use crate::ast::NgProject;
+use crate::config::relative_issue_path;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileCoverage {
+    pub file_path: String,
+    pub lines_found: u32,
+    pub lines_hit: u32,
+}
+
+impl FileCoverage {
+    pub fn percentage(&self) -> f64 {
+        if self.lines_found == 0 {
+            100.0
+        } else {
+            self.lines_hit as f64 * 100.0 / self.lines_found as f64
+        }
+    }
+}
+
+pub fn read_lcov(path: &Path) -> Result<Vec<FileCoverage>> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read coverage file: {}", path.display()))?;
+    Ok(parse_lcov(&content))
+}
+
+pub fn parse_lcov(content: &str) -> Vec<FileCoverage> {
+    let mut records = Vec::new();
+    let mut current: Option<FileCoverage> = None;
+    // LF/LH がない出力もあるので DA 行からも数える
+    let mut da_found = 0;
+    let mut da_hit = 0;
+
+    for line in content.lines().map(str::trim) {
+        if let Some(path) = line.strip_prefix("SF:") {
+            current = Some(FileCoverage {
+                file_path: path.replace('\\', "/"),
+                lines_found: 0,
+                lines_hit: 0,
+            });
+            da_found = 0;
+            da_hit = 0;
+        } else if let Some(data) = line.strip_prefix("DA:") {
+            da_found += 1;
+            let hits = data.split(',').nth(1).and_then(|h| h.parse::<u64>().ok()).unwrap_or(0);
+            if hits > 0 {
+                da_hit += 1;
+            }
+        } else if let Some(found) = line.strip_prefix("LF:") {
+            if let Some(record) = current.as_mut() {
+                record.lines_found = found.parse().unwrap_or(0);
+            }
+        } else if let Some(hit) = line.strip_prefix("LH:") {
+            if let Some(record) = current.as_mut() {
+                record.lines_hit = hit.parse().unwrap_or(0);
+            }
+        } else if line == "end_of_record" {
+            if let Some(mut record) = current.take() {
+                if record.lines_found == 0 {
+                    record.lines_found = da_found;
+                    record.lines_hit = da_hit;
+                }
+                records.push(record);
+            }
+        }
+    }
+
+    records
+}
+
+// lcov の SF はプロジェクトルートからの相対パスだったり絶対パスだったりするので末尾一致で対応付ける
+fn find_record<'a>(records: &'a [FileCoverage], file_path: &str, root_path: &Path) -> Option<&'a FileCoverage> {
+    let relative = relative_issue_path(file_path, root_path);
+    let relative = relative.trim_start_matches("./");
+    if relative.is_empty() {
+        return None;
+    }
+
+    records.iter().find(|record| {
+        let sf = record.file_path.trim_start_matches("./");
+        sf == relative || sf == file_path || sf.ends_with(&format!("/{}", relative)) || file_path.ends_with(&format!("/{}", sf))
+    })
+}
+
+pub fn apply_coverage(project: &mut NgProject, records: &[FileCoverage]) {
+    let root_path = project.root_path.clone();
+
+    for component in &mut project.components {
+        component.test_coverage = find_record(records, &component.file_path, &root_path).map(FileCoverage::percentage);
+    }
+    for service in &mut project.services {
+        service.test_coverage = find_record(records, &service.file_path, &root_path).map(FileCoverage::percentage);
+    }
+
+    let lines_found: u32 = records.iter().map(|r| r.lines_found).sum();
+    let lines_hit: u32 = records.iter().map(|r| r.lines_hit).sum();
+    project.test_coverage = Some(if lines_found == 0 {
+        0.0
+    } else {
+        lines_hit as f64 * 100.0 / lines_found as f64
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lcov() {
+        let content = "TN:\nSF:src/app/a.component.ts\nDA:1,3\nDA:2,0\nLF:4\nLH:3\nend_of_record\nSF:src/app/b.service.ts\nDA:1,1\nDA:2,0\nend_of_record\n";
+        let records = parse_lcov(content);
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].percentage(), 75.0);
+        assert_eq!(records[1].lines_found, 2);
+        assert_eq!(records[1].lines_hit, 1);
+    }
+}
@@ -1,5 +1,14 @@
+pub mod encoding;
+pub mod entry_points;
 pub mod html;
+pub mod lcov;
+pub mod packages;
 pub mod project;
+pub mod source_cache;
+pub mod styles;
+pub mod tokens;
+pub mod tsconfig;
 pub mod typescript;
+pub mod walk;
 
 pub use project::ProjectParser;
\ No newline at end of file
@@ -0,0 +1,263 @@
+use crate::ast::{DeclaredDependency, InstalledPackage, PackageManifest, WorkspacePackages};
+use ignore::WalkBuilder;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DEPENDENCY_FIELDS: [&str; 3] = ["dependencies", "devDependencies", "optionalDependencies"];
+
+enum Lockfile {
+    // package-lock.json の node_modules/... パス -> バージョン
+    Npm(HashMap<String, String>),
+    // yarn.lock の "name@range" -> バージョン
+    Yarn(HashMap<String, String>),
+}
+
+// 解析対象より上の階層も含めてロックファイルを探し、ワークスペース全体の package.json を読む
+pub fn load_workspace(root_path: &Path) -> Option<WorkspacePackages> {
+    let (workspace_root, lockfile_name) = find_workspace_root(root_path)?;
+
+    let lockfile = lockfile_name.as_deref().and_then(|name| {
+        let content = fs::read_to_string(workspace_root.join(name)).ok()?;
+        if name == "yarn.lock" {
+            Some(Lockfile::Yarn(parse_yarn_lock(&content)))
+        } else {
+            parse_package_lock(&content).map(Lockfile::Npm)
+        }
+    });
+
+    let mut manifests: Vec<PackageManifest> = WalkBuilder::new(&workspace_root)
+        .filter_entry(|entry| entry.file_name() != "node_modules")
+        .build()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name() == "package.json" && entry.path().is_file())
+        .filter_map(|entry| {
+            let content = fs::read_to_string(entry.path()).ok()?;
+            let relative = entry.path().strip_prefix(&workspace_root).unwrap_or(entry.path());
+            parse_manifest(&content, &relative.display().to_string().replace('\\', "/"))
+        })
+        .collect();
+    manifests.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+
+    let mut installed = Vec::new();
+    if let Some(lockfile) = &lockfile {
+        for manifest in &mut manifests {
+            for dependency in &mut manifest.dependencies {
+                dependency.resolved = resolve(lockfile, &manifest.file_path, dependency);
+            }
+        }
+        // レポートが肥大化しないよう、いずれかの package.json が直接依存するものだけ残す
+        installed = installed_packages(lockfile).into_iter()
+            .filter(|package| manifests.iter().any(|m| m.dependencies.iter().any(|d| d.name == package.name)))
+            .collect();
+    }
+
+    Some(WorkspacePackages {
+        root_path: workspace_root,
+        lockfile: lockfile_name,
+        manifests,
+        installed,
+    })
+}
+
+fn find_workspace_root(root_path: &Path) -> Option<(PathBuf, Option<String>)> {
+    // 相対パスのまま辿れる範囲を優先し、報告されるパスを短く保つ
+    let mut candidates: Vec<PathBuf> = root_path.ancestors()
+        .map(|dir| if dir.as_os_str().is_empty() { PathBuf::from(".") } else { dir.to_path_buf() })
+        .collect();
+    if let Ok(canonical) = fs::canonicalize(root_path) {
+        candidates.extend(canonical.ancestors().map(Path::to_path_buf));
+    }
+
+    for dir in &candidates {
+        for name in ["package-lock.json", "yarn.lock"] {
+            if dir.join(name).is_file() {
+                return Some((dir.clone(), Some(name.to_string())));
+            }
+        }
+    }
+
+    candidates.into_iter()
+        .find(|dir| dir.join("package.json").is_file())
+        .map(|dir| (dir, None))
+}
+
+fn parse_manifest(content: &str, file_path: &str) -> Option<PackageManifest> {
+    let json: Value = serde_json::from_str(content).ok()?;
+    let mut dependencies = Vec::new();
+
+    for field in DEPENDENCY_FIELDS {
+        let Some(entries) = json.get(field).and_then(Value::as_object) else { continue };
+        for (name, range) in entries {
+            let Some(range) = range.as_str() else { continue };
+            // ワークスペース内のパッケージへのリンクはバージョンを持たない
+            if ["workspace:", "file:", "link:"].iter().any(|prefix| range.starts_with(prefix)) {
+                continue;
+            }
+            if dependencies.iter().any(|d: &DeclaredDependency| &d.name == name) {
+                continue;
+            }
+            dependencies.push(DeclaredDependency {
+                name: name.clone(),
+                range: range.to_string(),
+                resolved: None,
+            });
+        }
+    }
+
+    Some(PackageManifest {
+        file_path: file_path.to_string(),
+        name: json.get("name").and_then(Value::as_str).map(String::from),
+        dependencies,
+    })
+}
+
+fn parse_package_lock(content: &str) -> Option<HashMap<String, String>> {
+    let json: Value = serde_json::from_str(content).ok()?;
+    let mut versions = HashMap::new();
+
+    // lockfileVersion 2/3
+    if let Some(packages) = json.get("packages").and_then(Value::as_object) {
+        for (location, entry) in packages {
+            if !location.contains("node_modules/") || entry.get("link").and_then(Value::as_bool) == Some(true) {
+                continue;
+            }
+            if let Some(version) = entry.get("version").and_then(Value::as_str) {
+                versions.insert(location.clone(), version.to_string());
+            }
+        }
+        return Some(versions);
+    }
+
+    // lockfileVersion 1 は依存関係がネストしている
+    fn collect(dependencies: &serde_json::Map<String, Value>, prefix: &str, versions: &mut HashMap<String, String>) {
+        for (name, entry) in dependencies {
+            let location = format!("{}node_modules/{}", prefix, name);
+            if let Some(version) = entry.get("version").and_then(Value::as_str) {
+                versions.insert(location.clone(), version.to_string());
+            }
+            if let Some(nested) = entry.get("dependencies").and_then(Value::as_object) {
+                collect(nested, &format!("{}/", location), versions);
+            }
+        }
+    }
+    if let Some(dependencies) = json.get("dependencies").and_then(Value::as_object) {
+        collect(dependencies, "", &mut versions);
+    }
+    Some(versions)
+}
+
+// yarn v1 と berry の両方の書式に対応する
+fn parse_yarn_lock(content: &str) -> HashMap<String, String> {
+    let mut versions = HashMap::new();
+    let mut specs: Vec<String> = Vec::new();
+
+    for line in content.lines() {
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with(' ') {
+            specs = line.trim_end_matches(':')
+                .split(", ")
+                .map(|spec| spec.trim().trim_matches('"').replacen("@npm:", "@", 1))
+                .collect();
+        } else if let Some(version) = line.trim().strip_prefix("version") {
+            let version = version.trim_start_matches(':').trim().trim_matches('"');
+            for spec in specs.drain(..) {
+                versions.insert(spec, version.to_string());
+            }
+        }
+    }
+
+    versions
+}
+
+// node_modules/a/node_modules/@scope/b -> @scope/b
+fn package_name(location: &str) -> &str {
+    location.rsplit_once("node_modules/").map(|(_, name)| name).unwrap_or(location)
+}
+
+fn resolve(lockfile: &Lockfile, manifest_path: &str, dependency: &DeclaredDependency) -> Option<String> {
+    match lockfile {
+        Lockfile::Yarn(versions) => versions.get(&format!("{}@{}", dependency.name, dependency.range)).cloned(),
+        Lockfile::Npm(versions) => {
+            // Node のモジュール解決と同じく、package.json の場所から上の node_modules を順に探す
+            let mut dir = Path::new(manifest_path).parent();
+            while let Some(current) = dir {
+                let prefix = current.display().to_string().replace('\\', "/");
+                let location = if prefix.is_empty() {
+                    format!("node_modules/{}", dependency.name)
+                } else {
+                    format!("{}/node_modules/{}", prefix, dependency.name)
+                };
+                if let Some(version) = versions.get(&location) {
+                    return Some(version.clone());
+                }
+                dir = current.parent();
+            }
+            None
+        }
+    }
+}
+
+fn installed_packages(lockfile: &Lockfile) -> Vec<InstalledPackage> {
+    let mut installed: Vec<InstalledPackage> = match lockfile {
+        Lockfile::Npm(versions) => versions.iter()
+            .map(|(location, version)| InstalledPackage {
+                name: package_name(location).to_string(),
+                version: version.clone(),
+                location: location.clone(),
+            })
+            .collect(),
+        Lockfile::Yarn(versions) => {
+            let mut by_version: HashMap<(String, String), Vec<String>> = HashMap::new();
+            for (spec, version) in versions {
+                let Some((name, _)) = spec.rsplit_once('@').filter(|(name, _)| !name.is_empty()) else { continue };
+                by_version.entry((name.to_string(), version.clone())).or_default().push(spec.clone());
+            }
+            by_version.into_iter()
+                .map(|((name, version), mut specs)| {
+                    specs.sort();
+                    InstalledPackage { name, version, location: specs.join(", ") }
+                })
+                .collect()
+        }
+    };
+    installed.sort_by(|a, b| (&a.name, &a.location).cmp(&(&b.name, &b.location)));
+    installed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_lockfiles() {
+        let package_lock = r#"{
+            "lockfileVersion": 3,
+            "packages": {
+                "": { "name": "workspace" },
+                "node_modules/rxjs": { "version": "7.8.1" },
+                "node_modules/shop": { "resolved": "apps/shop", "link": true },
+                "apps/legacy/node_modules/rxjs": { "version": "6.6.7" },
+                "node_modules/@scope/lib/node_modules/rxjs": { "version": "6.5.0" }
+            }
+        }"#;
+        let lockfile = Lockfile::Npm(parse_package_lock(package_lock).unwrap());
+        let dependency = DeclaredDependency { name: "rxjs".to_string(), range: "^6.6.0".to_string(), resolved: None };
+
+        assert_eq!(resolve(&lockfile, "apps/legacy/package.json", &dependency).as_deref(), Some("6.6.7"));
+        assert_eq!(resolve(&lockfile, "apps/shop/package.json", &dependency).as_deref(), Some("7.8.1"));
+        assert_eq!(installed_packages(&lockfile).iter().filter(|p| p.name == "rxjs").count(), 3);
+
+        let yarn_lock = "# yarn lockfile v1\n\n\"rxjs@^6.5.0\", rxjs@^6.6.0:\n  version \"6.6.7\"\n\n\"@angular/core@npm:^17.0.0\":\n  version: 17.3.0\n";
+        let lockfile = Lockfile::Yarn(parse_yarn_lock(yarn_lock));
+
+        assert_eq!(resolve(&lockfile, "package.json", &dependency).as_deref(), Some("6.6.7"));
+        let installed = installed_packages(&lockfile);
+        assert_eq!(installed.len(), 2);
+        assert_eq!(installed[0].name, "@angular/core");
+        assert_eq!(installed[0].version, "17.3.0");
+    }
+}
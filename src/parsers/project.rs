@@ -1,82 +1,467 @@
-use crate::ast::NgProject;
-use crate::parsers::typescript::TypeScriptParser;
+use crate::ast::{EncodingWarning, LineCounts, NgProject, SelectionChain, SourceFile, Stylesheet, TemplateControlFlow, TemplateUsage, ElementBindings};
+use crate::analyzers::dependency_graph::DependencyGraphAnalyzer;
+use crate::output::progress::Progress;
+use crate::parsers::entry_points::workspace_resources;
+use crate::parsers::html::{is_class_name, BindingKind, HtmlParser, TemplateAnalysis};
+use crate::parsers::packages;
+use crate::parsers::source_cache::{Source, SourceCache};
+use crate::parsers::styles;
+use crate::parsers::typescript::{element_type, resolve_relative_path, TypeScriptParser};
+use crate::parsers::walk::{walk_files, WalkOptions};
 use anyhow::Result;
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
 use std::path::PathBuf;
-use std::fs;
-use ignore::WalkBuilder;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use swc_ecma_ast::Module;
+
+// TypeScript 以外に読み込む入力。使うルールが実行されないときは読み込みを省く
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectInputs {
+    pub templates: bool,
+    pub resource_files: bool,
+    pub stylesheets: bool,
+    pub import_graph: bool,
+}
+
+impl Default for ProjectInputs {
+    fn default() -> Self {
+        Self {
+            templates: true,
+            resource_files: true,
+            stylesheets: true,
+            import_graph: true,
+        }
+    }
+}
+
+// --timing で表示する、実際に読み込んだ入力の数
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoadedInputs {
+    pub typescript_files: usize,
+    pub templates: usize,
+    pub resource_files: usize,
+    pub stylesheets: usize,
+    pub import_graph_files: usize,
+}
 
 pub struct ProjectParser {
     typescript_parser: TypeScriptParser,
+    html_parser: HtmlParser,
+    inputs: ProjectInputs,
+    walk: WalkOptions,
+    loaded_templates: AtomicUsize,
+    loaded_stylesheets: AtomicUsize,
+    encoding_warnings: Mutex<Vec<EncodingWarning>>,
+    progress: Arc<Progress>,
+    sources: Arc<SourceCache>,
 }
 
 impl ProjectParser {
     pub fn new() -> Self {
         Self {
             typescript_parser: TypeScriptParser::new(),
+            html_parser: HtmlParser::new(),
+            inputs: ProjectInputs::default(),
+            walk: WalkOptions::default(),
+            loaded_templates: AtomicUsize::new(0),
+            loaded_stylesheets: AtomicUsize::new(0),
+            encoding_warnings: Mutex::new(Vec::new()),
+            progress: Arc::new(Progress::hidden()),
+            sources: Arc::new(SourceCache::new()),
+        }
+    }
+
+    pub fn with_inputs(mut self, inputs: ProjectInputs) -> Self {
+        self.inputs = inputs;
+        self
+    }
+
+    pub fn with_walk_options(mut self, walk: WalkOptions) -> Self {
+        self.walk = walk;
+        self
+    }
+
+    pub fn with_progress(mut self, progress: Arc<Progress>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    // search の絞り込みなど、すでに読んだファイルを使い回すとき
+    pub fn with_source_cache(mut self, sources: Arc<SourceCache>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    pub fn inputs(&self) -> ProjectInputs {
+        self.inputs
+    }
+
+    pub fn loaded_inputs(&self, project: &NgProject) -> LoadedInputs {
+        LoadedInputs {
+            typescript_files: project.files.len(),
+            templates: self.loaded_templates.load(Ordering::Relaxed),
+            resource_files: project.resource_files.len(),
+            stylesheets: self.loaded_stylesheets.load(Ordering::Relaxed),
+            import_graph_files: project.import_graph.as_ref().map_or(0, |graph| graph.files.len()),
         }
     }
 
     pub async fn parse_project(&self, root_path: &PathBuf) -> Result<NgProject> {
         let mut project = NgProject {
             root_path: root_path.clone(),
+            sources: self.sources.clone(),
             ..Default::default()
         };
 
-        let walker = WalkBuilder::new(root_path)
-            .add_custom_ignore_filename(".ngignore")
-            .hidden(false)
-            .git_ignore(true)
-            .build();
-
-        for entry in walker {
-            let entry = entry?;
-            let path = entry.path();
-            
-            if path.is_file() {
-                if let Some(extension) = path.extension() {
-                    match extension.to_str() {
-                        Some("ts") => {
-                            if path.to_string_lossy().contains(".component.") {
-                                if let Some(component) = self.parse_component_file(path).await? {
-                                    project.components.push(component);
-                                }
-                            } else if path.to_string_lossy().contains(".service.") {
-                                if let Some(service) = self.parse_service_file(path).await? {
-                                    project.services.push(service);
-                                }
-                            } else if path.to_string_lossy().contains(".module.") {
-                                if let Some(module) = self.parse_module_file(path).await? {
-                                    project.modules.push(module);
-                                }
-                            }
-                        }
-                        _ => {}
+        let mut project_files = Vec::new();
+        let paths = walk_files(root_path, &self.walk)?;
+        let typescript_files: Vec<PathBuf> = paths.iter()
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("ts"))
+            .cloned()
+            .collect();
+        self.sources.preload(&typescript_files).await;
+        self.progress.start("progress.parsing", paths.len());
+        for path in paths {
+            let path = path.as_path();
+            self.progress.inc(&path.display().to_string());
+            if path.file_name().is_some_and(|name| name == "project.json") {
+                project_files.push(path.to_path_buf());
+            }
+
+            if path.is_file() && matches!(path.extension().and_then(|ext| ext.to_str()), Some("html" | "css" | "scss" | "sass" | "less")) {
+                if self.inputs.resource_files {
+                    project.resource_files.push(path.display().to_string().replace('\\', "/"));
+                }
+                continue;
+            }
+
+            if path.is_file() && path.extension().and_then(|ext| ext.to_str()) == Some("ts") {
+                let content = self.read_file(path)?;
+                let lines = count_lines(&content);
+                project.files.push(SourceFile {
+                    file_path: path.display().to_string().replace('\\', "/"),
+                    lines,
+                    minified: is_minified(&content),
+                });
+
+                let has_lazy_routes = content.contains("loadChildren") || content.contains("loadComponent");
+                let has_render_roots = content.contains("bootstrapApplication") || content.contains("component:") || content.contains("loadComponent");
+                let has_provider_overrides = content.contains("provide:");
+                let has_providers = has_provider_overrides || content.contains("providers") || content.contains("withInterceptors");
+                if (has_lazy_routes || has_render_roots || has_providers) && !path.to_string_lossy().ends_with(".spec.ts") {
+                    let module = self.parse_module(path)?;
+                    if has_provider_overrides {
+                        project.provider_overrides.extend(self.typescript_parser.extract_provider_overrides(&module, &content, path));
+                    }
+                    if has_providers {
+                        project.provider_registrations.extend(self.typescript_parser.extract_provider_registrations(&module, &content, path));
+                    }
+                    if has_lazy_routes {
+                        project.lazy_routes.extend(self.typescript_parser.extract_lazy_routes(&module, &content, path));
+                    }
+                    if has_render_roots {
+                        let (bootstrap, routed, parameterized) = self.typescript_parser.extract_render_roots(&module);
+                        project.bootstrap_components.extend(bootstrap);
+                        project.routed_components.extend(routed);
+                        project.parameterized_routes.extend(parameterized);
+                    }
+                }
+
+                if content.contains("class ") && !path.to_string_lossy().ends_with(".spec.ts") {
+                    let module = self.parse_module(path)?;
+                    project.classes.extend(self.typescript_parser.extract_classes(&module, &content, path));
+                }
+
+                if path.to_string_lossy().ends_with(".spec.ts") {
+                    project.specs.push(self.parse_spec_file(path).await?);
+                } else if path.to_string_lossy().contains(".component.") {
+                    if let Some(mut component) = self.parse_component_file(path, &content).await? {
+                        component.lines = lines;
+                        project.components.push(component);
+                    }
+                } else if path.to_string_lossy().contains(".service.") {
+                    if let Some(mut service) = self.parse_service_file(path).await? {
+                        service.lines = lines;
+                        project.services.push(service);
+                    }
+                } else if path.to_string_lossy().contains(".guard.") || path.to_string_lossy().contains(".resolver.") {
+                    project.guards.extend(self.parse_guard_file(path, &content).await?);
+                } else if path.to_string_lossy().contains(".pipe.") {
+                    project.pipes.extend(self.parse_pipe_file(path, &content).await?);
+                } else if path.to_string_lossy().contains(".module.") {
+                    if let Some(module) = self.parse_module_file(path, &content).await? {
+                        project.modules.push(module);
                     }
                 }
             }
         }
 
+        // 継承に関わらないクラスは落とす
+        let base_classes: HashSet<String> = project.classes.iter()
+            .filter_map(|class| class.heritage.base_class.clone())
+            .collect();
+        project.classes.retain(|class| class.heritage.extends() || base_classes.contains(&class.name));
+
+        if self.inputs.resource_files {
+            project.referenced_resources = self.referenced_resources(root_path, &project.resource_files, &project_files);
+        }
+        project.workspace_packages = packages::load_workspace(root_path);
+        if self.inputs.import_graph {
+            self.progress.start("progress.import_graph", 0);
+            project.import_graph = Some(DependencyGraphAnalyzer::new()
+                .with_walk_options(self.walk.clone())
+                .with_source_cache(self.sources.clone())
+                .analyze_project(root_path).await?);
+        }
+        project.encoding_warnings = std::mem::take(&mut *self.encoding_warnings.lock().unwrap());
+        self.progress.finish();
+
         Ok(project)
     }
 
-    async fn parse_component_file(&self, file_path: &std::path::Path) -> Result<Option<crate::ast::NgComponent>> {
-        let content = fs::read_to_string(file_path)?;
-        let _module = self.typescript_parser.parse_file(&content)?;
-        
-        self.typescript_parser.extract_component(&_module, &file_path.to_path_buf())
+    async fn parse_component_file(&self, file_path: &std::path::Path, content: &str) -> Result<Option<crate::ast::NgComponent>> {
+        let module = self.parse_module(file_path)?;
+
+        let Some(mut component) = self.typescript_parser.extract_component(&module, file_path)? else {
+            return Ok(None);
+        };
+        component.selection_chains = self.typescript_parser.extract_selection_chains(&module, content, file_path);
+        if self.inputs.stylesheets {
+            component.stylesheets = self.load_stylesheets(&component, file_path, content);
+            self.loaded_stylesheets.fetch_add(component.stylesheets.len(), Ordering::Relaxed);
+        }
+        if !self.inputs.templates {
+            return Ok(Some(component));
+        }
+        if let Some((template, template_path)) = self.load_template(&component, file_path) {
+            self.loaded_templates.fetch_add(1, Ordering::Relaxed);
+            component.selection_chains.extend(self.template_selection_chains(&component, &template, &template_path, content));
+            component.template_usages = self.html_parser.find_element_usages(&template).into_iter()
+                .map(|(tag, kind, count)| TemplateUsage { tag, kind, count })
+                .collect();
+            component.element_bindings = self.html_parser.find_element_bindings(&template).into_iter()
+                .map(|(tag, inputs, outputs)| ElementBindings { tag, inputs, outputs })
+                .collect();
+            // ViewEncapsulation.None のスタイルは他のコンポーネントにも当たるので照合しない
+            let has_rules = component.stylesheets.iter().any(|stylesheet| !stylesheet.rules.is_empty());
+            if has_rules && !content.contains("ViewEncapsulation.None") {
+                component.used_classes = Some(self.used_classes(&template, content));
+            }
+            let analysis = self.html_parser.parse_template(&template);
+            component.template_complexity = Some(analysis.complexity.clone());
+            let mut control_flow = self.template_control_flow(&component, analysis, &template_path, content);
+            let property_types = self.typescript_parser.extract_property_types(&module);
+            for found in &mut control_flow.loops {
+                found.item_type = found.property()
+                    .and_then(|property| property_types.iter().find(|(name, _)| name == property))
+                    .and_then(|(_, type_text)| element_type(type_text));
+            }
+            component.control_flow = Some(control_flow);
+        }
+        Ok(Some(component))
+    }
+
+    // ワークスペースの設定が読み込むファイルと assets のディレクトリ、スタイルの @import / @use 先
+    fn referenced_resources(&self, root_path: &std::path::Path, resource_files: &[String], project_files: &[PathBuf]) -> Vec<String> {
+        let style_import = Regex::new(r#"@(?:import|use|forward)\s+(?:url\()?['"]([^'"]+)['"]"#).unwrap();
+        let globals = workspace_resources(root_path, project_files);
+
+        let mut imported: HashSet<PathBuf> = HashSet::new();
+        for resource in resource_files.iter().filter(|resource| !resource.ends_with(".html")) {
+            let path = std::path::Path::new(resource);
+            let Ok(content) = std::fs::read_to_string(path) else { continue };
+            let dir = path.parent().unwrap_or(std::path::Path::new(""));
+            for caps in style_import.captures_iter(&content) {
+                // Sass は拡張子と先頭の _ を省略できる
+                let target = dir.join(&caps[1]);
+                let file_name = target.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+                let candidates = ["", ".scss", ".sass", ".css", ".less"].iter()
+                    .flat_map(|extension| [
+                        target.with_file_name(format!("{}{}", file_name, extension)),
+                        target.with_file_name(format!("_{}{}", file_name, extension)),
+                    ]);
+                imported.extend(candidates.filter_map(|candidate| std::fs::canonicalize(candidate).ok()));
+            }
+        }
+
+        resource_files.iter()
+            .filter(|resource| {
+                let Ok(path) = std::fs::canonicalize(resource) else { return false };
+                imported.contains(&path) || globals.iter().any(|global| path.starts_with(global))
+            })
+            .cloned()
+            .collect()
+    }
+
+    // UTF-8 以外で書かれたファイルも変換して読み、警告として記録する
+    fn read_file(&self, path: &std::path::Path) -> Result<Arc<str>> {
+        let Source { content, encoding } = self.sources.read(path)?;
+        if let Some(encoding) = encoding {
+            self.encoding_warnings.lock().unwrap().push(EncodingWarning {
+                file_path: path.display().to_string().replace('\\', "/"),
+                encoding,
+            });
+        }
+        Ok(content)
+    }
+
+    // 同じファイルを ProjectParser の中と import グラフで何度も解析しないよう、構文木はキャッシュから取る
+    fn parse_module(&self, path: &std::path::Path) -> Result<Arc<Module>> {
+        Ok(self.sources.module(path, |content| self.typescript_parser.parse_file(content))?.1)
+    }
+
+    // インラインテンプレートまたは templateUrl の HTML と、そのパス
+    fn load_template(&self, component: &crate::ast::NgComponent, file_path: &std::path::Path) -> Option<(String, String)> {
+        match (&component.template, &component.template_url) {
+            (Some(template), _) => Some((template.clone(), component.file_path.clone())),
+            (None, Some(url)) => {
+                let path = file_path.parent().map(|dir| dir.join(url)).unwrap_or_else(|| PathBuf::from(url));
+                let template = self.read_file(&path).ok()?;
+                Some((template.to_string(), path.display().to_string().replace('\\', "/")))
+            }
+            (None, None) => None,
+        }
+    }
+
+    // styleUrls のファイルとインラインの styles。インラインの ::ng-deep とルールの行番号はコンポーネントの .ts の行番号にする
+    fn load_stylesheets(&self, component: &crate::ast::NgComponent, file_path: &std::path::Path, content: &str) -> Vec<Stylesheet> {
+        let mut stylesheets: Vec<Stylesheet> = component.style_urls.iter()
+            .filter_map(|url| {
+                let path = resolve_relative_path(file_path, url);
+                let style = self.read_file(std::path::Path::new(&path)).ok()?;
+                Some(styles::parse_stylesheet(&style, &path, false))
+            })
+            .collect();
+
+        for style in &component.inline_styles {
+            let mut stylesheet = styles::parse_stylesheet(style, &component.file_path, true);
+            let line_offset = content.find(style.as_str()).map(|start| content[..start].matches('\n').count() as u32);
+            for line in &mut stylesheet.ng_deep_lines {
+                *line += line_offset.unwrap_or(0);
+            }
+            for rule in &mut stylesheet.rules {
+                rule.line += line_offset.unwrap_or(0);
+            }
+            stylesheets.push(stylesheet);
+        }
+
+        stylesheets
+    }
+
+    // テンプレートのクラスに、.ts の文字列リテラル（host の class や classList.add など）を足す
+    fn used_classes(&self, template: &str, content: &str) -> Vec<String> {
+        let literal = Regex::new(r#"'([^'\n]*)'|"([^"\n]*)"|`([^`]*)`"#).unwrap();
+        let host_binding = Regex::new(r"\[class\.([\w-]+)\]").unwrap();
+
+        let mut classes = self.html_parser.find_used_classes(template);
+        let tokens = literal.captures_iter(content)
+            .flat_map(|caps| {
+                let text = caps.get(1).or(caps.get(2)).or(caps.get(3)).map_or("", |m| m.as_str());
+                text.split_whitespace().map(String::from).collect::<Vec<_>>()
+            })
+            .chain(host_binding.captures_iter(content).map(|caps| caps[1].to_string()));
+        for token in tokens {
+            if is_class_name(&token) && !classes.contains(&token) {
+                classes.push(token);
+            }
+        }
+        classes
+    }
+
+    // @if / @for などのブロックと *ngIf などの構造ディレクティブ
+    fn template_control_flow(&self, component: &crate::ast::NgComponent, analysis: TemplateAnalysis, template_path: &str, content: &str) -> TemplateControlFlow {
+        let line_offset = template_line_offset(component, content).unwrap_or(0);
+
+        let mut structural_directives = BTreeMap::new();
+        for directive in analysis.bindings(BindingKind::Structural) {
+            if STRUCTURAL_DIRECTIVES.contains(&directive.name.as_str()) {
+                *structural_directives.entry(format!("*{}", directive.name)).or_insert(0) += 1;
+            }
+        }
+
+        TemplateControlFlow {
+            file_path: template_path.to_string(),
+            blocks: analysis.control_flow.into_iter()
+                .map(|mut block| {
+                    block.line += line_offset;
+                    block
+                })
+                .collect(),
+            structural_directives,
+            start_line: line_offset + 1,
+            elements: analysis.element_positions.into_iter()
+                .map(|mut element| {
+                    element.line += line_offset;
+                    element
+                })
+                .collect(),
+            pipes: analysis.pipes.into_iter()
+                .map(|mut pipe| {
+                    pipe.line += line_offset;
+                    pipe
+                })
+                .collect(),
+            calls: analysis.calls.into_iter()
+                .map(|mut call| {
+                    call.line += line_offset;
+                    call
+                })
+                .collect(),
+            loops: analysis.loops.into_iter()
+                .map(|mut found| {
+                    found.line += line_offset;
+                    found
+                })
+                .collect(),
+        }
+    }
+
+    // テンプレート内で子コンポーネントを切り替える分岐を拾う
+    fn template_selection_chains(&self, component: &crate::ast::NgComponent, template: &str, template_path: &str, content: &str) -> Vec<SelectionChain> {
+        let line_offset = template_line_offset(component, content);
+        self.html_parser.find_selection_chains(template).into_iter()
+            .map(|(kind, discriminator, branches, line)| SelectionChain {
+                kind,
+                discriminator,
+                branches,
+                file_path: template_path.to_string(),
+                line: line_offset.map(|offset| offset + line),
+            })
+            .collect()
+    }
+
+    async fn parse_spec_file(&self, file_path: &std::path::Path) -> Result<crate::ast::SpecFile> {
+        let module = self.parse_module(file_path)?;
+
+        Ok(self.typescript_parser.extract_spec(&module, file_path))
     }
 
     async fn parse_service_file(&self, file_path: &std::path::Path) -> Result<Option<crate::ast::NgService>> {
-        let content = fs::read_to_string(file_path)?;
-        let _module = self.typescript_parser.parse_file(&content)?;
-        
-        self.typescript_parser.extract_service(&_module, &file_path.to_path_buf())
+        let _module = self.parse_module(file_path)?;
+
+        self.typescript_parser.extract_service(&_module, file_path)
+    }
+
+    async fn parse_guard_file(&self, file_path: &std::path::Path, content: &str) -> Result<Vec<crate::ast::NgGuard>> {
+        let module = self.parse_module(file_path)?;
+
+        Ok(self.typescript_parser.extract_guards(&module, content, file_path))
+    }
+
+    async fn parse_pipe_file(&self, file_path: &std::path::Path, content: &str) -> Result<Vec<crate::ast::NgPipe>> {
+        let module = self.parse_module(file_path)?;
+
+        Ok(self.typescript_parser.extract_pipes(&module, content, file_path))
     }
 
-    async fn parse_module_file(&self, file_path: &std::path::Path) -> Result<Option<crate::ast::NgModule>> {
-        let content = fs::read_to_string(file_path)?;
-        let _module = self.typescript_parser.parse_file(&content)?;
-        
+    async fn parse_module_file(&self, file_path: &std::path::Path, content: &str) -> Result<Option<crate::ast::NgModule>> {
+        let module = self.parse_module(file_path)?;
+        if let Some(ng_module) = self.typescript_parser.extract_module(&module, content, file_path) {
+            return Ok(Some(ng_module));
+        }
+
         Ok(Some(crate::ast::NgModule {
             name: file_path.file_stem()
                 .and_then(|s| s.to_str())
@@ -88,6 +473,82 @@ impl ProjectParser {
             declarations: Vec::new(),
             providers: Vec::new(),
             bootstrap: Vec::new(),
+            for_root_imports: Vec::new(),
         }))
     }
-}
\ No newline at end of file
+}
+
+const STRUCTURAL_DIRECTIVES: [&str; 4] = ["ngIf", "ngFor", "ngSwitchCase", "ngSwitchDefault"];
+
+// インラインテンプレートは .ts ファイル内の開始行を足して行番号を合わせる
+fn template_line_offset(component: &crate::ast::NgComponent, content: &str) -> Option<u32> {
+    match &component.template {
+        Some(template) => content.find(template.as_str()).map(|start| content[..start].matches('\n').count() as u32),
+        None => Some(0),
+    }
+}
+
+// 物理行数と、空行・コメントのみの行を除いた論理行数
+// これより長い行を含むファイルは minify 済みとみなす
+pub const MINIFIED_LINE_LENGTH: usize = 1000;
+
+pub fn is_minified(content: &str) -> bool {
+    content.lines().any(|line| line.len() > MINIFIED_LINE_LENGTH && line.chars().count() > MINIFIED_LINE_LENGTH)
+}
+
+pub fn count_lines(content: &str) -> LineCounts {
+    let mut counts = LineCounts::default();
+    let mut in_block_comment = false;
+
+    for line in content.lines() {
+        counts.physical += 1;
+
+        let mut rest = line.trim();
+        let mut has_code = false;
+        while !rest.is_empty() {
+            if in_block_comment {
+                match rest.find("*/") {
+                    Some(end) => {
+                        in_block_comment = false;
+                        rest = rest[end + 2..].trim_start();
+                    }
+                    None => break,
+                }
+            } else if rest.starts_with("//") {
+                break;
+            } else if let Some(after) = rest.strip_prefix("/*") {
+                in_block_comment = true;
+                rest = after;
+            } else {
+                has_code = true;
+                break;
+            }
+        }
+
+        if has_code {
+            counts.logical += 1;
+        }
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_lines() {
+        let content = "import { Component } from '@angular/core';\n\n/**\n * Docs\n */\n// comment\n/* inline */ export class A {}\n  \n}\n";
+        let counts = count_lines(content);
+
+        assert_eq!(counts.physical, 9);
+        assert_eq!(counts.logical, 3);
+    }
+
+    #[test]
+    fn test_is_minified() {
+        assert!(!is_minified("export class A {}\nexport class B {}\n"));
+        assert!(is_minified(&format!("var a={};\n", "1,".repeat(MINIFIED_LINE_LENGTH))));
+    }
+}
@@ -0,0 +1,196 @@
+use crate::parsers::encoding::decode;
+use anyhow::Result;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use swc_ecma_ast::Module;
+use tokio::task::JoinSet;
+
+// 既定のメモリの上限。大きなモノレポでもこれを超えて保持しない
+pub const DEFAULT_CACHE_BYTES: usize = 256 * 1024 * 1024;
+// 構文木はソースの数倍の大きさになるので、この倍率で見積もる
+const MODULE_WEIGHT: usize = 8;
+// preload で同時に読むファイルの数
+const PRELOAD_BATCH: usize = 64;
+
+// 読み込んだソースと、UTF-8 以外から変換したときの形式
+#[derive(Debug, Clone)]
+pub struct Source {
+    pub content: Arc<str>,
+    pub encoding: Option<String>,
+}
+
+struct Entry {
+    source: Source,
+    module: Option<Arc<Module>>,
+}
+
+impl Entry {
+    fn bytes(&self) -> usize {
+        self.source.content.len() * if self.module.is_some() { 1 + MODULE_WEIGHT } else { 1 }
+    }
+}
+
+#[derive(Default)]
+struct Entries {
+    map: HashMap<PathBuf, Entry>,
+    // 追い出す順（古く入れたものから）
+    order: VecDeque<PathBuf>,
+    bytes: usize,
+}
+
+// ProjectParser・DependencyGraphAnalyzer・アナライザーで共有する (search では絞り込みの解析と検索で共有する)、パスごとのソースと構文木。
+// 同じファイルを何度も読んで解析しないようにし、上限を超えたら古いものから捨てる
+pub struct SourceCache {
+    entries: Mutex<Entries>,
+    max_bytes: usize,
+}
+
+impl SourceCache {
+    pub fn new() -> Self {
+        Self::with_max_bytes(DEFAULT_CACHE_BYTES)
+    }
+
+    pub fn with_max_bytes(max_bytes: usize) -> Self {
+        Self { entries: Mutex::new(Entries::default()), max_bytes }
+    }
+
+    // キャッシュになければ読み込んで入れる
+    pub fn read(&self, path: &Path) -> Result<Source> {
+        if let Some(entry) = self.entries.lock().unwrap().map.get(path) {
+            return Ok(entry.source.clone());
+        }
+        let source = to_source(std::fs::read(path)?);
+        self.insert(path, Entry { source: source.clone(), module: None }, true);
+        Ok(source)
+    }
+
+    // 構文木もキャッシュする。parse は TypeScriptParser::parse_file を渡す
+    pub fn module(&self, path: &Path, parse: impl FnOnce(&str) -> Result<Module>) -> Result<(Source, Arc<Module>)> {
+        let source = {
+            let entries = self.entries.lock().unwrap();
+            match entries.map.get(path) {
+                Some(Entry { source, module: Some(module) }) => return Ok((source.clone(), module.clone())),
+                Some(entry) => Some(entry.source.clone()),
+                None => None,
+            }
+        };
+        let source = match source {
+            Some(source) => source,
+            None => to_source(std::fs::read(path)?),
+        };
+        let module = Arc::new(parse(&source.content)?);
+        self.insert(path, Entry { source: source.clone(), module: Some(module.clone()) }, true);
+        Ok((source, module))
+    }
+
+    // 後で読むファイルを、PRELOAD_BATCH 件ずつ並行に読み込んでおく。上限に達したらそこでやめる
+    pub async fn preload(&self, paths: &[PathBuf]) {
+        let pending: Vec<PathBuf> = {
+            let entries = self.entries.lock().unwrap();
+            paths.iter().filter(|path| !entries.map.contains_key(*path)).cloned().collect()
+        };
+        for batch in pending.chunks(PRELOAD_BATCH) {
+            let mut reads = JoinSet::new();
+            for path in batch {
+                let path = path.clone();
+                reads.spawn(async move { (tokio::fs::read(&path).await, path) });
+            }
+            // 読めないファイルはここでは無視し、read で改めてエラーにする
+            while let Some(read) = reads.join_next().await {
+                let Ok((Ok(bytes), path)) = read else { continue };
+                if !self.insert(&path, Entry { source: to_source(bytes), module: None }, false) {
+                    reads.abort_all();
+                    return;
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub fn cached_bytes(&self) -> usize {
+        self.entries.lock().unwrap().bytes
+    }
+
+    // 入り切らなければ入れずに false を返す。evict なら古いものを追い出して場所を空ける
+    fn insert(&self, path: &Path, entry: Entry, evict: bool) -> bool {
+        let size = entry.bytes();
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(previous) = entries.map.remove(path) {
+            entries.bytes -= previous.bytes();
+            entries.order.retain(|cached| cached != path);
+        }
+        if size > self.max_bytes || (!evict && entries.bytes + size > self.max_bytes) {
+            return false;
+        }
+        while entries.bytes + size > self.max_bytes {
+            let Some(oldest) = entries.order.pop_front() else { break };
+            if let Some(evicted) = entries.map.remove(&oldest) {
+                entries.bytes -= evicted.bytes();
+            }
+        }
+        entries.bytes += size;
+        entries.order.push_back(path.to_path_buf());
+        entries.map.insert(path.to_path_buf(), entry);
+        true
+    }
+}
+
+impl Default for SourceCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for SourceCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self.entries.lock().unwrap();
+        f.debug_struct("SourceCache")
+            .field("files", &entries.map.len())
+            .field("bytes", &entries.bytes)
+            .field("max_bytes", &self.max_bytes)
+            .finish()
+    }
+}
+
+fn to_source(bytes: Vec<u8>) -> Source {
+    let (content, encoding) = decode(bytes);
+    Source { content: content.into(), encoding }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::typescript::TypeScriptParser;
+
+    #[tokio::test]
+    async fn test_source_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths: Vec<PathBuf> = (0..3).map(|i| dir.path().join(format!("file{}.ts", i))).collect();
+        for path in &paths {
+            std::fs::write(path, "export const value = 1;\n").unwrap();
+        }
+
+        let cache = SourceCache::new();
+        cache.preload(&paths).await;
+        assert_eq!(cache.cached_bytes(), 3 * 24);
+        // 読み込んだ後にファイルが変わっても、キャッシュの内容を返す
+        std::fs::write(&paths[0], "changed").unwrap();
+        assert_eq!(&*cache.read(&paths[0]).unwrap().content, "export const value = 1;\n");
+
+        let parser = TypeScriptParser::new();
+        let (_, module) = cache.module(&paths[1], |content| parser.parse_file(content)).unwrap();
+        let (_, again) = cache.module(&paths[1], |_| panic!("parsed twice")).unwrap();
+        assert!(Arc::ptr_eq(&module, &again));
+
+        // 上限を超えると古いものから追い出す
+        let small = SourceCache::with_max_bytes(50);
+        small.read(&paths[1]).unwrap();
+        small.read(&paths[2]).unwrap();
+        assert_eq!(small.cached_bytes(), 48);
+        small.read(&paths[0]).unwrap();
+        assert_eq!(small.cached_bytes(), 24 + 7);
+        assert!(small.read(&dir.path().join("missing.ts")).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+use crate::ast::{StyleRule, Stylesheet};
+
+// 中のルールをセレクタとして扱わない at-rule
+const OPAQUE_AT_RULES: [&str; 6] = ["@keyframes", "@font-face", "@mixin", "@function", "@page", "@-webkit-keyframes"];
+
+enum Frame {
+    // セレクタと、宣言の大きさを足していく rules の位置（@keyframes の中などは None）
+    Rule(Vec<String>, Option<usize>),
+    AtRule { opaque: bool },
+}
+
+// CSS / SCSS / Less をおおまかに読み、セレクタと宣言の数、::ng-deep と !important を数える
+pub fn parse_stylesheet(content: &str, file_path: &str, inline: bool) -> Stylesheet {
+    let source = strip_comments(content);
+    let mut stylesheet = Stylesheet {
+        file_path: file_path.to_string(),
+        inline,
+        lines: content.lines().count() as u32,
+        declarations: 0,
+        ng_deep_lines: Vec::new(),
+        important_count: source.matches("!important").count() as u32,
+        selectors: Vec::new(),
+        rules: Vec::new(),
+    };
+
+    for (index, line) in source.lines().enumerate() {
+        if line.contains("::ng-deep") {
+            stylesheet.ng_deep_lines.push(index as u32 + 1);
+        }
+    }
+
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut buffer = String::new();
+    let mut paren_depth = 0u32;
+    let mut line = 1u32;
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+        match c {
+            // SCSS の #{...} は波括弧を含むのでそのまま読み飛ばす
+            '#' if chars.peek() == Some(&'{') => {
+                buffer.push(c);
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        line += 1;
+                    }
+                    buffer.push(next);
+                    if next == '}' {
+                        break;
+                    }
+                }
+            }
+            '(' => {
+                paren_depth += 1;
+                buffer.push(c);
+            }
+            ')' => {
+                paren_depth = paren_depth.saturating_sub(1);
+                buffer.push(c);
+            }
+            '{' if paren_depth == 0 => {
+                let prelude = normalize(&buffer);
+                buffer.clear();
+                if prelude.starts_with('@') {
+                    let opaque = OPAQUE_AT_RULES.iter().any(|rule| prelude.starts_with(rule));
+                    frames.push(Frame::AtRule { opaque });
+                    continue;
+                }
+
+                let parents = frames.iter().rev().find_map(|frame| match frame {
+                    Frame::Rule(selectors, _) => Some(selectors.as_slice()),
+                    Frame::AtRule { .. } => None,
+                });
+                let selectors = combine_selectors(parents.unwrap_or(&[]), &prelude);
+                let opaque = frames.iter().any(|frame| matches!(frame, Frame::AtRule { opaque: true }));
+                let mut rule = None;
+                if !opaque {
+                    for selector in &selectors {
+                        if !stylesheet.selectors.contains(selector) {
+                            stylesheet.selectors.push(selector.clone());
+                        }
+                    }
+                    rule = Some(stylesheet.rules.len());
+                    stylesheet.rules.push(StyleRule { selectors: selectors.clone(), line, bytes: 0 });
+                }
+                frames.push(Frame::Rule(selectors, rule));
+            }
+            ';' if paren_depth == 0 => {
+                count_declaration(&mut stylesheet, &buffer, &frames);
+                buffer.clear();
+            }
+            '}' if paren_depth == 0 => {
+                count_declaration(&mut stylesheet, &buffer, &frames);
+                buffer.clear();
+                // 入れ子のルールだけを持つブロックは出力に残らない
+                if let Some(Frame::Rule(selectors, Some(rule))) = frames.pop() {
+                    let rule = &mut stylesheet.rules[rule];
+                    if rule.bytes > 0 {
+                        rule.bytes += selectors.join(",").len() as u32 + 2;
+                    }
+                }
+            }
+            _ => buffer.push(c),
+        }
+    }
+
+    stylesheet
+}
+
+fn count_declaration(stylesheet: &mut Stylesheet, text: &str, frames: &[Frame]) {
+    let text = text.trim();
+    // @include / @extend や SCSS 変数の定義は宣言に数えない
+    if !frames.is_empty() && text.contains(':') && !text.starts_with('@') && !text.starts_with('$') {
+        stylesheet.declarations += 1;
+        if let Some(Frame::Rule(_, Some(rule))) = frames.last() {
+            stylesheet.rules[*rule].bytes += normalize(text).len() as u32 + 1;
+        }
+    }
+}
+
+// ネストしたセレクタを親と組み合わせる。& は親のセレクタに置き換える
+fn combine_selectors(parents: &[String], prelude: &str) -> Vec<String> {
+    let children = prelude.split(',').map(normalize).filter(|selector| !selector.is_empty());
+    if parents.is_empty() {
+        return children.map(|selector| selector.replace('&', "")).collect();
+    }
+
+    children
+        .flat_map(|child| {
+            parents.iter().map(move |parent| {
+                if child.contains('&') {
+                    child.replace('&', parent)
+                } else {
+                    format!("{} {}", parent, child)
+                }
+            })
+        })
+        .collect()
+}
+
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// /* */ と SCSS の // コメントを空白に置き換える。行番号を保つため改行は残す
+fn strip_comments(content: &str) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut quote: Option<char> = None;
+    let mut paren_depth = 0u32;
+
+    while let Some(c) = chars.next() {
+        if let Some(q) = quote {
+            result.push(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    result.push(escaped);
+                }
+            } else if c == q {
+                quote = None;
+            }
+            continue;
+        }
+
+        match c {
+            '"' | '\'' => {
+                quote = Some(c);
+                result.push(c);
+            }
+            '(' => {
+                paren_depth += 1;
+                result.push(c);
+            }
+            ')' => {
+                paren_depth = paren_depth.saturating_sub(1);
+                result.push(c);
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        result.push('\n');
+                    }
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            // url(http://...) の // はコメントではない
+            '/' if chars.peek() == Some(&'/') && paren_depth == 0 => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        result.push('\n');
+                        break;
+                    }
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stylesheet() {
+        let content = r#"// header
+:host { display: block; }
+.card {
+  padding: 8px !important;
+  background: url(http://example.com/a.png);
+  &__title { font-weight: bold; }
+  .icon, &:hover .icon { color: red !important; }
+  @media (max-width: 600px) {
+    ::ng-deep .mat-button { margin: 0; }
+  }
+}
+@keyframes fade { from { opacity: 0; } to { opacity: 1; } }
+/* .commented { color: blue; } */
+"#;
+        let stylesheet = parse_stylesheet(content, "card.component.scss", false);
+
+        assert_eq!(stylesheet.important_count, 2);
+        assert_eq!(stylesheet.ng_deep_lines, vec![9]);
+        assert_eq!(stylesheet.selectors, vec![
+            ":host",
+            ".card",
+            ".card__title",
+            ".card .icon",
+            ".card:hover .icon",
+            ".card ::ng-deep .mat-button",
+        ]);
+        assert_eq!(stylesheet.declarations, 8);
+        let rules: Vec<(&str, u32, u32)> = stylesheet.rules.iter().map(|rule| (rule.selectors[0].as_str(), rule.line, rule.bytes)).collect();
+        assert_eq!(rules[0], (":host", 2, 22));
+        assert_eq!(rules[2], (".card__title", 6, 32));
+        assert_eq!(rules[4].0, ".card ::ng-deep .mat-button");
+
+        let empty = parse_stylesheet("/* generated */\n", "empty.component.css", false);
+        assert_eq!(empty.declarations, 0);
+        assert!(empty.selectors.is_empty());
+    }
+}
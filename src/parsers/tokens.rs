@@ -0,0 +1,83 @@
+use crate::parsers::typescript::TypeScriptParser;
+use anyhow::Result;
+use swc_common::BytePos;
+use swc_ecma_ast::{EsVersion, ModuleDecl, ModuleItem};
+use swc_ecma_parser::token::Token as SwcToken;
+use swc_ecma_parser::{lexer::Lexer, StringInput, Syntax, TsConfig};
+
+// コピペ検出用のトークン。hash はトークンの文字列の FNV-1a、line は 1 始まり
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Token {
+    pub hash: u64,
+    pub line: u32,
+}
+
+// import 宣言を除いたソースのトークン列。どのファイルにも似た import が並ぶので比較の対象にしない
+pub fn tokenize(content: &str) -> Result<Vec<Token>> {
+    let module = TypeScriptParser::new().parse_file(content)?;
+    let imports: Vec<(u32, u32)> = module.body.iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import)) => Some((import.span.lo.0, import.span.hi.0)),
+            _ => None,
+        })
+        .collect();
+
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+        .collect();
+    let input = StringInput::new(content, BytePos(0), BytePos(content.len() as u32));
+    let lexer = Lexer::new(
+        Syntax::Typescript(TsConfig {
+            tsx: true,
+            decorators: true,
+            ..Default::default()
+        }),
+        EsVersion::Es2020,
+        input,
+        None,
+    );
+
+    let mut tokens = Vec::new();
+    for token in lexer {
+        if matches!(token.token, SwcToken::Error(_)) {
+            return Err(anyhow::anyhow!("Tokenize error at byte {}", token.span.lo.0));
+        }
+        let (lo, hi) = (token.span.lo.0, token.span.hi.0);
+        if imports.iter().any(|(start, end)| lo >= *start && hi <= *end) {
+            continue;
+        }
+        let (lo, hi) = ((lo as usize).min(content.len()), (hi as usize).min(content.len()));
+        tokens.push(Token {
+            hash: fnv(&content.as_bytes()[lo..hi]),
+            line: line_starts.partition_point(|start| *start <= lo) as u32,
+        });
+    }
+    Ok(tokens)
+}
+
+fn fnv(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let content = "import { Injectable } from '@angular/core';\n\nexport const total = items\n  .reduce((sum, item) => sum + item.price, 0);\n";
+        let tokens = tokenize(content).unwrap();
+
+        assert_eq!(tokens.len(), 23);
+        assert_eq!(tokens[0].line, 3);
+        assert_eq!(tokens.last().unwrap().line, 4);
+        // 同じ文字列のトークンは同じハッシュになる
+        assert_eq!(tokens[11].hash, tokens[16].hash);
+        assert_ne!(tokens[11].hash, tokens[9].hash);
+    }
+}
@@ -0,0 +1,107 @@
+use super::entry_points::read_json;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// extends 先の設定をマージした tsconfig の compilerOptions と angularCompilerOptions
+#[derive(Debug, Clone, Default)]
+pub struct TsConfig {
+    pub path: PathBuf,
+    pub compiler_options: Map<String, Value>,
+    pub angular_compiler_options: Map<String, Value>,
+}
+
+impl TsConfig {
+    // 解析対象に最も近い tsconfig.json（なければ tsconfig.base.json）
+    pub fn find(root_path: &Path) -> Option<Self> {
+        let root = fs::canonicalize(root_path).ok()?;
+        let path = root.ancestors()
+            .flat_map(|dir| ["tsconfig.json", "tsconfig.base.json"].map(|name| dir.join(name)))
+            .find(|path| path.is_file())?;
+
+        let mut tsconfig = Self { path: path.clone(), ..Default::default() };
+        tsconfig.merge(&path, 0);
+        Some(tsconfig)
+    }
+
+    // extends 先を先にマージし、自分の設定で上書きする。extends が循環していても止まるように、辿る深さを決めておく
+    fn merge(&mut self, path: &Path, depth: u32) {
+        let Some(value) = read_json(path).filter(|_| depth < 8) else { return };
+        let extends = match value.get("extends") {
+            Some(Value::String(extends)) => vec![extends.as_str()],
+            Some(Value::Array(extends)) => extends.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        // node_modules のパッケージ（@tsconfig/strictest など）は辿らない
+        for extends in extends.into_iter().filter(|extends| extends.starts_with('.')) {
+            let next = path.parent().unwrap_or(Path::new("")).join(extends);
+            // TypeScript と同じく、そのままのパスがなければ .json を補う（tsconfig.base → tsconfig.base.json）
+            let next = if next.is_file() { next } else { PathBuf::from(format!("{}.json", next.display())) };
+            self.merge(&next, depth + 1);
+        }
+
+        if let Some(options) = value.get("compilerOptions").and_then(Value::as_object) {
+            self.compiler_options.extend(options.clone());
+        }
+        if let Some(options) = value.get("angularCompilerOptions").and_then(Value::as_object) {
+            self.angular_compiler_options.extend(options.clone());
+        }
+    }
+
+    pub fn strict(&self) -> bool {
+        self.compiler_options.get("strict").and_then(Value::as_bool).unwrap_or(false)
+    }
+
+    pub fn strict_templates(&self) -> bool {
+        self.angular_compiler_options.get("strictTemplates").and_then(Value::as_bool).unwrap_or(false)
+    }
+
+    pub fn skip_lib_check(&self) -> bool {
+        self.compiler_options.get("skipLibCheck").and_then(Value::as_bool).unwrap_or(false)
+    }
+
+    pub fn target(&self) -> Option<&str> {
+        self.compiler_options.get("target").and_then(Value::as_str)
+    }
+}
+
+// ES5 -> 2009、ES6 / ES2015 -> 2015、ESNext は最新として扱う。大文字小文字は区別しない
+pub fn es_target_year(target: &str) -> Option<u32> {
+    match target.to_lowercase().as_str() {
+        "es3" => Some(1999),
+        "es5" => Some(2009),
+        "es6" => Some(2015),
+        "esnext" => Some(u32::MAX),
+        target => target.strip_prefix("es")?.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tsconfig_extends() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("apps/shop/src")).unwrap();
+        std::fs::write(root.join("tsconfig.base.json"), "{\n  // 共通設定\n  \"compilerOptions\": { \"strict\": true, \"target\": \"es2015\", \"skipLibCheck\": true },\n}\n").unwrap();
+        std::fs::write(root.join("tsconfig.angular.json"), "{ \"angularCompilerOptions\": { \"strictTemplates\": true } }").unwrap();
+        std::fs::write(
+            root.join("apps/shop/tsconfig.json"),
+            "{ \"extends\": [\"../../tsconfig.base\", \"../../tsconfig.angular.json\", \"@tsconfig/strictest\"], \"compilerOptions\": { \"target\": \"ES2022\" } }",
+        ).unwrap();
+
+        let tsconfig = TsConfig::find(&root.join("apps/shop/src")).unwrap();
+        assert!(tsconfig.path.ends_with("apps/shop/tsconfig.json"));
+        assert!(tsconfig.strict());
+        assert!(tsconfig.strict_templates());
+        assert!(tsconfig.skip_lib_check());
+        assert_eq!(tsconfig.target(), Some("ES2022"));
+
+        assert_eq!(es_target_year("ES6"), Some(2015));
+        assert_eq!(es_target_year("es2022"), Some(2022));
+        assert!(es_target_year("ESNext") > es_target_year("ES2024"));
+        assert_eq!(es_target_year("commonjs"), None);
+    }
+}
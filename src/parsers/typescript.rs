@@ -1,25 +1,27 @@
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
 use swc_ecma_ast::*;
-use swc_common::{SourceMap, BytePos};
-use std::sync::Arc;
+use swc_ecma_visit::{Visit, VisitWith};
+use swc_common::{SourceMap, BytePos, Span, Spanned};
+use std::rc::Rc;
 use anyhow::Result;
-use crate::ast::{NgComponent, NgService, ChangeDetectionStrategy, NgInput, NgOutput, NgMethod, Parameter};
-use crate::ast::{Import, Export, ImportType, ExportType, FileType};
-use std::path::PathBuf;
+use crate::ast::{NgComponent, NgService, ChangeDetectionStrategy, NgInput, NgOutput, NgMethod, Parameter, RouterEventSubscription, StoreMutation, LocationNavigation, NgGuard, LineCounts, SelectionChain, ChangeDetectionCall, SpecFile, FormUsage, NgModule, ForRootImport, LazyRoute, HttpUsage, HttpRequest, InjectionUsage, ZoneUsage, RouteParamUsage, HostUsage, EventListenerUsage, ServiceCall, TypeEscapes, NgPipe, NgClass, ClassHeritage};
+use crate::ast::{Import, Export, ImportType, ExportType, FileType, MutableExport, DeclaredSymbol, ProviderOverride, ParameterizedRoute, ConfigValue, EnvironmentAccess, SymbolReference};
+use std::path::Path;
+use std::sync::LazyLock;
 
 pub struct TypeScriptParser {
     #[allow(dead_code)]
-    source_map: Arc<SourceMap>,
+    source_map: Rc<SourceMap>,
 }
 
 impl TypeScriptParser {
     pub fn new() -> Self {
         Self {
-            source_map: Arc::new(SourceMap::default()),
+            source_map: Rc::new(SourceMap::default()),
         }
     }
     
-    fn normalize_path(path: &PathBuf) -> String {
+    fn normalize_path(path: &Path) -> String {
         path.display().to_string().replace('\\', "/")
     }
 
@@ -43,7 +45,7 @@ impl TypeScriptParser {
         Ok(module)
     }
 
-    pub fn extract_component(&self, module: &Module, file_path: &PathBuf) -> Result<Option<NgComponent>> {
+    pub fn extract_component(&self, module: &Module, file_path: &Path) -> Result<Option<NgComponent>> {
         let mut component = None;
         
         for item in &module.body {
@@ -60,7 +62,7 @@ impl TypeScriptParser {
         Ok(component)
     }
 
-    pub fn extract_service(&self, module: &Module, file_path: &PathBuf) -> Result<Option<NgService>> {
+    pub fn extract_service(&self, module: &Module, file_path: &Path) -> Result<Option<NgService>> {
         for item in &module.body {
             if let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) = item {
                 if let Decl::Class(class_decl) = &export_decl.decl {
@@ -73,190 +75,732 @@ impl TypeScriptParser {
         Ok(None)
     }
 
-    pub fn extract_imports_exports(&self, module: &Module, file_path: &PathBuf) -> Result<(Vec<Import>, Vec<Export>)> {
-        let mut imports = Vec::new();
-        let mut exports = Vec::new();
+    pub fn extract_guards(&self, module: &Module, content: &str, file_path: &Path) -> Vec<NgGuard> {
+        let kind = if file_path.to_string_lossy().contains(".resolver.") { "resolver" } else { "guard" };
+        let mut guards = Vec::new();
 
         for item in &module.body {
-            match item {
-                ModuleItem::ModuleDecl(module_decl) => {
-                    match module_decl {
-                        ModuleDecl::Import(import_decl) => {
-                            let source_module = import_decl.src.value.to_string();
-                            
-                            for specifier in &import_decl.specifiers {
-                                match specifier {
-                                    ImportSpecifier::Named(named) => {
-                                        let symbol_name = match &named.imported {
-                                            Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
-                                            _ => named.local.sym.to_string(),
-                                        };
-                                        imports.push(Import {
-                                            file_path: Self::normalize_path(file_path),
-                                            symbol_name,
-                                            source_module: source_module.clone(),
-                                            import_type: ImportType::Named,
-                                            line_number: None,
-                                        });
-                                    }
-                                    ImportSpecifier::Default(default) => {
-                                        imports.push(Import {
-                                            file_path: Self::normalize_path(file_path),
-                                            symbol_name: default.local.sym.to_string(),
-                                            source_module: source_module.clone(),
-                                            import_type: ImportType::Default,
-                                            line_number: None,
-                                        });
-                                    }
-                                    ImportSpecifier::Namespace(namespace) => {
-                                        imports.push(Import {
-                                            file_path: Self::normalize_path(file_path),
-                                            symbol_name: namespace.local.sym.to_string(),
-                                            source_module: source_module.clone(),
-                                            import_type: ImportType::Namespace,
-                                            line_number: None,
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                        ModuleDecl::ExportDecl(export_decl) => {
-                            match &export_decl.decl {
-                                Decl::Class(class_decl) => {
-                                    exports.push(Export {
-                                        file_path: Self::normalize_path(file_path),
-                                        symbol_name: class_decl.ident.sym.to_string(),
-                                        export_type: ExportType::Named,
-                                        line_number: None,
-                                    });
-                                }
-                                Decl::Fn(fn_decl) => {
-                                    exports.push(Export {
-                                        file_path: Self::normalize_path(file_path),
-                                        symbol_name: fn_decl.ident.sym.to_string(),
-                                        export_type: ExportType::Named,
-                                        line_number: None,
-                                    });
-                                }
-                                Decl::Var(var_decl) => {
-                                    for decl in &var_decl.decls {
-                                        if let Pat::Ident(ident) = &decl.name {
-                                            exports.push(Export {
-                                                file_path: Self::normalize_path(file_path),
-                                                symbol_name: ident.id.sym.to_string(),
-                                                export_type: ExportType::Named,
-                                                line_number: None,
+            let decl = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => &export_decl.decl,
+                _ => continue,
+            };
+
+            // export const authGuard: CanActivateFn = (route, state) => { ... }
+            // export function authGuard(route, state) { ... }
+            // export class AuthGuard implements CanActivate { canActivate(...) { ... } }
+            let (name, functional, bodies): (String, bool, Vec<(Vec<String>, Span)>) = match decl {
+                Decl::Var(var_decl) => {
+                    let Some(declarator) = var_decl.decls.first() else { continue };
+                    let Pat::Ident(ident) = &declarator.name else { continue };
+                    let body = match declarator.init.as_deref() {
+                        Some(Expr::Arrow(arrow)) => (param_names(arrow.params.iter()), arrow.body.span()),
+                        Some(Expr::Fn(fn_expr)) => match &fn_expr.function.body {
+                            Some(body) => (param_names(fn_expr.function.params.iter().map(|p| &p.pat)), body.span),
+                            None => continue,
+                        },
+                        _ => continue,
+                    };
+                    (ident.id.sym.to_string(), true, vec![body])
+                }
+                Decl::Fn(fn_decl) => match &fn_decl.function.body {
+                    Some(body) => (fn_decl.ident.sym.to_string(), true, vec![(param_names(fn_decl.function.params.iter().map(|p| &p.pat)), body.span)]),
+                    None => continue,
+                },
+                Decl::Class(class_decl) => {
+                    let bodies: Vec<_> = class_decl.class.body.iter()
+                        .filter_map(|member| match member {
+                            ClassMember::Method(method) => Some(method),
+                            _ => None,
+                        })
+                        .filter(|method| matches!(&method.key, PropName::Ident(key) if GUARD_METHODS.contains(&key.sym.as_ref())))
+                        .filter_map(|method| {
+                            method.function.body.as_ref()
+                                .map(|body| (param_names(method.function.params.iter().map(|p| &p.pat)), body.span))
+                        })
+                        .collect();
+                    if bodies.is_empty() {
+                        continue;
+                    }
+                    (class_decl.ident.sym.to_string(), false, bodies)
+                }
+                _ => continue,
+            };
+
+            let mut body_tokens = Vec::new();
+            for (params, span) in bodies {
+                let start = (span.lo.0 as usize).min(content.len());
+                let end = (span.hi.0 as usize).min(content.len());
+                body_tokens.extend(normalize_guard_body(content.get(start..end).unwrap_or(""), &params));
+            }
+
+            guards.push(NgGuard {
+                name,
+                file_path: Self::normalize_path(file_path),
+                kind: kind.to_string(),
+                functional,
+                body_tokens,
+            });
+        }
+
+        guards
+    }
+
+    // @Pipe を付けた export class
+    pub fn extract_pipes(&self, module: &Module, content: &str, file_path: &Path) -> Vec<NgPipe> {
+        let mut pipes = Vec::new();
+        for item in &module.body {
+            let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) = item else { continue };
+            let Decl::Class(class_decl) = &export_decl.decl else { continue };
+            let Some(metadata) = class_decl.class.decorators.iter().find_map(|decorator| match &*decorator.expr {
+                Expr::Call(call) if matches!(&call.callee, Callee::Expr(callee) if matches!(&**callee, Expr::Ident(ident) if ident.sym.as_ref() == "Pipe")) => Some(call),
+                _ => None,
+            }) else { continue };
+
+            let props: Vec<&Prop> = match metadata.args.first().map(|arg| &*arg.expr) {
+                Some(Expr::Object(object)) => object.props.iter()
+                    .filter_map(|prop| match prop {
+                        PropOrSpread::Prop(prop) => Some(&**prop),
+                        PropOrSpread::Spread(_) => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            let pipe_name = props.iter().find_map(|prop| match prop {
+                Prop::KeyValue(kv) if prop_key(prop) == Some("name") => match &*kv.value {
+                    Expr::Lit(Lit::Str(str_lit)) => Some(str_lit.value.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            });
+            // pure は省略すると true
+            let impure = props.iter().any(|prop| matches!(prop, Prop::KeyValue(kv)
+                if prop_key(prop) == Some("pure") && matches!(&*kv.value, Expr::Lit(Lit::Bool(value)) if !value.value)));
+
+            let mut finder = ArrayOperationFinder::default();
+            if let Some(transform) = class_method(&class_decl.class, "transform") {
+                transform.visit_with(&mut finder);
+            }
+            let offset = (class_decl.ident.span.lo.0 as usize).min(content.len());
+            pipes.push(NgPipe {
+                name: class_decl.ident.sym.to_string(),
+                file_path: Self::normalize_path(file_path),
+                pure: !impure,
+                pipe_name: pipe_name.unwrap_or_default(),
+                line: Some(content[..offset].matches('\n').count() as u32 + 1),
+                array_operations: finder.operations,
+            });
+        }
+        pipes
+    }
+
+    // トップレベルで宣言したクラス（export の有無を問わない）
+    pub fn extract_classes(&self, module: &Module, content: &str, file_path: &Path) -> Vec<NgClass> {
+        module.body.iter()
+            .filter_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl: Decl::Class(class_decl), .. }))
+                | ModuleItem::Stmt(Stmt::Decl(Decl::Class(class_decl))) => Some(class_decl),
+                _ => None,
+            })
+            .map(|class_decl| {
+                let decorator = class_decl.class.decorators.iter().find_map(|decorator| match &*decorator.expr {
+                    Expr::Call(CallExpr { callee: Callee::Expr(callee), .. }) => match &**callee {
+                        Expr::Ident(ident) => Some(ident.sym.to_string()),
+                        _ => None,
+                    },
+                    Expr::Ident(ident) => Some(ident.sym.to_string()),
+                    _ => None,
+                });
+                let offset = (class_decl.ident.span.lo.0 as usize).min(content.len());
+                NgClass {
+                    name: class_decl.ident.sym.to_string(),
+                    file_path: Self::normalize_path(file_path),
+                    line: content[..offset].matches('\n').count() as u32 + 1,
+                    is_abstract: class_decl.class.is_abstract,
+                    decorator,
+                    lifecycle_hooks: self.extract_lifecycle_hooks(&class_decl.class).unwrap_or_default(),
+                    heritage: class_heritage(&class_decl.class),
+                }
+            })
+            .collect()
+    }
+
+    // export したクラスのプロパティと getter の型。型注釈がなければ signal<T>()・input<T>() などの型引数
+    pub fn extract_property_types(&self, module: &Module) -> Vec<(String, String)> {
+        let mut types = Vec::new();
+        for item in &module.body {
+            let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) = item else { continue };
+            let Decl::Class(class_decl) = &export_decl.decl else { continue };
+            for member in &class_decl.class.body {
+                let (key, ts_type) = match member {
+                    ClassMember::ClassProp(prop) => {
+                        let ts_type = prop.type_ann.as_ref().map(|ann| &*ann.type_ann).or_else(|| match prop.value.as_deref() {
+                            Some(Expr::Call(call)) => call.type_args.as_ref().and_then(|args| args.params.first()).map(|param| &**param),
+                            _ => None,
+                        });
+                        (&prop.key, ts_type)
+                    }
+                    ClassMember::Method(method) if method.kind == MethodKind::Getter => {
+                        (&method.key, method.function.return_type.as_ref().map(|ann| &*ann.type_ann))
+                    }
+                    _ => continue,
+                };
+                if let (PropName::Ident(key), Some(ts_type)) = (key, ts_type) {
+                    types.push((key.sym.to_string(), type_text(ts_type)));
+                }
+            }
+        }
+        types
+    }
+
+    // interface・オブジェクト型の type・class のメンバー名。name を宣言していなければ None
+    pub fn extract_type_members(&self, module: &Module, name: &str) -> Option<Vec<String>> {
+        let property_names = |members: &[TsTypeElement]| -> Vec<String> {
+            members.iter()
+                .filter_map(|member| match member {
+                    TsTypeElement::TsPropertySignature(property) => match &*property.key {
+                        Expr::Ident(ident) => Some(ident.sym.to_string()),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect()
+        };
+        module.body.iter()
+            .filter_map(|item| match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => Some(&export_decl.decl),
+                ModuleItem::Stmt(Stmt::Decl(decl)) => Some(decl),
+                _ => None,
+            })
+            .find_map(|decl| match decl {
+                Decl::TsInterface(interface) if interface.id.sym.as_ref() == name => Some(property_names(&interface.body.body)),
+                Decl::TsTypeAlias(alias) if alias.id.sym.as_ref() == name => match &*alias.type_ann {
+                    TsType::TsTypeLit(literal) => Some(property_names(&literal.members)),
+                    _ => None,
+                },
+                Decl::Class(class_decl) if class_decl.ident.sym.as_ref() == name => Some(class_decl.class.body.iter()
+                    .filter_map(|member| match member {
+                        ClassMember::ClassProp(prop) => match &prop.key {
+                            PropName::Ident(key) => Some(key.sym.to_string()),
+                            _ => None,
+                        },
+                        _ => None,
+                    })
+                    .collect()),
+                _ => None,
+            })
+    }
+
+    pub fn extract_spec(&self, module: &Module, file_path: &Path) -> SpecFile {
+        let mut collector = SpecCallCollector::default();
+        module.visit_with(&mut collector);
+
+        SpecFile {
+            file_path: Self::normalize_path(file_path),
+            tests: collector.tests,
+            disabled: collector.disabled,
+        }
+    }
+
+    pub fn extract_module(&self, module: &Module, content: &str, file_path: &Path) -> Option<NgModule> {
+        for item in &module.body {
+            let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) = item else { continue };
+            let Decl::Class(class_decl) = &export_decl.decl else { continue };
+
+            for decorator in &class_decl.class.decorators {
+                let Expr::Call(call) = &*decorator.expr else { continue };
+                let is_ng_module = matches!(&call.callee, Callee::Expr(callee)
+                    if matches!(&**callee, Expr::Ident(ident) if ident.sym.as_ref() == "NgModule"));
+                if !is_ng_module {
+                    continue;
+                }
+
+                let mut ng_module = NgModule {
+                    name: class_decl.ident.sym.to_string(),
+                    file_path: Self::normalize_path(file_path),
+                    imports: Vec::new(),
+                    exports: Vec::new(),
+                    declarations: Vec::new(),
+                    providers: Vec::new(),
+                    bootstrap: Vec::new(),
+                    for_root_imports: Vec::new(),
+                };
+
+                if let Some(Expr::Object(obj_lit)) = call.args.first().map(|arg| &*arg.expr) {
+                    for prop in &obj_lit.props {
+                        let PropOrSpread::Prop(prop) = prop else { continue };
+                        let Prop::KeyValue(kv) = &**prop else { continue };
+                        let (PropName::Ident(key), Expr::Array(array)) = (&kv.key, &*kv.value) else { continue };
+                        let target = match key.sym.as_ref() {
+                            "imports" => &mut ng_module.imports,
+                            "exports" => &mut ng_module.exports,
+                            "declarations" => &mut ng_module.declarations,
+                            "providers" => &mut ng_module.providers,
+                            "bootstrap" => &mut ng_module.bootstrap,
+                            _ => continue,
+                        };
+
+                        for element in array.elems.iter().flatten() {
+                            let name = match &*element.expr {
+                                Expr::Ident(ident) => ident.sym.to_string(),
+                                // RouterModule.forRoot(routes) など
+                                Expr::Call(call) => match member_callee_obj(call) {
+                                    Some(Expr::Ident(ident)) => {
+                                        if key.sym.as_ref() == "imports" && callee_member_name(call) == Some("forRoot") {
+                                            let offset = (call.span.lo.0 as usize).min(content.len());
+                                            ng_module.for_root_imports.push(ForRootImport {
+                                                module: ident.sym.to_string(),
+                                                line: Some(content[..offset].matches('\n').count() as u32 + 1),
                                             });
                                         }
+                                        ident.sym.to_string()
                                     }
-                                }
-                                Decl::TsInterface(interface_decl) => {
-                                    exports.push(Export {
+                                    _ => continue,
+                                },
+                                Expr::Object(_) => match provider_name(&element.expr) {
+                                    Some(name) => name,
+                                    None => continue,
+                                },
+                                _ => continue,
+                            };
+                            target.push(name);
+                        }
+                    }
+                }
+
+                return Some(ng_module);
+            }
+        }
+
+        None
+    }
+
+    // loadChildren / loadComponent で遅延読み込みされる対象（動的 import と旧来の 'path#Module' 形式）
+    pub fn extract_lazy_routes(&self, module: &Module, content: &str, file_path: &Path) -> Vec<LazyRoute> {
+        let mut collector = LazyRouteCollector::default();
+        module.visit_with(&mut collector);
+
+        collector.routes.into_iter()
+            .map(|(module_name, import_path, span)| {
+                let offset = (span.lo.0 as usize).min(content.len());
+                LazyRoute {
+                    module: module_name,
+                    target: resolve_import_path(file_path, &import_path),
+                    file_path: Self::normalize_path(file_path),
+                    line: Some(content[..offset].matches('\n').count() as u32 + 1),
+                }
+            })
+            .collect()
+    }
+
+    // bootstrapApplication(AppComponent) で起動するコンポーネントと、{ path, component } のルートで表示されるコンポーネント
+    // (bootstrap されるコンポーネント, ルートの component, パラメータ付きのルート)
+    pub fn extract_render_roots(&self, module: &Module) -> (Vec<String>, Vec<String>, Vec<ParameterizedRoute>) {
+        let mut collector = RenderRootCollector::default();
+        module.visit_with(&mut collector);
+        (collector.bootstrap, collector.routed, collector.parameterized)
+    }
+
+    // symbol の import、コンストラクタ引数と inject() による注入、ルート定義（component・loadComponent・ガードなど）での参照
+    pub fn extract_symbol_references(&self, module: &Module, content: &str, file_path: &Path, symbol: &str) -> Vec<SymbolReference> {
+        let mut collector = SymbolReferenceCollector { symbol, classes: Vec::new(), references: Vec::new() };
+        module.visit_with(&mut collector);
+
+        collector.references.into_iter()
+            .map(|(kind, span, owner, source_module)| {
+                let offset = (span.lo.0 as usize).min(content.len());
+                let line_start = content[..offset].rfind('\n').map_or(0, |newline| newline + 1);
+                let line_end = content[offset..].find('\n').map_or(content.len(), |newline| offset + newline);
+                SymbolReference {
+                    kind: kind.to_string(),
+                    file_path: Self::normalize_path(file_path),
+                    line: Some(content[..offset].matches('\n').count() as u32 + 1),
+                    text: content[line_start..line_end].trim().to_string(),
+                    owner,
+                    source_module,
+                }
+            })
+            .collect()
+    }
+
+    // 型などの判別式で描画する子コンポーネントを切り替える switch / if-else 連鎖
+    pub fn extract_selection_chains(&self, module: &Module, content: &str, file_path: &Path) -> Vec<SelectionChain> {
+        let mut collector = SelectionChainCollector::default();
+        module.visit_with(&mut collector);
+
+        collector.chains.into_iter()
+            .filter(|(_, _, branches, _)| *branches > 1)
+            .map(|(kind, discriminator, branches, span)| {
+                let offset = (span.lo.0 as usize).min(content.len());
+                SelectionChain {
+                    kind: kind.to_string(),
+                    discriminator,
+                    branches,
+                    file_path: Self::normalize_path(file_path),
+                    line: Some(content[..offset].matches('\n').count() as u32 + 1),
+                }
+            })
+            .collect()
+    }
+
+    pub fn extract_imports_exports(&self, module: &Module, file_path: &Path) -> Result<(Vec<Import>, Vec<Export>)> {
+        let mut imports = Vec::new();
+        let mut exports = Vec::new();
+
+        for item in &module.body {
+            if let ModuleItem::ModuleDecl(module_decl) = item {
+                match module_decl {
+                    ModuleDecl::Import(import_decl) => {
+                        let source_module = import_decl.src.value.to_string();
+                        
+                        for specifier in &import_decl.specifiers {
+                            match specifier {
+                                ImportSpecifier::Named(named) => {
+                                    let symbol_name = match &named.imported {
+                                        Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+                                        _ => named.local.sym.to_string(),
+                                    };
+                                    imports.push(Import {
                                         file_path: Self::normalize_path(file_path),
-                                        symbol_name: interface_decl.id.sym.to_string(),
-                                        export_type: ExportType::Named,
+                                        symbol_name,
+                                        source_module: source_module.clone(),
+                                        import_type: ImportType::Named,
                                         line_number: None,
                                     });
                                 }
-                                Decl::TsTypeAlias(type_alias) => {
-                                    exports.push(Export {
+                                ImportSpecifier::Default(default) => {
+                                    imports.push(Import {
                                         file_path: Self::normalize_path(file_path),
-                                        symbol_name: type_alias.id.sym.to_string(),
-                                        export_type: ExportType::Named,
+                                        symbol_name: default.local.sym.to_string(),
+                                        source_module: source_module.clone(),
+                                        import_type: ImportType::Default,
                                         line_number: None,
                                     });
                                 }
-                                Decl::TsEnum(enum_decl) => {
-                                    exports.push(Export {
+                                ImportSpecifier::Namespace(namespace) => {
+                                    imports.push(Import {
                                         file_path: Self::normalize_path(file_path),
-                                        symbol_name: enum_decl.id.sym.to_string(),
-                                        export_type: ExportType::Named,
+                                        symbol_name: namespace.local.sym.to_string(),
+                                        source_module: source_module.clone(),
+                                        import_type: ImportType::Namespace,
                                         line_number: None,
                                     });
                                 }
-                                _ => {}
                             }
                         }
-                        ModuleDecl::ExportNamed(export_named) => {
-                            for specifier in &export_named.specifiers {
-                                match specifier {
-                                    ExportSpecifier::Named(named) => {
-                                                                let symbol_name = match &named.exported {
-                            Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
-                            _ => match &named.orig {
-                                ModuleExportName::Ident(ident) => ident.sym.to_string(),
-                                ModuleExportName::Str(s) => s.value.to_string(),
-                            },
-                        };
+                    }
+                    ModuleDecl::ExportDecl(export_decl) => {
+                        match &export_decl.decl {
+                            Decl::Class(class_decl) => {
+                                exports.push(Export {
+                                    file_path: Self::normalize_path(file_path),
+                                    symbol_name: class_decl.ident.sym.to_string(),
+                                    export_type: ExportType::Named,
+                                    source_module: None,
+                                    line_number: None,
+                                });
+                            }
+                            Decl::Fn(fn_decl) => {
+                                exports.push(Export {
+                                    file_path: Self::normalize_path(file_path),
+                                    symbol_name: fn_decl.ident.sym.to_string(),
+                                    export_type: ExportType::Named,
+                                    source_module: None,
+                                    line_number: None,
+                                });
+                            }
+                            Decl::Var(var_decl) => {
+                                for decl in &var_decl.decls {
+                                    if let Pat::Ident(ident) = &decl.name {
                                         exports.push(Export {
                                             file_path: Self::normalize_path(file_path),
-                                            symbol_name,
-                                            export_type: if export_named.src.is_some() {
-                                                ExportType::ReExport
-                                            } else {
-                                                ExportType::Named
-                                            },
+                                            symbol_name: ident.id.sym.to_string(),
+                                            export_type: ExportType::Named,
+                                            source_module: None,
                                             line_number: None,
                                         });
                                     }
-                                    _ => {}
                                 }
                             }
+                            Decl::TsInterface(interface_decl) => {
+                                exports.push(Export {
+                                    file_path: Self::normalize_path(file_path),
+                                    symbol_name: interface_decl.id.sym.to_string(),
+                                    export_type: ExportType::Named,
+                                    source_module: None,
+                                    line_number: None,
+                                });
+                            }
+                            Decl::TsTypeAlias(type_alias) => {
+                                exports.push(Export {
+                                    file_path: Self::normalize_path(file_path),
+                                    symbol_name: type_alias.id.sym.to_string(),
+                                    export_type: ExportType::Named,
+                                    source_module: None,
+                                    line_number: None,
+                                });
+                            }
+                            Decl::TsEnum(enum_decl) => {
+                                exports.push(Export {
+                                    file_path: Self::normalize_path(file_path),
+                                    symbol_name: enum_decl.id.sym.to_string(),
+                                    export_type: ExportType::Named,
+                                    source_module: None,
+                                    line_number: None,
+                                });
+                            }
+                            _ => {}
                         }
-                        ModuleDecl::ExportDefaultDecl(export_default) => {
-                            let symbol_name = match &export_default.decl {
-                                DefaultDecl::Class(class_expr) => {
-                                    if let Some(ident) = &class_expr.ident {
-                                        ident.sym.to_string()
-                                    } else {
-                                        "default".to_string()
-                                    }
-                                }
-                                DefaultDecl::Fn(fn_expr) => {
-                                    if let Some(ident) = &fn_expr.ident {
-                                        ident.sym.to_string()
+                    }
+                    ModuleDecl::ExportNamed(export_named) => {
+                        for specifier in &export_named.specifiers {
+                            if let ExportSpecifier::Named(named) = specifier {
+                                let symbol_name = match &named.exported {
+                                    Some(ModuleExportName::Ident(ident)) => ident.sym.to_string(),
+                                    _ => match &named.orig {
+                                        ModuleExportName::Ident(ident) => ident.sym.to_string(),
+                                        ModuleExportName::Str(s) => s.value.to_string(),
+                                    },
+                                };
+                                exports.push(Export {
+                                    file_path: Self::normalize_path(file_path),
+                                    symbol_name,
+                                    export_type: if export_named.src.is_some() {
+                                        ExportType::ReExport
                                     } else {
-                                        "default".to_string()
-                                    }
+                                        ExportType::Named
+                                    },
+                                    source_module: export_named.src.as_ref().map(|src| src.value.to_string()),
+                                    line_number: None,
+                                });
+                            }
+                        }
+                    }
+                    ModuleDecl::ExportDefaultDecl(export_default) => {
+                        let symbol_name = match &export_default.decl {
+                            DefaultDecl::Class(class_expr) => {
+                                if let Some(ident) = &class_expr.ident {
+                                    ident.sym.to_string()
+                                } else {
+                                    "default".to_string()
                                 }
-                                DefaultDecl::TsInterfaceDecl(interface) => {
-                                    interface.id.sym.to_string()
+                            }
+                            DefaultDecl::Fn(fn_expr) => {
+                                if let Some(ident) = &fn_expr.ident {
+                                    ident.sym.to_string()
+                                } else {
+                                    "default".to_string()
                                 }
-                            };
-                            exports.push(Export {
-                                file_path: Self::normalize_path(file_path),
-                                symbol_name,
-                                export_type: ExportType::Default,
-                                line_number: None,
-                            });
-                        }
-                        ModuleDecl::ExportDefaultExpr(_) => {
-                            exports.push(Export {
-                                file_path: Self::normalize_path(file_path),
-                                symbol_name: "default".to_string(),
-                                export_type: ExportType::Default,
-                                line_number: None,
-                            });
-                        }
-                        ModuleDecl::ExportAll(_) => {
-                            exports.push(Export {
-                                file_path: Self::normalize_path(file_path),
-                                symbol_name: "*".to_string(),
-                                export_type: ExportType::Namespace,
-                                line_number: None,
-                            });
+                            }
+                            DefaultDecl::TsInterfaceDecl(interface) => {
+                                interface.id.sym.to_string()
+                            }
+                        };
+                        exports.push(Export {
+                            file_path: Self::normalize_path(file_path),
+                            symbol_name,
+                            export_type: ExportType::Default,
+                            source_module: None,
+                            line_number: None,
+                        });
+                    }
+                    ModuleDecl::ExportDefaultExpr(_) => {
+                        exports.push(Export {
+                            file_path: Self::normalize_path(file_path),
+                            symbol_name: "default".to_string(),
+                            export_type: ExportType::Default,
+                            source_module: None,
+                            line_number: None,
+                        });
+                    }
+                    ModuleDecl::ExportAll(export_all) => {
+                        exports.push(Export {
+                            file_path: Self::normalize_path(file_path),
+                            symbol_name: "*".to_string(),
+                            export_type: ExportType::Namespace,
+                            source_module: Some(export_all.src.value.to_string()),
+                            line_number: None,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok((imports, exports))
+    }
+
+    // providers に並ぶ { provide: ErrorHandler, ... } などのうち、フレームワークのトークンを上書きするもの
+    pub fn extract_provider_overrides(&self, module: &Module, content: &str, file_path: &Path) -> Vec<ProviderOverride> {
+        Self::collect_providers(module, content, file_path, false)
+    }
+
+    // providers に並ぶすべてのクラスとトークン、withInterceptors に渡した関数型インターセプター
+    pub fn extract_provider_registrations(&self, module: &Module, content: &str, file_path: &Path) -> Vec<ProviderOverride> {
+        Self::collect_providers(module, content, file_path, true)
+    }
+
+    fn collect_providers(module: &Module, content: &str, file_path: &Path, all: bool) -> Vec<ProviderOverride> {
+        let file_stem = file_path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default();
+        let mut collector = ProviderOverrideCollector {
+            contexts: vec![("application".to_string(), file_stem)],
+            all,
+            overrides: Vec::new(),
+        };
+        module.visit_with(&mut collector);
+
+        collector.overrides.into_iter()
+            .map(|(mut provider_override, span)| {
+                let offset = (span.lo.0 as usize).min(content.len());
+                provider_override.file_path = Self::normalize_path(file_path);
+                provider_override.line = Some(content[..offset].matches('\n').count() as u32 + 1);
+                provider_override
+            })
+            .collect()
+    }
+
+    // import('./x') の呼び出し。遅延ロードのルートに限らず、式の中にあるものをすべて集める
+    pub fn extract_dynamic_imports(&self, module: &Module, content: &str, file_path: &Path) -> Vec<Import> {
+        let mut collector = DynamicImportCollector::default();
+        module.visit_with(&mut collector);
+
+        collector.imports.into_iter()
+            .map(|(source_module, span)| {
+                let offset = (span.lo.0 as usize).min(content.len());
+                Import {
+                    file_path: Self::normalize_path(file_path),
+                    symbol_name: "*".to_string(),
+                    source_module,
+                    import_type: ImportType::Dynamic,
+                    line_number: Some(content[..offset].matches('\n').count() as u32 + 1),
+                }
+            })
+            .collect()
+    }
+
+    // トップレベルの export let / export var と、Object.freeze や as const のない
+    // オブジェクト・配列・Map・Set の export const
+    pub fn extract_mutable_exports(&self, module: &Module, content: &str, file_path: &Path) -> Vec<MutableExport> {
+        let mut mutable_exports = Vec::new();
+        for item in &module.body {
+            let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl: Decl::Var(var_decl), .. })) = item else { continue };
+            for decl in &var_decl.decls {
+                let Pat::Ident(ident) = &decl.name else { continue };
+                if ident.type_ann.as_ref().is_some_and(|ann| is_readonly_type(&ann.type_ann)) {
+                    continue;
+                }
+                let kind = match var_decl.kind {
+                    VarDeclKind::Let => Some("let".to_string()),
+                    VarDeclKind::Var => Some("var".to_string()),
+                    VarDeclKind::Const => decl.init.as_deref().and_then(mutable_value_kind),
+                };
+                let Some(kind) = kind else { continue };
+                let offset = (decl.span.lo.0 as usize).min(content.len());
+                mutable_exports.push(MutableExport {
+                    file_path: Self::normalize_path(file_path),
+                    name: ident.id.sym.to_string(),
+                    kind,
+                    line: Some(content[..offset].matches('\n').count() as u32 + 1),
+                });
+            }
+        }
+        mutable_exports
+    }
+
+    // any の型注釈・as any・! と @ts-ignore のコメントの数
+    pub fn extract_type_escapes(&self, module: &Module, content: &str) -> TypeEscapes {
+        let mut collector = TypeEscapeCollector::default();
+        module.visit_with(&mut collector);
+        let ts_ignore = regex::Regex::new(r"(?://|/\*)[\s*]*@ts-ignore\b").unwrap();
+        collector.escapes.ts_ignores = ts_ignore.find_iter(content).count() as u32;
+        collector.escapes
+    }
+
+    // トップレベルで export したクラス・インターフェース・型・enum・関数（アロー関数の export const を含む）
+    pub fn extract_declared_symbols(&self, module: &Module, content: &str, file_path: &Path) -> Vec<DeclaredSymbol> {
+        let mut symbols = Vec::new();
+        let mut push = |name: &str, kind: &str, span: Span| {
+            let start = (span.lo.0 as usize).min(content.len());
+            let end = (span.hi.0 as usize).clamp(start, content.len());
+            let text = &content[start..end];
+            symbols.push(DeclaredSymbol {
+                file_path: Self::normalize_path(file_path),
+                name: name.to_string(),
+                kind: kind.to_string(),
+                line: Some(content[..start].matches('\n').count() as u32 + 1),
+                lines: crate::parsers::project::count_lines(text).logical,
+                body_hash: body_hash(text),
+            });
+        };
+
+        for item in &module.body {
+            let ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) = item else { continue };
+            match &export_decl.decl {
+                Decl::Class(class_decl) => push(&class_decl.ident.sym, "class", class_decl.class.span),
+                Decl::Fn(fn_decl) => push(&fn_decl.ident.sym, "function", fn_decl.function.span),
+                Decl::TsInterface(interface_decl) => push(&interface_decl.id.sym, "interface", interface_decl.span),
+                Decl::TsTypeAlias(type_alias) => push(&type_alias.id.sym, "type", type_alias.span),
+                Decl::TsEnum(enum_decl) => push(&enum_decl.id.sym, "enum", enum_decl.span),
+                Decl::Var(var_decl) => {
+                    for decl in &var_decl.decls {
+                        let Pat::Ident(ident) = &decl.name else { continue };
+                        if matches!(decl.init.as_deref(), Some(Expr::Arrow(_) | Expr::Fn(_))) {
+                            push(&ident.id.sym, "function", decl.span);
                         }
-                        _ => {}
                     }
                 }
                 _ => {}
             }
         }
+        symbols
+    }
 
-        Ok((imports, exports))
+    // トップレベルの const environment = { ... } などにある文字列の値
+    pub fn extract_config_values(&self, module: &Module, content: &str) -> Vec<ConfigValue> {
+        let mut values = Vec::new();
+        for item in &module.body {
+            let var_decl = match item {
+                ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) => var_decl,
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl: Decl::Var(var_decl), .. })) => var_decl,
+                _ => continue,
+            };
+            for init in var_decl.decls.iter().filter_map(|decl| decl.init.as_deref()) {
+                if let Expr::Object(object) = unwrap_type_expr(init) {
+                    collect_config_values(object, "", content, &mut values);
+                }
+            }
+        }
+        values
+    }
+
+    // 最後のセグメントが environment / environment.prod などのモジュールの import と、そこから読んでいるフィールド
+    pub fn extract_environment_accesses(&self, module: &Module, content: &str) -> Vec<EnvironmentAccess> {
+        let environment_module = regex::Regex::new(r"(?:^|/)environment(?:\.[\w-]+)?$").unwrap();
+        let mut accesses = Vec::new();
+        for item in &module.body {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(import)) = item else { continue };
+            if import.type_only || !environment_module.is_match(&import.src.value) {
+                continue;
+            }
+            let locals: Vec<String> = import.specifiers.iter()
+                .map(|specifier| match specifier {
+                    ImportSpecifier::Named(named) => named.local.sym.to_string(),
+                    ImportSpecifier::Default(default) => default.local.sym.to_string(),
+                    ImportSpecifier::Namespace(namespace) => namespace.local.sym.to_string(),
+                })
+                .collect();
+            let mut collector = MemberReadCollector { objects: locals, fields: Vec::new() };
+            module.visit_with(&mut collector);
+            let offset = (import.span.lo.0 as usize).min(content.len());
+            accesses.push(EnvironmentAccess {
+                source_module: import.src.value.to_string(),
+                fields: collector.fields,
+                line: Some(content[..offset].matches('\n').count() as u32 + 1),
+            });
+        }
+        accesses
     }
 
-    pub fn get_file_type(&self, file_path: &PathBuf) -> FileType {
+    pub fn get_file_type(&self, file_path: &Path) -> FileType {
         let extension = file_path.extension()
             .and_then(|ext| ext.to_str())
             .unwrap_or("");
@@ -269,12 +813,15 @@ impl TypeScriptParser {
         }
     }
 
-    fn analyze_class_for_component(&self, class_decl: &ClassDecl, file_path: &PathBuf) -> Result<Option<NgComponent>> {
+    fn analyze_class_for_component(&self, class_decl: &ClassDecl, file_path: &Path) -> Result<Option<NgComponent>> {
         let mut selector = None;
         let mut template_url = None;
         let mut template = None;
         let mut style_urls = Vec::new();
+        let mut inline_styles = Vec::new();
+        let mut providers = Vec::new();
         let mut change_detection = ChangeDetectionStrategy::Default;
+        let mut standalone = false;
 
         if !class_decl.class.decorators.is_empty() {
             for decorator in &class_decl.class.decorators {
@@ -286,7 +833,10 @@ impl TypeScriptParser {
                                     if let Expr::Object(obj_lit) = &*args.expr {
                                         for prop in &obj_lit.props {
                                             if let PropOrSpread::Prop(prop) = prop {
-                                                self.extract_component_metadata(&**prop, &mut selector, &mut template_url, &mut template, &mut style_urls, &mut change_detection);
+                                                self.extract_component_metadata(prop, &mut selector, &mut template_url, &mut template, &mut style_urls, &mut change_detection);
+                                                standalone |= is_true_prop(prop, "standalone");
+                                                inline_styles.extend(inline_styles_prop(prop));
+                                                providers.extend(providers_prop(prop));
                                             }
                                         }
                                     }
@@ -295,8 +845,16 @@ impl TypeScriptParser {
                                 let inputs = self.extract_inputs(&class_decl.class)?;
                                 let outputs = self.extract_outputs(&class_decl.class)?;
                                 let lifecycle_hooks = self.extract_lifecycle_hooks(&class_decl.class)?;
-                                let dependencies = self.extract_dependencies(&class_decl.class)?;
+                                let injection = self.extract_injection(&class_decl.class);
+                                let dependencies = injection_dependencies(&injection);
                                 let complexity_score = self.calculate_complexity(&class_decl.class)?;
+                                let has_on_destroy = lifecycle_hooks.iter().any(|hook| hook == "ngOnDestroy");
+                                let router_event_subscriptions = self.extract_router_event_subscriptions(&class_decl.class, has_on_destroy);
+                                let store_mutations = self.extract_store_mutations(&class_decl.class);
+                                let location_navigations = self.extract_location_navigations(&class_decl.class);
+                                let change_detection_calls = self.extract_change_detection_calls(&class_decl.class);
+                                let forms = self.extract_form_usage(&class_decl.class);
+                                let service_calls = self.extract_service_calls(&class_decl.class, &dependencies);
 
                                 return Ok(Some(NgComponent {
                                     name: class_decl.ident.sym.to_string(),
@@ -311,6 +869,31 @@ impl TypeScriptParser {
                                     dependencies,
                                     change_detection,
                                     complexity_score,
+                                    router_event_subscriptions,
+                                    store_mutations,
+                                    location_navigations,
+                                    lines: LineCounts::default(),
+                                    selection_chains: vec![],
+                                    test_coverage: None,
+                                    change_detection_calls,
+                                    standalone,
+                                    forms,
+                                    template_usages: vec![],
+                                    http: self.extract_http_usage(&class_decl.class),
+                                    inline_styles,
+                                    stylesheets: vec![],
+                                    providers,
+                                    used_classes: None,
+                                    injection,
+                                    control_flow: None,
+                                    zone: self.extract_zone_usage(&class_decl.class),
+                                    public_methods: self.extract_public_methods(&class_decl.class),
+                                    route_params: self.extract_route_params(&class_decl.class),
+                                    host: self.extract_host_usage(&class_decl.class),
+                                    service_calls,
+                                    template_complexity: None,
+                                    heritage: class_heritage(&class_decl.class),
+                                    element_bindings: vec![],
                                 }));
                             }
                         }
@@ -321,7 +904,7 @@ impl TypeScriptParser {
         Ok(None)
     }
 
-    fn analyze_class_for_service(&self, class_decl: &ClassDecl, file_path: &PathBuf) -> Result<Option<NgService>> {
+    fn analyze_class_for_service(&self, class_decl: &ClassDecl, file_path: &Path) -> Result<Option<NgService>> {
         let mut provided_in = None;
         let mut injectable = false;
 
@@ -357,8 +940,15 @@ impl TypeScriptParser {
         }
 
         if injectable {
-            let dependencies = self.extract_dependencies(&class_decl.class)?;
+            let injection = self.extract_injection(&class_decl.class);
+            let dependencies = injection_dependencies(&injection);
             let methods = self.extract_methods(&class_decl.class)?;
+            let constructor_statements = self.count_constructor_statements(&class_decl.class);
+            let store_mutations = self.extract_store_mutations(&class_decl.class);
+            let location_navigations = self.extract_location_navigations(&class_decl.class);
+            let change_detection_calls = self.extract_change_detection_calls(&class_decl.class);
+            let forms = self.extract_form_usage(&class_decl.class);
+            let service_calls = self.extract_service_calls(&class_decl.class, &dependencies);
 
             return Ok(Some(NgService {
                 name: class_decl.ident.sym.to_string(),
@@ -367,6 +957,17 @@ impl TypeScriptParser {
                 injectable,
                 dependencies,
                 methods,
+                constructor_statements,
+                store_mutations,
+                location_navigations,
+                lines: LineCounts::default(),
+                test_coverage: None,
+                change_detection_calls,
+                forms,
+                http: self.extract_http_usage(&class_decl.class),
+                injection,
+                service_calls,
+                heritage: class_heritage(&class_decl.class),
             }));
         }
 
@@ -402,15 +1003,18 @@ impl TypeScriptParser {
                     }
                     "styleUrls" => {
                         if let Expr::Array(arr_lit) = &*kv.value {
-                            for elem in &arr_lit.elems {
-                                if let Some(ExprOrSpread { expr, .. }) = elem {
-                                    if let Expr::Lit(Lit::Str(str_lit)) = &**expr {
-                                        style_urls.push(str_lit.value.to_string());
-                                    }
+                            for ExprOrSpread { expr, .. } in arr_lit.elems.iter().flatten() {
+                                if let Expr::Lit(Lit::Str(str_lit)) = &**expr {
+                                    style_urls.push(str_lit.value.to_string());
                                 }
                             }
                         }
                     }
+                    "styleUrl" => {
+                        if let Expr::Lit(Lit::Str(str_lit)) = &*kv.value {
+                            style_urls.push(str_lit.value.to_string());
+                        }
+                    }
                     "changeDetection" => {
                         if let Expr::Member(member_expr) = &*kv.value {
                             if let MemberProp::Ident(ident) = &member_expr.prop {
@@ -426,6 +1030,7 @@ impl TypeScriptParser {
         }
     }
 
+    // @Input() name: string / @Input() size = 10 / name = input<string>()。型が分からないものは any
     fn extract_inputs(&self, class: &Class) -> Result<Vec<NgInput>> {
         let mut inputs = Vec::new();
         
@@ -437,10 +1042,15 @@ impl TypeScriptParser {
                             if let Expr::Ident(ident) = &**expr {
                                 if ident.sym.as_ref() == "Input" {
                                     if let PropName::Ident(ident) = &prop.key {
+                                        // @Input('alias') / @Input({ alias: 'alias' })
                                         inputs.push(NgInput {
                                             name: ident.sym.to_string(),
-                                            alias: None,
-                                            input_type: "any".to_string(),
+                                            alias: call_expr.args.first().and_then(|arg| alias_option(&arg.expr)),
+                                            input_type: prop.type_ann.as_ref().map(|ann| type_text(&ann.type_ann))
+                                                .or_else(|| prop.value.as_deref().and_then(literal_type))
+                                                .unwrap_or_else(|| "any".to_string()),
+                                            signal: false,
+                                            optional: prop.is_optional,
                                         });
                                     }
                                 }
@@ -448,12 +1058,31 @@ impl TypeScriptParser {
                         }
                     }
                 }
+
+                // name = input<string>() / input.required<string>() / model()
+                if let (PropName::Ident(key), Some(Expr::Call(call))) = (&prop.key, prop.value.as_deref()) {
+                    if is_signal_input_call(call) {
+                        // input('') は初期値から、input.required() は型引数からしか型が分からない
+                        let required = matches!(&call.callee, Callee::Expr(callee) if matches!(&**callee, Expr::Member(_)));
+                        let initial = if required { None } else { call.args.first() };
+                        inputs.push(NgInput {
+                            name: key.sym.to_string(),
+                            alias: call.args.iter().skip(usize::from(!required)).find_map(|arg| alias_option(&arg.expr)),
+                            input_type: call.type_args.as_ref().and_then(|args| args.params.first()).map(|param| type_text(param))
+                                .or_else(|| initial.and_then(|arg| literal_type(&arg.expr)))
+                                .unwrap_or_else(|| "any".to_string()),
+                            signal: true,
+                            optional: false,
+                        });
+                    }
+                }
             }
         }
 
         Ok(inputs)
     }
 
+    // 型注釈か new EventEmitter<T>() から型を取る。new EventEmitter() は型引数なしの EventEmitter のまま残す
     fn extract_outputs(&self, class: &Class) -> Result<Vec<NgOutput>> {
         let mut outputs = Vec::new();
         
@@ -465,10 +1094,29 @@ impl TypeScriptParser {
                             if let Expr::Ident(ident) = &**expr {
                                 if ident.sym.as_ref() == "Output" {
                                     if let PropName::Ident(ident) = &prop.key {
+                                        // @Output('alias')
+                                        let alias = call_expr.args.first().and_then(|arg| match &*arg.expr {
+                                            Expr::Lit(Lit::Str(str_lit)) => Some(str_lit.value.to_string()),
+                                            _ => None,
+                                        });
+                                        let initializer_type = match prop.value.as_deref() {
+                                            Some(Expr::New(new_expr)) => match (&*new_expr.callee, &new_expr.type_args) {
+                                                (Expr::Ident(callee), Some(args)) => Some(format!(
+                                                    "{}<{}>",
+                                                    callee.sym,
+                                                    args.params.iter().map(|param| type_text(param)).collect::<Vec<_>>().join(", ")
+                                                )),
+                                                (Expr::Ident(callee), None) => Some(callee.sym.to_string()),
+                                                _ => None,
+                                            },
+                                            _ => None,
+                                        };
                                         outputs.push(NgOutput {
                                             name: ident.sym.to_string(),
-                                            alias: None,
-                                            output_type: "EventEmitter<any>".to_string(),
+                                            alias,
+                                            output_type: prop.type_ann.as_ref().map(|ann| type_text(&ann.type_ann))
+                                                .or(initializer_type)
+                                                .unwrap_or_else(|| "any".to_string()),
                                         });
                                     }
                                 }
@@ -484,7 +1132,7 @@ impl TypeScriptParser {
 
     fn extract_lifecycle_hooks(&self, class: &Class) -> Result<Vec<String>> {
         let mut hooks = Vec::new();
-        let lifecycle_methods = vec![
+        let lifecycle_methods = [
             "ngOnInit", "ngOnDestroy", "ngOnChanges", "ngAfterViewInit",
             "ngAfterViewChecked", "ngAfterContentInit", "ngAfterContentChecked",
             "ngDoCheck"
@@ -504,24 +1152,220 @@ impl TypeScriptParser {
         Ok(hooks)
     }
 
-    fn extract_dependencies(&self, class: &Class) -> Result<Vec<String>> {
-        let mut dependencies = Vec::new();
+    fn extract_injection(&self, class: &Class) -> InjectionUsage {
+        let mut injection = InjectionUsage::default();
 
         for member in &class.body {
             if let ClassMember::Constructor(constructor) = member {
                 for param in &constructor.params {
-                    if let ParamOrTsParamProp::TsParamProp(ts_param) = param {
-                        if let TsParamPropParam::Ident(ident) = &ts_param.param {
-                            if let Some(type_ann) = &ident.type_ann {
-                                dependencies.push(self.extract_type_from_annotation(&type_ann.type_ann));
-                            }
+                    let ident = match param {
+                        ParamOrTsParamProp::TsParamProp(ts_param) => match &ts_param.param {
+                            TsParamPropParam::Ident(ident) => ident,
+                            _ => continue,
+                        },
+                        ParamOrTsParamProp::Param(param) => match &param.pat {
+                            Pat::Ident(ident) => ident,
+                            _ => continue,
+                        },
+                    };
+                    if let Some(type_ann) = &ident.type_ann {
+                        injection.constructor.push(self.extract_type_from_annotation(&type_ann.type_ann));
+                    }
+                }
+            }
+        }
+
+        // private store = inject(Store) などの inject() 呼び出し
+        let mut collector = InjectCallCollector::default();
+        class.visit_with(&mut collector);
+        injection.inject = collector.names;
+
+        injection
+    }
+
+    fn count_constructor_statements(&self, class: &Class) -> u32 {
+        class.body.iter()
+            .filter_map(|member| match member {
+                ClassMember::Constructor(constructor) => constructor.body.as_ref(),
+                _ => None,
+            })
+            .map(|body| body.stmts.len() as u32)
+            .sum()
+    }
+
+    fn extract_router_event_subscriptions(&self, class: &Class, has_on_destroy: bool) -> Vec<RouterEventSubscription> {
+        let mut collector = RouterEventsCollector::default();
+        class.visit_with(&mut collector);
+
+        collector.calls.into_iter()
+            .map(|call| {
+                // 変数に保持していても ngOnDestroy がなければ解除されない
+                let stored = collector.stored_spans.contains(&call.span);
+                RouterEventSubscription {
+                    filters_event_type: call.filters_event_type,
+                    has_teardown: call.operators.iter().any(|op| TEARDOWN_OPERATORS.contains(&op.as_str()))
+                        || (stored && has_on_destroy),
+                    handler_signature: if call.handler_calls.is_empty() {
+                        String::new()
+                    } else {
+                        format!("{}=>{}", call.operators.join("|"), call.handler_calls.join(","))
+                    },
+                }
+            })
+            .collect()
+    }
+
+    fn extract_store_mutations(&self, class: &Class) -> Vec<StoreMutation> {
+        let mut collector = StoreMutationCollector::default();
+        class.visit_with(&mut collector);
+        collector.mutations
+    }
+
+    fn extract_location_navigations(&self, class: &Class) -> Vec<LocationNavigation> {
+        let mut collector = LocationNavigationCollector::default();
+        class.visit_with(&mut collector);
+        collector.navigations
+    }
+
+    fn extract_change_detection_calls(&self, class: &Class) -> Vec<ChangeDetectionCall> {
+        let mut collector = ChangeDetectionCallCollector {
+            app_refs: self.injected_names(class, "ApplicationRef"),
+            ..Default::default()
+        };
+        class.visit_with(&mut collector);
+        collector.calls
+    }
+
+    fn extract_form_usage(&self, class: &Class) -> FormUsage {
+        let mut collector = FormUsageCollector {
+            typed_builders: [self.injected_names(class, "FormBuilder"), self.injected_names(class, "NonNullableFormBuilder")].concat(),
+            untyped_builders: self.injected_names(class, "UntypedFormBuilder"),
+            ..Default::default()
+        };
+        class.visit_with(&mut collector);
+        collector.usage
+    }
+
+    fn extract_host_usage(&self, class: &Class) -> HostUsage {
+        let mut usage = HostUsage::default();
+
+        for member in &class.body {
+            let (decorators, name, handler) = match member {
+                ClassMember::Method(method) => (&method.function.decorators, &method.key, Some(&*method.function)),
+                ClassMember::ClassProp(prop) => (&prop.decorators, &prop.key, None),
+                _ => continue,
+            };
+            for decorator in decorators {
+                let Expr::Call(call) = &*decorator.expr else { continue };
+                let Callee::Expr(callee) = &call.callee else { continue };
+                let Expr::Ident(ident) = &**callee else { continue };
+                let argument = call.args.first().and_then(|arg| match &*arg.expr {
+                    Expr::Lit(Lit::Str(str_lit)) => Some(str_lit.value.to_string()),
+                    _ => None,
+                });
+                match ident.sym.as_ref() {
+                    // @HostListener('window:resize') / @HostListener('click')
+                    "HostListener" => {
+                        let Some(argument) = argument else { continue };
+                        let (target, event) = match argument.split_once(':') {
+                            Some((target, event)) => (target.to_string(), event.to_string()),
+                            None => ("host".to_string(), argument),
+                        };
+                        usage.listeners.push(EventListenerUsage {
+                            target,
+                            event,
+                            source: "HostListener".to_string(),
+                            removed: true,
+                            throttled: handler.is_some_and(is_throttled),
+                        });
+                    }
+                    "HostBinding" => {
+                        let property = match name {
+                            PropName::Ident(ident) => Some(ident.sym.to_string()),
+                            _ => None,
+                        };
+                        usage.bindings.extend(argument.or(property));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut collector = HostAccessCollector::default();
+        class.visit_with(&mut collector);
+        for (mut listener, method) in collector.listeners {
+            if let Some(function) = method.and_then(|name| class_method(class, &name)) {
+                listener.throttled |= is_throttled(function);
+            }
+            // removeEventListener は同じ対象とイベントの組で照合する
+            listener.removed |= collector.removed.iter().any(|(target, event)| *target == listener.target && *event == listener.event);
+            usage.listeners.push(listener);
+        }
+        usage.native_element_writes = collector.native_element_writes;
+        usage
+    }
+
+    fn extract_route_params(&self, class: &Class) -> RouteParamUsage {
+        let mut collector = RouteParamCollector::default();
+        class.visit_with(&mut collector);
+        collector.usage
+    }
+
+    fn extract_zone_usage(&self, class: &Class) -> ZoneUsage {
+        let mut collector = ZoneUsageCollector::default();
+        class.visit_with(&mut collector);
+        collector.usage
+    }
+
+    fn extract_http_usage(&self, class: &Class) -> HttpUsage {
+        let clients = self.injected_names(class, "HttpClient");
+        let mut collector = HttpRequestCollector {
+            clients: clients.clone(),
+            ..Default::default()
+        };
+        class.visit_with(&mut collector);
+
+        HttpUsage {
+            injected: !clients.is_empty(),
+            requests: collector.requests,
+        }
+    }
+
+    // constructor(private appRef: ApplicationRef) / private appRef = inject(ApplicationRef)
+    fn injected_names(&self, class: &Class, type_name: &str) -> Vec<String> {
+        let mut names = Vec::new();
+
+        for member in &class.body {
+            match member {
+                ClassMember::Constructor(constructor) => {
+                    for param in &constructor.params {
+                        let ident = match param {
+                            ParamOrTsParamProp::TsParamProp(ts_param) => match &ts_param.param {
+                                TsParamPropParam::Ident(ident) => ident,
+                                _ => continue,
+                            },
+                            ParamOrTsParamProp::Param(param) => match &param.pat {
+                                Pat::Ident(ident) => ident,
+                                _ => continue,
+                            },
+                        };
+                        if ident.type_ann.as_ref().is_some_and(|ann| self.extract_type_from_annotation(&ann.type_ann) == type_name) {
+                            names.push(ident.id.sym.to_string());
                         }
                     }
                 }
+                ClassMember::ClassProp(prop) => {
+                    if let (PropName::Ident(key), Some(Expr::Call(call))) = (&prop.key, prop.value.as_deref()) {
+                        if is_inject_of(call, type_name) {
+                            names.push(key.sym.to_string());
+                        }
+                    }
+                }
+                _ => {}
             }
         }
 
-        Ok(dependencies)
+        names
     }
 
     fn extract_methods(&self, class: &Class) -> Result<Vec<NgMethod>> {
@@ -533,18 +1377,15 @@ impl TypeScriptParser {
                     let method_name = ident.sym.to_string();
                     if !method_name.starts_with("ng") {
                         let parameters = method.function.params.iter()
-                            .map(|_param| Parameter {
-                                name: "param".to_string(),
-                                param_type: "any".to_string(),
-                                optional: false,
-                            })
+                            .map(|param| parameter(&param.pat))
                             .collect();
 
                         methods.push(NgMethod {
                             name: method_name,
                             parameters,
-                            return_type: None,
+                            return_type: method.function.return_type.as_ref().map(|ann| type_text(&ann.type_ann)),
                             complexity_score: 1,
+                            public: is_public_method(method),
                         });
                     }
                 }
@@ -554,10 +1395,35 @@ impl TypeScriptParser {
         Ok(methods)
     }
 
-    fn calculate_complexity(&self, class: &Class) -> Result<u32> {
-        let mut complexity = 1;
+    // 親コンポーネントやテストから呼べるメソッド。ng で始まるライフサイクルフックは API に数えない
+    fn extract_public_methods(&self, class: &Class) -> Vec<String> {
+        class.body.iter()
+            .filter_map(|member| match member {
+                ClassMember::Method(method) if is_public_method(method) => match &method.key {
+                    PropName::Ident(ident) if !ident.sym.starts_with("ng") => Some(ident.sym.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
 
-        for member in &class.body {
+    // 注入したサービスのメソッドを呼んでいる箇所。同じサービスの同じメソッドは 1 回だけ数える
+    fn extract_service_calls(&self, class: &Class, dependencies: &[String]) -> Vec<ServiceCall> {
+        let mut collector = ServiceCallCollector::default();
+        for dependency in dependencies {
+            for name in self.injected_names(class, dependency) {
+                collector.fields.push((name, dependency.clone()));
+            }
+        }
+        class.visit_with(&mut collector);
+        collector.calls
+    }
+
+    fn calculate_complexity(&self, class: &Class) -> Result<u32> {
+        let mut complexity = 1;
+
+        for member in &class.body {
             if let ClassMember::Method(method) = member {
                 complexity += self.calculate_method_complexity(&method.function);
             }
@@ -582,4 +1448,2889 @@ impl TypeScriptParser {
             _ => "unknown".to_string(),
         }
     }
-}
\ No newline at end of file
+}
+
+fn is_public_method(method: &ClassMethod) -> bool {
+    method.kind == MethodKind::Method
+        && !method.is_static
+        && !matches!(method.accessibility, Some(Accessibility::Private) | Some(Accessibility::Protected))
+}
+
+// 型注釈のない引数は any。分割代入した引数は { id, name } のように書く
+fn parameter(pat: &Pat) -> Parameter {
+    let annotation = |ann: &Option<Box<TsTypeAnn>>| ann.as_ref().map(|ann| type_text(&ann.type_ann)).unwrap_or_else(|| "any".to_string());
+    match pat {
+        Pat::Ident(ident) => Parameter {
+            name: ident.id.sym.to_string(),
+            param_type: annotation(&ident.type_ann),
+            optional: ident.id.optional,
+        },
+        Pat::Assign(assign) => Parameter { optional: true, ..parameter(&assign.left) },
+        Pat::Rest(rest) => {
+            let inner = parameter(&rest.arg);
+            Parameter {
+                name: format!("...{}", inner.name),
+                param_type: rest.type_ann.as_ref().map(|ann| type_text(&ann.type_ann)).unwrap_or(inner.param_type),
+                optional: true,
+            }
+        }
+        Pat::Object(object) => {
+            let keys: Vec<String> = object.props.iter()
+                .map(|prop| match prop {
+                    ObjectPatProp::KeyValue(key_value) => match &key_value.key {
+                        PropName::Ident(ident) => ident.sym.to_string(),
+                        PropName::Str(key) => key.value.to_string(),
+                        _ => "?".to_string(),
+                    },
+                    ObjectPatProp::Assign(assign) => assign.key.sym.to_string(),
+                    ObjectPatProp::Rest(rest) => format!("...{}", parameter(&rest.arg).name),
+                })
+                .collect();
+            Parameter {
+                name: format!("{{ {} }}", keys.join(", ")),
+                param_type: annotation(&object.type_ann),
+                optional: object.optional,
+            }
+        }
+        Pat::Array(array) => Parameter {
+            name: "[...]".to_string(),
+            param_type: annotation(&array.type_ann),
+            optional: array.optional,
+        },
+        _ => Parameter { name: "?".to_string(), param_type: "any".to_string(), optional: false },
+    }
+}
+
+// 型注釈をソースに近い形の文字列にする。オブジェクト型などの細部は省略する
+fn type_text(ts_type: &TsType) -> String {
+    let join = |types: &[Box<TsType>], separator: &str| types.iter().map(|ts_type| type_text(ts_type)).collect::<Vec<_>>().join(separator);
+    match ts_type {
+        TsType::TsKeywordType(keyword) => match keyword.kind {
+            TsKeywordTypeKind::TsAnyKeyword => "any",
+            TsKeywordTypeKind::TsUnknownKeyword => "unknown",
+            TsKeywordTypeKind::TsNumberKeyword => "number",
+            TsKeywordTypeKind::TsObjectKeyword => "object",
+            TsKeywordTypeKind::TsBooleanKeyword => "boolean",
+            TsKeywordTypeKind::TsBigIntKeyword => "bigint",
+            TsKeywordTypeKind::TsStringKeyword => "string",
+            TsKeywordTypeKind::TsSymbolKeyword => "symbol",
+            TsKeywordTypeKind::TsVoidKeyword => "void",
+            TsKeywordTypeKind::TsUndefinedKeyword => "undefined",
+            TsKeywordTypeKind::TsNullKeyword => "null",
+            TsKeywordTypeKind::TsNeverKeyword => "never",
+            TsKeywordTypeKind::TsIntrinsicKeyword => "intrinsic",
+        }.to_string(),
+        TsType::TsThisType(_) => "this".to_string(),
+        TsType::TsTypeRef(type_ref) => {
+            let name = entity_name(&type_ref.type_name);
+            match &type_ref.type_params {
+                Some(params) => format!("{}<{}>", name, join(&params.params, ", ")),
+                None => name,
+            }
+        }
+        TsType::TsArrayType(array) => match &*array.elem_type {
+            TsType::TsUnionOrIntersectionType(_) | TsType::TsFnOrConstructorType(_) => format!("({})[]", type_text(&array.elem_type)),
+            elem_type => format!("{}[]", type_text(elem_type)),
+        },
+        TsType::TsTupleType(tuple) => format!(
+            "[{}]",
+            tuple.elem_types.iter().map(|elem| type_text(&elem.ty)).collect::<Vec<_>>().join(", ")
+        ),
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsUnionType(union)) => join(&union.types, " | "),
+        TsType::TsUnionOrIntersectionType(TsUnionOrIntersectionType::TsIntersectionType(intersection)) => join(&intersection.types, " & "),
+        TsType::TsParenthesizedType(paren) => format!("({})", type_text(&paren.type_ann)),
+        TsType::TsTypeOperator(operator) => {
+            let op = match operator.op {
+                TsTypeOperatorOp::KeyOf => "keyof",
+                TsTypeOperatorOp::Unique => "unique",
+                TsTypeOperatorOp::ReadOnly => "readonly",
+            };
+            format!("{} {}", op, type_text(&operator.type_ann))
+        }
+        TsType::TsLitType(lit) => match &lit.lit {
+            TsLit::Str(value) => format!("'{}'", value.value),
+            TsLit::Number(value) => value.value.to_string(),
+            TsLit::Bool(value) => value.value.to_string(),
+            _ => "literal".to_string(),
+        },
+        TsType::TsFnOrConstructorType(TsFnOrConstructorType::TsFnType(function)) => format!("(...) => {}", type_text(&function.type_ann.type_ann)),
+        TsType::TsTypeLit(_) | TsType::TsMappedType(_) => "{ ... }".to_string(),
+        _ => "unknown".to_string(),
+    }
+}
+
+fn entity_name(name: &TsEntityName) -> String {
+    match name {
+        TsEntityName::Ident(ident) => ident.sym.to_string(),
+        TsEntityName::TsQualifiedName(qualified) => format!("{}.{}", entity_name(&qualified.left), qualified.right.sym),
+    }
+}
+
+const GUARD_METHODS: [&str; 6] = ["canActivate", "canActivateChild", "canDeactivate", "canMatch", "canLoad", "resolve"];
+
+fn param_names<'a>(pats: impl Iterator<Item = &'a Pat>) -> Vec<String> {
+    pats.filter_map(|pat| match pat {
+            Pat::Ident(ident) => Some(ident.id.sym.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+static GUARD_COMMENTS: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"(?s)/\*.*?\*/|//[^\n]*").unwrap());
+static GUARD_TOKENS: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"[A-Za-z_$][\w$]*|\d+|'[^']*'|\x22[^\x22]*\x22|`[^`]*`|\S").unwrap());
+
+// 引数名の違いを吸収するために $0, $1 ... に置き換え、空白やコメントの差を無視したトークン列にする
+fn normalize_guard_body(body: &str, params: &[String]) -> Vec<String> {
+    let body = GUARD_COMMENTS.replace_all(body, "");
+    GUARD_TOKENS.find_iter(&body)
+        .map(|token| match params.iter().position(|param| param == token.as_str()) {
+            Some(index) => format!("${}", index),
+            None => token.as_str().to_string(),
+        })
+        .collect()
+}
+
+pub(crate) const TEARDOWN_OPERATORS: [&str; 5] = ["takeUntil", "takeUntilDestroyed", "take", "first", "takeWhile"];
+
+struct RouterEventsCall {
+    span: Span,
+    operators: Vec<String>,
+    filters_event_type: bool,
+    handler_calls: Vec<String>,
+}
+
+#[derive(Default)]
+struct RouterEventsCollector {
+    calls: Vec<RouterEventsCall>,
+    stored_spans: Vec<Span>,
+}
+
+impl Visit for RouterEventsCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Some(call_info) = router_events_subscribe(call) {
+            self.calls.push(call_info);
+        }
+
+        // this.subscriptions.add(this.router.events.subscribe(...))
+        if callee_member_name(call) == Some("add") {
+            for arg in &call.args {
+                if let Expr::Call(inner) = &*arg.expr {
+                    self.stored_spans.push(inner.span);
+                }
+            }
+        }
+
+        call.visit_children_with(self);
+    }
+
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        if let Expr::Call(call) = &*assign.right {
+            self.stored_spans.push(call.span);
+        }
+        assign.visit_children_with(self);
+    }
+
+    fn visit_var_declarator(&mut self, declarator: &VarDeclarator) {
+        if let Some(init) = &declarator.init {
+            if let Expr::Call(call) = &**init {
+                self.stored_spans.push(call.span);
+            }
+        }
+        declarator.visit_children_with(self);
+    }
+
+    fn visit_class_prop(&mut self, prop: &ClassProp) {
+        if let Some(value) = &prop.value {
+            if let Expr::Call(call) = &**value {
+                self.stored_spans.push(call.span);
+            }
+        }
+        prop.visit_children_with(self);
+    }
+}
+
+fn callee_member_name(call: &CallExpr) -> Option<&str> {
+    if let Callee::Expr(callee) = &call.callee {
+        if let Expr::Member(member) = &**callee {
+            if let MemberProp::Ident(ident) = &member.prop {
+                return Some(ident.sym.as_ref());
+            }
+        }
+    }
+    None
+}
+
+fn member_callee_obj(call: &CallExpr) -> Option<&Expr> {
+    match &call.callee {
+        Callee::Expr(callee) => match &**callee {
+            Expr::Member(member) => Some(&*member.obj),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn unwind_pipes(mut target: &Expr) -> (&Expr, Vec<&ExprOrSpread>) {
+    let mut pipe_args = Vec::new();
+    while let Expr::Call(pipe_call) = target {
+        if callee_member_name(pipe_call) != Some("pipe") {
+            break;
+        }
+        match member_callee_obj(pipe_call) {
+            Some(obj) => {
+                pipe_args.splice(0..0, pipe_call.args.iter());
+                target = obj;
+            }
+            None => break,
+        }
+    }
+    (target, pipe_args)
+}
+
+fn router_events_subscribe(call: &CallExpr) -> Option<RouterEventsCall> {
+    if callee_member_name(call) != Some("subscribe") {
+        return None;
+    }
+
+    let target = member_callee_obj(call)?;
+
+    // .pipe(...) を遡って events まで辿る
+    let (target, pipe_args) = unwind_pipes(target);
+
+    match target {
+        Expr::Member(member) if is_router_expr(&member.obj) => {
+            if !matches!(&member.prop, MemberProp::Ident(ident) if ident.sym.as_ref() == "events") {
+                return None;
+            }
+        }
+        _ => return None,
+    }
+
+    let mut operators = Vec::new();
+    let mut filters_event_type = false;
+    for arg in pipe_args {
+        if let Expr::Call(op_call) = &*arg.expr {
+            if let Callee::Expr(callee) = &op_call.callee {
+                if let Expr::Ident(ident) = &**callee {
+                    let name = ident.sym.to_string();
+                    if name == "filter" {
+                        let mut finder = InstanceOfFinder::default();
+                        op_call.visit_with(&mut finder);
+                        filters_event_type |= finder.found;
+                    }
+                    operators.push(name);
+                }
+            }
+        }
+    }
+
+    let mut handler = HandlerCallCollector::default();
+    for arg in &call.args {
+        arg.visit_with(&mut handler);
+    }
+
+    Some(RouterEventsCall {
+        span: call.span,
+        operators,
+        filters_event_type,
+        handler_calls: handler.calls,
+    })
+}
+
+const MUTATING_METHODS: [&str; 9] = ["push", "pop", "shift", "unshift", "splice", "sort", "reverse", "fill", "copyWithin"];
+
+#[derive(Default)]
+struct StoreMutationCollector {
+    mutations: Vec<StoreMutation>,
+}
+
+impl Visit for StoreMutationCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let name = callee_member_name(call);
+        let selection = match (name, member_callee_obj(call)) {
+            (Some("subscribe"), Some(obj)) => Some(unwind_pipes(obj)),
+            (Some("pipe"), Some(obj)) => {
+                let (source, mut pipe_args) = unwind_pipes(obj);
+                pipe_args.extend(call.args.iter());
+                Some((source, pipe_args))
+            }
+            _ => None,
+        };
+
+        let pipe_args = match selection {
+            Some((source, pipe_args)) if is_store_selection(source, &pipe_args) => pipe_args,
+            _ => return call.visit_children_with(self),
+        };
+
+        let mut callbacks = Vec::new();
+        let mut cloned = false;
+        for arg in &pipe_args {
+            if let Expr::Call(op_call) = &*arg.expr {
+                callbacks.extend(op_call.args.iter().map(|op_arg| &*op_arg.expr));
+                // map で新しいオブジェクトを返した後はコピーなので対象外
+                if is_cloning_map(op_call) {
+                    cloned = true;
+                    break;
+                }
+            }
+        }
+        if name == Some("subscribe") && !cloned {
+            for arg in &call.args {
+                match &*arg.expr {
+                    Expr::Object(observer) => {
+                        for prop in &observer.props {
+                            if let PropOrSpread::Prop(prop) = prop {
+                                if let Prop::KeyValue(kv) = &**prop {
+                                    if matches!(&kv.key, PropName::Ident(key) if key.sym.as_ref() == "next") {
+                                        callbacks.push(&*kv.value);
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    expr => callbacks.push(expr),
+                }
+            }
+        }
+
+        for callback in callbacks {
+            let param = match callback {
+                Expr::Arrow(arrow) => arrow.params.first(),
+                Expr::Fn(fn_expr) => fn_expr.function.params.first().map(|p| &p.pat),
+                _ => None,
+            };
+            if let Some(Pat::Ident(param)) = param {
+                let mut finder = MutationFinder { param: param.id.sym.to_string(), mutations: Vec::new() };
+                match callback {
+                    Expr::Arrow(arrow) => arrow.body.visit_with(&mut finder),
+                    Expr::Fn(fn_expr) => fn_expr.function.body.visit_with(&mut finder),
+                    _ => {}
+                }
+                self.mutations.extend(finder.mutations);
+            }
+        }
+
+        // ネストしたチェーンのために引数だけ辿る
+        call.args.visit_with(self);
+    }
+}
+
+fn is_store_selection(source: &Expr, pipe_args: &[&ExprOrSpread]) -> bool {
+    match source {
+        // this.store.select(selectUser)
+        Expr::Call(call) => {
+            callee_member_name(call) == Some("select")
+                && member_callee_obj(call).is_some_and(is_store_expr)
+        }
+        // this.store.pipe(select(selectUser))
+        expr if is_store_expr(expr) => pipe_args.iter().any(|arg| {
+            matches!(&*arg.expr, Expr::Call(call) if matches!(&call.callee, Callee::Expr(callee) if matches!(&**callee, Expr::Ident(ident) if ident.sym.as_ref() == "select")))
+        }),
+        _ => false,
+    }
+}
+
+fn is_store_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Ident(ident) => ident.sym.to_lowercase().contains("store"),
+        Expr::Member(member) => match &member.prop {
+            MemberProp::Ident(ident) => ident.sym.to_lowercase().contains("store"),
+            _ => false,
+        },
+        Expr::Paren(paren) => is_store_expr(&paren.expr),
+        _ => false,
+    }
+}
+
+fn is_cloning_map(call: &CallExpr) -> bool {
+    let is_map = matches!(&call.callee, Callee::Expr(callee) if matches!(&**callee, Expr::Ident(ident) if ident.sym.as_ref() == "map"));
+    is_map && call.args.first().is_some_and(|arg| match &*arg.expr {
+        Expr::Arrow(arrow) => matches!(&*arrow.body, BlockStmtOrExpr::Expr(body) if is_fresh_literal(body)),
+        _ => false,
+    })
+}
+
+fn is_fresh_literal(expr: &Expr) -> bool {
+    match expr {
+        Expr::Object(_) | Expr::Array(_) => true,
+        Expr::Paren(paren) => is_fresh_literal(&paren.expr),
+        _ => false,
+    }
+}
+
+fn member_root(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.sym.as_ref()),
+        Expr::Member(member) => member_root(&member.obj),
+        Expr::Paren(paren) => member_root(&paren.expr),
+        _ => None,
+    }
+}
+
+struct MutationFinder {
+    param: String,
+    mutations: Vec<StoreMutation>,
+}
+
+impl MutationFinder {
+    fn record_member(&mut self, expr: &Expr, operation: &str) {
+        if let Expr::Member(_) = expr {
+            if member_root(expr) == Some(self.param.as_str()) {
+                self.mutations.push(StoreMutation {
+                    target: expr_path(expr),
+                    operation: operation.to_string(),
+                });
+            }
+        }
+    }
+}
+
+impl Visit for MutationFinder {
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        match &assign.left {
+            PatOrExpr::Expr(expr) => self.record_member(expr, "assignment"),
+            PatOrExpr::Pat(pat) => {
+                if let Pat::Expr(expr) = &**pat {
+                    self.record_member(expr, "assignment");
+                }
+            }
+        }
+        assign.visit_children_with(self);
+    }
+
+    fn visit_update_expr(&mut self, update: &UpdateExpr) {
+        self.record_member(&update.arg, "assignment");
+        update.visit_children_with(self);
+    }
+
+    fn visit_unary_expr(&mut self, unary: &UnaryExpr) {
+        if unary.op == UnaryOp::Delete {
+            self.record_member(&unary.arg, "delete");
+        }
+        unary.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Some(method) = callee_member_name(call) {
+            if let Some(obj) = member_callee_obj(call) {
+                if MUTATING_METHODS.contains(&method) && member_root(obj) == Some(self.param.as_str()) {
+                    self.mutations.push(StoreMutation {
+                        target: expr_path(obj),
+                        operation: method.to_string(),
+                    });
+                }
+                // Object.assign(user, ...)
+                if method == "assign" && matches!(obj, Expr::Ident(ident) if ident.sym.as_ref() == "Object") {
+                    if let Some(first) = call.args.first() {
+                        if member_root(&first.expr) == Some(self.param.as_str()) {
+                            self.mutations.push(StoreMutation {
+                                target: expr_path(&first.expr),
+                                operation: "Object.assign".to_string(),
+                            });
+                        }
+                    }
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+#[derive(Default)]
+struct LocationNavigationCollector {
+    navigations: Vec<LocationNavigation>,
+}
+
+impl Visit for LocationNavigationCollector {
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        let left = match &assign.left {
+            PatOrExpr::Expr(expr) => Some(&**expr),
+            PatOrExpr::Pat(pat) => match &**pat {
+                Pat::Expr(expr) => Some(&**expr),
+                _ => None,
+            },
+        };
+
+        if let Some(Expr::Member(member)) = left {
+            if let MemberProp::Ident(prop) = &member.prop {
+                // window.location.href = ... / window.location = ...
+                let method = match prop.sym.as_ref() {
+                    "href" if is_location_expr(&member.obj) => Some("href"),
+                    "location" if matches!(&*member.obj, Expr::Ident(obj) if matches!(obj.sym.as_ref(), "window" | "document")) => Some("location"),
+                    _ => None,
+                };
+                if let Some(method) = method {
+                    self.navigations.push(LocationNavigation {
+                        method: method.to_string(),
+                        target: static_url_prefix(&assign.right),
+                    });
+                }
+            }
+        }
+
+        assign.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let (Some(method @ ("assign" | "replace")), Some(obj)) = (callee_member_name(call), member_callee_obj(call)) {
+            if is_location_expr(obj) {
+                self.navigations.push(LocationNavigation {
+                    method: method.to_string(),
+                    target: call.args.first().and_then(|arg| static_url_prefix(&arg.expr)),
+                });
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+// this.location は Angular の Location なので対象外
+fn is_location_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Ident(ident) => ident.sym.as_ref() == "location",
+        Expr::Member(member) => {
+            matches!(&member.prop, MemberProp::Ident(prop) if prop.sym.as_ref() == "location")
+                && matches!(&*member.obj, Expr::Ident(obj) if matches!(obj.sym.as_ref(), "window" | "document"))
+        }
+        _ => false,
+    }
+}
+
+// 静的に分かる URL の先頭部分（'https://...'、`https://${host}` や 'https://' + path など）
+fn static_url_prefix(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(str_lit)) => Some(str_lit.value.to_string()),
+        Expr::Tpl(tpl) => tpl.quasis.first()
+            .map(|quasi| quasi.raw.to_string())
+            .filter(|prefix| !prefix.is_empty()),
+        Expr::Bin(bin) if bin.op == BinaryOp::Add => static_url_prefix(&bin.left),
+        Expr::Paren(paren) => static_url_prefix(&paren.expr),
+        _ => None,
+    }
+}
+
+fn is_router_expr(expr: &Expr) -> bool {
+    match expr {
+        Expr::Ident(ident) => ident.sym.to_lowercase().contains("router"),
+        Expr::Member(member) => match &member.prop {
+            MemberProp::Ident(ident) => ident.sym.to_lowercase().contains("router"),
+            _ => false,
+        },
+        // inject(Router).events
+        Expr::Call(call) => call.args.iter().any(|arg| {
+            matches!(&*arg.expr, Expr::Ident(ident) if ident.sym.as_ref() == "Router")
+        }),
+        Expr::Paren(paren) => is_router_expr(&paren.expr),
+        _ => false,
+    }
+}
+
+fn expr_path(expr: &Expr) -> String {
+    match expr {
+        Expr::This(_) => "this".to_string(),
+        Expr::Ident(ident) => ident.sym.to_string(),
+        Expr::Member(member) => match &member.prop {
+            MemberProp::Ident(ident) => format!("{}.{}", expr_path(&member.obj), ident.sym),
+            _ => format!("{}[]", expr_path(&member.obj)),
+        },
+        _ => "?".to_string(),
+    }
+}
+
+const HTTP_METHODS: [&str; 8] = ["get", "post", "put", "patch", "delete", "head", "options", "request"];
+
+#[derive(Default)]
+struct HttpRequestCollector {
+    clients: Vec<String>,
+    handled_depth: u32,
+    requests: Vec<HttpRequest>,
+}
+
+impl HttpRequestCollector {
+    fn is_client(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Member(member) => matches!(&*member.obj, Expr::This(_))
+                && matches!(&member.prop, MemberProp::Ident(prop) if self.clients.iter().any(|c| c == prop.sym.as_ref())),
+            Expr::Ident(ident) => self.clients.iter().any(|c| c == ident.sym.as_ref()),
+            _ => false,
+        }
+    }
+}
+
+impl Visit for HttpRequestCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let method = callee_member_name(call).unwrap_or_default();
+
+        if HTTP_METHODS.contains(&method) && member_callee_obj(call).is_some_and(|obj| self.is_client(obj)) {
+            // request(method, url) だけ URL が第 2 引数
+            let url_arg = if method == "request" { call.args.get(1) } else { call.args.first() };
+            self.requests.push(HttpRequest {
+                method: method.to_string(),
+                url: url_arg.and_then(|arg| url_pattern(&arg.expr)),
+                error_handled: self.handled_depth > 0,
+            });
+        }
+
+        // .pipe(catchError(...)) や subscribe のエラーハンドラーで処理されているか
+        let handles_error = match method {
+            "pipe" => call.args.iter().any(|arg| matches!(&*arg.expr, Expr::Call(op)
+                if matches!(&op.callee, Callee::Expr(callee) if matches!(&**callee, Expr::Ident(ident) if ident.sym.as_ref() == "catchError")))),
+            "subscribe" => call.args.len() > 1 || call.args.first().is_some_and(|arg| matches!(&*arg.expr, Expr::Object(observer)
+                if observer.props.iter().any(|prop| matches!(prop, PropOrSpread::Prop(prop) if matches!(&**prop,
+                    Prop::KeyValue(KeyValueProp { key: PropName::Ident(key), .. }) | Prop::Method(MethodProp { key: PropName::Ident(key), .. })
+                        if key.sym.as_ref() == "error"))))),
+            _ => false,
+        };
+
+        if handles_error {
+            self.handled_depth += 1;
+        }
+        call.visit_children_with(self);
+        if handles_error {
+            self.handled_depth -= 1;
+        }
+    }
+}
+
+// URL の式を、動的な部分を ${...} に置き換えた文字列にする
+fn url_pattern(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(str_lit)) => Some(str_lit.value.to_string()),
+        Expr::Tpl(tpl) => {
+            let mut pattern = String::new();
+            for (i, quasi) in tpl.quasis.iter().enumerate() {
+                pattern.push_str(&quasi.raw);
+                if let Some(expr) = tpl.exprs.get(i) {
+                    pattern.push_str(&format!("${{{}}}", expr_path(expr)));
+                }
+            }
+            Some(pattern)
+        }
+        Expr::Bin(bin) if bin.op == BinaryOp::Add => {
+            let left = url_pattern(&bin.left).unwrap_or_else(|| "${?}".to_string());
+            let right = url_pattern(&bin.right).unwrap_or_else(|| "${?}".to_string());
+            Some(format!("{}{}", left, right))
+        }
+        Expr::Ident(_) | Expr::Member(_) => Some(format!("${{{}}}", expr_path(expr))),
+        Expr::Paren(paren) => url_pattern(&paren.expr),
+        _ => None,
+    }
+}
+
+const TYPED_FORM_CLASSES: [&str; 4] = ["FormControl", "FormGroup", "FormArray", "FormRecord"];
+const UNTYPED_FORM_CLASSES: [&str; 3] = ["UntypedFormControl", "UntypedFormGroup", "UntypedFormArray"];
+
+#[derive(Default)]
+struct FormUsageCollector {
+    typed_builders: Vec<String>,
+    untyped_builders: Vec<String>,
+    usage: FormUsage,
+}
+
+impl Visit for FormUsageCollector {
+    fn visit_new_expr(&mut self, new_expr: &NewExpr) {
+        if let Expr::Ident(ident) = &*new_expr.callee {
+            let name = ident.sym.as_ref();
+            // new FormGroup<any>(...) は型付きにならない
+            let any_type_arg = new_expr.type_args.as_ref()
+                .is_some_and(|args| args.params.iter().any(|param| matches!(&**param, TsType::TsKeywordType(keyword) if keyword.kind == TsKeywordTypeKind::TsAnyKeyword)));
+            if UNTYPED_FORM_CLASSES.contains(&name) || (TYPED_FORM_CLASSES.contains(&name) && any_type_arg) {
+                self.usage.untyped += 1;
+            } else if TYPED_FORM_CLASSES.contains(&name) {
+                self.usage.typed += 1;
+            }
+        }
+        new_expr.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        // this.fb.group(...) は注入された FormBuilder の種類で判定する
+        if matches!(callee_member_name(call), Some("group" | "control" | "array" | "record")) {
+            let builder = member_callee_obj(call).map(|obj| match obj {
+                Expr::Member(member) => match &member.prop {
+                    MemberProp::Ident(prop) => prop.sym.to_string(),
+                    _ => String::new(),
+                },
+                Expr::Ident(ident) => ident.sym.to_string(),
+                _ => String::new(),
+            });
+            if let Some(builder) = builder {
+                if self.untyped_builders.contains(&builder) {
+                    self.usage.untyped += 1;
+                } else if self.typed_builders.contains(&builder) {
+                    self.usage.typed += 1;
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+fn is_signal_input_call(call: &CallExpr) -> bool {
+    match &call.callee {
+        Callee::Expr(callee) => match &**callee {
+            Expr::Ident(ident) => matches!(ident.sym.as_ref(), "input" | "model"),
+            Expr::Member(member) => matches!(&*member.obj, Expr::Ident(obj) if matches!(obj.sym.as_ref(), "input" | "model"))
+                && matches!(&member.prop, MemberProp::Ident(prop) if prop.sym.as_ref() == "required"),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+// 'alias' か { alias: 'alias' }
+fn alias_option(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(alias)) => Some(alias.value.to_string()),
+        Expr::Object(object) => object.props.iter().find_map(|prop| match prop {
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::KeyValue(kv) if matches!(&kv.key, PropName::Ident(key) if key.sym.as_ref() == "alias") => match &*kv.value {
+                    Expr::Lit(Lit::Str(alias)) => Some(alias.value.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+// 初期値のリテラルから TypeScript が推論する型
+fn literal_type(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(_)) | Expr::Tpl(_) => Some("string".to_string()),
+        Expr::Lit(Lit::Num(_)) => Some("number".to_string()),
+        Expr::Lit(Lit::Bool(_)) => Some("boolean".to_string()),
+        _ => None,
+    }
+}
+
+fn is_true_prop(prop: &Prop, key: &str) -> bool {
+    matches!(prop, Prop::KeyValue(kv)
+        if matches!(&kv.key, PropName::Ident(ident) if ident.sym.as_ref() == key)
+            && matches!(&*kv.value, Expr::Lit(Lit::Bool(value)) if value.value))
+}
+
+// styles: ['...'] と styles: `...` (Angular 17 以降は配列でなくてもよい)
+fn inline_styles_prop(prop: &Prop) -> Vec<String> {
+    let Prop::KeyValue(kv) = prop else { return Vec::new() };
+    if !matches!(&kv.key, PropName::Ident(ident) if ident.sym.as_ref() == "styles") {
+        return Vec::new();
+    }
+    let style_text = |expr: &Expr| match expr {
+        Expr::Lit(Lit::Str(str_lit)) => Some(str_lit.value.to_string()),
+        Expr::Tpl(tpl) if tpl.exprs.is_empty() => tpl.quasis.first().map(|quasi| quasi.raw.to_string()),
+        _ => None,
+    };
+    match &*kv.value {
+        Expr::Array(arr_lit) => arr_lit.elems.iter().flatten().filter_map(|elem| style_text(&elem.expr)).collect(),
+        expr => style_text(expr).into_iter().collect(),
+    }
+}
+
+// providers: [UserService, { provide: TOKEN, useClass: ... }] と viewProviders
+fn providers_prop(prop: &Prop) -> Vec<String> {
+    let Prop::KeyValue(kv) = prop else { return Vec::new() };
+    if !matches!(&kv.key, PropName::Ident(ident) if ["providers", "viewProviders"].contains(&ident.sym.as_ref())) {
+        return Vec::new();
+    }
+    match &*kv.value {
+        Expr::Array(arr_lit) => arr_lit.elems.iter().flatten().filter_map(|elem| provider_name(&elem.expr)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+// { provide: TOKEN, useClass: ... } は TOKEN を返す
+fn provider_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::Object(provider) => provider.props.iter()
+            .find_map(|prop| match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(kv) if matches!(&kv.key, PropName::Ident(k) if k.sym.as_ref() == "provide") => Some(expr_path(&kv.value)),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .filter(|name| !name.is_empty()),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct SpecCallCollector {
+    tests: u32,
+    disabled: u32,
+    disabled_depth: u32,
+}
+
+impl Visit for SpecCallCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        // it / xit / it.skip / describe.skip など
+        let (name, skipped) = match &call.callee {
+            Callee::Expr(callee) => match &**callee {
+                Expr::Ident(ident) => (ident.sym.to_string(), false),
+                Expr::Member(member) => match (&*member.obj, &member.prop) {
+                    (Expr::Ident(obj), MemberProp::Ident(prop)) => (obj.sym.to_string(), prop.sym.as_ref() == "skip"),
+                    _ => (String::new(), false),
+                },
+                _ => (String::new(), false),
+            },
+            _ => (String::new(), false),
+        };
+
+        match name.as_str() {
+            "it" | "test" if !skipped && self.disabled_depth == 0 => self.tests += 1,
+            "it" | "test" | "xit" | "xtest" => self.disabled += 1,
+            "xdescribe" => {}
+            "describe" if skipped => {}
+            _ => {
+                call.visit_children_with(self);
+                return;
+            }
+        }
+
+        let disables_block = name == "xdescribe" || (name == "describe" && skipped);
+        if disables_block {
+            self.disabled_depth += 1;
+        }
+        call.visit_children_with(self);
+        if disables_block {
+            self.disabled_depth -= 1;
+        }
+    }
+}
+
+#[derive(Default)]
+struct ChangeDetectionCallCollector {
+    app_refs: Vec<String>,
+    loop_depth: u32,
+    calls: Vec<ChangeDetectionCall>,
+}
+
+impl ChangeDetectionCallCollector {
+    fn in_loop<N: VisitWith<Self>>(&mut self, node: &N) {
+        self.loop_depth += 1;
+        node.visit_children_with(self);
+        self.loop_depth -= 1;
+    }
+
+    fn is_app_ref(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Ident(ident) => self.app_refs.iter().any(|name| ident.sym.as_ref() == name),
+            Expr::Member(member) => matches!(&*member.obj, Expr::This(_))
+                && matches!(&member.prop, MemberProp::Ident(prop) if self.app_refs.iter().any(|name| prop.sym.as_ref() == name)),
+            // inject(ApplicationRef).tick()
+            Expr::Call(call) => is_inject_of(call, "ApplicationRef"),
+            _ => false,
+        }
+    }
+}
+
+impl Visit for ChangeDetectionCallCollector {
+    fn visit_for_stmt(&mut self, stmt: &ForStmt) {
+        self.in_loop(stmt);
+    }
+
+    fn visit_for_of_stmt(&mut self, stmt: &ForOfStmt) {
+        self.in_loop(stmt);
+    }
+
+    fn visit_for_in_stmt(&mut self, stmt: &ForInStmt) {
+        self.in_loop(stmt);
+    }
+
+    fn visit_while_stmt(&mut self, stmt: &WhileStmt) {
+        self.in_loop(stmt);
+    }
+
+    fn visit_do_while_stmt(&mut self, stmt: &DoWhileStmt) {
+        self.in_loop(stmt);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let method = callee_member_name(call);
+        let is_manual_call = match method {
+            Some("tick") => member_callee_obj(call).is_some_and(|obj| self.is_app_ref(obj)),
+            Some("detectChanges") | Some("markForCheck") => true,
+            _ => false,
+        };
+        if is_manual_call {
+            self.calls.push(ChangeDetectionCall {
+                method: method.unwrap_or_default().to_string(),
+                in_loop: self.loop_depth > 0,
+            });
+        }
+
+        // コールバックが繰り返し呼ばれる呼び出しはループと同じ扱いにする
+        call.callee.visit_with(self);
+        if is_repeating_call(call) {
+            self.loop_depth += 1;
+            call.args.visit_with(self);
+            self.loop_depth -= 1;
+        } else {
+            call.args.visit_with(self);
+        }
+    }
+}
+
+// setInterval(...) / arr.forEach(...) / interval(1000).pipe(...).subscribe(...)
+fn is_repeating_call(call: &CallExpr) -> bool {
+    if let Callee::Expr(callee) = &call.callee {
+        if let Expr::Ident(ident) = &**callee {
+            return matches!(ident.sym.as_ref(), "setInterval" | "requestAnimationFrame");
+        }
+    }
+
+    match callee_member_name(call) {
+        Some("forEach") => true,
+        Some("subscribe") | Some("pipe") => {
+            let Some(obj) = member_callee_obj(call) else { return false };
+            match unwind_pipes(obj).0 {
+                Expr::Call(source) => match &source.callee {
+                    Callee::Expr(callee) => match &**callee {
+                        Expr::Ident(ident) => match ident.sym.as_ref() {
+                            "interval" | "animationFrames" => true,
+                            "timer" => source.args.len() > 1,
+                            _ => false,
+                        },
+                        _ => false,
+                    },
+                    _ => false,
+                },
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+#[derive(Default)]
+struct ZoneUsageCollector {
+    timer_depth: u32,
+    async_depth: u32,
+    usage: ZoneUsage,
+}
+
+impl ZoneUsageCollector {
+    fn record(&mut self, target: &Expr) {
+        let Some(field) = this_field(target) else { return };
+        let updates = if self.timer_depth > 0 {
+            &mut self.usage.timer_updates
+        } else if self.async_depth > 0 {
+            &mut self.usage.async_updates
+        } else {
+            return;
+        };
+        if !updates.iter().any(|name| name == field) {
+            updates.push(field.to_string());
+        }
+    }
+}
+
+impl Visit for ZoneUsageCollector {
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        match &assign.left {
+            PatOrExpr::Expr(expr) => self.record(expr),
+            PatOrExpr::Pat(pat) => {
+                if let Pat::Expr(expr) = &**pat {
+                    self.record(expr);
+                }
+            }
+        }
+        assign.visit_children_with(self);
+    }
+
+    fn visit_update_expr(&mut self, update: &UpdateExpr) {
+        self.record(&update.arg);
+        update.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let (Some(method), Some(obj)) = (callee_member_name(call), member_callee_obj(call)) {
+            if MUTATING_METHODS.contains(&method) {
+                self.record(obj);
+            }
+        }
+
+        call.callee.visit_with(self);
+        match zone_callback_kind(call) {
+            Some(ZoneCallback::Timer) => {
+                self.timer_depth += 1;
+                call.args.visit_with(self);
+                self.timer_depth -= 1;
+            }
+            Some(ZoneCallback::Async) => {
+                self.async_depth += 1;
+                call.args.visit_with(self);
+                self.async_depth -= 1;
+            }
+            None => call.args.visit_with(self),
+        }
+    }
+}
+
+#[derive(Default)]
+struct HostAccessCollector {
+    destroy_depth: u32,
+    // (リスナー, ハンドラーが this.xxx ならメソッド名)
+    listeners: Vec<(EventListenerUsage, Option<String>)>,
+    // ngOnDestroy で外している (target, event)
+    removed: Vec<(String, String)>,
+    native_element_writes: Vec<String>,
+    // pipe の中で処理済みの fromEvent(...)
+    handled_sources: Vec<Span>,
+}
+
+impl HostAccessCollector {
+    fn record_write(&mut self, target: &Expr) {
+        if let Some(path) = native_element_path(target) {
+            if !path.is_empty() && !self.native_element_writes.contains(&path) {
+                self.native_element_writes.push(path);
+            }
+        }
+    }
+
+    fn record_from_event(&mut self, call: &CallExpr, operators: &[&ExprOrSpread]) {
+        let (Some(target), Some(event)) = (call.args.first().and_then(|arg| global_target(&arg.expr)), string_arg(call, 1)) else { return };
+        self.handled_sources.push(call.span);
+        self.listeners.push((
+            EventListenerUsage {
+                target,
+                event,
+                source: "fromEvent".to_string(),
+                removed: true,
+                throttled: operators.iter().any(|operator| is_throttled(&*operator.expr)),
+            },
+            None,
+        ));
+    }
+}
+
+impl Visit for HostAccessCollector {
+    fn visit_class_method(&mut self, method: &ClassMethod) {
+        let is_destroy = matches!(&method.key, PropName::Ident(ident) if ident.sym.as_ref() == "ngOnDestroy");
+        if is_destroy {
+            self.destroy_depth += 1;
+        }
+        method.visit_children_with(self);
+        if is_destroy {
+            self.destroy_depth -= 1;
+        }
+    }
+
+    fn visit_assign_expr(&mut self, assign: &AssignExpr) {
+        match &assign.left {
+            PatOrExpr::Expr(expr) => self.record_write(expr),
+            PatOrExpr::Pat(pat) => {
+                if let Pat::Expr(expr) = &**pat {
+                    self.record_write(expr);
+                }
+            }
+        }
+        assign.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let method = callee_member_name(call);
+        let obj = member_callee_obj(call);
+        match (method, obj) {
+            // this.el.nativeElement.appendChild(...) / nativeElement.classList.add(...)
+            (Some(method), Some(obj)) if DOM_MUTATING_METHODS.contains(&method) => {
+                if let Some(path) = native_element_path(obj) {
+                    let write = if path.is_empty() { method.to_string() } else { format!("{}.{}", path, method) };
+                    if !self.native_element_writes.contains(&write) {
+                        self.native_element_writes.push(write);
+                    }
+                }
+            }
+            // window.addEventListener('resize', ...) / document.removeEventListener(...)
+            (Some(method @ ("addEventListener" | "removeEventListener")), Some(obj)) => {
+                if let (Some(target), Some(event)) = (global_target(obj), string_arg(call, 0)) {
+                    if method == "removeEventListener" {
+                        if self.destroy_depth > 0 {
+                            self.removed.push((target, event));
+                        }
+                    } else {
+                        let handler = call.args.get(1).map(|arg| &*arg.expr);
+                        // { signal: controller.signal } を渡していれば abort() で外せる
+                        let has_signal = call.args.get(2).is_some_and(|arg| matches!(&*arg.expr, Expr::Object(options)
+                            if options.props.iter().any(|prop| matches!(prop, PropOrSpread::Prop(prop) if prop_key(prop) == Some("signal")))));
+                        self.listeners.push((
+                            EventListenerUsage {
+                                target,
+                                event,
+                                source: "addEventListener".to_string(),
+                                removed: has_signal,
+                                throttled: handler.is_some_and(is_throttled),
+                            },
+                            handler.and_then(this_method_reference),
+                        ));
+                    }
+                }
+            }
+            // fromEvent(window, 'scroll').pipe(throttleTime(100))
+            (Some("pipe"), Some(obj)) => {
+                let (source, mut operators) = unwind_pipes(obj);
+                operators.extend(call.args.iter());
+                if let Expr::Call(source) = source {
+                    if is_from_event(source) && !self.handled_sources.contains(&source.span) {
+                        self.record_from_event(source, &operators);
+                    }
+                }
+            }
+            _ => {
+                if is_from_event(call) && !self.handled_sources.contains(&call.span) {
+                    self.record_from_event(call, &[]);
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+const DOM_MUTATING_METHODS: [&str; 11] = [
+    "appendChild", "removeChild", "insertBefore", "replaceChild", "remove", "append", "prepend",
+    "setAttribute", "removeAttribute", "add", "toggle",
+];
+
+// this.el.nativeElement.style.color -> Some("style.color")、this.el.nativeElement -> Some("")
+fn native_element_path(expr: &Expr) -> Option<String> {
+    let Expr::Member(member) = expr else { return None };
+    let MemberProp::Ident(prop) = &member.prop else { return None };
+    if prop.sym.as_ref() == "nativeElement" {
+        return Some(String::new());
+    }
+    let parent = native_element_path(&member.obj)?;
+    Some(if parent.is_empty() { prop.sym.to_string() } else { format!("{}.{}", parent, prop.sym) })
+}
+
+// window / document / document.body
+fn global_target(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(ident) if matches!(ident.sym.as_ref(), "window" | "document") => Some(ident.sym.to_string()),
+        Expr::Member(member) => match (&*member.obj, &member.prop) {
+            (Expr::Ident(obj), MemberProp::Ident(prop)) if obj.sym.as_ref() == "document" && prop.sym.as_ref() == "body" => Some("body".to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn is_from_event(call: &CallExpr) -> bool {
+    matches!(&call.callee, Callee::Expr(callee) if matches!(&**callee, Expr::Ident(ident) if ident.sym.as_ref() == "fromEvent"))
+}
+
+fn string_arg(call: &CallExpr, index: usize) -> Option<String> {
+    match call.args.get(index).map(|arg| &*arg.expr) {
+        Some(Expr::Lit(Lit::Str(str_lit))) => Some(str_lit.value.to_string()),
+        _ => None,
+    }
+}
+
+fn prop_key(prop: &Prop) -> Option<&str> {
+    match prop {
+        Prop::KeyValue(kv) => match &kv.key {
+            PropName::Ident(ident) => Some(ident.sym.as_ref()),
+            _ => None,
+        },
+        Prop::Shorthand(ident) => Some(ident.sym.as_ref()),
+        _ => None,
+    }
+}
+
+// this.onResize / this.onResize.bind(this)
+fn this_method_reference(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Member(member) => match (&*member.obj, &member.prop) {
+            (Expr::This(_), MemberProp::Ident(prop)) => Some(prop.sym.to_string()),
+            _ => None,
+        },
+        Expr::Call(call) if callee_member_name(call) == Some("bind") => member_callee_obj(call).and_then(this_method_reference),
+        _ => None,
+    }
+}
+
+// extends BaseComponent / extends WithLoading(WithDestroy(BaseComponent)) / implements OnInit, OnDestroy
+fn class_heritage(class: &Class) -> ClassHeritage {
+    let mut heritage = ClassHeritage {
+        implements: class.implements.iter().filter_map(|implemented| heritage_name(&implemented.expr)).collect(),
+        ..Default::default()
+    };
+
+    let mut super_class = class.super_class.as_deref();
+    while let Some(expr) = super_class {
+        match expr {
+            Expr::Call(call) => {
+                let Callee::Expr(callee) = &call.callee else { break };
+                heritage.mixins.extend(heritage_name(callee));
+                super_class = call.args.first().map(|arg| &*arg.expr);
+            }
+            _ => {
+                heritage.base_class = heritage_name(expr);
+                break;
+            }
+        }
+    }
+
+    for member in &class.body {
+        let ClassMember::Method(method) = member else { continue };
+        let PropName::Ident(key) = &method.key else { continue };
+        let mut finder = SuperCallFinder::default();
+        method.function.visit_with(&mut finder);
+        if finder.methods.iter().any(|name| name == key.sym.as_ref()) {
+            heritage.super_calls.push(key.sym.to_string());
+        }
+    }
+
+    heritage
+}
+
+// Base / shared.Base のように名前で参照しているときだけ
+fn heritage_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::Member(member) => match &member.prop {
+            MemberProp::Ident(prop) => Some(prop.sym.to_string()),
+            _ => None,
+        },
+        Expr::TsInstantiation(instantiation) => heritage_name(&instantiation.expr),
+        _ => None,
+    }
+}
+
+fn class_method<'a>(class: &'a Class, name: &str) -> Option<&'a Function> {
+    class.body.iter().find_map(|member| match member {
+        ClassMember::Method(method) if matches!(&method.key, PropName::Ident(ident) if ident.sym.as_ref() == name) => Some(&*method.function),
+        _ => None,
+    })
+}
+
+// debounce / throttle / auditTime / requestAnimationFrame の呼び出しか、Subject への next（受け側の pipe で間引く前提）
+fn is_throttled<N: VisitWith<ThrottleFinder>>(node: &N) -> bool {
+    let mut finder = ThrottleFinder::default();
+    node.visit_with(&mut finder);
+    finder.found
+}
+
+#[derive(Default)]
+struct ThrottleFinder {
+    found: bool,
+}
+
+impl Visit for ThrottleFinder {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let name = match &call.callee {
+            Callee::Expr(callee) => match &**callee {
+                Expr::Ident(ident) => Some(ident.sym.as_ref()),
+                Expr::Member(_) => callee_member_name(call),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(name) = name {
+            let lower = name.to_ascii_lowercase();
+            if lower.contains("debounce") || lower.contains("throttle") || matches!(name, "auditTime" | "sampleTime" | "requestAnimationFrame" | "next") {
+                self.found = true;
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+#[derive(Default)]
+struct RouteParamCollector {
+    // コンストラクタ・ngOnInit・フィールドの初期化の中
+    once_depth: u32,
+    usage: RouteParamUsage,
+}
+
+impl RouteParamCollector {
+    fn in_once<N: VisitWith<Self>>(&mut self, node: &N) {
+        self.once_depth += 1;
+        node.visit_children_with(self);
+        self.once_depth -= 1;
+    }
+
+    fn record(&mut self, name: String) {
+        if self.once_depth > 0 && !self.usage.snapshot_reads.contains(&name) {
+            self.usage.snapshot_reads.push(name);
+        }
+    }
+}
+
+impl Visit for RouteParamCollector {
+    fn visit_constructor(&mut self, constructor: &Constructor) {
+        self.in_once(constructor);
+    }
+
+    fn visit_class_method(&mut self, method: &ClassMethod) {
+        if matches!(&method.key, PropName::Ident(ident) if ident.sym.as_ref() == "ngOnInit") {
+            self.in_once(method);
+        } else {
+            method.visit_children_with(self);
+        }
+    }
+
+    fn visit_class_prop(&mut self, prop: &ClassProp) {
+        self.in_once(prop);
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        // route.snapshot.paramMap.get('id')
+        if callee_member_name(call) == Some("get") && member_callee_obj(call).is_some_and(is_snapshot_params) {
+            let name = match call.args.first().map(|arg| &*arg.expr) {
+                Some(Expr::Lit(Lit::Str(str_lit))) => str_lit.value.to_string(),
+                _ => "*".to_string(),
+            };
+            self.record(name);
+            call.args.visit_with(self);
+            return;
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        // route.snapshot.params.id / route.snapshot.params['id']
+        if is_snapshot_params(&member.obj) {
+            let name = match &member.prop {
+                MemberProp::Ident(ident) => ident.sym.to_string(),
+                MemberProp::Computed(computed) => match &*computed.expr {
+                    Expr::Lit(Lit::Str(str_lit)) => str_lit.value.to_string(),
+                    _ => "*".to_string(),
+                },
+                _ => "*".to_string(),
+            };
+            self.record(name);
+            return;
+        }
+        if is_snapshot_params_member(member) {
+            self.record("*".to_string());
+            return;
+        }
+        if matches!(&member.prop, MemberProp::Ident(prop) if matches!(prop.sym.as_ref(), "paramMap" | "params")) {
+            self.usage.observed = true;
+        }
+        member.visit_children_with(self);
+    }
+}
+
+// xxx.snapshot.paramMap / xxx.snapshot.params
+fn is_snapshot_params(expr: &Expr) -> bool {
+    matches!(expr, Expr::Member(member) if is_snapshot_params_member(member))
+}
+
+fn is_snapshot_params_member(member: &MemberExpr) -> bool {
+    matches!(&member.prop, MemberProp::Ident(prop) if matches!(prop.sym.as_ref(), "paramMap" | "params"))
+        && matches!(&*member.obj, Expr::Member(snapshot) if matches!(&snapshot.prop, MemberProp::Ident(prop) if prop.sym.as_ref() == "snapshot"))
+}
+
+enum ZoneCallback {
+    Timer,
+    Async,
+}
+
+// zone.js がパッチしている非同期 API。コールバックの後に変更検知が走ることを前提にした更新を探す
+fn zone_callback_kind(call: &CallExpr) -> Option<ZoneCallback> {
+    if let Callee::Expr(callee) = &call.callee {
+        if let Expr::Ident(ident) = &**callee {
+            return matches!(ident.sym.as_ref(), "setTimeout" | "setInterval" | "requestAnimationFrame")
+                .then_some(ZoneCallback::Timer);
+        }
+    }
+
+    match callee_member_name(call)? {
+        // window.setTimeout(...)
+        "setTimeout" | "setInterval" | "requestAnimationFrame" => Some(ZoneCallback::Timer),
+        "subscribe" => {
+            let source = unwind_pipes(member_callee_obj(call)?).0;
+            let is_timer = matches!(source, Expr::Call(source) if matches!(&source.callee, Callee::Expr(callee)
+                if matches!(&**callee, Expr::Ident(ident) if matches!(ident.sym.as_ref(), "interval" | "timer"))));
+            Some(if is_timer { ZoneCallback::Timer } else { ZoneCallback::Async })
+        }
+        "then" | "catch" | "finally" | "addEventListener" => Some(ZoneCallback::Async),
+        _ => None,
+    }
+}
+
+// this.items.push(...) / this.user.name = ... -> items / user
+fn this_field(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Member(member) => match (&*member.obj, &member.prop) {
+            (Expr::This(_), MemberProp::Ident(prop)) => Some(prop.sym.as_ref()),
+            (obj, _) => this_field(obj),
+        },
+        Expr::Paren(paren) => this_field(&paren.expr),
+        _ => None,
+    }
+}
+
+#[derive(Default)]
+struct TypeEscapeCollector {
+    escapes: TypeEscapes,
+}
+
+impl TypeEscapeCollector {
+    // as any の any は型注釈として数えない
+    fn cast(&mut self, expr: &Expr, ts_type: &TsType) {
+        if matches!(ts_type, TsType::TsKeywordType(keyword) if keyword.kind == TsKeywordTypeKind::TsAnyKeyword) {
+            self.escapes.any_casts += 1;
+        } else {
+            ts_type.visit_with(self);
+        }
+        expr.visit_with(self);
+    }
+}
+
+impl Visit for TypeEscapeCollector {
+    fn visit_ts_keyword_type(&mut self, keyword: &TsKeywordType) {
+        if keyword.kind == TsKeywordTypeKind::TsAnyKeyword {
+            self.escapes.any_annotations += 1;
+        }
+    }
+
+    fn visit_ts_as_expr(&mut self, as_expr: &TsAsExpr) {
+        self.cast(&as_expr.expr, &as_expr.type_ann);
+    }
+
+    fn visit_ts_type_assertion(&mut self, assertion: &TsTypeAssertion) {
+        self.cast(&assertion.expr, &assertion.type_ann);
+    }
+
+    fn visit_ts_non_null_expr(&mut self, non_null: &TsNonNullExpr) {
+        self.escapes.non_null_assertions += 1;
+        non_null.visit_children_with(self);
+    }
+}
+
+// { api: { key: '...' } } の文字列を api.key のような名前で集める
+fn collect_config_values(object: &ObjectLit, prefix: &str, content: &str, values: &mut Vec<ConfigValue>) {
+    for prop in &object.props {
+        let PropOrSpread::Prop(prop) = prop else { continue };
+        let Prop::KeyValue(key_value) = prop.as_ref() else { continue };
+        let key = match &key_value.key {
+            PropName::Ident(ident) => ident.sym.to_string(),
+            PropName::Str(str) => str.value.to_string(),
+            _ => continue,
+        };
+        let key = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+        let value = match unwrap_type_expr(&key_value.value) {
+            Expr::Lit(Lit::Str(str)) => str.value.to_string(),
+            Expr::Tpl(tpl) if tpl.exprs.is_empty() => tpl.quasis.iter().map(|quasi| quasi.raw.to_string()).collect(),
+            Expr::Object(nested) => {
+                collect_config_values(nested, &key, content, values);
+                continue;
+            }
+            _ => continue,
+        };
+        let offset = (key_value.value.span().lo.0 as usize).min(content.len());
+        values.push(ConfigValue { key, value, line: Some(content[..offset].matches('\n').count() as u32 + 1) });
+    }
+}
+
+// { ... } as const や satisfies Environment、括弧を外した式
+fn unwrap_type_expr(expr: &Expr) -> &Expr {
+    match expr {
+        Expr::TsAs(as_expr) => unwrap_type_expr(&as_expr.expr),
+        Expr::TsConstAssertion(assertion) => unwrap_type_expr(&assertion.expr),
+        Expr::TsSatisfies(satisfies) => unwrap_type_expr(&satisfies.expr),
+        Expr::Paren(paren) => unwrap_type_expr(&paren.expr),
+        expr => expr,
+    }
+}
+
+// environment.apiUrl のように、objects のいずれかから直接読んでいるフィールド
+struct MemberReadCollector {
+    objects: Vec<String>,
+    fields: Vec<String>,
+}
+
+impl Visit for MemberReadCollector {
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        if let (Expr::Ident(object), MemberProp::Ident(prop)) = (member.obj.as_ref(), &member.prop) {
+            if self.objects.iter().any(|name| *name == *object.sym) && !self.fields.iter().any(|field| *field == *prop.sym) {
+                self.fields.push(prop.sym.to_string());
+            }
+        }
+        member.visit_children_with(self);
+    }
+}
+
+// this.userService.load() と、コンストラクタ引数のまま呼ぶ userService.load()
+#[derive(Default)]
+struct ServiceCallCollector {
+    // 注入したフィールド・引数の名前とサービスのクラス名
+    fields: Vec<(String, String)>,
+    calls: Vec<ServiceCall>,
+}
+
+impl Visit for ServiceCallCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let field = match member_callee_obj(call) {
+            Some(Expr::Member(member)) if matches!(&*member.obj, Expr::This(_)) => match &member.prop {
+                MemberProp::Ident(prop) => Some(prop.sym.as_ref()),
+                _ => None,
+            },
+            Some(Expr::Ident(ident)) => Some(ident.sym.as_ref()),
+            _ => None,
+        };
+        let service = field.and_then(|field| self.fields.iter().find(|(name, _)| name == field));
+        if let (Some((_, service)), Some(method)) = (service, callee_member_name(call)) {
+            let service_call = ServiceCall { service: service.clone(), method: method.to_string() };
+            if !self.calls.contains(&service_call) {
+                self.calls.push(service_call);
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+#[derive(Default)]
+struct InjectCallCollector {
+    names: Vec<String>,
+}
+
+impl Visit for InjectCallCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let is_inject = matches!(&call.callee, Callee::Expr(callee) if matches!(&**callee, Expr::Ident(ident) if ident.sym.as_ref() == "inject"));
+        if is_inject {
+            if let Some(Expr::Ident(ident)) = call.args.first().map(|arg| &*arg.expr) {
+                self.names.push(ident.sym.to_string());
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+fn is_inject_of(call: &CallExpr, type_name: &str) -> bool {
+    matches!(&call.callee, Callee::Expr(callee) if matches!(&**callee, Expr::Ident(ident) if ident.sym.as_ref() == "inject"))
+        && call.args.first().is_some_and(|arg| matches!(&*arg.expr, Expr::Ident(ident) if ident.sym.as_ref() == type_name))
+}
+
+#[derive(Default)]
+struct SelectionChainCollector {
+    chains: Vec<(&'static str, String, u32, Span)>,
+}
+
+impl Visit for SelectionChainCollector {
+    fn visit_switch_stmt(&mut self, switch: &SwitchStmt) {
+        let branches = switch.cases.iter()
+            .filter(|case| references_component(&case.cons))
+            .count() as u32;
+        self.chains.push(("switch", expr_path(&switch.discriminant), branches, switch.span));
+        switch.visit_children_with(self);
+    }
+
+    fn visit_if_stmt(&mut self, if_stmt: &IfStmt) {
+        // else if は先頭の if からまとめて辿り、個別の連鎖としては数えない
+        let mut discriminators = Vec::new();
+        let mut branches = 0;
+        let mut current = if_stmt;
+        loop {
+            discriminators.push(compared_path(&current.test));
+            if references_component(&*current.cons) {
+                branches += 1;
+            }
+            current.test.visit_with(self);
+            current.cons.visit_with(self);
+            match current.alt.as_deref() {
+                Some(Stmt::If(next)) => current = next,
+                Some(alt) => {
+                    alt.visit_with(self);
+                    break;
+                }
+                None => break,
+            }
+        }
+
+        if let Some(Some(first)) = discriminators.first() {
+            if discriminators.len() > 1 && discriminators.iter().all(|d| d.as_ref() == Some(first)) {
+                self.chains.push(("if-else", first.clone(), branches, if_stmt.span));
+            }
+        }
+    }
+}
+
+// `x.type === 'chart'` / `x.type === WidgetType.Chart` の左辺
+fn compared_path(test: &Expr) -> Option<String> {
+    match test {
+        Expr::Paren(paren) => compared_path(&paren.expr),
+        Expr::Bin(bin) if matches!(bin.op, BinaryOp::EqEqEq | BinaryOp::EqEq) => {
+            match &*bin.right {
+                Expr::Lit(_) | Expr::Member(_) => Some(expr_path(&bin.left)).filter(|path| !path.contains('?')),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn references_component<N: VisitWith<ComponentReferenceFinder>>(node: &N) -> bool {
+    let mut finder = ComponentReferenceFinder::default();
+    node.visit_with(&mut finder);
+    finder.found
+}
+
+#[derive(Default)]
+struct ComponentReferenceFinder {
+    found: bool,
+}
+
+impl Visit for ComponentReferenceFinder {
+    fn visit_ident(&mut self, ident: &Ident) {
+        let name = ident.sym.as_ref();
+        if name.len() > "Component".len() && name.ends_with("Component") && name.starts_with(|c: char| c.is_ascii_uppercase()) {
+            self.found = true;
+        }
+    }
+}
+
+// 配列全体をたどって並べ替え・絞り込みをする呼び出し
+const ARRAY_OPERATIONS: [&str; 5] = ["sort", "filter", "reduce", "flatMap", "toSorted"];
+
+#[derive(Default)]
+struct ArrayOperationFinder {
+    operations: Vec<String>,
+}
+
+impl Visit for ArrayOperationFinder {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Some(name) = callee_member_name(call).filter(|name| ARRAY_OPERATIONS.contains(name)) {
+            if !self.operations.iter().any(|operation| operation == name) {
+                self.operations.push(name.to_string());
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+// super.xxx() で呼んでいる親のメソッド
+#[derive(Default)]
+struct SuperCallFinder {
+    methods: Vec<String>,
+}
+
+impl Visit for SuperCallFinder {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            if let Expr::SuperProp(SuperPropExpr { prop: SuperProp::Ident(prop), .. }) = &**callee {
+                self.methods.push(prop.sym.to_string());
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+#[derive(Default)]
+struct InstanceOfFinder {
+    found: bool,
+}
+
+impl Visit for InstanceOfFinder {
+    fn visit_bin_expr(&mut self, bin: &BinExpr) {
+        if bin.op == BinaryOp::InstanceOf {
+            self.found = true;
+        }
+        bin.visit_children_with(self);
+    }
+}
+
+#[derive(Default)]
+struct HandlerCallCollector {
+    calls: Vec<String>,
+}
+
+impl Visit for HandlerCallCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            self.calls.push(expr_path(callee));
+        }
+        call.visit_children_with(self);
+    }
+}
+
+#[derive(Default)]
+struct LazyRouteCollector {
+    routes: Vec<(Option<String>, String, Span)>,
+}
+
+impl Visit for LazyRouteCollector {
+    fn visit_key_value_prop(&mut self, prop: &KeyValueProp) {
+        if matches!(&prop.key, PropName::Ident(key) if matches!(key.sym.as_ref(), "loadChildren" | "loadComponent")) {
+            match &*prop.value {
+                Expr::Lit(Lit::Str(str_lit)) => {
+                    let value = str_lit.value.to_string();
+                    let (path, module_name) = match value.split_once('#') {
+                        Some((path, module_name)) => (path.to_string(), Some(module_name.to_string())),
+                        None => (value, None),
+                    };
+                    self.routes.push((module_name, path, prop.span()));
+                }
+                value => {
+                    let mut finder = DynamicImportFinder::default();
+                    value.visit_with(&mut finder);
+                    if let Some(path) = finder.path {
+                        self.routes.push((finder.module, path, prop.span()));
+                    }
+                }
+            }
+        }
+        prop.visit_children_with(self);
+    }
+}
+
+#[derive(Default)]
+struct RenderRootCollector {
+    bootstrap: Vec<String>,
+    routed: Vec<String>,
+    parameterized: Vec<ParameterizedRoute>,
+}
+
+impl Visit for RenderRootCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            if matches!(&**callee, Expr::Ident(ident) if ident.sym.as_ref() == "bootstrapApplication") {
+                if let Some(Expr::Ident(component)) = call.args.first().map(|arg| &*arg.expr) {
+                    self.bootstrap.push(component.sym.to_string());
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_object_lit(&mut self, object: &ObjectLit) {
+        let props: Vec<&KeyValueProp> = object.props.iter()
+            .filter_map(|prop| match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(kv) => Some(kv),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        let path = props.iter()
+            .find(|kv| matches!(&kv.key, PropName::Ident(key) if key.sym.as_ref() == "path"))
+            .map(|kv| match &*kv.value {
+                Expr::Lit(Lit::Str(str_lit)) => str_lit.value.to_string(),
+                _ => String::new(),
+            });
+        if let Some(path) = path {
+            for kv in &props {
+                let PropName::Ident(key) = &kv.key else { continue };
+                let component = match (key.sym.as_ref(), &*kv.value) {
+                    ("component", Expr::Ident(component)) => {
+                        self.routed.push(component.sym.to_string());
+                        Some(component.sym.to_string())
+                    }
+                    ("loadComponent", value) => {
+                        let mut finder = DynamicImportFinder::default();
+                        value.visit_with(&mut finder);
+                        finder.module
+                    }
+                    _ => None,
+                };
+                if let Some(component) = component.filter(|_| path.split('/').any(|segment| segment.starts_with(':'))) {
+                    self.parameterized.push(ParameterizedRoute { component, path: path.clone() });
+                }
+            }
+        }
+        object.visit_children_with(self);
+    }
+}
+
+// () => import('./admin/admin.module').then(m => m.AdminModule)
+#[derive(Default)]
+struct DynamicImportFinder {
+    path: Option<String>,
+    module: Option<String>,
+}
+
+impl Visit for DynamicImportFinder {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if matches!(call.callee, Callee::Import(_)) {
+            if let Some(Expr::Lit(Lit::Str(str_lit))) = call.args.first().map(|arg| &*arg.expr) {
+                self.path.get_or_insert_with(|| str_lit.value.to_string());
+            }
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_member_expr(&mut self, member: &MemberExpr) {
+        if let (Expr::Ident(_), MemberProp::Ident(prop)) = (&*member.obj, &member.prop) {
+            self.module = Some(prop.sym.to_string());
+        }
+        member.visit_children_with(self);
+    }
+}
+
+// ルート定義でコンポーネント・モジュール・ガード・リゾルバーを指定するキー
+const ROUTE_REFERENCE_KEYS: [&str; 9] = [
+    "component",
+    "loadComponent",
+    "loadChildren",
+    "canActivate",
+    "canActivateChild",
+    "canDeactivate",
+    "canMatch",
+    "canLoad",
+    "resolve",
+];
+
+struct SymbolReferenceCollector<'a> {
+    symbol: &'a str,
+    // 注入しているクラスを示すため、外側から順に積む
+    classes: Vec<String>,
+    // (種類, 位置, クラス, import の from)
+    references: Vec<(&'static str, Span, Option<String>, Option<String>)>,
+}
+
+impl SymbolReferenceCollector<'_> {
+    fn is_symbol(&self, ident: &Ident) -> bool {
+        ident.sym.as_ref() == self.symbol
+    }
+
+    fn inject(&mut self, span: Span) {
+        let owner = self.classes.last().cloned();
+        self.references.push(("injection", span, owner, None));
+    }
+}
+
+impl Visit for SymbolReferenceCollector<'_> {
+    fn visit_import_decl(&mut self, import: &ImportDecl) {
+        let imported = import.specifiers.iter().any(|specifier| match specifier {
+            ImportSpecifier::Named(named) => match &named.imported {
+                Some(ModuleExportName::Ident(ident)) => self.is_symbol(ident),
+                _ => self.is_symbol(&named.local),
+            },
+            ImportSpecifier::Default(default) => self.is_symbol(&default.local),
+            ImportSpecifier::Namespace(_) => false,
+        });
+        if imported {
+            self.references.push(("import", import.span, None, Some(import.src.value.to_string())));
+        }
+    }
+
+    fn visit_class_decl(&mut self, class: &ClassDecl) {
+        self.classes.push(class.ident.sym.to_string());
+        class.visit_children_with(self);
+        self.classes.pop();
+    }
+
+    // constructor(private users: UserService)
+    fn visit_constructor(&mut self, constructor: &Constructor) {
+        for param in &constructor.params {
+            let binding = match param {
+                ParamOrTsParamProp::Param(Param { pat: Pat::Ident(binding), .. }) => binding,
+                ParamOrTsParamProp::TsParamProp(TsParamProp { param: TsParamPropParam::Ident(binding), .. }) => binding,
+                _ => continue,
+            };
+            let Some(type_ann) = &binding.type_ann else { continue };
+            if let TsType::TsTypeRef(TsTypeRef { type_name: TsEntityName::Ident(ident), span, .. }) = &*type_ann.type_ann {
+                if self.is_symbol(ident) {
+                    self.inject(*span);
+                }
+            }
+        }
+        constructor.visit_children_with(self);
+    }
+
+    // inject(UserService)
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if let Callee::Expr(callee) = &call.callee {
+            if matches!(&**callee, Expr::Ident(ident) if ident.sym.as_ref() == "inject") {
+                if let Some(Expr::Ident(ident)) = call.args.first().map(|arg| &*arg.expr) {
+                    if self.is_symbol(ident) {
+                        self.inject(call.span);
+                    }
+                }
+            }
+        }
+        call.visit_children_with(self);
+    }
+
+    fn visit_object_lit(&mut self, object: &ObjectLit) {
+        let props: Vec<&KeyValueProp> = object.props.iter()
+            .filter_map(|prop| match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(kv) => Some(kv),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+        let is_route = props.iter().any(|kv| matches!(&kv.key, PropName::Ident(key) if key.sym.as_ref() == "path"));
+        for kv in props.iter().filter(|_| is_route) {
+            let PropName::Ident(key) = &kv.key else { continue };
+            if !ROUTE_REFERENCE_KEYS.contains(&key.sym.as_ref()) {
+                continue;
+            }
+            let referenced = match key.sym.as_ref() {
+                "loadComponent" | "loadChildren" => {
+                    let mut finder = DynamicImportFinder::default();
+                    kv.value.visit_with(&mut finder);
+                    finder.module.as_deref() == Some(self.symbol)
+                }
+                _ => {
+                    let mut finder = IdentFinder { name: self.symbol, found: false };
+                    kv.value.visit_with(&mut finder);
+                    finder.found
+                }
+            };
+            if referenced {
+                self.references.push(("route", key.span, None, None));
+            }
+        }
+        object.visit_children_with(self);
+    }
+}
+
+struct IdentFinder<'a> {
+    name: &'a str,
+    found: bool,
+}
+
+impl Visit for IdentFinder<'_> {
+    fn visit_ident(&mut self, ident: &Ident) {
+        self.found |= ident.sym.as_ref() == self.name;
+    }
+}
+
+// アプリケーション全体で 1 つだけ使われることを前提にしたフレームワークのトークン
+const FRAMEWORK_PROVIDER_TOKENS: [&str; 7] = [
+    "ErrorHandler",
+    "RouteReuseStrategy",
+    "HTTP_INTERCEPTORS",
+    "APP_BASE_HREF",
+    "TitleStrategy",
+    "LocationStrategy",
+    "UrlSerializer",
+];
+
+struct ProviderOverrideCollector {
+    // (scope, injector)。外側から順に積む
+    contexts: Vec<(String, String)>,
+    // false ならフレームワークのトークンだけを集める
+    all: bool,
+    overrides: Vec<(ProviderOverride, Span)>,
+}
+
+impl ProviderOverrideCollector {
+    fn with_context(&mut self, scope: &str, injector: String, visit: impl FnOnce(&mut Self)) {
+        self.contexts.push((scope.to_string(), injector));
+        visit(self);
+        self.contexts.pop();
+    }
+
+    fn push(&mut self, token: String, implementation: Option<String>, multi: bool, span: Span) {
+        let (scope, injector) = self.contexts.last().cloned().unwrap_or_default();
+        self.overrides.push((ProviderOverride {
+            token,
+            implementation,
+            multi,
+            scope,
+            injector,
+            file_path: String::new(),
+            line: None,
+        }, span));
+    }
+
+    fn collect(&mut self, providers: &ArrayLit) {
+        for element in providers.elems.iter().flatten() {
+            // providers: [OrdersService] は { provide: OrdersService, useClass: OrdersService } と同じ
+            if let (true, Expr::Ident(ident)) = (self.all, &*element.expr) {
+                self.push(ident.sym.to_string(), None, false, ident.span);
+                continue;
+            }
+            let Expr::Object(provider) = &*element.expr else { continue };
+            let value = |key: &str| provider.props.iter().find_map(|prop| match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(kv) if matches!(&kv.key, PropName::Ident(k) if k.sym.as_ref() == key) => Some(&*kv.value),
+                    _ => None,
+                },
+                _ => None,
+            });
+            let Some(token) = value("provide").map(expr_path) else { continue };
+            if !self.all && !FRAMEWORK_PROVIDER_TOKENS.contains(&token.as_str()) {
+                continue;
+            }
+            let implementation = ["useClass", "useExisting", "useFactory", "useValue"].iter()
+                .find_map(|key| value(key))
+                .map(|expr| match expr {
+                    Expr::Lit(Lit::Str(str_lit)) => format!("'{}'", str_lit.value),
+                    Expr::Arrow(_) | Expr::Fn(_) => "factory".to_string(),
+                    expr => expr_path(expr),
+                });
+            let multi = matches!(value("multi"), Some(Expr::Lit(Lit::Bool(flag))) if flag.value);
+            self.push(token, implementation, multi, provider.span);
+        }
+    }
+}
+
+impl Visit for ProviderOverrideCollector {
+    fn visit_class_decl(&mut self, class_decl: &ClassDecl) {
+        let name = class_decl.ident.sym.to_string();
+        for decorator in &class_decl.class.decorators {
+            let Expr::Call(call) = &*decorator.expr else { continue };
+            let scope = match &call.callee {
+                Callee::Expr(callee) => match &**callee {
+                    Expr::Ident(ident) if matches!(ident.sym.as_ref(), "Component" | "Directive") => "component",
+                    Expr::Ident(ident) if ident.sym.as_ref() == "NgModule" => "module",
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            self.with_context(scope, name.clone(), |collector| decorator.visit_with(collector));
+        }
+        class_decl.class.body.visit_with(self);
+    }
+
+    fn visit_var_declarator(&mut self, declarator: &VarDeclarator) {
+        match &declarator.name {
+            Pat::Ident(ident) => self.with_context("application", ident.id.sym.to_string(), |collector| declarator.init.visit_with(collector)),
+            _ => declarator.visit_children_with(self),
+        }
+    }
+
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        let callee = match &call.callee {
+            Callee::Expr(callee) => match &**callee {
+                Expr::Ident(ident) => Some(ident.sym.to_string()),
+                _ => None,
+            },
+            _ => None,
+        };
+        match callee.as_deref() {
+            Some("bootstrapApplication") => self.with_context("application", "bootstrapApplication".to_string(), |collector| call.visit_children_with(collector)),
+            Some("makeEnvironmentProviders") => {
+                if let Some(Expr::Array(providers)) = call.args.first().map(|arg| &*arg.expr) {
+                    self.collect(providers);
+                }
+                call.visit_children_with(self);
+            }
+            // provideHttpClient(withInterceptors([authInterceptor])) は HTTP_INTERCEPTORS に multi: true で足すのと同じ
+            Some("withInterceptors") if self.all => {
+                if let Some(Expr::Array(interceptors)) = call.args.first().map(|arg| &*arg.expr) {
+                    for element in interceptors.elems.iter().flatten() {
+                        if let Expr::Ident(ident) = &*element.expr {
+                            self.push("HTTP_INTERCEPTORS".to_string(), Some(ident.sym.to_string()), true, ident.span);
+                        }
+                    }
+                }
+                call.visit_children_with(self);
+            }
+            _ => call.visit_children_with(self),
+        }
+    }
+
+    // { path: 'admin', providers: [...] } はそのルートの環境インジェクターに入る
+    fn visit_object_lit(&mut self, object: &ObjectLit) {
+        let path = object.props.iter().find_map(|prop| match prop {
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::KeyValue(kv) if matches!(&kv.key, PropName::Ident(k) if k.sym.as_ref() == "path") => match &*kv.value {
+                    Expr::Lit(Lit::Str(str_lit)) => Some(str_lit.value.to_string()),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        });
+        match path {
+            Some(path) => self.with_context("route", format!("route '{}'", path), |collector| object.visit_children_with(collector)),
+            None => object.visit_children_with(self),
+        }
+    }
+
+    fn visit_key_value_prop(&mut self, prop: &KeyValueProp) {
+        if matches!(&prop.key, PropName::Ident(key) if matches!(key.sym.as_ref(), "providers" | "viewProviders")) {
+            if let Expr::Array(providers) = &*prop.value {
+                self.collect(providers);
+            }
+        }
+        prop.visit_children_with(self);
+    }
+}
+
+#[derive(Default)]
+struct DynamicImportCollector {
+    imports: Vec<(String, Span)>,
+}
+
+impl Visit for DynamicImportCollector {
+    fn visit_call_expr(&mut self, call: &CallExpr) {
+        if matches!(call.callee, Callee::Import(_)) {
+            // 式を含まないテンプレートリテラル import(`./x`) も同じく扱う
+            let source_module = match call.args.first().map(|arg| &*arg.expr) {
+                Some(Expr::Lit(Lit::Str(str_lit))) => Some(str_lit.value.to_string()),
+                Some(Expr::Tpl(tpl)) if tpl.exprs.is_empty() => tpl.quasis.first().map(|quasi| quasi.raw.to_string()),
+                _ => None,
+            };
+            if let Some(source_module) = source_module {
+                self.imports.push((source_module, call.span));
+            }
+        }
+        call.visit_children_with(self);
+    }
+}
+
+// import 元のファイルからの相対パスを、拡張子なしのプロジェクト内パスにする
+fn resolve_import_path(file_path: &Path, import_path: &str) -> String {
+    let import_path = import_path.strip_suffix(".ts").unwrap_or(import_path);
+    if !import_path.starts_with('.') {
+        return import_path.to_string();
+    }
+    resolve_relative_path(file_path, import_path)
+}
+
+// templateUrl や import のように、ファイルからの相対パスを正規化したパスにする
+// Order[]・Array<Order>・Observable<Order[]>・Signal<Order[]> などの要素の型名
+pub fn element_type(type_text: &str) -> Option<String> {
+    let array = regex::Regex::new(r"(?:ReadonlyArray|Array)<([A-Za-z_$][\w$.]*)>|([A-Za-z_$][\w$.]*)\[\]").unwrap();
+    array.captures(type_text).and_then(|caps| caps.get(1).or(caps.get(2))).map(|name| name.as_str().to_string())
+}
+
+// trackBy / track に使える、要素を見分けるメンバー
+pub fn identity_member(members: &[String]) -> Option<&str> {
+    ["id", "uuid", "key", "slug", "code"].into_iter()
+        .find(|name| members.iter().any(|member| member == name))
+        .or_else(|| members.iter().map(String::as_str).find(|member| member.ends_with("Id")))
+}
+
+pub fn resolve_relative_path(file_path: &Path, relative: &str) -> String {
+    let dir = file_path.parent().map(|dir| dir.display().to_string().replace('\\', "/")).unwrap_or_default();
+    let mut segments: Vec<&str> = dir.split('/').filter(|s| !s.is_empty() && *s != ".").collect();
+    for segment in relative.split('/') {
+        match segment {
+            "." | "" => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    let resolved = segments.join("/");
+    if dir.starts_with('/') {
+        format!("/{}", resolved)
+    } else {
+        resolved
+    }
+}
+
+// const の初期値が中身を書き換えられる値なら、その種類
+fn mutable_value_kind(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Paren(paren) => mutable_value_kind(&paren.expr),
+        Expr::TsSatisfies(satisfies) => mutable_value_kind(&satisfies.expr),
+        Expr::TsAs(ts_as) if is_readonly_type(&ts_as.type_ann) => None,
+        Expr::TsAs(ts_as) => mutable_value_kind(&ts_as.expr),
+        Expr::TsConstAssertion(_) => None,
+        Expr::Object(_) => Some("object".to_string()),
+        Expr::Array(_) => Some("array".to_string()),
+        Expr::New(new_expr) => match &*new_expr.callee {
+            Expr::Ident(ident) if matches!(&*ident.sym, "Map" | "Set") => Some(ident.sym.to_string()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Readonly<T> / ReadonlyArray<T> / readonly T[] は TypeScript が書き換えを禁じる
+fn is_readonly_type(ts_type: &TsType) -> bool {
+    match ts_type {
+        TsType::TsTypeOperator(operator) => matches!(operator.op, TsTypeOperatorOp::ReadOnly),
+        TsType::TsTypeRef(type_ref) => matches!(&type_ref.type_name, TsEntityName::Ident(ident) if ident.sym.starts_with("Readonly")),
+        _ => false,
+    }
+}
+
+
+// コンストラクタ引数の型に、まだ含まれていない inject() の引数を足す
+fn injection_dependencies(injection: &InjectionUsage) -> Vec<String> {
+    let mut dependencies = injection.constructor.clone();
+    for name in &injection.inject {
+        if !dependencies.contains(name) {
+            dependencies.push(name.clone());
+        }
+    }
+    dependencies
+}
+
+// 空白とコメントを除いたソースの FNV-1a。書式やコメントだけが違うコピーも同じ値になる
+fn body_hash(text: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut feed = |c: char| {
+        let mut buffer = [0u8; 4];
+        for byte in c.encode_utf8(&mut buffer).bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    };
+
+    let mut chars = text.chars().peekable();
+    let mut quote: Option<char> = None;
+    while let Some(c) = chars.next() {
+        if let Some(open) = quote {
+            feed(c);
+            if c == '\\' {
+                if let Some(escaped) = chars.next() {
+                    feed(escaped);
+                }
+            } else if c == open {
+                quote = None;
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('/')) => {
+                while chars.peek().is_some_and(|next| *next != '\n') {
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for next in chars.by_ref() {
+                    if previous == '*' && next == '/' {
+                        break;
+                    }
+                    previous = next;
+                }
+            }
+            ('\'' | '"' | '`', _) => {
+                quote = Some(c);
+                feed(c);
+            }
+            (c, _) if c.is_whitespace() => {}
+            (c, _) => feed(c),
+        }
+    }
+    format!("{:016x}", hash)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_router_event_subscriptions() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Component({ selector: 'app-a', template: '' })
+export class AComponent implements OnDestroy {
+  private sub = this.router.events.subscribe(() => this.title.setTitle('a'));
+
+  constructor(private router: Router, private title: Title) {
+    this.router.events
+      .pipe(filter(e => e instanceof NavigationEnd), takeUntilDestroyed())
+      .subscribe(() => window.scrollTo(0, 0));
+  }
+
+  ngOnInit() {
+    this.router.events.subscribe(e => this.track(e));
+  }
+
+  ngOnDestroy() {
+    this.sub.unsubscribe();
+  }
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let component = parser.extract_component(&module, Path::new("a.component.ts")).unwrap().unwrap();
+        let subscriptions = component.router_event_subscriptions;
+
+        assert_eq!(subscriptions.len(), 3);
+        assert!(!subscriptions[0].filters_event_type);
+        assert!(subscriptions[0].has_teardown);
+        assert!(subscriptions[1].filters_event_type);
+        assert!(subscriptions[1].has_teardown);
+        assert_eq!(subscriptions[1].handler_signature, "filter|takeUntilDestroyed=>window.scrollTo");
+        assert!(!subscriptions[2].has_teardown);
+    }
+
+    #[test]
+    fn test_store_mutations() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Injectable({ providedIn: 'root' })
+export class CartService {
+  constructor(private store: Store) {
+    this.store.select(selectCart).subscribe(cart => {
+      cart.items.push(item);
+      cart.total = 0;
+    });
+    this.store.pipe(select(selectUser), tap(user => { delete user.token; })).subscribe();
+    this.store.select(selectCart).pipe(map(cart => ({ ...cart }))).subscribe(copy => { copy.total = 1; });
+    this.other.select(selectCart).subscribe(cart => { cart.total = 2; });
+  }
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let service = parser.extract_service(&module, Path::new("cart.service.ts")).unwrap().unwrap();
+        let mutations: Vec<_> = service.store_mutations.iter()
+            .map(|m| format!("{}:{}", m.target, m.operation))
+            .collect();
+
+        assert_eq!(mutations, vec!["cart.items:push", "cart.total:assignment", "user.token:delete"]);
+    }
+
+    #[test]
+    fn test_location_navigations() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Injectable({ providedIn: 'root' })
+export class AuthService {
+  logout() {
+    window.location.href = '/login';
+    location.assign(`https://sso.example.com/${tenant}`);
+    this.location.replace('/home');
+    document.location.replace(url);
+  }
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let service = parser.extract_service(&module, Path::new("auth.service.ts")).unwrap().unwrap();
+        let navigations: Vec<_> = service.location_navigations.iter()
+            .map(|n| format!("{}:{}", n.method, n.target.as_deref().unwrap_or("?")))
+            .collect();
+
+        assert_eq!(navigations, vec!["href:/login", "assign:https://sso.example.com/", "replace:?"]);
+    }
+
+    #[test]
+    fn test_selection_chains() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Component({ selector: 'app-host', template: '<ng-container *ngComponentOutlet="view" />' })
+export class HostComponent {
+  pick(widget: Widget) {
+    switch (widget.type) {
+      case 'chart': return ChartWidgetComponent;
+      case 'table': return TableWidgetComponent;
+      case 'text': return TextWidgetComponent;
+      default: return null;
+    }
+  }
+
+  resolve(item: Item) {
+    if (item.kind === 'video') {
+      this.view = VideoComponent;
+    } else if (item.kind === 'image') {
+      this.view = ImageComponent;
+    } else if (this.loading) {
+      this.view = SpinnerComponent;
+    }
+    if (mode === 'a') {
+      this.view = AComponent;
+    } else if (mode === 'b') {
+      this.view = BComponent;
+    }
+  }
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let chains = parser.extract_selection_chains(&module, content, Path::new("host.component.ts"));
+        let summary: Vec<_> = chains.iter()
+            .map(|chain| (chain.kind.as_str(), chain.discriminator.as_str(), chain.branches, chain.line))
+            .collect();
+
+        assert_eq!(summary, vec![
+            ("switch", "widget.type", 3, Some(5)),
+            ("if-else", "mode", 2, Some(21)),
+        ]);
+    }
+
+    #[test]
+    fn test_change_detection_calls() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Injectable({ providedIn: 'root' })
+export class TickerService {
+  private cdr = inject(ChangeDetectorRef);
+
+  constructor(private appRef: ApplicationRef, private zone: NgZone) {
+    setInterval(() => this.appRef.tick(), 100);
+    interval(50).pipe(tap(() => this.cdr.markForCheck())).subscribe();
+    for (const item of this.items) {
+      this.cdr.detectChanges();
+    }
+    this.cdr.markForCheck();
+    this.other.tick();
+  }
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let service = parser.extract_service(&module, Path::new("ticker.service.ts")).unwrap().unwrap();
+        let calls: Vec<_> = service.change_detection_calls.iter()
+            .map(|call| (call.method.as_str(), call.in_loop))
+            .collect();
+
+        assert_eq!(calls, vec![
+            ("tick", true),
+            ("markForCheck", true),
+            ("detectChanges", true),
+            ("markForCheck", false),
+        ]);
+    }
+
+    #[test]
+    fn test_zone_usage() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Component({ selector: 'app-clock', template: '{{ now }}' })
+export class ClockComponent {
+  now = new Date();
+  ticks = 0;
+  users: User[] = [];
+  count = signal(0);
+
+  constructor(private api: ApiService) {
+    setInterval(() => { this.now = new Date(); this.ticks++; }, 1000);
+    this.api.users().subscribe(users => this.users.push(...users));
+    fetch('/ping').then(() => this.count.set(1));
+    interval(500).pipe(take(3)).subscribe(() => { this.ticks = 0; });
+    this.ticks = 1;
+  }
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let component = parser.extract_component(&module, Path::new("clock.component.ts")).unwrap().unwrap();
+
+        assert_eq!(component.zone.timer_updates, vec!["now", "ticks"]);
+        assert_eq!(component.zone.async_updates, vec!["users"]);
+    }
+
+    #[test]
+    fn test_route_params() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Component({ selector: 'app-order', template: '' })
+export class OrderComponent {
+  private route = inject(ActivatedRoute);
+  tab = this.route.snapshot.params['tab'];
+
+  ngOnInit() {
+    this.id = this.route.snapshot.paramMap.get('id');
+    const { page } = this.route.snapshot.queryParams;
+  }
+
+  reload() {
+    this.other = this.route.snapshot.params.other;
+  }
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let component = parser.extract_component(&module, Path::new("order.component.ts")).unwrap().unwrap();
+        assert_eq!(component.route_params.snapshot_reads, vec!["tab", "id"]);
+        assert!(!component.route_params.observed);
+
+        let content = content.replace("reload() {", "reload() {\n    this.route.paramMap.subscribe(() => {});");
+        let module = parser.parse_file(&content).unwrap();
+        let component = parser.extract_component(&module, Path::new("order.component.ts")).unwrap().unwrap();
+        assert!(component.route_params.observed);
+    }
+
+    #[test]
+    fn test_host_usage() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Component({ selector: 'app-sticky', template: '' })
+export class StickyComponent {
+  @HostBinding('class.sticky') stuck = false;
+  @HostBinding() role = 'banner';
+
+  constructor(private el: ElementRef) {
+    window.addEventListener('resize', this.onResize.bind(this));
+    document.addEventListener('keydown', (e) => this.key = e.key, { signal: this.abort.signal });
+    fromEvent(window, 'resize').pipe(debounceTime(100)).subscribe();
+  }
+
+  @HostListener('window:scroll')
+  onScroll() {
+    this.el.nativeElement.style.top = '0';
+    this.el.nativeElement.classList.add('fixed');
+  }
+
+  @HostListener('click', ['$event'])
+  onClick(event) {
+    this.el.nativeElement.focus();
+  }
+
+  onResize() {
+    requestAnimationFrame(() => this.measure());
+  }
+
+  ngOnDestroy() {
+    window.removeEventListener('resize', this.onResize);
+  }
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let component = parser.extract_component(&module, Path::new("sticky.component.ts")).unwrap().unwrap();
+        let host = &component.host;
+
+        let listeners: Vec<(&str, &str, &str, bool, bool)> = host.listeners.iter()
+            .map(|l| (l.target.as_str(), l.event.as_str(), l.source.as_str(), l.removed, l.throttled))
+            .collect();
+        assert_eq!(listeners, vec![
+            ("window", "scroll", "HostListener", true, false),
+            ("host", "click", "HostListener", true, false),
+            ("window", "resize", "addEventListener", true, true),
+            ("document", "keydown", "addEventListener", true, false),
+            ("window", "resize", "fromEvent", true, true),
+        ]);
+        assert_eq!(host.bindings, vec!["class.sticky", "role"]);
+        assert_eq!(host.native_element_writes, vec!["style.top", "classList.add"]);
+    }
+
+    #[test]
+    fn test_extract_spec() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+describe('OrderListComponent', () => {
+  beforeEach(() => TestBed.configureTestingModule({}));
+  it('creates', () => expect(true).toBe(true));
+  xit('sorts', () => {});
+  it.skip('filters', () => {});
+  xdescribe('paging', () => {
+    it('pages', () => {});
+  });
+});
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let spec = parser.extract_spec(&module, Path::new("order-list.component.spec.ts"));
+
+        assert_eq!(spec.tests, 1);
+        assert_eq!(spec.disabled, 3);
+    }
+
+    #[test]
+    fn test_extract_module_and_lazy_routes() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+const routes: Routes = [
+  { path: 'admin', loadChildren: () => import('../admin/admin.module').then(m => m.AdminModule) },
+  { path: 'legacy', loadChildren: './legacy/legacy.module#LegacyModule' },
+];
+
+@NgModule({
+  declarations: [OrdersComponent],
+  imports: [CommonModule, RouterModule.forChild(routes), StoreModule.forRoot({})],
+  providers: [OrdersService, { provide: API_URL, useValue: '/api' }],
+})
+export class OrdersModule {}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let ng_module = parser.extract_module(&module, content, Path::new("src/app/orders/orders.module.ts")).unwrap();
+
+        assert_eq!(ng_module.name, "OrdersModule");
+        assert_eq!(ng_module.imports, vec!["CommonModule", "RouterModule", "StoreModule"]);
+        assert_eq!(ng_module.providers, vec!["OrdersService", "API_URL"]);
+        assert_eq!(ng_module.for_root_imports.len(), 1);
+        assert_eq!(ng_module.for_root_imports[0].module, "StoreModule");
+        assert_eq!(ng_module.for_root_imports[0].line, Some(9));
+
+        let routes = parser.extract_lazy_routes(&module, content, Path::new("src/app/orders/orders.module.ts"));
+
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].module.as_deref(), Some("AdminModule"));
+        assert_eq!(routes[0].target, "src/app/admin/admin.module");
+        assert_eq!(routes[0].line, Some(3));
+        assert_eq!(routes[1].module.as_deref(), Some("LegacyModule"));
+        assert_eq!(routes[1].target, "src/app/orders/legacy/legacy.module");
+    }
+
+    #[test]
+    fn test_extract_render_roots() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+const routes: Routes = [
+  { path: '', component: HomeComponent },
+  { path: 'orders', component: OrdersComponent, children: [{ path: ':id', component: OrderDetailComponent }] },
+  { path: 'admin', loadComponent: () => import('./admin.component').then(m => m.AdminComponent) },
+  { path: 'users/:userId/edit', loadComponent: () => import('./user-edit.component').then(m => m.UserEditComponent) },
+];
+const options = { component: NotARouteComponent };
+
+bootstrapApplication(AppComponent, { providers: [provideRouter(routes)] });
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let (bootstrap, routed, parameterized) = parser.extract_render_roots(&module);
+
+        assert_eq!(bootstrap, vec!["AppComponent"]);
+        assert_eq!(routed, vec!["HomeComponent", "OrdersComponent", "OrderDetailComponent"]);
+        let parameterized: Vec<(&str, &str)> = parameterized.iter().map(|route| (route.component.as_str(), route.path.as_str())).collect();
+        assert_eq!(parameterized, vec![("OrderDetailComponent", ":id"), ("UserEditComponent", "users/:userId/edit")]);
+    }
+
+    #[test]
+    fn test_http_usage() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Injectable({ providedIn: 'root' })
+export class UserService {
+  private api = inject(HttpClient);
+
+  load(id: string) {
+    return this.api.get(`${environment.apiUrl}/users/${id}`).pipe(map(u => u), catchError(() => of(null)));
+  }
+
+  save(user: User) {
+    return this.api.post('https://api.example.com/users', user);
+  }
+
+  remove(id: string) {
+    this.api.delete(environment.apiUrl + '/users/' + id).subscribe({ next: () => {}, error: () => {} });
+  }
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let service = parser.extract_service(&module, Path::new("user.service.ts")).unwrap().unwrap();
+
+        assert!(service.http.injected);
+        let requests: Vec<_> = service.http.requests.iter()
+            .map(|r| (r.method.as_str(), r.url.as_deref().unwrap_or_default(), r.error_handled))
+            .collect();
+        assert_eq!(requests, vec![
+            ("get", "${environment.apiUrl}/users/${id}", true),
+            ("post", "https://api.example.com/users", false),
+            ("delete", "${environment.apiUrl}/users/${id}", true),
+        ]);
+    }
+
+    #[test]
+    fn test_adoption_signals() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Component({ selector: 'app-profile', standalone: true, template: '' })
+export class ProfileComponent {
+  @Input() legacy: string;
+  name = input<string>();
+  id = input.required<number>();
+  private fb = inject(FormBuilder);
+
+  constructor(private legacyFb: UntypedFormBuilder) {}
+
+  build() {
+    this.form = this.fb.group({ name: [''] });
+    this.old = this.legacyFb.group({});
+    this.control = new FormControl('');
+    this.loose = new FormGroup<any>({});
+    this.legacyControl = new UntypedFormControl();
+  }
+
+  ngOnInit() {}
+  get label() { return this.name(); }
+  protected reset() {}
+  static create() {}
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let component = parser.extract_component(&module, Path::new("profile.component.ts")).unwrap().unwrap();
+
+        assert!(component.standalone);
+        assert_eq!(component.inputs.iter().filter(|input| input.signal).count(), 2);
+        assert_eq!(component.forms.typed, 2);
+        assert_eq!(component.forms.untyped, 3);
+        assert_eq!(component.public_methods, vec!["build"]);
+    }
+
+    #[test]
+    fn test_typed_inputs_and_outputs() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Component({ selector: 'app-card', template: '' })
+export class CardComponent {
+  @Input() title: string;
+  @Input('cardSize') size = 10;
+  @Input({ alias: 'items' }) rows?: Array<Row | null>;
+  @Input() data;
+  label = input('', { alias: 'cardLabel' });
+  id = input.required<number>({ alias: 'cardId' });
+  @Output() selected = new EventEmitter<Row>();
+  @Output() closed = new EventEmitter();
+  @Output('changed') change: EventEmitter<string> = new EventEmitter();
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let component = parser.extract_component(&module, Path::new("card.component.ts")).unwrap().unwrap();
+
+        let inputs: Vec<(&str, Option<&str>, &str, bool)> = component.inputs.iter()
+            .map(|input| (input.name.as_str(), input.alias.as_deref(), input.input_type.as_str(), input.optional))
+            .collect();
+        assert_eq!(inputs, vec![
+            ("title", None, "string", false),
+            ("size", Some("cardSize"), "number", false),
+            ("rows", Some("items"), "Array<Row | null>", true),
+            ("data", None, "any", false),
+            ("label", Some("cardLabel"), "string", false),
+            ("id", Some("cardId"), "number", false),
+        ]);
+        let outputs: Vec<&str> = component.outputs.iter().map(|output| output.output_type.as_str()).collect();
+        assert_eq!(outputs, vec!["EventEmitter<Row>", "EventEmitter", "EventEmitter<string>"]);
+    }
+
+    #[test]
+    fn test_extract_mutable_exports() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+export let currentUser = null;
+export var counter = 0;
+export const cache = {};
+export const handlers = [] as Handler[];
+export const registry = new Map<string, string>();
+export const ROUTES = ['a', 'b'] as const;
+export const CONFIG = Object.freeze({ retries: 3 });
+export const DEFAULTS: Readonly<Options> = { retries: 3 };
+export const API_URL = '/api';
+const local = {};
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let exports = parser.extract_mutable_exports(&module, content, Path::new("src/app/state.ts"));
+        let found: Vec<(&str, &str, Option<u32>)> = exports.iter().map(|e| (e.name.as_str(), e.kind.as_str(), e.line)).collect();
+        assert_eq!(found, vec![
+            ("currentUser", "let", Some(2)),
+            ("counter", "var", Some(3)),
+            ("cache", "object", Some(4)),
+            ("handlers", "array", Some(5)),
+            ("registry", "Map", Some(6)),
+        ]);
+    }
+
+    #[test]
+    fn test_extract_declared_symbols() {
+        let parser = TypeScriptParser::new();
+        let a = r#"
+// shared helpers
+export function formatDate(value: Date): string {
+  return value.toISOString(); // ISO
+}
+export const toSlug = (text: string) => text.toLowerCase();
+export interface User { id: string }
+export const API_URL = '/api';
+"#;
+        let b = "export function formatDate(value: Date): string { return value.toISOString(); }\nexport function other() { return 'a  b'; }\n";
+        let c = "export function other() { return 'a b'; }\n";
+        let symbols = |content: &str| parser.extract_declared_symbols(&parser.parse_file(content).unwrap(), content, Path::new("src/a.ts"));
+
+        let found: Vec<(String, String, Option<u32>, u32)> = symbols(a).into_iter().map(|s| (s.name, s.kind, s.line, s.lines)).collect();
+        assert_eq!(found, vec![
+            ("formatDate".to_string(), "function".to_string(), Some(3), 3),
+            ("toSlug".to_string(), "function".to_string(), Some(6), 1),
+            ("User".to_string(), "interface".to_string(), Some(7), 1),
+        ]);
+        // 書式とコメントの違いは無視し、文字列の中の空白は区別する
+        assert_eq!(symbols(a)[0].body_hash, symbols(b)[0].body_hash);
+        assert_ne!(symbols(b)[1].body_hash, symbols(c)[0].body_hash);
+    }
+
+    #[test]
+    fn test_extract_classes() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+@Directive()
+export abstract class BaseList<T> implements OnInit, OnDestroy {
+  ngOnInit() {}
+  ngOnDestroy() {}
+}
+
+@Component({ selector: 'app-orders', template: '' })
+export class OrdersComponent extends WithLoading(WithDestroy(BaseList<Order>)) implements OnInit {
+  ngOnInit() {
+    super.ngOnInit();
+  }
+  ngOnDestroy() {
+    this.save();
+  }
+}
+"#;
+        let classes = parser.extract_classes(&parser.parse_file(content).unwrap(), content, Path::new("src/orders.component.ts"));
+
+        assert_eq!(classes.len(), 2);
+        assert_eq!((classes[0].name.as_str(), classes[0].line, classes[0].is_abstract, classes[0].decorator.as_deref()), ("BaseList", 3, true, Some("Directive")));
+        assert_eq!(classes[0].heritage, ClassHeritage {
+            implements: vec!["OnInit".to_string(), "OnDestroy".to_string()],
+            ..Default::default()
+        });
+        assert_eq!(classes[1].lifecycle_hooks, vec!["ngOnInit", "ngOnDestroy"]);
+        assert_eq!(classes[1].heritage, ClassHeritage {
+            base_class: Some("BaseList".to_string()),
+            mixins: vec!["WithLoading".to_string(), "WithDestroy".to_string()],
+            implements: vec!["OnInit".to_string()],
+            super_calls: vec!["ngOnInit".to_string()],
+        });
+    }
+
+    #[test]
+    fn test_extract_dynamic_imports() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+export const routes: Routes = [
+  { path: 'admin', loadChildren: () => import('./admin/admin.routes').then(m => m.ADMIN_ROUTES) },
+];
+
+export async function loadChart() {
+  const { Chart } = await import(`./chart`);
+  const name = 'x';
+  await import(`./locales/${name}`);
+  return Chart;
+}
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let imports = parser.extract_dynamic_imports(&module, content, Path::new("src/app/app.routes.ts"));
+        let found: Vec<(&str, Option<u32>)> = imports.iter().map(|i| (i.source_module.as_str(), i.line_number)).collect();
+        assert_eq!(found, vec![("./admin/admin.routes", Some(3)), ("./chart", Some(7))]);
+        assert!(imports.iter().all(|i| matches!(i.import_type, ImportType::Dynamic)));
+    }
+
+    #[test]
+    fn test_extract_provider_overrides() {
+        let parser = TypeScriptParser::new();
+        let content = r#"
+export const appConfig: ApplicationConfig = {
+  providers: [
+    { provide: ErrorHandler, useClass: SentryErrorHandler },
+    { provide: HTTP_INTERCEPTORS, useClass: AuthInterceptor, multi: true },
+    { provide: API_URL, useValue: '/api' },
+  ],
+};
+
+export const routes: Routes = [
+  { path: 'admin', providers: [{ provide: RouteReuseStrategy, useClass: AdminReuseStrategy }] },
+];
+
+@Component({ selector: 'app-shell', template: '', providers: [{ provide: APP_BASE_HREF, useValue: '/shop' }] })
+export class ShellComponent {}
+
+export const ordersConfig = { providers: [OrdersService, provideHttpClient(withInterceptors([authInterceptor]))] };
+"#;
+        let module = parser.parse_file(content).unwrap();
+        let overrides = parser.extract_provider_overrides(&module, content, Path::new("src/app/app.config.ts"));
+        let found: Vec<String> = overrides.iter()
+            .map(|o| format!("{} {} {} {}", o.token, o.scope, o.injector, o.implementation.as_deref().unwrap_or("?")))
+            .collect();
+        assert_eq!(found, vec![
+            "ErrorHandler application appConfig SentryErrorHandler",
+            "HTTP_INTERCEPTORS application appConfig AuthInterceptor",
+            "RouteReuseStrategy route route 'admin' AdminReuseStrategy",
+            "APP_BASE_HREF component ShellComponent '/shop'",
+        ]);
+        assert_eq!(overrides.iter().map(|o| o.multi).collect::<Vec<_>>(), vec![false, true, false, false]);
+        assert_eq!(overrides.iter().map(|o| o.line).collect::<Vec<_>>(), vec![Some(4), Some(5), Some(11), Some(14)]);
+
+        let registrations = parser.extract_provider_registrations(&module, content, Path::new("src/app/app.config.ts"));
+        let found: Vec<String> = registrations.iter()
+            .map(|o| format!("{} {} {}", o.token, o.injector, o.implementation.as_deref().unwrap_or("-")))
+            .collect();
+        assert_eq!(found, vec![
+            "ErrorHandler appConfig SentryErrorHandler",
+            "HTTP_INTERCEPTORS appConfig AuthInterceptor",
+            "API_URL appConfig '/api'",
+            "RouteReuseStrategy route 'admin' AdminReuseStrategy",
+            "APP_BASE_HREF ShellComponent '/shop'",
+            "OrdersService ordersConfig -",
+            "HTTP_INTERCEPTORS ordersConfig authInterceptor",
+        ]);
+        assert_eq!(registrations[6].line, Some(17));
+    }
+}
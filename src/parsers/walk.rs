@@ -0,0 +1,142 @@
+use crate::config::{build_glob_set, relative_issue_path, Config};
+use anyhow::Result;
+use ignore::WalkBuilder;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// ファイルの列挙方法。.gitignore と .ngignore はどちらも常に従う
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    pub follow_symlinks: bool,
+    // 設定ファイルの ignore。解析するディレクトリからの相対パスに照合する
+    pub ignore: Vec<String>,
+}
+
+impl WalkOptions {
+    pub fn new(follow_symlinks: bool, config: Option<&Config>) -> Self {
+        Self {
+            follow_symlinks,
+            ignore: config.map(|config| config.ignore.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+// 解析対象のファイルを列挙する。project / graph / fix / search で共通
+// node_modules は pnpm のストアやワークスペースのパッケージへのシンボリックリンクを含むので辿らない
+pub fn walk_files(root_path: &Path, options: &WalkOptions) -> Result<Vec<PathBuf>> {
+    let follow_symlinks = options.follow_symlinks;
+    let ignore = build_glob_set(&options.ignore)?;
+    let walker = WalkBuilder::new(root_path)
+        .add_custom_ignore_filename(".ngignore")
+        .hidden(false)
+        .git_ignore(true)
+        // git リポジトリの外（展開したアーカイブなど）でも .gitignore に従う
+        .require_git(false)
+        .follow_links(follow_symlinks)
+        .filter_entry(|entry| entry.depth() == 0 || entry.file_name() != "node_modules")
+        // 出力の順序がファイルシステムに左右されないよう名前順に辿る
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+
+    // リンク経由と実体の両方から同じファイルに届く場合は、先に見つかった方だけ残す
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(error) if is_symlink_error(&error) => continue,
+            Err(error) => return Err(error.into()),
+        };
+        let path = entry.path();
+        if !follow_symlinks && entry.path_is_symlink() {
+            continue;
+        }
+        if !path.is_file() || ignore.is_match(relative_issue_path(&path.display().to_string(), root_path)) {
+            continue;
+        }
+        if follow_symlinks && !seen.insert(fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())) {
+            continue;
+        }
+        files.push(path.to_path_buf());
+    }
+
+    Ok(files)
+}
+
+// 循環しているリンクとリンク切れは読み飛ばす
+fn is_symlink_error(error: &ignore::Error) -> bool {
+    match error {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithPath { err, .. } | ignore::Error::WithLineNumber { err, .. } => is_symlink_error(err),
+        ignore::Error::Io(io_error) => io_error.kind() == std::io::ErrorKind::NotFound,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_files_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("apps/web/src")).unwrap();
+        fs::create_dir_all(root.join("libs/ui/src")).unwrap();
+        fs::create_dir_all(root.join("node_modules/.pnpm/rxjs@7.8.1/node_modules/rxjs")).unwrap();
+        fs::write(root.join("apps/web/src/main.ts"), "").unwrap();
+        fs::write(root.join("libs/ui/src/button.ts"), "").unwrap();
+        fs::write(root.join("node_modules/.pnpm/rxjs@7.8.1/node_modules/rxjs/index.js"), "").unwrap();
+        // pnpm のワークスペースのリンク、ライブラリへのリンク、親ディレクトリへの循環するリンク、リンク切れ
+        symlink(root.join("libs/ui"), root.join("node_modules/ui")).unwrap();
+        symlink(root.join("libs/ui/src"), root.join("apps/web/src/ui")).unwrap();
+        symlink(root.join("apps"), root.join("apps/web/src/loop")).unwrap();
+        symlink(root.join("missing.ts"), root.join("apps/web/src/broken.ts")).unwrap();
+
+        let relative = |files: Vec<PathBuf>| {
+            let mut files: Vec<String> = files.iter()
+                .map(|file| file.strip_prefix(root).unwrap().display().to_string())
+                .collect();
+            files.sort();
+            files
+        };
+
+        let files = relative(walk_files(root, &WalkOptions::default()).unwrap());
+        assert_eq!(files, vec!["apps/web/src/main.ts", "libs/ui/src/button.ts"]);
+
+        let follow = WalkOptions { follow_symlinks: true, ..Default::default() };
+        let files = walk_files(root, &follow).unwrap();
+        assert_eq!(files.len(), 2);
+
+        // ワークスペースの一部だけを解析するときはリンク先のライブラリも読む
+        let files = relative(walk_files(&root.join("apps"), &follow).unwrap());
+        assert_eq!(files, vec!["apps/web/src/main.ts", "apps/web/src/ui/button.ts"]);
+    }
+
+    #[test]
+    fn test_walk_files_ignores() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("src/app")).unwrap();
+        fs::create_dir_all(root.join("dist")).unwrap();
+        for file in ["src/app/app.ts", "src/app/app.spec.ts", "src/app/generated.ts", "src/app/legacy.ts", "dist/main.js"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        fs::write(root.join(".gitignore"), "dist/\n").unwrap();
+        fs::write(root.join(".ngignore"), "generated.ts\n").unwrap();
+
+        let options = WalkOptions {
+            follow_symlinks: false,
+            ignore: vec!["**/*.spec.ts".to_string(), "src/app/legacy.ts".to_string()],
+        };
+        let files: Vec<String> = walk_files(root, &options).unwrap().iter()
+            .map(|file| file.strip_prefix(root).unwrap().display().to_string())
+            .filter(|file| !file.starts_with('.'))
+            .collect();
+        assert_eq!(files, vec!["src/app/app.ts"]);
+    }
+}
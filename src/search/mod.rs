@@ -1,9 +1,20 @@
+use crate::output::i18n::Lang;
+use crate::parsers::project::{is_minified, ProjectInputs, ProjectParser};
+use crate::parsers::source_cache::SourceCache;
+use crate::parsers::typescript::TypeScriptParser;
+use crate::parsers::walk::{walk_files, WalkOptions};
 use anyhow::Result;
-use std::path::PathBuf;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use scope::{SearchScope, SemanticFilter};
+use structural::StructuralPattern;
 use serde::{Serialize, Deserialize};
 
+pub mod scope;
 pub mod simple;
-pub use simple::SimpleSearchEngine;
+pub mod structural;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchConfig {
@@ -18,9 +29,24 @@ pub struct SearchConfig {
     pub output_format: String,
     #[allow(dead_code)]
     pub verbose: bool,
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    // 設定ファイルの ignore
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    // 構造検索のパターン。指定すると keyword の代わりに TypeScript の構文木で照合する
+    #[serde(default)]
+    pub structural: Option<String>,
+    // --in・--selector・--decorator。指定するとプロジェクトを解析し、該当する成果物の中だけを検索する
+    #[serde(default)]
+    pub filter: SemanticFilter,
+    // エラーメッセージの言語
+    #[serde(skip)]
+    pub lang: Lang,
 }
 
 impl SearchConfig {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         path: PathBuf,
         keyword: String,
@@ -42,6 +68,11 @@ impl SearchConfig {
             context,
             output_format,
             verbose,
+            follow_symlinks: false,
+            ignore: Vec::new(),
+            structural: None,
+            filter: SemanticFilter::default(),
+            lang: Lang::En,
         }
     }
 }
@@ -53,7 +84,6 @@ pub enum SearchType {
     HtmlClass(String),
     HtmlText(String),
     FunctionName(String),
-    #[allow(dead_code)]
     Structural(String), // パターン文字列
 }
 
@@ -63,6 +93,9 @@ pub struct SearchResult {
     pub total_matches: usize,
     pub matches: Vec<SearchMatch>,
     pub search_type: SearchType,
+    // minify 済みのファイルは前後の行を付けない
+    #[serde(default)]
+    pub minified: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,8 +107,12 @@ pub struct SearchMatch {
     pub context_before: Vec<String>,
     pub context_after: Vec<String>,
     pub match_type: String,
+    // 構造検索でメタ変数（$X など）に一致したソース
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub bindings: BTreeMap<String, String>,
 }
 
+#[allow(dead_code)]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SearchSummary {
     pub total_files_searched: usize,
@@ -84,66 +121,94 @@ pub struct SearchSummary {
     pub search_config: SearchConfig,
 }
 
-#[allow(dead_code)]
 pub struct SearchEngine {
     config: SearchConfig,
+    sources: Arc<SourceCache>,
 }
 
 impl SearchEngine {
-    #[allow(dead_code)]
     pub fn new(config: SearchConfig) -> Self {
-        Self { config }
+        Self { config, sources: Arc::new(SourceCache::new()) }
     }
 
-    #[allow(dead_code)]
-    pub async fn search(&self) -> Result<Vec<SearchResult>> {
-        let files = self.collect_files().await?;
-        let mut results = Vec::new();
-
-        for file_path in files {
-            if let Ok(content) = tokio::fs::read_to_string(&file_path).await {
-                let matches = self.search_in_content(&content)?;
-                if !matches.is_empty() {
-                    results.push(SearchResult {
-                        file_path,
-                        total_matches: matches.len(),
-                        matches,
-                        search_type: SearchType::Simple,
-                    });
+    // マッチしたファイルの結果と、検索したファイルの数。ファイルは rayon で並列に検索し、結果は列挙した順に並べる
+    pub async fn search(&self) -> Result<(Vec<SearchResult>, usize)> {
+        let structural = self.config.structural.as_deref().map(|pattern| StructuralPattern::parse(pattern, self.config.lang)).transpose()?;
+        let search_type = match &self.config.structural {
+            Some(pattern) => SearchType::Structural(pattern.clone()),
+            None => SearchType::Simple,
+        };
+        let scope = self.build_scope().await?;
+        let files = match &scope {
+            Some(scope) => scope.files().into_iter().filter(|path| self.has_requested_type(path)).collect(),
+            None => self.collect_files().await?,
+        };
+        self.sources.preload(&files).await;
+
+        let results = files.par_iter()
+            .map(|file_path| {
+                let Ok(source) = self.sources.read(file_path) else { return Ok(None) };
+                let minified = is_minified(&source.content);
+                let matches = match &structural {
+                    Some(pattern) => self.search_structural(pattern, file_path, &source.content, minified)?,
+                    None => self.search_in_content(&source.content, minified)?,
+                };
+                let matches: Vec<SearchMatch> = match &scope {
+                    Some(scope) => matches.into_iter().filter(|found| scope.contains(file_path, found.line_number)).collect(),
+                    None => matches,
+                };
+                if matches.is_empty() {
+                    return Ok(None);
                 }
-            }
-        }
-
-        Ok(results)
+                Ok(Some(SearchResult {
+                    file_path: file_path.clone(),
+                    total_matches: matches.len(),
+                    matches,
+                    search_type: search_type.clone(),
+                    minified,
+                }))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((results.into_iter().flatten().collect(), files.len()))
     }
 
-    #[allow(dead_code)]
     async fn collect_files(&self) -> Result<Vec<PathBuf>> {
-        let mut files = Vec::new();
-        let mut dir = tokio::fs::read_dir(&self.config.path).await?;
-
-        while let Some(entry) = dir.next_entry().await? {
-            let path = entry.path();
-            if path.is_file() && self.should_include_file(&path) {
-                files.push(path);
-            } else if path.is_dir() {
-                // 再帰的にディレクトリを探索
-                let mut subdir = tokio::fs::read_dir(&path).await?;
-                while let Some(subentry) = subdir.next_entry().await? {
-                    let subpath = subentry.path();
-                    if subpath.is_file() && self.should_include_file(&subpath) {
-                        files.push(subpath);
-                    }
-                }
-            }
+        let walk = WalkOptions { follow_symlinks: self.config.follow_symlinks, ignore: self.config.ignore.clone() };
+        let files = walk_files(&self.config.path, &walk)?;
+        Ok(files.into_iter().filter(|path| self.should_include_file(path)).collect())
+    }
+
+    // 絞り込みがあれば、テンプレートとスタイルも含めて解析したプロジェクトから検索する範囲を求める
+    async fn build_scope(&self) -> Result<Option<SearchScope>> {
+        if self.config.filter.is_empty() {
+            return Ok(None);
         }
+        let inputs = ProjectInputs {
+            templates: true,
+            resource_files: false,
+            stylesheets: self.config.filter.needs_styles(),
+            import_graph: false,
+        };
+        let project = ProjectParser::new()
+            .with_inputs(inputs)
+            .with_walk_options(WalkOptions { follow_symlinks: self.config.follow_symlinks, ignore: self.config.ignore.clone() })
+            .with_source_cache(self.sources.clone())
+            .parse_project(&self.config.path)
+            .await?;
+        Ok(Some(SearchScope::build(&project, &self.config.filter, &self.sources)?))
+    }
 
-        Ok(files)
+    // 絞り込みの結果は .html や .scss も含むので、--file-type を明示したときだけ拡張子で絞る
+    fn has_requested_type(&self, path: &Path) -> bool {
+        match self.config.file_type.as_deref().filter(|file_type| *file_type != "all") {
+            Some(file_type) => path.extension().and_then(|ext| ext.to_str()) == Some(file_type),
+            None => true,
+        }
     }
 
-    #[allow(dead_code)]
     fn should_include_file(&self, path: &std::path::Path) -> bool {
-        if let Some(file_type) = &self.config.file_type {
+        if let Some(file_type) = self.config.file_type.as_deref().filter(|file_type| *file_type != "all") {
             if let Some(extension) = path.extension() {
                 return extension.to_str() == Some(file_type);
             }
@@ -158,15 +223,41 @@ impl SearchEngine {
         }
     }
 
-    #[allow(dead_code)]
-    fn search_in_content(&self, content: &str) -> Result<Vec<SearchMatch>> {
-        match &self.config.keyword {
-            _ => self.search_simple(content),
+    // TypeScript として解析できないファイルと .ts / .js 以外は飛ばす。複数行にわたる一致は最初の行で示す
+    fn search_structural(&self, pattern: &StructuralPattern, file_path: &Path, content: &str, minified: bool) -> Result<Vec<SearchMatch>> {
+        if !matches!(file_path.extension().and_then(|ext| ext.to_str()), Some("ts" | "tsx" | "js" | "jsx")) {
+            return Ok(Vec::new());
         }
+        let Ok((_, module)) = self.sources.module(file_path, |content| TypeScriptParser::new().parse_file(content)) else {
+            return Ok(Vec::new());
+        };
+        let lines: Vec<&str> = content.lines().collect();
+
+        Ok(pattern.find(&module, content)?.into_iter()
+            .map(|found| {
+                let line_start = content[..found.start].rfind('\n').map_or(0, |newline| newline + 1);
+                let line_end = content[found.start..].find('\n').map_or(content.len(), |newline| found.start + newline);
+                let line_number = content[..found.start].matches('\n').count();
+                let with_context = self.config.context > 0 && !minified;
+                SearchMatch {
+                    line_number: line_number + 1,
+                    line_content: content[line_start..line_end].trim_end_matches('\r').to_string(),
+                    match_start: found.start - line_start,
+                    match_end: found.end.min(line_end) - line_start,
+                    context_before: if with_context { self.get_context_lines(&lines, line_number, true) } else { Vec::new() },
+                    context_after: if with_context { self.get_context_lines(&lines, line_number, false) } else { Vec::new() },
+                    match_type: "structural".to_string(),
+                    bindings: found.bindings,
+                }
+            })
+            .collect())
     }
 
-    #[allow(dead_code)]
-    fn search_simple(&self, content: &str) -> Result<Vec<SearchMatch>> {
+    fn search_in_content(&self, content: &str, minified: bool) -> Result<Vec<SearchMatch>> {
+        self.search_simple(content, minified)
+    }
+
+    fn search_simple(&self, content: &str, minified: bool) -> Result<Vec<SearchMatch>> {
         let mut matches = Vec::new();
         let lines: Vec<&str> = content.lines().collect();
 
@@ -183,14 +274,20 @@ impl SearchEngine {
                 self.config.keyword.to_lowercase()
             };
 
-            if let Some(start) = search_line.find(&search_keyword) {
-                let context_before = if self.config.context > 0 {
+            // 空のキーワードはすべての位置にマッチしてしまうので探さない
+            if search_keyword.is_empty() {
+                break;
+            }
+
+            // 1 行に複数あればすべて記録する
+            for (start, _) in search_line.match_indices(&search_keyword) {
+                let context_before = if self.config.context > 0 && !minified {
                     self.get_context_lines(&lines, line_number, true)
                 } else {
                     Vec::new()
                 };
 
-                let context_after = if self.config.context > 0 {
+                let context_after = if self.config.context > 0 && !minified {
                     self.get_context_lines(&lines, line_number, false)
                 } else {
                     Vec::new()
@@ -204,6 +301,7 @@ impl SearchEngine {
                     context_before,
                     context_after,
                     match_type: "simple".to_string(),
+                    bindings: BTreeMap::new(),
                 });
             }
         }
@@ -239,6 +337,7 @@ impl SearchEngine {
                     context_before,
                     context_after,
                     match_type: "regex".to_string(),
+                    bindings: BTreeMap::new(),
                 });
             }
         }
@@ -277,6 +376,7 @@ impl SearchEngine {
                             context_before,
                             context_after,
                             match_type: "html_class".to_string(),
+                            bindings: BTreeMap::new(),
                         });
                     }
                 }
@@ -317,6 +417,7 @@ impl SearchEngine {
                             context_before,
                             context_after,
                             match_type: "html_text".to_string(),
+                            bindings: BTreeMap::new(),
                         });
                     }
                 }
@@ -356,6 +457,7 @@ impl SearchEngine {
                             context_before,
                             context_after,
                             match_type: "function_name".to_string(),
+                            bindings: BTreeMap::new(),
                         });
                     }
                 }
@@ -388,6 +490,7 @@ impl SearchEngine {
                             context_before,
                             context_after,
                             match_type: "function_name".to_string(),
+                            bindings: BTreeMap::new(),
                         });
                     }
                 }
@@ -397,42 +500,6 @@ impl SearchEngine {
         Ok(matches)
     }
 
-    #[allow(dead_code)]
-    fn search_structural(&self, content: &str, pattern: &str) -> Result<Vec<SearchMatch>> {
-        let mut matches = Vec::new();
-        let lines: Vec<&str> = content.lines().collect();
-        let regex = regex::Regex::new(pattern)?;
-
-        for (line_number, line) in lines.iter().enumerate() {
-            for mat in regex.find_iter(line) {
-                let context_before = if self.config.context > 0 {
-                    self.get_context_lines(&lines, line_number, true)
-                } else {
-                    Vec::new()
-                };
-
-                let context_after = if self.config.context > 0 {
-                    self.get_context_lines(&lines, line_number, false)
-                } else {
-                    Vec::new()
-                };
-
-                matches.push(SearchMatch {
-                    line_number: line_number + 1,
-                    line_content: line.to_string(),
-                    match_start: mat.start(),
-                    match_end: mat.end(),
-                    context_before,
-                    context_after,
-                    match_type: "structural".to_string(),
-                });
-            }
-        }
-
-        Ok(matches)
-    }
-
-    #[allow(dead_code)]
     fn get_context_lines(&self, lines: &[&str], current_line: usize, before: bool) -> Vec<String> {
         let mut context = Vec::new();
         let context_size = self.config.context as usize;
@@ -455,4 +522,39 @@ impl SearchEngine {
 
         context
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_search_walks_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/app/features/orders/list")).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/rxjs")).unwrap();
+        std::fs::write(root.join("src/app/app.component.ts"), "// TODO: routes\n").unwrap();
+        std::fs::write(root.join("src/app/features/orders/list/list.component.html"), "<p>ok</p>\n<!-- TODO -->\n").unwrap();
+        std::fs::write(root.join("src/app/ignored.ts"), "// TODO\n").unwrap();
+        std::fs::write(root.join("node_modules/rxjs/index.js"), "// TODO\n").unwrap();
+        std::fs::write(root.join(".gitignore"), "ignored.ts\n").unwrap();
+
+        let config = SearchConfig::new(root.to_path_buf(), "todo".to_string(), Some("all".to_string()), None, false, true, 0, "simple".to_string(), false);
+        let (results, files_searched) = SearchEngine::new(config).search().await.unwrap();
+        let files: Vec<String> = results.iter()
+            .map(|result| result.file_path.strip_prefix(root).unwrap().display().to_string())
+            .collect();
+        assert_eq!(files, vec!["src/app/app.component.ts", "src/app/features/orders/list/list.component.html"]);
+        assert_eq!(files_searched, 2);
+        assert_eq!(results[1].matches[0].line_number, 2);
+    }
+
+    #[test]
+    fn test_search_simple_every_occurrence() {
+        let config = SearchConfig::new(PathBuf::new(), "Store".to_string(), None, None, false, true, 0, "simple".to_string(), false);
+        let matches = SearchEngine::new(config).search_simple("store = inject(CounterStore);\nno match\n", false).unwrap();
+        let ranges: Vec<(usize, usize, usize)> = matches.iter().map(|m| (m.line_number, m.match_start, m.match_end)).collect();
+        assert_eq!(ranges, vec![(1, 0, 5), (1, 22, 27)]);
+    }
+}
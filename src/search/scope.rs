@@ -0,0 +1,297 @@
+use crate::ast::{NgComponent, NgProject};
+use crate::output::i18n::Lang;
+use crate::parsers::source_cache::SourceCache;
+use crate::parsers::typescript::{resolve_relative_path, TypeScriptParser};
+use anyhow::Result;
+use globset::{Glob, GlobMatcher};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+// 解析したプロジェクトのうち、検索の対象にする Angular の成果物
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtifactKind {
+    Components,
+    Templates,
+    Styles,
+    Services,
+    Modules,
+    Guards,
+    Specs,
+}
+
+impl ArtifactKind {
+    pub const NAMES: &'static str = "components, templates, styles, services, modules, guards, specs";
+
+    pub fn parse(name: &str, lang: Lang) -> Result<Self> {
+        match name {
+            "components" => Ok(Self::Components),
+            "templates" => Ok(Self::Templates),
+            "styles" => Ok(Self::Styles),
+            "services" => Ok(Self::Services),
+            "modules" => Ok(Self::Modules),
+            "guards" => Ok(Self::Guards),
+            "specs" => Ok(Self::Specs),
+            _ => Err(anyhow::anyhow!("{}", lang.trf("error.unsupported", &[&"--in", &name, &Self::NAMES]))),
+        }
+    }
+}
+
+// --in・--selector・--decorator による絞り込み
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SemanticFilter {
+    pub kinds: Vec<ArtifactKind>,
+    // コンポーネントのセレクタ（glob 可）。kinds を省くとクラスとテンプレートを検索する
+    pub selector: Option<String>,
+    // このデコレーター（@Input など）が付いたクラスとメンバーの中だけを検索する
+    pub decorator: Option<String>,
+}
+
+impl SemanticFilter {
+    pub fn is_empty(&self) -> bool {
+        self.kinds.is_empty() && self.selector.is_none() && self.decorator.is_none()
+    }
+
+    pub fn needs_styles(&self) -> bool {
+        self.kinds.contains(&ArtifactKind::Styles)
+    }
+}
+
+// 検索するファイルと、その中で検索する行の範囲（1 始まりで両端を含む）。範囲がなければファイル全体
+#[derive(Debug, Default)]
+pub struct SearchScope {
+    files: BTreeMap<PathBuf, Option<Vec<(usize, usize)>>>,
+}
+
+impl SearchScope {
+    // インラインのテンプレート・スタイルとデコレーターの位置は、search と共有するキャッシュから読む
+    pub fn build(project: &NgProject, filter: &SemanticFilter, sources: &SourceCache) -> Result<Self> {
+        let mut scope = Self::default();
+        let selector = filter.selector.as_deref().map(|selector| Glob::new(selector).map(|glob| glob.compile_matcher())).transpose()?;
+        let kinds = match (filter.kinds.is_empty(), &selector) {
+            (false, _) => filter.kinds.clone(),
+            (true, Some(_)) => vec![ArtifactKind::Components, ArtifactKind::Templates],
+            // --decorator だけなら TypeScript のファイルすべて
+            (true, None) => {
+                for file in &project.files {
+                    scope.add(PathBuf::from(&file.file_path), None);
+                }
+                Vec::new()
+            }
+        };
+
+        let components = project.components.iter().filter(|component| selector_matches(component, selector.as_ref()));
+        for component in components {
+            let file_path = Path::new(&component.file_path);
+            if kinds.contains(&ArtifactKind::Components) {
+                scope.add(file_path.to_path_buf(), None);
+            }
+            if kinds.contains(&ArtifactKind::Templates) {
+                match (&component.template, &component.template_url) {
+                    (Some(template), _) => scope.add_inline(file_path, template, sources),
+                    (None, Some(url)) => scope.add(PathBuf::from(resolve_relative_path(file_path, url)), None),
+                    (None, None) => {}
+                }
+            }
+            if kinds.contains(&ArtifactKind::Styles) {
+                for url in &component.style_urls {
+                    scope.add(PathBuf::from(resolve_relative_path(file_path, url)), None);
+                }
+                for style in &component.inline_styles {
+                    scope.add_inline(file_path, style, sources);
+                }
+            }
+        }
+        // セレクタはコンポーネントにだけ照合する
+        if selector.is_none() {
+            let files = kinds.iter().flat_map(|kind| -> Vec<&String> {
+                match kind {
+                    ArtifactKind::Services => project.services.iter().map(|service| &service.file_path).collect(),
+                    ArtifactKind::Modules => project.modules.iter().map(|module| &module.file_path).collect(),
+                    ArtifactKind::Guards => project.guards.iter().map(|guard| &guard.file_path).collect(),
+                    ArtifactKind::Specs => project.specs.iter().map(|spec| &spec.file_path).collect(),
+                    _ => Vec::new(),
+                }
+            });
+            for file in files.collect::<Vec<_>>() {
+                scope.add(PathBuf::from(file), None);
+            }
+        }
+
+        if let Some(decorator) = &filter.decorator {
+            scope.restrict_to_decorator(decorator, sources)?;
+        }
+        Ok(scope)
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.files.keys().cloned().collect()
+    }
+
+    pub fn contains(&self, file_path: &Path, line: usize) -> bool {
+        match self.files.get(file_path) {
+            Some(Some(ranges)) => ranges.iter().any(|&(start, end)| (start..=end).contains(&line)),
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    // ファイル全体が入っていれば範囲は足さない
+    fn add(&mut self, file_path: PathBuf, range: Option<(usize, usize)>) {
+        match (self.files.get_mut(&file_path), range) {
+            (Some(Some(ranges)), Some(range)) => ranges.push(range),
+            (Some(entry), None) => *entry = None,
+            (Some(None), Some(_)) => {}
+            (None, range) => {
+                self.files.insert(file_path, range.map(|range| vec![range]));
+            }
+        }
+    }
+
+    // .ts に書かれたテンプレートやスタイルの行
+    fn add_inline(&mut self, file_path: &Path, text: &str, sources: &SourceCache) {
+        let Ok(source) = sources.read(file_path) else { return };
+        let Some(start) = source.content.find(text) else { return };
+        let content = &source.content;
+        let first = content[..start].matches('\n').count() + 1;
+        self.add(file_path.to_path_buf(), Some((first, first + text.matches('\n').count())));
+    }
+
+    // .ts 以外のファイルは外し、デコレーターの付いた範囲と重なる行だけを残す
+    fn restrict_to_decorator(&mut self, decorator: &str, sources: &SourceCache) -> Result<()> {
+        let parser = TypeScriptParser::new();
+        let mut restricted = BTreeMap::new();
+        for (file_path, ranges) in &self.files {
+            if file_path.extension().and_then(|ext| ext.to_str()) != Some("ts") {
+                continue;
+            }
+            let Ok((source, module)) = sources.module(file_path, |content| parser.parse_file(content)) else { continue };
+            let content = &source.content;
+            let decorated: Vec<(usize, usize)> = decorated_spans(&serde_json::to_value(&*module)?, decorator).into_iter()
+                .map(|(start, end)| (line_of(content, start), line_of(content, end.saturating_sub(1))))
+                .filter(|&(start, end)| ranges.as_ref().is_none_or(|ranges| ranges.iter().any(|range| start <= range.1 && range.0 <= end)))
+                .collect();
+            if !decorated.is_empty() {
+                restricted.insert(file_path.clone(), Some(decorated));
+            }
+        }
+        self.files = restricted;
+        Ok(())
+    }
+}
+
+fn selector_matches(component: &NgComponent, selector: Option<&GlobMatcher>) -> bool {
+    let Some(selector) = selector else { return true };
+    component.selector.as_deref().is_some_and(|selectors| selectors.split(',').any(|part| selector.is_match(part.trim())))
+}
+
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset.min(content.len())].matches('\n').count() + 1
+}
+
+// @name または @name(...) が付いたノード（クラス・プロパティ・メソッド・引数）のバイト範囲。デコレーターも含める
+fn decorated_spans(node: &Value, name: &str) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    match node {
+        Value::Object(object) => {
+            let decorators: Vec<&Value> = object.get("decorators").and_then(Value::as_array).into_iter().flatten()
+                .filter(|decorator| decorator_name(decorator) == Some(name))
+                .collect();
+            if let Some((start, end)) = span(node).filter(|_| !decorators.is_empty()) {
+                let start = decorators.iter().filter_map(|decorator| span(decorator)).map(|(start, _)| start).fold(start, usize::min);
+                spans.push((start, end));
+            }
+            for value in object.values() {
+                spans.extend(decorated_spans(value, name));
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                spans.extend(decorated_spans(item, name));
+            }
+        }
+        _ => {}
+    }
+    spans
+}
+
+fn decorator_name(decorator: &Value) -> Option<&str> {
+    let expression = decorator.get("expression")?;
+    let identifier = match expression.get("type")?.as_str()? {
+        "CallExpression" => expression.get("callee")?,
+        _ => expression,
+    };
+    identifier.get("value")?.as_str()
+}
+
+fn span(node: &Value) -> Option<(usize, usize)> {
+    let span = node.get("span")?;
+    Some((span.get("start")?.as_u64()? as usize, span.get("end")?.as_u64()? as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::ProjectParser;
+
+    #[tokio::test]
+    async fn test_search_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("app/card")).unwrap();
+        std::fs::write(root.join("app/card/card.component.ts"), r#"import { Component, Input } from '@angular/core';
+
+@Component({
+  selector: 'app-card',
+  templateUrl: './card.component.html',
+})
+export class CardComponent {
+  @Input() title = '';
+  subtitle = '';
+}
+"#).unwrap();
+        std::fs::write(root.join("app/card/card.component.html"), "<h2>{{ title }}</h2>\n").unwrap();
+        std::fs::write(root.join("app/badge.component.ts"), r#"import { Component } from '@angular/core';
+
+@Component({
+  selector: 'app-badge',
+  template: '<span>{{ title }}</span>',
+})
+export class BadgeComponent {
+  title = '';
+}
+"#).unwrap();
+        std::fs::write(root.join("app/title.service.ts"), "import { Injectable } from '@angular/core';\n\n@Injectable()\nexport class TitleService {}\n").unwrap();
+
+        let project = ProjectParser::new().parse_project(&root.to_path_buf()).await.unwrap();
+        let sources = &project.sources;
+        let relative = |scope: &SearchScope| -> Vec<String> {
+            scope.files().iter().map(|file| file.strip_prefix(root).unwrap().display().to_string()).collect()
+        };
+
+        let filter = SemanticFilter { selector: Some("app-card".to_string()), ..Default::default() };
+        let scope = SearchScope::build(&project, &filter, sources).unwrap();
+        assert_eq!(relative(&scope), vec!["app/card/card.component.html", "app/card/card.component.ts"]);
+
+        // インラインのテンプレートはその行だけ
+        let filter = SemanticFilter { kinds: vec![ArtifactKind::Templates], selector: Some("app-b*".to_string()), ..Default::default() };
+        let scope = SearchScope::build(&project, &filter, sources).unwrap();
+        let badge = root.join("app/badge.component.ts");
+        assert!(scope.contains(&badge, 5));
+        assert!(!scope.contains(&badge, 8));
+
+        let filter = SemanticFilter { kinds: vec![ArtifactKind::Services], ..Default::default() };
+        assert_eq!(relative(&SearchScope::build(&project, &filter, sources).unwrap()), vec!["app/title.service.ts"]);
+
+        let filter = SemanticFilter { decorator: Some("Input".to_string()), ..Default::default() };
+        let scope = SearchScope::build(&project, &filter, sources).unwrap();
+        let card = root.join("app/card/card.component.ts");
+        assert_eq!(relative(&scope), vec!["app/card/card.component.ts"]);
+        assert!(scope.contains(&card, 8));
+        assert!(!scope.contains(&card, 9));
+
+        assert!(ArtifactKind::parse("widgets", Lang::En).is_err());
+    }
+}
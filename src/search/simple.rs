@@ -1,68 +1,68 @@
 use crate::search::SearchMatch;
-use anyhow::Result;
 use serde::{Serialize, Deserialize};
-use std::path::PathBuf;
 
-pub struct SimpleSearchEngine {
-    pub keyword: String,
-    pub case_sensitive: bool,
-    pub context: u32,
-    #[allow(dead_code)]
-    pub line_numbers: bool,
+const HIGHLIGHT: &str = "\x1b[1;31m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Serialize, Deserialize)]
+pub struct SearchResult {
+    pub file_path: String,
+    pub matches: Vec<SearchMatch>,
 }
 
-impl SimpleSearchEngine {
-    pub fn new(keyword: String, case_sensitive: bool, line_numbers: bool, context: u32) -> Self {
-        Self {
-            keyword,
-            case_sensitive,
-            context,
-            line_numbers,
-        }
+impl SearchResult {
+    pub fn total_matches(&self) -> usize {
+        self.matches.len()
     }
 
-    pub fn search(&self, content: &str) -> Result<Vec<SearchMatch>> {
-        let mut matches = Vec::new();
-        let lines: Vec<&str> = content.lines().collect();
-
-        for (line_number, line) in lines.iter().enumerate() {
-            let search_line = if self.case_sensitive {
-                line.to_string()
-            } else {
-                line.to_lowercase()
-            };
-
-            let search_keyword = if self.case_sensitive {
-                self.keyword.clone()
-            } else {
-                self.keyword.to_lowercase()
-            };
-
-            if let Some(start) = search_line.find(&search_keyword) {
-                matches.push(SearchMatch {
-                    line_number: line_number + 1,
-                    line_content: line.to_string(),
-                    match_start: start,
-                    match_end: start + search_keyword.len(),
-                    context_before: Vec::new(),
-                    context_after: Vec::new(),
-                    match_type: "simple".to_string(),
-                });
+    // 同じ行の複数のマッチを 1 行にまとめ、最初のマッチとマッチした範囲の一覧にする
+    pub fn matched_lines(&self) -> Vec<(&SearchMatch, Vec<(usize, usize)>)> {
+        let mut lines: Vec<(&SearchMatch, Vec<(usize, usize)>)> = Vec::new();
+        for search_match in &self.matches {
+            let range = (search_match.match_start, search_match.match_end);
+            match lines.last_mut() {
+                Some((first, ranges)) if first.line_number == search_match.line_number => ranges.push(range),
+                _ => lines.push((search_match, vec![range])),
             }
         }
-
-        Ok(matches)
+        lines
     }
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct SearchResult {
-    pub file_path: String,
-    pub matches: Vec<SearchMatch>,
+// マッチした範囲を ANSI の色で強調する。文字の境界にない範囲（大文字小文字の変換で長さが変わった行など）は飛ばす
+pub fn highlight(line: &str, ranges: &[(usize, usize)], color: bool) -> String {
+    if !color {
+        return line.to_string();
+    }
+    let mut output = String::with_capacity(line.len());
+    let mut position = 0;
+    for &(start, end) in ranges {
+        if start < position || end > line.len() || !line.is_char_boundary(start) || !line.is_char_boundary(end) {
+            continue;
+        }
+        output.push_str(&line[position..start]);
+        output.push_str(HIGHLIGHT);
+        output.push_str(&line[start..end]);
+        output.push_str(RESET);
+        position = end;
+    }
+    output.push_str(&line[position..]);
+    output
 }
 
-impl SearchResult {
-    pub fn total_matches(&self) -> usize {
-        self.matches.len()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_highlight() {
+        let line = "store = inject(CounterStore);";
+        assert_eq!(highlight(line, &[(0, 5), (22, 27)], false), line);
+        assert_eq!(
+            highlight(line, &[(0, 5), (22, 27)], true),
+            "\x1b[1;31mstore\x1b[0m = inject(Counter\x1b[1;31mStore\x1b[0m);"
+        );
+        // 行の外を指す範囲は無視する
+        assert_eq!(highlight("ab", &[(1, 9)], true), "ab");
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,276 @@
+use crate::output::i18n::Lang;
+use crate::parsers::typescript::TypeScriptParser;
+use anyhow::Result;
+use regex::Regex;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use swc_ecma_ast::{Decl, Expr, Module, ModuleItem, Stmt};
+
+// メタ変数を、パターンを TypeScript として解析できる識別子に置き換える
+const METAVARIABLE: &str = "__ng_mv_";
+const ELLIPSIS: &str = "__ng_ellipsis_";
+// 位置と、一致しても区別しないフィールド（'a' と "a" の違いなど）
+const IGNORED_FIELDS: [&str; 3] = ["span", "ctxt", "raw"];
+
+// ast-grep と同じ書き方の構造検索のパターン。$X は任意の 1 つのノード（同じ名前は同じ内容）、
+// $$$ と $$$ARGS は引数や文の 0 個以上の並びに一致する
+#[derive(Debug, Clone)]
+pub struct StructuralPattern {
+    tree: Value,
+}
+
+// パターンに一致したノードのバイト範囲と、メタ変数に一致したソース
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructuralMatch {
+    pub start: usize,
+    pub end: usize,
+    pub bindings: BTreeMap<String, String>,
+}
+
+impl StructuralPattern {
+    // 式、クラスのメンバー（@Input() $NAME: any など）、文、デコレーターの順に解析を試す
+    pub fn parse(pattern: &str, lang: Lang) -> Result<Self> {
+        let metavariable = Regex::new(r"\$\$\$([A-Z_][A-Z0-9_]*)?|\$([A-Z_][A-Z0-9_]*)").unwrap();
+        let mut anonymous = 0;
+        let source = metavariable.replace_all(pattern, |caps: &regex::Captures| {
+            match (caps.get(1), caps.get(2)) {
+                (_, Some(name)) => format!("{}{}", METAVARIABLE, name.as_str()),
+                (Some(name), None) => format!("{}{}", ELLIPSIS, name.as_str()),
+                (None, None) => {
+                    anonymous += 1;
+                    format!("{}{}", ELLIPSIS, anonymous)
+                }
+            }
+        });
+
+        let parser = TypeScriptParser::new();
+        let expression = parser.parse_file(&format!("({});", source)).ok()
+            .and_then(|module| single_expression(&module).map(serde_json::to_value));
+        let member = || parser.parse_file(&format!("class __NgPattern {{ {} }}", source)).ok()
+            .and_then(|module| match module.body.first() {
+                Some(ModuleItem::Stmt(Stmt::Decl(Decl::Class(class)))) if class.class.body.len() == 1 => Some(serde_json::to_value(&class.class.body[0])),
+                _ => None,
+            });
+        let statement = || parser.parse_file(&source).ok()
+            .filter(|module| module.body.len() == 1)
+            .map(|module| serde_json::to_value(&module.body[0]));
+        // @Component($$$) のようなデコレーターだけのパターン
+        let decorator = || parser.parse_file(&format!("{} class __NgPattern {{}}", source)).ok()
+            .and_then(|module| match module.body.first() {
+                Some(ModuleItem::Stmt(Stmt::Decl(Decl::Class(class)))) if class.class.decorators.len() == 1 => Some(serde_json::to_value(&class.class.decorators[0])),
+                _ => None,
+            });
+        // 括弧で囲むとクラスと関数の宣言も式として解析されるので、宣言として解析できればそちらを使う
+        let declaration = expression.as_ref()
+            .and_then(|expr| expr.as_ref().ok())
+            .is_some_and(|expr| matches!(expr.get("type").and_then(Value::as_str), Some("ClassExpression" | "FunctionExpression")));
+        let expression = if declaration { statement().or(expression) } else { expression };
+        let tree = expression.or_else(member).or_else(statement).or_else(decorator)
+            .ok_or_else(|| anyhow::anyhow!("{}", lang.trf("error.structural_pattern", &[&pattern])))??;
+
+        Ok(Self { tree: strip_ignored(tree) })
+    }
+
+    // 外側から順に、一致したノードをすべて返す（一致したノードの内側も探す）
+    pub fn find(&self, module: &Module, content: &str) -> Result<Vec<StructuralMatch>> {
+        let mut matches = Vec::new();
+        self.find_in(&serde_json::to_value(module)?, content, &mut matches);
+        Ok(matches)
+    }
+
+    fn find_in(&self, node: &Value, content: &str, matches: &mut Vec<StructuralMatch>) {
+        match node {
+            Value::Object(object) => {
+                if let Some((start, end)) = span(node, content) {
+                    let mut bindings = BTreeMap::new();
+                    if match_node(&self.tree, node, content, &mut bindings) {
+                        matches.push(StructuralMatch { start, end, bindings });
+                    }
+                }
+                for (key, value) in object {
+                    if !IGNORED_FIELDS.contains(&key.as_str()) {
+                        self.find_in(value, content, matches);
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.find_in(item, content, matches);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// (式); の形の文から括弧の中の式を取り出す
+fn single_expression(module: &Module) -> Option<&Expr> {
+    match module.body.as_slice() {
+        [ModuleItem::Stmt(Stmt::Expr(stmt))] => match &*stmt.expr {
+            Expr::Paren(paren) => Some(&paren.expr),
+            expr => Some(expr),
+        },
+        _ => None,
+    }
+}
+
+fn strip_ignored(value: Value) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(object.into_iter()
+            .filter(|(key, _)| !IGNORED_FIELDS.contains(&key.as_str()))
+            .map(|(key, value)| (key, strip_ignored(value)))
+            .collect()),
+        Value::Array(items) => Value::Array(items.into_iter().map(strip_ignored).collect()),
+        value => value,
+    }
+}
+
+// ノードのバイト範囲。swc の位置は 0 から数えたバイト位置
+fn span(node: &Value, content: &str) -> Option<(usize, usize)> {
+    let span = node.get("span")?;
+    let start = span.get("start")?.as_u64()? as usize;
+    let end = span.get("end")?.as_u64()? as usize;
+    (start < end && end <= content.len() && content.is_char_boundary(start) && content.is_char_boundary(end)).then_some((start, end))
+}
+
+// パターン中の識別子がメタ変数なら、その名前を返す
+fn identifier_with_prefix<'a>(node: &'a Value, prefix: &str) -> Option<&'a str> {
+    if node.get("type")?.as_str()? != "Identifier" {
+        return None;
+    }
+    node.get("value")?.as_str()?.strip_prefix(prefix)
+}
+
+fn metavariable(node: &Value) -> Option<&str> {
+    if let Some(name) = identifier_with_prefix(node, METAVARIABLE) {
+        return Some(name);
+    }
+    // 型の位置の $T は型の参照として解析されるので、任意の型に一致させる
+    if node.get("type")?.as_str()? == "TsTypeReference" && node.get("typeParams").is_none_or(Value::is_null) {
+        return identifier_with_prefix(node.get("typeName")?, METAVARIABLE);
+    }
+    None
+}
+
+// $$$ そのものか、$$$ だけを包むノード（引数の ExprOrSpread、式文など）
+fn is_ellipsis(node: &Value) -> bool {
+    if identifier_with_prefix(node, ELLIPSIS).is_some() {
+        return true;
+    }
+    let Value::Object(object) = node else { return false };
+    let mut children = object.iter().filter(|(key, value)| *key != "type" && !is_empty(value));
+    matches!((children.next(), children.next()), (Some((_, child)), None) if is_ellipsis(child))
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null | Value::Bool(false) => true,
+        Value::Array(items) => items.is_empty(),
+        _ => false,
+    }
+}
+
+fn match_node(pattern: &Value, node: &Value, content: &str, bindings: &mut BTreeMap<String, String>) -> bool {
+    if let Some(name) = metavariable(pattern) {
+        let Some((start, end)) = span(node, content) else { return false };
+        let type_name = node.get("type").and_then(Value::as_str).unwrap_or_default();
+        if pattern.get("type").and_then(Value::as_str) == Some("TsTypeReference") && !type_name.starts_with("Ts") {
+            return false;
+        }
+        let text = &content[start..end];
+        return match bindings.get(name) {
+            Some(bound) => bound == text,
+            None => {
+                bindings.insert(name.to_string(), text.to_string());
+                true
+            }
+        };
+    }
+
+    match (pattern, node) {
+        (Value::Object(pattern), Value::Object(node)) => match_object(pattern, node, content, bindings),
+        (Value::Array(pattern), Value::Array(node)) => match_sequence(pattern, node, content, bindings),
+        (pattern, node) => pattern == node,
+    }
+}
+
+// パターンにデコレーターを書かなければ、デコレーターの有無を問わない
+fn match_object(pattern: &Map<String, Value>, node: &Map<String, Value>, content: &str, bindings: &mut BTreeMap<String, String>) -> bool {
+    pattern.iter().all(|(key, value)| match node.get(key) {
+        _ if key == "decorators" && is_empty(value) => true,
+        Some(child) => match_node(value, child, content, bindings),
+        None => is_empty(value),
+    })
+}
+
+// $$$ は 0 個以上の要素に一致する。一致しなければメタ変数の割り当ては元に戻す
+fn match_sequence(pattern: &[Value], node: &[Value], content: &str, bindings: &mut BTreeMap<String, String>) -> bool {
+    let Some((first, rest)) = pattern.split_first() else { return node.is_empty() };
+    if is_ellipsis(first) {
+        return (0..=node.len()).any(|skip| {
+            let mut attempt = bindings.clone();
+            let matched = match_sequence(rest, &node[skip..], content, &mut attempt);
+            if matched {
+                *bindings = attempt;
+            }
+            matched
+        });
+    }
+    let Some((head, tail)) = node.split_first() else { return false };
+    let mut attempt = bindings.clone();
+    if match_node(first, head, content, &mut attempt) && match_sequence(rest, tail, content, &mut attempt) {
+        *bindings = attempt;
+        return true;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find(pattern: &str, content: &str) -> Vec<(String, BTreeMap<String, String>)> {
+        let module = TypeScriptParser::new().parse_file(content).unwrap();
+        StructuralPattern::parse(pattern, Lang::En).unwrap().find(&module, content).unwrap().into_iter()
+            .map(|found| (content[found.start..found.end].to_string(), found.bindings))
+            .collect()
+    }
+
+    #[test]
+    fn test_structural_search() {
+        let content = r#"
+export class OrdersComponent {
+  @Input() order: any;
+  @Input() title: string;
+  @Output() changed = new EventEmitter();
+
+  ngOnInit() {
+    this.orders$.subscribe(orders => this.render(orders));
+    this.route.params.subscribe();
+    orders.subscribe(() => {});
+    this.log('a', "a");
+  }
+}
+"#;
+        let found = find("this.$X.subscribe($$$)", content);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, "this.orders$.subscribe(orders => this.render(orders))");
+        assert_eq!(found[0].1["X"], "orders$");
+
+        let found = find("@Input() $NAME: any", content);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1["NAME"], "order");
+
+        // 文字列の引用符は区別せず、同じ名前のメタ変数は同じソースにだけ一致する
+        assert_eq!(find("this.log('a', 'a')", content).len(), 1);
+        assert_eq!(find("this.log($A, $A)", content).len(), 0);
+        assert_eq!(find("this.log($A, $B)", content).len(), 1);
+        assert_eq!(find("$OBJ.subscribe($$$ARGS)", content).len(), 3);
+        assert_eq!(find("@Input() $NAME: $TYPE", content).len(), 2);
+        assert_eq!(find("@Output()", content).len(), 1);
+        let found = find("export class $C { $$$ }", content);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].1["C"], "OrdersComponent");
+        assert!(StructuralPattern::parse("class {", Lang::En).is_err());
+    }
+}
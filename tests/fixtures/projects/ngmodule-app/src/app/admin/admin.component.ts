@@ -0,0 +1,8 @@
+import { ChangeDetectionStrategy, Component } from '@angular/core';
+
+@Component({
+  selector: 'app-admin',
+  template: '<p>admin</p>',
+  changeDetection: ChangeDetectionStrategy.OnPush
+})
+export class AdminComponent {}
@@ -0,0 +1,9 @@
+import { NgModule } from '@angular/core';
+import { CommonModule } from '@angular/common';
+import { AdminComponent } from './admin.component';
+
+@NgModule({
+  declarations: [AdminComponent],
+  imports: [CommonModule]
+})
+export class AdminModule {}
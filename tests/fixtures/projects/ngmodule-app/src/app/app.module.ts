@@ -0,0 +1,13 @@
+import { NgModule } from '@angular/core';
+import { BrowserModule } from '@angular/platform-browser';
+import { HttpClientModule } from '@angular/common/http';
+import { AppRoutingModule } from './app-routing.module';
+import { AppComponent } from './app.component';
+import { UserListComponent } from './users/user-list.component';
+
+@NgModule({
+  declarations: [AppComponent, UserListComponent],
+  imports: [BrowserModule, HttpClientModule, AppRoutingModule],
+  bootstrap: [AppComponent]
+})
+export class AppModule {}
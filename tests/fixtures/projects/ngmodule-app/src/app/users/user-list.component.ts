@@ -0,0 +1,23 @@
+import { Component, Input, OnInit } from '@angular/core';
+import { UserService, User } from './user.service';
+
+@Component({
+  selector: 'app-user-list',
+  template: `
+    <ul>
+      <li *ngFor="let user of users">{{ user.name }}</li>
+    </ul>
+  `
+})
+export class UserListComponent implements OnInit {
+  @Input() title = '';
+  users: User[] = [];
+
+  constructor(private userService: UserService) {}
+
+  ngOnInit() {
+    this.userService.getUsers().subscribe(users => {
+      this.users = users;
+    });
+  }
+}
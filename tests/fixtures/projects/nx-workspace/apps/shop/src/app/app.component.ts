@@ -0,0 +1,13 @@
+import { Component } from '@angular/core';
+import { ButtonComponent } from '@acme/ui';
+import { CartService } from '@acme/data';
+
+@Component({
+  selector: 'shop-root',
+  standalone: true,
+  imports: [ButtonComponent],
+  template: '<acme-button (pressed)="cart.add(1)"></acme-button>'
+})
+export class AppComponent {
+  constructor(public cart: CartService) {}
+}
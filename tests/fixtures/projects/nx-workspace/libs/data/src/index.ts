@@ -0,0 +1 @@
+export { CartService } from './lib/cart.service';
@@ -0,0 +1,11 @@
+import { Injectable } from '@angular/core';
+import { BehaviorSubject } from 'rxjs';
+
+@Injectable({ providedIn: 'root' })
+export class CartService {
+  private readonly items$ = new BehaviorSubject<number[]>([]);
+
+  add(id: number) {
+    this.items$.next([...this.items$.value, id]);
+  }
+}
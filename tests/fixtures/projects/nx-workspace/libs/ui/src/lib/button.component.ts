@@ -0,0 +1,11 @@
+import { ChangeDetectionStrategy, Component, EventEmitter, Output } from '@angular/core';
+
+@Component({
+  selector: 'acme-button',
+  standalone: true,
+  template: '<button (click)="pressed.emit()"><ng-content /></button>',
+  changeDetection: ChangeDetectionStrategy.OnPush
+})
+export class ButtonComponent {
+  @Output() pressed = new EventEmitter<void>();
+}
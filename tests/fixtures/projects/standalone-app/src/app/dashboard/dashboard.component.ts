@@ -0,0 +1,18 @@
+import { ChangeDetectionStrategy, Component, inject, input } from '@angular/core';
+import { CounterStore } from '../store/counter.store';
+
+@Component({
+  selector: 'app-dashboard',
+  standalone: true,
+  template: `
+    @for (item of items(); track item) {
+      <p>{{ item }}</p>
+    }
+    <button (click)="store.increment()">{{ store.count() }}</button>
+  `,
+  changeDetection: ChangeDetectionStrategy.OnPush
+})
+export class DashboardComponent {
+  items = input<string[]>([]);
+  store = inject(CounterStore);
+}
@@ -0,0 +1,11 @@
+import { Injectable, computed, signal } from '@angular/core';
+
+@Injectable({ providedIn: 'root' })
+export class CounterStore {
+  private readonly state = signal(0);
+  readonly count = computed(() => this.state());
+
+  increment() {
+    this.state.update(value => value + 1);
+  }
+}
@@ -0,0 +1,24 @@
+use std::path::Path;
+use std::process::Command;
+
+// 標準出力を jq にそのまま渡せるよう、ndjson では全行が JSON であること
+#[test]
+fn test_ndjson_stdout_lines_are_json() {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/projects/ngmodule-app");
+    let output = Command::new(env!("CARGO_BIN_EXE_ng-analyzer"))
+        .args(["audit", "--full", "--formats", "ndjson"])
+        .arg(&fixture)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.lines().count() > 0);
+    for line in stdout.lines() {
+        let issue: serde_json::Value = serde_json::from_str(line).unwrap_or_else(|error| panic!("{}: {}", error, line));
+        assert!(issue["rule"].is_string(), "{}", line);
+    }
+    // サマリー行は標準エラーに出る
+    assert!(String::from_utf8_lossy(&output.stderr).contains(" in "));
+}